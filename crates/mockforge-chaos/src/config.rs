@@ -133,6 +133,11 @@ pub struct FaultInjectionConfig {
     /// at server startup (see `mockforge_chaos::chaos_listener`).
     #[serde(default)]
     pub connection_error_kind: ConnectionErrorKind,
+    /// Seconds to advertise in a `Retry-After` header on injected 503s
+    /// (HTTP-layer connection errors and 503s drawn from `http_errors`).
+    /// `None` sends no header.
+    #[serde(default)]
+    pub retry_after_secs: Option<u64>,
     /// Inject timeout errors
     pub timeout_errors: bool,
     /// Timeout duration in milliseconds
@@ -172,6 +177,7 @@ impl Default for FaultInjectionConfig {
             connection_errors: false,
             connection_error_probability: 0.05,
             connection_error_kind: ConnectionErrorKind::default(),
+            retry_after_secs: None,
             timeout_errors: false,
             timeout_ms: 5000,
             timeout_probability: 0.05,
@@ -260,6 +266,13 @@ pub struct CircuitBreakerConfig {
     pub min_requests_for_rate: u64,
     /// Rolling window duration for failure rate calculation (in milliseconds)
     pub rolling_window_ms: u64,
+    /// Seconds advertised in the `Retry-After` header while the circuit is open
+    #[serde(default = "default_circuit_retry_after_secs")]
+    pub retry_after_secs: u64,
+}
+
+fn default_circuit_retry_after_secs() -> u64 {
+    1
 }
 
 impl Default for CircuitBreakerConfig {
@@ -273,6 +286,7 @@ impl Default for CircuitBreakerConfig {
             failure_rate_threshold: 50.0,
             min_requests_for_rate: 10,
             rolling_window_ms: 10000,
+            retry_after_secs: 1,
         }
     }
 }
@@ -404,6 +418,7 @@ impl NetworkProfile {
                         connection_errors: true,
                         connection_error_probability: 0.03, // 3% chance of disconnects
                         connection_error_kind: ConnectionErrorKind::Http503,
+                        retry_after_secs: None,
                         timeout_errors: false,
                         timeout_ms: 5000,
                         timeout_probability: 0.0,
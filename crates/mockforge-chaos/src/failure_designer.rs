@@ -204,6 +204,7 @@ impl FailureDesigner {
                     connection_errors: false,
                     connection_error_probability: 0.0,
                     connection_error_kind: crate::config::ConnectionErrorKind::Http503,
+                    retry_after_secs: None,
                     timeout_errors: false,
                     timeout_ms: 5000,
                     timeout_probability: 0.0,
@@ -254,6 +255,7 @@ impl FailureDesigner {
                     connection_errors: false,
                     connection_error_probability: 0.0,
                     connection_error_kind: crate::config::ConnectionErrorKind::Http503,
+                    retry_after_secs: None,
                     timeout_errors: true,
                     timeout_ms: *timeout_ms,
                     timeout_probability: rule.probability,
@@ -285,6 +287,7 @@ impl FailureDesigner {
                     connection_errors: true,
                     connection_error_probability: rule.probability,
                     connection_error_kind: crate::config::ConnectionErrorKind::Http503,
+                    retry_after_secs: None,
                     timeout_errors: false,
                     timeout_ms: 5000,
                     timeout_probability: 0.0,
@@ -318,6 +321,7 @@ impl FailureDesigner {
                     connection_errors: false,
                     connection_error_probability: 0.0,
                     connection_error_kind: crate::config::ConnectionErrorKind::Http503,
+                    retry_after_secs: None,
                     timeout_errors: false,
                     timeout_ms: 5000,
                     timeout_probability: 0.0,
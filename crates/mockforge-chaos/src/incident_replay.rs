@@ -283,6 +283,7 @@ impl IncidentReplayGenerator {
                 connection_errors: false,
                 connection_error_probability: 0.0,
                 connection_error_kind: crate::config::ConnectionErrorKind::Http503,
+                retry_after_secs: None,
                 timeout_errors: inject_timeouts,
                 timeout_ms: 5000,
                 timeout_probability: if inject_timeouts { error_rate } else { 0.0 },
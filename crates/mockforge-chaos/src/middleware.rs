@@ -243,11 +243,15 @@ async fn chaos_middleware_core(
         let circuit_breaker = chaos.circuit_breaker.read().await;
         if !circuit_breaker.allow_request().await {
             warn!("Circuit breaker open, rejecting request: {}", path);
-            return (
+            let response = (
                 StatusCode::SERVICE_UNAVAILABLE,
                 "Service temporarily unavailable (circuit breaker open)",
             )
                 .into_response();
+            return with_retry_after(
+                response,
+                Some(circuit_breaker.config().await.retry_after_secs),
+            );
         }
     }
 
@@ -404,16 +408,18 @@ async fn chaos_middleware_core(
     } else {
         None
     };
+    let retry_after_secs = fault_config.and_then(|f| f.retry_after_secs);
     drop(config);
 
     if let Some(status_code) = http_error_status {
         warn!("Injecting HTTP error: {}", status_code);
         crate::metrics::CHAOS_METRICS.record_fault("http_error", &path);
-        return (
-            StatusCode::from_u16(status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
-            format!("Injected error: {}", status_code),
-        )
-            .into_response();
+        let status = StatusCode::from_u16(status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let response = (status, format!("Injected error: {}", status_code)).into_response();
+        if status == StatusCode::SERVICE_UNAVAILABLE {
+            return with_retry_after(response, retry_after_secs);
+        }
+        return response;
     }
 
     // HTTP-layer connection-error injection (when `connection_error_kind` is
@@ -422,11 +428,12 @@ async fn chaos_middleware_core(
     if let Some(ConnectionErrorKind::Http503) = connection_error_kind {
         warn!("Injecting connection_error (Http503) for: {}", path);
         crate::metrics::CHAOS_METRICS.record_fault("connection_error", &path);
-        return (
+        let response = (
             StatusCode::SERVICE_UNAVAILABLE,
             "Injected connection error (Service Unavailable)",
         )
             .into_response();
+        return with_retry_after(response, retry_after_secs);
     }
 
     if let Some(timeout_ms) = timeout_fault {
@@ -588,6 +595,14 @@ async fn chaos_middleware_core(
 }
 
 /// Corrupt a payload based on the corruption type
+/// Attach a `Retry-After` header when a retry delay is configured
+fn with_retry_after(mut response: Response, retry_after_secs: Option<u64>) -> Response {
+    if let Some(secs) = retry_after_secs {
+        response.headers_mut().insert(http::header::RETRY_AFTER, secs.into());
+    }
+    response
+}
+
 fn corrupt_payload(data: &[u8], corruption_type: CorruptionType) -> Vec<u8> {
     if data.is_empty() {
         return data.to_vec();
@@ -725,6 +740,39 @@ mod tests {
         assert_eq!(samples[0].latency_ms, 50, "Recorded latency should match injected delay");
     }
 
+    #[tokio::test]
+    async fn injected_connection_error_sets_retry_after() {
+        use crate::config::FaultInjectionConfig;
+        use axum::{middleware::from_fn_with_state, routing::get, Router};
+        use tower::ServiceExt;
+
+        let config = Arc::new(RwLock::new(ChaosConfig {
+            enabled: true,
+            fault_injection: Some(FaultInjectionConfig {
+                enabled: true,
+                http_error_probability: 0.0,
+                connection_errors: true,
+                connection_error_probability: 1.0,
+                retry_after_secs: Some(15),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }));
+        let middleware =
+            Arc::new(ChaosMiddleware::new(config, Arc::new(LatencyMetricsTracker::new())));
+        middleware.init_from_config().await;
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(from_fn_with_state(middleware, chaos_middleware));
+
+        let res = app
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.headers()[http::header::RETRY_AFTER], "15");
+    }
+
     /// Issue #79 item 6: pre-fix the prometheus counter sat at zero forever
     /// because nothing called `record_fault`. This is a coarse smoke check
     /// that the counter mechanism itself works — the actual call sites are
@@ -467,6 +467,7 @@ mod tests {
             failure_rate_threshold: 50.0,
             min_requests_for_rate: 10,
             rolling_window_ms: 60000,
+            retry_after_secs: 1,
         };
         let registry = Arc::new(Registry::new());
         let cb_manager = Arc::new(CircuitBreakerManager::new(cb_config, registry.clone()));
@@ -500,6 +501,7 @@ mod tests {
             failure_rate_threshold: 50.0,
             min_requests_for_rate: 10,
             rolling_window_ms: 60000,
+            retry_after_secs: 1,
         };
         let registry = Arc::new(Registry::new());
         let cb_manager = Arc::new(CircuitBreakerManager::new(cb_config, registry.clone()));
@@ -533,6 +535,7 @@ mod tests {
             failure_rate_threshold: 50.0,
             min_requests_for_rate: 10,
             rolling_window_ms: 60000,
+            retry_after_secs: 1,
         };
         let registry = Arc::new(Registry::new());
         let cb_manager = Arc::new(CircuitBreakerManager::new(cb_config, registry.clone()));
@@ -110,7 +110,7 @@ pub async fn resilience_middleware(
     //    *or* when the breaker is closed/half-open with budget.
     let breaker = state.circuit_manager.get_breaker(&endpoint).await;
     if !breaker.allow_request().await {
-        return circuit_open_response(&endpoint);
+        return circuit_open_response(&endpoint, breaker.config().await.retry_after_secs);
     }
 
     // 2. Bulkhead admit. The guard auto-releases on drop, which we hold
@@ -153,14 +153,14 @@ pub async fn resilience_middleware(
     response
 }
 
-fn circuit_open_response(endpoint: &str) -> Response {
+fn circuit_open_response(endpoint: &str, retry_after_secs: u64) -> Response {
     let body = Json(json!({
         "error": "circuit_open",
         "endpoint": endpoint,
         "message": "Circuit breaker is open for this endpoint; refusing the request.",
     }));
     let mut resp = (StatusCode::SERVICE_UNAVAILABLE, body).into_response();
-    resp.headers_mut().insert("retry-after", HeaderValue::from_static("1"));
+    resp.headers_mut().insert("retry-after", HeaderValue::from(retry_after_secs));
     resp
 }
 
@@ -211,6 +211,7 @@ mod tests {
                 failure_rate_threshold: 50.0,
                 min_requests_for_rate: 100, // high so the test only trips via consecutive count
                 rolling_window_ms: 10_000,
+                retry_after_secs: 1,
             },
             registry.clone(),
         ));
@@ -311,6 +312,7 @@ mod tests {
             failure_rate_threshold: 100.0,
             min_requests_for_rate: 100,
             rolling_window_ms: 10_000,
+            retry_after_secs: 1,
         };
         let (mw, _api) = resilience_state_from_configs(Some(cb), None);
         let app = app(mw).await;
@@ -319,6 +321,27 @@ mod tests {
         assert_eq!(call(&app, "/boom").await, StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    #[tokio::test]
+    async fn open_breaker_advertises_configured_retry_after() {
+        let cb = CircuitBreakerConfig {
+            enabled: true,
+            failure_threshold: 1,
+            min_requests_for_rate: 100,
+            retry_after_secs: 30,
+            ..Default::default()
+        };
+        let (mw, _api) = resilience_state_from_configs(Some(cb), None);
+        let app = app(mw).await;
+        assert_eq!(call(&app, "/boom").await, StatusCode::INTERNAL_SERVER_ERROR);
+        let res = app
+            .clone()
+            .oneshot(Request::builder().uri("/boom").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.headers()["retry-after"], "30");
+    }
+
     #[tokio::test]
     async fn from_configs_bulkhead_capacity_rejects() {
         // 0-slot bulkhead via the new builder rejects unconditionally —
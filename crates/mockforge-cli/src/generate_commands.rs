@@ -33,7 +33,7 @@ pub(crate) enum SchemaCommands {
         #[arg(short, long)]
         output: Option<PathBuf>,
 
-        /// Schema type to generate (config, reality, persona, blueprint, all)
+        /// Schema type to generate (config, reality, persona, blueprint, websocket, grpc, all)
         /// If not specified and output is a file, defaults to 'config'
         /// If not specified and output is a directory, generates all schemas
         #[arg(short, long, default_value = "all")]
@@ -54,11 +54,17 @@ pub(crate) enum SchemaCommands {
         #[arg(short, long)]
         file: Option<PathBuf>,
 
-        /// Directory containing config files to validate (mutually exclusive with --file)
+        /// Directory containing config files to validate, searched recursively
+        /// (mutually exclusive with --file)
         #[arg(short, long)]
         directory: Option<PathBuf>,
 
-        /// Schema type to use for validation (config, reality, persona, blueprint)
+        /// Glob patterns, relative to --directory, for files and directories
+        /// to skip (e.g. "target/**"); can be repeated
+        #[arg(long)]
+        ignore: Vec<String>,
+
+        /// Schema type to use for validation (config, reality, persona, blueprint, websocket, grpc)
         /// If not specified, will attempt to auto-detect from file path
         #[arg(long)]
         schema_type: Option<String>,
@@ -98,6 +104,8 @@ pub(crate) async fn handle_schema(
                     "reality-config",
                     "persona-config",
                     "blueprint-config",
+                    "websocket-config",
+                    "grpc-config",
                 ]
             } else {
                 vec![&r#type]
@@ -140,6 +148,8 @@ pub(crate) async fn handle_schema(
                             "reality-config" => "**/reality*.yaml",
                             "persona-config" => "**/personas/**/*.yaml",
                             "blueprint-config" => "**/blueprint.yaml",
+                            "websocket-config" => "**/ws/**/*.yaml",
+                            "grpc-config" => "**/*.grpc.yaml",
                             _ => "*.yaml",
                         };
                         println!(
@@ -162,7 +172,7 @@ pub(crate) async fn handle_schema(
                         println!("\u{2705} JSON Schema generated: {}", output_path.display());
                     } else {
                         eprintln!("\u{274c} Unknown schema type: {}", schema_type);
-                        eprintln!("Available types: mockforge-config, reality-config, persona-config, blueprint-config");
+                        eprintln!("Available types: mockforge-config, reality-config, persona-config, blueprint-config, websocket-config, grpc-config");
                         return Err("Invalid schema type".into());
                     }
                 }
@@ -181,7 +191,7 @@ pub(crate) async fn handle_schema(
                     println!("{}", serde_json::to_string_pretty(schema)?);
                 } else {
                     eprintln!("\u{274c} Unknown schema type: {}", r#type);
-                    eprintln!("Available types: mockforge-config, reality-config, persona-config, blueprint-config");
+                    eprintln!("Available types: mockforge-config, reality-config, persona-config, blueprint-config, websocket-config, grpc-config");
                     return Err("Invalid schema type".into());
                 }
             }
@@ -189,15 +199,13 @@ pub(crate) async fn handle_schema(
         SchemaCommands::Validate {
             file,
             directory,
+            ignore,
             schema_type,
             schema_dir,
             strict,
         } => {
-            use crate::schema::{detect_schema_type, generate_all_schemas, validate_config_file};
-            use std::fs;
+            use crate::schema::{validate_directory, validate_files};
 
-            let schemas = generate_all_schemas();
-            let mut validation_results = Vec::new();
             let mut has_errors = false;
 
             // Determine schema directory
@@ -210,33 +218,19 @@ pub(crate) async fn handle_schema(
                     None
                 }
             });
+            let schemas = load_schemas(schema_dir_path.as_deref());
 
-            // Collect files to validate
-            let files_to_validate: Vec<PathBuf> = if let Some(file_path) = file {
-                vec![file_path]
+            // Validate the given file, every config file under the given
+            // directory, or mockforge.yaml in the current directory
+            let validation_results = if let Some(file_path) = file {
+                validate_files(&[file_path], &schemas, schema_type.as_deref())
             } else if let Some(dir_path) = directory {
-                // Find all YAML/JSON files in directory
-                let mut files = Vec::new();
-                if let Ok(entries) = fs::read_dir(&dir_path) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.is_file() {
-                            if let Some(ext) = path.extension() {
-                                let ext_str = ext.to_string_lossy().to_lowercase();
-                                if ext_str == "yaml" || ext_str == "yml" || ext_str == "json" {
-                                    files.push(path);
-                                }
-                            }
-                        }
-                    }
-                }
-                files
+                validate_directory(&dir_path, &schemas, schema_type.as_deref(), &ignore)?
             } else {
-                // Default: validate mockforge.yaml in current directory
                 let current_dir = std::env::current_dir()?;
                 let default_file = current_dir.join("mockforge.yaml");
                 if default_file.exists() {
-                    vec![default_file]
+                    validate_files(&[default_file], &schemas, schema_type.as_deref())
                 } else {
                     eprintln!("\u{274c} No config file specified and mockforge.yaml not found in current directory");
                     eprintln!("   Use --file or --directory to specify files to validate");
@@ -244,73 +238,6 @@ pub(crate) async fn handle_schema(
                 }
             };
 
-            // Validate each file
-            for file_path in &files_to_validate {
-                // Determine schema type
-                let file_schema_type = schema_type.clone().unwrap_or_else(|| {
-                    detect_schema_type(file_path).unwrap_or_else(|| "mockforge-config".to_string())
-                });
-
-                // Get schema (try from schema_dir first, then use generated)
-                let schema = if let Some(ref schema_dir) = schema_dir_path {
-                    let schema_file = schema_dir
-                        .join(format!("{}.schema.json", file_schema_type.replace("-", "_")));
-                    if schema_file.exists() {
-                        match fs::read_to_string(&schema_file).and_then(|content| {
-                            serde_json::from_str::<serde_json::Value>(&content).map_err(|e| {
-                                std::io::Error::new(std::io::ErrorKind::InvalidData, e)
-                            })
-                        }) {
-                            Ok(s) => s,
-                            Err(e) => {
-                                eprintln!(
-                                    "\u{26a0}\u{fe0f}  Failed to load schema from {}: {}",
-                                    schema_file.display(),
-                                    e
-                                );
-                                schemas.get(&file_schema_type).cloned().unwrap_or_else(|| {
-                                    eprintln!(
-                                        "\u{274c} Schema type '{}' not found",
-                                        file_schema_type
-                                    );
-                                    has_errors = true;
-                                    serde_json::json!({})
-                                })
-                            }
-                        }
-                    } else {
-                        schemas.get(&file_schema_type).cloned().unwrap_or_else(|| {
-                            eprintln!(
-                                "\u{26a0}\u{fe0f}  Schema file not found: {}, using generated schema",
-                                schema_file.display()
-                            );
-                            schemas.get(&file_schema_type).cloned().unwrap_or_else(|| {
-                                eprintln!("\u{274c} Schema type '{}' not found", file_schema_type);
-                                has_errors = true;
-                                serde_json::json!({})
-                            })
-                        })
-                    }
-                } else {
-                    schemas.get(&file_schema_type).cloned().unwrap_or_else(|| {
-                        eprintln!("\u{274c} Schema type '{}' not found", file_schema_type);
-                        has_errors = true;
-                        serde_json::json!({})
-                    })
-                };
-
-                // Validate
-                match validate_config_file(file_path, &file_schema_type, &schema) {
-                    Ok(result) => {
-                        validation_results.push(result);
-                    }
-                    Err(e) => {
-                        eprintln!("\u{274c} Failed to validate {}: {}", file_path.display(), e);
-                        has_errors = true;
-                    }
-                }
-            }
-
             // Print results
             println!("\n\u{1f4cb} Validation Results:\n");
             for result in &validation_results {
@@ -318,7 +245,7 @@ pub(crate) async fn handle_schema(
                     println!("  \u{2705} {} (schema: {})", result.file_path, result.schema_type);
                 } else {
                     println!("  \u{274c} {} (schema: {})", result.file_path, result.schema_type);
-                    for error in &result.errors {
+                    for error in &result.errors_detailed {
                         println!("     \u{2022} {}", error);
                     }
                     has_errors = true;
@@ -349,6 +276,35 @@ pub(crate) async fn handle_schema(
     Ok(())
 }
 
+/// The generated schemas, each replaced by `<schema_dir>/<type>.schema.json`
+/// when that file exists and parses
+fn load_schemas(
+    schema_dir: Option<&std::path::Path>,
+) -> std::collections::HashMap<String, serde_json::Value> {
+    let mut schemas = crate::schema::generate_all_schemas();
+    let Some(schema_dir) = schema_dir else {
+        return schemas;
+    };
+    for (schema_type, schema) in &mut schemas {
+        let schema_file = schema_dir.join(format!("{}.schema.json", schema_type.replace("-", "_")));
+        if !schema_file.exists() {
+            continue;
+        }
+        match std::fs::read_to_string(&schema_file).and_then(|content| {
+            serde_json::from_str::<serde_json::Value>(&content)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        }) {
+            Ok(loaded) => *schema = loaded,
+            Err(e) => eprintln!(
+                "\u{26a0}\u{fe0f}  Failed to load schema from {}: {}, using generated schema",
+                schema_file.display(),
+                e
+            ),
+        }
+    }
+    schemas
+}
+
 /// Handle mock generation from configuration
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn handle_generate(
@@ -387,6 +387,10 @@ struct ChaosArgs {
     #[arg(long, default_value = "50.0", help_heading = "Resilience Patterns")]
     pub circuit_breaker_failure_rate: f64,
 
+    /// Circuit breaker: Retry-After seconds sent while the circuit is open
+    #[arg(long, default_value = "1", help_heading = "Resilience Patterns")]
+    pub circuit_breaker_retry_after_secs: u64,
+
     /// Enable bulkhead pattern
     #[arg(long, help_heading = "Resilience Patterns")]
     pub bulkhead: bool,
@@ -2828,6 +2832,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
                     .circuit_breaker_success_threshold,
                 circuit_breaker_timeout_ms: args.chaos_opts.circuit_breaker_timeout_ms,
                 circuit_breaker_failure_rate: args.chaos_opts.circuit_breaker_failure_rate,
+                circuit_breaker_retry_after_secs: args.chaos_opts.circuit_breaker_retry_after_secs,
                 bulkhead: args.chaos_opts.bulkhead,
                 bulkhead_max_concurrent: args.chaos_opts.bulkhead_max_concurrent,
                 bulkhead_max_queue: args.chaos_opts.bulkhead_max_queue,
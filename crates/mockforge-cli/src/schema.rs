@@ -111,6 +111,63 @@ pub fn generate_persona_schema() -> serde_json::Value {
     schema_value
 }
 
+/// Generate JSON Schema for WebSocket server configuration
+///
+/// Covers the `websocket` section of `mockforge.yaml` (port, host, replay
+/// file and connection timeout) for files that configure it on its own.
+pub fn generate_websocket_schema() -> serde_json::Value {
+    let schema = schema_for!(mockforge_core::config::WebSocketConfig);
+
+    let mut schema_value =
+        serde_json::to_value(schema).expect("Failed to serialize WebSocket schema");
+
+    // Add metadata for better IDE support
+    if let Some(obj) = schema_value.as_object_mut() {
+        obj.insert(
+            "$schema".to_string(),
+            serde_json::json!("http://json-schema.org/draft-07/schema#"),
+        );
+        obj.insert("title".to_string(), serde_json::json!("MockForge WebSocket Configuration"));
+        obj.insert(
+            "description".to_string(),
+            serde_json::json!(
+                "WebSocket server configuration for MockForge. \
+             Sets the listen address, the replay file to serve and the connection timeout."
+            ),
+        );
+    }
+
+    schema_value
+}
+
+/// Generate JSON Schema for gRPC server configuration
+///
+/// Covers the `grpc` section of `mockforge.yaml`, including TLS and the
+/// per-method response overrides.
+pub fn generate_grpc_schema() -> serde_json::Value {
+    let schema = schema_for!(mockforge_core::config::GrpcConfig);
+
+    let mut schema_value = serde_json::to_value(schema).expect("Failed to serialize gRPC schema");
+
+    // Add metadata for better IDE support
+    if let Some(obj) = schema_value.as_object_mut() {
+        obj.insert(
+            "$schema".to_string(),
+            serde_json::json!("http://json-schema.org/draft-07/schema#"),
+        );
+        obj.insert("title".to_string(), serde_json::json!("MockForge gRPC Configuration"));
+        obj.insert(
+            "description".to_string(),
+            serde_json::json!(
+                "gRPC server configuration for MockForge. \
+             Sets the listen address, proto directory, TLS and per-method response overrides."
+            ),
+        );
+    }
+
+    schema_value
+}
+
 /// Generate JSON Schema for Blueprint metadata
 ///
 /// Generates schema for blueprint.yaml files that define app archetypes.
@@ -353,6 +410,8 @@ pub fn generate_all_schemas() -> std::collections::HashMap<String, serde_json::V
     schemas.insert("reality-config".to_string(), generate_reality_schema());
     schemas.insert("persona-config".to_string(), generate_persona_schema());
     schemas.insert("blueprint-config".to_string(), generate_blueprint_schema());
+    schemas.insert("websocket-config".to_string(), generate_websocket_schema());
+    schemas.insert("grpc-config".to_string(), generate_grpc_schema());
 
     schemas
 }
@@ -368,6 +427,39 @@ pub struct ValidationResult {
     pub schema_type: String,
     /// Validation errors (empty if valid)
     pub errors: Vec<String>,
+    /// The same errors with their JSON Pointer and, for YAML files, the line
+    /// and column they refer to
+    pub errors_detailed: Vec<SchemaError>,
+}
+
+/// One schema violation in a validated config file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaError {
+    /// JSON Pointer to the offending value (empty for the document root)
+    pub instance_path: String,
+    /// What is wrong with the value
+    pub message: String,
+    /// 1-based line of the value in the source file, when known
+    pub line: Option<usize>,
+    /// 1-based column of the value in the source file, when known
+    pub column: Option<usize>,
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.instance_path.is_empty() {
+            "/"
+        } else {
+            &self.instance_path
+        };
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "line {}, column {} ({}): {}", line, column, path, self.message)
+            }
+            (Some(line), None) => write!(f, "line {} ({}): {}", line, path, self.message),
+            _ => write!(f, "{}: {}", path, self.message),
+        }
+    }
 }
 
 impl ValidationResult {
@@ -378,78 +470,366 @@ impl ValidationResult {
             file_path,
             schema_type,
             errors: Vec::new(),
+            errors_detailed: Vec::new(),
         }
     }
 
     /// Create a failed validation result
+    ///
+    /// The errors carry no location; use [`ValidationResult::failure_detailed`]
+    /// when the JSON Pointer or source position is known.
     pub fn failure(file_path: String, schema_type: String, errors: Vec<String>) -> Self {
+        let errors_detailed = errors
+            .iter()
+            .map(|message| SchemaError {
+                instance_path: String::new(),
+                message: message.clone(),
+                line: None,
+                column: None,
+            })
+            .collect();
+        Self {
+            valid: false,
+            file_path,
+            schema_type,
+            errors,
+            errors_detailed,
+        }
+    }
+
+    /// Create a failed validation result from located errors, keeping
+    /// `errors` in the `{instance_path}: {message}` form
+    pub fn failure_detailed(
+        file_path: String,
+        schema_type: String,
+        errors_detailed: Vec<SchemaError>,
+    ) -> Self {
+        let errors = errors_detailed
+            .iter()
+            .map(|error| format!("{}: {}", error.instance_path, error.message))
+            .collect();
         Self {
             valid: false,
             file_path,
             schema_type,
             errors,
+            errors_detailed,
         }
     }
 }
 
-/// Validate a config file against its corresponding JSON Schema
+/// Source positions of every node in a YAML document, keyed by JSON Pointer
 ///
-/// # Arguments
+/// Values inside a mapping are located at their key, so a wrong-typed nested
+/// block points at the line naming it rather than its first child.
+#[derive(Default)]
+struct YamlLocator {
+    locations: std::collections::HashMap<String, (usize, usize)>,
+    stack: Vec<YamlFrame>,
+}
+
+enum YamlFrame {
+    Mapping {
+        pointer: String,
+        key: Option<(String, yaml_rust2::scanner::Marker)>,
+    },
+    Sequence {
+        pointer: String,
+        index: usize,
+    },
+}
+
+impl YamlLocator {
+    /// Locate the nodes of the first document in `content`; positions are
+    /// best-effort and missing if the YAML cannot be scanned
+    fn locate(content: &str) -> std::collections::HashMap<String, (usize, usize)> {
+        let mut locator = Self::default();
+        let _ = yaml_rust2::parser::Parser::new_from_str(content).load(&mut locator, false);
+        locator.locations
+    }
+
+    /// Record a node starting at `mark` and return its pointer, or `None` if
+    /// the node is a mapping key
+    fn enter_node(
+        &mut self,
+        scalar: Option<&str>,
+        mark: yaml_rust2::scanner::Marker,
+    ) -> Option<String> {
+        let (pointer, mark) = match self.stack.last_mut() {
+            None => (String::new(), mark),
+            Some(YamlFrame::Mapping {
+                key: key @ None, ..
+            }) => {
+                *key = Some((scalar.unwrap_or_default().to_string(), mark));
+                return None;
+            }
+            Some(YamlFrame::Mapping { pointer, key }) => {
+                let (name, key_mark) = key.take().expect("key is set");
+                (format!("{}/{}", pointer, name.replace('~', "~0").replace('/', "~1")), key_mark)
+            }
+            Some(YamlFrame::Sequence { pointer, index }) => {
+                *index += 1;
+                (format!("{}/{}", pointer, *index - 1), mark)
+            }
+        };
+        self.locations.entry(pointer.clone()).or_insert((mark.line(), mark.col() + 1));
+        Some(pointer)
+    }
+}
+
+impl yaml_rust2::parser::MarkedEventReceiver for YamlLocator {
+    fn on_event(&mut self, event: yaml_rust2::Event, mark: yaml_rust2::scanner::Marker) {
+        use yaml_rust2::Event;
+        match event {
+            Event::Scalar(value, ..) => {
+                self.enter_node(Some(&value), mark);
+            }
+            Event::Alias(_) => {
+                self.enter_node(None, mark);
+            }
+            Event::MappingStart(..) => {
+                // Complex keys are not addressable by a pointer; their
+                // contents are located under an unreachable path
+                let pointer = self.enter_node(None, mark).unwrap_or_else(|| "\u{0}".to_string());
+                self.stack.push(YamlFrame::Mapping { pointer, key: None });
+            }
+            Event::SequenceStart(..) => {
+                let pointer = self.enter_node(None, mark).unwrap_or_else(|| "\u{0}".to_string());
+                self.stack.push(YamlFrame::Sequence { pointer, index: 0 });
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A JSON Schema compiled once for validating any number of config files
 ///
-/// * `file_path` - Path to the config file (YAML or JSON)
-/// * `schema_type` - Type of schema to validate against (config, reality, persona, blueprint)
-/// * `schema` - The JSON Schema to validate against
+/// Compiling dominates the cost of validating a single file, so callers that
+/// check several files against the same schema should compile it once (or go
+/// through a [`SchemaCache`]) and call [`CompiledSchema::validate`] per file.
+/// The compiled schema is `Send + Sync` and can be shared behind an `Arc`.
+pub struct CompiledSchema {
+    schema_type: String,
+    validator: jsonschema::Validator,
+}
+
+impl CompiledSchema {
+    /// Compile `schema` (draft 7) for validating files of `schema_type`
+    pub fn compile(
+        schema_type: &str,
+        schema: &serde_json::Value,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let validator = jsonschema::Validator::options()
+            .with_draft(jsonschema::Draft::Draft7)
+            .build(schema)
+            .map_err(|e| format!("Failed to compile schema: {}", e))?;
+        Ok(Self {
+            schema_type: schema_type.to_string(),
+            validator,
+        })
+    }
+
+    /// Validate a config file (YAML or JSON) against the compiled schema
+    pub fn validate(
+        &self,
+        file_path: &std::path::Path,
+    ) -> Result<ValidationResult, Box<dyn std::error::Error>> {
+        // Read and parse the config file
+        let content = std::fs::read_to_string(file_path)?;
+        let is_yaml = file_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
+            .unwrap_or(false);
+        let config_value: serde_json::Value = if is_yaml {
+            // Parse YAML
+            serde_yaml::from_str(&content).map_err(|e| format!("Failed to parse YAML: {}", e))?
+        } else {
+            // Parse JSON
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?
+        };
+
+        // Validate
+        let mut errors = Vec::new();
+        for error in self.validator.iter_errors(&config_value) {
+            errors.push(SchemaError {
+                instance_path: error.instance_path.to_string(),
+                message: error.to_string(),
+                line: None,
+                column: None,
+            });
+        }
+
+        // Map each pointer back to the YAML source, falling back to the
+        // nearest located ancestor (e.g. the object missing a required field)
+        if is_yaml && !errors.is_empty() {
+            let locations = YamlLocator::locate(&content);
+            for error in &mut errors {
+                let mut pointer = error.instance_path.as_str();
+                let location = loop {
+                    if let Some(location) = locations.get(pointer) {
+                        break Some(*location);
+                    }
+                    match pointer.rfind('/') {
+                        Some(parent) => pointer = &pointer[..parent],
+                        None => break None,
+                    }
+                };
+                if let Some((line, column)) = location {
+                    error.line = Some(line);
+                    error.column = Some(column);
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(ValidationResult::success(
+                file_path.to_string_lossy().to_string(),
+                self.schema_type.clone(),
+            ))
+        } else {
+            Ok(ValidationResult::failure_detailed(
+                file_path.to_string_lossy().to_string(),
+                self.schema_type.clone(),
+                errors,
+            ))
+        }
+    }
+}
+
+/// Compiled schemas by schema type, each compiled on first use
 ///
-/// # Returns
+/// Safe to share between threads; the first caller for a schema type compiles
+/// it and later callers get the same [`CompiledSchema`].
+#[derive(Default)]
+pub struct SchemaCache {
+    compiled: std::sync::Mutex<std::collections::HashMap<String, std::sync::Arc<CompiledSchema>>>,
+}
+
+impl SchemaCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The compiled schema for `schema_type`, compiling `schema` if this is
+    /// the first request for that type. Later calls ignore `schema`.
+    pub fn get_or_compile(
+        &self,
+        schema_type: &str,
+        schema: &serde_json::Value,
+    ) -> Result<std::sync::Arc<CompiledSchema>, Box<dyn std::error::Error>> {
+        let mut compiled = self.compiled.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(existing) = compiled.get(schema_type) {
+            return Ok(existing.clone());
+        }
+        let schema = std::sync::Arc::new(CompiledSchema::compile(schema_type, schema)?);
+        compiled.insert(schema_type.to_string(), schema.clone());
+        Ok(schema)
+    }
+}
+
+/// Find the config files (`.yaml`, `.yml`, `.json`) under `root`, recursively
 ///
-/// A ValidationResult indicating whether validation passed and any errors
-pub fn validate_config_file(
-    file_path: &std::path::Path,
-    schema_type: &str,
-    schema: &serde_json::Value,
-) -> Result<ValidationResult, Box<dyn std::error::Error>> {
-    use jsonschema::{Draft, Validator as SchemaValidator};
-    use std::fs;
-
-    // Read and parse the config file
-    let content = fs::read_to_string(file_path)?;
-    let config_value: serde_json::Value = if file_path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml"))
-        .unwrap_or(false)
-    {
-        // Parse YAML
-        serde_yaml::from_str(&content).map_err(|e| format!("Failed to parse YAML: {}", e))?
-    } else {
-        // Parse JSON
-        serde_json::from_str(&content).map_err(|e| format!("Failed to parse JSON: {}", e))?
+/// `ignore` holds glob patterns matched against paths relative to `root`
+/// (e.g. `target/**`, `**/node_modules/**`, `*.draft.yaml`); matching files
+/// are skipped and matching directories are not entered. Unreadable
+/// directories are skipped. The files are returned in sorted order.
+///
+/// Returns an error naming the first pattern that is not a valid glob.
+pub fn config_files_in(
+    root: &std::path::Path,
+    ignore: &[String],
+) -> Result<Vec<std::path::PathBuf>, String> {
+    let patterns = ignore
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern)
+                .map_err(|e| format!("Invalid ignore pattern '{}': {}", pattern, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let ignored = |path: &std::path::Path| {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        patterns.iter().any(|pattern| pattern.matches_path(relative))
     };
 
-    // Compile the schema
-    let compiled_schema = SchemaValidator::options()
-        .with_draft(Draft::Draft7)
-        .build(schema)
-        .map_err(|e| format!("Failed to compile schema: {}", e))?;
-
-    // Validate
-    let mut errors = Vec::new();
-    for error in compiled_schema.iter_errors(&config_value) {
-        errors.push(format!("{}: {}", error.instance_path, error));
-    }
-
-    if errors.is_empty() {
-        Ok(ValidationResult::success(
-            file_path.to_string_lossy().to_string(),
-            schema_type.to_string(),
-        ))
-    } else {
-        Ok(ValidationResult::failure(
-            file_path.to_string_lossy().to_string(),
-            schema_type.to_string(),
-            errors,
-        ))
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if ignored(&path) {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ["yaml", "yml", "json"].iter().any(|e| ext.eq_ignore_ascii_case(e)))
+                .unwrap_or(false)
+            {
+                files.push(path);
+            }
+        }
     }
+    files.sort();
+    Ok(files)
+}
+
+/// Validate config files against the schema for their type
+///
+/// Each file is validated against `schemas[schema_type]`, or against the
+/// schema for [`detect_schema_type`] when no `schema_type` is given,
+/// compiling each schema once. Returns one result per file; a file that
+/// cannot be read or parsed, or whose schema type is missing from `schemas`,
+/// gets a failed result describing why.
+pub fn validate_files(
+    files: &[std::path::PathBuf],
+    schemas: &std::collections::HashMap<String, serde_json::Value>,
+    schema_type: Option<&str>,
+) -> Vec<ValidationResult> {
+    let cache = SchemaCache::new();
+    files
+        .iter()
+        .map(|file_path| {
+            let schema_type = schema_type.map(str::to_string).unwrap_or_else(|| {
+                detect_schema_type(file_path).unwrap_or_else(|| "mockforge-config".to_string())
+            });
+            let result = match schemas.get(&schema_type) {
+                Some(schema) => cache
+                    .get_or_compile(&schema_type, schema)
+                    .and_then(|compiled| compiled.validate(file_path))
+                    .map_err(|e| e.to_string()),
+                None => Err(format!("Schema type '{}' not found", schema_type)),
+            };
+            result.unwrap_or_else(|error| {
+                ValidationResult::failure(
+                    file_path.to_string_lossy().to_string(),
+                    schema_type,
+                    vec![error],
+                )
+            })
+        })
+        .collect()
+}
+
+/// Validate every config file under `root` with [`validate_files`]
+///
+/// Files are found with [`config_files_in`], so `ignore` takes glob patterns.
+/// Fails only on an invalid `ignore` pattern.
+pub fn validate_directory(
+    root: &std::path::Path,
+    schemas: &std::collections::HashMap<String, serde_json::Value>,
+    schema_type: Option<&str>,
+    ignore: &[String],
+) -> Result<Vec<ValidationResult>, String> {
+    Ok(validate_files(&config_files_in(root, ignore)?, schemas, schema_type))
 }
 
 /// Auto-detect schema type from file path or content
@@ -472,6 +852,17 @@ pub fn detect_schema_type(file_path: &std::path::Path) -> Option<String> {
         return Some("blueprint-config".to_string());
     }
 
+    if file_name.ends_with(".grpc.yaml") || file_name.ends_with(".grpc.yml") {
+        return Some("grpc-config".to_string());
+    }
+
+    if file_path
+        .parent()
+        .is_some_and(|dir| dir.components().any(|c| c.as_os_str().eq_ignore_ascii_case("ws")))
+    {
+        return Some("websocket-config".to_string());
+    }
+
     if path_str.contains("reality") {
         return Some("reality-config".to_string());
     }
@@ -544,6 +935,38 @@ mod tests {
         assert!(obj.contains_key("description"));
     }
 
+    #[test]
+    fn test_generate_websocket_schema() {
+        let schema = generate_websocket_schema();
+        let obj = schema.as_object().unwrap();
+
+        assert_eq!(
+            obj.get("$schema").unwrap(),
+            &serde_json::json!("http://json-schema.org/draft-07/schema#")
+        );
+        assert_eq!(
+            obj.get("title").unwrap(),
+            &serde_json::json!("MockForge WebSocket Configuration")
+        );
+        assert!(obj.contains_key("description"));
+        assert!(schema["properties"].get("replay_file").is_some());
+    }
+
+    #[test]
+    fn test_generate_grpc_schema() {
+        let schema = generate_grpc_schema();
+        let obj = schema.as_object().unwrap();
+
+        assert_eq!(
+            obj.get("$schema").unwrap(),
+            &serde_json::json!("http://json-schema.org/draft-07/schema#")
+        );
+        assert_eq!(obj.get("title").unwrap(), &serde_json::json!("MockForge gRPC Configuration"));
+        assert!(obj.contains_key("description"));
+        assert!(schema["properties"].get("proto_dir").is_some());
+        assert!(schema["properties"].get("overrides").is_some());
+    }
+
     #[test]
     fn test_generate_blueprint_schema() {
         let schema = generate_blueprint_schema();
@@ -617,11 +1040,13 @@ mod tests {
         let schemas = generate_all_schemas();
 
         // Verify all expected schemas are present
-        assert_eq!(schemas.len(), 4);
+        assert_eq!(schemas.len(), 6);
         assert!(schemas.contains_key("mockforge-config"));
         assert!(schemas.contains_key("reality-config"));
         assert!(schemas.contains_key("persona-config"));
         assert!(schemas.contains_key("blueprint-config"));
+        assert!(schemas.contains_key("websocket-config"));
+        assert!(schemas.contains_key("grpc-config"));
 
         // Verify each schema is valid
         for (name, schema) in &schemas {
@@ -688,6 +1113,117 @@ mod tests {
         assert_eq!(cloned.errors, result.errors);
     }
 
+    #[test]
+    fn test_yaml_errors_report_source_line() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "http": {
+                    "type": "object",
+                    "properties": {
+                        "port": { "type": "integer" },
+                        "cors": {
+                            "type": "object",
+                            "properties": { "origins": { "type": "array" } }
+                        }
+                    }
+                },
+                "routes": {
+                    "type": "array",
+                    "items": { "type": "object", "required": ["path"] }
+                }
+            }
+        });
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("mockforge.yaml");
+        std::fs::write(
+            &file_path,
+            "# server settings\nhttp:\n  port: 3000\n  cors:\n    origins: \"*\"\nroutes:\n  - path: /a\n  - method: GET\n",
+        )
+        .unwrap();
+
+        let result = validate_config_file(&file_path, "test-config", &schema).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.errors.len(), result.errors_detailed.len());
+
+        let origins = result
+            .errors_detailed
+            .iter()
+            .find(|e| e.instance_path == "/http/cors/origins")
+            .unwrap();
+        assert_eq!((origins.line, origins.column), (Some(5), Some(5)));
+        assert!(origins.to_string().starts_with("line 5, column 5 (/http/cors/origins): "));
+        assert!(result.errors.iter().any(|e| e.starts_with("/http/cors/origins: ")));
+
+        // A missing required field points at the object lacking it
+        let route = result.errors_detailed.iter().find(|e| e.instance_path == "/routes/1").unwrap();
+        assert_eq!(route.line, Some(8));
+    }
+
+    #[test]
+    fn test_validate_directory_aggregates_results() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("mockforge.yaml"), "http:\n  port: 3000\n").unwrap();
+        let nested = root.path().join("blueprints/saas");
+        std::fs::create_dir_all(&nested).unwrap();
+        // Missing the required metadata fields
+        std::fs::write(nested.join("blueprint.yaml"), "name: saas\n").unwrap();
+        std::fs::write(nested.join("README.md"), "# not a config").unwrap();
+        let drafts = root.path().join("drafts");
+        std::fs::create_dir_all(&drafts).unwrap();
+        std::fs::write(drafts.join("mockforge.yaml"), "http: [").unwrap();
+
+        let schemas = std::collections::HashMap::from([
+            (
+                "mockforge-config".to_string(),
+                serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "http": {
+                            "type": "object",
+                            "properties": { "port": { "type": "integer" } }
+                        }
+                    }
+                }),
+            ),
+            ("blueprint-config".to_string(), generate_blueprint_schema()),
+        ]);
+        let results =
+            validate_directory(root.path(), &schemas, None, &["drafts/**".to_string()]).unwrap();
+
+        assert_eq!(results.len(), 2, "{:?}", results);
+        let blueprint = results.iter().find(|r| r.file_path.ends_with("blueprint.yaml")).unwrap();
+        assert_eq!(blueprint.schema_type, "blueprint-config");
+        assert!(!blueprint.valid);
+        assert!(!blueprint.errors.is_empty());
+        let config = results.iter().find(|r| r.file_path.ends_with("mockforge.yaml")).unwrap();
+        assert_eq!(config.schema_type, "mockforge-config");
+        assert!(config.valid, "{:?}", config.errors);
+
+        // An explicit schema type applies to every file
+        let results = validate_directory(
+            root.path(),
+            &schemas,
+            Some("persona-config"),
+            &["drafts/**".to_string()],
+        )
+        .unwrap();
+        assert!(results.iter().all(|r| r.schema_type == "persona-config" && !r.valid));
+    }
+
+    #[test]
+    fn test_config_files_in_rejects_invalid_ignore_pattern() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("mockforge.yaml"), "http: {}\n").unwrap();
+
+        let error = config_files_in(root.path(), &["target/[".to_string()]).unwrap_err();
+        assert!(error.contains("'target/['"), "{}", error);
+
+        let files = config_files_in(root.path(), &["target/**".to_string()]).unwrap();
+        assert_eq!(files, vec![root.path().join("mockforge.yaml")]);
+    }
+
     // ==================== detect_schema_type Tests ====================
 
     #[test]
@@ -732,6 +1268,25 @@ mod tests {
         assert_eq!(detect_schema_type(&path), Some("persona-config".to_string()));
     }
 
+    #[test]
+    fn test_detect_schema_type_ws_directory() {
+        let path = PathBuf::from("/project/ws/chat-replay.yaml");
+        assert_eq!(detect_schema_type(&path), Some("websocket-config".to_string()));
+
+        // Only a `ws` directory counts, not names containing it
+        let path2 = PathBuf::from("/project/news/feed.yaml");
+        assert_eq!(detect_schema_type(&path2), Some("mockforge-config".to_string()));
+    }
+
+    #[test]
+    fn test_detect_schema_type_grpc_yaml() {
+        let path = PathBuf::from("/project/greeter.grpc.yaml");
+        assert_eq!(detect_schema_type(&path), Some("grpc-config".to_string()));
+
+        let path2 = PathBuf::from("/project/ws/Greeter.GRPC.yml");
+        assert_eq!(detect_schema_type(&path2), Some("grpc-config".to_string()));
+    }
+
     #[test]
     fn test_detect_schema_type_default() {
         let path = PathBuf::from("/some/other/config.yaml");
@@ -747,7 +1302,15 @@ mod tests {
         assert_eq!(detect_schema_type(&path2), Some("blueprint-config".to_string()));
     }
 
-    // ==================== validate_config_file Tests ====================
+    // ==================== CompiledSchema::validate Tests ====================
+
+    fn validate_config_file(
+        file_path: &std::path::Path,
+        schema_type: &str,
+        schema: &serde_json::Value,
+    ) -> Result<ValidationResult, Box<dyn std::error::Error>> {
+        CompiledSchema::compile(schema_type, schema)?.validate(file_path)
+    }
 
     #[test]
     fn test_validate_yaml_file_valid() {
@@ -852,6 +1415,54 @@ mod tests {
         std::fs::remove_file(&file_path).ok();
     }
 
+    #[test]
+    fn test_schema_cache_compiles_each_schema_once() {
+        let schema = serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "port": { "type": "integer" }
+            },
+            "required": ["port"]
+        });
+        let temp_dir = tempfile::tempdir().unwrap();
+        let files: Vec<PathBuf> = (0..5)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("service-{}.yaml", i));
+                let port = if i == 3 {
+                    "\"eighty\"".to_string()
+                } else {
+                    (8000 + i).to_string()
+                };
+                std::fs::write(&path, format!("port: {}\n", port)).unwrap();
+                path
+            })
+            .collect();
+
+        let cache = std::sync::Arc::new(SchemaCache::new());
+        let first = cache.get_or_compile("test-config", &schema).unwrap();
+        let schema = &schema;
+        let results: Vec<ValidationResult> = std::thread::scope(|scope| {
+            let handles: Vec<_> = files
+                .iter()
+                .map(|path| {
+                    let cache = cache.clone();
+                    let first = first.clone();
+                    scope.spawn(move || {
+                        let compiled = cache.get_or_compile("test-config", schema).unwrap();
+                        assert!(std::sync::Arc::ptr_eq(&compiled, &first), "schema recompiled");
+                        compiled.validate(path).unwrap()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+
+        assert_eq!(results.len(), 5);
+        assert_eq!(results.iter().filter(|r| r.valid).count(), 4);
+        assert!(results.iter().all(|r| r.schema_type == "test-config"));
+    }
+
     #[test]
     fn test_validate_file_not_found() {
         let schema = serde_json::json!({
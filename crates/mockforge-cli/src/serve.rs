@@ -108,6 +108,7 @@ pub(crate) struct ServeArgs {
     pub(crate) circuit_breaker_success_threshold: u64,
     pub(crate) circuit_breaker_timeout_ms: u64,
     pub(crate) circuit_breaker_failure_rate: f64,
+    pub(crate) circuit_breaker_retry_after_secs: u64,
     /// Enable the global bulkhead resilience pattern (#468 follow-up).
     pub(crate) bulkhead: bool,
     pub(crate) bulkhead_max_concurrent: u32,
@@ -214,6 +215,7 @@ impl Default for ServeArgs {
             circuit_breaker_success_threshold: 2,
             circuit_breaker_timeout_ms: 60_000,
             circuit_breaker_failure_rate: 50.0,
+            circuit_breaker_retry_after_secs: 1,
             bulkhead: false,
             bulkhead_max_concurrent: 100,
             bulkhead_max_queue: 10,
@@ -1018,6 +1020,9 @@ pub async fn handle_serve(
     // This ensures HTTP request logs are captured from the very first request,
     // not just after the admin UI router happens to initialize.
     mockforge_core::init_global_logger(1000);
+    if mockforge_http::log_sink::init_log_sink_from_env() {
+        println!("📤 Exporting request logs to MOCKFORGE_LOG_SINK_WEBHOOK_URL");
+    }
 
     println!("📡 HTTP server on port {}", config.http.port);
     println!("🔌 WebSocket server on port {}", config.websocket.port);
@@ -1890,6 +1895,9 @@ pub async fn handle_serve(
         None
     };
 
+    // Let the management API serve the merged config (`/config/export.yaml`)
+    mockforge_core::config::set_effective_config(config.clone());
+
     // Use standard router
     let mut http_app = mockforge_http::build_router_with_chains_and_multi_tenant(
         final_spec_path,
@@ -1929,6 +1937,7 @@ pub async fn handle_serve(
                 half_open_max_requests: 3,
                 min_requests_for_rate: 10,
                 rolling_window_ms: 10_000,
+                retry_after_secs: serve_args.circuit_breaker_retry_after_secs,
             });
     let cli_bulkhead = serve_args.bulkhead.then_some(mockforge_chaos::config::BulkheadConfig {
         enabled: true,
@@ -2019,7 +2028,12 @@ pub async fn handle_serve(
     // MOCKFORGE_WS_PORT=0 to skip it.
     #[cfg(feature = "ws")]
     {
-        http_app = http_app.merge(mockforge_ws::router());
+        // Sockets count against `http.streaming_limit.max_websocket`
+        let ws_router = mockforge_ws::router().layer(axum::middleware::from_fn_with_state(
+            mockforge_http::connection_tracker::global_connections().clone(),
+            mockforge_http::connection_tracker::websocket_limit_middleware,
+        ));
+        http_app = http_app.merge(ws_router);
         println!("✅ WebSocket endpoint available at /ws (HTTP upgrade)");
     }
 
@@ -2330,6 +2344,124 @@ pub async fn handle_serve(
         ));
     }
 
+    // Mirror a sample of requests to a secondary upstream for shadow testing.
+    // Fire-and-forget: the mirror never affects the mock response.
+    if let Some(mirror) = config.http.mirror.clone().filter(|m| m.enabled) {
+        use axum::middleware::from_fn_with_state;
+        println!(
+            "✅ Request mirroring to {} (sample rate {})",
+            mirror.target_url, mirror.sample_rate
+        );
+        http_app = http_app.layer(from_fn_with_state(
+            mockforge_http::middleware::MirrorState::new(mirror),
+            mockforge_http::middleware::mirror_middleware,
+        ));
+    }
+
+    // Collapse concurrent identical requests into one handler run, like a
+    // caching reverse proxy. Layered outside the mirror so duplicates that
+    // are coalesced away are not mirrored either.
+    if let Some(coalescing) = config.http.coalescing.clone().filter(|c| c.enabled) {
+        use axum::middleware::from_fn_with_state;
+        println!("✅ Request coalescing for {}", coalescing.methods.join(", "));
+        http_app = http_app.layer(from_fn_with_state(
+            mockforge_http::middleware::CoalescingState::new(coalescing),
+            mockforge_http::middleware::coalescing_middleware,
+        ));
+    }
+
+    // Named scenario presets, switched at runtime through
+    // POST /__mockforge/api/scenario/{name}
+    if !config.http.scenario_presets.is_empty() {
+        use axum::middleware::from_fn_with_state;
+        use mockforge_http::scenario_preset_runtime::{
+            scenario_preset_api_router, scenario_preset_middleware, ScenarioPresetState,
+        };
+        let state = ScenarioPresetState::new(config.http.scenario_presets.clone());
+        println!("✅ Scenario presets: {}", state.names().join(", "));
+        http_app = http_app
+            .layer(from_fn_with_state(state.clone(), scenario_preset_middleware))
+            .nest("/__mockforge/api/scenario", scenario_preset_api_router(state));
+    }
+
+    // Serve a static directory (with SPA history fallback) for GETs the mock
+    // routes leave unanswered, so API routes always take precedence.
+    if let Some(static_site) = config.http.static_site.clone().filter(|s| s.enabled) {
+        use axum::middleware::from_fn_with_state;
+        println!(
+            "✅ Serving static site from {} (SPA fallback: {})",
+            static_site.dir, static_site.spa_fallback
+        );
+        http_app = http_app.layer(from_fn_with_state(
+            mockforge_http::middleware::StaticSiteState::new(static_site),
+            mockforge_http::middleware::static_site_middleware,
+        ));
+    }
+
+    // Answer request bodies that fail to parse as JSON with the configured
+    // `http.malformed_json_response` instead of the generic 400
+    if let Some(malformed_json) = config.http.malformed_json_response.clone() {
+        use axum::middleware::from_fn_with_state;
+        println!("✅ Custom malformed JSON response: {}", malformed_json.status);
+        http_app = http_app.layer(from_fn_with_state(
+            mockforge_http::middleware::MalformedJsonState::new(malformed_json),
+            mockforge_http::middleware::malformed_json_middleware,
+        ));
+    }
+
+    // Add the global `http.response_headers` to every response. Layered
+    // outside the static site so served files get them too.
+    if let Some(response_headers) = config.http.response_headers.clone() {
+        use axum::middleware::from_fn_with_state;
+        println!("✅ Global response headers: {}", response_headers.headers.len());
+        http_app = http_app.layer(from_fn_with_state(
+            mockforge_http::middleware::ResponseHeadersState::new(response_headers),
+            mockforge_http::middleware::response_headers_middleware,
+        ));
+    }
+
+    // Compress responses per `performance.compression`; routes setting
+    // `response.compress` override it either way.
+    let compression = &config.performance.compression;
+    if compression.enabled || config.routes.iter().any(|r| r.response.compress == Some(true)) {
+        http_app = http_app.layer(mockforge_http::middleware::compression_layer(compression));
+        if compression.enabled {
+            println!(
+                "✅ Response compression: {} above {} bytes",
+                compression.algorithm, compression.min_size
+            );
+        }
+    }
+
+    // Strict path mode: 404 unrouted requests with trailing or repeated
+    // slashes rather than letting lenient fallbacks (dynamic mocks, fixtures)
+    // answer them. Must wrap the router itself to see which requests routed.
+    if config.http.strict_paths {
+        http_app = http_app.layer(axum::middleware::from_fn(
+            mockforge_http::middleware::strict_path_middleware,
+        ));
+        println!("✅ Strict path matching enabled");
+    }
+
+    // Reject requests whose headers exceed `http.max_header_bytes` with 431.
+    // Outermost so oversized requests never reach the rest of the stack.
+    if let Some(max_header_bytes) = config.http.max_header_bytes {
+        use axum::middleware::from_fn_with_state;
+        http_app = http_app.layer(from_fn_with_state(
+            max_header_bytes,
+            mockforge_http::middleware::max_header_size_middleware,
+        ));
+        println!("✅ Request header size limit: {} bytes", max_header_bytes);
+    }
+
+    // Rewrite request paths with `http.rewrites` before anything routes them
+    if !config.http.rewrites.is_empty() {
+        println!("✅ Path rewrite rules: {}", config.http.rewrites.len());
+        http_app =
+            mockforge_http::path_rewrite::with_path_rewrites(http_app, &config.http.rewrites)
+                .map_err(|e| format!("Invalid path rewrite pattern: {}", e))?;
+    }
+
     // Note: OData URI rewrite is applied at the service level in serve_router_with_tls()
 
     println!(
@@ -2543,9 +2675,72 @@ pub async fn handle_serve(
     // are both cheap to clone.
     let http_app_clone_for_dual = http_app.clone();
     let chaos_listener_cfg_for_dual = chaos_listener_cfg.clone();
+    // Connect latency: every listener holds new connections for
+    // `http.accept_delay_ms` before serving them
+    let accept_delay =
+        std::time::Duration::from_millis(config.http.accept_delay_ms.unwrap_or_default());
+    if !accept_delay.is_zero() {
+        println!("✅ Connection accept delay: {}ms", accept_delay.as_millis());
+    }
+    // Connection pool simulation: each listener serves at most
+    // `http.connection_limit.max_connections` connections, queueing the next few
+    let connection_limit = config.http.connection_limit.clone();
+    if let Some(limit) = &connection_limit {
+        println!(
+            "✅ Connection limit: {} (queue {}, {}ms timeout)",
+            limit.max_connections, limit.queue_depth, limit.queue_timeout_ms
+        );
+    }
+    let https_connection_limit = connection_limit.clone();
+    // SSE/WebSocket caps live in the process-wide connection tracker, so all
+    // listeners count against the same `http.streaming_limit`
+    if let Some(limit) = &config.http.streaming_limit {
+        mockforge_http::connection_tracker::global_connections().set_limits(limit.clone());
+        let describe = |max: Option<usize>| max.map_or("unlimited".to_string(), |m| m.to_string());
+        println!(
+            "✅ Streaming connection limit: {} SSE, {} WebSocket",
+            describe(limit.max_sse),
+            describe(limit.max_websocket)
+        );
+    }
+    // Pipelining anomalies need their own accept loop, which only speaks
+    // plain HTTP/1.1; TLS listeners keep the regular server.
+    let http_pipelining = config
+        .http
+        .pipelining
+        .clone()
+        .filter(|p| p.enabled)
+        .filter(|_| plain_http_tls_config.as_ref().map(|t| !t.enabled).unwrap_or(true));
     let http_handle = tokio::spawn(async move {
+        let server = async move {
+            match http_pipelining {
+                Some(pipelining) => {
+                    mockforge_http::pipelining::serve_router_pipelined(
+                        http_port,
+                        http_app,
+                        pipelining,
+                        Some(http_bound_tx),
+                        accept_delay,
+                        connection_limit,
+                    )
+                    .await
+                }
+                None => {
+                    mockforge_http::serve_router_with_tls_notify_chaos(
+                        http_port,
+                        http_app,
+                        plain_http_tls_config,
+                        Some(http_bound_tx),
+                        chaos_listener_cfg,
+                        accept_delay,
+                        connection_limit,
+                    )
+                    .await
+                }
+            }
+        };
         tokio::select! {
-            result = mockforge_http::serve_router_with_tls_notify_chaos(http_port, http_app, plain_http_tls_config, Some(http_bound_tx), chaos_listener_cfg) => {
+            result = server => {
                 result.map_err(|e| format!("HTTP server error: {}", e))
             }
             _ = http_shutdown.cancelled() => {
@@ -2577,7 +2772,7 @@ pub async fn handle_serve(
         Some(tokio::spawn(async move {
             println!("🔒 HTTPS server listening on https://localhost:{}", https_port);
             tokio::select! {
-                result = mockforge_http::serve_router_with_tls_notify_chaos(https_port, https_app, https_tls, None, chaos_listener_cfg_https) => {
+                result = mockforge_http::serve_router_with_tls_notify_chaos(https_port, https_app, https_tls, None, chaos_listener_cfg_https, accept_delay, https_connection_limit) => {
                     result.map_err(|e| format!("HTTPS server error: {}", e))
                 }
                 _ = https_shutdown.cancelled() => {
@@ -3406,6 +3601,7 @@ fn fault_config_to_chaos(
             ConnectionErrorKindConfig::TcpReset => ConnectionErrorKind::TcpReset,
             ConnectionErrorKindConfig::TcpClose => ConnectionErrorKind::TcpClose,
         },
+        retry_after_secs: f.retry_after_secs,
         timeout_errors: f.timeout_errors,
         timeout_ms: f.timeout_ms,
         timeout_probability: f.timeout_probability,
@@ -3470,6 +3666,7 @@ mod chaos_yaml_bridge_tests {
             connection_errors: true,
             connection_error_probability: 0.05,
             connection_error_kind: ConnectionErrorKindConfig::TcpReset,
+            retry_after_secs: Some(5),
             timeout_errors: true,
             timeout_ms: 30000,
             timeout_probability: 0.1,
@@ -3499,6 +3696,7 @@ mod chaos_yaml_bridge_tests {
 
         assert!(bridged.enabled);
         assert_eq!(bridged.connection_error_kind, ConnectionErrorKind::TcpReset);
+        assert_eq!(bridged.retry_after_secs, Some(5));
         assert!(bridged.partial_responses);
         assert_eq!(bridged.partial_response_probability, 0.2);
         assert!(bridged.payload_corruption);
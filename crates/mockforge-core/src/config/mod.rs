@@ -366,10 +366,30 @@ pub async fn load_config<P: AsRef<Path>>(path: P) -> Result<ServerConfig> {
     // off the parsed struct alone (#927).
     let validation_explicit = raw_http_key_present(&content, "validation");
     reconcile_unknown_http_keys(&mut config, validation_explicit);
+    validate_loaded_config(&config)?;
 
     Ok(config)
 }
 
+/// Reject settings that parse but cannot be used: route latency
+/// distributions that cannot be sampled and rewrite patterns that are not
+/// valid regular expressions
+fn validate_loaded_config(config: &ServerConfig) -> Result<()> {
+    for route in &config.routes {
+        if let Some(latency) = &route.latency {
+            latency.validate().map_err(|e| {
+                Error::config(format!("Route {} {}: {}", route.method, route.path, e))
+            })?;
+        }
+    }
+    for rule in &config.http.rewrites {
+        rule.regex().map_err(|e| {
+            Error::config(format!("Invalid rewrite pattern '{}': {}", rule.pattern, e))
+        })?;
+    }
+    Ok(())
+}
+
 /// Best-effort check for whether `http.<key>` was literally present in the
 /// config source. Parses the raw text generically (YAML or JSON) so it works
 /// regardless of how the typed struct defaults the field.
@@ -537,6 +557,11 @@ pub fn apply_env_overrides(mut config: ServerConfig) -> ServerConfig {
         config.http.host = host;
     }
 
+    if let Ok(enabled) = std::env::var("MOCKFORGE_HTTP_SERVER_TIMING") {
+        config.http.server_timing =
+            matches!(enabled.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on");
+    }
+
     // WebSocket server overrides
     if let Ok(port) = std::env::var("MOCKFORGE_WS_PORT") {
         if let Ok(port_num) = port.parse() {
@@ -803,6 +828,20 @@ pub fn apply_env_overrides(mut config: ServerConfig) -> ServerConfig {
     config
 }
 
+/// Effective configuration of the running server, published at startup
+static EFFECTIVE_CONFIG: std::sync::RwLock<Option<ServerConfig>> = std::sync::RwLock::new(None);
+
+/// Publish the fully merged configuration the server is running with
+/// (called by the CLI once files, profiles, env vars and flags are applied)
+pub fn set_effective_config(config: ServerConfig) {
+    *EFFECTIVE_CONFIG.write().expect("effective config poisoned") = Some(config);
+}
+
+/// The configuration published with [`set_effective_config`], if any
+pub fn effective_config() -> Option<ServerConfig> {
+    EFFECTIVE_CONFIG.read().expect("effective config poisoned").clone()
+}
+
 /// Validate configuration
 pub fn validate_config(config: &ServerConfig) -> Result<()> {
     // Validate port ranges
@@ -899,6 +938,7 @@ pub async fn load_config_with_profile<P: AsRef<Path>>(
         if let Some(profile_config) = config.profiles.remove(profile) {
             tracing::info!("Applying profile: {}", profile);
             config = apply_profile(config, profile_config);
+            validate_loaded_config(&config)?;
         } else {
             return Err(Error::config(format!(
                 "Profile '{}' not found in configuration. Available profiles: {}",
@@ -958,8 +998,11 @@ pub async fn load_config_from_js<P: AsRef<Path>>(path: P) -> Result<ServerConfig
             .map_err(|e| Error::config(format!("Failed to get JSON string: {}", e)))?;
 
         // Parse JSON into ServerConfig
-        serde_json::from_str(&json_str)
-            .map_err(|e| Error::config(format!("Failed to parse JS config as ServerConfig: {}", e)))
+        let config: ServerConfig = serde_json::from_str(&json_str).map_err(|e| {
+            Error::config(format!("Failed to parse JS config as ServerConfig: {}", e))
+        })?;
+        validate_loaded_config(&config)?;
+        Ok(config)
     })
 }
 
@@ -1301,6 +1344,60 @@ const config: Config = {
         assert_eq!(config.http.port, 3000);
     }
 
+    /// `http.server_timing` is an ordinary config setting, off by default.
+    #[tokio::test]
+    async fn server_timing_is_read_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("mockforge.yaml");
+        std::fs::write(&config_path, "http:\n  port: 3000\n").unwrap();
+        assert!(!load_config(&config_path).await.expect("config loads").http.server_timing);
+
+        std::fs::write(&config_path, "http:\n  server_timing: true\n").unwrap();
+        assert!(load_config(&config_path).await.expect("config loads").http.server_timing);
+    }
+
+    /// Latency distributions that cannot be sampled fail the load instead of
+    /// saturating every delay at request time.
+    #[tokio::test]
+    async fn invalid_route_latency_distribution_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("mockforge.yaml");
+        let route = |shape: &str| {
+            format!(
+                "routes:\n  - path: /slow\n    method: GET\n    response:\n      status: 200\n    \
+                 latency:\n      enabled: true\n      probability: 1.0\n      jitter_percent: 0.0\n      \
+                 distribution: !pareto\n        scale_ms: 10.0\n        shape: {shape}\n"
+            )
+        };
+
+        std::fs::write(&config_path, route("1.5")).unwrap();
+        let config = load_config(&config_path).await.expect("config loads");
+        assert_eq!(config.routes[0].latency.as_ref().unwrap().max_delay_ms, 60_000);
+
+        std::fs::write(&config_path, route("0.0")).unwrap();
+        let err = load_config(&config_path).await.unwrap_err().to_string();
+        assert!(err.contains("GET /slow") && err.contains("shape"), "{err}");
+    }
+
+    /// Rewrite patterns that are not valid regexes fail the load instead of
+    /// being skipped at startup.
+    #[tokio::test]
+    async fn invalid_rewrite_pattern_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("mockforge.yaml");
+        let rewrite = |pattern: &str| {
+            format!("http:\n  rewrites:\n    - pattern: '{pattern}'\n      replacement: /$1\n")
+        };
+
+        std::fs::write(&config_path, rewrite(r"/api/v\d+/(.*)")).unwrap();
+        let config = load_config(&config_path).await.expect("config loads");
+        assert_eq!(config.http.rewrites.len(), 1);
+
+        std::fs::write(&config_path, rewrite("/api/(v1")).unwrap();
+        let err = load_config(&config_path).await.unwrap_err().to_string();
+        assert!(err.contains("/api/(v1"), "{err}");
+    }
+
     /// A path that resolves from the CWD keeps winning (backwards compatible).
     #[tokio::test]
     async fn openapi_spec_missing_path_is_left_alone_for_a_clear_error() {
@@ -58,6 +58,7 @@ impl DeceptiveDeployConfig {
                 requests_per_minute: 1000,
                 burst: 2000,
                 per_ip: true,
+                trust_forwarded_headers: false,
             }),
             headers,
             oauth: None, // Configured separately
@@ -95,6 +96,10 @@ pub struct ProductionRateLimitConfig {
     pub burst: u32,
     /// Enable per-IP rate limiting
     pub per_ip: bool,
+    /// Key per-IP limits on the leftmost `X-Forwarded-For` address; only
+    /// enable behind a proxy that sets it
+    #[serde(default)]
+    pub trust_forwarded_headers: bool,
 }
 
 /// Production-like OAuth configuration
@@ -1418,6 +1423,10 @@ pub struct FaultConfig {
     /// connection_errors is enabled and this is `tcp_reset` / `tcp_close`.
     #[serde(default)]
     pub connection_error_kind: ConnectionErrorKindConfig,
+    /// Seconds to advertise in a `Retry-After` header on injected 503s.
+    /// Unset sends no header.
+    #[serde(default)]
+    pub retry_after_secs: Option<u64>,
     /// Enable timeout errors.
     pub timeout_errors: bool,
     /// Timeout duration in milliseconds.
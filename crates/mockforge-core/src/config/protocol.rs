@@ -33,6 +33,11 @@ pub struct HttpCorsConfig {
     /// Note: Cannot be true when using wildcard origin (*)
     #[serde(default = "default_cors_allow_credentials")]
     pub allow_credentials: bool,
+    /// Per-origin credentials: origin => whether that origin may send
+    /// credentials. Origins not listed fall back to `allow_credentials`.
+    /// Ignored with a wildcard origin, which can never carry credentials
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub origin_credentials: HashMap<String, bool>,
 }
 
 fn default_cors_allow_credentials() -> bool {
@@ -75,6 +80,57 @@ pub struct HttpConfig {
     /// TLS/HTTPS configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tls: Option<HttpTlsConfig>,
+    /// Maximum total size of request headers in bytes; larger requests are
+    /// rejected with 431 Request Header Fields Too Large
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_header_bytes: Option<usize>,
+    /// Delay in milliseconds before each new connection is served, to
+    /// simulate a slow accept (connect latency)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_delay_ms: Option<u64>,
+    /// Cap on concurrently served connections, with a bounded queue for the
+    /// overflow (simulates a backend's connection pool)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection_limit: Option<HttpConnectionLimitConfig>,
+    /// Caps on concurrent SSE and WebSocket connections
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streaming_limit: Option<HttpStreamingLimitConfig>,
+    /// Mirror a copy of each request to a secondary upstream (shadow testing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mirror: Option<HttpMirrorConfig>,
+    /// Response-ordering anomalies for pipelined HTTP/1.1 requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipelining: Option<HttpPipeliningConfig>,
+    /// Serve concurrent identical requests from a single handler run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coalescing: Option<HttpCoalescingConfig>,
+    /// Serve a static directory (e.g. a built SPA) behind the mock routes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub static_site: Option<HttpStaticSiteConfig>,
+    /// Headers added to every response (e.g. `X-Mock: true`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_headers: Option<HttpResponseHeadersConfig>,
+    /// Response sent instead of the default 400 when a request body that
+    /// should be JSON fails to parse
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub malformed_json_response: Option<HttpMalformedJsonResponse>,
+    /// Answer 404 for requests that only match a route once trailing or
+    /// repeated slashes are ignored, instead of letting fallbacks serve them
+    pub strict_paths: bool,
+    /// Keep recent 5xx requests so they can be re-run with
+    /// `POST /__mockforge/api/replay/failed`
+    pub failed_replay: bool,
+    /// Add a `Server-Timing` header breaking each response's time down by
+    /// latency source (network profile, route chaos, handler)
+    pub server_timing: bool,
+    /// Regex path rewrites applied before routing, in order; the first
+    /// matching rule wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rewrites: Vec<HttpRewriteRule>,
+    /// Named bundles of latency, fault and maintenance settings, activated
+    /// at runtime with `POST /__mockforge/api/scenario/{name}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scenario_presets: HashMap<String, HttpScenarioPresetConfig>,
 
     /// Keys under `http:` that MockForge does not recognise.
     ///
@@ -108,6 +164,7 @@ impl Default for HttpConfig {
                 ],
                 allowed_headers: vec!["content-type".to_string(), "authorization".to_string()],
                 allow_credentials: false, // Must be false when using wildcard origin
+                origin_credentials: HashMap::new(),
             }),
             request_timeout_secs: 30,
             validation: Some(HttpValidationConfig {
@@ -121,11 +178,352 @@ impl Default for HttpConfig {
             skip_admin_validation: true,
             auth: None,
             tls: None,
+            max_header_bytes: None,
+            accept_delay_ms: None,
+            connection_limit: None,
+            streaming_limit: None,
+            mirror: None,
+            pipelining: None,
+            coalescing: None,
+            static_site: None,
+            response_headers: None,
+            malformed_json_response: None,
+            strict_paths: false,
+            failed_replay: false,
+            server_timing: false,
+            rewrites: Vec::new(),
+            scenario_presets: HashMap::new(),
             unknown_keys: HashMap::new(),
         }
     }
 }
 
+/// Request mirroring configuration
+///
+/// Each sampled request is copied and sent to `target_url` in the background;
+/// the mirror's response is ignored and its failures never reach the client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpMirrorConfig {
+    /// Enable request mirroring
+    #[serde(default = "default_mirror_enabled")]
+    pub enabled: bool,
+    /// Base URL of the mirror; the request path and query are appended
+    pub target_url: String,
+    /// Fraction of requests to mirror (0.0-1.0)
+    #[serde(default = "default_mirror_sample_rate")]
+    pub sample_rate: f64,
+    /// Only forward these headers (case-insensitive); empty forwards all.
+    /// `Host` and hop-by-hop headers are never forwarded
+    #[serde(default)]
+    pub include_headers: Vec<String>,
+    /// Never forward these headers (case-insensitive), e.g. `authorization`
+    #[serde(default)]
+    pub exclude_headers: Vec<String>,
+    /// Timeout for each mirrored request in milliseconds
+    #[serde(default = "default_mirror_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_mirror_enabled() -> bool {
+    true
+}
+
+fn default_mirror_sample_rate() -> f64 {
+    1.0
+}
+
+fn default_mirror_timeout_ms() -> u64 {
+    5000
+}
+
+/// Request coalescing (single-flight) configuration
+///
+/// While a request is in flight, identical requests that arrive wait for it
+/// and receive a copy of its response instead of running the handler again,
+/// like a caching reverse proxy collapsing concurrent cache misses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpCoalescingConfig {
+    /// Enable request coalescing
+    #[serde(default = "default_coalescing_enabled")]
+    pub enabled: bool,
+    /// Methods eligible for coalescing (case-insensitive)
+    #[serde(default = "default_coalescing_methods")]
+    pub methods: Vec<String>,
+    /// Request headers that are part of the fingerprint, in addition to the
+    /// method, path, query and body (case-insensitive)
+    #[serde(default)]
+    pub key_headers: Vec<String>,
+}
+
+fn default_coalescing_enabled() -> bool {
+    true
+}
+
+fn default_coalescing_methods() -> Vec<String> {
+    vec!["GET".to_string(), "HEAD".to_string()]
+}
+
+/// Connection limit for the HTTP listeners
+///
+/// At most `max_connections` connections are served at once. Up to
+/// `queue_depth` more wait for a free slot; a queued connection that gets none
+/// within `queue_timeout_ms`, or any connection beyond the queue, is answered
+/// with 503 Service Unavailable and closed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpConnectionLimitConfig {
+    /// Connections served at the same time
+    pub max_connections: usize,
+    /// Connections allowed to wait for a free slot
+    #[serde(default)]
+    pub queue_depth: usize,
+    /// How long a queued connection waits before it is rejected
+    #[serde(default = "default_connection_queue_timeout_ms")]
+    pub queue_timeout_ms: u64,
+}
+
+fn default_connection_queue_timeout_ms() -> u64 {
+    30_000
+}
+
+/// Caps on concurrent streaming connections
+///
+/// Once `max_sse` Server-Sent Events streams are open, further SSE clients
+/// are answered with 503 Service Unavailable. Once `max_websocket` sockets
+/// are open, further WebSocket clients are refused the upgrade with 503.
+/// Unset means unlimited.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpStreamingLimitConfig {
+    /// SSE streams open at the same time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sse: Option<usize>,
+    /// WebSocket connections open at the same time
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_websocket: Option<usize>,
+}
+
+/// A pre-routing path rewrite
+///
+/// `pattern` must match the whole request path; the path is replaced by
+/// `replacement`, where `$1`, `$2`, ... (or `${name}`) insert capture groups.
+/// The query string is kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpRewriteRule {
+    /// Regular expression matched against the path, e.g. `/api/v(\d+)/(.*)`
+    pub pattern: String,
+    /// Replacement path, e.g. `/$2`
+    pub replacement: String,
+}
+
+impl HttpRewriteRule {
+    /// Compile `pattern`, anchored so it has to match the whole path
+    ///
+    /// # Errors
+    /// Returns the parse error when `pattern` is not a valid regular expression.
+    pub fn regex(&self) -> Result<regex::Regex, regex::Error> {
+        regex::Regex::new(&format!("^(?:{})$", self.pattern))
+    }
+}
+
+/// A named scenario preset, e.g. "database down" or "high latency"
+///
+/// Activating a preset replaces the previously active one; a preset with
+/// nothing set (e.g. "all healthy") turns every effect off. Effects never
+/// apply to `/__mockforge` paths.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpScenarioPresetConfig {
+    /// Latency added to every request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<HttpScenarioLatencyConfig>,
+    /// Failures for matching routes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub faults: Vec<HttpScenarioFaultConfig>,
+    /// Answer every request with a maintenance response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<HttpScenarioMaintenanceConfig>,
+}
+
+/// Latency applied while a scenario preset is active
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpScenarioLatencyConfig {
+    /// Base delay in milliseconds
+    pub base_ms: u64,
+    /// Random jitter (±) in milliseconds
+    #[serde(default)]
+    pub jitter_ms: u64,
+}
+
+/// A route failure applied while a scenario preset is active
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpScenarioFaultConfig {
+    /// Path pattern; `{name}` matches one segment and a trailing `*` any rest,
+    /// e.g. `/api/orders/{id}` or `/api/orders/*`
+    pub path: String,
+    /// Only fail this method (case-insensitive); any method when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Status of the failure response
+    #[serde(default = "default_scenario_fault_status")]
+    pub status: u16,
+    /// Fraction of matching requests that fail (0.0-1.0)
+    #[serde(default = "default_scenario_fault_probability")]
+    pub probability: f64,
+    /// Body of the failure response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+fn default_scenario_fault_status() -> u16 {
+    503
+}
+
+fn default_scenario_fault_probability() -> f64 {
+    1.0
+}
+
+/// Maintenance mode applied while a scenario preset is active
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpScenarioMaintenanceConfig {
+    /// Message in the 503 response body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Seconds advertised in the `Retry-After` header
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+/// Global response header configuration
+///
+/// Unlike the deceptive-deploy production headers, these are plain mock
+/// settings: every response gets `headers`, whichever route served it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpResponseHeadersConfig {
+    /// Header names and values to add
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Replace headers the route already set; by default a route's own
+    /// header wins
+    #[serde(default)]
+    pub override_existing: bool,
+}
+
+/// Response for request bodies that are not valid JSON
+///
+/// Replaces the default `400 {"error": "request validation failed", ...}`
+/// when a route validating its body as JSON cannot parse it. `body` is
+/// template-expanded with the request tokens (`{{request.path}}`,
+/// `{{request.headers.<name>}}`, ...) and `{{error}}`, the parser's message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpMalformedJsonResponse {
+    /// Response status
+    #[serde(default = "default_malformed_json_status")]
+    pub status: u16,
+    /// Response headers
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Response body template
+    pub body: serde_json::Value,
+}
+
+fn default_malformed_json_status() -> u16 {
+    400
+}
+
+/// Static site configuration
+///
+/// Requests that no mock route answers are served from `dir`. With
+/// `spa_fallback`, GETs for client-side routes (paths without a file
+/// extension outside `api_prefixes`) that match no file get `index` instead,
+/// so deep links into a single-page app load the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpStaticSiteConfig {
+    /// Enable static file serving
+    #[serde(default = "default_static_site_enabled")]
+    pub enabled: bool,
+    /// Directory to serve
+    pub dir: String,
+    /// Serve `index` for unmatched client-side routes
+    #[serde(default = "default_static_site_spa_fallback")]
+    pub spa_fallback: bool,
+    /// Entry document, relative to `dir`
+    #[serde(default = "default_static_site_index")]
+    pub index: String,
+    /// Path prefixes that never fall back to `index`; unmatched requests
+    /// under them keep their 404
+    #[serde(default = "default_static_site_api_prefixes")]
+    pub api_prefixes: Vec<String>,
+}
+
+fn default_static_site_enabled() -> bool {
+    true
+}
+
+fn default_static_site_spa_fallback() -> bool {
+    true
+}
+
+fn default_static_site_index() -> String {
+    "index.html".to_string()
+}
+
+fn default_static_site_api_prefixes() -> Vec<String> {
+    vec!["/api".to_string(), "/__mockforge".to_string()]
+}
+
+/// Response-ordering anomalies for pipelined HTTP/1.1 requests
+///
+/// When enabled, plain-HTTP connections are served by a pipelining-aware
+/// accept loop: every complete request already received on a connection is
+/// handled concurrently, and the responses are released per `mode`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct HttpPipeliningConfig {
+    /// Enable the pipelining anomalies
+    #[serde(default = "default_pipelining_enabled")]
+    pub enabled: bool,
+    /// Which anomaly to introduce
+    #[serde(default)]
+    pub mode: HttpPipeliningMode,
+    /// How long `delay_first` holds back the first request of a batch, in
+    /// milliseconds
+    #[serde(default = "default_pipelining_delay_ms")]
+    pub delay_ms: u64,
+}
+
+/// Ordering anomaly applied to a batch of pipelined requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum HttpPipeliningMode {
+    /// Start handling the first request `delay_ms` late, so later requests
+    /// complete first; responses are still written in request order, as
+    /// HTTP/1.1 requires
+    #[default]
+    DelayFirst,
+    /// Write the batch's responses in reverse order. This violates HTTP/1.1
+    /// and exercises how clients cope with mismatched responses
+    Reverse,
+}
+
+fn default_pipelining_enabled() -> bool {
+    true
+}
+
+fn default_pipelining_delay_ms() -> u64 {
+    500
+}
+
 /// HTTP TLS/HTTPS configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -282,6 +680,11 @@ pub struct GrpcOverrideResponse {
     /// message type from the proto. Ignored when `status` is non-OK.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub body: Option<serde_json::Value>,
+    /// Extra trailing metadata sent alongside `grpc-status`/`grpc-message`,
+    /// e.g. `retry-after-ms: "500"`. `grpc-status` and `grpc-message` cannot
+    /// be overridden here.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub trailers: HashMap<String, String>,
 }
 
 /// GraphQL server configuration
@@ -1,5 +1,6 @@
 //! Route configuration types
 
+use super::RequestMatcherConfig;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -24,11 +25,23 @@ pub struct RouteConfig {
 }
 
 /// Request configuration for routes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RouteRequestConfig {
     /// Request validation configuration
     pub validation: Option<RouteValidationConfig>,
+    /// Query parameters (name → value) a request must carry for the route's
+    /// fault injection and latency to apply
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub query: HashMap<String, String>,
+    /// Headers (case-insensitive name → value) a request must carry for the
+    /// route's fault injection and latency to apply
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Regex the raw, undecoded query string (without `?`) must match for
+    /// the route's fault injection and latency to apply
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_query_regex: Option<String>,
 }
 
 /// Response configuration for routes
@@ -42,14 +55,395 @@ pub struct RouteResponseConfig {
     pub headers: HashMap<String, String>,
     /// Response body
     pub body: Option<serde_json::Value>,
+    /// Localized response bodies keyed by language tag (e.g. `fr`, `en-US`),
+    /// chosen by the request's `Accept-Language`. `body` is the fallback when
+    /// no tag is acceptable.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub localized: HashMap<String, serde_json::Value>,
+    /// Alternative responses to cycle through; when non-empty, each request
+    /// is served one variant picked by `selection`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<RouteResponseVariant>,
+    /// How a variant is picked for each request
+    #[serde(default)]
+    pub selection: RouteResponseSelection,
+    /// Request attributes hashed by `hash` selection: `method`, `path`,
+    /// `query.<name>`, `header.<name>` or `body.<field>` (dot-separated for
+    /// nested fields). Defaults to `path`. With `sequence` or `round_robin`
+    /// selection, requests with the same values for these attributes form a
+    /// session that steps through the variants on its own; without any, all
+    /// requests share one sequence.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hash_on: Vec<String>,
+    /// Stream the body incrementally instead of sending it in one piece
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stream: Option<RouteStreamConfig>,
+    /// Stamp a generated id derived from a request key onto the body, so
+    /// repeated requests with the same key see the same id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consistent_id: Option<RouteConsistentIdConfig>,
+    /// Announce the route as deprecated via `Deprecation`, `Sunset` and
+    /// `Link` headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<RouteDeprecationConfig>,
+    /// Serve the normal response to the first `limit` requests of each
+    /// window and the `quota` response to the rest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<RouteQuotaConfig>,
+    /// Protobuf message types for `application/x-protobuf` request and
+    /// response bodies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protobuf: Option<RouteProtobufConfig>,
+    /// Deterministically alternate between the normal response and a
+    /// failure response across requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flaky: Option<RouteFlakyConfig>,
+    /// Delay computed per request from an arithmetic expression over
+    /// request values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay: Option<RouteDelayConfig>,
+    /// Fail a growing fraction of requests, ramping up to a target error
+    /// rate after the route's first request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_ramp: Option<RouteErrorRampConfig>,
+    /// Compress (`true`) or never compress (`false`) this route's responses,
+    /// overriding `performance.compression`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compress: Option<bool>,
+    /// Reason phrase sent in the HTTP/1.1 status line instead of the
+    /// standard one (e.g. `Totally Fine` for `200 Totally Fine`). Templated
+    /// like the body; HTTP/2 has no reason phrases, so it is dropped there
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason_phrase: Option<String>,
+}
+
+impl Default for RouteResponseConfig {
+    fn default() -> Self {
+        Self {
+            status: 200,
+            headers: HashMap::new(),
+            body: None,
+            localized: HashMap::new(),
+            variants: Vec::new(),
+            selection: RouteResponseSelection::default(),
+            hash_on: Vec::new(),
+            stream: None,
+            consistent_id: None,
+            deprecation: None,
+            quota: None,
+            protobuf: None,
+            flaky: None,
+            delay: None,
+            error_ramp: None,
+            compress: None,
+            reason_phrase: None,
+        }
+    }
+}
+
+/// Request-dependent delay for a route
+///
+/// `expression` combines numbers and request values with `+ - * /` and
+/// parentheses, e.g. `body.size_kb * 10` or `50 + query.page_size * 2`.
+/// Values are `query.<name>`, `header.<name>`, `body.<field>` (dot-separated
+/// for nested fields), `body.size_bytes` and `body.size_kb`; missing or
+/// non-numeric values count as 0. The result is clamped to `0..=max_ms`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteDelayConfig {
+    /// Delay in milliseconds, as an expression over request values
+    pub expression: String,
+    /// Upper bound on the computed delay in milliseconds
+    #[serde(default = "default_delay_max_ms")]
+    pub max_ms: u64,
+}
+
+fn default_delay_max_ms() -> u64 {
+    10_000
+}
+
+/// Deterministic success/failure alternation for a route
+///
+/// With `failure_pattern`, request *n* fails when entry `n % len` is `true`
+/// (`[false, true]` fails every other call). Otherwise every `every_nth`
+/// request fails, starting with request `every_nth`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteFlakyConfig {
+    /// Repeating pattern of outcomes; `true` serves the failure response
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failure_pattern: Vec<bool>,
+    /// Fail every n-th request (used when `failure_pattern` is empty)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub every_nth: Option<u64>,
+    /// Status of the failure response
+    #[serde(default = "default_flaky_status")]
+    pub status: u16,
+    /// Headers of the failure response
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Body of the failure response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+fn default_flaky_status() -> u16 {
+    500
+}
+
+/// Gradually rising error rate for a route
+///
+/// The ramp starts at the route's first request: the failure rate grows
+/// linearly from 0 to `target_error_rate` over `ramp_secs` seconds, then
+/// holds. Failures are spread evenly across requests at the current rate
+/// rather than drawn at random, so the observed fraction tracks the ramp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteErrorRampConfig {
+    /// Fraction of requests (0.0 to 1.0) that fail once the ramp completes
+    pub target_error_rate: f64,
+    /// Seconds from the first request until `target_error_rate` is reached
+    #[serde(default)]
+    pub ramp_secs: u64,
+    /// Status of the failure response
+    #[serde(default = "default_flaky_status")]
+    pub status: u16,
+    /// Headers of the failure response
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Body of the failure response
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+/// Protobuf bodies for a non-gRPC route
+///
+/// Requests sent as `application/x-protobuf` are decoded with
+/// `request_message` and exposed to templates as `request.body`; the
+/// response body is written as JSON in config and encoded with
+/// `response_message`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteProtobufConfig {
+    /// Path to a compiled `FileDescriptorSet` (`protoc --descriptor_set_out`)
+    pub descriptor_set: String,
+    /// Fully-qualified request message name, e.g. `shop.v1.CreateOrder`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_message: Option<String>,
+    /// Fully-qualified response message name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_message: Option<String>,
+}
+
+/// Windowed request counter for a route, e.g. "5 requests per minute"
+///
+/// The window opens with the first request and resets once `window_ms` has
+/// passed. This is a response behavior, separate from the rate limiter.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteQuotaConfig {
+    /// Requests served normally per window
+    pub limit: u64,
+    /// Window length in milliseconds
+    pub window_ms: u64,
+    /// Status for requests over the limit
+    #[serde(default = "default_quota_status")]
+    pub status: u16,
+    /// Headers for requests over the limit (`Retry-After` is added unless set)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Body for requests over the limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+fn default_quota_status() -> u16 {
+    429
+}
+
+/// Deprecation headers for a route (RFC 9745 / RFC 8594)
+///
+/// Dates accept templates such as `{{now+30d}}` and may be RFC 3339,
+/// HTTP-dates or plain `YYYY-MM-DD`; they are rendered in each header's
+/// wire format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteDeprecationConfig {
+    /// When the route was (or will be) deprecated, sent as `Deprecation`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// When the route stops responding, sent as `Sunset`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
+    /// Documentation URL, sent as `Link: <url>; rel="deprecation"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+/// Stable generated ids for a route, keyed by part of the request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteConsistentIdConfig {
+    /// Template rendering the request key, e.g. `{{request.query.email}}`
+    pub key: String,
+    /// Top-level body field the id is written to
+    #[serde(default = "default_consistent_id_field")]
+    pub field: String,
+    /// Entity type the id is registered under in the consistency engine
+    /// (defaults to the route path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity_type: Option<String>,
+}
+
+fn default_consistent_id_field() -> String {
+    "id".to_string()
+}
+
+/// Incremental streaming of a route's response body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteStreamConfig {
+    /// Wire format of the stream
+    #[serde(default)]
+    pub format: RouteStreamFormat,
+    /// Delay in milliseconds between consecutive pieces
+    #[serde(default)]
+    pub delay_ms: u64,
+    /// Per-piece delays in milliseconds: entry `i` is the pause before piece
+    /// `i` (so the first entry delays the first byte). Pieces past the end
+    /// of the list fall back to `delay_ms`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delays_ms: Vec<u64>,
+}
+
+/// Wire format for streamed route responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RouteStreamFormat {
+    /// Newline-delimited JSON (`application/x-ndjson`): each element of an
+    /// array body is sent as one line
+    #[default]
+    Ndjson,
+    /// Raw chunks: each element of an array body is written as one chunk,
+    /// strings verbatim and other values as JSON. The Content-Type defaults
+    /// to `application/octet-stream`; set another in the route's `headers`
+    Chunks,
+}
+
+/// One alternative response for a route with `variants`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteResponseVariant {
+    /// HTTP status code (defaults to the route's `status`)
+    #[serde(default)]
+    pub status: Option<u16>,
+    /// Headers added on top of the route's `headers`
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Response body (defaults to the route's `body`)
+    pub body: Option<serde_json::Value>,
+    /// Relative weight for `weighted` selection
+    #[serde(default = "default_variant_weight")]
+    pub weight: f64,
+    /// Only serve this variant while the current time of day falls inside
+    /// this window; variants without a window are the fallback outside it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_window: Option<RouteTimeWindow>,
+    /// Only serve this variant when every one of these flags is listed in
+    /// the request's `X-Feature-Flags` header (comma-separated); variants
+    /// without flags are the fallback when no flagged variant matches
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feature_flags: Vec<String>,
+    /// Only serve this variant to `multipart/form-data` requests matching
+    /// these field values and file uploads; variants without conditions are
+    /// the fallback when no multipart variant matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multipart: Option<RouteMultipartMatch>,
+    /// Only serve this variant when this regex matches the raw, undecoded
+    /// query string (without `?`), e.g. `filter\[name\]=x`; variants without
+    /// one are the fallback when no such variant matches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_query_regex: Option<String>,
+}
+
+/// Multipart form conditions gating a response variant
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteMultipartMatch {
+    /// Text fields that must be present with exactly these values
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    /// File parts that must be uploaded, keyed by field name
+    #[serde(default)]
+    pub files: HashMap<String, RouteMultipartFileMatch>,
+}
+
+/// Conditions on one uploaded file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteMultipartFileMatch {
+    /// Required Content-Type of the file part (case-insensitive); `image/*`
+    /// matches any subtype. Any type when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+/// Time-of-day window (e.g. business hours) gating a response variant
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteTimeWindow {
+    /// Window start as `HH:MM` (inclusive)
+    pub start: String,
+    /// Window end as `HH:MM` (exclusive); an end before the start wraps
+    /// past midnight
+    pub end: String,
+    /// IANA timezone the times are expressed in (e.g. `Europe/Berlin`)
+    #[serde(default = "default_time_window_timezone")]
+    pub timezone: String,
+}
+
+fn default_time_window_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_variant_weight() -> f64 {
+    1.0
+}
+
+/// Strategy for picking one of a route's response variants
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RouteResponseSelection {
+    /// Cycle through the variants in order
+    #[default]
+    RoundRobin,
+    /// Random pick proportional to each variant's `weight`
+    Weighted,
+    /// Uniform random pick
+    Random,
+    /// Stable pick from a hash of the request attributes in `hash_on`, so
+    /// identical requests always get the same variant
+    Hash,
+    /// Serve the variants in order, one per request, then keep serving the
+    /// last one (a scripted sequence, e.g. polling until a job is done)
+    Sequence,
 }
 
 /// Validation configuration for routes
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct RouteValidationConfig {
-    /// JSON schema for request validation
-    pub schema: serde_json::Value,
+    /// JSON schema for request validation (bodies are parsed as JSON)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+    /// Schemas keyed by request media type (e.g. `application/json`,
+    /// `application/x-www-form-urlencoded`, `text/*`). When non-empty, the
+    /// schema matching the request's `Content-Type` is used and other media
+    /// types are rejected with 415
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub content_types: HashMap<String, serde_json::Value>,
 }
 
 /// Per-route fault injection configuration
@@ -62,6 +456,16 @@ pub struct RouteFaultInjectionConfig {
     pub probability: f64,
     /// Fault types to inject
     pub fault_types: Vec<RouteFaultType>,
+    /// Relative weight of each entry in `fault_types` when picking the fault
+    /// to inject; must have one entry per fault type. Without weights, every
+    /// fault type is equally likely
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<f64>>,
+    /// Only inject faults into requests that match (client address,
+    /// headers, body size, chunked encoding); other requests get the normal
+    /// response. Same shape as the global `fault_injection.request_matcher`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_matcher: Option<RequestMatcherConfig>,
 }
 
 /// Fault types that can be injected per route
@@ -80,6 +484,9 @@ pub enum RouteFaultType {
     ConnectionError {
         /// Optional error message
         message: Option<String>,
+        /// Seconds advertised in the `Retry-After` header of the 503
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry_after_secs: Option<u64>,
     },
     /// Timeout error
     Timeout {
@@ -98,6 +505,16 @@ pub enum RouteFaultType {
         /// Type of corruption to apply
         corruption_type: String,
     },
+    /// Circuit breaker: after `trip_after` matching requests, every request
+    /// fails with a 503 for `open_for_ms`, then counting starts over.
+    /// Evaluated on every matching request rather than rolled by
+    /// `probability`.
+    CircuitBreaker {
+        /// Matching requests let through before the breaker opens
+        trip_after: u32,
+        /// How long the breaker stays open, in milliseconds
+        open_for_ms: u64,
+    },
 }
 
 /// Per-route latency configuration
@@ -117,6 +534,72 @@ pub struct RouteLatencyConfig {
     /// Latency distribution type
     #[serde(default)]
     pub distribution: LatencyDistribution,
+    /// Spread the response body over this many milliseconds. The delays
+    /// above hold back the first byte; this one dribbles out the rest, so
+    /// headers arrive promptly while the body transfers slowly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_transfer_ms: Option<u64>,
+    /// Simulate a cache in front of the route: the delay above only applies
+    /// on a miss, and repeat requests within the TTL are served as hits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache: Option<RouteCacheLatencyConfig>,
+    /// Upper bound in milliseconds on delays sampled from `distribution`, so
+    /// heavy-tailed draws stay within a usable range
+    #[serde(default = "default_latency_max_delay_ms")]
+    pub max_delay_ms: u64,
+}
+
+fn default_latency_max_delay_ms() -> u64 {
+    60_000
+}
+
+impl RouteLatencyConfig {
+    /// Check that the distribution's parameters describe a distribution that
+    /// can be sampled
+    ///
+    /// # Errors
+    /// Returns a description of the first invalid parameter.
+    pub fn validate(&self) -> Result<(), String> {
+        match self.distribution {
+            LatencyDistribution::LogNormal { mu, sigma } => {
+                if !mu.is_finite() {
+                    return Err(format!("log-normal mu must be finite, got {mu}"));
+                }
+                if !sigma.is_finite() || sigma < 0.0 {
+                    return Err(format!(
+                        "log-normal sigma must be finite and non-negative, got {sigma}"
+                    ));
+                }
+            }
+            LatencyDistribution::Pareto { scale_ms, shape } => {
+                if !scale_ms.is_finite() || scale_ms <= 0.0 {
+                    return Err(format!(
+                        "Pareto scale_ms must be finite and positive, got {scale_ms}"
+                    ));
+                }
+                if !shape.is_finite() || shape <= 0.0 {
+                    return Err(format!("Pareto shape must be finite and positive, got {shape}"));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Cache-miss latency simulation for a route
+///
+/// Requests are keyed by method, path and query string. The first request
+/// for a key is a miss and pays the route's configured delay; the key then
+/// stays warm for `ttl_ms`, during which requests for it get `hit_delay_ms`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RouteCacheLatencyConfig {
+    /// How long a key stays warm after the miss that cached it, in milliseconds
+    pub ttl_ms: u64,
+    /// Delay for cache hits in milliseconds
+    #[serde(default)]
+    pub hit_delay_ms: u64,
 }
 
 /// Latency distribution type
@@ -142,6 +625,21 @@ pub enum LatencyDistribution {
     },
     /// Uniform distribution (uses random_delay_range_ms)
     Uniform,
+    /// Log-normal distribution: the delay's natural log is normally
+    /// distributed with mean `mu` and standard deviation `sigma`
+    LogNormal {
+        /// Mean of the delay's natural log (ln ms)
+        mu: f64,
+        /// Standard deviation of the delay's natural log
+        sigma: f64,
+    },
+    /// Pareto distribution for heavy-tailed delays (requires scale and shape)
+    Pareto {
+        /// Minimum delay in milliseconds
+        scale_ms: f64,
+        /// Shape (tail index); smaller values give heavier tails
+        shape: f64,
+    },
 }
 
 impl Default for RouteFaultInjectionConfig {
@@ -150,6 +648,7 @@ impl Default for RouteFaultInjectionConfig {
             enabled: false,
             probability: 0.0,
             fault_types: Vec::new(),
+            weights: None,
         }
     }
 }
@@ -163,6 +662,9 @@ impl Default for RouteLatencyConfig {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: default_latency_max_delay_ms(),
         }
     }
 }
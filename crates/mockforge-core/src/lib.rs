@@ -629,8 +629,9 @@ pub use traffic_shaping::{BandwidthConfig, BurstLossConfig, TrafficShaper, Traff
 pub use uuid::Uuid;
 pub use validation::{validate_openapi_operation_security, validate_openapi_security, Validator};
 pub use verification::{
-    matches_verification_pattern, verify_at_least, verify_never, verify_requests, verify_sequence,
-    VerificationCount, VerificationRequest, VerificationResult,
+    matches_verification_pattern, verify_at_least, verify_batch, verify_never, verify_requests,
+    verify_sequence, BatchVerificationItem, BatchVerificationResult, VerificationCount,
+    VerificationRequest, VerificationResult,
 };
 #[cfg(feature = "voice")]
 pub use voice::{
@@ -700,8 +701,9 @@ pub mod validate {
         validate_openapi_operation_security, validate_openapi_security, Validator,
     };
     pub use crate::verification::{
-        matches_verification_pattern, verify_at_least, verify_never, verify_requests,
-        verify_sequence, VerificationCount, VerificationRequest, VerificationResult,
+        matches_verification_pattern, verify_at_least, verify_batch, verify_never, verify_requests,
+        verify_sequence, BatchVerificationItem, BatchVerificationResult, VerificationCount,
+        VerificationRequest, VerificationResult,
     };
 }
 
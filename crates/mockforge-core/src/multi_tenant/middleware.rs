@@ -128,10 +128,14 @@ pub mod axum_middleware {
         // Store workspace context in request extensions
         request.extensions_mut().insert(context.clone());
 
-        // Update request URI with stripped path
+        // Update request URI with stripped path, keeping the query string
         if context.original_path != context.stripped_path {
+            let path_and_query = match request.uri().query() {
+                Some(query) => format!("{}?{}", context.stripped_path, query),
+                None => context.stripped_path.clone(),
+            };
             let mut parts = request.uri().clone().into_parts();
-            parts.path_and_query = context.stripped_path.parse().ok().or(parts.path_and_query);
+            parts.path_and_query = path_and_query.parse().ok().or(parts.path_and_query);
 
             if let Ok(uri) = ::axum::http::Uri::from_parts(parts) {
                 *request.uri_mut() = uri;
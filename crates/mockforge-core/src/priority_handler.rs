@@ -24,6 +24,8 @@ pub struct RouteFaultResponse {
     pub error_message: String,
     /// Fault type identifier
     pub fault_type: String,
+    /// Seconds to advertise in a `Retry-After` header, if any
+    pub retry_after_secs: Option<u64>,
 }
 
 /// Trait for route chaos injection (fault injection and latency)
@@ -36,6 +38,40 @@ pub trait RouteChaosInjectorTrait: Send + Sync {
 
     /// Get fault injection response for a request
     fn get_fault_response(&self, method: &Method, uri: &Uri) -> Option<RouteFaultResponse>;
+
+    /// Inject latency for this request, letting routes match on its headers
+    async fn inject_latency_with_headers(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        _headers: &HeaderMap,
+    ) -> Result<()> {
+        self.inject_latency(method, uri).await
+    }
+
+    /// Get fault injection response for a request, letting routes match on
+    /// its headers
+    fn get_fault_response_with_headers(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        _headers: &HeaderMap,
+    ) -> Option<RouteFaultResponse> {
+        self.get_fault_response(method, uri)
+    }
+
+    /// Get fault injection response for a request from `client` (the peer
+    /// address, when known), so faults restricted to some clients can skip
+    /// the others
+    fn get_fault_response_for_client(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+        _client: Option<std::net::IpAddr>,
+    ) -> Option<RouteFaultResponse> {
+        self.get_fault_response_with_headers(method, uri, headers)
+    }
 }
 
 /// Trait for behavioral scenario replay engines
@@ -415,18 +451,24 @@ impl PriorityHttpHandler {
         // 2.5. ROUTE CHAOS: Check for per-route fault injection and latency
         if let Some(ref route_chaos) = self.route_chaos_injector {
             // Inject latency first (before fault injection)
-            if let Err(e) = route_chaos.inject_latency(method, uri).await {
+            if let Err(e) = route_chaos.inject_latency_with_headers(method, uri, headers).await {
                 tracing::warn!("Failed to inject per-route latency: {}", e);
             }
 
             // Check for per-route fault injection
-            if let Some(fault_response) = route_chaos.get_fault_response(method, uri) {
+            if let Some(fault_response) =
+                route_chaos.get_fault_response_with_headers(method, uri, headers)
+            {
                 let error_response = serde_json::json!({
                     "error": fault_response.error_message,
                     "injected_failure": true,
                     "fault_type": fault_response.fault_type,
                     "timestamp": chrono::Utc::now().to_rfc3339()
                 });
+                let mut headers = HashMap::new();
+                if let Some(secs) = fault_response.retry_after_secs {
+                    headers.insert("retry-after".to_string(), secs.to_string());
+                }
 
                 return Ok(PriorityResponse {
                     source: ResponseSource::new(
@@ -436,7 +478,7 @@ impl PriorityHttpHandler {
                     .with_metadata("fault_type".to_string(), fault_response.fault_type)
                     .with_metadata("error_message".to_string(), fault_response.error_message),
                     status_code: fault_response.status_code,
-                    headers: HashMap::new(),
+                    headers,
                     body: serde_json::to_string(&error_response)?.into_bytes(),
                     content_type: "application/json".to_string(),
                 });
@@ -456,6 +498,10 @@ impl PriorityHttpHandler {
                     "injected_failure": true,
                     "timestamp": chrono::Utc::now().to_rfc3339()
                 });
+                let mut headers = HashMap::new();
+                if let Some(secs) = fault_response.retry_after_secs {
+                    headers.insert("retry-after".to_string(), secs.to_string());
+                }
 
                 return Ok(PriorityResponse {
                     source: ResponseSource::new(
@@ -1112,6 +1158,7 @@ mod tests {
                 status_code: 503,
                 error_message: "Service unavailable".to_string(),
                 fault_type: "test_fault".to_string(),
+                retry_after_secs: None,
             })
         }
     }
@@ -2190,6 +2237,7 @@ paths:
                         status_code: 503,
                         error_message: "Service unavailable".to_string(),
                         fault_type: "injected_fault".to_string(),
+                        retry_after_secs: None,
                     })
                 } else {
                     None
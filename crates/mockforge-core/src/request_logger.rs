@@ -3,8 +3,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{Notify, RwLock};
 
 /// Reality continuum type based on blend ratio
 ///
@@ -227,6 +229,11 @@ pub struct CentralizedRequestLogger {
     logs: Arc<RwLock<VecDeque<RequestLogEntry>>>,
     /// Maximum number of logs to keep in memory
     max_logs: usize,
+    /// Number of entries ever logged, used as the cursor for [`Self::logs_since`].
+    /// Only changed while `logs` is write-locked.
+    logged: Arc<AtomicU64>,
+    /// Wakes callers waiting for new entries
+    new_entry: Arc<Notify>,
 }
 
 impl Default for CentralizedRequestLogger {
@@ -241,6 +248,8 @@ impl CentralizedRequestLogger {
         Self {
             logs: Arc::new(RwLock::new(VecDeque::new())),
             max_logs,
+            logged: Arc::new(AtomicU64::new(0)),
+            new_entry: Arc::new(Notify::new()),
         }
     }
 
@@ -255,6 +264,44 @@ impl CentralizedRequestLogger {
         while logs.len() > self.max_logs {
             logs.pop_back();
         }
+        self.logged.fetch_add(1, Ordering::SeqCst);
+        drop(logs);
+        self.new_entry.notify_waiters();
+    }
+
+    /// Entries logged after `cursor`, oldest first, together with the cursor
+    /// of the latest entry to pass on the next call. Cursor `0` means "from
+    /// the start"; entries already evicted from the buffer are skipped.
+    pub async fn logs_since(&self, cursor: u64) -> (Vec<RequestLogEntry>, u64) {
+        let logs = self.logs.read().await;
+        let latest = self.logged.load(Ordering::SeqCst);
+        let count = latest.saturating_sub(cursor).min(logs.len() as u64) as usize;
+        (logs.iter().take(count).rev().cloned().collect(), latest)
+    }
+
+    /// Like [`Self::logs_since`], but waits up to `timeout` for an entry to be
+    /// logged when there is none after `cursor` yet
+    pub async fn wait_for_logs_since(
+        &self,
+        cursor: u64,
+        timeout: Duration,
+    ) -> (Vec<RequestLogEntry>, u64) {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            // Register for the wakeup before checking, so an entry logged in
+            // between is not missed
+            let notified = self.new_entry.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let (entries, latest) = self.logs_since(cursor).await;
+            if !entries.is_empty() {
+                return (entries, latest);
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return self.logs_since(cursor).await;
+            }
+        }
     }
 
     /// Get recent logs (most recent first)
@@ -264,6 +311,11 @@ impl CentralizedRequestLogger {
         logs.iter().take(take_count).cloned().collect()
     }
 
+    /// Get a single log entry by its request ID
+    pub async fn get_log(&self, id: &str) -> Option<RequestLogEntry> {
+        self.logs.read().await.iter().find(|log| log.id == id).cloned()
+    }
+
     /// Get logs filtered by server type
     pub async fn get_logs_by_server(
         &self,
@@ -616,6 +668,48 @@ mod tests {
         assert_eq!(logs.len(), 5); // Should only keep 5 most recent
     }
 
+    #[tokio::test]
+    async fn test_logs_since_cursor() {
+        let logger = CentralizedRequestLogger::new(3);
+        for i in 0..5 {
+            let mut entry = create_test_entry("HTTP", "GET");
+            entry.id = format!("entry-{}", i);
+            logger.log_request(entry).await;
+        }
+
+        let (entries, cursor) = logger.logs_since(3).await;
+        assert_eq!(cursor, 5);
+        let ids: Vec<_> = entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, ["entry-3", "entry-4"]);
+
+        // Evicted entries are skipped
+        let (entries, _) = logger.logs_since(0).await;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].id, "entry-2");
+
+        assert!(logger.logs_since(cursor).await.0.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_logs_since() {
+        let logger = CentralizedRequestLogger::new(10);
+        let (entries, cursor) = logger.wait_for_logs_since(0, Duration::from_millis(20)).await;
+        assert!(entries.is_empty());
+        assert_eq!(cursor, 0);
+
+        let waiter = {
+            let logger = logger.clone();
+            tokio::spawn(async move { logger.wait_for_logs_since(0, Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        logger.log_request(create_test_entry("HTTP", "POST")).await;
+
+        let (entries, cursor) = waiter.await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].method, "POST");
+        assert_eq!(cursor, 1);
+    }
+
     #[tokio::test]
     async fn test_get_recent_logs_with_limit() {
         let logger = CentralizedRequestLogger::new(100);
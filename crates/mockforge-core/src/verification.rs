@@ -126,6 +126,26 @@ impl VerificationResult {
     }
 }
 
+/// One expectation in a batch verification
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BatchVerificationItem {
+    /// Pattern to match requests
+    pub pattern: VerificationRequest,
+    /// Expected count assertion
+    pub expected: VerificationCount,
+}
+
+/// Outcome of a batch verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchVerificationResult {
+    /// Whether every expectation passed
+    pub matched: bool,
+    /// One result per expectation, in request order. `None` marks an
+    /// expectation skipped because an earlier one failed with short-circuiting
+    /// enabled.
+    pub results: Vec<Option<VerificationResult>>,
+}
+
 /// Check if a request log entry matches the verification pattern
 pub fn matches_verification_pattern(
     entry: &RequestLogEntry,
@@ -371,6 +391,30 @@ pub fn verify_sequence_entries(
     )
 }
 
+/// Batch verification against an explicit slice of log entries
+///
+/// With `short_circuit`, evaluation stops at the first failing expectation and
+/// the remaining results are `None`.
+pub fn verify_batch_entries(
+    entries: &[RequestLogEntry],
+    items: &[BatchVerificationItem],
+    short_circuit: bool,
+) -> BatchVerificationResult {
+    let mut matched = true;
+    let results = items
+        .iter()
+        .map(|item| {
+            if short_circuit && !matched {
+                return None;
+            }
+            let result = verify_entries(entries, &item.pattern, item.expected.clone());
+            matched &= result.matched;
+            Some(result)
+        })
+        .collect();
+    BatchVerificationResult { matched, results }
+}
+
 /// Verify requests against a pattern and count assertion
 pub async fn verify_requests(
     logger: &crate::request_logger::CentralizedRequestLogger,
@@ -398,6 +442,16 @@ pub async fn verify_at_least(
     verify_requests(logger, pattern, VerificationCount::AtLeast(min)).await
 }
 
+/// Verify several expectations against one snapshot of the request log
+pub async fn verify_batch(
+    logger: &crate::request_logger::CentralizedRequestLogger,
+    items: &[BatchVerificationItem],
+    short_circuit: bool,
+) -> BatchVerificationResult {
+    let logs = logger.get_recent_logs(None).await;
+    verify_batch_entries(&logs, items, short_circuit)
+}
+
 /// Verify that requests occurred in a specific sequence
 pub async fn verify_sequence(
     logger: &crate::request_logger::CentralizedRequestLogger,
@@ -491,6 +545,41 @@ mod tests {
         assert_eq!(result.count, 0);
     }
 
+    #[tokio::test]
+    async fn test_verify_batch() {
+        let logger = CentralizedRequestLogger::new(100);
+        logger.log_request(create_test_entry("POST", "/api/orders")).await;
+        logger.log_request(create_test_entry("GET", "/api/orders/1")).await;
+        logger.log_request(create_test_entry("GET", "/api/orders/1")).await;
+
+        let item = |method: &str, path: &str, expected| BatchVerificationItem {
+            pattern: VerificationRequest {
+                method: Some(method.to_string()),
+                path: Some(path.to_string()),
+                ..Default::default()
+            },
+            expected,
+        };
+        let items = vec![
+            item("POST", "/api/orders", VerificationCount::Exactly(1)),
+            item("DELETE", "/api/orders/1", VerificationCount::AtLeastOnce),
+            item("GET", "/api/orders/1", VerificationCount::AtLeast(2)),
+        ];
+
+        let batch = verify_batch(&logger, &items, false).await;
+        assert!(!batch.matched);
+        let outcomes: Vec<Option<(bool, usize)>> =
+            batch.results.iter().map(|r| r.as_ref().map(|r| (r.matched, r.count))).collect();
+        assert_eq!(outcomes, vec![Some((true, 1)), Some((false, 0)), Some((true, 2))]);
+
+        // Short-circuiting skips everything after the first failure
+        let batch = verify_batch(&logger, &items, true).await;
+        assert!(!batch.matched);
+        assert!(batch.results[0].as_ref().unwrap().matched);
+        assert!(!batch.results[1].as_ref().unwrap().matched);
+        assert!(batch.results[2].is_none());
+    }
+
     #[tokio::test]
     async fn test_verify_sequence() {
         let logger = CentralizedRequestLogger::new(100);
@@ -30,7 +30,7 @@ pub use sync::*;
 pub use template_application::*;
 
 // Legacy imports for compatibility
-use crate::config::AuthConfig;
+use crate::config::{AuthConfig, ProductionRateLimitConfig};
 use crate::encryption::AutoEncryptionConfig;
 use crate::fidelity::FidelityScore;
 use crate::reality::RealityLevel;
@@ -303,6 +303,10 @@ pub struct WorkspaceConfig {
     /// Measures how close the mock is to the real upstream
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fidelity_score: Option<FidelityScore>,
+    /// Per-workspace rate limit used in multi-tenant mode
+    /// When unset, requests routed to this workspace fall back to the global limits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<ProductionRateLimitConfig>,
 }
 
 /// Workspace registry for managing multiple workspaces
@@ -1263,6 +1267,7 @@ impl Default for WorkspaceConfig {
             reality_level: None,
             fidelity_score: None,
             ai_mode: None,
+            rate_limit: None,
         }
     }
 }
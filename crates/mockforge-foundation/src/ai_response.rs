@@ -267,6 +267,7 @@ mod tests {
             path: context.path.clone(),
             path_params: context.path_params.clone(),
             query_params: context.query_params.clone(),
+            raw_query: String::new(),
             headers: context.headers.clone(),
             body: context.body.clone(),
             multipart_fields: context.multipart_fields.clone(),
@@ -0,0 +1,62 @@
+//! Admission permits for long-lived connections.
+//!
+//! The HTTP server caps concurrent WebSocket connections
+//! (`http.streaming_limit`). Its admission middleware attaches a
+//! [`ConnectionPermit`] to each request it lets through, and a protocol
+//! handler that upgrades the connection moves the permit into the task
+//! serving the socket. The slot stays taken while the socket is open and is
+//! released when that task ends, without the protocol crate having to know
+//! about the tracker that issued it.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+/// Opaque token holding a connection slot; clones share the same slot,
+/// which is released once the last clone is dropped
+#[derive(Clone)]
+pub struct ConnectionPermit {
+    _slot: Arc<dyn Any + Send + Sync>,
+}
+
+impl ConnectionPermit {
+    /// Wrap the value that releases the slot when dropped (e.g. a tracker
+    /// registration guard)
+    pub fn new(slot: impl Any + Send + Sync) -> Self {
+        Self {
+            _slot: Arc::new(slot),
+        }
+    }
+}
+
+impl fmt::Debug for ConnectionPermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionPermit").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    struct Slot(Arc<AtomicBool>);
+
+    impl Drop for Slot {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn slot_is_released_with_the_last_clone() {
+        let released = Arc::new(AtomicBool::new(false));
+        let permit = ConnectionPermit::new(Slot(released.clone()));
+        let held = permit.clone();
+
+        drop(permit);
+        assert!(!released.load(Ordering::SeqCst));
+        drop(held);
+        assert!(released.load(Ordering::SeqCst));
+    }
+}
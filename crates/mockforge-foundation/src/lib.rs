@@ -14,6 +14,7 @@ pub mod ai_studio_types;
 pub mod chaos_utilities;
 pub mod clock;
 pub mod conformance_violations;
+pub mod connection_permit;
 pub mod contract_diff_types;
 pub mod contract_drift_types;
 pub mod encryption_error;
@@ -30,11 +31,15 @@ pub mod protocol_contract_types;
 pub mod rate_counters;
 pub mod response_generation_trace;
 pub mod response_selection;
+pub mod route_drift;
+pub mod route_hits;
 pub mod scenario_types;
 pub mod schema_diff;
 pub mod state_machine;
 pub mod threat_modeling_types;
 pub mod unknown_paths;
+pub mod validation_outcomes;
+pub mod validation_report;
 pub mod workspace_promotion;
 
 pub use encryption_error::{EncryptionError, EncryptionResult};
@@ -0,0 +1,422 @@
+//! Per-route aggregation of response schema drift.
+//!
+//! The drift tracking middleware compares every JSON response it sees
+//! against the response schema the loaded spec declares for that route
+//! and status, and records what differs here, keyed by
+//! `"METHOD /template"`. `GET /__mockforge/api/drift/routes` serves the
+//! aggregate so drifting routes can be spotted without reading logs:
+//!
+//!   - `added`: fields the response carries that the schema does not declare
+//!   - `removed`: required fields the response left out
+//!   - `type_changed`: fields whose JSON type differs from the schema's
+//!
+//! Field paths are rooted at `$`, with `[]` standing for any array
+//! element, so the same drift in every element of a list is one finding.
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Distinct findings kept per category and route; later ones only bump counts.
+const MAX_FINDINGS_PER_ROUTE: usize = 50;
+
+/// `$ref` hops followed before giving up, so cyclic schemas terminate.
+const MAX_REF_DEPTH: usize = 32;
+
+/// A field whose JSON type no longer matches the schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeChange {
+    /// Field path (e.g. `$.items[].id`).
+    pub field: String,
+    /// Type the schema declares.
+    pub expected: String,
+    /// Type the response carried.
+    pub found: String,
+}
+
+/// Differences between one response body and its schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriftFindings {
+    /// Fields present in the response but not declared by the schema.
+    pub added: Vec<String>,
+    /// Required fields missing from the response.
+    pub removed: Vec<String>,
+    /// Fields whose type differs from the declared one.
+    pub type_changed: Vec<TypeChange>,
+}
+
+impl DriftFindings {
+    /// Whether the response matched its schema.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.type_changed.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.added.len() + self.removed.len() + self.type_changed.len()
+    }
+}
+
+/// Aggregated drift for one route.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteDriftReport {
+    /// HTTP method (uppercase).
+    pub method: String,
+    /// Spec-template path (e.g. `/users/{id}`).
+    pub path: String,
+    /// Responses on this route compared against the schema.
+    pub checked: u64,
+    /// Of those, responses that did not match it.
+    pub drifted: u64,
+    /// Distinct drift findings seen on this route.
+    #[serde(flatten)]
+    pub findings: DriftFindings,
+    /// When drift was first recorded for this route.
+    pub first_seen: Option<DateTime<Utc>>,
+    /// When drift was most recently recorded.
+    pub last_seen: Option<DateTime<Utc>>,
+}
+
+static REPORT: Lazy<Mutex<BTreeMap<String, RouteDriftReport>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+/// Compare `body` against `schema`, resolving local `$ref`s against `root`
+/// (normally the whole OpenAPI document).
+pub fn diff_against_schema(schema: &Value, root: &Value, body: &Value) -> DriftFindings {
+    let mut findings = DriftFindings::default();
+    walk(schema, root, body, "$", 0, &mut findings);
+    // Every element of an array reports under the same `[]` path
+    dedup(&mut findings.added);
+    dedup(&mut findings.removed);
+    dedup(&mut findings.type_changed);
+    findings
+}
+
+fn dedup<T: PartialEq>(items: &mut Vec<T>) {
+    let mut kept: Vec<T> = Vec::with_capacity(items.len());
+    for item in items.drain(..) {
+        if !kept.contains(&item) {
+            kept.push(item);
+        }
+    }
+    *items = kept;
+}
+
+fn walk(
+    schema: &Value,
+    root: &Value,
+    value: &Value,
+    path: &str,
+    depth: usize,
+    out: &mut DriftFindings,
+) {
+    let Some(schema) = resolve(schema, root, depth) else {
+        return;
+    };
+    if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        return;
+    }
+
+    // Of several alternatives, judge against the closest one
+    for key in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            let closest = variants
+                .iter()
+                .map(|variant| {
+                    let mut findings = DriftFindings::default();
+                    walk(variant, root, value, path, depth + 1, &mut findings);
+                    findings
+                })
+                .min_by_key(DriftFindings::len);
+            if let Some(closest) = closest {
+                out.added.extend(closest.added);
+                out.removed.extend(closest.removed);
+                out.type_changed.extend(closest.type_changed);
+            }
+            return;
+        }
+    }
+
+    // allOf parts describe one object together, so merge before comparing
+    let merged;
+    let schema = match schema.get("allOf").and_then(Value::as_array) {
+        Some(parts) => {
+            merged = merge_all_of(schema, parts, root, depth);
+            &merged
+        }
+        None => schema,
+    };
+
+    if let Some(expected) = declared_type(schema) {
+        let found = json_type(value);
+        let matches = expected == found
+            || (expected == "number" && found == "integer")
+            || (found == "null" && schema.get("nullable").and_then(Value::as_bool) == Some(true));
+        if !matches {
+            out.type_changed.push(TypeChange {
+                field: path.to_string(),
+                expected: expected.to_string(),
+                found: found.to_string(),
+            });
+            return;
+        }
+    }
+
+    match value {
+        Value::Object(fields) => walk_object(schema, root, fields, path, depth, out),
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                let item_path = format!("{}[]", path);
+                for item in items {
+                    walk(item_schema, root, item, &item_path, depth, out);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn walk_object(
+    schema: &Value,
+    root: &Value,
+    fields: &Map<String, Value>,
+    path: &str,
+    depth: usize,
+    out: &mut DriftFindings,
+) {
+    let empty = Map::new();
+    let properties = schema.get("properties").and_then(Value::as_object).unwrap_or(&empty);
+
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !fields.contains_key(name) {
+                out.removed.push(format!("{}.{}", path, name));
+            }
+        }
+    }
+
+    // Without declared properties the schema says nothing about field names
+    let additional = schema.get("additionalProperties");
+    for (name, field) in fields {
+        let field_path = format!("{}.{}", path, name);
+        match (properties.get(name), additional) {
+            (Some(field_schema), _) => walk(field_schema, root, field, &field_path, depth, out),
+            (None, Some(extra)) if extra.is_object() => {
+                walk(extra, root, field, &field_path, depth, out)
+            }
+            (None, Some(Value::Bool(true))) => {}
+            (None, _) if !properties.is_empty() => out.added.push(field_path),
+            (None, _) => {}
+        }
+    }
+}
+
+/// Follow `$ref` chains to the schema they point at.
+fn resolve<'a>(schema: &'a Value, root: &'a Value, depth: usize) -> Option<&'a Value> {
+    let mut current = schema;
+    for _ in depth..MAX_REF_DEPTH {
+        match current.get("$ref").and_then(Value::as_str) {
+            Some(reference) => current = root.pointer(reference.strip_prefix('#')?)?,
+            None => return Some(current),
+        }
+    }
+    None
+}
+
+fn merge_all_of(schema: &Value, parts: &[Value], root: &Value, depth: usize) -> Value {
+    let mut merged = schema.as_object().cloned().unwrap_or_default();
+    merged.remove("allOf");
+    for part in parts.iter().filter_map(|part| resolve(part, root, depth + 1)) {
+        let Some(part) = part.as_object() else {
+            continue;
+        };
+        for (key, value) in part {
+            match (key.as_str(), merged.get_mut(key)) {
+                ("properties", Some(Value::Object(existing))) => {
+                    if let Value::Object(more) = value {
+                        existing.extend(more.clone());
+                    }
+                }
+                ("required", Some(Value::Array(existing))) => {
+                    if let Value::Array(more) = value {
+                        existing.extend(more.iter().cloned());
+                    }
+                }
+                (_, Some(_)) => {}
+                (_, None) => {
+                    merged.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+    Value::Object(merged)
+}
+
+fn declared_type(schema: &Value) -> Option<&str> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => Some(ty),
+        _ if schema.get("properties").is_some() => Some("object"),
+        _ => None,
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Record one response on the route compared against its schema.
+pub fn record(method: &str, path: &str, findings: &DriftFindings) {
+    let method = method.to_ascii_uppercase();
+    let mut report = REPORT.lock();
+    let entry = report
+        .entry(format!("{} {}", method, path))
+        .or_insert_with(|| RouteDriftReport {
+            method,
+            path: path.to_string(),
+            checked: 0,
+            drifted: 0,
+            findings: DriftFindings::default(),
+            first_seen: None,
+            last_seen: None,
+        });
+    entry.checked += 1;
+    if findings.is_empty() {
+        return;
+    }
+
+    let now = Utc::now();
+    entry.drifted += 1;
+    entry.first_seen.get_or_insert(now);
+    entry.last_seen = Some(now);
+    merge_distinct(&mut entry.findings.added, &findings.added);
+    merge_distinct(&mut entry.findings.removed, &findings.removed);
+    merge_distinct(&mut entry.findings.type_changed, &findings.type_changed);
+}
+
+fn merge_distinct<T: Clone + PartialEq>(kept: &mut Vec<T>, new: &[T]) {
+    for item in new {
+        if kept.len() >= MAX_FINDINGS_PER_ROUTE {
+            break;
+        }
+        if !kept.contains(item) {
+            kept.push(item.clone());
+        }
+    }
+}
+
+/// Routes that have drifted, most drifted responses first (ties ordered by route).
+pub fn snapshot() -> Vec<RouteDriftReport> {
+    let mut routes: Vec<_> = REPORT.lock().values().filter(|r| r.drifted > 0).cloned().collect();
+    // sort_by is stable, so equal counts keep the map's route order
+    routes.sort_by(|a, b| b.drifted.cmp(&a.drifted));
+    routes
+}
+
+/// Responses compared against a schema across all routes.
+pub fn total_checked() -> u64 {
+    REPORT.lock().values().map(|r| r.checked).sum()
+}
+
+/// Reset the report.
+pub fn clear() {
+    REPORT.lock().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// The tests mutate the global `REPORT` static, so they must not
+    /// interleave. Serialize them through a shared lock.
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    fn user_document() -> Value {
+        json!({
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "required": ["id", "name"],
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "name": { "type": "string" },
+                            "tags": { "type": "array", "items": { "type": "string" } }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn conforming_body_has_no_findings() {
+        let root = user_document();
+        let schema = json!({ "$ref": "#/components/schemas/User" });
+        let findings =
+            diff_against_schema(&schema, &root, &json!({ "id": 1, "name": "Ada", "tags": ["a"] }));
+        assert!(findings.is_empty());
+    }
+
+    #[test]
+    fn reports_added_removed_and_type_changed_fields() {
+        let root = user_document();
+        let schema = json!({
+            "type": "array",
+            "items": { "$ref": "#/components/schemas/User" }
+        });
+        let body = json!([
+            { "id": "1", "email": "ada@example.com", "tags": [1] },
+            { "id": 2, "name": "Bob" }
+        ]);
+        let findings = diff_against_schema(&schema, &root, &body);
+        assert_eq!(findings.added, vec!["$[].email"]);
+        assert_eq!(findings.removed, vec!["$[].name"]);
+        assert_eq!(
+            findings.type_changed,
+            vec![
+                TypeChange {
+                    field: "$[].id".to_string(),
+                    expected: "integer".to_string(),
+                    found: "string".to_string(),
+                },
+                TypeChange {
+                    field: "$[].tags[]".to_string(),
+                    expected: "string".to_string(),
+                    found: "integer".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn aggregates_only_drifted_routes() {
+        let _guard = TEST_LOCK.lock();
+        clear();
+        let drift = DriftFindings {
+            added: vec!["$.email".to_string()],
+            ..Default::default()
+        };
+        record("get", "/users/{id}", &drift);
+        record("GET", "/users/{id}", &drift);
+        record("GET", "/users/{id}", &DriftFindings::default());
+        record("GET", "/health", &DriftFindings::default());
+
+        let report = snapshot();
+        assert_eq!(report.len(), 1);
+        assert_eq!((report[0].method.as_str(), report[0].path.as_str()), ("GET", "/users/{id}"));
+        assert_eq!((report[0].checked, report[0].drifted), (3, 2));
+        assert_eq!(report[0].findings.added, vec!["$.email"]);
+        assert_eq!(total_checked(), 4);
+    }
+}
@@ -0,0 +1,81 @@
+//! Per-route hit counts and last-hit timestamps.
+//!
+//! The HTTP metrics middleware records every request here, keyed by
+//! `"METHOD /template"` (the matched route template, or the raw path when no
+//! route matched). Coverage reports join these with the spec's routes to
+//! show how often and how recently each operation was exercised, so soak
+//! tests can tell hot endpoints from cold ones.
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Hits observed for one route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteHits {
+    /// HTTP method (uppercase).
+    pub method: String,
+    /// Route template path (e.g. `/users/{id}`).
+    pub path: String,
+    /// Requests served on this route.
+    pub hit_count: u64,
+    /// When the most recent request arrived.
+    pub last_hit: DateTime<Utc>,
+}
+
+static HITS: Lazy<Mutex<BTreeMap<String, RouteHits>>> = Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+fn key(method: &str, path: &str) -> String {
+    format!("{} {}", method.to_ascii_uppercase(), path)
+}
+
+/// Record one request on the route.
+pub fn record(method: &str, path: &str) {
+    let now = Utc::now();
+    let mut hits = HITS.lock();
+    let entry = hits.entry(key(method, path)).or_insert_with(|| RouteHits {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        hit_count: 0,
+        last_hit: now,
+    });
+    entry.hit_count += 1;
+    entry.last_hit = now;
+}
+
+/// Hits for one route, if it has been requested.
+pub fn get(method: &str, path: &str) -> Option<RouteHits> {
+    HITS.lock().get(&key(method, path)).cloned()
+}
+
+/// Hits for every requested route, ordered by route.
+pub fn snapshot() -> Vec<RouteHits> {
+    HITS.lock().values().cloned().collect()
+}
+
+/// Reset all counts.
+pub fn clear() {
+    HITS.lock().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_hits_and_tracks_the_latest() {
+        clear();
+        record("get", "/orders/{id}");
+        let first = get("GET", "/orders/{id}").unwrap();
+        record("GET", "/orders/{id}");
+        record("POST", "/orders");
+
+        let orders = get("GET", "/orders/{id}").unwrap();
+        assert_eq!((orders.method.as_str(), orders.hit_count), ("GET", 2));
+        assert!(orders.last_hit >= first.last_hit);
+        assert_eq!(get("DELETE", "/orders/{id}"), None);
+        assert_eq!(snapshot().len(), 2);
+    }
+}
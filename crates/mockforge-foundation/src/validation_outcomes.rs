@@ -0,0 +1,84 @@
+//! Per-operation counts of schema-valid and invalid requests.
+//!
+//! Whenever the OpenAPI router validates a request (in `Warn` or `Enforce`
+//! mode) it records the outcome here, keyed by `"METHOD /template"`. The
+//! HTTP crate joins these counts with route coverage to show which
+//! operations have been exercised with valid requests, invalid ones, or
+//! both. Requests that are not validated (validation disabled) are not
+//! counted.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Validation outcomes observed for one operation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationValidationCounts {
+    /// HTTP method (uppercase).
+    pub method: String,
+    /// Spec-template path (e.g. `/users/{id}`).
+    pub path: String,
+    /// Requests that passed validation.
+    pub valid: u64,
+    /// Requests that failed validation.
+    pub invalid: u64,
+}
+
+static OUTCOMES: Lazy<Mutex<BTreeMap<String, OperationValidationCounts>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+fn key(method: &str, path: &str) -> String {
+    format!("{} {}", method.to_ascii_uppercase(), path)
+}
+
+/// Record one validated request and whether it conformed to the schema.
+pub fn record(method: &str, path: &str, valid: bool) {
+    let mut outcomes = OUTCOMES.lock();
+    let entry = outcomes.entry(key(method, path)).or_insert_with(|| OperationValidationCounts {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        ..Default::default()
+    });
+    if valid {
+        entry.valid += 1;
+    } else {
+        entry.invalid += 1;
+    }
+}
+
+/// Counts for one operation, if any request to it was validated.
+pub fn get(method: &str, path: &str) -> Option<OperationValidationCounts> {
+    OUTCOMES.lock().get(&key(method, path)).cloned()
+}
+
+/// Counts for every operation with at least one validated request, ordered
+/// by route.
+pub fn snapshot() -> Vec<OperationValidationCounts> {
+    OUTCOMES.lock().values().cloned().collect()
+}
+
+/// Reset all counts.
+pub fn clear() {
+    OUTCOMES.lock().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_valid_and_invalid_per_operation() {
+        clear();
+        record("post", "/orders", true);
+        record("POST", "/orders", false);
+        record("POST", "/orders", true);
+        record("GET", "/orders/{id}", false);
+
+        let orders = get("POST", "/orders").unwrap();
+        assert_eq!((orders.method.as_str(), orders.valid, orders.invalid), ("POST", 2, 1));
+        assert_eq!(get("get", "/orders/{id}").unwrap().invalid, 1);
+        assert_eq!(get("DELETE", "/orders/{id}"), None);
+        assert_eq!(snapshot().len(), 2);
+    }
+}
@@ -0,0 +1,131 @@
+//! Per-route aggregation of request-validation warnings.
+//!
+//! In `Warn` validation mode the OpenAPI router accepts non-conforming
+//! requests and only logs the violations, which makes it hard to see
+//! which routes (and therefore which clients) misbehave most. The router
+//! also records every warned request here, keyed by `"METHOD /template"`,
+//! and `GET /__mockforge/api/validation-report` serves the aggregate:
+//! a lifetime count plus a few sample violation messages per route.
+//!
+//! Enforce-mode rejections are tracked separately in
+//! [`crate::conformance_violations`].
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Sample violation messages kept per route; later ones only bump counts.
+const MAX_SAMPLES_PER_ROUTE: usize = 5;
+
+/// Aggregated validation warnings for one route.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteValidationReport {
+    /// HTTP method (uppercase).
+    pub method: String,
+    /// Spec-template path (e.g. `/users/{id}`).
+    pub path: String,
+    /// Requests to this route that failed validation.
+    pub count: u64,
+    /// The first few distinct violation messages seen on this route.
+    pub samples: Vec<String>,
+    /// When a violation was first recorded for this route.
+    pub first_seen: DateTime<Utc>,
+    /// When the most recent violation was recorded.
+    pub last_seen: DateTime<Utc>,
+}
+
+static REPORT: Lazy<Mutex<BTreeMap<String, RouteValidationReport>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+/// Record one request that failed validation with the given `errors`.
+pub fn record(method: &str, path: &str, errors: &[String]) {
+    let now = Utc::now();
+    let method = method.to_ascii_uppercase();
+    let mut report = REPORT.lock();
+    let entry =
+        report
+            .entry(format!("{} {}", method, path))
+            .or_insert_with(|| RouteValidationReport {
+                method,
+                path: path.to_string(),
+                count: 0,
+                samples: Vec::new(),
+                first_seen: now,
+                last_seen: now,
+            });
+    entry.count += 1;
+    entry.last_seen = now;
+    for error in errors {
+        if entry.samples.len() >= MAX_SAMPLES_PER_ROUTE {
+            break;
+        }
+        if !entry.samples.contains(error) {
+            entry.samples.push(error.clone());
+        }
+    }
+}
+
+/// Per-route reports, most violations first (ties ordered by route).
+pub fn snapshot() -> Vec<RouteValidationReport> {
+    let mut routes: Vec<_> = REPORT.lock().values().cloned().collect();
+    // sort_by is stable, so equal counts keep the map's route order
+    routes.sort_by(|a, b| b.count.cmp(&a.count));
+    routes
+}
+
+/// Total warned requests across all routes.
+pub fn total() -> u64 {
+    REPORT.lock().values().map(|r| r.count).sum()
+}
+
+/// Reset the report.
+pub fn clear() {
+    REPORT.lock().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The tests mutate the global `REPORT` static, so they must not
+    /// interleave. Serialize them through a shared lock.
+    static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn aggregates_counts_and_samples_per_route() {
+        let _guard = TEST_LOCK.lock();
+        clear();
+        record("post", "/users", &["body.name: required".to_string()]);
+        record(
+            "POST",
+            "/users",
+            &[
+                "body.name: required".to_string(),
+                "query.x: bad".to_string(),
+            ],
+        );
+        record("GET", "/users/{id}", &["path.id: not an integer".to_string()]);
+
+        let report = snapshot();
+        assert_eq!(report.len(), 2);
+        assert_eq!((report[0].method.as_str(), report[0].path.as_str()), ("POST", "/users"));
+        assert_eq!(report[0].count, 2);
+        assert_eq!(report[0].samples, vec!["body.name: required", "query.x: bad"]);
+        assert_eq!(report[1].count, 1);
+        assert_eq!(total(), 3);
+    }
+
+    #[test]
+    fn samples_are_capped() {
+        let _guard = TEST_LOCK.lock();
+        clear();
+        for i in 0..(MAX_SAMPLES_PER_ROUTE + 3) {
+            record("GET", "/items", &[format!("violation {}", i)]);
+        }
+        let report = snapshot();
+        assert_eq!(report[0].count, (MAX_SAMPLES_PER_ROUTE + 3) as u64);
+        assert_eq!(report[0].samples.len(), MAX_SAMPLES_PER_ROUTE);
+    }
+}
@@ -10,7 +10,7 @@ pub mod router;
 pub mod service_generator;
 
 use crate::reflection::{MockReflectionProxy, ProxyConfig};
-use proto_parser::ProtoParser;
+use proto_parser::{ProtoParser, ProtoService};
 use service_generator::DynamicGrpcService;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -148,6 +148,21 @@ impl ServiceRegistry {
         }
     }
 
+    /// Create a service registry serving every service in `descriptor_pool`,
+    /// e.g. one decoded from a compiled descriptor set
+    pub fn from_descriptor_pool(descriptor_pool: prost_reflect::DescriptorPool) -> Self {
+        let mut registry = Self::with_descriptor_pool(descriptor_pool);
+        let services: Vec<_> = registry
+            .descriptor_pool
+            .services()
+            .map(|s| ProtoService::from_descriptor(&s))
+            .collect();
+        for service in services {
+            registry.register(service.name.clone(), DynamicGrpcService::new(service, None));
+        }
+        registry
+    }
+
     /// Replace the override list with `overrides`. Typically called once at
     /// server start with the rules from `GrpcConfig::overrides`.
     pub fn set_overrides(&mut self, overrides: Vec<mockforge_core::config::GrpcOverride>) {
@@ -375,6 +390,21 @@ async fn start_grpc_only_server(
         info!("  - Dynamic service: {}", service_name);
     }
 
+    let routes = build_grpc_routes(config, registry_arc)?;
+    server_builder.add_routes(routes).serve(grpc_addr).await?;
+
+    info!("gRPC server stopped");
+    Ok(())
+}
+
+/// Build the gRPC routes served by the dynamic server: the built-in Greeter,
+/// the reflection service (when `enable_reflection` is set) advertising every
+/// service in the registry's descriptor pool, and a fallback that answers
+/// unary and streaming calls for those services with generated responses.
+pub fn build_grpc_routes(
+    config: &DynamicGrpcConfig,
+    registry_arc: Arc<ServiceRegistry>,
+) -> Result<tonic::service::Routes, Box<dyn std::error::Error + Send + Sync>> {
     // Build the built-in Greeter service
     use crate::generated::greeter_server::{Greeter, GreeterServer};
     use crate::generated::{HelloReply, HelloRequest};
@@ -569,12 +599,7 @@ async fn start_grpc_only_server(
                 }
             });
 
-    // Convert back to tonic Routes and serve with the configured server
-    let routes = tonic::service::Routes::from(axum_router);
-    server_builder.add_routes(routes).serve(grpc_addr).await?;
-
-    info!("gRPC server stopped");
-    Ok(())
+    Ok(tonic::service::Routes::from(axum_router))
 }
 
 // start_combined_server removed - was a stub that was never implemented
@@ -3,7 +3,7 @@
 //! This module handles parsing of .proto files and extracting service definitions
 //! to generate dynamic gRPC service implementations.
 
-use prost_reflect::DescriptorPool;
+use prost_reflect::{DescriptorPool, ServiceDescriptor};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -24,6 +24,29 @@ pub struct ProtoService {
     pub methods: Vec<ProtoMethod>,
 }
 
+impl ProtoService {
+    /// Build a service definition from a loaded service descriptor
+    pub fn from_descriptor(descriptor: &ServiceDescriptor) -> Self {
+        let methods = descriptor
+            .methods()
+            .map(|method| ProtoMethod {
+                name: method.name().to_string(),
+                input_type: method.input().full_name().to_string(),
+                output_type: method.output().full_name().to_string(),
+                client_streaming: method.is_client_streaming(),
+                server_streaming: method.is_server_streaming(),
+            })
+            .collect();
+
+        Self {
+            name: descriptor.full_name().to_string(),
+            package: descriptor.parent_file().package_name().to_string(),
+            short_name: descriptor.name().to_string(),
+            methods,
+        }
+    }
+}
+
 /// A parsed proto method definition
 #[derive(Debug, Clone)]
 pub struct ProtoMethod {
@@ -415,39 +438,16 @@ impl ProtoParser {
 
         // Extract services from the descriptor pool
         for service_descriptor in self.pool.services() {
-            let service_name = service_descriptor.full_name().to_string();
-            let package_name = service_descriptor.parent_file().package_name().to_string();
-            let short_name = service_descriptor.name().to_string();
-
-            debug!("Found service: {} in package: {}", service_name, package_name);
-
-            // Extract methods for this service
-            let mut methods = Vec::new();
-            for method_descriptor in service_descriptor.methods() {
-                let method = ProtoMethod {
-                    name: method_descriptor.name().to_string(),
-                    input_type: method_descriptor.input().full_name().to_string(),
-                    output_type: method_descriptor.output().full_name().to_string(),
-                    client_streaming: method_descriptor.is_client_streaming(),
-                    server_streaming: method_descriptor.is_server_streaming(),
-                };
-
+            let service = ProtoService::from_descriptor(&service_descriptor);
+            debug!("Found service: {} in package: {}", service.name, service.package);
+            for method in &service.methods {
                 debug!(
                     "  Found method: {} ({} -> {})",
                     method.name, method.input_type, method.output_type
                 );
-
-                methods.push(method);
             }
 
-            let service = ProtoService {
-                name: service_name.clone(),
-                package: package_name,
-                short_name,
-                methods,
-            };
-
-            self.services.insert(service_name, service);
+            self.services.insert(service.name.clone(), service);
         }
 
         info!("Extracted {} services from descriptor pool", self.services.len());
@@ -4,10 +4,12 @@
 //! using the service registry and descriptor pool for response generation.
 
 use super::ServiceRegistry;
-use http::header::HeaderValue;
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use http_body_util::{BodyExt, Full};
 use mockforge_core::config::{GrpcOverride, GrpcOverrideResponse};
 use prost_reflect::prost::Message as _;
 use prost_reflect::{DynamicMessage, MessageDescriptor, Value};
+use std::collections::HashMap;
 use tonic::{Code, Status};
 use tracing::{debug, warn};
 
@@ -133,24 +135,60 @@ pub fn encode_grpc_body(message: &DynamicMessage) -> Vec<u8> {
 
 /// Create an HTTP response representing a gRPC error
 pub fn create_grpc_error_response(status: Status) -> axum::response::Response {
-    let code = status.code() as i32;
-    let message = status.message().to_string();
+    trailers_only_response(status.code(), status.message(), &HashMap::new())
+}
 
+/// Build a "Trailers-Only" response: a call that fails before sending any
+/// message carries its status and trailing metadata in the single HEADERS
+/// frame
+fn trailers_only_response(
+    code: Code,
+    message: &str,
+    extra: &HashMap<String, String>,
+) -> axum::response::Response {
     let mut response = axum::response::Response::new(axum::body::Body::empty());
     *response.status_mut() = http::StatusCode::OK; // gRPC always returns HTTP 200
     response
         .headers_mut()
         .insert("content-type", HeaderValue::from_static("application/grpc"));
-    response.headers_mut().insert(
-        "grpc-status",
-        HeaderValue::from_str(&code.to_string()).unwrap_or(HeaderValue::from_static("2")),
-    );
+    response.headers_mut().extend(grpc_trailers(code, message, extra));
+    response
+}
+
+/// Trailing metadata for a call: `grpc-status`, a percent-encoded
+/// `grpc-message` when non-empty, and any `extra` entries
+fn grpc_trailers(code: Code, message: &str, extra: &HashMap<String, String>) -> HeaderMap {
+    let mut trailers = HeaderMap::new();
+    for (name, value) in extra {
+        match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+            (Ok(name), Ok(value)) => {
+                trailers.insert(name, value);
+            }
+            _ => warn!("Skipping invalid gRPC trailer '{}'", name),
+        }
+    }
+    trailers.insert("grpc-status", HeaderValue::from(code as i32));
+    trailers.remove("grpc-message");
     if !message.is_empty() {
-        if let Ok(val) = HeaderValue::from_str(&message) {
-            response.headers_mut().insert("grpc-message", val);
+        if let Ok(value) = HeaderValue::from_str(&percent_encode_grpc_message(message)) {
+            trailers.insert("grpc-message", value);
         }
     }
-    response
+    trailers
+}
+
+/// Percent-encode a status message as the gRPC HTTP/2 protocol requires:
+/// every byte outside printable ASCII, and `%` itself, becomes `%XX`
+fn percent_encode_grpc_message(message: &str) -> String {
+    let mut encoded = String::with_capacity(message.len());
+    for byte in message.bytes() {
+        if (0x20..=0x7e).contains(&byte) && byte != b'%' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
 }
 
 /// Handle a dynamic gRPC request using the service registry and descriptor pool.
@@ -185,6 +223,23 @@ pub async fn handle_dynamic_grpc_request(
                 ))
             })?;
 
+    // Non-OK override statuses apply to every kind of call; unary calls can
+    // also override the response body (see `handle_unary`)
+    if method.client_streaming || method.server_streaming {
+        let input_desc = registry.descriptor_pool().get_message_by_name(&method.input_type);
+        if let Some(response) = find_matching_override(
+            registry.overrides(),
+            service_name,
+            method_name,
+            input_desc.as_ref(),
+            Some(_body.as_ref()),
+        )
+        .and_then(|rule| override_error_response(&rule.response))
+        {
+            return Ok(response);
+        }
+    }
+
     // Determine streaming type and handle
     match (method.client_streaming, method.server_streaming) {
         (false, false) => handle_unary(registry, service_name, method, &_body).await,
@@ -294,6 +349,16 @@ fn parse_status_code(name: &str) -> Code {
     }
 }
 
+/// The error response for an override rule whose `status` is non-OK
+fn override_error_response(rule: &GrpcOverrideResponse) -> Option<axum::response::Response> {
+    let code = parse_status_code(rule.status.as_deref()?);
+    if code == Code::Ok {
+        return None;
+    }
+    let message = rule.message.as_deref().unwrap_or_default();
+    Some(trailers_only_response(code, message, &rule.trailers))
+}
+
 /// Build a response from an override rule.
 ///
 /// - If `response.status` is set and non-OK, returns a gRPC error response.
@@ -306,12 +371,8 @@ fn apply_override(
     output_desc: Option<&MessageDescriptor>,
 ) -> Result<Option<axum::response::Response>, Status> {
     // Status code first — non-OK short-circuits regardless of body.
-    if let Some(name) = rule.status.as_deref() {
-        let code = parse_status_code(name);
-        if code != Code::Ok {
-            let msg = rule.message.clone().unwrap_or_default();
-            return Ok(Some(create_grpc_error_response(Status::new(code, msg))));
-        }
+    if let Some(response) = override_error_response(rule) {
+        return Ok(Some(response));
     }
 
     let Some(body) = rule.body.as_ref() else {
@@ -329,7 +390,9 @@ fn apply_override(
     let converter =
         super::http_bridge::converters::ProtobufJsonConverter::new(desc.parent_pool().clone());
     match converter.json_to_protobuf(desc, body) {
-        Ok(msg) => Ok(Some(build_grpc_response(encode_grpc_body(&msg))?)),
+        Ok(msg) => {
+            Ok(Some(build_grpc_response_with_trailers(encode_grpc_body(&msg), &rule.trailers)))
+        }
         Err(e) => {
             warn!("Override body failed to convert into response message: {}", e);
             Ok(None)
@@ -533,13 +596,24 @@ fn json_fallback_frame(
 
 /// Build a proper gRPC HTTP/2 response from encoded body bytes
 fn build_grpc_response(body: Vec<u8>) -> Result<axum::response::Response, Status> {
-    let mut response = axum::response::Response::new(axum::body::Body::from(body));
+    Ok(build_grpc_response_with_trailers(body, &HashMap::new()))
+}
+
+/// Build a successful gRPC response whose `grpc-status: 0` and `extra`
+/// metadata follow the messages as HTTP/2 trailers
+fn build_grpc_response_with_trailers(
+    body: Vec<u8>,
+    extra: &HashMap<String, String>,
+) -> axum::response::Response {
+    let trailers = grpc_trailers(Code::Ok, "", extra);
+    let body = Full::new(axum::body::Bytes::from(body))
+        .with_trailers(std::future::ready(Some(Ok(trailers))));
+    let mut response = axum::response::Response::new(axum::body::Body::new(body));
     *response.status_mut() = http::StatusCode::OK;
     response
         .headers_mut()
         .insert("content-type", HeaderValue::from_static("application/grpc"));
-    response.headers_mut().insert("grpc-status", HeaderValue::from_static("0"));
-    Ok(response)
+    response
 }
 
 #[cfg(test)]
@@ -560,6 +634,7 @@ mod tests {
                 status: status.map(|s| s.to_string()),
                 message: None,
                 body: None,
+                trailers: HashMap::new(),
             },
         }
     }
@@ -707,4 +782,38 @@ mod tests {
         assert_eq!(response.headers().get("grpc-status").unwrap(), "5"); // NOT_FOUND
         assert_eq!(response.headers().get("grpc-message").unwrap(), "Service not found");
     }
+
+    #[test]
+    fn test_override_error_response_carries_trailers() {
+        let rule = GrpcOverrideResponse {
+            status: Some("unavailable".to_string()),
+            message: Some("Inventory backend 100% down — retry".to_string()),
+            body: None,
+            trailers: HashMap::from([("Retry-After-Ms".to_string(), "250".to_string())]),
+        };
+        let response = override_error_response(&rule).unwrap();
+
+        assert_eq!(response.headers().get("grpc-status").unwrap(), "14"); // UNAVAILABLE
+        assert_eq!(
+            response.headers().get("grpc-message").unwrap(),
+            "Inventory backend 100%25 down %E2%80%94 retry"
+        );
+        assert_eq!(response.headers().get("retry-after-ms").unwrap(), "250");
+
+        let ok = GrpcOverrideResponse {
+            status: Some("OK".to_string()),
+            ..rule
+        };
+        assert!(override_error_response(&ok).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_success_status_sent_as_trailers() {
+        let response = build_grpc_response(vec![0, 0, 0, 0, 0]).unwrap();
+        assert!(response.headers().get("grpc-status").is_none());
+
+        let collected = response.into_body().collect().await.unwrap();
+        assert_eq!(collected.trailers().unwrap().get("grpc-status").unwrap(), "0");
+        assert_eq!(collected.to_bytes().len(), 5);
+    }
 }
@@ -232,3 +232,206 @@ async fn test_grpc_descriptor_pool_accessible() {
         "HelloReply should have an 'items' field"
     );
 }
+
+/// Descriptor pool for a small `mock.inventory.Inventory` service, built in
+/// code so the test doesn't depend on `protoc` being installed
+fn inventory_descriptor_pool() -> prost_reflect::DescriptorPool {
+    use prost_reflect::prost_types::field_descriptor_proto::{Label, Type};
+    use prost_reflect::prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto,
+    };
+
+    let string_field = |name: &str, number: i32| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(Label::Optional as i32),
+        r#type: Some(Type::String as i32),
+        json_name: Some(name.to_string()),
+        ..Default::default()
+    };
+    let file = FileDescriptorProto {
+        name: Some("inventory.proto".to_string()),
+        package: Some("mock.inventory".to_string()),
+        syntax: Some("proto3".to_string()),
+        message_type: vec![
+            DescriptorProto {
+                name: Some("GetItemRequest".to_string()),
+                field: vec![string_field("id", 1)],
+                ..Default::default()
+            },
+            DescriptorProto {
+                name: Some("Item".to_string()),
+                field: vec![string_field("id", 1), string_field("name", 2)],
+                ..Default::default()
+            },
+        ],
+        service: vec![ServiceDescriptorProto {
+            name: Some("Inventory".to_string()),
+            method: vec![MethodDescriptorProto {
+                name: Some("GetItem".to_string()),
+                input_type: Some(".mock.inventory.GetItemRequest".to_string()),
+                output_type: Some(".mock.inventory.Item".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    prost_reflect::DescriptorPool::from_file_descriptor_set(FileDescriptorSet { file: vec![file] })
+        .expect("inventory descriptor set should be valid")
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct GetItemRequest {
+    #[prost(string, tag = "1")]
+    id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct Item {
+    #[prost(string, tag = "1")]
+    id: String,
+    #[prost(string, tag = "2")]
+    name: String,
+}
+
+#[tokio::test]
+async fn test_grpc_reflection_and_generated_unary_stub() {
+    use mockforge_grpc::dynamic::{build_grpc_routes, ServiceRegistry};
+    use std::sync::Arc;
+    use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+    use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+    use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+    use tonic_reflection::pb::v1::ServerReflectionRequest;
+
+    let registry = Arc::new(ServiceRegistry::from_descriptor_pool(inventory_descriptor_pool()));
+    let config = DynamicGrpcConfig {
+        enable_reflection: true,
+        http_bridge: None,
+        tls: None,
+        ..Default::default()
+    };
+    let routes = build_grpc_routes(&config, registry).expect("routes should build");
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_routes(routes)
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+    );
+    let channel = tonic::transport::Endpoint::from_shared(format!("http://{}", addr))
+        .unwrap()
+        .connect()
+        .await
+        .expect("should connect to the mock gRPC server");
+
+    // Reflection: list services
+    let mut reflection = ServerReflectionClient::new(channel.clone());
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(MessageRequest::ListServices(String::new())),
+    };
+    let mut responses = reflection
+        .server_reflection_info(tokio_stream::iter(vec![request]))
+        .await
+        .expect("reflection call should succeed")
+        .into_inner();
+    let response = responses.message().await.unwrap().expect("reflection response");
+    let Some(MessageResponse::ListServicesResponse(list)) = response.message_response else {
+        panic!("expected a list-services response, got {:?}", response.message_response);
+    };
+    let services: Vec<String> = list.service.into_iter().map(|s| s.name).collect();
+    assert!(
+        services.iter().any(|s| s == "mock.inventory.Inventory"),
+        "reflection should advertise the loaded service, got {:?}",
+        services
+    );
+
+    // Unary call against the generated stub
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready().await.unwrap();
+    let item: Item = grpc
+        .unary(
+            tonic::Request::new(GetItemRequest {
+                id: "42".to_string(),
+            }),
+            tonic::codegen::http::uri::PathAndQuery::from_static(
+                "/mock.inventory.Inventory/GetItem",
+            ),
+            tonic_prost::ProstCodec::default(),
+        )
+        .await
+        .expect("unary call should succeed")
+        .into_inner();
+    assert_eq!(item.id, "mock-id-001");
+    assert_eq!(item.name, "Mock name");
+}
+
+/// Call `mock.inventory.Inventory/GetItem` with `id`
+async fn get_item(
+    grpc: &mut tonic::client::Grpc<tonic::transport::Channel>,
+    id: &str,
+) -> Result<tonic::Response<Item>, tonic::Status> {
+    grpc.ready().await.unwrap();
+    grpc.unary(
+        tonic::Request::new(GetItemRequest { id: id.to_string() }),
+        tonic::codegen::http::uri::PathAndQuery::from_static("/mock.inventory.Inventory/GetItem"),
+        tonic_prost::ProstCodec::default(),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_grpc_override_returns_configured_status_and_trailers() {
+    use mockforge_core::config::{GrpcOverride, GrpcOverrideResponse};
+    use mockforge_grpc::dynamic::{build_grpc_routes, ServiceRegistry};
+    use std::sync::Arc;
+
+    let mut registry = ServiceRegistry::from_descriptor_pool(inventory_descriptor_pool());
+    registry.set_overrides(vec![GrpcOverride {
+        service: "mock.inventory.Inventory".to_string(),
+        method: "GetItem".to_string(),
+        r#match: HashMap::from([("id".to_string(), "down".to_string())]),
+        response: GrpcOverrideResponse {
+            status: Some("UNAVAILABLE".to_string()),
+            message: Some("Inventory backend unavailable".to_string()),
+            body: None,
+            trailers: HashMap::from([("retry-after-ms".to_string(), "250".to_string())]),
+        },
+    }]);
+    let config = DynamicGrpcConfig {
+        http_bridge: None,
+        tls: None,
+        ..Default::default()
+    };
+    let routes = build_grpc_routes(&config, Arc::new(registry)).expect("routes should build");
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_routes(routes)
+            .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+    );
+    let channel = tonic::transport::Endpoint::from_shared(format!("http://{}", addr))
+        .unwrap()
+        .connect()
+        .await
+        .expect("should connect to the mock gRPC server");
+    let mut grpc = tonic::client::Grpc::new(channel);
+
+    let status = get_item(&mut grpc, "down").await.expect_err("override should fail the call");
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+    assert_eq!(status.message(), "Inventory backend unavailable");
+    assert_eq!(status.metadata().get("retry-after-ms").unwrap(), "250");
+
+    // Calls the override doesn't match still get the generated stub
+    let item = get_item(&mut grpc, "42")
+        .await
+        .expect("unmatched call should succeed")
+        .into_inner();
+    assert_eq!(item.id, "mock-id-001");
+}
@@ -0,0 +1,101 @@
+//! Connect latency for HTTP serve paths.
+//!
+//! [`AcceptDelayMakeService`] wraps the make-service handed to
+//! `axum::serve` / `axum_server::Server::serve` and sleeps for the
+//! configured `http.accept_delay_ms` before producing each connection's
+//! service, so nothing is read from a new connection until the delay has
+//! passed. Both servers build the per-connection service in their accept
+//! loop, so connections are taken one at a time: a burst of N connections
+//! waits up to N × delay, like a server whose accept loop is slow.
+//!
+//! The kernel still completes the TCP handshake, so clients see the delay as
+//! a slow TLS handshake or a late first response rather than a slow
+//! `connect()`.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Tower make-service that delays every new connection by a fixed amount
+/// before delegating to the inner make-service. A zero delay adds no sleep.
+#[derive(Clone)]
+pub struct AcceptDelayMakeService<M> {
+    inner: M,
+    delay: Duration,
+}
+
+impl<M> AcceptDelayMakeService<M> {
+    /// Wrap a make-service so each new connection waits `delay` first
+    pub fn new(inner: M, delay: Duration) -> Self {
+        Self { inner, delay }
+    }
+}
+
+impl<M, T> tower::Service<T> for AcceptDelayMakeService<M>
+where
+    M: tower::Service<T>,
+    M::Future: Send + 'static,
+{
+    type Response = M::Response;
+    type Error = M::Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, target: T) -> Self::Future {
+        let delay = self.delay;
+        let fut = self.inner.call(target);
+        Box::pin(async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            fut.await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{routing::get, Router};
+    use std::time::Instant;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn request(port: u16) -> String {
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        stream
+            .write_all(b"GET /ping HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn new_connections_wait_for_the_accept_delay() {
+        let delay = std::time::Duration::from_millis(300);
+        let app = Router::new().route("/ping", get(|| async { "pong" }));
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let server = tokio::spawn(crate::serve_router_with_tls_notify_chaos(
+            0,
+            app,
+            None,
+            Some(tx),
+            None,
+            delay,
+            None,
+        ));
+        let port = rx.await.unwrap();
+
+        let started = Instant::now();
+        let response = request(port).await;
+        assert!(started.elapsed() >= delay, "served after {:?}", started.elapsed());
+        assert!(response.starts_with("HTTP/1.1 200"), "unexpected response: {}", response);
+        assert!(response.ends_with("pong"), "unexpected response: {}", response);
+
+        server.abort();
+    }
+}
@@ -1,6 +1,7 @@
 //! Token lifecycle management
 //!
 //! This module provides functionality for managing token lifecycle scenarios:
+//! - Issued token tracking
 //! - Token revocation tracking
 //! - Key rotation management
 //! - Clock skew simulation
@@ -112,6 +113,71 @@ impl Default for TokenRevocationStore {
     }
 }
 
+/// An access token issued by the mock OAuth2 server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssuedToken {
+    /// Token identifier, as used for revocation
+    pub token_id: String,
+    /// Start and end of the token with the middle elided
+    pub redacted: String,
+    /// Subject (sub claim)
+    pub subject: String,
+    /// Client the token was issued to, if known
+    pub client_id: Option<String>,
+    /// Granted scopes (space-separated)
+    pub scope: Option<String>,
+    /// When the token was issued (effective time)
+    pub issued_at: i64,
+    /// When the token expires (effective time)
+    pub expires_at: i64,
+}
+
+/// Registry of issued access tokens, so tests can find and revoke them
+#[derive(Debug, Clone, Default)]
+pub struct IssuedTokenRegistry {
+    /// Map of token_id -> IssuedToken
+    tokens: Arc<RwLock<HashMap<String, IssuedToken>>>,
+}
+
+impl IssuedTokenRegistry {
+    /// Record an issued token. Tokens that expired before `issued_at` are
+    /// dropped so the registry does not grow without bound.
+    pub async fn record(
+        &self,
+        token: &str,
+        subject: String,
+        client_id: Option<String>,
+        scope: Option<String>,
+        issued_at: i64,
+        expires_at: i64,
+    ) {
+        let issued = IssuedToken {
+            token_id: extract_token_id(token),
+            redacted: redact_token(token),
+            subject,
+            client_id,
+            scope,
+            issued_at,
+            expires_at,
+        };
+        let mut tokens = self.tokens.write().await;
+        tokens.retain(|_, t| t.expires_at > issued_at);
+        tokens.insert(issued.token_id.clone(), issued);
+    }
+
+    /// Look up an issued token by ID
+    pub async fn get(&self, token_id: &str) -> Option<IssuedToken> {
+        self.tokens.read().await.get(token_id).cloned()
+    }
+
+    /// All recorded tokens, oldest first
+    pub async fn list(&self) -> Vec<IssuedToken> {
+        let mut tokens: Vec<_> = self.tokens.read().await.values().cloned().collect();
+        tokens.sort_by_key(|t| t.issued_at);
+        tokens
+    }
+}
+
 /// Key rotation state
 #[derive(Debug, Clone)]
 pub struct KeyRotationState {
@@ -239,10 +305,21 @@ impl ClockSkewState {
         *skew
     }
 
+    /// Advance the effective clock by `seconds` on top of any existing skew,
+    /// returning the new skew
+    pub async fn advance(&self, seconds: i64) -> i64 {
+        let mut skew = self.skew_seconds.write().await;
+        *skew += seconds;
+        *skew
+    }
+
     /// Get adjusted time (current time + skew)
+    ///
+    /// "Current time" comes from `mockforge_foundation::clock`, so a time-travel
+    /// virtual clock, when registered, is skewed too.
     pub async fn get_adjusted_time(&self) -> i64 {
         let skew = self.skew_seconds.read().await;
-        Utc::now().timestamp() + *skew
+        mockforge_foundation::clock::now().timestamp() + *skew
     }
 
     /// Apply skew to a timestamp (for issuance)
@@ -275,6 +352,8 @@ impl Default for ClockSkewState {
 /// Token lifecycle manager combining all lifecycle features
 #[derive(Debug, Clone)]
 pub struct TokenLifecycleManager {
+    /// Issued access tokens
+    pub issued: IssuedTokenRegistry,
     /// Token revocation store
     pub revocation: TokenRevocationStore,
     /// Key rotation state
@@ -287,6 +366,7 @@ impl TokenLifecycleManager {
     /// Create new token lifecycle manager
     pub fn new(grace_period_seconds: i64) -> Self {
         Self {
+            issued: IssuedTokenRegistry::default(),
             revocation: TokenRevocationStore::new(),
             key_rotation: KeyRotationState::new(grace_period_seconds),
             clock_skew: ClockSkewState::new(),
@@ -308,3 +388,14 @@ pub fn extract_token_id(token: &str) -> String {
     hasher.update(token.as_bytes());
     format!("{:x}", hasher.finalize())
 }
+
+/// Keep only the first and last few characters of a token for display
+pub fn redact_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 16 {
+        return "*".repeat(chars.len());
+    }
+    let head: String = chars[..8].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}...{}", head, tail)
+}
@@ -0,0 +1,303 @@
+//! Connection limit with a bounded queue for HTTP serve paths.
+//!
+//! [`ConnectionLimiter`] hands each accepted connection an [`Admission`]:
+//! connections under `http.connection_limit.max_connections` are served
+//! straight away, the next `queue_depth` wait for one of them to close, and
+//! the rest are rejected. A queued connection that gets no slot within
+//! `queue_timeout_ms` is rejected too. Rejected connections are answered with
+//! 503 Service Unavailable and closed, like a backend whose connection pool
+//! is exhausted.
+//!
+//! Waiting happens when the connection's first request is served, not in the
+//! accept loop, so queued connections never hold up new ones from being
+//! accepted (and rejected when the queue is full).
+//! [`ConnectionLimitMakeService`] applies this to `axum::serve` /
+//! `axum_server` make-services; the pipelined listener checks an
+//! [`Admission`] itself.
+
+use axum::http::{header, Request, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use mockforge_core::config::HttpConnectionLimitConfig;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::sync::{OnceCell, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// Shared connection slots and queue for one or more listeners
+pub struct ConnectionLimiter {
+    slots: Arc<Semaphore>,
+    queued: AtomicUsize,
+    queue_depth: usize,
+    queue_timeout: Duration,
+}
+
+impl ConnectionLimiter {
+    /// Create a limiter from the `http.connection_limit` settings
+    pub fn new(config: &HttpConnectionLimitConfig) -> Arc<Self> {
+        Arc::new(Self {
+            slots: Arc::new(Semaphore::new(config.max_connections)),
+            queued: AtomicUsize::new(0),
+            queue_depth: config.queue_depth,
+            queue_timeout: Duration::from_millis(config.queue_timeout_ms),
+        })
+    }
+
+    /// Admit a newly accepted connection: take a free slot, join the queue,
+    /// or reject it when the queue is full. Never waits.
+    pub fn enter(self: &Arc<Self>) -> Admission {
+        let admission = Admission {
+            limiter: self.clone(),
+            deadline: Instant::now() + self.queue_timeout,
+            slot: OnceCell::new(),
+            in_queue: AtomicBool::new(false),
+        };
+        if let Ok(slot) = self.slots.clone().try_acquire_owned() {
+            let _ = admission.slot.set(Some(slot));
+        } else if self
+            .queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |queued| {
+                (queued < self.queue_depth).then_some(queued + 1)
+            })
+            .is_ok()
+        {
+            admission.in_queue.store(true, Ordering::Release);
+        } else {
+            let _ = admission.slot.set(None);
+        }
+        admission
+    }
+
+    /// Connections currently waiting for a slot
+    pub fn queued(&self) -> usize {
+        self.queued.load(Ordering::Acquire)
+    }
+}
+
+/// One connection's place under a [`ConnectionLimiter`]. The slot is held
+/// until the admission is dropped, i.e. when the connection closes.
+pub struct Admission {
+    limiter: Arc<ConnectionLimiter>,
+    deadline: Instant,
+    slot: OnceCell<Option<OwnedSemaphorePermit>>,
+    in_queue: AtomicBool,
+}
+
+impl Admission {
+    /// Whether the connection may be served, waiting for a slot until the
+    /// queue timeout if it was queued
+    pub async fn admitted(&self) -> bool {
+        let slot = self
+            .slot
+            .get_or_init(|| async {
+                let slot = tokio::time::timeout_at(
+                    self.deadline,
+                    self.limiter.slots.clone().acquire_owned(),
+                )
+                .await
+                .ok()
+                .and_then(Result::ok);
+                self.leave_queue();
+                slot
+            })
+            .await;
+        slot.is_some()
+    }
+
+    fn leave_queue(&self) {
+        if self.in_queue.swap(false, Ordering::AcqRel) {
+            self.limiter.queued.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+impl Drop for Admission {
+    fn drop(&mut self) {
+        self.leave_queue();
+    }
+}
+
+/// Response for connections turned away by the limit
+pub fn rejection_response() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        [(header::CONNECTION, "close")],
+        Json(serde_json::json!({
+            "error": "Service Unavailable",
+            "message": "Connection limit reached"
+        })),
+    )
+        .into_response()
+}
+
+/// Tower make-service that puts every new connection under a
+/// [`ConnectionLimiter`]; with no limiter, connections pass straight through.
+#[derive(Clone)]
+pub struct ConnectionLimitMakeService<M> {
+    inner: M,
+    limiter: Option<Arc<ConnectionLimiter>>,
+}
+
+impl<M> ConnectionLimitMakeService<M> {
+    /// Wrap a make-service so its connections share `limiter`
+    pub fn new(inner: M, limiter: Option<Arc<ConnectionLimiter>>) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+impl<M, T> tower::Service<T> for ConnectionLimitMakeService<M>
+where
+    M: tower::Service<T>,
+    M::Future: Send + 'static,
+    M::Response: 'static,
+{
+    type Response = LimitedService<M::Response>;
+    type Error = M::Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, target: T) -> Self::Future {
+        let admission = self.limiter.as_ref().map(|limiter| Arc::new(limiter.enter()));
+        let fut = self.inner.call(target);
+        Box::pin(async move {
+            let inner = fut.await?;
+            Ok(LimitedService { inner, admission })
+        })
+    }
+}
+
+/// Per-connection service that serves requests only once the connection has
+/// been admitted. Clones share the admission, so the slot is released when
+/// the last clone is dropped.
+#[derive(Clone)]
+pub struct LimitedService<S> {
+    inner: S,
+    admission: Option<Arc<Admission>>,
+}
+
+impl<S, B> tower::Service<Request<B>> for LimitedService<S>
+where
+    S: tower::Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send,
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        // Call the instance that was polled ready, leaving a clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let admission = self.admission.clone();
+        Box::pin(async move {
+            if let Some(admission) = admission {
+                if !admission.admitted().await {
+                    return Ok(rejection_response());
+                }
+            }
+            inner.call(req).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    async fn request(port: u16) -> String {
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        stream
+            .write_all(b"GET /slow HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn excess_connections_queue_then_overflow_with_503() {
+        let app = Router::new().route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                "done"
+            }),
+        );
+        let limit = HttpConnectionLimitConfig {
+            max_connections: 2,
+            queue_depth: 2,
+            queue_timeout_ms: 5_000,
+        };
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let server = tokio::spawn(crate::serve_router_with_tls_notify_chaos(
+            0,
+            app,
+            None,
+            Some(tx),
+            None,
+            Duration::ZERO,
+            Some(limit),
+        ));
+        let port = rx.await.unwrap();
+
+        // Six clients at once: two are served, two queue behind them and are
+        // served next, two find the queue full
+        let started = std::time::Instant::now();
+        let clients: Vec<_> = (0..6).map(|_| tokio::spawn(request(port))).collect();
+        let mut ok = 0;
+        let mut rejected = 0;
+        for client in clients {
+            let response = client.await.unwrap();
+            if response.starts_with("HTTP/1.1 200") {
+                assert!(response.ends_with("done"), "unexpected response: {}", response);
+                ok += 1;
+            } else {
+                assert!(response.starts_with("HTTP/1.1 503"), "unexpected response: {}", response);
+                assert!(response.contains("Connection limit reached"));
+                rejected += 1;
+            }
+        }
+        assert_eq!((ok, rejected), (4, 2));
+        // The queued pair only ran once the first pair finished
+        assert!(started.elapsed() >= Duration::from_millis(400), "took {:?}", started.elapsed());
+
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn queued_connections_time_out() {
+        let limiter = ConnectionLimiter::new(&HttpConnectionLimitConfig {
+            max_connections: 1,
+            queue_depth: 1,
+            queue_timeout_ms: 50,
+        });
+        let served = limiter.enter();
+        assert!(served.admitted().await);
+
+        let queued = limiter.enter();
+        assert_eq!(limiter.queued(), 1);
+        assert!(!limiter.enter().admitted().await, "queue is full");
+        assert!(!queued.admitted().await, "no slot frees up before the timeout");
+        assert_eq!(limiter.queued(), 0);
+
+        // Closing the served connection frees its slot
+        drop(served);
+        assert!(limiter.enter().admitted().await);
+    }
+}
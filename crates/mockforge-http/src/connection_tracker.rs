@@ -0,0 +1,387 @@
+//! Registry of live streaming connections (SSE and WebSocket)
+//!
+//! Long-lived endpoints such as the request-log SSE stream and the management
+//! WebSocket register each client on connect and get back a
+//! [`ConnectionGuard`]; dropping the guard (when the stream or socket task
+//! ends) removes the entry. `GET /__mockforge/api/connections` reports the
+//! current registry.
+//!
+//! Servers share one process-wide tracker ([`global_connections`]) so streams
+//! served by the admin UI show up next to those on the main HTTP port.
+//!
+//! The tracker also enforces `http.streaming_limit`: endpoints that admit
+//! clients with [`ConnectionTracker::try_register`] are refused a guard once
+//! that kind of connection is at its limit and answer 503. Mock WebSocket
+//! routes from other crates are admitted by [`websocket_limit_middleware`],
+//! which hands the guard to the socket as a [`ConnectionPermit`].
+
+use axum::body::Body;
+use axum::extract::{ConnectInfo, State};
+use axum::http::{header::UPGRADE, Extensions, Request, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use futures::stream::{Stream, StreamExt};
+use mockforge_core::config::HttpStreamingLimitConfig;
+use mockforge_foundation::connection_permit::ConnectionPermit;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Kind of streaming connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionKind {
+    /// Server-Sent Events stream
+    Sse,
+    /// WebSocket connection
+    WebSocket,
+}
+
+/// Metadata about one live connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionInfo {
+    /// Tracker-assigned connection ID
+    pub id: u64,
+    /// SSE or WebSocket
+    pub kind: ConnectionKind,
+    /// Client address, when the server was started with connect info
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_addr: Option<String>,
+    /// ISO 8601 timestamp of when the client connected
+    pub connected_at: String,
+    /// Stream the client subscribed to (e.g. `logs`, `management`)
+    pub stream: String,
+}
+
+/// Response body for `GET /__mockforge/api/connections`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionsSummary {
+    /// Total live connections
+    pub total: usize,
+    /// Live SSE connections
+    pub sse: usize,
+    /// Live WebSocket connections
+    pub websocket: usize,
+    /// Per-connection metadata, oldest first
+    pub connections: Vec<ConnectionInfo>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    next_id: AtomicU64,
+    connections: Mutex<BTreeMap<u64, ConnectionInfo>>,
+    limits: Mutex<HttpStreamingLimitConfig>,
+}
+
+/// Registry of live SSE/WebSocket connections. Cheap to clone (Arc).
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionTracker {
+    inner: Arc<Inner>,
+}
+
+impl ConnectionTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a connection; it stays listed until the guard is dropped
+    pub fn register(
+        &self,
+        kind: ConnectionKind,
+        remote_addr: Option<SocketAddr>,
+        stream: impl Into<String>,
+    ) -> ConnectionGuard {
+        self.insert(&mut self.lock(), kind, remote_addr, stream.into())
+    }
+
+    /// Register a connection unless `kind` is already at its streaming
+    /// limit, in which case the client should be turned away
+    pub fn try_register(
+        &self,
+        kind: ConnectionKind,
+        remote_addr: Option<SocketAddr>,
+        stream: impl Into<String>,
+    ) -> Option<ConnectionGuard> {
+        let limit = {
+            let limits = self.inner.limits.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            match kind {
+                ConnectionKind::Sse => limits.max_sse,
+                ConnectionKind::WebSocket => limits.max_websocket,
+            }
+        };
+        // Count and insert under one lock so concurrent clients can't overshoot
+        let mut connections = self.lock();
+        if let Some(limit) = limit {
+            if connections.values().filter(|c| c.kind == kind).count() >= limit {
+                return None;
+            }
+        }
+        Some(self.insert(&mut connections, kind, remote_addr, stream.into()))
+    }
+
+    /// Apply `http.streaming_limit`; already open connections are kept
+    pub fn set_limits(&self, limits: HttpStreamingLimitConfig) {
+        *self.inner.limits.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = limits;
+    }
+
+    fn insert(
+        &self,
+        connections: &mut BTreeMap<u64, ConnectionInfo>,
+        kind: ConnectionKind,
+        remote_addr: Option<SocketAddr>,
+        stream: String,
+    ) -> ConnectionGuard {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let info = ConnectionInfo {
+            id,
+            kind,
+            remote_addr: remote_addr.map(|addr| addr.to_string()),
+            connected_at: chrono::Utc::now().to_rfc3339(),
+            stream,
+        };
+        connections.insert(id, info);
+        ConnectionGuard {
+            tracker: self.clone(),
+            id,
+        }
+    }
+
+    /// Live connections, oldest first
+    pub fn list(&self) -> Vec<ConnectionInfo> {
+        self.lock().values().cloned().collect()
+    }
+
+    /// Counts plus per-connection metadata
+    pub fn summary(&self) -> ConnectionsSummary {
+        let connections = self.list();
+        let sse = connections.iter().filter(|c| c.kind == ConnectionKind::Sse).count();
+        ConnectionsSummary {
+            total: connections.len(),
+            sse,
+            websocket: connections.len() - sse,
+            connections,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<u64, ConnectionInfo>> {
+        self.inner.connections.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Keeps a connection listed in its [`ConnectionTracker`] until dropped
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    tracker: ConnectionTracker,
+    id: u64,
+}
+
+impl ConnectionGuard {
+    /// Tracker-assigned ID of this connection
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.tracker.lock().remove(&self.id);
+    }
+}
+
+/// Tie a guard to a stream so the connection is unlisted when the stream is
+/// dropped (for SSE, when the client disconnects)
+pub fn track_stream<S: Stream>(guard: ConnectionGuard, stream: S) -> impl Stream<Item = S::Item> {
+    stream.map(move |item| {
+        let _ = &guard;
+        item
+    })
+}
+
+/// Client address from request extensions, if the server records connect info
+pub fn remote_addr(extensions: &Extensions) -> Option<SocketAddr> {
+    extensions.get::<ConnectInfo<SocketAddr>>().map(|ConnectInfo(addr)| *addr)
+}
+
+/// 503 for a client turned away at the streaming limit for `kind`
+pub fn limit_reached_response(kind: ConnectionKind) -> Response {
+    let kind = match kind {
+        ConnectionKind::Sse => "SSE",
+        ConnectionKind::WebSocket => "WebSocket",
+    };
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({
+            "error": "Service Unavailable",
+            "message": format!("{} connection limit reached", kind)
+        })),
+    )
+        .into_response()
+}
+
+/// Middleware: admit WebSocket upgrades under the tracker's WebSocket limit,
+/// answering 503 at the cap. Each admitted request carries a
+/// [`ConnectionPermit`] that the WebSocket handler holds until the socket
+/// closes. Apply to routers serving WebSockets that do not register with
+/// the tracker themselves.
+pub async fn websocket_limit_middleware(
+    State(tracker): State<ConnectionTracker>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    let upgrade = req
+        .headers()
+        .get(UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    if !upgrade {
+        return next.run(req).await;
+    }
+    let remote_addr = remote_addr(req.extensions());
+    let stream = req.uri().path().to_string();
+    let Some(guard) = tracker.try_register(ConnectionKind::WebSocket, remote_addr, stream) else {
+        return limit_reached_response(ConnectionKind::WebSocket);
+    };
+    req.extensions_mut().insert(ConnectionPermit::new(guard));
+    next.run(req).await
+}
+
+static GLOBAL_CONNECTIONS: OnceLock<ConnectionTracker> = OnceLock::new();
+
+/// Process-wide tracker shared by all servers in this process
+pub fn global_connections() -> &'static ConnectionTracker {
+    GLOBAL_CONNECTIONS.get_or_init(ConnectionTracker::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::management::{management_router, ManagementState};
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use axum::routing::get;
+    use axum::Router;
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    #[test]
+    fn guard_drop_unregisters() {
+        let tracker = ConnectionTracker::new();
+        let ws = tracker.register(ConnectionKind::WebSocket, None, "management");
+        let sse = tracker.register(ConnectionKind::Sse, None, "logs");
+
+        let summary = tracker.summary();
+        assert_eq!((summary.total, summary.sse, summary.websocket), (2, 1, 1));
+        assert_eq!(summary.connections[0].id, ws.id());
+
+        drop(sse);
+        let summary = tracker.summary();
+        assert_eq!((summary.total, summary.sse, summary.websocket), (1, 0, 1));
+    }
+
+    #[test]
+    fn try_register_stops_at_the_limit_for_its_kind() {
+        let tracker = ConnectionTracker::new();
+        tracker.set_limits(HttpStreamingLimitConfig {
+            max_sse: Some(1),
+            max_websocket: None,
+        });
+        let first = tracker.try_register(ConnectionKind::Sse, None, "sse");
+        assert!(first.is_some());
+        assert!(tracker.try_register(ConnectionKind::Sse, None, "sse").is_none());
+        // The limit is per kind
+        assert!(tracker.try_register(ConnectionKind::WebSocket, None, "management").is_some());
+
+        drop(first);
+        assert!(tracker.try_register(ConnectionKind::Sse, None, "sse").is_some());
+    }
+
+    #[tokio::test]
+    async fn sse_client_is_listed_while_connected() {
+        let tracker = ConnectionTracker::new();
+        let sse_tracker = tracker.clone();
+        let logs_sse = move |extensions: Extensions| async move {
+            let guard = sse_tracker.register(ConnectionKind::Sse, remote_addr(&extensions), "logs");
+            let events = futures::stream::pending::<Result<Event, Infallible>>();
+            // Keep-alives make the server notice the client going away
+            Sse::new(track_stream(guard, events))
+                .keep_alive(KeepAlive::new().interval(Duration::from_millis(50)))
+        };
+        let state = ManagementState::new(None, None, 0).with_connections(tracker.clone());
+        let app = Router::new()
+            .route("/logs/sse", get(logs_sse))
+            .nest("/__mockforge/api", management_router(state));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .unwrap();
+        });
+
+        let client = reqwest::Client::new();
+        let sse = client.get(format!("http://{}/logs/sse", addr)).send().await.unwrap();
+        assert!(sse.status().is_success());
+
+        let summary: ConnectionsSummary = client
+            .get(format!("http://{}/__mockforge/api/connections", addr))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(summary.sse, 1);
+        assert_eq!(summary.connections.len(), 1);
+        let conn = &summary.connections[0];
+        assert_eq!(conn.kind, ConnectionKind::Sse);
+        assert_eq!(conn.stream, "logs");
+        assert!(conn.remote_addr.as_deref().is_some_and(|a| a.starts_with("127.0.0.1:")));
+
+        // Disconnecting the client drops the stream and unlists the connection
+        drop(sse);
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while !tracker.list().is_empty() {
+            assert!(tokio::time::Instant::now() < deadline, "connection still listed");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn mock_websocket_routes_are_capped() {
+        use tokio_tungstenite::tungstenite::Error as ClientError;
+
+        let tracker = ConnectionTracker::new();
+        tracker.set_limits(HttpStreamingLimitConfig {
+            max_sse: None,
+            max_websocket: Some(1),
+        });
+        let app = mockforge_ws::router().layer(axum::middleware::from_fn_with_state(
+            tracker.clone(),
+            websocket_limit_middleware,
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/ws", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // The first socket takes the only slot for as long as it is open
+        let (first, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        assert_eq!(tracker.summary().websocket, 1);
+        assert_eq!(tracker.list()[0].stream, "/ws");
+        match tokio_tungstenite::connect_async(&url).await {
+            Err(ClientError::Http(response)) => assert_eq!(response.status(), 503),
+            other => panic!("expected a 503, got {other:?}"),
+        }
+
+        // Closing it frees the slot
+        drop(first);
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while !tracker.list().is_empty() {
+            assert!(tokio::time::Instant::now() < deadline, "socket still listed");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(tokio_tungstenite::connect_async(&url).await.is_ok());
+    }
+}
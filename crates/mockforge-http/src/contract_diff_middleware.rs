@@ -5,7 +5,7 @@
 //! This middleware captures incoming HTTP requests for contract diff analysis.
 //! It extracts request data and stores it in the capture manager.
 
-use axum::http::header::CONTENT_LENGTH;
+use crate::middleware::body_capture::{capture_limit_from_env, capture_prefix};
 use axum::http::HeaderMap;
 use axum::{body::Body, extract::Request, middleware::Next, response::Response};
 use mockforge_core::{
@@ -14,52 +14,34 @@ use mockforge_core::{
 use std::collections::HashMap;
 use tracing::debug;
 
-/// Maximum request body size to buffer for capture.
+/// Maximum request body size to capture.
 ///
 /// Issue #79 — Srikanth reported `200 OK` returned mid-upload on 10 MB
-/// chunked PATCH requests against MockForge. Root cause was right here:
-/// the old buffer limit was 1 MiB and the over-limit branch silently
-/// substituted `Body::empty()` before forwarding, so every downstream
-/// handler (Json/Bytes extractors, the OpenAPI route handler) saw an
-/// empty body and either parsed-error-then-responded or responded with
-/// a default — *before* the client finished uploading. The bumped
-/// default plus the cleaner "skip capture, forward original body"
-/// branch below fix both the limit and the empty-body-substitution.
+/// chunked PATCH requests against MockForge. The old capture buffered the
+/// whole body under a 1 MiB limit and substituted `Body::empty()` when it
+/// was exceeded, so downstream handlers saw an empty body. Bodies are now
+/// captured up to this cap only and forwarded whole regardless of size;
+/// larger ones are diffed as a truncated copy. Configurable via
+/// `MOCKFORGE_CONTRACT_DIFF_MAX_BODY_MB`.
 fn max_capture_body_size() -> usize {
-    const DEFAULT_MB: usize = 10;
-    std::env::var("MOCKFORGE_CONTRACT_DIFF_MAX_BODY_MB")
-        .ok()
-        .and_then(|v| v.parse::<usize>().ok())
-        .unwrap_or(DEFAULT_MB)
-        .saturating_mul(1024 * 1024)
+    capture_limit_from_env("MOCKFORGE_CONTRACT_DIFF_MAX_BODY_MB")
 }
 
 /// Middleware to capture requests for contract diff analysis
 pub async fn capture_for_contract_diff(req: Request<Body>, next: Next) -> Response {
+    capture_for_contract_diff_with_limit(req, next, max_capture_body_size()).await
+}
+
+/// [`capture_for_contract_diff`] with an explicit capture limit in bytes
+pub async fn capture_for_contract_diff_with_limit(
+    req: Request<Body>,
+    next: Next,
+    limit: usize,
+) -> Response {
     let method = req.method().to_string();
     let uri = req.uri().clone();
     let path = uri.path().to_string();
     let query = uri.query();
-    let max_body = max_capture_body_size();
-
-    // Issue #79 — if the request advertises a Content-Length larger than
-    // the capture cap, skip the capture entirely (and don't consume the
-    // body). This avoids the previous bug where over-limit bodies were
-    // replaced with Body::empty() before being forwarded to the handler.
-    let content_length = req
-        .headers()
-        .get(CONTENT_LENGTH)
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.parse::<usize>().ok());
-    if let Some(len) = content_length {
-        if len > max_body {
-            debug!(
-                "contract_diff: skipping capture for {} {} — content-length {} exceeds cap {}",
-                method, path, len, max_body
-            );
-            return next.run(req).await;
-        }
-    }
 
     // Extract headers
     let headers = extract_headers_for_capture(req.headers());
@@ -71,46 +53,19 @@ pub async fn capture_for_contract_diff(req: Request<Body>, next: Next) -> Respon
         HashMap::new()
     };
 
-    // Buffer the request body so we can capture it and still forward it.
+    // Keep a copy of at most `limit` bytes of the body; the handler still
+    // receives all of it
     let (parts, body) = req.into_parts();
-    let body_bytes = match axum::body::to_bytes(body, max_body).await {
-        Ok(b) => b,
-        Err(_) => {
-            // Chunked body that exceeded the cap (no Content-Length to
-            // pre-check). The body has been partially consumed and we
-            // can't put it back. The least-bad behaviour is a 413
-            // PayloadTooLarge so the caller knows the request was
-            // rejected — emitting `Body::empty()` here used to silently
-            // truncate the request and cause the handler to respond
-            // before the client finished uploading. Issue #79.
-            return Response::builder()
-                .status(http::StatusCode::PAYLOAD_TOO_LARGE)
-                .header(
-                    http::header::CONTENT_TYPE,
-                    "application/json",
-                )
-                .body(Body::from(format!(
-                    r#"{{"error":"PAYLOAD_TOO_LARGE","message":"chunked request body exceeded contract_diff capture cap (~{} MiB); raise MOCKFORGE_CONTRACT_DIFF_MAX_BODY_MB or send Content-Length"}}"#,
-                    max_body / (1024 * 1024)
-                )))
-                .unwrap_or_else(|_| {
-                    Response::new(Body::from("payload too large"))
-                });
-        }
-    };
-
-    // Try to parse body as JSON for structured capture
-    let captured_body = if !body_bytes.is_empty() {
-        serde_json::from_slice::<serde_json::Value>(&body_bytes).ok()
-    } else {
-        None
-    };
-
-    // Reconstruct the request with the buffered body
-    let rebuilt = Request::from_parts(parts, Body::from(body_bytes));
+    let (captured_body, body) = capture_prefix(body, limit).await;
+    if captured_body.truncated {
+        debug!(
+            "contract_diff: {} {} body exceeds capture cap {}; capturing truncated copy",
+            method, path, limit
+        );
+    }
 
     // Call the next middleware/handler
-    let response = next.run(rebuilt).await;
+    let response = next.run(Request::from_parts(parts, body)).await;
 
     // Extract response status
     let status_code = response.status().as_u16();
@@ -121,7 +76,7 @@ pub async fn capture_for_contract_diff(req: Request<Body>, next: Next) -> Respon
         .with_query_params(query_params)
         .with_response(status_code, None);
 
-    if let Some(body_value) = captured_body {
+    if let Some(body_value) = captured_body.to_value() {
         captured = captured.with_body(body_value);
     }
 
@@ -212,4 +167,39 @@ mod tests {
         let params = parse_query_params("");
         assert!(params.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_large_body_captured_truncated_and_forwarded_whole() {
+        use crate::middleware::body_capture::TRUNCATION_MARKER;
+        use axum::{routing::post, Router};
+        use mockforge_core::request_capture::init_global_capture_manager;
+        use tower::ServiceExt;
+
+        let manager = init_global_capture_manager(1000);
+        let app = Router::new()
+            .route(
+                "/uploads/large",
+                post(|body: axum::body::Bytes| async move { body.len().to_string() }),
+            )
+            .layer(axum::middleware::from_fn(|req: Request<Body>, next: Next| {
+                capture_for_contract_diff_with_limit(req, next, 1024)
+            }));
+
+        let req = Request::builder()
+            .method("POST")
+            .uri("/uploads/large")
+            .body(Body::from("y".repeat(5000)))
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        let received = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(received, "5000");
+
+        let captures = manager.get_recent_captures(None).await;
+        let (captured, _) = captures
+            .iter()
+            .find(|(c, _)| c.path == "/uploads/large")
+            .expect("request should be captured");
+        let body = captured.body.as_ref().and_then(|b| b.as_str()).unwrap();
+        assert_eq!(body, format!("{}{}", "y".repeat(1024), TRUNCATION_MARKER));
+    }
 }
@@ -2,11 +2,17 @@
 //!
 //! This module provides API coverage tracking functionality, allowing users to see
 //! which endpoints from their OpenAPI spec have been exercised during testing.
-//! This is analogous to code coverage but for API surface area.
+//! This is analogous to code coverage but for API surface area. The validation
+//! coverage report additionally breaks each operation down by how many
+//! schema-valid and invalid requests it has received. Hit counts and
+//! last-hit times come from the metrics middleware's per-route hit store, so
+//! hot and cold endpoints can be told apart.
 use axum::{
     extract::{Query, State},
     response::Json,
 };
+use chrono::{DateTime, Utc};
+use mockforge_foundation::{route_hits, validation_outcomes};
 use mockforge_observability::prometheus::{get_global_registry, MetricFamily};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -24,10 +30,12 @@ pub struct RouteCoverage {
     pub operation_id: Option<String>,
     /// Operation summary
     pub summary: Option<String>,
-    /// Whether this route has been called
+    /// Whether this route has been called (`hit_count > 0`)
     pub covered: bool,
     /// Number of times this route has been called
     pub hit_count: u64,
+    /// When this route was last called
+    pub last_hit: Option<DateTime<Utc>>,
     /// Breakdown by status code
     pub status_breakdown: HashMap<u16, u64>,
     /// Average latency in seconds (if called)
@@ -90,12 +98,11 @@ pub async fn calculate_coverage(routes: &[RouteInfo]) -> CoverageReport {
         let key = format!("{} {}", route.method, normalized_path);
 
         // Check if this route has been hit
-        let (covered, hit_count, status_breakdown) = if let Some(metrics) = path_metrics.get(&key) {
-            let total_hits: u64 = metrics.values().sum();
-            (total_hits > 0, total_hits, metrics.clone())
-        } else {
-            (false, 0, HashMap::new())
-        };
+        let hits = route_hits::get(&route.method, &route.path);
+        let hit_count = hits.as_ref().map_or(0, |hits| hits.hit_count);
+        let last_hit = hits.map(|hits| hits.last_hit);
+        let covered = hit_count > 0;
+        let status_breakdown = path_metrics.get(&key).cloned().unwrap_or_default();
 
         // Get average latency if available
         let avg_latency = if covered {
@@ -122,6 +129,7 @@ pub async fn calculate_coverage(routes: &[RouteInfo]) -> CoverageReport {
             summary: route.summary.clone(),
             covered,
             hit_count,
+            last_hit,
             status_breakdown,
             avg_latency_seconds: avg_latency,
         });
@@ -164,6 +172,84 @@ pub async fn calculate_coverage(routes: &[RouteInfo]) -> CoverageReport {
     }
 }
 
+/// Validation outcomes for a single OpenAPI operation, alongside its route
+/// coverage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationValidationCoverage {
+    /// HTTP method (GET, POST, etc.)
+    pub method: String,
+    /// Route path template
+    pub path: String,
+    /// Operation ID from OpenAPI spec
+    pub operation_id: Option<String>,
+    /// Whether this route has been called
+    pub covered: bool,
+    /// Number of times this route has been called
+    pub hit_count: u64,
+    /// Requests that passed schema validation
+    pub valid_requests: u64,
+    /// Requests that failed schema validation
+    pub invalid_requests: u64,
+}
+
+/// Which operations have been exercised with schema-valid and invalid
+/// requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationCoverageReport {
+    /// Total number of operations defined in the spec
+    pub total_operations: usize,
+    /// Operations that received at least one valid request
+    pub valid_covered: usize,
+    /// Operations that received at least one invalid request
+    pub invalid_covered: usize,
+    /// Percentage of operations exercised with a valid request (0.0 to 100.0)
+    pub valid_coverage_percentage: f64,
+    /// Individual operation details
+    pub operations: Vec<OperationValidationCoverage>,
+    /// Timestamp of the report
+    pub timestamp: String,
+}
+
+/// Join route coverage with the per-operation validation outcomes recorded
+/// by the OpenAPI router
+pub async fn calculate_validation_coverage(routes: &[RouteInfo]) -> ValidationCoverageReport {
+    let coverage = calculate_coverage(routes).await;
+    let operations: Vec<OperationValidationCoverage> = coverage
+        .routes
+        .into_iter()
+        .map(|route| {
+            let counts = validation_outcomes::get(&route.method, &route.path).unwrap_or_default();
+            OperationValidationCoverage {
+                method: route.method,
+                path: route.path,
+                operation_id: route.operation_id,
+                covered: route.covered,
+                hit_count: route.hit_count,
+                valid_requests: counts.valid,
+                invalid_requests: counts.invalid,
+            }
+        })
+        .collect();
+
+    let total_operations = operations.len();
+    let valid_covered = operations.iter().filter(|op| op.valid_requests > 0).count();
+    let invalid_covered = operations.iter().filter(|op| op.invalid_requests > 0).count();
+    let valid_coverage_percentage = if total_operations > 0 {
+        (valid_covered as f64 / total_operations as f64) * 100.0
+    } else {
+        0.0
+    };
+
+    ValidationCoverageReport {
+        total_operations,
+        valid_covered,
+        invalid_covered,
+        valid_coverage_percentage,
+        operations,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
 /// Extract path-based metrics from Prometheus metric families
 fn extract_path_metrics(metric_families: &[MetricFamily]) -> HashMap<String, HashMap<u16, u64>> {
     let mut path_metrics: HashMap<String, HashMap<u16, u64>> = HashMap::new();
@@ -291,6 +377,13 @@ pub async fn get_coverage_handler(
     Json(report)
 }
 
+/// Handler for the validation coverage endpoint
+pub async fn get_validation_coverage_handler(
+    State(state): State<HttpServerState>,
+) -> Json<ValidationCoverageReport> {
+    Json(calculate_validation_coverage(&state.routes).await)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -367,6 +460,7 @@ mod tests {
             summary: Some("List users".to_string()),
             covered: true,
             hit_count: 100,
+            last_hit: None,
             status_breakdown: HashMap::new(),
             avg_latency_seconds: Some(0.05),
         };
@@ -385,6 +479,7 @@ mod tests {
             summary: None,
             covered: false,
             hit_count: 0,
+            last_hit: None,
             status_breakdown: HashMap::new(),
             avg_latency_seconds: None,
         };
@@ -409,6 +504,7 @@ mod tests {
             summary: None,
             covered: true,
             hit_count: 85,
+            last_hit: None,
             status_breakdown,
             avg_latency_seconds: Some(0.1),
         };
@@ -426,6 +522,7 @@ mod tests {
             summary: None,
             covered: true,
             hit_count: 10,
+            last_hit: None,
             status_breakdown: HashMap::new(),
             avg_latency_seconds: None,
         };
@@ -681,6 +778,7 @@ mod tests {
             summary: None,
             covered: false,
             hit_count: 0,
+            last_hit: None,
             status_breakdown: HashMap::new(),
             avg_latency_seconds: None,
         };
@@ -727,4 +825,39 @@ mod tests {
         let debug = format!("{:?}", query);
         assert!(debug.contains("CoverageQuery"));
     }
+
+    #[tokio::test]
+    async fn test_calculate_coverage_counts_hits_and_last_hit() {
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        // A path no other test requests, since hit counts are process-wide
+        let app = Router::new()
+            .route("/coverage-hits/{id}", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn(crate::collect_http_metrics));
+        for uri in ["/coverage-hits/1", "/coverage-hits/2"] {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            app.clone().oneshot(req).await.unwrap();
+        }
+
+        let routes = vec![RouteInfo {
+            method: "GET".to_string(),
+            path: "/coverage-hits/{id}".to_string(),
+            operation_id: None,
+            summary: None,
+            description: None,
+            parameters: vec![],
+        }];
+        let report = calculate_coverage(&routes).await;
+
+        let route = &report.routes[0];
+        assert_eq!(route.hit_count, 2);
+        assert!(route.covered);
+        assert!(route.last_hit.is_some());
+        assert_eq!(report.covered_routes, 1);
+
+        let json = serde_json::to_value(route).unwrap();
+        assert_eq!(json["hit_count"], 2);
+        assert!(json["last_hit"].is_string());
+    }
 }
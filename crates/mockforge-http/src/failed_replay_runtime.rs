@@ -0,0 +1,296 @@
+//! Replay of recently failed requests.
+//!
+//! When chasing an intermittent 5xx it helps to re-run exactly the requests
+//! that just failed. A middleware keeps the most recent requests that got a
+//! 5xx response (method, URI, headers and body), and the API re-dispatches
+//! them through the router and reports the new statuses.
+//!
+//! Replayed requests carry the `x-mockforge-replay` header. They are never
+//! recorded again and cannot trigger another replay, so a persistently
+//! failing route cannot make the log grow or a replay recurse.
+//!
+//! Only bodies up to 1 MiB are kept. Larger requests are forwarded unchanged
+//! but not recorded, so the log stays within `100 × 1 MiB`. The middleware is
+//! installed only when `http.failed_replay` is enabled.
+//!
+//! ## Endpoints
+//!
+//! - `POST /__mockforge/api/replay/failed?count=N` — replay the last N failed
+//!   requests (default 1), oldest first
+
+use crate::middleware::body_capture::capture_prefix;
+use axum::body::{Body, Bytes};
+use axum::extract::{Query, Request, State};
+use axum::http::{header::CONTENT_LENGTH, HeaderMap, HeaderValue, Method, StatusCode, Uri};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tower::ServiceExt;
+use tracing::debug;
+
+/// Header marking a request as a replay
+pub const REPLAY_HEADER: &str = "x-mockforge-replay";
+/// Failed requests kept for replay; older ones are dropped
+const CAPACITY: usize = 100;
+/// Requests with larger bodies are passed through but not recorded
+const MAX_REPLAY_BODY: usize = 1024 * 1024;
+/// Upper bound on how long a single replayed request may take
+const REPLAY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A request that got a 5xx response
+#[derive(Clone)]
+struct FailedRequest {
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+    status: u16,
+}
+
+/// Cheap-to-clone shared state: the failed-request log and the router
+/// replays are dispatched through.
+#[derive(Clone, Default)]
+pub struct FailedReplayState {
+    failed: Arc<Mutex<VecDeque<FailedRequest>>>,
+    target: Arc<OnceLock<Router>>,
+}
+
+impl FailedReplayState {
+    /// Start with an empty log and no router attached
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the router replays are dispatched through. Call once the app is
+    /// built; later calls are ignored.
+    pub fn attach(&self, router: Router) {
+        let _ = self.target.set(router);
+    }
+
+    /// Number of failed requests currently kept
+    pub fn len(&self) -> usize {
+        self.failed.lock().unwrap_or_else(|p| p.into_inner()).len()
+    }
+
+    /// Whether no failed requests are kept
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn record(&self, request: FailedRequest) {
+        let mut failed = self.failed.lock().unwrap_or_else(|p| p.into_inner());
+        if failed.len() == CAPACITY {
+            failed.pop_front();
+        }
+        failed.push_back(request);
+    }
+
+    /// The last `count` failed requests, oldest first
+    fn recent(&self, count: usize) -> Vec<FailedRequest> {
+        let failed = self.failed.lock().unwrap_or_else(|p| p.into_inner());
+        failed.iter().skip(failed.len().saturating_sub(count)).cloned().collect()
+    }
+}
+
+/// Middleware: remember requests answered with a 5xx so they can be replayed
+pub async fn record_failed_requests_middleware(
+    State(state): State<FailedReplayState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if req.headers().contains_key(REPLAY_HEADER) {
+        return next.run(req).await;
+    }
+
+    // A body declared over the limit is never buffered; anything else is
+    // captured up to the limit and streamed on whole either way
+    let (parts, body) = req.into_parts();
+    let declared_len = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_len.is_some_and(|len| len > MAX_REPLAY_BODY) {
+        debug!("Request body too large to keep for replay");
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+    let (captured, body) = capture_prefix(body, MAX_REPLAY_BODY).await;
+    let (method, uri, headers) = (parts.method.clone(), parts.uri.clone(), parts.headers.clone());
+
+    let response = next.run(Request::from_parts(parts, body)).await;
+    if response.status().is_server_error() {
+        if captured.truncated {
+            debug!("Request body too large to keep for replay");
+            return response;
+        }
+        state.record(FailedRequest {
+            method,
+            uri,
+            headers,
+            body: captured.bytes,
+            status: response.status().as_u16(),
+        });
+    }
+    response
+}
+
+#[derive(Debug, Deserialize)]
+struct ReplayQuery {
+    count: Option<usize>,
+}
+
+/// Outcome of one replayed request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplayOutcome {
+    /// Request method
+    pub method: String,
+    /// Request path and query
+    pub uri: String,
+    /// Status the request originally failed with
+    pub original_status: u16,
+    /// Status of the replay, or `None` if it timed out
+    pub status: Option<u16>,
+}
+
+async fn replay_failed_handler(
+    State(state): State<FailedReplayState>,
+    Query(query): Query<ReplayQuery>,
+    headers: HeaderMap,
+) -> Response {
+    if headers.contains_key(REPLAY_HEADER) {
+        return (StatusCode::LOOP_DETECTED, "Replayed requests cannot trigger a replay")
+            .into_response();
+    }
+    let Some(target) = state.target.get().cloned() else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Replay router not attached").into_response();
+    };
+
+    let count = query.count.unwrap_or(1).min(CAPACITY);
+    let mut results = Vec::new();
+    for failed in state.recent(count) {
+        let mut req = Request::new(Body::from(failed.body.clone()));
+        *req.method_mut() = failed.method.clone();
+        *req.uri_mut() = failed.uri.clone();
+        *req.headers_mut() = failed.headers.clone();
+        req.headers_mut().insert(REPLAY_HEADER, HeaderValue::from_static("1"));
+
+        let status = match tokio::time::timeout(REPLAY_TIMEOUT, target.clone().oneshot(req)).await {
+            Ok(Ok(response)) => Some(response.status().as_u16()),
+            Ok(Err(infallible)) => match infallible {},
+            Err(_) => None,
+        };
+        results.push(ReplayOutcome {
+            method: failed.method.to_string(),
+            uri: failed.uri.to_string(),
+            original_status: failed.status,
+            status,
+        });
+    }
+
+    Json(serde_json::json!({
+        "replayed": results.len(),
+        "results": results,
+    }))
+    .into_response()
+}
+
+/// Build the failed-request replay API router. Mount under
+/// `/__mockforge/api/replay`.
+pub fn failed_replay_api_router(state: FailedReplayState) -> Router {
+    Router::new().route("/failed", post(replay_failed_handler)).with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::middleware::from_fn_with_state;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn failed_requests_are_replayed_through_the_router() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler_calls = calls.clone();
+        let state = FailedReplayState::new();
+        let app = Router::new()
+            .route(
+                "/boom",
+                post(move |body: String| {
+                    let calls = handler_calls.clone();
+                    async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        assert_eq!(body, "payload");
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    }
+                }),
+            )
+            .route("/ok", post(|| async { "fine" }))
+            .layer(from_fn_with_state(state.clone(), record_failed_requests_middleware))
+            .nest("/__mockforge/api/replay", failed_replay_api_router(state.clone()));
+        state.attach(app.clone());
+        let call = |uri: &str| {
+            let req = axum::http::Request::builder()
+                .method("POST")
+                .uri(uri)
+                .body(Body::from("payload"))
+                .unwrap();
+            app.clone().oneshot(req)
+        };
+
+        assert_eq!(call("/boom").await.unwrap().status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(call("/ok").await.unwrap().status(), StatusCode::OK);
+        assert_eq!(state.len(), 1);
+
+        for _ in 0..2 {
+            let res = call("/__mockforge/api/replay/failed?count=5").await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(report["replayed"], 1);
+            assert_eq!(report["results"][0]["uri"], "/boom");
+            assert_eq!(report["results"][0]["original_status"], 500);
+            assert_eq!(report["results"][0]["status"], 500);
+        }
+
+        // Replays hit the handler but are not recorded as new failures
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(state.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_pass_through_unrecorded() {
+        let state = FailedReplayState::new();
+        let app = Router::new()
+            .route(
+                "/boom",
+                post(|body: Bytes| async move {
+                    (StatusCode::INTERNAL_SERVER_ERROR, body.len().to_string())
+                }),
+            )
+            .layer(from_fn_with_state(state.clone(), record_failed_requests_middleware));
+        let payload = vec![b'x'; MAX_REPLAY_BODY + 1];
+
+        // Streamed without a Content-Length, and declared up front
+        let streamed = axum::http::Request::post("/boom")
+            .body(Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(
+                Bytes::from(payload.clone()),
+            )])))
+            .unwrap();
+        let declared = axum::http::Request::post("/boom")
+            .header(CONTENT_LENGTH, payload.len())
+            .body(Body::from(payload.clone()))
+            .unwrap();
+        for req in [streamed, declared] {
+            let res = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+            let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(body, payload.len().to_string());
+        }
+        assert!(state.is_empty());
+    }
+}
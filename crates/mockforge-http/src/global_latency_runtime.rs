@@ -0,0 +1,195 @@
+//! Runtime global latency API.
+//!
+//! Static latency (`core.latency_enabled`, per-route `latency:`) is fixed at
+//! startup. During a demo or an exploratory test it is handy to flip
+//! "everything is slow now" on and off without restarting; this module
+//! holds one optional latency profile that a middleware applies to every
+//! request, on top of any route-level latency.
+//!
+//! ## Endpoints
+//!
+//! - `GET    /__mockforge/api/config/latency` — active global latency (or 204 if none)
+//! - `PUT    /__mockforge/api/config/latency` — set or replace it
+//! - `DELETE /__mockforge/api/config/latency` — remove it
+
+use crate::middleware::server_timing::{ServerTimings, GLOBAL_METRIC};
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use mockforge_foundation::latency::{LatencyDistribution, LatencyProfile};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Global latency profile, as accepted and returned by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalLatency {
+    /// Base delay in milliseconds (the mean for `normal`/`pareto`)
+    pub base_ms: u64,
+    /// Random jitter (±) in milliseconds for the `fixed` distribution
+    #[serde(default)]
+    pub jitter_ms: u64,
+    /// How delays are distributed around `base_ms`
+    #[serde(default)]
+    pub distribution: LatencyDistribution,
+    /// Standard deviation for the `normal` distribution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub std_dev_ms: Option<f64>,
+    /// Shape parameter for the `pareto` distribution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pareto_shape: Option<f64>,
+    /// Upper bound on any single delay
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ms: Option<u64>,
+}
+
+impl From<&GlobalLatency> for LatencyProfile {
+    fn from(latency: &GlobalLatency) -> Self {
+        Self {
+            base_ms: latency.base_ms,
+            jitter_ms: latency.jitter_ms,
+            distribution: latency.distribution.clone(),
+            std_dev_ms: latency.std_dev_ms,
+            pareto_shape: latency.pareto_shape,
+            min_ms: 0,
+            max_ms: latency.max_ms,
+            tag_overrides: HashMap::new(),
+        }
+    }
+}
+
+/// Cheap-to-clone shared state holding the active global latency.
+#[derive(Clone, Default)]
+pub struct GlobalLatencyRuntimeState {
+    active: Arc<RwLock<Option<(GlobalLatency, LatencyProfile)>>>,
+}
+
+impl GlobalLatencyRuntimeState {
+    /// Start with no global latency; the middleware fast-paths off this.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Active global latency snapshot.
+    pub fn active(&self) -> Option<GlobalLatency> {
+        self.active
+            .read()
+            .expect("global latency state poisoned")
+            .as_ref()
+            .map(|(latency, _)| latency.clone())
+    }
+
+    /// Set or replace the global latency.
+    pub fn set(&self, latency: GlobalLatency) {
+        let profile = LatencyProfile::from(&latency);
+        *self.active.write().expect("global latency state poisoned") = Some((latency, profile));
+    }
+
+    /// Remove the global latency.
+    pub fn clear(&self) {
+        *self.active.write().expect("global latency state poisoned") = None;
+    }
+
+    fn delay(&self) -> Option<std::time::Duration> {
+        self.active
+            .read()
+            .expect("global latency state poisoned")
+            .as_ref()
+            .map(|(_, profile)| profile.calculate_latency(&[]))
+    }
+}
+
+/// Middleware that sleeps for the active global latency before passing to
+/// the next layer. Changes take effect on the very next request.
+pub async fn global_latency_middleware(
+    State(state): State<GlobalLatencyRuntimeState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if let Some(delay) = state.delay().filter(|d| !d.is_zero()) {
+        tokio::time::sleep(delay).await;
+        ServerTimings::record_in(req.extensions(), GLOBAL_METRIC, delay);
+    }
+    next.run(req).await
+}
+
+async fn get_handler(State(state): State<GlobalLatencyRuntimeState>) -> Response {
+    match state.active() {
+        Some(latency) => Json(latency).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+async fn put_handler(
+    State(state): State<GlobalLatencyRuntimeState>,
+    Json(latency): Json<GlobalLatency>,
+) -> Json<GlobalLatency> {
+    state.set(latency.clone());
+    Json(latency)
+}
+
+async fn delete_handler(State(state): State<GlobalLatencyRuntimeState>) -> StatusCode {
+    state.clear();
+    StatusCode::NO_CONTENT
+}
+
+/// Build the global latency API router. Mount under
+/// `/__mockforge/api/config/latency`.
+pub fn global_latency_api_router(state: GlobalLatencyRuntimeState) -> Router {
+    Router::new()
+        .route("/", get(get_handler).put(put_handler).delete(delete_handler))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::middleware::from_fn_with_state;
+    use std::time::{Duration, Instant};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn global_latency_can_be_set_and_removed_at_runtime() {
+        let state = GlobalLatencyRuntimeState::new();
+        let app = Router::new()
+            .route("/hello", get(|| async { "hi" }))
+            .layer(from_fn_with_state(state.clone(), global_latency_middleware))
+            .nest("/__mockforge/api/config/latency", global_latency_api_router(state));
+        let call = |method: &str, uri: &str, body: Body| {
+            let req = axum::http::Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(body)
+                .unwrap();
+            app.clone().oneshot(req)
+        };
+
+        let res = call("PUT", "/__mockforge/api/config/latency", Body::from(r#"{"base_ms":200}"#))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let res = call("GET", "/__mockforge/api/config/latency", Body::empty()).await.unwrap();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let active: GlobalLatency = serde_json::from_slice(&body).unwrap();
+        assert_eq!(active.base_ms, 200);
+
+        let started = Instant::now();
+        let res = call("GET", "/hello", Body::empty()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        let res = call("DELETE", "/__mockforge/api/config/latency", Body::empty()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        let res = call("GET", "/__mockforge/api/config/latency", Body::empty()).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+        let started = Instant::now();
+        call("GET", "/hello", Body::empty()).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(200));
+    }
+}
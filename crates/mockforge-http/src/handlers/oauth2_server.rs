@@ -34,6 +34,48 @@ pub struct OAuth2ServerState {
     pub refresh_tokens: Arc<RwLock<HashMap<String, RefreshTokenInfo>>>,
 }
 
+impl OAuth2ServerState {
+    /// Effective "now" for token expiry: the injectable clock plus any
+    /// skew set through the token lifecycle clock endpoints
+    async fn now(&self) -> i64 {
+        self.lifecycle_manager.clock_skew.get_adjusted_time().await
+    }
+
+    /// Sign an access token valid for `expires_in` seconds of effective time
+    ///
+    /// `generate_oidc_token` reads the signing keys with `blocking_read`, which
+    /// panics inside the async runtime, so signing runs on the blocking pool.
+    async fn issue_access_token(
+        &self,
+        subject: String,
+        claims: HashMap<String, serde_json::Value>,
+        expires_in: i64,
+        tenant_context: Option<TenantContext>,
+    ) -> Result<String, StatusCode> {
+        let oidc_state =
+            self.oidc_state.read().await.clone().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        // `exp` is stamped from the wall clock; shift it by the skew so the
+        // token lives `expires_in` seconds of effective time
+        let now = self.now().await;
+        let lifetime = expires_in + (now - Utc::now().timestamp());
+        let claim = |name: &str| claims.get(name).and_then(|v| v.as_str()).map(str::to_string);
+        let (client_id, scope) = (claim("client_id"), claim("scope"));
+        let token_subject = subject.clone();
+        let token = tokio::task::spawn_blocking(move || {
+            generate_oidc_token(&oidc_state, subject, Some(claims), Some(lifetime), tenant_context)
+        })
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        self.lifecycle_manager
+            .issued
+            .record(&token, token_subject, client_id, scope, now, now + expires_in)
+            .await;
+        Ok(token)
+    }
+}
+
 /// Refresh token information
 #[derive(Debug, Clone)]
 pub struct RefreshTokenInfo {
@@ -181,7 +223,7 @@ pub async fn authorize(
         // In production, extract from authenticated session
         user_id: "user-default".to_string(),
         state: params.state.clone(),
-        expires_at: Utc::now().timestamp() + 600, // 10 minutes
+        expires_at: state.now().await + 600, // 10 minutes
         // Tenant context can be extracted from request headers or session
         tenant_context: None,
     };
@@ -235,14 +277,10 @@ async fn handle_authorization_code_grant(
     }
 
     // Check expiration
-    if code_info.expires_at < Utc::now().timestamp() {
+    if code_info.expires_at < state.now().await {
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    // Generate access token using OIDC
-    let oidc_state_guard = state.oidc_state.read().await;
-    let oidc_state = oidc_state_guard.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-
     // Build claims
     let mut additional_claims = HashMap::new();
     additional_claims.insert("scope".to_string(), json!(code_info.scopes.join(" ")));
@@ -250,14 +288,15 @@ async fn handle_authorization_code_grant(
         additional_claims.insert("nonce".to_string(), json!(nonce));
     }
 
-    let access_token = generate_oidc_token(
-        oidc_state,
-        code_info.user_id.clone(),
-        Some(additional_claims),
-        Some(3600), // 1 hour expiration
-        code_info.tenant_context.clone(),
-    )
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Generate access token using OIDC (1 hour expiration)
+    let access_token = state
+        .issue_access_token(
+            code_info.user_id.clone(),
+            additional_claims,
+            3600,
+            code_info.tenant_context.clone(),
+        )
+        .await?;
 
     // Check if token is revoked (shouldn't be, but check anyway)
     let token_id = extract_token_id(&access_token);
@@ -275,7 +314,7 @@ async fn handle_authorization_code_grant(
                 client_id: code_info.client_id.clone(),
                 scopes: code_info.scopes.clone(),
                 user_id: code_info.user_id.clone(),
-                expires_at: Utc::now().timestamp() + 86400, // 24 hours
+                expires_at: state.now().await + 86400, // 24 hours
             },
         );
     }
@@ -301,9 +340,6 @@ async fn handle_client_credentials_grant(
     // Validate client credentials (simplified - in production, check against database)
 
     // Generate access token
-    let oidc_state_guard = state.oidc_state.read().await;
-    let oidc_state = oidc_state_guard.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-
     let mut additional_claims = HashMap::new();
     additional_claims.insert("client_id".to_string(), serde_json::json!(client_id));
     let scope_clone = request.scope.clone();
@@ -311,14 +347,9 @@ async fn handle_client_credentials_grant(
         additional_claims.insert("scope".to_string(), serde_json::json!(scope));
     }
 
-    let access_token = generate_oidc_token(
-        oidc_state,
-        format!("client_{}", client_id),
-        Some(additional_claims),
-        Some(3600),
-        None,
-    )
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let access_token = state
+        .issue_access_token(format!("client_{}", client_id), additional_claims, 3600, None)
+        .await?;
 
     Ok(Json(TokenResponse {
         access_token,
@@ -345,7 +376,7 @@ async fn handle_refresh_token_grant(
     };
 
     // Check expiration
-    if token_info.expires_at < Utc::now().timestamp() {
+    if token_info.expires_at < state.now().await {
         return Err(StatusCode::UNAUTHORIZED);
     }
 
@@ -357,9 +388,6 @@ async fn handle_refresh_token_grant(
     }
 
     // Generate new access token
-    let oidc_state_guard = state.oidc_state.read().await;
-    let oidc_state = oidc_state_guard.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-
     let mut additional_claims = HashMap::new();
     additional_claims.insert("client_id".to_string(), json!(token_info.client_id.clone()));
 
@@ -373,14 +401,9 @@ async fn handle_refresh_token_grant(
         scope_str
     };
 
-    let access_token = generate_oidc_token(
-        oidc_state,
-        token_info.user_id.clone(),
-        Some(additional_claims),
-        Some(3600),
-        None,
-    )
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let access_token = state
+        .issue_access_token(token_info.user_id.clone(), additional_claims, 3600, None)
+        .await?;
 
     // Generate and store new refresh token (rotation)
     let new_refresh_token = format!("refresh_{}", uuid::Uuid::new_v4());
@@ -392,7 +415,7 @@ async fn handle_refresh_token_grant(
                 client_id: token_info.client_id,
                 scopes: token_info.scopes,
                 user_id: token_info.user_id,
-                expires_at: Utc::now().timestamp() + 86400, // 24 hours
+                expires_at: state.now().await + 86400, // 24 hours
             },
         );
     }
@@ -407,6 +430,89 @@ async fn handle_refresh_token_grant(
     }))
 }
 
+/// Token introspection request (RFC 7662)
+#[derive(Debug, Deserialize)]
+pub struct IntrospectionRequest {
+    /// Token to introspect
+    pub token: String,
+    /// Optional hint about the token type (ignored; only access tokens are known)
+    pub token_type_hint: Option<String>,
+}
+
+/// OAuth2 token introspection endpoint (RFC 7662)
+///
+/// A token is active when it was signed by one of the configured OIDC keys,
+/// has not been revoked, and its `exp` is still ahead of the effective clock.
+/// Inactive tokens get only `{"active": false}`, as the RFC recommends.
+pub async fn introspect(
+    State(state): State<OAuth2ServerState>,
+    axum::extract::Form(request): axum::extract::Form<IntrospectionRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let oidc_state =
+        state.oidc_state.read().await.clone().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let Some(claims) = verify_access_token(&oidc_state, &request.token) else {
+        return Ok(Json(json!({ "active": false })));
+    };
+
+    let revoked = state
+        .lifecycle_manager
+        .revocation
+        .is_revoked(&extract_token_id(&request.token))
+        .await
+        .is_some();
+    let now = state.now().await;
+    let expired = claims.get("exp").and_then(|v| v.as_i64()).is_some_and(|exp| exp <= now);
+    if revoked || expired {
+        return Ok(Json(json!({ "active": false })));
+    }
+
+    let mut response = json!({ "active": true, "token_type": "Bearer" });
+    for claim in ["sub", "client_id", "scope", "iss", "exp", "iat"] {
+        if let Some(value) = claims.get(claim) {
+            response[claim] = value.clone();
+        }
+    }
+    Ok(Json(response))
+}
+
+/// Verify a token's signature against the configured OIDC keys and return its
+/// claims. Expiry is not checked here; callers compare `exp` with the
+/// effective clock.
+fn verify_access_token(
+    oidc_state: &OidcState,
+    token: &str,
+) -> Option<HashMap<String, serde_json::Value>> {
+    use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+
+    let header = jsonwebtoken::decode_header(token).ok()?;
+    let keys = &oidc_state.config.jwks.keys;
+    let key = match &header.kid {
+        Some(kid) => keys.iter().find(|k| &k.kid == kid)?,
+        None => keys.first()?,
+    };
+    let decoding_key = match header.alg {
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => DecodingKey::from_secret(
+            key.private_key.as_deref().unwrap_or(&key.public_key).as_bytes(),
+        ),
+        Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 => {
+            DecodingKey::from_rsa_pem(key.public_key.as_bytes()).ok()?
+        }
+        Algorithm::ES256 | Algorithm::ES384 => {
+            DecodingKey::from_ec_pem(key.public_key.as_bytes()).ok()?
+        }
+        _ => return None,
+    };
+
+    let mut validation = Validation::new(header.alg);
+    validation.validate_exp = false;
+    validation.validate_aud = false;
+    validation.required_spec_claims.clear();
+    validation.set_issuer(&[&oidc_state.config.issuer]);
+    jsonwebtoken::decode::<HashMap<String, serde_json::Value>>(token, &decoding_key, &validation)
+        .ok()
+        .map(|data| data.claims)
+}
+
 /// Create OAuth2 server router
 pub fn oauth2_server_router(state: OAuth2ServerState) -> axum::Router {
     use axum::routing::{get, post};
@@ -414,5 +520,137 @@ pub fn oauth2_server_router(state: OAuth2ServerState) -> axum::Router {
     axum::Router::new()
         .route("/oauth2/authorize", get(authorize))
         .route("/oauth2/token", post(token))
+        .route("/oauth2/introspect", post(introspect))
         .with_state(state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::token_lifecycle::{
+        issued_tokens_router, token_lifecycle_router, TokenLifecycleState,
+    };
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn app() -> axum::Router {
+        let lifecycle_manager = Arc::new(TokenLifecycleManager::default());
+        let state = OAuth2ServerState {
+            oidc_state: Arc::new(RwLock::new(Some(OidcState::default_mock().unwrap()))),
+            lifecycle_manager: lifecycle_manager.clone(),
+            auth_codes: Arc::new(RwLock::new(HashMap::new())),
+            refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
+        };
+        let lifecycle_state = TokenLifecycleState {
+            manager: lifecycle_manager,
+        };
+        oauth2_server_router(state)
+            .nest("/api/v1/auth", token_lifecycle_router(lifecycle_state.clone()))
+            .nest("/__mockforge/api/auth", issued_tokens_router(lifecycle_state))
+    }
+
+    async fn send(app: &axum::Router, req: Request<Body>) -> serde_json::Value {
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn form(uri: &str, body: String) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn advancing_clock_expires_issued_token() {
+        let app = app();
+        let issued = send(
+            &app,
+            form(
+                "/oauth2/token",
+                "grant_type=client_credentials&client_id=svc&client_secret=s".to_string(),
+            ),
+        )
+        .await;
+        let token = issued["access_token"].as_str().unwrap().to_string();
+        let introspect = || form("/oauth2/introspect", format!("token={}", token));
+
+        let active = send(&app, introspect()).await;
+        assert_eq!(active["active"], true);
+        assert_eq!(active["sub"], "client_svc");
+
+        let advanced = send(
+            &app,
+            Request::builder()
+                .method("POST")
+                .uri("/api/v1/auth/clock/advance")
+                .header("content-type", "application/json")
+                .body(Body::from(r#"{"seconds": 3601}"#))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(advanced["skew_seconds"], 3601);
+
+        let expired = send(&app, introspect()).await;
+        assert_eq!(expired, json!({ "active": false }));
+    }
+
+    #[tokio::test]
+    async fn revoking_issued_token_deactivates_it() {
+        let app = app();
+        let issued = send(
+            &app,
+            form(
+                "/oauth2/token",
+                "grant_type=client_credentials&client_id=svc&client_secret=s&scope=read"
+                    .to_string(),
+            ),
+        )
+        .await;
+        let token = issued["access_token"].as_str().unwrap().to_string();
+        let list = || Request::get("/__mockforge/api/auth/tokens").body(Body::empty()).unwrap();
+
+        let listed = send(&app, list()).await;
+        assert_eq!(listed["count"], 1);
+        let entry = &listed["tokens"][0];
+        assert_eq!(entry["client_id"], "svc");
+        assert_eq!(entry["scope"], "read");
+        assert!(!listed.to_string().contains(&token));
+        let token_id = entry["token_id"].as_str().unwrap().to_string();
+
+        let revoked = send(
+            &app,
+            Request::post(format!("/__mockforge/api/auth/tokens/{}/revoke", token_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(revoked["success"], true);
+
+        let introspected = send(&app, form("/oauth2/introspect", format!("token={}", token))).await;
+        assert_eq!(introspected, json!({ "active": false }));
+        assert_eq!(send(&app, list()).await["count"], 0);
+
+        let res = app
+            .clone()
+            .oneshot(
+                Request::post("/__mockforge/api/auth/tokens/unknown/revoke")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn introspection_rejects_foreign_tokens() {
+        let res = send(&app(), form("/oauth2/introspect", "token=not-a-jwt".to_string())).await;
+        assert_eq!(res, json!({ "active": false }));
+    }
+}
@@ -2,12 +2,13 @@
 //!
 //! This module provides API endpoints for testing token lifecycle scenarios:
 //! - Token revocation
+//! - Listing and revoking issued tokens
 //! - Key rotation
 //! - Clock skew
 //! - Prebuilt test scenarios
 
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json,
 };
@@ -64,6 +65,13 @@ pub struct ClockSkewRequest {
     pub duration_seconds: Option<u64>,
 }
 
+/// Clock advance request
+#[derive(Debug, Deserialize)]
+pub struct AdvanceClockRequest {
+    /// Seconds to move the effective clock forward (negative moves it back)
+    pub seconds: i64,
+}
+
 /// Force refresh failure request
 #[derive(Debug, Deserialize)]
 pub struct ForceRefreshFailureRequest {
@@ -157,6 +165,51 @@ pub async fn get_token_status(
     }
 }
 
+/// List issued access tokens that are neither revoked nor expired
+pub async fn list_issued_tokens(
+    State(state): State<TokenLifecycleState>,
+) -> Json<serde_json::Value> {
+    let now = state.manager.clock_skew.get_adjusted_time().await;
+    let mut active = Vec::new();
+    for token in state.manager.issued.list().await {
+        if token.expires_at > now
+            && state.manager.revocation.is_revoked(&token.token_id).await.is_none()
+        {
+            active.push(token);
+        }
+    }
+
+    Json(serde_json::json!({
+        "count": active.len(),
+        "tokens": active,
+    }))
+}
+
+/// Revoke an issued access token by ID
+pub async fn revoke_issued_token(
+    State(state): State<TokenLifecycleState>,
+    Path(token_id): Path<String>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let token = state.manager.issued.get(&token_id).await.ok_or(StatusCode::NOT_FOUND)?;
+
+    state
+        .manager
+        .revocation
+        .revoke_token(
+            token_id.clone(),
+            Some(token.subject),
+            "revoked via management API".to_string(),
+            Some(token.expires_at),
+        )
+        .await;
+
+    Ok(Json(serde_json::json!({
+        "success": true,
+        "token_id": token_id,
+        "message": "Token revoked successfully"
+    })))
+}
+
 /// Rotate keys
 pub async fn rotate_keys(
     State(state): State<TokenLifecycleState>,
@@ -217,11 +270,26 @@ pub async fn set_clock_skew(
     })))
 }
 
+/// Advance the effective clock so issued tokens expire on demand
+pub async fn advance_clock(
+    State(state): State<TokenLifecycleState>,
+    Json(request): Json<AdvanceClockRequest>,
+) -> Json<serde_json::Value> {
+    let skew = state.manager.clock_skew.advance(request.seconds).await;
+    let adjusted_time = state.manager.clock_skew.get_adjusted_time().await;
+
+    Json(serde_json::json!({
+        "success": true,
+        "skew_seconds": skew,
+        "adjusted_time": adjusted_time
+    }))
+}
+
 /// Get clock skew
 pub async fn get_clock_skew(State(state): State<TokenLifecycleState>) -> Json<serde_json::Value> {
     let skew = state.manager.clock_skew.get_skew().await;
     let adjusted_time = state.manager.clock_skew.get_adjusted_time().await;
-    let server_time = chrono::Utc::now().timestamp();
+    let server_time = mockforge_foundation::clock::now().timestamp();
 
     Json(serde_json::json!({
         "skew_seconds": skew,
@@ -289,7 +357,19 @@ pub fn token_lifecycle_router(state: TokenLifecycleState) -> axum::Router {
         .route("/keys/active", get(get_active_keys))
         .route("/clock/skew", post(set_clock_skew))
         .route("/clock/skew", get(get_clock_skew))
+        .route("/clock/advance", post(advance_clock))
         .route("/test/force-refresh-failure", post(force_refresh_failure))
         .route("/test/revoke-mid-session", post(revoke_mid_session))
         .with_state(state)
 }
+
+/// Create the issued-token management router. Mount under
+/// `/__mockforge/api/auth`.
+pub fn issued_tokens_router(state: TokenLifecycleState) -> axum::Router {
+    use axum::routing::{get, post};
+
+    axum::Router::new()
+        .route("/tokens", get(list_issued_tokens))
+        .route("/tokens/{id}/revoke", post(revoke_issued_token))
+        .with_state(state)
+}
@@ -25,6 +25,8 @@ pub enum ServiceStatus {
     Initializing,
     /// Service is ready to accept traffic
     Ready,
+    /// Service is serving traffic but a dependency is degraded
+    Degraded,
     /// Service is shutting down (not accepting new requests)
     ShuttingDown,
     /// Service has failed and is unhealthy
@@ -32,15 +34,37 @@ pub enum ServiceStatus {
 }
 
 impl ServiceStatus {
-    /// Check if service is ready to accept traffic
+    /// Check if service is ready to accept traffic (degraded services still are)
     pub fn is_ready(&self) -> bool {
-        matches!(self, ServiceStatus::Ready)
+        matches!(self, ServiceStatus::Ready | ServiceStatus::Degraded)
     }
 
     /// Check if service is alive (not failed)
     pub fn is_alive(&self) -> bool {
         !matches!(self, ServiceStatus::Failed)
     }
+
+    /// Collapse the lifecycle status into the three-state health model
+    pub fn health_state(&self) -> HealthState {
+        match self {
+            ServiceStatus::Ready => HealthState::Healthy,
+            ServiceStatus::Degraded => HealthState::Degraded,
+            ServiceStatus::Initializing | ServiceStatus::ShuttingDown | ServiceStatus::Failed => {
+                HealthState::Unhealthy
+            }
+        }
+    }
+}
+
+/// Three-state health model reported by the combined `/health` endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthState {
+    /// Everything is working
+    Healthy,
+    /// Serving traffic, but a dependency is degraded
+    Degraded,
+    /// Not serving traffic
+    Unhealthy,
 }
 
 /// Health check manager for tracking service state
@@ -54,6 +78,10 @@ pub struct HealthManager {
     init_deadline: Arc<Option<Instant>>,
     /// Shutdown signal for graceful termination
     shutdown_signal: Arc<RwLock<Option<tokio::sync::oneshot::Sender<()>>>>,
+    /// Why the service is degraded, while it is
+    degraded_reason: Arc<RwLock<Option<String>>>,
+    /// HTTP status `/health` returns while degraded
+    degraded_status: StatusCode,
 }
 
 impl HealthManager {
@@ -64,6 +92,8 @@ impl HealthManager {
             start_time: Arc::new(Instant::now()),
             init_deadline: Arc::new(None),
             shutdown_signal: Arc::new(RwLock::new(None)),
+            degraded_reason: Arc::new(RwLock::new(None)),
+            degraded_status: StatusCode::OK,
         }
     }
 
@@ -75,16 +105,45 @@ impl HealthManager {
             start_time: Arc::new(Instant::now()),
             init_deadline: Arc::new(Some(deadline)),
             shutdown_signal: Arc::new(RwLock::new(None)),
+            degraded_reason: Arc::new(RwLock::new(None)),
+            degraded_status: StatusCode::OK,
         }
     }
 
+    /// Set the HTTP status `/health` returns while the service is degraded
+    /// (defaults to 200, so load balancers keep routing to a degraded service)
+    pub fn with_degraded_status(mut self, status: StatusCode) -> Self {
+        self.degraded_status = status;
+        self
+    }
+
+    /// HTTP status `/health` returns while the service is degraded
+    pub fn degraded_status(&self) -> StatusCode {
+        self.degraded_status
+    }
+
     /// Mark service as ready
     pub async fn set_ready(&self) {
         let mut status = self.status.write().await;
         *status = ServiceStatus::Ready;
+        *self.degraded_reason.write().await = None;
         info!("Service marked as ready");
     }
 
+    /// Mark service as degraded: still serving, but a dependency is impaired.
+    /// Call [`set_ready`](Self::set_ready) once the dependency recovers.
+    pub async fn set_degraded(&self, reason: &str) {
+        let mut status = self.status.write().await;
+        *status = ServiceStatus::Degraded;
+        *self.degraded_reason.write().await = Some(reason.to_string());
+        warn!("Service marked as degraded: {}", reason);
+    }
+
+    /// Why the service is degraded, if it is
+    pub async fn degraded_reason(&self) -> Option<String> {
+        self.degraded_reason.read().await.clone()
+    }
+
     /// Mark service as failed
     pub async fn set_failed(&self, reason: &str) {
         let mut status = self.status.write().await;
@@ -168,6 +227,9 @@ pub struct HealthDetails {
     /// Memory usage in bytes (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub memory_bytes: Option<u64>,
+    /// Why the service is degraded (only while degraded)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
 }
 
 /// Liveness probe endpoint
@@ -220,6 +282,7 @@ async fn readiness_probe(
                 initialization: "complete".to_string(),
                 connections: None,
                 memory_bytes: None,
+                reason: None,
             }),
         };
         Ok(Json(response))
@@ -234,7 +297,7 @@ async fn readiness_probe(
             }
             ServiceStatus::ShuttingDown => "shutting_down".to_string(),
             ServiceStatus::Failed => "failed".to_string(),
-            ServiceStatus::Ready => unreachable!(),
+            ServiceStatus::Ready | ServiceStatus::Degraded => unreachable!(),
         };
 
         let response = HealthResponse {
@@ -246,6 +309,7 @@ async fn readiness_probe(
                 initialization: details,
                 connections: None,
                 memory_bytes: None,
+                reason: None,
             }),
         };
 
@@ -266,7 +330,7 @@ async fn startup_probe(
 
     // Startup probe checks if initialization is complete
     match status {
-        ServiceStatus::Ready => {
+        ServiceStatus::Ready | ServiceStatus::Degraded => {
             let response = HealthResponse {
                 status: "startup_complete".to_string(),
                 timestamp: chrono::Utc::now().to_rfc3339(),
@@ -276,6 +340,7 @@ async fn startup_probe(
                     initialization: "complete".to_string(),
                     connections: None,
                     memory_bytes: None,
+                    reason: None,
                 }),
             };
             Ok(Json(response))
@@ -301,6 +366,7 @@ async fn startup_probe(
                     initialization: "complete".to_string(),
                     connections: None,
                     memory_bytes: None,
+                    reason: None,
                 }),
             };
             Ok(Json(response))
@@ -312,47 +378,45 @@ async fn startup_probe(
 ///
 /// This endpoint provides a general health check that combines liveness and readiness.
 /// For Kubernetes deployments, prefer using the specific probe endpoints.
+///
+/// Reports the three-state model: `healthy` (200), `degraded` (the manager's
+/// configured degraded status, 200 by default) and anything else as 503.
 async fn health_check(
     State(health): State<Arc<HealthManager>>,
-) -> Result<Json<HealthResponse>, (StatusCode, Json<HealthResponse>)> {
+) -> (StatusCode, Json<HealthResponse>) {
     let status = health.get_status().await;
     let uptime = health.uptime_seconds();
 
-    if status.is_ready() {
-        let response = HealthResponse {
-            status: "healthy".to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            uptime_seconds: uptime,
-            version: env!("CARGO_PKG_VERSION").to_string(),
-            details: Some(HealthDetails {
-                initialization: "complete".to_string(),
-                connections: None,
-                memory_bytes: None,
-            }),
-        };
-        Ok(Json(response))
-    } else {
-        let status_str = match status {
-            ServiceStatus::Initializing => "initializing",
-            ServiceStatus::ShuttingDown => "shutting_down",
-            ServiceStatus::Failed => "failed",
-            ServiceStatus::Ready => unreachable!(),
-        };
-
-        let response = HealthResponse {
-            status: status_str.to_string(),
-            timestamp: chrono::Utc::now().to_rfc3339(),
-            uptime_seconds: uptime,
-            version: env!("CARGO_PKG_VERSION").to_string(),
-            details: Some(HealthDetails {
-                initialization: status_str.to_string(),
-                connections: None,
-                memory_bytes: None,
-            }),
-        };
+    let (code, status_str, initialization, reason) = match status.health_state() {
+        HealthState::Healthy => (StatusCode::OK, "healthy", "complete", None),
+        HealthState::Degraded => {
+            (health.degraded_status(), "degraded", "complete", health.degraded_reason().await)
+        }
+        HealthState::Unhealthy => {
+            let status_str = match status {
+                ServiceStatus::Initializing => "initializing",
+                ServiceStatus::ShuttingDown => "shutting_down",
+                ServiceStatus::Failed => "failed",
+                ServiceStatus::Ready | ServiceStatus::Degraded => unreachable!(),
+            };
+            (StatusCode::SERVICE_UNAVAILABLE, status_str, status_str, None)
+        }
+    };
+
+    let response = HealthResponse {
+        status: status_str.to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        uptime_seconds: uptime,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        details: Some(HealthDetails {
+            initialization: initialization.to_string(),
+            connections: None,
+            memory_bytes: None,
+            reason,
+        }),
+    };
 
-        Err((StatusCode::SERVICE_UNAVAILABLE, Json(response)))
-    }
+    (code, Json(response))
 }
 
 /// Create health check router with all probe endpoints
@@ -393,6 +457,8 @@ mod tests {
         assert!(!ServiceStatus::ShuttingDown.is_ready());
         assert!(!ServiceStatus::Failed.is_ready());
 
+        assert!(ServiceStatus::Degraded.is_ready());
+
         assert!(ServiceStatus::Ready.is_alive());
         assert!(ServiceStatus::Initializing.is_alive());
         assert!(ServiceStatus::ShuttingDown.is_alive());
@@ -522,6 +588,7 @@ mod tests {
                 initialization: "complete".to_string(),
                 connections: Some(10),
                 memory_bytes: Some(1024 * 1024),
+                reason: None,
             }),
         };
 
@@ -553,6 +620,7 @@ mod tests {
             initialization: "complete".to_string(),
             connections: Some(5),
             memory_bytes: Some(2048),
+            reason: None,
         };
 
         let json = serde_json::to_string(&details).unwrap();
@@ -567,6 +635,7 @@ mod tests {
             initialization: "initializing".to_string(),
             connections: None,
             memory_bytes: None,
+            reason: None,
         };
 
         let json = serde_json::to_string(&details).unwrap();
@@ -745,6 +814,42 @@ mod tests {
         assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
     }
 
+    #[tokio::test]
+    async fn test_health_check_degraded_uses_configured_status() {
+        let health =
+            Arc::new(HealthManager::new().with_degraded_status(StatusCode::PARTIAL_CONTENT));
+        health.set_degraded("payments upstream slow").await;
+
+        let app = health_router(health.clone());
+        let response = app
+            .oneshot(Request::builder().uri("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: HealthResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body.status, "degraded");
+        assert_eq!(body.details.unwrap().reason.as_deref(), Some("payments upstream slow"));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_degraded_defaults_to_ok_and_recovers() {
+        let health = Arc::new(HealthManager::new());
+        health.set_degraded("cache unavailable").await;
+        assert_eq!(health.get_status().await.health_state(), HealthState::Degraded);
+
+        let response = health_router(health.clone())
+            .oneshot(Request::builder().uri("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        health.set_ready().await;
+        assert_eq!(health.degraded_reason().await, None);
+        assert_eq!(health.get_status().await.health_state(), HealthState::Healthy);
+    }
+
     // ==================== Router Tests ====================
 
     #[test]
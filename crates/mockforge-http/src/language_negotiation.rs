@@ -0,0 +1,122 @@
+//! `Accept-Language` negotiation for localized responses
+//!
+//! Custom routes can carry localized bodies keyed by language tag
+//! (`response.localized`). [`negotiate`] picks the best tag for a request's
+//! `Accept-Language` header: ranges are tried in quality order (`q=`), each
+//! matching an available tag exactly, by prefix truncation (`fr-CA` → `fr`)
+//! or as a more specific tag (`fr` → `fr-FR`). Matching is case-insensitive.
+//! `None` means the route's default body should be served.
+
+/// A language range from `Accept-Language` with its quality value
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageRange {
+    /// Language range (e.g. `fr-CA`, `en`, `*`)
+    pub range: String,
+    /// Quality value, 0.0 to 1.0
+    pub quality: f32,
+}
+
+/// Parse an `Accept-Language` header into ranges ordered by descending
+/// quality. Ranges with `q=0` (not acceptable) or a malformed `q` are dropped;
+/// ties keep header order.
+pub fn parse_accept_language(header: &str) -> Vec<LanguageRange> {
+    let mut ranges: Vec<LanguageRange> = header
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let range = parts.next()?.trim();
+            if range.is_empty() {
+                return None;
+            }
+            let mut quality = 1.0;
+            for param in parts {
+                if let Some(q) = param.trim().strip_prefix("q=") {
+                    quality = q.trim().parse::<f32>().ok().filter(|q| (0.0..=1.0).contains(q))?;
+                }
+            }
+            (quality > 0.0).then(|| LanguageRange {
+                range: range.to_string(),
+                quality,
+            })
+        })
+        .collect();
+    // sort_by is stable, so equal qualities keep their header order
+    ranges.sort_by(|a, b| b.quality.total_cmp(&a.quality));
+    ranges
+}
+
+/// Pick the available language tag that best satisfies `accept_language`
+pub fn negotiate<'a, I>(accept_language: Option<&str>, available: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let available: Vec<&'a str> = available.into_iter().map(String::as_str).collect();
+    if available.is_empty() {
+        return None;
+    }
+
+    for LanguageRange { range, .. } in parse_accept_language(accept_language?) {
+        if range == "*" {
+            // Any language is fine: serve the default body
+            return None;
+        }
+        let found = available
+            .iter()
+            .find(|tag| tag.eq_ignore_ascii_case(&range))
+            .or_else(|| available.iter().find(|tag| is_prefix_tag(tag, &range)))
+            .or_else(|| available.iter().find(|tag| is_prefix_tag(&range, tag)));
+        if let Some(tag) = found {
+            return Some(tag);
+        }
+    }
+    None
+}
+
+/// Whether `prefix` is `tag` with one or more trailing subtags removed
+fn is_prefix_tag(prefix: &str, tag: &str) -> bool {
+    tag.len() > prefix.len()
+        && tag.as_bytes()[prefix.len()] == b'-'
+        && tag[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_orders_by_quality() {
+        let ranges = parse_accept_language("en;q=0.5, fr-CA, de;q=0.9, es;q=0");
+        let order: Vec<&str> = ranges.iter().map(|r| r.range.as_str()).collect();
+        assert_eq!(order, vec!["fr-CA", "de", "en"]);
+        assert_eq!(ranges[2].quality, 0.5);
+    }
+
+    #[test]
+    fn negotiate_prefers_higher_quality() {
+        let available = tags(&["en", "fr"]);
+        assert_eq!(negotiate(Some("en;q=0.4, fr;q=0.8"), &available), Some("fr"));
+        assert_eq!(negotiate(Some("fr;q=0.1, en"), &available), Some("en"));
+    }
+
+    #[test]
+    fn negotiate_matches_by_prefix() {
+        assert_eq!(negotiate(Some("fr-CA"), &tags(&["en", "fr"])), Some("fr"));
+        assert_eq!(negotiate(Some("fr"), &tags(&["en-US", "fr-FR"])), Some("fr-FR"));
+        assert_eq!(negotiate(Some("EN-us"), &tags(&["en-US"])), Some("en-US"));
+        // "en" must not match "eng"
+        assert_eq!(negotiate(Some("en"), &tags(&["eng"])), None);
+    }
+
+    #[test]
+    fn negotiate_falls_back_to_default() {
+        let available = tags(&["en", "fr"]);
+        assert_eq!(negotiate(None, &available), None);
+        assert_eq!(negotiate(Some("de, ja;q=0.5"), &available), None);
+        assert_eq!(negotiate(Some("*"), &available), None);
+        assert_eq!(negotiate(Some("fr;q=0"), &available), None);
+    }
+}
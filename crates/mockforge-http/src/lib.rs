@@ -197,9 +197,15 @@
 //! - [HTTP Mocking Guide](https://docs.mockforge.dev/user-guide/http-mocking.html)
 //! - [API Reference](https://docs.rs/mockforge-http)
 
+/// Make-service wrapper that delays new connections (`http.accept_delay_ms`)
+pub mod accept_delay;
 pub mod ai_handler;
 pub mod auth;
 pub mod chain_handlers;
+/// Connection limit with a bounded queue (`http.connection_limit`)
+pub mod connection_limit;
+/// Registry of live SSE/WebSocket connections
+pub mod connection_tracker;
 /// Cross-protocol consistency engine integration for HTTP
 pub mod consistency;
 /// Contract diff retrieval API
@@ -219,20 +225,34 @@ pub mod coverage;
 pub mod database {
     pub use mockforge_intelligence::database::*;
 }
+/// Runtime replay of recently failed (5xx) requests
+pub mod failed_replay_runtime;
 /// File generation service for creating mock PDF, CSV, JSON files
 pub mod file_generator;
 /// File serving for generated mock files
 pub mod file_server;
 /// Fixtures management API for hosted-mock deployments
 pub mod fixtures_api;
+/// Runtime global latency API
+pub mod global_latency_runtime;
 /// Kubernetes-native health check endpoints (liveness, readiness, startup probes)
 pub mod health;
 pub mod http_tracing_middleware;
+/// `Accept-Language` negotiation for localized custom-route responses
+pub mod language_negotiation;
 /// Latency profile configuration for HTTP request simulation
 pub mod latency_profiles;
 
 /// Mock LLM endpoint (OpenAI/Anthropic-compatible) for agent testing (#912).
 pub mod llm_mock;
+/// Fixed-rate load generation against an in-process router
+pub mod load_test;
+/// curl reproductions of logged requests
+pub mod log_curl_api;
+/// Long-polling access to the request log
+pub mod log_poll_api;
+/// Export of request log entries to external sinks (webhook)
+pub mod log_sink;
 
 /// Management API for server control and monitoring
 pub mod management;
@@ -247,6 +267,12 @@ pub mod mockai_api;
 /// Runtime network-profile switching API
 pub mod network_profile_runtime;
 pub mod op_middleware;
+/// Runtime network partition of selected routes
+pub mod partition_runtime;
+/// Regex path rewrites applied before routing
+pub mod path_rewrite;
+/// Pipelined HTTP/1.1 serving with response-ordering anomalies
+pub mod pipelining;
 /// Unified protocol server lifecycle implementation
 pub mod protocol_server;
 /// Browser/Mobile Proxy Server
@@ -257,11 +283,23 @@ pub mod quick_mock;
 pub mod rag_ai_generator;
 /// Reality-slider-driven mock/proxy switching middleware (#222)
 pub mod reality_proxy;
+/// Record-then-mock workflow: proxy-record a live API, then serve the capture
+pub mod record_then_mock;
 /// Replay listing and fixture management
 pub mod replay_listing;
 pub mod request_logging;
 /// Runtime route-scoped chaos rules API
 pub mod route_chaos_runtime;
+/// Request-dependent delays for custom routes
+pub mod route_delay;
+/// Multipart form matching for custom routes
+pub mod route_multipart;
+/// Protobuf request/response bodies for custom routes
+pub mod route_protobuf;
+/// Per-content-type request-body validation for custom routes
+pub mod route_validation;
+/// Runtime activation of configured scenario presets
+pub mod scenario_preset_runtime;
 /// Runtime named-scenario activation API
 #[cfg(feature = "scenario-engine")]
 pub mod scenarios_runtime;
@@ -281,6 +319,8 @@ pub mod token_response;
 pub mod ui_builder;
 /// Verification API for request verification
 pub mod verification;
+/// Multi-tenant workspace routing applied before routing
+pub mod workspace_routing;
 
 // Access review handlers
 pub mod handlers;
@@ -288,7 +328,7 @@ pub mod handlers;
 // Re-export AI handler utilities
 pub use ai_handler::{process_response_with_ai, AiResponseConfig, AiResponseHandler};
 // Re-export health check utilities
-pub use health::{HealthManager, ServiceStatus};
+pub use health::{HealthManager, HealthState, ServiceStatus};
 
 // Re-export management API utilities
 pub use management::{
@@ -312,7 +352,10 @@ pub use metrics_middleware::collect_http_metrics;
 pub use http_tracing_middleware::http_tracing_middleware;
 
 // Re-export coverage utilities
-pub use coverage::{calculate_coverage, CoverageReport, MethodCoverage, RouteCoverage};
+pub use coverage::{
+    calculate_coverage, calculate_validation_coverage, CoverageReport, MethodCoverage,
+    OperationValidationCoverage, RouteCoverage, ValidationCoverageReport,
+};
 
 /// Helper function to load persona from config file
 /// Tries to load from common config locations: config.yaml, mockforge.yaml, tools/mockforge/config.yaml
@@ -376,6 +419,7 @@ use mockforge_foundation::latency::LatencyProfile;
 use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::path::Path;
+use std::time::Duration;
 use tokio::fs;
 use tokio::sync::RwLock;
 use tracing::*;
@@ -404,6 +448,8 @@ pub struct HttpServerState {
     pub routes: Vec<RouteInfo>,
     /// Optional global rate limiter for request throttling
     pub rate_limiter: Option<Arc<middleware::rate_limit::GlobalRateLimiter>>,
+    /// Per-workspace rate limiters, applied whether or not `rate_limiter` is set
+    pub tenant_rate_limiter: Arc<middleware::TenantRateLimiter>,
     /// Production headers to add to all responses (for deceptive deploy)
     pub production_headers: Option<Arc<HashMap<String, String>>>,
 }
@@ -420,6 +466,7 @@ impl HttpServerState {
         Self {
             routes: Vec::new(),
             rate_limiter: None,
+            tenant_rate_limiter: Arc::new(middleware::TenantRateLimiter::new()),
             production_headers: None,
         }
     }
@@ -429,6 +476,7 @@ impl HttpServerState {
         Self {
             routes,
             rate_limiter: None,
+            tenant_rate_limiter: Arc::new(middleware::TenantRateLimiter::new()),
             production_headers: None,
         }
     }
@@ -449,22 +497,21 @@ impl HttpServerState {
     }
 }
 
+/// JSON shape of one route in the `/__mockforge/routes` responses
+fn route_info_json(route: &RouteInfo) -> serde_json::Value {
+    serde_json::json!({
+        "method": route.method,
+        "path": route.path,
+        "operation_id": route.operation_id,
+        "summary": route.summary,
+        "description": route.description,
+        "parameters": route.parameters
+    })
+}
+
 /// Handler to return OpenAPI routes information
 async fn get_routes_handler(State(state): State<HttpServerState>) -> Json<serde_json::Value> {
-    let route_info: Vec<serde_json::Value> = state
-        .routes
-        .iter()
-        .map(|route| {
-            serde_json::json!({
-                "method": route.method,
-                "path": route.path,
-                "operation_id": route.operation_id,
-                "summary": route.summary,
-                "description": route.description,
-                "parameters": route.parameters
-            })
-        })
-        .collect();
+    let route_info: Vec<serde_json::Value> = state.routes.iter().map(route_info_json).collect();
 
     Json(serde_json::json!({
         "routes": route_info,
@@ -472,6 +519,40 @@ async fn get_routes_handler(State(state): State<HttpServerState>) -> Json<serde_
     }))
 }
 
+/// Handler to return a single OpenAPI route by method and path template.
+///
+/// The path may be sent percent-encoded (`/__mockforge/routes/GET/%2Fusers%2F%7Bid%7D`)
+/// or as plain segments (`/__mockforge/routes/GET/users/%7Bid%7D`); it is
+/// decoded before the lookup. Answers 404 when no such route is registered.
+async fn get_route_handler(
+    State(state): State<HttpServerState>,
+    axum::extract::Path((method, path)): axum::extract::Path<(String, String)>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let path = if path.starts_with('/') {
+        path
+    } else {
+        format!("/{}", path)
+    };
+    match state
+        .routes
+        .iter()
+        .find(|route| route.method.eq_ignore_ascii_case(&method) && route.path == path)
+    {
+        Some(route) => Json(route_info_json(route)).into_response(),
+        None => (
+            axum::http::StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "Route not found",
+                "method": method.to_uppercase(),
+                "path": path
+            })),
+        )
+            .into_response(),
+    }
+}
+
 /// Handler to serve the Scalar API docs page
 async fn get_docs_handler() -> axum::response::Html<&'static str> {
     axum::response::Html(include_str!("../static/docs.html"))
@@ -504,7 +585,7 @@ fn apply_cors_middleware(
     cors_config: Option<mockforge_core::config::HttpCorsConfig>,
 ) -> Router {
     use http::Method;
-    use tower_http::cors::AllowOrigin;
+    use tower_http::cors::{AllowCredentials, AllowOrigin};
 
     if let Some(config) = cors_config {
         if !config.enabled {
@@ -520,12 +601,10 @@ fn apply_cors_middleware(
             is_wildcard_origin = true;
         } else if !config.allowed_origins.is_empty() {
             // Try to parse each origin, fallback to permissive if parsing fails
-            let origins: Vec<_> = config
+            let origins: Vec<http::HeaderValue> = config
                 .allowed_origins
                 .iter()
-                .filter_map(|origin| {
-                    origin.parse::<http::HeaderValue>().ok().map(AllowOrigin::exact)
-                })
+                .filter_map(|origin| origin.parse::<http::HeaderValue>().ok())
                 .collect();
 
             if origins.is_empty() {
@@ -533,21 +612,13 @@ fn apply_cors_middleware(
                 warn!("No valid CORS origins configured, using permissive CORS");
                 cors_layer = cors_layer.allow_origin(Any);
                 is_wildcard_origin = true;
+            } else if origins.len() == 1 {
+                cors_layer = cors_layer.allow_origin(AllowOrigin::exact(origins[0].clone()));
+                is_wildcard_origin = false;
             } else {
-                // Use the first origin as exact match (tower-http limitation)
-                // For multiple origins, we'd need a custom implementation
-                if origins.len() == 1 {
-                    cors_layer = cors_layer.allow_origin(origins[0].clone());
-                    is_wildcard_origin = false;
-                } else {
-                    // Multiple origins - use permissive for now
-                    warn!(
-                        "Multiple CORS origins configured, using permissive CORS. \
-                        Consider using '*' for all origins."
-                    );
-                    cors_layer = cors_layer.allow_origin(Any);
-                    is_wildcard_origin = true;
-                }
+                // Multiple origins - echo back whichever listed origin made the request
+                cors_layer = cors_layer.allow_origin(AllowOrigin::list(origins));
+                is_wildcard_origin = false;
             }
         } else {
             // No origins specified, use permissive for development
@@ -601,11 +672,26 @@ fn apply_cors_middleware(
             config.allow_credentials
         };
 
-        cors_layer = cors_layer.allow_credentials(should_allow_credentials);
+        if is_wildcard_origin || config.origin_credentials.is_empty() {
+            cors_layer = cors_layer.allow_credentials(should_allow_credentials);
+        } else {
+            // Per-origin credentials; unlisted origins fall back to `allow_credentials`
+            let origin_credentials = config.origin_credentials.clone();
+            cors_layer = cors_layer.allow_credentials(AllowCredentials::predicate(
+                move |origin: &http::HeaderValue, _parts: &http::request::Parts| {
+                    origin
+                        .to_str()
+                        .ok()
+                        .and_then(|origin| origin_credentials.get(origin).copied())
+                        .unwrap_or(should_allow_credentials)
+                },
+            ));
+        }
 
         info!(
-            "CORS middleware enabled with configured settings (credentials: {})",
-            should_allow_credentials
+            "CORS middleware enabled with configured settings (credentials: {}, per-origin overrides: {})",
+            should_allow_credentials,
+            config.origin_credentials.len()
         );
         app.layer(cors_layer)
     } else {
@@ -653,7 +739,10 @@ pub async fn build_router_with_multi_tenant(
             .and_then(|v| v.parse().ok())
             .unwrap_or(2000),
         per_ip: true,
-        per_endpoint: false,
+        per_endpoint: std::env::var("MOCKFORGE_RATE_LIMIT_PER_ENDPOINT")
+            .is_ok_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")),
+        trust_forwarded_headers: std::env::var("MOCKFORGE_RATE_LIMIT_TRUST_FORWARDED")
+            .is_ok_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")),
     };
 
     // Apply deceptive deploy configuration if enabled
@@ -674,6 +763,7 @@ pub async fn build_router_with_multi_tenant(
                     allowed_methods: prod_cors.allowed_methods.clone(),
                     allowed_headers: prod_cors.allowed_headers.clone(),
                     allow_credentials: prod_cors.allow_credentials,
+                    origin_credentials: Default::default(),
                 });
                 info!("Applied production-like CORS configuration");
             }
@@ -685,6 +775,7 @@ pub async fn build_router_with_multi_tenant(
                     burst: prod_rate_limit.burst,
                     per_ip: prod_rate_limit.per_ip,
                     per_endpoint: false,
+                    trust_forwarded_headers: prod_rate_limit.trust_forwarded_headers,
                 };
                 info!(
                     "Applied production-like rate limiting: {} req/min, burst: {}",
@@ -856,10 +947,7 @@ pub async fn build_router_with_multi_tenant(
                 // configurations; redoing it at the merge site is cheap
                 // insurance against the "200 OK before body completes"
                 // bug Srikanth reported.
-                let body_limit_mb = std::env::var("MOCKFORGE_HTTP_BODY_LIMIT_MB")
-                    .ok()
-                    .and_then(|v| v.parse::<usize>().ok())
-                    .unwrap_or(50);
+                let body_limit_mb = http_body_limit_mb();
                 let body_limit_bytes = body_limit_mb.saturating_mul(1024 * 1024);
                 let openapi_router =
                     openapi_router.layer(axum::extract::DefaultBodyLimit::max(body_limit_bytes));
@@ -909,7 +997,12 @@ pub async fn build_router_with_multi_tenant(
     // Create a router with state for the routes and coverage endpoints
     let routes_router = Router::new()
         .route("/__mockforge/routes", axum::routing::get(get_routes_handler))
+        .route("/__mockforge/routes/{method}/{*path}", axum::routing::get(get_route_handler))
         .route("/__mockforge/coverage", axum::routing::get(coverage::get_coverage_handler))
+        .route(
+            "/__mockforge/coverage/validation",
+            axum::routing::get(coverage::get_validation_coverage_handler),
+        )
         .with_state(state_for_routes);
 
     // Merge the routes router with the main app
@@ -963,6 +1056,12 @@ pub async fn build_router_with_multi_tenant(
     let ws_broadcast = Arc::new(ws_state.tx.clone());
     let management_state = management_state.with_ws_broadcast(ws_broadcast);
 
+    // Attach the effective config published at startup (config export, bulk updates)
+    let management_state = match mockforge_core::config::effective_config() {
+        Some(config) => management_state.with_server_config(Arc::new(RwLock::new(config))),
+        None => management_state,
+    };
+
     // Note: ProxyConfig not available in this build function path
     // Migration endpoints will work once ProxyConfig is passed to build_router_with_chains_and_multi_tenant
 
@@ -1056,14 +1155,21 @@ pub async fn build_router_with_multi_tenant(
         }
     }
 
+    // One lifecycle manager shared by the token lifecycle API, OAuth2 server and
+    // consent screen, so clock skew and revocations apply to issued tokens
+    let token_lifecycle_manager =
+        Arc::new(crate::auth::token_lifecycle::TokenLifecycleManager::default());
+
     // Add token lifecycle API
     {
-        use crate::auth::token_lifecycle::TokenLifecycleManager;
-        use crate::handlers::token_lifecycle::{token_lifecycle_router, TokenLifecycleState};
-        let lifecycle_manager = Arc::new(TokenLifecycleManager::default());
+        use crate::handlers::token_lifecycle::{
+            issued_tokens_router, token_lifecycle_router, TokenLifecycleState,
+        };
+        let lifecycle_manager = token_lifecycle_manager.clone();
         let lifecycle_state = TokenLifecycleState {
             manager: lifecycle_manager,
         };
+        app = app.nest("/__mockforge/api/auth", issued_tokens_router(lifecycle_state.clone()));
         app = app.nest("/api/v1/auth", token_lifecycle_router(lifecycle_state));
         debug!("Token lifecycle API mounted at /api/v1/auth");
     }
@@ -1071,11 +1177,10 @@ pub async fn build_router_with_multi_tenant(
     // Add OAuth2 server endpoints
     {
         use crate::auth::oidc::load_oidc_state;
-        use crate::auth::token_lifecycle::TokenLifecycleManager;
         use crate::handlers::oauth2_server::{oauth2_server_router, OAuth2ServerState};
         // Load OIDC state from configuration (environment variables or config file)
         let oidc_state = Arc::new(RwLock::new(load_oidc_state()));
-        let lifecycle_manager = Arc::new(TokenLifecycleManager::default());
+        let lifecycle_manager = token_lifecycle_manager.clone();
         let oauth2_state = OAuth2ServerState {
             oidc_state,
             lifecycle_manager,
@@ -1090,12 +1195,11 @@ pub async fn build_router_with_multi_tenant(
     {
         use crate::auth::oidc::load_oidc_state;
         use crate::auth::risk_engine::RiskEngine;
-        use crate::auth::token_lifecycle::TokenLifecycleManager;
         use crate::handlers::consent::{consent_router, ConsentState};
         use crate::handlers::oauth2_server::OAuth2ServerState;
         // Load OIDC state from configuration (environment variables or config file)
         let oidc_state = Arc::new(RwLock::new(load_oidc_state()));
-        let lifecycle_manager = Arc::new(TokenLifecycleManager::default());
+        let lifecycle_manager = token_lifecycle_manager.clone();
         let oauth2_state = OAuth2ServerState {
             oidc_state: oidc_state.clone(),
             lifecycle_manager: lifecycle_manager.clone(),
@@ -1126,6 +1230,10 @@ pub async fn build_router_with_multi_tenant(
 
     // Add request logging middleware to capture all requests
     app = app.layer(axum::middleware::from_fn(request_logging::log_http_requests));
+    app = app.nest(
+        "/__mockforge/api/logs",
+        log_curl_api::log_curl_api_router().merge(log_poll_api::log_poll_api_router()),
+    );
 
     // Add security middleware for security event tracking (after logging, before contract diff)
     app = app.layer(axum::middleware::from_fn(middleware::security_middleware));
@@ -1155,6 +1263,15 @@ pub async fn build_router_with_multi_tenant(
         app = app.layer(axum::middleware::from_fn(middleware::keepalive_hint_middleware));
     }
 
+    // Optionally break injected latency down per source (network profile,
+    // route chaos, handler) in a `Server-Timing` response header. Layered
+    // outside the latency middleware so they can record into it. Opt in via
+    // `http.server_timing` (or `MOCKFORGE_HTTP_SERVER_TIMING=1`).
+    if middleware::is_server_timing_enabled() {
+        info!("Server-Timing enabled — emitting Server-Timing headers");
+        app = app.layer(axum::middleware::from_fn(middleware::server_timing_middleware));
+    }
+
     // Issue #79 (round 5): per-request log line with HTTP version + Connection
     // header MockForge actually sees, so users debugging proxy ↔ MockForge
     // negotiation can confirm whether their proxy is speaking HTTP/1.1 with
@@ -1289,11 +1406,11 @@ pub async fn build_router_with_multi_tenant(
             // Wrap registry in Arc for shared access
             let registry = Arc::new(registry);
 
-            // Create workspace router and wrap the app with workspace middleware
-            let _workspace_router = WorkspaceRouter::new(registry);
-
-            // Note: The actual middleware integration would need to be implemented
-            // in the WorkspaceRouter to work with Axum's middleware system
+            // Resolve workspace prefixes before the app routes the request, so
+            // prefixed paths reach their routes and the rate limiter can read
+            // the `WorkspaceContext` for per-workspace limits.
+            let workspace_router = Arc::new(WorkspaceRouter::new(registry));
+            app = workspace_routing::with_workspace_routing(app, workspace_router);
             info!("Workspace routing middleware initialized for HTTP server");
         }
     }
@@ -1475,6 +1592,24 @@ pub async fn serve_router(
     serve_router_with_tls(port, app, None).await
 }
 
+/// Serve a provided router on an OS-assigned port on the loopback interface.
+///
+/// Binds `127.0.0.1:0` and returns the resolved address together with the
+/// future that serves the router; nothing is served until the future is
+/// polled (typically via `tokio::spawn`). Parallel tests use this instead of
+/// picking fixed ports that may collide.
+pub async fn serve_router_ephemeral(
+    app: Router,
+) -> std::io::Result<(
+    std::net::SocketAddr,
+    impl std::future::Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>> + Send,
+)> {
+    let listener = tokio::net::TcpListener::bind((std::net::Ipv4Addr::LOCALHOST, 0)).await?;
+    let addr = listener.local_addr()?;
+    info!("HTTP listening on {}", addr);
+    Ok((addr, serve_listener(listener, app, Duration::ZERO, None)))
+}
+
 /// Serve a provided router on the given port with optional TLS support.
 pub async fn serve_router_with_tls(
     port: u16,
@@ -1488,16 +1623,24 @@ pub async fn serve_router_with_tls(
 /// the caller via `bound_port_tx` with the actual OS-assigned port once the
 /// listener has bound. This matters when the requested `port` is `0` (ephemeral),
 /// because callers such as the `@mockforge-dev/sdk` need to learn the real port
-/// to connect to it. On the TLS path, `bound_port_tx` is currently ignored and
-/// the requested `port` is reported verbatim — callers that need dynamic TLS
-/// ports should bind on a known port.
+/// to connect to it. With TLS enabled the port is reported once the HTTPS
+/// listener is accepting connections.
 pub async fn serve_router_with_tls_notify(
     port: u16,
     app: Router,
     tls_config: Option<mockforge_core::config::HttpTlsConfig>,
     bound_port_tx: Option<tokio::sync::oneshot::Sender<u16>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    serve_router_with_tls_notify_chaos(port, app, tls_config, bound_port_tx, None).await
+    serve_router_with_tls_notify_chaos(
+        port,
+        app,
+        tls_config,
+        bound_port_tx,
+        None,
+        Duration::ZERO,
+        None,
+    )
+    .await
 }
 
 /// Serve a router with optional TLS *and* an optional shared chaos config.
@@ -1508,24 +1651,27 @@ pub async fn serve_router_with_tls_notify(
 /// This is the only way to surface real transport-level failures rather than
 /// HTTP 503 responses on healthy connections. TLS path does not yet support
 /// chaos listener wrapping (axum-server uses its own accept loop).
+///
+/// Every new connection, plain or TLS, waits `accept_delay` before it is
+/// served (see [`accept_delay`]); pass [`Duration::ZERO`] for none. With a
+/// `connection_limit`, connections beyond it queue or get 503 (see
+/// [`connection_limit`]).
 pub async fn serve_router_with_tls_notify_chaos(
     port: u16,
     app: Router,
     tls_config: Option<mockforge_core::config::HttpTlsConfig>,
     bound_port_tx: Option<tokio::sync::oneshot::Sender<u16>>,
     chaos_config: Option<Arc<RwLock<mockforge_chaos::ChaosConfig>>>,
+    accept_delay: Duration,
+    connection_limit: Option<mockforge_core::config::HttpConnectionLimitConfig>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    use std::net::SocketAddr;
-
     let addr = mockforge_core::wildcard_socket_addr(port);
+    let limiter = connection_limit.as_ref().map(connection_limit::ConnectionLimiter::new);
 
     if let Some(ref tls) = tls_config {
         if tls.enabled {
             info!("HTTPS listening on {}", addr);
-            if let Some(tx) = bound_port_tx {
-                let _ = tx.send(port);
-            }
-            return serve_with_tls(addr, app, tls).await;
+            return serve_with_tls(addr, app, tls, bound_port_tx, accept_delay, limiter).await;
         }
     }
 
@@ -1543,14 +1689,12 @@ pub async fn serve_router_with_tls_notify_chaos(
         let _ = tx.send(actual_port);
     }
 
-    // Wrap the Router with OData URI rewrite.
-    // Router::layer() only applies to matched routes, so we must wrap at the service level
-    // to rewrite URIs BEFORE route matching occurs.
-    let odata_app = tower::ServiceBuilder::new()
-        .layer(mockforge_core::odata_rewrite::ODataRewriteLayer)
-        .service(app);
     if let Some(cfg) = chaos_config {
         info!("HTTP listener wrapped with chaos TCP listener (RST/FIN injection enabled)");
+        // Wrap the Router with OData URI rewrite (same as `serve_listener`).
+        let odata_app = tower::ServiceBuilder::new()
+            .layer(mockforge_core::odata_rewrite::ODataRewriteLayer)
+            .service(app);
         let chaos_listener = mockforge_chaos::ChaosTcpListener::new(listener, cfg);
         // Layer that copies ConnectInfo<ChaosClientAddr> → ConnectInfo<SocketAddr>
         // so existing handlers using `ConnectInfo<SocketAddr>` keep working.
@@ -1562,19 +1706,40 @@ pub async fn serve_router_with_tls_notify_chaos(
         >(app_with_addr_compat);
         // Bump the accept counter on each connection that gets through chaos.
         let counted = counting_listener::CountingMakeService::new(make_svc);
-        axum::serve(chaos_listener, counted).await?;
+        let limited = connection_limit::ConnectionLimitMakeService::new(counted, limiter);
+        let delayed = accept_delay::AcceptDelayMakeService::new(limited, accept_delay);
+        axum::serve(chaos_listener, delayed).await?;
     } else {
-        let make_svc = axum::ServiceExt::<Request<Body>>::into_make_service_with_connect_info::<
-            SocketAddr,
-        >(odata_app);
-        // Bump the accept counter once per accepted connection so the
-        // dashboard sampler can derive CPS.
-        let counted = counting_listener::CountingMakeService::new(make_svc);
-        axum::serve(listener, counted).await?;
+        serve_listener(listener, app, accept_delay, limiter).await?;
     }
     Ok(())
 }
 
+/// Serve a router on an already-bound plain TCP listener
+async fn serve_listener(
+    listener: tokio::net::TcpListener,
+    app: Router,
+    accept_delay: Duration,
+    limiter: Option<Arc<connection_limit::ConnectionLimiter>>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Wrap the Router with OData URI rewrite.
+    // Router::layer() only applies to matched routes, so we must wrap at the service level
+    // to rewrite URIs BEFORE route matching occurs.
+    let odata_app = tower::ServiceBuilder::new()
+        .layer(mockforge_core::odata_rewrite::ODataRewriteLayer)
+        .service(app);
+    let make_svc = axum::ServiceExt::<Request<Body>>::into_make_service_with_connect_info::<
+        std::net::SocketAddr,
+    >(odata_app);
+    // Bump the accept counter once per accepted connection so the
+    // dashboard sampler can derive CPS.
+    let counted = counting_listener::CountingMakeService::new(make_svc);
+    let limited = connection_limit::ConnectionLimitMakeService::new(counted, limiter);
+    let delayed = accept_delay::AcceptDelayMakeService::new(limited, accept_delay);
+    axum::serve(listener, delayed).await?;
+    Ok(())
+}
+
 /// Mirror `ConnectInfo<ChaosClientAddr>` (set by the chaos listener) onto
 /// `ConnectInfo<SocketAddr>` so handlers extracting `ConnectInfo<SocketAddr>`
 /// keep working when chaos TCP wrapping is enabled.
@@ -1600,6 +1765,9 @@ async fn serve_with_tls(
     addr: std::net::SocketAddr,
     app: Router,
     tls_config: &mockforge_core::config::HttpTlsConfig,
+    bound_port_tx: Option<tokio::sync::oneshot::Sender<u16>>,
+    accept_delay: Duration,
+    limiter: Option<Arc<connection_limit::ConnectionLimiter>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     use axum_server::tls_rustls::RustlsConfig;
     use std::net::SocketAddr;
@@ -1630,10 +1798,25 @@ async fn serve_with_tls(
     // Bump the accept counter once per successful TLS handshake / connection
     // so the dashboard sampler can derive CPS for HTTPS-only setups.
     let counted = counting_listener::CountingMakeService::new(make_svc);
+    let limited = connection_limit::ConnectionLimitMakeService::new(counted, limiter);
+    let delayed = accept_delay::AcceptDelayMakeService::new(limited, accept_delay);
+
+    // Report the bound port once axum-server is listening, so ephemeral
+    // (port 0) HTTPS servers are reachable just like plain HTTP ones.
+    let handle: axum_server::Handle = axum_server::Handle::new();
+    if let Some(tx) = bound_port_tx {
+        let handle = handle.clone();
+        tokio::spawn(async move {
+            if let Some(bound) = handle.listening().await {
+                let _ = tx.send(bound.port());
+            }
+        });
+    }
 
     // Serve with TLS using axum-server
     axum_server::bind_rustls(addr, rustls_config)
-        .serve(counted)
+        .handle(handle)
+        .serve(delayed)
         .await
         .map_err(|e| format!("HTTPS server error: {}", e).into())
 }
@@ -1726,13 +1909,17 @@ async fn apply_route_chaos(
     injector: Option<&dyn mockforge_core::priority_handler::RouteChaosInjectorTrait>,
     method: &http::Method,
     uri: &http::Uri,
+    headers: &http::HeaderMap,
+    client: Option<std::net::IpAddr>,
 ) -> Option<axum::response::Response> {
     use axum::http::StatusCode;
     use axum::response::IntoResponse;
 
     if let Some(injector) = injector {
         // Check for fault injection first
-        if let Some(fault_response) = injector.get_fault_response(method, uri) {
+        if let Some(fault_response) =
+            injector.get_fault_response_for_client(method, uri, headers, client)
+        {
             // Return fault response
             let mut response = Json(serde_json::json!({
                 "error": fault_response.error_message,
@@ -1741,11 +1928,14 @@ async fn apply_route_chaos(
             .into_response();
             *response.status_mut() = StatusCode::from_u16(fault_response.status_code)
                 .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            if let Some(secs) = fault_response.retry_after_secs {
+                response.headers_mut().insert(http::header::RETRY_AFTER, secs.into());
+            }
             return Some(response);
         }
 
         // Inject latency if configured (this is async and may delay the request)
-        if let Err(e) = injector.inject_latency(method, uri).await {
+        if let Err(e) = injector.inject_latency_with_headers(method, uri, headers).await {
             tracing::warn!("Failed to inject latency: {}", e);
         }
     }
@@ -1753,1874 +1943,4816 @@ async fn apply_route_chaos(
     None // No fault response, processing should continue
 }
 
-/// Build the base HTTP router with chaining and multi-tenant support
-#[allow(clippy::too_many_arguments)]
-#[allow(deprecated)] // uses core engines (DriftBudgetEngine, ThreatAnalyzer, Forecaster, ProtocolContractRegistry, MockAI, MultiTenantWorkspaceRegistry, etc.) that stay in core
-pub async fn build_router_with_chains_and_multi_tenant(
-    spec_path: Option<String>,
-    options: Option<ValidationOptions>,
-    _circling_config: Option<mockforge_core::request_chaining::ChainConfig>,
-    multi_tenant_config: Option<mockforge_foundation::multi_tenant_types::MultiTenantConfig>,
-    route_configs: Option<Vec<mockforge_core::config::RouteConfig>>,
-    cors_config: Option<mockforge_core::config::HttpCorsConfig>,
-    _ai_generator: Option<Arc<dyn mockforge_openapi::response::AiGenerator + Send + Sync>>,
-    smtp_registry: Option<Arc<dyn std::any::Any + Send + Sync>>,
-    mqtt_broker: Option<Arc<dyn std::any::Any + Send + Sync>>,
-    amqp_broker: Option<Arc<dyn std::any::Any + Send + Sync>>,
-    kafka_broker: Option<Arc<dyn std::any::Any + Send + Sync>>,
-    traffic_shaper: Option<mockforge_core::traffic_shaping::TrafficShaper>,
-    traffic_shaping_enabled: bool,
-    health_manager: Option<Arc<HealthManager>>,
-    mockai: Option<Arc<RwLock<mockforge_core::intelligent_behavior::MockAI>>>,
-    deceptive_deploy_config: Option<mockforge_core::config::DeceptiveDeployConfig>,
-    proxy_config: Option<mockforge_proxy::config::ProxyConfig>,
-) -> Router {
-    use crate::latency_profiles::LatencyProfiles;
-    use crate::op_middleware::Shared;
-    use mockforge_core::Overrides;
+/// Build the handler for a custom route from config.
+///
+/// Returns the configured response with template expansion. Supports both basic
+/// templates ({{uuid}}, {{now}}) and request-aware templates
+/// ({{request.query.name}}, {{request.path.id}}, {{request.headers.name}}).
+/// Registered with `any()` since we need full Request access for template
+/// expansion; other methods get 405, except HEAD on GET routes, which is
+/// answered like GET without a body.
+fn custom_route_handler(
+    route_config: &mockforge_core::config::RouteConfig,
+    template_expand: bool,
+    injector: Option<Arc<dyn mockforge_core::priority_handler::RouteChaosInjectorTrait>>,
+    chaos_state: route_chaos_runtime::RuntimeRouteChaosState,
+) -> axum::routing::MethodRouter {
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
 
-    // Extract template expansion setting before options is moved (used in OpenAPI routes and custom routes)
-    let template_expand =
-        options.as_ref().map(|o| o.response_template_expand).unwrap_or_else(|| {
-            std::env::var("MOCKFORGE_RESPONSE_TEMPLATE_EXPAND")
-                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-                .unwrap_or(false)
+    let status = route_config.response.status;
+    let body = route_config.response.body.clone();
+    let localized = Arc::new(route_config.response.localized.clone());
+    let headers = route_config.response.headers.clone();
+    // String bodies on routes declaring an XML Content-Type are served as raw XML
+    let xml_body = headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("content-type") && value.to_ascii_lowercase().contains("xml")
+    });
+    let stream = route_config.response.stream.clone();
+    let body_transfer = route_config
+        .latency
+        .as_ref()
+        .and_then(route_chaos_runtime::body_transfer_duration);
+    let consistent_id = route_config.response.consistent_id.clone();
+    let deprecation = route_config.response.deprecation.clone();
+    let compress = route_config.response.compress;
+    let reason_phrase = route_config.response.reason_phrase.clone();
+    let quota = route_config
+        .response
+        .quota
+        .clone()
+        .map(|config| Arc::new(QuotaWindow::new(config)));
+    let flaky = route_config
+        .response
+        .flaky
+        .clone()
+        .map(|config| Arc::new(FlakyRoute::new(config)));
+    let error_ramp = route_config
+        .response
+        .error_ramp
+        .clone()
+        .map(|config| Arc::new(ErrorRamp::new(config)));
+    let body_validator = route_config
+        .request
+        .as_ref()
+        .and_then(|request| request.validation.as_ref())
+        .and_then(|validation| match route_validation::RouteBodyValidator::new(validation) {
+            Ok(validator) => Some(Arc::new(validator)),
+            Err(e) => {
+                warn!(
+                    "Ignoring request validation for {} {}: {}",
+                    route_config.method, route_config.path, e
+                );
+                None
+            }
         });
-
-    let _shared = Shared {
-        profiles: LatencyProfiles::default(),
-        overrides: Overrides::default(),
-        failure_injector: None,
-        traffic_shaper,
-        overrides_enabled: false,
-        traffic_shaping_enabled,
+    let protobuf = route_config.response.protobuf.as_ref().and_then(|config| {
+        match route_protobuf::RouteProtobufCodec::new(config) {
+            Ok(codec) => Some(Arc::new(codec)),
+            Err(e) => {
+                warn!(
+                    "Ignoring protobuf config for {} {}: {}",
+                    route_config.method, route_config.path, e
+                );
+                None
+            }
+        }
+    });
+    let delay =
+        route_config
+            .response
+            .delay
+            .as_ref()
+            .and_then(|config| match route_delay::RouteDelay::new(config) {
+                Ok(delay) => Some(Arc::new(delay)),
+                Err(e) => {
+                    warn!(
+                        "Ignoring delay expression for {} {}: {}",
+                        route_config.method, route_config.path, e
+                    );
+                    None
+                }
+            });
+    let expected_method = route_config.method.to_uppercase();
+    // HEAD requests to GET routes run the GET response with the body stripped,
+    // unless disabled with MOCKFORGE_HTTP_AUTO_HEAD=false
+    let auto_head = expected_method == "GET"
+        && std::env::var("MOCKFORGE_HTTP_AUTO_HEAD")
+            .map(|v| !(v == "0" || v.eq_ignore_ascii_case("false")))
+            .unwrap_or(true);
+    let route_path = route_config.path.clone();
+    // Response variants: the selector picks an index into `variant_keys`
+    let variants = Arc::new(route_config.response.variants.clone());
+    let variant_keys: Arc<Vec<String>> =
+        Arc::new((0..variants.len()).map(|i| i.to_string()).collect());
+    let variant_selector = {
+        use mockforge_core::config::RouteResponseSelection;
+        use mockforge_foundation::response_selection::{ResponseSelectionMode, ResponseSelector};
+        let selector = match route_config.response.selection {
+            RouteResponseSelection::RoundRobin => {
+                ResponseSelector::new(ResponseSelectionMode::Sequential)
+            }
+            RouteResponseSelection::Random => ResponseSelector::new(ResponseSelectionMode::Random),
+            RouteResponseSelection::Weighted => {
+                ResponseSelector::new(ResponseSelectionMode::WeightedRandom).with_weights(
+                    variant_keys.iter().cloned().zip(variants.iter().map(|v| v.weight)).collect(),
+                )
+            }
+            // Unused: `hash_on` or the sequence picks the variant directly
+            RouteResponseSelection::Hash | RouteResponseSelection::Sequence => {
+                ResponseSelector::new(ResponseSelectionMode::Sequential)
+            }
+        };
+        Arc::new(selector)
     };
+    // Scripted sequences, and round robin per session when `hash_on` names
+    // the attributes identifying a session
+    let sequence = {
+        use mockforge_core::config::RouteResponseSelection;
+        match route_config.response.selection {
+            RouteResponseSelection::Sequence => Some(Arc::new(VariantSequence::new(true))),
+            RouteResponseSelection::RoundRobin if !route_config.response.hash_on.is_empty() => {
+                Some(Arc::new(VariantSequence::new(false)))
+            }
+            _ => None,
+        }
+    };
+    let hashes_request = route_config.response.selection
+        == mockforge_core::config::RouteResponseSelection::Hash
+        || (sequence.is_some() && !route_config.response.hash_on.is_empty());
+    let hash_on: Option<Arc<Vec<VariantHashAttribute>>> = hashes_request.then(|| {
+        Arc::new(VariantHashAttribute::parse_all(
+            &route_config.response.hash_on,
+            &route_config.path,
+        ))
+    });
+    let buffer_body = body_validator.is_some()
+        || protobuf.as_ref().is_some_and(|codec| codec.decodes_requests())
+        || delay.as_ref().is_some_and(|delay| delay.reads_body())
+        || hash_on.as_ref().is_some_and(|a| a.iter().any(VariantHashAttribute::reads_body));
+    let variant_flags: Arc<Vec<Vec<String>>> =
+        Arc::new(variants.iter().map(|v| v.feature_flags.clone()).collect());
+    let schedules: Arc<Vec<VariantSchedule>> = Arc::new(
+        variants
+            .iter()
+            .map(|v| VariantSchedule::new(v.time_window.as_ref(), &route_config.path))
+            .collect(),
+    );
+    let variant_multipart: Arc<Vec<Option<mockforge_core::config::RouteMultipartMatch>>> =
+        Arc::new(variants.iter().map(|v| v.multipart.clone()).collect());
+    let variant_raw_query: Arc<Vec<Option<regex::Regex>>> = Arc::new(
+        variants
+            .iter()
+            .map(|v| {
+                let pattern = v.raw_query_regex.as_deref()?;
+                match regex::Regex::new(pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        warn!(
+                            "Ignoring raw_query_regex for {} {}: {}",
+                            route_config.method, route_config.path, e
+                        );
+                        None
+                    }
+                }
+            })
+            .collect(),
+    );
+    // Multipart bodies are parsed when variants match on them or templates may echo them
+    let reads_multipart = template_expand || variant_multipart.iter().any(Option::is_some);
+    // Clone Arc for the closure - Arc is Send-safe
+    // Note: RouteChaosInjector is marked as Send+Sync via unsafe impl, so we have to
+    // clone the Arc rather than move the inner injector into the route handler.
+    let injector_clone = injector;
+    axum::routing::any(move |mut req: Request<Body>| {
+        let body = body.clone();
+        let localized = localized.clone();
+        let mut headers = headers.clone();
+        let variants = variants.clone();
+        let variant_keys = variant_keys.clone();
+        let variant_selector = variant_selector.clone();
+        let sequence = sequence.clone();
+        let hash_on = hash_on.clone();
+        let schedules = schedules.clone();
+        let variant_flags = variant_flags.clone();
+        let variant_multipart = variant_multipart.clone();
+        let variant_raw_query = variant_raw_query.clone();
+        let stream = stream.clone();
+        let consistent_id = consistent_id.clone();
+        let deprecation = deprecation.clone();
+        let reason_phrase = reason_phrase.clone();
+        let quota = quota.clone();
+        let flaky = flaky.clone();
+        let error_ramp = error_ramp.clone();
+        let body_validator = body_validator.clone();
+        let protobuf = protobuf.clone();
+        let delay = delay.clone();
+        let expand = template_expand;
+        let expected = expected_method.clone();
+        let mut status_code = status;
+        // Clone Arc again for the async block
+        let injector_for_chaos = injector_clone.clone();
+        let chaos_state = chaos_state.clone();
+        let route_path = route_path.clone();
+
+        async move {
+            // Serve HEAD as GET; axum drops the body of responses to HEAD
+            // requests after setting Content-Length, so headers match GET
+            if auto_head && req.method() == http::Method::HEAD {
+                *req.method_mut() = http::Method::GET;
+            }
 
-    // Start with basic router
-    let mut app = Router::new();
-    let mut include_default_health = true;
-    let mut captured_routes: Vec<RouteInfo> = Vec::new();
-
-    // If an OpenAPI spec is provided, integrate it
-    if let Some(ref spec) = spec_path {
-        match OpenApiSpec::from_file(&spec).await {
-            Ok(openapi) => {
-                info!("Loaded OpenAPI spec from {}", spec);
+            // Check if request method matches expected method
+            if req.method().as_str() != expected.as_str() {
+                // Return 405 Method Not Allowed for wrong method
+                return axum::response::Response::builder()
+                    .status(StatusCode::METHOD_NOT_ALLOWED)
+                    .header(
+                        "Allow",
+                        if auto_head {
+                            "GET, HEAD"
+                        } else {
+                            expected.as_str()
+                        },
+                    )
+                    .body(Body::empty())
+                    .unwrap()
+                    .into_response();
+            }
 
-                // Try to load persona from config if available
-                let persona = load_persona_from_config().await;
+            // Protobuf bodies need a request message to decode them with
+            let protobuf_request = route_protobuf::is_protobuf(
+                req.headers().get(http::header::CONTENT_TYPE).and_then(|v| v.to_str().ok()),
+            );
+            if protobuf_request && !protobuf.as_ref().is_some_and(|codec| codec.decodes_requests())
+            {
+                return route_validation::BodyValidationError::UnsupportedMediaType(
+                    "Protobuf request bodies need a protobuf descriptor configured for this route"
+                        .to_string(),
+                )
+                .into_response();
+            }
 
-                let mut registry = if let Some(opts) = options {
-                    tracing::debug!("Using custom validation options");
-                    if let Some(ref persona) = persona {
-                        tracing::info!("Using persona '{}' for route generation", persona.name);
-                    }
-                    OpenApiRouteRegistry::new_with_options_and_persona(openapi, opts, persona)
+            let content_type = req
+                .headers()
+                .get(http::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let multipart_request =
+                reads_multipart && route_multipart::is_multipart(content_type.as_deref());
+            // JSON bodies are exposed to templates as `{{request.body.*}}`,
+            // unless they declare a length over the templating limit
+            let json_template_request = expand
+                && route_validation::is_json_content_type(content_type.as_deref())
+                && req
+                    .headers()
+                    .get(http::header::CONTENT_LENGTH)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .is_none_or(|len| len <= TEMPLATE_BODY_LIMIT);
+
+            // Buffer the body when it is validated, hashed, decoded, templated
+            // or a multipart form, validating it against the schema for its Content-Type
+            let mut decoded_body = None;
+            let (req, request_body) = if buffer_body || multipart_request || json_template_request {
+                // Bodies are capped at the server's body limit, or at the
+                // templating limit when read only for templating, even when
+                // they are chunked and declare no length
+                let (limit, limit_name) = if buffer_body || multipart_request {
+                    (http_body_limit_mb().saturating_mul(1024 * 1024), "body")
                 } else {
-                    tracing::debug!("Using environment-based options");
-                    if let Some(ref persona) = persona {
-                        tracing::info!("Using persona '{}' for route generation", persona.name);
+                    (TEMPLATE_BODY_LIMIT, "templating")
+                };
+                let payload_too_large = || {
+                    (
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        Json(serde_json::json!({
+                            "error": "payload_too_large",
+                            "message": format!(
+                                "Request body exceeds the {} byte {} limit",
+                                limit, limit_name
+                            ),
+                        })),
+                    )
+                        .into_response()
+                };
+                let declared_len = req
+                    .headers()
+                    .get(http::header::CONTENT_LENGTH)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<usize>().ok());
+                if declared_len.is_some_and(|len| len > limit) {
+                    return payload_too_large();
+                }
+                let (parts, body) = req.into_parts();
+                let bytes = match axum::body::to_bytes(body, limit).await {
+                    Ok(bytes) => bytes,
+                    Err(e)
+                        if std::error::Error::source(&e)
+                            .is_some_and(|e| e.is::<http_body_util::LengthLimitError>()) =>
+                    {
+                        return payload_too_large();
+                    }
+                    Err(e) => {
+                        return route_validation::BodyValidationError::Invalid(format!(
+                            "Failed to read request body: {}",
+                            e
+                        ))
+                        .into_response();
                     }
-                    OpenApiRouteRegistry::new_with_env_and_persona(openapi, persona)
                 };
+                if let (true, Some(codec)) = (protobuf_request, &protobuf) {
+                    match codec.decode_request(&bytes) {
+                        Ok(value) => decoded_body = Some(value),
+                        Err(e) => return e.into_response(),
+                    }
+                } else if let Some(validator) = body_validator {
+                    let content_type =
+                        parts.headers.get(http::header::CONTENT_TYPE).and_then(|v| v.to_str().ok());
+                    if let Err(e) = validator.validate(content_type, &bytes) {
+                        return e.into_response();
+                    }
+                }
+                // Bodies that are not valid JSON (or too large) are simply
+                // not available to templates
+                if decoded_body.is_none()
+                    && json_template_request
+                    && bytes.len() <= TEMPLATE_BODY_LIMIT
+                {
+                    decoded_body = serde_json::from_slice(&bytes).ok();
+                }
+                (Request::from_parts(parts, Body::from(bytes.clone())), bytes)
+            } else {
+                (req, axum::body::Bytes::new())
+            };
+            let multipart_form = match content_type.filter(|_| multipart_request) {
+                Some(content_type) => {
+                    route_multipart::MultipartForm::parse(&content_type, request_body.clone()).await
+                }
+                None => None,
+            };
 
-                // Load custom fixtures if enabled
-                let fixtures_dir = std::env::var("MOCKFORGE_FIXTURES_DIR")
-                    .unwrap_or_else(|_| "/app/fixtures".to_string());
-                let custom_fixtures_enabled = std::env::var("MOCKFORGE_CUSTOM_FIXTURES_ENABLED")
-                    .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
-                    .unwrap_or(true); // Enabled by default
+            // Requests over the route's quota for the current window get the quota response
+            if let Some(quota) = &quota {
+                if let Some(retry_after) = quota.hit(mockforge_foundation::clock::now()) {
+                    return quota.exceeded_response(retry_after);
+                }
+            }
 
-                if custom_fixtures_enabled {
-                    use mockforge_openapi::CustomFixtureLoader;
-                    use std::path::PathBuf;
-                    use std::sync::Arc;
+            // Flaky routes serve their failure response on the configured calls
+            if let Some(flaky) = &flaky {
+                if flaky.next_fails() {
+                    return flaky.failure_response();
+                }
+            }
 
-                    let fixtures_path = PathBuf::from(&fixtures_dir);
-                    let mut custom_loader = CustomFixtureLoader::new(fixtures_path, true);
+            // Error ramps fail a growing share of requests over time
+            if let Some(error_ramp) = &error_ramp {
+                if error_ramp.fails_at(mockforge_foundation::clock::now()) {
+                    return error_ramp.failure_response();
+                }
+            }
 
-                    if let Err(e) = custom_loader.load_fixtures().await {
-                        tracing::warn!("Failed to load custom fixtures: {}", e);
-                    } else {
-                        tracing::info!("Custom fixtures loaded from {}", fixtures_dir);
-                        registry = registry.with_custom_fixture_loader(Arc::new(custom_loader));
+            // Apply advanced routing features (fault injection and latency) if available
+            // Use helper function to avoid capturing RouteChaosInjector in closure
+            // Pass the Arc as a reference to the helper function
+            let chaos_started = std::time::Instant::now();
+            if let Some(fault_response) = apply_route_chaos(
+                injector_for_chaos.as_deref(),
+                req.method(),
+                req.uri(),
+                req.headers(),
+                req.extensions()
+                    .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+                    .map(|info| info.0.ip()),
+            )
+            .await
+            {
+                chaos_state.record_fault(
+                    route_chaos_runtime::RuleSource::Static,
+                    &expected,
+                    &route_path,
+                );
+                return fault_response;
+            }
+            if injector_for_chaos.is_some() {
+                middleware::server_timing::ServerTimings::record_in(
+                    req.extensions(),
+                    middleware::server_timing::ROUTE_CHAOS_METRIC,
+                    chaos_started.elapsed(),
+                );
+            }
+
+            // Hold the response for the delay computed from this request
+            if let Some(delay) = &delay {
+                tokio::time::sleep(delay.duration(&req, &request_body)).await;
+            }
+
+            // Pick a response variant among those whose time window (if any) is
+            // active and whose feature flags, multipart conditions and raw query
+            // regex (if any) the request satisfies; its status, headers and body
+            // override the route's
+            let eligible = raw_query_matched_variants(
+                route_multipart::multipart_matched_variants(
+                    flag_matched_variants(
+                        eligible_variants(&schedules, mockforge_foundation::clock::now()),
+                        &variant_flags,
+                        &request_feature_flags(req.headers()),
+                    ),
+                    &variant_multipart,
+                    multipart_form.as_ref(),
+                ),
+                &variant_raw_query,
+                req.uri().query().unwrap_or_default(),
+            );
+            let variant = (!eligible.is_empty()).then(|| {
+                let pick = match (&sequence, &hash_on) {
+                    (Some(sequence), session) => {
+                        let session = session
+                            .as_ref()
+                            .map_or(0, |attributes| variant_hash(attributes, &req, &request_body));
+                        sequence.next(session, eligible.len())
+                    }
+                    (None, Some(attributes)) => {
+                        (variant_hash(attributes, &req, &request_body) % eligible.len() as u64)
+                            as usize
                     }
+                    (None, None) => {
+                        let keys: Vec<String> =
+                            eligible.iter().map(|&i| variant_keys[i].clone()).collect();
+                        variant_selector.select(&keys)
+                    }
+                };
+                &variants[eligible[pick]]
+            });
+            let variant_body = variant.and_then(|v| {
+                status_code = v.status.unwrap_or(status_code);
+                headers.extend(v.headers.clone());
+                v.body.clone()
+            });
+
+            // Pick a localized body by Accept-Language, falling back to the default body
+            let accept_language =
+                req.headers().get(http::header::ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok());
+            let content_language = match variant_body {
+                Some(_) => None,
+                None => language_negotiation::negotiate(accept_language, localized.keys()),
+            };
+            let body = match (variant_body, content_language) {
+                (Some(variant_body), _) => Some(variant_body),
+                (None, Some(tag)) => localized.get(tag).cloned(),
+                (None, None) => body,
+            };
+
+            // Create JSON response from body, or empty object if None
+            let mut body_value = body.unwrap_or(serde_json::json!({}));
+
+            // Apply template expansion if enabled
+            // Use mockforge-template-expansion crate which is completely isolated
+            // from mockforge-core to avoid Send issues (no rng() in dependency chain)
+            if expand {
+                use serde_json::Value;
+
+                let mut context = custom_route_request_context(&req);
+                context.body = decoded_body.clone();
+                if let Some(form) = &multipart_form {
+                    context.multipart_fields = form
+                        .fields
+                        .iter()
+                        .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+                        .collect();
+                    context.multipart_files = form
+                        .files
+                        .iter()
+                        .map(|(name, file)| {
+                            (name.clone(), file.file_name.clone().unwrap_or_default())
+                        })
+                        .collect();
                 }
 
-                if registry
-                    .routes()
-                    .iter()
-                    .any(|route| route.method == "GET" && route.path == "/health")
+                // Perform template expansion in spawn_blocking to ensure Send safety
+                // The template expansion crate is completely isolated from mockforge-core
+                // and doesn't have rng() in its dependency chain
+                let body_value_clone = body_value.clone();
+                let context_clone = context.clone();
+                body_value = match tokio::task::spawn_blocking(move || match body_value_clone {
+                    // XML bodies: escape substituted values so the document stays well-formed
+                    Value::String(xml) if xml_body => Value::String(
+                        mockforge_template_expansion::expand_templates_in_xml(&xml, &context_clone),
+                    ),
+                    other => mockforge_template_expansion::expand_templates_in_json(
+                        other,
+                        &context_clone,
+                    ),
+                })
+                .await
                 {
-                    include_default_health = false;
+                    Ok(result) => result,
+                    Err(_) => body_value, // Fallback to original on error
+                };
+            }
+
+            // Stamp the id tracked for this request's key onto object bodies
+            if let (Some(consistent_id), serde_json::Value::Object(fields)) =
+                (&consistent_id, &mut body_value)
+            {
+                let key = match mockforge_template_expansion::expand_templates_in_json(
+                    serde_json::Value::String(consistent_id.key.clone()),
+                    &custom_route_request_context(&req),
+                ) {
+                    serde_json::Value::String(key) => key,
+                    other => other.to_string(),
+                };
+                let engine = req
+                    .extensions()
+                    .get::<consistency::ConsistencyMiddlewareState>()
+                    .map(|state| state.engine.clone());
+                let workspace_id = req
+                    .extensions()
+                    .get::<mockforge_core::consistency::UnifiedState>()
+                    .map_or_else(|| "default".to_string(), |state| state.workspace_id.clone());
+                let entity_type = consistent_id.entity_type.as_deref().unwrap_or(&route_path);
+                let id = consistent_route_id(
+                    engine.as_deref(),
+                    &workspace_id,
+                    entity_type,
+                    &key,
+                    &consistent_id.field,
+                )
+                .await;
+                fields.insert(consistent_id.field.clone(), serde_json::Value::String(id));
+            }
+
+            let encoded = protobuf.as_ref().and_then(|codec| codec.encode_response(&body_value));
+            let mut response = match (encoded, body_value) {
+                (Some(Ok(bytes)), _) => {
+                    ([(http::header::CONTENT_TYPE, route_protobuf::PROTOBUF_CONTENT_TYPE)], bytes)
+                        .into_response()
                 }
-                // Capture route info for the /__mockforge/routes endpoint
-                captured_routes = registry
-                    .routes()
-                    .iter()
-                    .map(|r| RouteInfo {
-                        method: r.method.clone(),
-                        path: r.path.clone(),
-                        operation_id: r.operation.operation_id.clone(),
-                        summary: r.operation.summary.clone(),
-                        description: r.operation.description.clone(),
-                        parameters: r.parameters.clone(),
-                    })
-                    .collect();
+                (Some(Err(e)), _) => {
+                    warn!("{} {}: {}", expected, route_path, e);
+                    return (StatusCode::INTERNAL_SERVER_ERROR, e).into_response();
+                }
+                // Content-Type comes from the route's custom headers below
+                (None, serde_json::Value::String(xml)) if xml_body => xml.into_response(),
+                (None, body_value) => match &stream {
+                    Some(stream) => streamed_response(body_value, stream),
+                    None => Json(body_value).into_response(),
+                },
+            };
 
-                // Store routes in the global route store so the admin server
-                // can serve them without proxying back to the HTTP server.
-                {
-                    let global_routes: Vec<mockforge_core::request_logger::GlobalRouteInfo> =
-                        captured_routes
-                            .iter()
-                            .map(|r| mockforge_core::request_logger::GlobalRouteInfo {
-                                method: r.method.clone(),
-                                path: r.path.clone(),
-                                operation_id: r.operation_id.clone(),
-                                summary: r.summary.clone(),
-                                description: r.description.clone(),
-                                parameters: r.parameters.clone(),
-                            })
-                            .collect();
-                    mockforge_core::request_logger::set_global_routes(global_routes);
-                    tracing::info!("Stored {} routes in global route store", captured_routes.len());
+            // Set status code
+            *response.status_mut() = StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK);
+
+            if !localized.is_empty() {
+                response
+                    .headers_mut()
+                    .insert(http::header::VARY, http::HeaderValue::from_static("accept-language"));
+            }
+            if let Some(tag) = content_language.and_then(|t| http::HeaderValue::from_str(t).ok()) {
+                response.headers_mut().insert(http::header::CONTENT_LANGUAGE, tag);
+            }
+
+            if let Some(deprecation) = &deprecation {
+                response.headers_mut().extend(deprecation_headers(deprecation));
+            }
+
+            // Add custom headers
+            for (key, value) in headers {
+                if let Ok(header_name) = http::HeaderName::from_bytes(key.as_bytes()) {
+                    if let Ok(header_value) = http::HeaderValue::from_str(&value) {
+                        response.headers_mut().insert(header_name, header_value);
+                    }
                 }
+            }
 
-                // Use MockAI if available, otherwise use standard router
-                let spec_router = if let Some(ref mockai_instance) = mockai {
-                    tracing::debug!("Building router with MockAI support");
-                    registry.build_router_with_mockai(Some(mockai_instance.clone()))
+            // Custom reason phrase for the HTTP/1.1 status line
+            if let Some(phrase) = &reason_phrase {
+                let phrase = if expand {
+                    match mockforge_template_expansion::expand_templates_in_json(
+                        serde_json::Value::String(phrase.clone()),
+                        &custom_route_request_context(&req),
+                    ) {
+                        serde_json::Value::String(phrase) => phrase,
+                        other => other.to_string(),
+                    }
                 } else {
-                    registry.build_router()
+                    phrase.clone()
                 };
-                // Issue #79 — explicitly stamp the body-size limit on the
-                // openapi sub-router BEFORE merging. The limit is also
-                // applied inside `OpenApiRouteRegistry::build_router_*`,
-                // but axum's `merge` can drop nested layers in some
-                // configurations; redoing it at the merge site is cheap
-                // insurance against the "200 OK before body completes"
-                // bug Srikanth reported on Issue #79.
-                let body_limit_mb = std::env::var("MOCKFORGE_HTTP_BODY_LIMIT_MB")
-                    .ok()
-                    .and_then(|v| v.parse::<usize>().ok())
-                    .unwrap_or(50);
-                let body_limit_bytes = body_limit_mb.saturating_mul(1024 * 1024);
-                let spec_router =
-                    spec_router.layer(axum::extract::DefaultBodyLimit::max(body_limit_bytes));
-                tracing::info!(
-                    body_limit_mb = body_limit_mb,
-                    "Merging OpenAPI router with main router"
-                );
-                app = app.merge(spec_router);
+                match hyper::ext::ReasonPhrase::try_from(phrase.into_bytes()) {
+                    Ok(reason) => {
+                        response.extensions_mut().insert(reason);
+                    }
+                    Err(_) => warn!("Ignoring invalid reason phrase for {}", route_path),
+                }
             }
-            Err(e) => {
-                warn!("Failed to load OpenAPI spec from {:?}: {}. Starting without OpenAPI integration.", spec_path, e);
+
+            // Let the compression layer honour the route's override
+            if let Some(compress) = compress {
+                response.extensions_mut().insert(middleware::RouteCompression(compress));
             }
-        }
-    }
 
-    // Register custom routes from config with advanced routing features
-    // Create RouteChaosInjector for advanced fault injection and latency
-    // Store as trait object to avoid circular dependency (RouteChaosInjectorTrait is in mockforge-core)
-    let route_chaos_injector: Option<
-        std::sync::Arc<dyn mockforge_core::priority_handler::RouteChaosInjectorTrait>,
-    > = if let Some(ref route_configs) = route_configs {
-        if !route_configs.is_empty() {
-            // Convert to the type expected by RouteChaosInjector
-            // Note: Both use the same mockforge-core, but we need to ensure type compatibility
-            let route_configs_converted: Vec<mockforge_core::config::RouteConfig> =
-                route_configs.to_vec();
-            match mockforge_route_chaos::RouteChaosInjector::new(route_configs_converted) {
-                Ok(injector) => {
-                    info!(
-                        "Initialized advanced routing features for {} route(s)",
-                        route_configs.len()
-                    );
-                    // RouteChaosInjector implements RouteChaosInjectorTrait, so we can cast it
-                    // The trait is implemented in mockforge-route-chaos/src/lib.rs
-                    Some(std::sync::Arc::new(injector)
-                        as std::sync::Arc<
-                            dyn mockforge_core::priority_handler::RouteChaosInjectorTrait,
-                        >)
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to initialize advanced routing features: {}. Using basic routing.",
-                        e
-                    );
-                    None
-                }
+            // Dribble the body out over the configured transfer time;
+            // streamed bodies already pace themselves
+            match body_transfer.filter(|_| stream.is_none()) {
+                Some(duration) => route_chaos_runtime::throttle_body(response, duration).await,
+                None => response,
             }
-        } else {
-            None
         }
-    } else {
-        None
-    };
+    })
+}
 
-    if let Some(route_configs) = route_configs {
-        use axum::http::StatusCode;
-        use axum::response::IntoResponse;
+/// Largest JSON request body parsed for `{{request.body.*}}` templates; a
+/// chunked body read only for templating is rejected with 413 beyond it
+const TEMPLATE_BODY_LIMIT: usize = 1024 * 1024;
 
-        if !route_configs.is_empty() {
-            info!("Registering {} custom route(s) from config", route_configs.len());
-        }
+/// Request body limit in MiB, from `MOCKFORGE_HTTP_BODY_LIMIT_MB` (default 50)
+fn http_body_limit_mb() -> usize {
+    std::env::var("MOCKFORGE_HTTP_BODY_LIMIT_MB")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(50)
+}
 
-        let injector = route_chaos_injector.clone();
-        for route_config in route_configs {
-            let status = route_config.response.status;
-            let body = route_config.response.body.clone();
-            let headers = route_config.response.headers.clone();
-            let path = route_config.path.clone();
-            let method = route_config.method.clone();
-
-            // Create handler that returns the configured response with template expansion
-            // Supports both basic templates ({{uuid}}, {{now}}) and request-aware templates
-            // ({{request.query.name}}, {{request.path.id}}, {{request.headers.name}})
-            // Register route using `any()` since we need full Request access for template expansion
-            let expected_method = method.to_uppercase();
-            // Clone Arc for the closure - Arc is Send-safe
-            // Note: RouteChaosInjector is marked as Send+Sync via unsafe impl, so we have to
-            // clone the Arc rather than move the inner injector into the route handler.
-            let injector_clone = injector.clone();
-            app = app.route(
-                &path,
-                #[allow(clippy::non_send_fields_in_send_ty)]
-                axum::routing::any(move |req: Request<Body>| {
-                    let body = body.clone();
-                    let headers = headers.clone();
-                    let expand = template_expand;
-                    let expected = expected_method.clone();
-                    let status_code = status;
-                    // Clone Arc again for the async block
-                    let injector_for_chaos = injector_clone.clone();
+/// Request data exposed to a custom route's templates
+pub(crate) fn custom_route_request_context(
+    req: &Request<Body>,
+) -> mockforge_template_expansion::RequestContext {
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    // Extract query parameters
+    let query_params: HashMap<String, Value> = req
+        .uri()
+        .query()
+        .map(|q| {
+            url::form_urlencoded::parse(q.as_bytes())
+                .into_owned()
+                .map(|(k, v)| (k, Value::String(v)))
+                .collect()
+        })
+        .unwrap_or_default();
 
-                    async move {
-                        // Check if request method matches expected method
-                        if req.method().as_str() != expected.as_str() {
-                            // Return 405 Method Not Allowed for wrong method
-                            return axum::response::Response::builder()
-                                .status(StatusCode::METHOD_NOT_ALLOWED)
-                                .header("Allow", &expected)
-                                .body(Body::empty())
-                                .unwrap()
-                                .into_response();
-                        }
+    // Extract headers
+    let headers: HashMap<String, Value> = req
+        .headers()
+        .iter()
+        .map(|(k, v)| (k.to_string(), Value::String(v.to_str().unwrap_or_default().to_string())))
+        .collect();
 
-                        // Apply advanced routing features (fault injection and latency) if available
-                        // Use helper function to avoid capturing RouteChaosInjector in closure
-                        // Pass the Arc as a reference to the helper function
-                        if let Some(fault_response) = apply_route_chaos(
-                            injector_for_chaos.as_deref(),
-                            req.method(),
-                            req.uri(),
-                        )
-                        .await
-                        {
-                            return fault_response;
-                        }
+    // Using RequestContext from mockforge-template-expansion (not mockforge-core)
+    // to avoid bringing rng() into scope
+    mockforge_template_expansion::RequestContext {
+        method: req.method().to_string(),
+        path: req.uri().path().to_string(),
+        query_params,
+        raw_query: req.uri().query().unwrap_or_default().to_string(),
+        headers,
+        body: None, // Filled in by the handler once the body is buffered
+        path_params: HashMap::new(),
+        multipart_fields: HashMap::new(),
+        multipart_files: HashMap::new(),
+    }
+}
 
-                        // Create JSON response from body, or empty object if None
-                        let mut body_value = body.unwrap_or(serde_json::json!({}));
-
-                        // Apply template expansion if enabled
-                        // Use mockforge-template-expansion crate which is completely isolated
-                        // from mockforge-core to avoid Send issues (no rng() in dependency chain)
-                        if expand {
-                            use mockforge_template_expansion::RequestContext;
-                            use serde_json::Value;
-                            use std::collections::HashMap;
-
-                            // Extract request data for template expansion
-                            let method = req.method().to_string();
-                            let path = req.uri().path().to_string();
-
-                            // Extract query parameters
-                            let query_params: HashMap<String, Value> = req
-                                .uri()
-                                .query()
-                                .map(|q| {
-                                    url::form_urlencoded::parse(q.as_bytes())
-                                        .into_owned()
-                                        .map(|(k, v)| (k, Value::String(v)))
-                                        .collect()
-                                })
-                                .unwrap_or_default();
-
-                            // Extract headers
-                            let headers: HashMap<String, Value> = req
-                                .headers()
-                                .iter()
-                                .map(|(k, v)| {
-                                    (
-                                        k.to_string(),
-                                        Value::String(v.to_str().unwrap_or_default().to_string()),
-                                    )
-                                })
-                                .collect();
-
-                            // Create RequestContext for expand_prompt_template
-                            // Using RequestContext from mockforge-template-expansion (not mockforge-core)
-                            // to avoid bringing rng() into scope
-                            let context = RequestContext {
-                                method,
-                                path,
-                                query_params,
-                                headers,
-                                body: None, // Body extraction would require reading the request stream
-                                path_params: HashMap::new(),
-                                multipart_fields: HashMap::new(),
-                                multipart_files: HashMap::new(),
-                            };
-
-                            // Perform template expansion in spawn_blocking to ensure Send safety
-                            // The template expansion crate is completely isolated from mockforge-core
-                            // and doesn't have rng() in its dependency chain
-                            let body_value_clone = body_value.clone();
-                            let context_clone = context.clone();
-                            body_value = match tokio::task::spawn_blocking(move || {
-                                mockforge_template_expansion::expand_templates_in_json(
-                                    body_value_clone,
-                                    &context_clone,
-                                )
-                            })
-                            .await
-                            {
-                                Ok(result) => result,
-                                Err(_) => body_value, // Fallback to original on error
-                            };
-                        }
+/// Id for `key` under `entity_type`, shared through the consistency engine
+///
+/// The id is a UUIDv5 of the entity type and key, so it is stable even without
+/// an engine. With one, the id is registered as an entity the first time the
+/// key is seen and read back afterwards, which lets other protocols (or an
+/// explicitly registered entity) agree on the same id.
+async fn consistent_route_id(
+    engine: Option<&mockforge_core::consistency::ConsistencyEngine>,
+    workspace_id: &str,
+    entity_type: &str,
+    key: &str,
+    field: &str,
+) -> String {
+    use mockforge_core::consistency::EntityState;
+
+    let derived = uuid::Uuid::new_v5(
+        &uuid::Uuid::NAMESPACE_OID,
+        format!("mockforge:{}:{}", entity_type, key).as_bytes(),
+    )
+    .to_string();
+    let Some(engine) = engine else {
+        return derived;
+    };
+
+    if let Some(entity) = engine.get_entity(workspace_id, entity_type, key).await {
+        if let Some(id) = entity.data.get(field).and_then(|id| id.as_str()) {
+            return id.to_string();
+        }
+    }
+    let entity = EntityState::new(
+        entity_type.to_string(),
+        key.to_string(),
+        serde_json::json!({ field: derived }),
+    );
+    if let Err(e) = engine.register_entity(workspace_id, entity).await {
+        tracing::warn!("Failed to register consistent id for {}:{}: {}", entity_type, key, e);
+    }
+    derived
+}
 
-                        let mut response = Json(body_value).into_response();
+/// `Deprecation`, `Sunset` and `Link` headers for a deprecated route
+///
+/// Dates are template-expanded per request, so `{{now+30d}}` keeps the sunset
+/// a month away. Values that do not parse as dates are sent verbatim.
+fn deprecation_headers(
+    config: &mockforge_core::config::RouteDeprecationConfig,
+) -> Vec<(http::HeaderName, http::HeaderValue)> {
+    let date = |raw: &str| {
+        let expanded = mockforge_core::templating::expand_str(raw);
+        parse_route_date(&expanded).ok_or(expanded)
+    };
+    let mut headers = Vec::new();
+    if let Some(raw) = &config.date {
+        // RFC 9745 structured-field date: `@<unix seconds>`
+        let value = match date(raw) {
+            Ok(at) => format!("@{}", at.timestamp()),
+            Err(raw) => raw,
+        };
+        headers.push(("deprecation", value));
+    }
+    if let Some(raw) = &config.sunset {
+        // RFC 8594 HTTP-date
+        let value = match date(raw) {
+            Ok(at) => at.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            Err(raw) => raw,
+        };
+        headers.push(("sunset", value));
+    }
+    if let Some(link) = &config.link {
+        headers.push(("link", format!("<{}>; rel=\"deprecation\"", link)));
+    }
+    headers
+        .into_iter()
+        .filter_map(|(name, value)| {
+            Some((http::HeaderName::from_static(name), http::HeaderValue::from_str(&value).ok()?))
+        })
+        .collect()
+}
 
-                        // Set status code
-                        *response.status_mut() =
-                            StatusCode::from_u16(status_code).unwrap_or(StatusCode::OK);
+/// Parse an RFC 3339 timestamp, an HTTP-date or a `YYYY-MM-DD` date (midnight UTC)
+fn parse_route_date(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let value = value.trim();
+    chrono::DateTime::parse_from_rfc3339(value)
+        .or_else(|_| chrono::DateTime::parse_from_rfc2822(value))
+        .map(|at| at.with_timezone(&chrono::Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|day| day.and_hms_opt(0, 0, 0))
+                .map(|at| at.and_utc())
+        })
+}
 
-                        // Add custom headers
-                        for (key, value) in headers {
-                            if let Ok(header_name) = http::HeaderName::from_bytes(key.as_bytes()) {
-                                if let Ok(header_value) = http::HeaderValue::from_str(&value) {
-                                    response.headers_mut().insert(header_name, header_value);
-                                }
-                            }
-                        }
+/// Stream a custom route's body per its `stream` config
+///
+/// Each element of an array body is one piece (any other body is a single
+/// piece): NDJSON sends it as a line of JSON, chunks send strings verbatim.
+/// Pieces are paused by `delays_ms`/`delay_ms` so clients see them arrive
+/// incrementally.
+fn streamed_response(
+    body: serde_json::Value,
+    stream: &mockforge_core::config::RouteStreamConfig,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+    use futures::StreamExt;
+    use mockforge_core::config::RouteStreamFormat;
 
-                        response
-                    }
-                }),
-            );
+    let items = match body {
+        serde_json::Value::Array(items) => items,
+        other => vec![other],
+    };
+    let format = stream.format;
+    let content_type = match format {
+        RouteStreamFormat::Ndjson => "application/x-ndjson",
+        RouteStreamFormat::Chunks => "application/octet-stream",
+    };
+    let delays: Vec<std::time::Duration> = (0..items.len())
+        .map(|i| {
+            let ms = match stream.delays_ms.get(i) {
+                Some(ms) => *ms,
+                None if i > 0 => stream.delay_ms,
+                None => 0,
+            };
+            std::time::Duration::from_millis(ms)
+        })
+        .collect();
+    let pieces = futures::stream::iter(items.into_iter().zip(delays)).then(
+        move |(item, delay)| async move {
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            let piece = match (format, item) {
+                (RouteStreamFormat::Chunks, serde_json::Value::String(text)) => text.into_bytes(),
+                (RouteStreamFormat::Chunks, item) => serde_json::to_vec(&item).unwrap_or_default(),
+                (RouteStreamFormat::Ndjson, item) => {
+                    let mut line = serde_json::to_vec(&item).unwrap_or_default();
+                    line.push(b'\n');
+                    line
+                }
+            };
+            Ok::<_, std::convert::Infallible>(piece)
+        },
+    );
+    ([(http::header::CONTENT_TYPE, content_type)], Body::from_stream(pieces)).into_response()
+}
+
+/// Request counter behind a custom route's `quota`
+struct QuotaWindow {
+    config: mockforge_core::config::RouteQuotaConfig,
+    /// Start of the current window and requests counted in it
+    window: std::sync::Mutex<Option<(chrono::DateTime<chrono::Utc>, u64)>>,
+}
 
-            debug!("Registered route: {} {}", method, path);
+impl QuotaWindow {
+    fn new(config: mockforge_core::config::RouteQuotaConfig) -> Self {
+        Self {
+            config,
+            window: std::sync::Mutex::new(None),
         }
     }
 
-    // Add health check endpoints
-    if let Some(health) = health_manager {
-        // Use comprehensive health check router with all probe endpoints
-        app = app.merge(health::health_router(health));
-        info!(
-            "Health check endpoints enabled: /health, /health/live, /health/ready, /health/startup"
-        );
-    } else if include_default_health {
-        // Fallback to basic health endpoint for backwards compatibility
-        app = app.route(
-            "/health",
-            axum::routing::get(|| async {
-                use mockforge_core::server_utils::health::HealthStatus;
-                {
-                    // HealthStatus should always serialize, but handle errors gracefully
-                    match serde_json::to_value(HealthStatus::healthy(0, "mockforge-http")) {
-                        Ok(value) => Json(value),
-                        Err(e) => {
-                            // Log error but return a simple healthy response
-                            tracing::error!("Failed to serialize health status: {}", e);
-                            Json(serde_json::json!({
-                                "status": "healthy",
-                                "service": "mockforge-http",
-                                "uptime_seconds": 0
-                            }))
-                        }
-                    }
-                }
-            }),
-        );
+    /// Count a request made at `now`; over the limit, returns the time left
+    /// until the window resets
+    fn hit(&self, now: chrono::DateTime<chrono::Utc>) -> Option<chrono::Duration> {
+        let length = chrono::Duration::milliseconds(self.config.window_ms as i64);
+        let mut window = self.window.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let expired = match *window {
+            Some((start, _)) => now - start >= length,
+            None => true,
+        };
+        if expired {
+            *window = Some((now, 0));
+        }
+        let (start, count) = window.as_mut().expect("quota window was just opened");
+        *count += 1;
+        (*count > self.config.limit).then(|| *start + length - now)
     }
 
-    app = app.merge(sse::sse_router());
-    // Add file serving endpoints for generated mock files
-    app = app.merge(file_server::file_serving_router());
+    fn exceeded_response(&self, retry_after: chrono::Duration) -> axum::response::Response {
+        use axum::http::StatusCode;
+        use axum::response::IntoResponse;
 
-    // Add management API endpoints
-    // Load spec for ManagementState so /__mockforge/api/spec can serve it
-    let mgmt_spec = if let Some(ref sp) = spec_path {
-        match OpenApiSpec::from_file(sp).await {
-            Ok(s) => Some(Arc::new(s)),
-            Err(e) => {
-                debug!("Failed to load OpenAPI spec for management API: {}", e);
-                None
+        let body = self.config.body.clone().unwrap_or_else(|| {
+            serde_json::json!({
+                "error": "quota_exceeded",
+                "message": format!(
+                    "Quota of {} requests per {}ms exceeded",
+                    self.config.limit, self.config.window_ms
+                ),
+            })
+        });
+        let mut response = Json(body).into_response();
+        *response.status_mut() =
+            StatusCode::from_u16(self.config.status).unwrap_or(StatusCode::TOO_MANY_REQUESTS);
+        if !self.config.headers.keys().any(|k| k.eq_ignore_ascii_case("retry-after")) {
+            // Whole seconds, rounded up
+            let seconds = (retry_after.num_milliseconds().max(0) as u64).div_ceil(1000);
+            response
+                .headers_mut()
+                .insert(http::header::RETRY_AFTER, http::HeaderValue::from(seconds));
+        }
+        for (key, value) in &self.config.headers {
+            if let (Ok(name), Ok(value)) =
+                (http::HeaderName::from_bytes(key.as_bytes()), http::HeaderValue::from_str(value))
+            {
+                response.headers_mut().insert(name, value);
             }
         }
-    } else {
-        None
-    };
-    let spec_path_clone = spec_path.clone();
-    let mgmt_port = std::env::var("PORT")
-        .or_else(|_| std::env::var("MOCKFORGE_HTTP_PORT"))
-        .ok()
-        .and_then(|p| p.parse().ok())
-        .unwrap_or(3000);
-    let management_state = ManagementState::new(mgmt_spec, spec_path_clone, mgmt_port);
+        response
+    }
+}
 
-    // Create WebSocket state and connect it to management state
-    use std::sync::Arc;
-    let ws_state = WsManagementState::new();
-    let ws_broadcast = Arc::new(ws_state.tx.clone());
-    let management_state = management_state.with_ws_broadcast(ws_broadcast);
+/// Call counter behind a custom route's `flaky` alternation
+struct FlakyRoute {
+    config: mockforge_core::config::RouteFlakyConfig,
+    calls: std::sync::atomic::AtomicU64,
+}
 
-    // Add proxy config to management state if available
-    let management_state = if let Some(proxy_cfg) = proxy_config {
-        use tokio::sync::RwLock;
-        let proxy_config_arc = Arc::new(RwLock::new(proxy_cfg));
-        management_state.with_proxy_config(proxy_config_arc)
-    } else {
-        management_state
-    };
+impl FlakyRoute {
+    fn new(config: mockforge_core::config::RouteFlakyConfig) -> Self {
+        Self {
+            config,
+            calls: std::sync::atomic::AtomicU64::new(0),
+        }
+    }
 
-    #[cfg(feature = "smtp")]
-    let management_state = {
-        if let Some(smtp_reg) = smtp_registry {
-            match smtp_reg.downcast::<mockforge_smtp::SmtpSpecRegistry>() {
-                Ok(smtp_reg) => management_state.with_smtp_registry(smtp_reg),
-                Err(e) => {
-                    error!(
-                        "Invalid SMTP registry type passed to HTTP management state: {:?}",
-                        e.type_id()
-                    );
-                    management_state
-                }
-            }
-        } else {
-            management_state
-        }
-    };
-    #[cfg(not(feature = "smtp"))]
-    let management_state = {
-        let _ = smtp_registry;
-        management_state
-    };
-    #[cfg(feature = "mqtt")]
-    let management_state = {
-        // The `mqtt_broker` slot carries the live `Arc<SessionManager>` (the
-        // running listener's state), not an `MqttBroker` — see issue #730.
-        if let Some(sessions) = mqtt_broker {
-            match sessions.downcast::<mockforge_mqtt::SessionManager>() {
-                Ok(sessions) => management_state.with_mqtt_sessions(sessions),
-                Err(e) => {
-                    error!(
-                        "Invalid MQTT session manager passed to HTTP management state: {:?}",
-                        e.type_id()
-                    );
-                    management_state
-                }
-            }
-        } else {
-            management_state
-        }
-    };
-    #[cfg(not(feature = "mqtt"))]
-    let management_state = {
-        let _ = mqtt_broker;
-        management_state
-    };
-    #[cfg(feature = "amqp")]
-    let management_state = {
-        if let Some(broker) = amqp_broker {
-            match broker.downcast::<mockforge_amqp::AmqpBroker>() {
-                Ok(broker) => management_state.with_amqp_broker(broker),
-                Err(e) => {
-                    error!(
-                        "Invalid AMQP broker passed to HTTP management state: {:?}",
-                        e.type_id()
-                    );
-                    management_state
-                }
-            }
-        } else {
-            management_state
+    /// Count a call and report whether it gets the failure response
+    fn next_fails(&self) -> bool {
+        let n = self.calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let pattern = &self.config.failure_pattern;
+        if !pattern.is_empty() {
+            return pattern[(n % pattern.len() as u64) as usize];
         }
-    };
-    #[cfg(not(feature = "amqp"))]
-    let management_state = {
-        let _ = amqp_broker;
-        management_state
-    };
-    #[cfg(feature = "kafka")]
-    let management_state = {
-        if let Some(broker) = kafka_broker {
-            match broker.downcast::<mockforge_kafka::KafkaMockBroker>() {
-                Ok(broker) => management_state.with_kafka_broker(broker),
-                Err(e) => {
-                    error!(
-                        "Invalid Kafka broker passed to HTTP management state: {:?}",
-                        e.type_id()
-                    );
-                    management_state
-                }
-            }
-        } else {
-            management_state
+        match self.config.every_nth {
+            Some(nth) if nth > 0 => (n + 1) % nth == 0,
+            _ => false,
         }
-    };
-    #[cfg(not(feature = "kafka"))]
-    let management_state = {
-        let _ = kafka_broker;
-        management_state
-    };
-    let management_state_for_fallback = management_state.clone();
-    app = app.nest("/__mockforge/api", management_router(management_state));
-    // Dynamic-mock fallback; see identical block earlier in this file.
-    app = app.fallback_service(
-        axum::routing::any(management::dynamic_mock_fallback)
-            .with_state(management_state_for_fallback),
-    );
-
-    // Add verification API endpoint
-    app = app.merge(verification_router());
-
-    // Mount the request-chains management API at the prefix the UI's
-    // ChainsPage expects (`/__mockforge/chains/*`). Without this nest the
-    // page 404s on every call. The registry/engine are constructed from
-    // the chain_config param when supplied, otherwise from defaults.
-    {
-        use crate::chain_handlers::{chains_router, create_chain_state};
-        let chain_config = _circling_config.clone().unwrap_or_default();
-        let chain_registry = Arc::new(mockforge_core::request_chaining::RequestChainRegistry::new(
-            chain_config.clone(),
-        ));
-        let chain_engine = Arc::new(mockforge_core::chain_execution::ChainExecutionEngine::new(
-            chain_registry.clone(),
-            chain_config,
-        ));
-        app = app.nest(
-            "/__mockforge/chains",
-            chains_router(create_chain_state(chain_registry, chain_engine)),
-        );
-    }
-
-    // Contract-diff retrieval API. The `capture_for_contract_diff`
-    // middleware (layered earlier) populates the global capture manager
-    // on every request; this surface lets operators read the captures
-    // and run the analyser against the current OpenAPI spec.
-    {
-        use crate::contract_diff_api::{contract_diff_api_router, ContractDiffApiState};
-        let cd_state = Arc::new(ContractDiffApiState::new(spec_path.clone()));
-        app = app.nest("/__mockforge/api/contract-diff", contract_diff_api_router(cd_state));
     }
 
-    // Fixtures management API. Lives on the admin server too (port 9080),
-    // but hosted-mock Fly machines only expose port 3000 publicly — so
-    // we mount the same surface on the main HTTP app. Newly-uploaded
-    // fixtures land in `MOCKFORGE_FIXTURES_DIR` and are picked up by
-    // the startup-time loader on the next deploy/restart; live reload
-    // is a separate concern.
-    {
-        use crate::fixtures_api::{fixtures_api_router, FixturesApiState};
-        let fx_state = FixturesApiState::from_env();
-        app = app.nest("/__mockforge/fixtures", fixtures_api_router(fx_state));
-    }
+    fn failure_response(&self) -> axum::response::Response {
+        use axum::http::StatusCode;
+        use axum::response::IntoResponse;
 
-    // Standalone MockAI API. Until now MockAI was only invoked from the
-    // OpenAPI route generator — so a hosted mock without a spec, or a
-    // user wanting to prototype an "AI persona" mock without registering
-    // routes, had no way to reach the engine. The endpoint surfaces 503
-    // when MockAI isn't configured (no API key) so callers get a clear
-    // signal rather than a silent failure.
-    {
-        use crate::mockai_api::{mockai_api_router, MockAiApiState};
-        let api_state = MockAiApiState::new(mockai.clone());
-        app = app.nest("/__mockforge/api/mockai", mockai_api_router(api_state));
+        let body = self.config.body.clone().unwrap_or_else(|| {
+            serde_json::json!({
+                "error": "flaky_failure",
+                "message": "Simulated intermittent failure",
+            })
+        });
+        let mut response = Json(body).into_response();
+        *response.status_mut() =
+            StatusCode::from_u16(self.config.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        for (key, value) in &self.config.headers {
+            if let (Ok(name), Ok(value)) =
+                (http::HeaderName::from_bytes(key.as_bytes()), http::HeaderValue::from_str(value))
+            {
+                response.headers_mut().insert(name, value);
+            }
+        }
+        response
     }
+}
 
-    // Time-travel runtime API. The admin server mounts these routes on
-    // its own port (9080), but hosted-mock Fly machines only expose
-    // port 3000 publicly — so until now operators couldn't set / advance
-    // virtual time on a deployed mock. The handlers consult a process-
-    // wide TimeTravelManager that serve.rs initialises alongside the
-    // existing admin-server registration; both paths see the same Arc.
-    app = app.nest("/__mockforge/time-travel", time_travel_api::time_travel_router());
+/// Per-session request counts behind `sequence` selection, and behind
+/// `round_robin` selection keyed on `hash_on`
+struct VariantSequence {
+    /// Stay on the last variant once reached instead of starting over
+    hold_last: bool,
+    /// Requests served so far, by session hash (0 when there are no sessions)
+    served: std::sync::Mutex<HashMap<u64, usize>>,
+}
 
-    // Runtime route-chaos rules API + middleware. This sits in front of
-    // the static per-route handlers so operators can add/remove fault and
-    // latency rules without redeploying. Static rules from the YAML
-    // config still apply to their routes; runtime rules are additive.
-    {
-        use crate::route_chaos_runtime::{
-            route_chaos_api_router, runtime_route_chaos_middleware, RuntimeRouteChaosState,
-        };
-        let runtime_state = RuntimeRouteChaosState::new(Vec::new());
-        let middleware_state = runtime_state.clone();
-        app = app.layer(from_fn_with_state(middleware_state, runtime_route_chaos_middleware));
-        app = app.nest("/__mockforge/api/route-chaos", route_chaos_api_router(runtime_state));
+impl VariantSequence {
+    fn new(hold_last: bool) -> Self {
+        Self {
+            hold_last,
+            served: std::sync::Mutex::new(HashMap::new()),
+        }
     }
 
-    // Runtime network-profile switching. Operators activate a named
-    // catalog profile (e.g., "mobile_3g") and the middleware applies its
-    // latency to every subsequent request — useful for "what does my
-    // consumer look like under bad network" probes against a live
-    // hosted mock without a redeploy.
-    {
-        use crate::network_profile_runtime::{
-            network_profile_api_router, network_profile_middleware, NetworkProfileRuntimeState,
+    /// Count a request from `session` and pick its index among `len` variants
+    fn next(&self, session: u64, len: usize) -> usize {
+        let mut served = self.served.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let count = served.entry(session).or_insert(0);
+        let pick = if self.hold_last {
+            (*count).min(len - 1)
+        } else {
+            *count % len
         };
-        let runtime_state = NetworkProfileRuntimeState::new(
-            mockforge_core::network_profiles::NetworkProfileCatalog::new(),
-        );
-        let middleware_state = runtime_state.clone();
-        app = app.layer(from_fn_with_state(middleware_state, network_profile_middleware));
-        app = app
-            .nest("/__mockforge/api/network-profiles", network_profile_api_router(runtime_state));
+        *count = count.saturating_add(1);
+        pick
     }
+}
 
-    // Add OIDC well-known endpoints
-    use crate::auth::oidc::oidc_router;
-    app = app.merge(oidc_router());
+/// Ramp state behind a custom route's `error_ramp`
+struct ErrorRamp {
+    config: mockforge_core::config::RouteErrorRampConfig,
+    /// Time of the first request and the accumulated failure fraction not
+    /// yet served as a failure
+    state: std::sync::Mutex<Option<(chrono::DateTime<chrono::Utc>, f64)>>,
+}
 
-    // Add access review API if enabled
-    {
-        use mockforge_core::security::get_global_access_review_service;
-        if let Some(service) = get_global_access_review_service().await {
-            use crate::handlers::access_review::{access_review_router, AccessReviewState};
-            let review_state = AccessReviewState { service };
-            app = app.nest("/api/v1/security/access-reviews", access_review_router(review_state));
-            debug!("Access review API mounted at /api/v1/security/access-reviews");
+impl ErrorRamp {
+    fn new(config: mockforge_core::config::RouteErrorRampConfig) -> Self {
+        Self {
+            config,
+            state: std::sync::Mutex::new(None),
         }
     }
 
-    // Add privileged access API if enabled
-    {
-        use mockforge_core::security::get_global_privileged_access_manager;
-        if let Some(manager) = get_global_privileged_access_manager().await {
-            use crate::handlers::privileged_access::{
-                privileged_access_router, PrivilegedAccessState,
-            };
-            let privileged_state = PrivilegedAccessState { manager };
-            app = app.nest(
-                "/api/v1/security/privileged-access",
-                privileged_access_router(privileged_state),
-            );
-            debug!("Privileged access API mounted at /api/v1/security/privileged-access");
+    /// Failure rate `elapsed` after the first request
+    fn rate_after(&self, elapsed: chrono::Duration) -> f64 {
+        let target = self.config.target_error_rate.clamp(0.0, 1.0);
+        let ramp_ms = self.config.ramp_secs.saturating_mul(1000);
+        if ramp_ms == 0 {
+            return target;
         }
+        let progress = elapsed.num_milliseconds().max(0) as f64 / ramp_ms as f64;
+        target * progress.min(1.0)
     }
 
-    // Add change management API if enabled
-    {
-        use mockforge_core::security::get_global_change_management_engine;
-        if let Some(engine) = get_global_change_management_engine().await {
-            use crate::handlers::change_management::{
-                change_management_router, ChangeManagementState,
-            };
-            let change_state = ChangeManagementState { engine };
-            app = app.nest("/api/v1/change-management", change_management_router(change_state));
-            debug!("Change management API mounted at /api/v1/change-management");
+    /// Count a request made at `now` and report whether it gets the failure
+    /// response
+    fn fails_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let (start, debt) = state.get_or_insert((now, 0.0));
+        *debt += self.rate_after(now - *start);
+        if *debt >= 1.0 {
+            *debt -= 1.0;
+            true
+        } else {
+            false
         }
     }
 
-    // Add risk assessment API if enabled
-    {
-        use mockforge_core::security::get_global_risk_assessment_engine;
-        if let Some(engine) = get_global_risk_assessment_engine().await {
-            use crate::handlers::risk_assessment::{risk_assessment_router, RiskAssessmentState};
-            let risk_state = RiskAssessmentState { engine };
-            app = app.nest("/api/v1/security", risk_assessment_router(risk_state));
-            debug!("Risk assessment API mounted at /api/v1/security/risks");
+    fn failure_response(&self) -> axum::response::Response {
+        use axum::http::StatusCode;
+        use axum::response::IntoResponse;
+
+        let body = self.config.body.clone().unwrap_or_else(|| {
+            serde_json::json!({
+                "error": "error_ramp_failure",
+                "message": "Simulated failure from error rate ramp",
+            })
+        });
+        let mut response = Json(body).into_response();
+        *response.status_mut() =
+            StatusCode::from_u16(self.config.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        for (key, value) in &self.config.headers {
+            if let (Ok(name), Ok(value)) =
+                (http::HeaderName::from_bytes(key.as_bytes()), http::HeaderValue::from_str(value))
+            {
+                response.headers_mut().insert(name, value);
+            }
         }
+        response
     }
+}
 
-    // Add token lifecycle API
-    {
-        use crate::auth::token_lifecycle::TokenLifecycleManager;
-        use crate::handlers::token_lifecycle::{token_lifecycle_router, TokenLifecycleState};
-        let lifecycle_manager = Arc::new(TokenLifecycleManager::default());
-        let lifecycle_state = TokenLifecycleState {
-            manager: lifecycle_manager,
+/// When a custom-route variant may be served, resolved from its `time_window`
+#[derive(Debug, Clone, Copy)]
+enum VariantSchedule {
+    /// No window: the fallback whenever no windowed variant is active
+    Always,
+    /// Served only while the current time of day is inside the window
+    Within {
+        start: chrono::NaiveTime,
+        end: chrono::NaiveTime,
+        tz: chrono_tz::Tz,
+    },
+    /// The window failed to parse; the variant is never served
+    Never,
+}
+
+impl VariantSchedule {
+    fn new(window: Option<&mockforge_core::config::RouteTimeWindow>, route_path: &str) -> Self {
+        let Some(window) = window else {
+            return Self::Always;
         };
-        app = app.nest("/api/v1/auth", token_lifecycle_router(lifecycle_state));
-        debug!("Token lifecycle API mounted at /api/v1/auth");
+        let parse_time = |value: &str| {
+            let value = value.trim();
+            chrono::NaiveTime::parse_from_str(value, "%H:%M")
+                .or_else(|_| chrono::NaiveTime::parse_from_str(value, "%H:%M:%S"))
+                .map_err(|e| format!("invalid time '{}': {}", value, e))
+        };
+        let parsed = parse_time(&window.start).and_then(|start| {
+            let end = parse_time(&window.end)?;
+            let tz = window
+                .timezone
+                .parse::<chrono_tz::Tz>()
+                .map_err(|e| format!("invalid timezone '{}': {}", window.timezone, e))?;
+            Ok(Self::Within { start, end, tz })
+        });
+        parsed.unwrap_or_else(|e| {
+            tracing::warn!("Ignoring response variant on {}: {}", route_path, e);
+            Self::Never
+        })
     }
 
-    // Add OAuth2 server endpoints
-    {
-        use crate::auth::oidc::load_oidc_state;
-        use crate::auth::token_lifecycle::TokenLifecycleManager;
-        use crate::handlers::oauth2_server::{oauth2_server_router, OAuth2ServerState};
-        // Load OIDC state from configuration (environment variables or config file)
-        let oidc_state = Arc::new(RwLock::new(load_oidc_state()));
-        let lifecycle_manager = Arc::new(TokenLifecycleManager::default());
-        let oauth2_state = OAuth2ServerState {
-            oidc_state,
-            lifecycle_manager,
-            auth_codes: Arc::new(RwLock::new(HashMap::new())),
-            refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
-        };
-        app = app.merge(oauth2_server_router(oauth2_state));
-        debug!("OAuth2 server endpoints mounted at /oauth2/authorize and /oauth2/token");
-    }
-
-    // Add consent screen endpoints
-    {
-        use crate::auth::oidc::load_oidc_state;
-        use crate::auth::risk_engine::RiskEngine;
-        use crate::auth::token_lifecycle::TokenLifecycleManager;
-        use crate::handlers::consent::{consent_router, ConsentState};
-        use crate::handlers::oauth2_server::OAuth2ServerState;
-        // Load OIDC state from configuration (environment variables or config file)
-        let oidc_state = Arc::new(RwLock::new(load_oidc_state()));
-        let lifecycle_manager = Arc::new(TokenLifecycleManager::default());
-        let oauth2_state = OAuth2ServerState {
-            oidc_state: oidc_state.clone(),
-            lifecycle_manager: lifecycle_manager.clone(),
-            auth_codes: Arc::new(RwLock::new(HashMap::new())),
-            refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
-        };
-        let risk_engine = Arc::new(RiskEngine::default());
-        let consent_state = ConsentState {
-            oauth2_state,
-            risk_engine,
-        };
-        app = app.merge(consent_router(consent_state));
-        debug!("Consent screen endpoints mounted at /consent");
-    }
-
-    // Add risk simulation API
-    {
-        use crate::auth::risk_engine::RiskEngine;
-        use crate::handlers::risk_simulation::{risk_simulation_router, RiskSimulationState};
-        let risk_engine = Arc::new(RiskEngine::default());
-        let risk_state = RiskSimulationState { risk_engine };
-        app = app.nest("/api/v1/auth", risk_simulation_router(risk_state));
-        debug!("Risk simulation API mounted at /api/v1/auth/risk");
-    }
-
-    // Initialize database connection (optional)
-    #[cfg(feature = "database")]
-    let database = {
-        use crate::database::Database;
-        let database_url = std::env::var("DATABASE_URL").ok();
-        match Database::connect_optional(database_url.as_deref()).await {
-            Ok(db) => {
-                if db.is_connected() {
-                    // Run migrations if database is connected
-                    if let Err(e) = db.migrate_if_connected().await {
-                        warn!("Failed to run database migrations: {}", e);
-                    } else {
-                        info!("Database connected and migrations applied");
-                    }
+    fn is_active(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        match *self {
+            Self::Always => true,
+            Self::Within { start, end, tz } => {
+                let local = now.with_timezone(&tz).time();
+                if start <= end {
+                    start <= local && local < end
+                } else {
+                    // Overnight window, e.g. 22:00-06:00
+                    local >= start || local < end
                 }
-                Some(db)
-            }
-            Err(e) => {
-                warn!("Failed to connect to database: {}. Continuing without database support.", e);
-                None
             }
+            Self::Never => false,
         }
+    }
+}
+
+/// Indices of the variants servable at `now`: those whose time window is
+/// active, or — when none is — the variants without a window
+fn eligible_variants(
+    schedules: &[VariantSchedule],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Vec<usize> {
+    let indices = |windowed: bool| -> Vec<usize> {
+        schedules
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| matches!(s, VariantSchedule::Within { .. }) == windowed)
+            .filter(|(_, s)| s.is_active(now))
+            .map(|(i, _)| i)
+            .collect()
     };
+    let in_window = indices(true);
+    if in_window.is_empty() {
+        indices(false)
+    } else {
+        in_window
+    }
+}
 
-    // Add drift budget and incident management endpoints
-    // Initialize shared components for drift tracking and protocol contracts
-    let (drift_engine, incident_manager, drift_config) = {
-        use mockforge_core::contract_drift::{DriftBudgetConfig, DriftBudgetEngine};
-        use mockforge_core::incidents::{IncidentManager, IncidentStore};
-        use std::sync::Arc;
+/// Flags listed in the request's `X-Feature-Flags` headers
+fn request_feature_flags(headers: &http::HeaderMap) -> std::collections::HashSet<String> {
+    headers
+        .get_all("x-feature-flags")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|flag| !flag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
 
-        // Initialize drift budget engine with default config
-        let drift_config = DriftBudgetConfig::default();
-        let drift_engine = Arc::new(DriftBudgetEngine::new(drift_config.clone()));
+/// Narrow `eligible` by feature flags: the variants whose flags are all set
+/// on the request, or — when none is — the variants without flags
+fn flag_matched_variants(
+    eligible: Vec<usize>,
+    variant_flags: &[Vec<String>],
+    request_flags: &std::collections::HashSet<String>,
+) -> Vec<usize> {
+    let (flagged, unflagged): (Vec<usize>, Vec<usize>) =
+        eligible.into_iter().partition(|&i| !variant_flags[i].is_empty());
+    let matched: Vec<usize> = flagged
+        .into_iter()
+        .filter(|&i| variant_flags[i].iter().all(|flag| request_flags.contains(flag)))
+        .collect();
+    if matched.is_empty() {
+        unflagged
+    } else {
+        matched
+    }
+}
 
-        // Initialize incident store and manager
-        let incident_store = Arc::new(IncidentStore::default());
-        let incident_manager = Arc::new(IncidentManager::new(incident_store.clone()));
+/// Narrow eligible variants to those whose raw query regex matches the
+/// undecoded query string, or to the variants without one if none match
+fn raw_query_matched_variants(
+    eligible: Vec<usize>,
+    variant_raw_query: &[Option<regex::Regex>],
+    raw_query: &str,
+) -> Vec<usize> {
+    let (gated, ungated): (Vec<usize>, Vec<usize>) =
+        eligible.into_iter().partition(|&i| variant_raw_query[i].is_some());
+    let matched: Vec<usize> = gated
+        .into_iter()
+        .filter(|&i| variant_raw_query[i].as_ref().is_some_and(|re| re.is_match(raw_query)))
+        .collect();
+    if matched.is_empty() {
+        ungated
+    } else {
+        matched
+    }
+}
 
-        (drift_engine, incident_manager, drift_config)
-    };
+/// A request attribute hashed by `hash` variant selection, parsed from
+/// `hash_on`
+#[derive(Debug, Clone)]
+enum VariantHashAttribute {
+    Method,
+    Path,
+    Query(String),
+    Header(http::HeaderName),
+    /// JSON pointer into the request body
+    Body(String),
+}
 
-    {
-        use crate::handlers::drift_budget::{drift_budget_router, DriftBudgetState};
-        use crate::middleware::drift_tracking::DriftTrackingState;
-        use mockforge_contracts::consumer_contracts::{
-            ConsumerBreakingChangeDetector, UsageRecorder,
-        };
-        use mockforge_core::ai_contract_diff::ContractDiffAnalyzer;
-        use std::sync::Arc;
+impl VariantHashAttribute {
+    /// Parse `hash_on`, skipping (and logging) unknown attributes; an empty
+    /// list hashes the path
+    fn parse_all(specs: &[String], route_path: &str) -> Vec<Self> {
+        if specs.is_empty() {
+            return vec![Self::Path];
+        }
+        specs
+            .iter()
+            .filter_map(|spec| {
+                let attribute = match spec.split_once('.') {
+                    None if spec == "method" => Some(Self::Method),
+                    None if spec == "path" => Some(Self::Path),
+                    Some(("query", name)) => Some(Self::Query(name.to_string())),
+                    Some(("header", name)) => {
+                        http::HeaderName::from_bytes(name.as_bytes()).ok().map(Self::Header)
+                    }
+                    Some(("body", field)) => Some(Self::Body(format!(
+                        "/{}",
+                        field
+                            .split('.')
+                            .map(|part| part.replace('~', "~0").replace('/', "~1"))
+                            .collect::<Vec<_>>()
+                            .join("/")
+                    ))),
+                    _ => None,
+                };
+                if attribute.is_none() {
+                    warn!("Ignoring unknown hash_on attribute '{}' for route {}", spec, route_path);
+                }
+                attribute
+            })
+            .collect()
+    }
 
-        // Initialize usage recorder and consumer detector
-        let usage_recorder = Arc::new(UsageRecorder::default());
-        let consumer_detector =
-            Arc::new(ConsumerBreakingChangeDetector::new(usage_recorder.clone()));
+    fn reads_body(&self) -> bool {
+        matches!(self, Self::Body(_))
+    }
+}
 
-        // Initialize contract diff analyzer if enabled
-        let diff_analyzer = if drift_config.enabled {
-            match ContractDiffAnalyzer::new(
-                mockforge_core::ai_contract_diff::ContractDiffConfig::default(),
-            ) {
-                Ok(analyzer) => Some(Arc::new(analyzer)),
-                Err(e) => {
-                    warn!("Failed to create contract diff analyzer: {}", e);
-                    None
-                }
-            }
-        } else {
-            None
-        };
+/// Stable hash of the request attributes a route's `hash` selection keys on
+///
+/// SHA-256 rather than `DefaultHasher`, so the same request maps to the same
+/// variant across processes and Rust versions. Missing attributes hash as
+/// empty.
+fn variant_hash(attributes: &[VariantHashAttribute], req: &Request<Body>, body: &[u8]) -> u64 {
+    use sha2::{Digest, Sha256};
 
-        // Get OpenAPI spec if available
-        // Note: Load from spec_path if available, or leave as None for manual configuration.
-        let spec = if let Some(ref spec_path) = spec_path {
-            match OpenApiSpec::from_file(spec_path).await {
-                Ok(s) => Some(Arc::new(s)),
-                Err(e) => {
-                    debug!("Failed to load OpenAPI spec for drift tracking: {}", e);
-                    None
+    let json_body = attributes
+        .iter()
+        .any(VariantHashAttribute::reads_body)
+        .then(|| serde_json::from_slice::<serde_json::Value>(body).ok())
+        .flatten();
+    let mut hasher = Sha256::new();
+    for attribute in attributes {
+        let value = match attribute {
+            VariantHashAttribute::Method => req.method().to_string(),
+            VariantHashAttribute::Path => req.uri().path().to_string(),
+            VariantHashAttribute::Query(name) => req
+                .uri()
+                .query()
+                .and_then(|q| {
+                    url::form_urlencoded::parse(q.as_bytes())
+                        .find(|(k, _)| k == name)
+                        .map(|(_, v)| v.into_owned())
+                })
+                .unwrap_or_default(),
+            VariantHashAttribute::Header(name) => req
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string(),
+            VariantHashAttribute::Body(pointer) => {
+                match json_body.as_ref().and_then(|b| b.pointer(pointer)) {
+                    Some(serde_json::Value::String(s)) => s.clone(),
+                    Some(other) => other.to_string(),
+                    None => String::new(),
                 }
             }
-        } else {
-            None
         };
+        hasher.update(value.as_bytes());
+        hasher.update([0]);
+    }
+    let digest = hasher.finalize();
+    u64::from_be_bytes(digest[..8].try_into().expect("SHA-256 digest is 32 bytes"))
+}
 
-        // Create drift tracking state
-        let drift_tracking_state = DriftTrackingState {
-            diff_analyzer,
-            spec,
-            drift_engine: drift_engine.clone(),
-            incident_manager: incident_manager.clone(),
-            usage_recorder,
-            consumer_detector,
-            enabled: drift_config.enabled,
-        };
+/// Build the base HTTP router with chaining and multi-tenant support
+#[allow(clippy::too_many_arguments)]
+#[allow(deprecated)] // uses core engines (DriftBudgetEngine, ThreatAnalyzer, Forecaster, ProtocolContractRegistry, MockAI, MultiTenantWorkspaceRegistry, etc.) that stay in core
+pub async fn build_router_with_chains_and_multi_tenant(
+    spec_path: Option<String>,
+    options: Option<ValidationOptions>,
+    _circling_config: Option<mockforge_core::request_chaining::ChainConfig>,
+    multi_tenant_config: Option<mockforge_foundation::multi_tenant_types::MultiTenantConfig>,
+    route_configs: Option<Vec<mockforge_core::config::RouteConfig>>,
+    cors_config: Option<mockforge_core::config::HttpCorsConfig>,
+    _ai_generator: Option<Arc<dyn mockforge_openapi::response::AiGenerator + Send + Sync>>,
+    smtp_registry: Option<Arc<dyn std::any::Any + Send + Sync>>,
+    mqtt_broker: Option<Arc<dyn std::any::Any + Send + Sync>>,
+    amqp_broker: Option<Arc<dyn std::any::Any + Send + Sync>>,
+    kafka_broker: Option<Arc<dyn std::any::Any + Send + Sync>>,
+    traffic_shaper: Option<mockforge_core::traffic_shaping::TrafficShaper>,
+    traffic_shaping_enabled: bool,
+    health_manager: Option<Arc<HealthManager>>,
+    mockai: Option<Arc<RwLock<mockforge_core::intelligent_behavior::MockAI>>>,
+    deceptive_deploy_config: Option<mockforge_core::config::DeceptiveDeployConfig>,
+    proxy_config: Option<mockforge_proxy::config::ProxyConfig>,
+) -> Router {
+    use crate::latency_profiles::LatencyProfiles;
+    use crate::op_middleware::Shared;
+    use mockforge_core::Overrides;
 
-        // Add response body buffering middleware (before drift tracking)
-        app = app.layer(axum::middleware::from_fn(middleware::buffer_response_middleware));
+    // Extract template expansion setting before options is moved (used in OpenAPI routes and custom routes)
+    let template_expand =
+        options.as_ref().map(|o| o.response_template_expand).unwrap_or_else(|| {
+            std::env::var("MOCKFORGE_RESPONSE_TEMPLATE_EXPAND")
+                .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                .unwrap_or(false)
+        });
 
-        // Add drift tracking middleware (after response buffering)
-        // Use a wrapper that inserts state into extensions before calling the middleware
-        let drift_tracking_state_clone = drift_tracking_state.clone();
-        app = app.layer(axum::middleware::from_fn(
-            move |mut req: axum::extract::Request, next: axum::middleware::Next| {
-                let state = drift_tracking_state_clone.clone();
-                async move {
-                    // Insert state into extensions if not already present
-                    if req.extensions().get::<DriftTrackingState>().is_none() {
-                        req.extensions_mut().insert(state);
-                    }
-                    // Call the middleware function
-                    middleware::drift_tracking::drift_tracking_middleware_with_extensions(req, next)
-                        .await
-                }
-            },
-        ));
+    let _shared = Shared {
+        profiles: LatencyProfiles::default(),
+        overrides: Overrides::default(),
+        failure_injector: None,
+        traffic_shaper,
+        overrides_enabled: false,
+        traffic_shaping_enabled,
+    };
 
-        let drift_state = DriftBudgetState {
-            engine: drift_engine.clone(),
-            incident_manager: incident_manager.clone(),
-            gitops_handler: None, // Can be initialized later if GitOps is configured
-        };
+    // Start with basic router
+    let mut app = Router::new();
+    let mut include_default_health = true;
+    let mut captured_routes: Vec<RouteInfo> = Vec::new();
 
-        app = app.merge(drift_budget_router(drift_state));
-        debug!("Drift budget and incident management endpoints mounted at /api/v1/drift");
-    }
+    // If an OpenAPI spec is provided, integrate it
+    if let Some(ref spec) = spec_path {
+        match OpenApiSpec::from_file(&spec).await {
+            Ok(openapi) => {
+                info!("Loaded OpenAPI spec from {}", spec);
 
-    // Add pipeline management endpoints (MockOps)
-    #[cfg(feature = "pipelines")]
-    {
-        use crate::handlers::pipelines::{pipeline_router, PipelineState};
+                // Try to load persona from config if available
+                let persona = load_persona_from_config().await;
 
-        let pipeline_state = PipelineState::new();
-        app = app.merge(pipeline_router(pipeline_state));
-        debug!("Pipeline management endpoints mounted at /api/v1/pipelines");
-    }
+                let mut registry = if let Some(opts) = options {
+                    tracing::debug!("Using custom validation options");
+                    if let Some(ref persona) = persona {
+                        tracing::info!("Using persona '{}' for route generation", persona.name);
+                    }
+                    OpenApiRouteRegistry::new_with_options_and_persona(openapi, opts, persona)
+                } else {
+                    tracing::debug!("Using environment-based options");
+                    if let Some(ref persona) = persona {
+                        tracing::info!("Using persona '{}' for route generation", persona.name);
+                    }
+                    OpenApiRouteRegistry::new_with_env_and_persona(openapi, persona)
+                };
 
-    // Add governance endpoints (forecasting, semantic drift, threat modeling, contract health)
+                // Load custom fixtures if enabled
+                let fixtures_dir = std::env::var("MOCKFORGE_FIXTURES_DIR")
+                    .unwrap_or_else(|_| "/app/fixtures".to_string());
+                let custom_fixtures_enabled = std::env::var("MOCKFORGE_CUSTOM_FIXTURES_ENABLED")
+                    .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+                    .unwrap_or(true); // Enabled by default
+
+                if custom_fixtures_enabled {
+                    use mockforge_openapi::CustomFixtureLoader;
+                    use std::path::PathBuf;
+                    use std::sync::Arc;
+
+                    let fixtures_path = PathBuf::from(&fixtures_dir);
+                    let mut custom_loader = CustomFixtureLoader::new(fixtures_path, true);
+
+                    if let Err(e) = custom_loader.load_fixtures().await {
+                        tracing::warn!("Failed to load custom fixtures: {}", e);
+                    } else {
+                        tracing::info!("Custom fixtures loaded from {}", fixtures_dir);
+                        registry = registry.with_custom_fixture_loader(Arc::new(custom_loader));
+                    }
+                }
+
+                if registry
+                    .routes()
+                    .iter()
+                    .any(|route| route.method == "GET" && route.path == "/health")
+                {
+                    include_default_health = false;
+                }
+                // Capture route info for the /__mockforge/routes endpoint
+                captured_routes = registry
+                    .routes()
+                    .iter()
+                    .map(|r| RouteInfo {
+                        method: r.method.clone(),
+                        path: r.path.clone(),
+                        operation_id: r.operation.operation_id.clone(),
+                        summary: r.operation.summary.clone(),
+                        description: r.operation.description.clone(),
+                        parameters: r.parameters.clone(),
+                    })
+                    .collect();
+
+                // Store routes in the global route store so the admin server
+                // can serve them without proxying back to the HTTP server.
+                {
+                    let global_routes: Vec<mockforge_core::request_logger::GlobalRouteInfo> =
+                        captured_routes
+                            .iter()
+                            .map(|r| mockforge_core::request_logger::GlobalRouteInfo {
+                                method: r.method.clone(),
+                                path: r.path.clone(),
+                                operation_id: r.operation_id.clone(),
+                                summary: r.summary.clone(),
+                                description: r.description.clone(),
+                                parameters: r.parameters.clone(),
+                            })
+                            .collect();
+                    mockforge_core::request_logger::set_global_routes(global_routes);
+                    tracing::info!("Stored {} routes in global route store", captured_routes.len());
+                }
+
+                // Use MockAI if available, otherwise use standard router
+                let spec_router = if let Some(ref mockai_instance) = mockai {
+                    tracing::debug!("Building router with MockAI support");
+                    registry.build_router_with_mockai(Some(mockai_instance.clone()))
+                } else {
+                    registry.build_router()
+                };
+                // Issue #79 — explicitly stamp the body-size limit on the
+                // openapi sub-router BEFORE merging. The limit is also
+                // applied inside `OpenApiRouteRegistry::build_router_*`,
+                // but axum's `merge` can drop nested layers in some
+                // configurations; redoing it at the merge site is cheap
+                // insurance against the "200 OK before body completes"
+                // bug Srikanth reported on Issue #79.
+                let body_limit_mb = http_body_limit_mb();
+                let body_limit_bytes = body_limit_mb.saturating_mul(1024 * 1024);
+                let spec_router =
+                    spec_router.layer(axum::extract::DefaultBodyLimit::max(body_limit_bytes));
+                tracing::info!(
+                    body_limit_mb = body_limit_mb,
+                    "Merging OpenAPI router with main router"
+                );
+                app = app.merge(spec_router);
+            }
+            Err(e) => {
+                warn!("Failed to load OpenAPI spec from {:?}: {}. Starting without OpenAPI integration.", spec_path, e);
+            }
+        }
+    }
+
+    // Shared route-chaos state: holds runtime rules (see the route-chaos API
+    // below) and counts faults injected by both static and runtime rules for
+    // the chaos status feed.
+    let route_chaos_state = route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new());
+    if let Some(ref route_configs) = route_configs {
+        route_chaos_state.set_static_routes(route_configs.clone());
+    }
+
+    // Register custom routes from config with advanced routing features
+    // Create RouteChaosInjector for advanced fault injection and latency
+    // Store as trait object to avoid circular dependency (RouteChaosInjectorTrait is in mockforge-core)
+    let route_chaos_injector: Option<
+        std::sync::Arc<dyn mockforge_core::priority_handler::RouteChaosInjectorTrait>,
+    > = if let Some(ref route_configs) = route_configs {
+        if !route_configs.is_empty() {
+            // Convert to the type expected by RouteChaosInjector
+            // Note: Both use the same mockforge-core, but we need to ensure type compatibility
+            let route_configs_converted: Vec<mockforge_core::config::RouteConfig> =
+                route_configs.to_vec();
+            match mockforge_route_chaos::RouteChaosInjector::new(route_configs_converted) {
+                Ok(injector) => {
+                    info!(
+                        "Initialized advanced routing features for {} route(s)",
+                        route_configs.len()
+                    );
+                    // RouteChaosInjector implements RouteChaosInjectorTrait, so we can cast it
+                    // The trait is implemented in mockforge-route-chaos/src/lib.rs
+                    Some(std::sync::Arc::new(injector)
+                        as std::sync::Arc<
+                            dyn mockforge_core::priority_handler::RouteChaosInjectorTrait,
+                        >)
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to initialize advanced routing features: {}. Using basic routing.",
+                        e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    if let Some(route_configs) = route_configs {
+        use axum::http::StatusCode;
+        use axum::response::IntoResponse;
+
+        if !route_configs.is_empty() {
+            info!("Registering {} custom route(s) from config", route_configs.len());
+        }
+
+        let injector = route_chaos_injector.clone();
+        for route_config in route_configs {
+            app = app.route(
+                &route_config.path,
+                custom_route_handler(
+                    &route_config,
+                    template_expand,
+                    injector.clone(),
+                    route_chaos_state.clone(),
+                ),
+            );
+            debug!("Registered route: {} {}", route_config.method, route_config.path);
+        }
+    }
+
+    // Add health check endpoints
+    if let Some(health) = health_manager {
+        // Use comprehensive health check router with all probe endpoints
+        app = app.merge(health::health_router(health));
+        info!(
+            "Health check endpoints enabled: /health, /health/live, /health/ready, /health/startup"
+        );
+    } else if include_default_health {
+        // Fallback to basic health endpoint for backwards compatibility
+        app = app.route(
+            "/health",
+            axum::routing::get(|| async {
+                use mockforge_core::server_utils::health::HealthStatus;
+                {
+                    // HealthStatus should always serialize, but handle errors gracefully
+                    match serde_json::to_value(HealthStatus::healthy(0, "mockforge-http")) {
+                        Ok(value) => Json(value),
+                        Err(e) => {
+                            // Log error but return a simple healthy response
+                            tracing::error!("Failed to serialize health status: {}", e);
+                            Json(serde_json::json!({
+                                "status": "healthy",
+                                "service": "mockforge-http",
+                                "uptime_seconds": 0
+                            }))
+                        }
+                    }
+                }
+            }),
+        );
+    }
+
+    app = app.merge(sse::sse_router());
+    // Add file serving endpoints for generated mock files
+    app = app.merge(file_server::file_serving_router());
+
+    // Add management API endpoints
+    // Load spec for ManagementState so /__mockforge/api/spec can serve it
+    let mgmt_spec = if let Some(ref sp) = spec_path {
+        match OpenApiSpec::from_file(sp).await {
+            Ok(s) => Some(Arc::new(s)),
+            Err(e) => {
+                debug!("Failed to load OpenAPI spec for management API: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    let spec_path_clone = spec_path.clone();
+    let mgmt_port = std::env::var("PORT")
+        .or_else(|_| std::env::var("MOCKFORGE_HTTP_PORT"))
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(3000);
+    let management_state = ManagementState::new(mgmt_spec, spec_path_clone, mgmt_port);
+
+    // Create WebSocket state and connect it to management state
+    use std::sync::Arc;
+    let ws_state = WsManagementState::new();
+    let ws_broadcast = Arc::new(ws_state.tx.clone());
+    let management_state = management_state.with_ws_broadcast(ws_broadcast);
+
+    // Attach the effective config published at startup (config export, bulk updates)
+    let management_state = match mockforge_core::config::effective_config() {
+        Some(config) => management_state.with_server_config(Arc::new(RwLock::new(config))),
+        None => management_state,
+    };
+
+    // Add proxy config to management state if available
+    let management_state = if let Some(proxy_cfg) = proxy_config {
+        use tokio::sync::RwLock;
+        let proxy_config_arc = Arc::new(RwLock::new(proxy_cfg));
+        management_state.with_proxy_config(proxy_config_arc)
+    } else {
+        management_state
+    };
+
+    #[cfg(feature = "smtp")]
+    let management_state = {
+        if let Some(smtp_reg) = smtp_registry {
+            match smtp_reg.downcast::<mockforge_smtp::SmtpSpecRegistry>() {
+                Ok(smtp_reg) => management_state.with_smtp_registry(smtp_reg),
+                Err(e) => {
+                    error!(
+                        "Invalid SMTP registry type passed to HTTP management state: {:?}",
+                        e.type_id()
+                    );
+                    management_state
+                }
+            }
+        } else {
+            management_state
+        }
+    };
+    #[cfg(not(feature = "smtp"))]
+    let management_state = {
+        let _ = smtp_registry;
+        management_state
+    };
+    #[cfg(feature = "mqtt")]
+    let management_state = {
+        // The `mqtt_broker` slot carries the live `Arc<SessionManager>` (the
+        // running listener's state), not an `MqttBroker` — see issue #730.
+        if let Some(sessions) = mqtt_broker {
+            match sessions.downcast::<mockforge_mqtt::SessionManager>() {
+                Ok(sessions) => management_state.with_mqtt_sessions(sessions),
+                Err(e) => {
+                    error!(
+                        "Invalid MQTT session manager passed to HTTP management state: {:?}",
+                        e.type_id()
+                    );
+                    management_state
+                }
+            }
+        } else {
+            management_state
+        }
+    };
+    #[cfg(not(feature = "mqtt"))]
+    let management_state = {
+        let _ = mqtt_broker;
+        management_state
+    };
+    #[cfg(feature = "amqp")]
+    let management_state = {
+        if let Some(broker) = amqp_broker {
+            match broker.downcast::<mockforge_amqp::AmqpBroker>() {
+                Ok(broker) => management_state.with_amqp_broker(broker),
+                Err(e) => {
+                    error!(
+                        "Invalid AMQP broker passed to HTTP management state: {:?}",
+                        e.type_id()
+                    );
+                    management_state
+                }
+            }
+        } else {
+            management_state
+        }
+    };
+    #[cfg(not(feature = "amqp"))]
+    let management_state = {
+        let _ = amqp_broker;
+        management_state
+    };
+    #[cfg(feature = "kafka")]
+    let management_state = {
+        if let Some(broker) = kafka_broker {
+            match broker.downcast::<mockforge_kafka::KafkaMockBroker>() {
+                Ok(broker) => management_state.with_kafka_broker(broker),
+                Err(e) => {
+                    error!(
+                        "Invalid Kafka broker passed to HTTP management state: {:?}",
+                        e.type_id()
+                    );
+                    management_state
+                }
+            }
+        } else {
+            management_state
+        }
+    };
+    #[cfg(not(feature = "kafka"))]
+    let management_state = {
+        let _ = kafka_broker;
+        management_state
+    };
+    let management_state = management_state.with_route_chaos(route_chaos_state.clone());
+    let management_state_for_fallback = management_state.clone();
+    app = app.nest("/__mockforge/api", management_router(management_state));
+    // Dynamic-mock fallback; see identical block earlier in this file.
+    app = app.fallback_service(
+        axum::routing::any(management::dynamic_mock_fallback)
+            .with_state(management_state_for_fallback),
+    );
+
+    // Add verification API endpoint
+    app = app.merge(verification_router());
+
+    // Mount the request-chains management API at the prefix the UI's
+    // ChainsPage expects (`/__mockforge/chains/*`). Without this nest the
+    // page 404s on every call. The registry/engine are constructed from
+    // the chain_config param when supplied, otherwise from defaults.
+    {
+        use crate::chain_handlers::{chains_router, create_chain_state};
+        let chain_config = _circling_config.clone().unwrap_or_default();
+        let chain_registry = Arc::new(mockforge_core::request_chaining::RequestChainRegistry::new(
+            chain_config.clone(),
+        ));
+        let chain_engine = Arc::new(mockforge_core::chain_execution::ChainExecutionEngine::new(
+            chain_registry.clone(),
+            chain_config,
+        ));
+        app = app.nest(
+            "/__mockforge/chains",
+            chains_router(create_chain_state(chain_registry, chain_engine)),
+        );
+    }
+
+    // Contract-diff retrieval API. The `capture_for_contract_diff`
+    // middleware (layered earlier) populates the global capture manager
+    // on every request; this surface lets operators read the captures
+    // and run the analyser against the current OpenAPI spec.
+    {
+        use crate::contract_diff_api::{contract_diff_api_router, ContractDiffApiState};
+        let cd_state = Arc::new(ContractDiffApiState::new(spec_path.clone()));
+        app = app.nest("/__mockforge/api/contract-diff", contract_diff_api_router(cd_state));
+    }
+
+    // Fixtures management API. Lives on the admin server too (port 9080),
+    // but hosted-mock Fly machines only expose port 3000 publicly — so
+    // we mount the same surface on the main HTTP app. Newly-uploaded
+    // fixtures land in `MOCKFORGE_FIXTURES_DIR` and are picked up by
+    // the startup-time loader on the next deploy/restart; live reload
+    // is a separate concern.
+    {
+        use crate::fixtures_api::{fixtures_api_router, FixturesApiState};
+        let fx_state = FixturesApiState::from_env();
+        app = app.nest("/__mockforge/fixtures", fixtures_api_router(fx_state));
+    }
+
+    // Standalone MockAI API. Until now MockAI was only invoked from the
+    // OpenAPI route generator — so a hosted mock without a spec, or a
+    // user wanting to prototype an "AI persona" mock without registering
+    // routes, had no way to reach the engine. The endpoint surfaces 503
+    // when MockAI isn't configured (no API key) so callers get a clear
+    // signal rather than a silent failure.
+    {
+        use crate::mockai_api::{mockai_api_router, MockAiApiState};
+        let api_state = MockAiApiState::new(mockai.clone());
+        app = app.nest("/__mockforge/api/mockai", mockai_api_router(api_state));
+    }
+
+    // Time-travel runtime API. The admin server mounts these routes on
+    // its own port (9080), but hosted-mock Fly machines only expose
+    // port 3000 publicly — so until now operators couldn't set / advance
+    // virtual time on a deployed mock. The handlers consult a process-
+    // wide TimeTravelManager that serve.rs initialises alongside the
+    // existing admin-server registration; both paths see the same Arc.
+    app = app.nest("/__mockforge/time-travel", time_travel_api::time_travel_router());
+
+    // Runtime route-chaos rules API + middleware. This sits in front of
+    // the static per-route handlers so operators can add/remove fault and
+    // latency rules without redeploying. Static rules from the YAML
+    // config still apply to their routes; runtime rules are additive.
+    {
+        use crate::route_chaos_runtime::{route_chaos_api_router, runtime_route_chaos_middleware};
+        let runtime_state = route_chaos_state.clone();
+        let middleware_state = runtime_state.clone();
+        app = app.layer(from_fn_with_state(middleware_state, runtime_route_chaos_middleware));
+        app = app.nest("/__mockforge/api/route-chaos", route_chaos_api_router(runtime_state));
+    }
+
+    // Runtime network-profile switching. Operators activate a named
+    // catalog profile (e.g., "mobile_3g") and the middleware applies its
+    // latency to every subsequent request — useful for "what does my
+    // consumer look like under bad network" probes against a live
+    // hosted mock without a redeploy.
+    {
+        use crate::network_profile_runtime::{
+            network_profile_api_router, network_profile_middleware, NetworkProfileRuntimeState,
+        };
+        let runtime_state = NetworkProfileRuntimeState::new(
+            mockforge_core::network_profiles::NetworkProfileCatalog::new(),
+        );
+        let middleware_state = runtime_state.clone();
+        app = app.layer(from_fn_with_state(middleware_state, network_profile_middleware));
+        app = app
+            .nest("/__mockforge/api/network-profiles", network_profile_api_router(runtime_state));
+    }
+
+    // Runtime global latency: `PUT /__mockforge/api/config/latency` slows
+    // every route down until it is removed again, without a restart.
+    {
+        use crate::global_latency_runtime::{
+            global_latency_api_router, global_latency_middleware, GlobalLatencyRuntimeState,
+        };
+        let runtime_state = GlobalLatencyRuntimeState::new();
+        app = app.layer(from_fn_with_state(runtime_state.clone(), global_latency_middleware));
+        app = app.nest("/__mockforge/api/config/latency", global_latency_api_router(runtime_state));
+    }
+
+    // Runtime network partition: `POST /__mockforge/api/partition` makes the
+    // selected routes hang until the partition is cleared or times out.
+    {
+        use crate::partition_runtime::{
+            partition_api_router, partition_middleware, PartitionRuntimeState,
+        };
+        let runtime_state = PartitionRuntimeState::new();
+        app = app.layer(from_fn_with_state(runtime_state.clone(), partition_middleware));
+        app = app.nest("/__mockforge/api/partition", partition_api_router(runtime_state));
+    }
+
+    // Add OIDC well-known endpoints
+    use crate::auth::oidc::oidc_router;
+    app = app.merge(oidc_router());
+
+    // Add access review API if enabled
+    {
+        use mockforge_core::security::get_global_access_review_service;
+        if let Some(service) = get_global_access_review_service().await {
+            use crate::handlers::access_review::{access_review_router, AccessReviewState};
+            let review_state = AccessReviewState { service };
+            app = app.nest("/api/v1/security/access-reviews", access_review_router(review_state));
+            debug!("Access review API mounted at /api/v1/security/access-reviews");
+        }
+    }
+
+    // Add privileged access API if enabled
+    {
+        use mockforge_core::security::get_global_privileged_access_manager;
+        if let Some(manager) = get_global_privileged_access_manager().await {
+            use crate::handlers::privileged_access::{
+                privileged_access_router, PrivilegedAccessState,
+            };
+            let privileged_state = PrivilegedAccessState { manager };
+            app = app.nest(
+                "/api/v1/security/privileged-access",
+                privileged_access_router(privileged_state),
+            );
+            debug!("Privileged access API mounted at /api/v1/security/privileged-access");
+        }
+    }
+
+    // Add change management API if enabled
+    {
+        use mockforge_core::security::get_global_change_management_engine;
+        if let Some(engine) = get_global_change_management_engine().await {
+            use crate::handlers::change_management::{
+                change_management_router, ChangeManagementState,
+            };
+            let change_state = ChangeManagementState { engine };
+            app = app.nest("/api/v1/change-management", change_management_router(change_state));
+            debug!("Change management API mounted at /api/v1/change-management");
+        }
+    }
+
+    // Add risk assessment API if enabled
+    {
+        use mockforge_core::security::get_global_risk_assessment_engine;
+        if let Some(engine) = get_global_risk_assessment_engine().await {
+            use crate::handlers::risk_assessment::{risk_assessment_router, RiskAssessmentState};
+            let risk_state = RiskAssessmentState { engine };
+            app = app.nest("/api/v1/security", risk_assessment_router(risk_state));
+            debug!("Risk assessment API mounted at /api/v1/security/risks");
+        }
+    }
+
+    // One lifecycle manager shared by the token lifecycle API, OAuth2 server and
+    // consent screen, so clock skew and revocations apply to issued tokens
+    let token_lifecycle_manager =
+        Arc::new(crate::auth::token_lifecycle::TokenLifecycleManager::default());
+
+    // Add token lifecycle API
+    {
+        use crate::handlers::token_lifecycle::{
+            issued_tokens_router, token_lifecycle_router, TokenLifecycleState,
+        };
+        let lifecycle_manager = token_lifecycle_manager.clone();
+        let lifecycle_state = TokenLifecycleState {
+            manager: lifecycle_manager,
+        };
+        app = app.nest("/__mockforge/api/auth", issued_tokens_router(lifecycle_state.clone()));
+        app = app.nest("/api/v1/auth", token_lifecycle_router(lifecycle_state));
+        debug!("Token lifecycle API mounted at /api/v1/auth");
+    }
+
+    // Add OAuth2 server endpoints
+    {
+        use crate::auth::oidc::load_oidc_state;
+        use crate::handlers::oauth2_server::{oauth2_server_router, OAuth2ServerState};
+        // Load OIDC state from configuration (environment variables or config file)
+        let oidc_state = Arc::new(RwLock::new(load_oidc_state()));
+        let lifecycle_manager = token_lifecycle_manager.clone();
+        let oauth2_state = OAuth2ServerState {
+            oidc_state,
+            lifecycle_manager,
+            auth_codes: Arc::new(RwLock::new(HashMap::new())),
+            refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
+        };
+        app = app.merge(oauth2_server_router(oauth2_state));
+        debug!("OAuth2 server endpoints mounted at /oauth2/authorize and /oauth2/token");
+    }
+
+    // Add consent screen endpoints
+    {
+        use crate::auth::oidc::load_oidc_state;
+        use crate::auth::risk_engine::RiskEngine;
+        use crate::handlers::consent::{consent_router, ConsentState};
+        use crate::handlers::oauth2_server::OAuth2ServerState;
+        // Load OIDC state from configuration (environment variables or config file)
+        let oidc_state = Arc::new(RwLock::new(load_oidc_state()));
+        let lifecycle_manager = token_lifecycle_manager.clone();
+        let oauth2_state = OAuth2ServerState {
+            oidc_state: oidc_state.clone(),
+            lifecycle_manager: lifecycle_manager.clone(),
+            auth_codes: Arc::new(RwLock::new(HashMap::new())),
+            refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
+        };
+        let risk_engine = Arc::new(RiskEngine::default());
+        let consent_state = ConsentState {
+            oauth2_state,
+            risk_engine,
+        };
+        app = app.merge(consent_router(consent_state));
+        debug!("Consent screen endpoints mounted at /consent");
+    }
+
+    // Add risk simulation API
+    {
+        use crate::auth::risk_engine::RiskEngine;
+        use crate::handlers::risk_simulation::{risk_simulation_router, RiskSimulationState};
+        let risk_engine = Arc::new(RiskEngine::default());
+        let risk_state = RiskSimulationState { risk_engine };
+        app = app.nest("/api/v1/auth", risk_simulation_router(risk_state));
+        debug!("Risk simulation API mounted at /api/v1/auth/risk");
+    }
+
+    // Initialize database connection (optional)
+    #[cfg(feature = "database")]
+    let database = {
+        use crate::database::Database;
+        let database_url = std::env::var("DATABASE_URL").ok();
+        match Database::connect_optional(database_url.as_deref()).await {
+            Ok(db) => {
+                if db.is_connected() {
+                    // Run migrations if database is connected
+                    if let Err(e) = db.migrate_if_connected().await {
+                        warn!("Failed to run database migrations: {}", e);
+                    } else {
+                        info!("Database connected and migrations applied");
+                    }
+                }
+                Some(db)
+            }
+            Err(e) => {
+                warn!("Failed to connect to database: {}. Continuing without database support.", e);
+                None
+            }
+        }
+    };
+
+    // Add drift budget and incident management endpoints
+    // Initialize shared components for drift tracking and protocol contracts
+    let (drift_engine, incident_manager, drift_config) = {
+        use mockforge_core::contract_drift::{DriftBudgetConfig, DriftBudgetEngine};
+        use mockforge_core::incidents::{IncidentManager, IncidentStore};
+        use std::sync::Arc;
+
+        // Initialize drift budget engine with default config
+        let drift_config = DriftBudgetConfig::default();
+        let drift_engine = Arc::new(DriftBudgetEngine::new(drift_config.clone()));
+
+        // Initialize incident store and manager
+        let incident_store = Arc::new(IncidentStore::default());
+        let incident_manager = Arc::new(IncidentManager::new(incident_store.clone()));
+
+        (drift_engine, incident_manager, drift_config)
+    };
+
+    {
+        use crate::handlers::drift_budget::{drift_budget_router, DriftBudgetState};
+        use crate::middleware::drift_tracking::DriftTrackingState;
+        use mockforge_contracts::consumer_contracts::{
+            ConsumerBreakingChangeDetector, UsageRecorder,
+        };
+        use mockforge_core::ai_contract_diff::ContractDiffAnalyzer;
+        use std::sync::Arc;
+
+        // Initialize usage recorder and consumer detector
+        let usage_recorder = Arc::new(UsageRecorder::default());
+        let consumer_detector =
+            Arc::new(ConsumerBreakingChangeDetector::new(usage_recorder.clone()));
+
+        // Initialize contract diff analyzer if enabled
+        let diff_analyzer = if drift_config.enabled {
+            match ContractDiffAnalyzer::new(
+                mockforge_core::ai_contract_diff::ContractDiffConfig::default(),
+            ) {
+                Ok(analyzer) => Some(Arc::new(analyzer)),
+                Err(e) => {
+                    warn!("Failed to create contract diff analyzer: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Get OpenAPI spec if available
+        // Note: Load from spec_path if available, or leave as None for manual configuration.
+        let spec = if let Some(ref spec_path) = spec_path {
+            match OpenApiSpec::from_file(spec_path).await {
+                Ok(s) => Some(Arc::new(s)),
+                Err(e) => {
+                    debug!("Failed to load OpenAPI spec for drift tracking: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        // Create drift tracking state
+        let drift_tracking_state = DriftTrackingState {
+            diff_analyzer,
+            spec,
+            drift_engine: drift_engine.clone(),
+            incident_manager: incident_manager.clone(),
+            usage_recorder,
+            consumer_detector,
+            enabled: drift_config.enabled,
+        };
+
+        // Add response body buffering middleware (before drift tracking)
+        app = app.layer(axum::middleware::from_fn(middleware::buffer_response_middleware));
+
+        // Add drift tracking middleware (after response buffering)
+        // Use a wrapper that inserts state into extensions before calling the middleware
+        let drift_tracking_state_clone = drift_tracking_state.clone();
+        app = app.layer(axum::middleware::from_fn(
+            move |mut req: axum::extract::Request, next: axum::middleware::Next| {
+                let state = drift_tracking_state_clone.clone();
+                async move {
+                    // Insert state into extensions if not already present
+                    if req.extensions().get::<DriftTrackingState>().is_none() {
+                        req.extensions_mut().insert(state);
+                    }
+                    // Call the middleware function
+                    middleware::drift_tracking::drift_tracking_middleware_with_extensions(req, next)
+                        .await
+                }
+            },
+        ));
+
+        let drift_state = DriftBudgetState {
+            engine: drift_engine.clone(),
+            incident_manager: incident_manager.clone(),
+            gitops_handler: None, // Can be initialized later if GitOps is configured
+        };
+
+        app = app.merge(drift_budget_router(drift_state));
+        debug!("Drift budget and incident management endpoints mounted at /api/v1/drift");
+    }
+
+    // Add pipeline management endpoints (MockOps)
+    #[cfg(feature = "pipelines")]
+    {
+        use crate::handlers::pipelines::{pipeline_router, PipelineState};
+
+        let pipeline_state = PipelineState::new();
+        app = app.merge(pipeline_router(pipeline_state));
+        debug!("Pipeline management endpoints mounted at /api/v1/pipelines");
+    }
+
+    // Add governance endpoints (forecasting, semantic drift, threat modeling, contract health)
+    {
+        use crate::handlers::contract_health::{contract_health_router, ContractHealthState};
+        use mockforge_contracts::contract_drift::forecasting::{Forecaster, ForecastingConfig};
+        use mockforge_core::contract_drift::threat_modeling::ThreatAnalyzer;
+        use mockforge_core::incidents::semantic_manager::SemanticIncidentManager;
+        use mockforge_foundation::threat_modeling_types::ThreatModelingConfig;
+        use mockforge_intelligence::handlers::forecasting::{forecasting_router, ForecastingState};
+        use mockforge_intelligence::handlers::semantic_drift::{
+            semantic_drift_router, SemanticDriftState,
+        };
+        use mockforge_intelligence::handlers::threat_modeling::{
+            threat_modeling_router, ThreatModelingState,
+        };
+        use std::sync::Arc;
+
+        // Initialize forecasting
+        let forecasting_config = ForecastingConfig::default();
+        let forecaster = Arc::new(Forecaster::new(forecasting_config));
+        let forecasting_state = ForecastingState {
+            forecaster,
+            #[cfg(feature = "database")]
+            database: database.clone(),
+        };
+
+        // Initialize semantic drift manager
+        let semantic_manager = Arc::new(SemanticIncidentManager::new());
+        let semantic_state = SemanticDriftState {
+            manager: semantic_manager,
+            #[cfg(feature = "database")]
+            database: database.clone(),
+        };
+
+        // Initialize threat analyzer
+        let threat_config = ThreatModelingConfig::default();
+        let threat_analyzer = match ThreatAnalyzer::new(threat_config) {
+            Ok(analyzer) => Arc::new(analyzer),
+            Err(e) => {
+                warn!("Failed to create threat analyzer: {}. Using default.", e);
+                Arc::new(ThreatAnalyzer::new(ThreatModelingConfig::default()).unwrap_or_else(
+                    |_| {
+                        // Fallback to a minimal config if default also fails
+                        ThreatAnalyzer::new(ThreatModelingConfig {
+                            enabled: false,
+                            ..Default::default()
+                        })
+                        .expect("Failed to create fallback threat analyzer")
+                    },
+                ))
+            }
+        };
+        // Load webhook configs from ServerConfig
+        let mut webhook_configs = Vec::new();
+        let config_paths = [
+            "config.yaml",
+            "mockforge.yaml",
+            "tools/mockforge/config.yaml",
+            "../tools/mockforge/config.yaml",
+        ];
+
+        for path in &config_paths {
+            if let Ok(config) = mockforge_core::config::load_config(path).await {
+                if !config.incidents.webhooks.is_empty() {
+                    webhook_configs = config.incidents.webhooks.clone();
+                    info!("Loaded {} webhook configs from config: {}", webhook_configs.len(), path);
+                    break;
+                }
+            }
+        }
+
+        if webhook_configs.is_empty() {
+            debug!("No webhook configs found in config files, using empty list");
+        }
+
+        let threat_state = ThreatModelingState {
+            analyzer: threat_analyzer,
+            webhook_configs,
+            #[cfg(feature = "database")]
+            database: database.clone(),
+        };
+
+        // Initialize contract health state
+        let contract_health_state = ContractHealthState {
+            incident_manager: incident_manager.clone(),
+            semantic_manager: Arc::new(SemanticIncidentManager::new()),
+            #[cfg(feature = "database")]
+            database: database.clone(),
+        };
+
+        // Register routers
+        app = app.merge(forecasting_router(forecasting_state));
+        debug!("Forecasting endpoints mounted at /api/v1/forecasts");
+
+        app = app.merge(semantic_drift_router(semantic_state));
+        debug!("Semantic drift endpoints mounted at /api/v1/semantic-drift");
+
+        app = app.merge(threat_modeling_router(threat_state));
+        debug!("Threat modeling endpoints mounted at /api/v1/threats");
+
+        app = app.merge(contract_health_router(contract_health_state));
+        debug!("Contract health endpoints mounted at /api/v1/contract-health");
+    }
+
+    // Add protocol contracts endpoints with fitness registry initialization
+    {
+        use crate::handlers::protocol_contracts::{
+            protocol_contracts_router, ProtocolContractState,
+        };
+        use mockforge_core::contract_drift::{
+            ConsumerImpactAnalyzer, FitnessFunctionRegistry, ProtocolContractRegistry,
+        };
+        use std::sync::Arc;
+        use tokio::sync::RwLock;
+
+        // Initialize protocol contract registry
+        let contract_registry = Arc::new(RwLock::new(ProtocolContractRegistry::new()));
+
+        // Initialize fitness function registry and load from config
+        let mut fitness_registry = FitnessFunctionRegistry::new();
+
+        // Try to load config and populate fitness rules
+        let config_paths = [
+            "config.yaml",
+            "mockforge.yaml",
+            "tools/mockforge/config.yaml",
+            "../tools/mockforge/config.yaml",
+        ];
+
+        let mut config_loaded = false;
+        for path in &config_paths {
+            if let Ok(config) = mockforge_core::config::load_config(path).await {
+                if !config.contracts.fitness_rules.is_empty() {
+                    if let Err(e) =
+                        fitness_registry.load_from_config(&config.contracts.fitness_rules)
+                    {
+                        warn!("Failed to load fitness rules from config {}: {}", path, e);
+                    } else {
+                        info!(
+                            "Loaded {} fitness rules from config: {}",
+                            config.contracts.fitness_rules.len(),
+                            path
+                        );
+                        config_loaded = true;
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !config_loaded {
+            debug!("No fitness rules found in config files, using empty registry");
+        }
+
+        let fitness_registry = Arc::new(RwLock::new(fitness_registry));
+
+        // Reuse drift engine and incident manager from drift budget section
+
+        // Initialize consumer impact analyzer
+        let consumer_mapping_registry =
+            mockforge_core::contract_drift::ConsumerMappingRegistry::new();
+        let consumer_analyzer =
+            Arc::new(RwLock::new(ConsumerImpactAnalyzer::new(consumer_mapping_registry)));
+
+        let protocol_state = ProtocolContractState {
+            registry: contract_registry,
+            drift_engine: Some(drift_engine.clone()),
+            incident_manager: Some(incident_manager.clone()),
+            fitness_registry: Some(fitness_registry),
+            consumer_analyzer: Some(consumer_analyzer),
+        };
+
+        app = app.nest("/api/v1/contracts", protocol_contracts_router(protocol_state));
+        debug!("Protocol contracts endpoints mounted at /api/v1/contracts");
+    }
+
+    // Add behavioral cloning middleware (optional - applies learned behavior to requests)
+    #[cfg(feature = "behavioral-cloning")]
+    {
+        use crate::middleware::behavioral_cloning::BehavioralCloningMiddlewareState;
+        use std::path::PathBuf;
+
+        // Determine database path (defaults to ./recordings.db)
+        let db_path = std::env::var("RECORDER_DATABASE_PATH")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| std::env::current_dir().ok().map(|p| p.join("recordings.db")));
+
+        let bc_middleware_state = if let Some(path) = db_path {
+            BehavioralCloningMiddlewareState::with_database_path(path)
+        } else {
+            BehavioralCloningMiddlewareState::new()
+        };
+
+        // Only enable if BEHAVIORAL_CLONING_ENABLED is set to true
+        let enabled = std::env::var("BEHAVIORAL_CLONING_ENABLED")
+            .ok()
+            .and_then(|v| v.parse::<bool>().ok())
+            .unwrap_or(false);
+
+        if enabled {
+            let bc_state_clone = bc_middleware_state.clone();
+            app = app.layer(axum::middleware::from_fn(
+                move |mut req: axum::extract::Request, next: axum::middleware::Next| {
+                    let state = bc_state_clone.clone();
+                    async move {
+                        // Insert state into extensions if not already present
+                        if req.extensions().get::<BehavioralCloningMiddlewareState>().is_none() {
+                            req.extensions_mut().insert(state);
+                        }
+                        // Call the middleware function
+                        middleware::behavioral_cloning::behavioral_cloning_middleware(req, next)
+                            .await
+                    }
+                },
+            ));
+            debug!("Behavioral cloning middleware enabled (applies learned behavior to requests)");
+        }
+    }
+
+    // Add consumer contracts endpoints
+    {
+        use crate::handlers::consumer_contracts::{
+            consumer_contracts_router, ConsumerContractsState,
+        };
+        use mockforge_contracts::consumer_contracts::{
+            ConsumerBreakingChangeDetector, ConsumerRegistry, UsageRecorder,
+        };
+        use std::sync::Arc;
+
+        // Initialize consumer registry
+        let registry = Arc::new(ConsumerRegistry::default());
+
+        // Initialize usage recorder
+        let usage_recorder = Arc::new(UsageRecorder::default());
+
+        // Initialize breaking change detector
+        let detector = Arc::new(ConsumerBreakingChangeDetector::new(usage_recorder.clone()));
+
+        let consumer_state = ConsumerContractsState {
+            registry,
+            usage_recorder,
+            detector,
+            violations: Arc::new(RwLock::new(HashMap::new())),
+        };
+
+        app = app.merge(consumer_contracts_router(consumer_state));
+        debug!("Consumer contracts endpoints mounted at /api/v1/consumers");
+    }
+
+    // Add behavioral cloning endpoints
+    #[cfg(feature = "behavioral-cloning")]
+    {
+        use crate::handlers::behavioral_cloning::{
+            behavioral_cloning_router, BehavioralCloningState,
+        };
+        use std::path::PathBuf;
+
+        // Determine database path (defaults to ./recordings.db)
+        let db_path = std::env::var("RECORDER_DATABASE_PATH")
+            .ok()
+            .map(PathBuf::from)
+            .or_else(|| std::env::current_dir().ok().map(|p| p.join("recordings.db")));
+
+        let bc_state = if let Some(path) = db_path {
+            BehavioralCloningState::with_database_path(path)
+        } else {
+            BehavioralCloningState::new()
+        };
+
+        app = app.merge(behavioral_cloning_router(bc_state));
+        debug!("Behavioral cloning endpoints mounted at /api/v1/behavioral-cloning");
+    }
+
+    // Add consistency engine and cross-protocol state management
+    {
+        use crate::consistency::{ConsistencyMiddlewareState, HttpAdapter};
+        use mockforge_intelligence::consistency::ConsistencyEngine;
+        use mockforge_intelligence::handlers::consistency::{consistency_router, ConsistencyState};
+        use std::sync::Arc;
+
+        // Initialize consistency engine
+        let consistency_engine = Arc::new(ConsistencyEngine::new());
+
+        // Create and register HTTP adapter
+        let http_adapter = Arc::new(HttpAdapter::new(consistency_engine.clone()));
+        consistency_engine.register_adapter(http_adapter.clone()).await;
+
+        // Create consistency state for handlers
+        let consistency_state = ConsistencyState {
+            engine: consistency_engine.clone(),
+        };
+
+        // Create X-Ray state first (needed for middleware)
+        use mockforge_intelligence::handlers::xray::XRayState;
+        let xray_state = Arc::new(XRayState::new(consistency_engine.clone()));
+
+        // Create consistency middleware state
+        let consistency_middleware_state = ConsistencyMiddlewareState {
+            engine: consistency_engine.clone(),
+            adapter: http_adapter,
+            xray_state: Some(xray_state.clone()),
+        };
+
+        // Reality-driven mock/proxy middleware (#222). Must be added
+        // BEFORE the consistency layer so it ends up inner — that way
+        // each request hits consistency first (which injects UnifiedState
+        // including reality_continuum_ratio), then this middleware reads
+        // that state and either forwards to upstream or falls through to
+        // the mock chain. Activated only when MOCKFORGE_PROXY_UPSTREAM is
+        // set; absent that, the layer isn't even installed.
+        if let Some(reality_cfg) = reality_proxy::RealityProxyConfig::from_env() {
+            tracing::info!(
+                upstream = %reality_cfg.upstream_base,
+                "Reality-driven proxy middleware enabled — requests will be split between mock and upstream based on reality_continuum_ratio"
+            );
+            app = app.layer(axum::middleware::from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let cfg = reality_cfg.clone();
+                    async move { reality_proxy::reality_proxy_middleware(cfg, req, next).await }
+                },
+            ));
+        }
+
+        // Add consistency middleware (before other middleware to inject state early)
+        let consistency_middleware_state_clone = consistency_middleware_state.clone();
+        app = app.layer(axum::middleware::from_fn(
+            move |mut req: axum::extract::Request, next: axum::middleware::Next| {
+                let state = consistency_middleware_state_clone.clone();
+                async move {
+                    // Insert state into extensions if not already present
+                    if req.extensions().get::<ConsistencyMiddlewareState>().is_none() {
+                        req.extensions_mut().insert(state);
+                    }
+                    // Call the middleware function
+                    consistency::middleware::consistency_middleware(req, next).await
+                }
+            },
+        ));
+
+        // Add consistency API endpoints
+        app = app.merge(consistency_router(consistency_state));
+        debug!("Consistency engine initialized and endpoints mounted at /api/v1/consistency");
+
+        // Runtime named-scenario activation API. Locally-installed
+        // scenarios (from `~/.mockforge/scenario-metadata/*.json`) become
+        // listable + activatable via HTTP; activation writes through to
+        // the consistency engine's active_scenario slot for the chosen
+        // workspace. Failure to load the scenario index is non-fatal —
+        // the API just shows an empty list, which matches the natural
+        // state of a fresh deployment with no scenarios installed yet.
+        #[cfg(feature = "scenario-engine")]
+        {
+            use crate::scenarios_runtime::{scenarios_api_router, ScenarioRuntimeState};
+            let mut scenario_storage = match mockforge_scenarios::ScenarioStorage::new() {
+                Ok(s) => s,
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        "Failed to init scenario storage; runtime scenarios API will list empty"
+                    );
+                    // Construct an isolated tempdir-backed storage so the
+                    // API still mounts but lists nothing.
+                    let tmp = std::env::temp_dir().join("mockforge-empty-scenarios");
+                    mockforge_scenarios::ScenarioStorage::with_dir(&tmp)
+                        .expect("temp scenario storage")
+                }
+            };
+            if let Err(e) = scenario_storage.load().await {
+                tracing::warn!(
+                    error = %e,
+                    "Failed to load installed scenarios; API will list empty until scenarios are installed"
+                );
+            }
+            let scenarios_state =
+                ScenarioRuntimeState::new(scenario_storage, consistency_engine.clone());
+            app = app.nest("/__mockforge/api/scenarios", scenarios_api_router(scenarios_state));
+            debug!("Scenario runtime API mounted at /__mockforge/api/scenarios");
+        }
+
+        // Add fidelity score endpoints
+        {
+            use mockforge_intelligence::handlers::fidelity::{fidelity_router, FidelityState};
+            let fidelity_state = FidelityState::new();
+            app = app.merge(fidelity_router(fidelity_state));
+            debug!("Fidelity score endpoints mounted at /api/v1/workspace/:workspace_id/fidelity");
+        }
+
+        // Add scenario studio endpoints
+        {
+            use mockforge_intelligence::handlers::scenario_studio::{
+                scenario_studio_router, ScenarioStudioState,
+            };
+            let scenario_studio_state = ScenarioStudioState::new();
+            app = app.merge(scenario_studio_router(scenario_studio_state));
+            debug!("Scenario Studio endpoints mounted at /api/v1/scenario-studio");
+        }
+
+        // Add performance mode endpoints
+        {
+            use crate::handlers::performance::{performance_router, PerformanceState};
+            let performance_state = PerformanceState::new();
+            app = app.nest("/api/performance", performance_router(performance_state));
+            debug!("Performance mode endpoints mounted at /api/performance");
+        }
+
+        // Add world state endpoints
+        {
+            use crate::handlers::world_state::{world_state_router, WorldStateState};
+            use mockforge_world_state::WorldStateEngine;
+            use std::sync::Arc;
+            use tokio::sync::RwLock;
+
+            let world_state_engine = Arc::new(RwLock::new(WorldStateEngine::new()));
+            let world_state_state = WorldStateState {
+                engine: world_state_engine,
+            };
+            app = app.nest("/api/world-state", world_state_router().with_state(world_state_state));
+            debug!("World state endpoints mounted at /api/world-state");
+        }
+
+        // Add snapshot management endpoints
+        {
+            use crate::handlers::snapshots::{snapshot_router, SnapshotState};
+            use mockforge_core::snapshots::SnapshotManager;
+            use std::path::PathBuf;
+
+            let snapshot_dir = std::env::var("MOCKFORGE_SNAPSHOT_DIR").ok().map(PathBuf::from);
+            let snapshot_manager = Arc::new(SnapshotManager::new(snapshot_dir));
+
+            let snapshot_state = SnapshotState {
+                manager: snapshot_manager,
+                consistency_engine: Some(consistency_engine.clone()),
+                workspace_persistence: None, // Can be initialized later if workspace persistence is available
+                vbr_engine: None, // Can be initialized when VBR engine is available in server state
+                recorder: None,   // Can be initialized when Recorder is available in server state
+            };
+
+            app = app.merge(snapshot_router(snapshot_state));
+            debug!("Snapshot management endpoints mounted at /api/v1/snapshots");
+
+            // Add X-Ray API endpoints for browser extension
+            {
+                use mockforge_intelligence::handlers::xray::xray_router;
+                app = app.merge(xray_router((*xray_state).clone()));
+                debug!("X-Ray API endpoints mounted at /api/v1/xray");
+            }
+        }
+
+        // Add A/B testing endpoints and middleware
+        {
+            use crate::handlers::ab_testing::{ab_testing_router, ABTestingState};
+            use crate::middleware::ab_testing::ab_testing_middleware;
+
+            let ab_testing_state = ABTestingState::new();
+
+            // Add A/B testing middleware (before other response middleware)
+            let ab_testing_state_clone = ab_testing_state.clone();
+            app = app.layer(axum::middleware::from_fn(
+                move |mut req: axum::extract::Request, next: axum::middleware::Next| {
+                    let state = ab_testing_state_clone.clone();
+                    async move {
+                        // Insert state into extensions if not already present
+                        if req.extensions().get::<ABTestingState>().is_none() {
+                            req.extensions_mut().insert(state);
+                        }
+                        // Call the middleware function
+                        ab_testing_middleware(req, next).await
+                    }
+                },
+            ));
+
+            // Add A/B testing API endpoints
+            app = app.merge(ab_testing_router(ab_testing_state));
+            debug!("A/B testing endpoints mounted at /api/v1/ab-tests");
+        }
+    }
+
+    // Add PR generation endpoints (optional - only if configured)
     {
-        use crate::handlers::contract_health::{contract_health_router, ContractHealthState};
-        use mockforge_contracts::contract_drift::forecasting::{Forecaster, ForecastingConfig};
-        use mockforge_core::contract_drift::threat_modeling::ThreatAnalyzer;
-        use mockforge_core::incidents::semantic_manager::SemanticIncidentManager;
-        use mockforge_foundation::threat_modeling_types::ThreatModelingConfig;
-        use mockforge_intelligence::handlers::forecasting::{forecasting_router, ForecastingState};
-        use mockforge_intelligence::handlers::semantic_drift::{
-            semantic_drift_router, SemanticDriftState,
-        };
-        use mockforge_intelligence::handlers::threat_modeling::{
-            threat_modeling_router, ThreatModelingState,
-        };
+        use crate::handlers::pr_generation::{pr_generation_router, PRGenerationState};
+        use mockforge_intelligence::pr_generation::{PRGenerator, PRProvider};
         use std::sync::Arc;
 
-        // Initialize forecasting
-        let forecasting_config = ForecastingConfig::default();
-        let forecaster = Arc::new(Forecaster::new(forecasting_config));
-        let forecasting_state = ForecastingState {
-            forecaster,
-            #[cfg(feature = "database")]
-            database: database.clone(),
+        // Load PR generation config from environment or use default
+        let pr_config = mockforge_intelligence::pr_generation::PRGenerationConfig::from_env();
+
+        let generator = if pr_config.enabled && pr_config.token.is_some() {
+            let token = pr_config.token.as_ref().unwrap().clone();
+            let generator = match pr_config.provider {
+                PRProvider::GitHub => PRGenerator::new_github(
+                    pr_config.owner.clone(),
+                    pr_config.repo.clone(),
+                    token,
+                    pr_config.base_branch.clone(),
+                ),
+                PRProvider::GitLab => PRGenerator::new_gitlab(
+                    pr_config.owner.clone(),
+                    pr_config.repo.clone(),
+                    token,
+                    pr_config.base_branch.clone(),
+                ),
+            };
+            Some(Arc::new(generator))
+        } else {
+            None
         };
 
-        // Initialize semantic drift manager
-        let semantic_manager = Arc::new(SemanticIncidentManager::new());
-        let semantic_state = SemanticDriftState {
-            manager: semantic_manager,
-            #[cfg(feature = "database")]
-            database: database.clone(),
+        let pr_state = PRGenerationState {
+            generator: generator.clone(),
         };
 
-        // Initialize threat analyzer
-        let threat_config = ThreatModelingConfig::default();
-        let threat_analyzer = match ThreatAnalyzer::new(threat_config) {
-            Ok(analyzer) => Arc::new(analyzer),
-            Err(e) => {
-                warn!("Failed to create threat analyzer: {}. Using default.", e);
-                Arc::new(ThreatAnalyzer::new(ThreatModelingConfig::default()).unwrap_or_else(
-                    |_| {
-                        // Fallback to a minimal config if default also fails
-                        ThreatAnalyzer::new(ThreatModelingConfig {
-                            enabled: false,
-                            ..Default::default()
-                        })
-                        .expect("Failed to create fallback threat analyzer")
-                    },
-                ))
+        app = app.merge(pr_generation_router(pr_state));
+        if generator.is_some() {
+            debug!(
+                "PR generation endpoints mounted at /api/v1/pr (configured for {:?})",
+                pr_config.provider
+            );
+        } else {
+            debug!("PR generation endpoints mounted at /api/v1/pr (not configured - set GITHUB_TOKEN/GITLAB_TOKEN and PR_REPO_OWNER/PR_REPO_NAME)");
+        }
+    }
+
+    // Add management WebSocket endpoint
+    app = app.nest("/__mockforge/ws", ws_management_router(ws_state));
+
+    // Build the workspace router if multi-tenant is enabled
+    let mut workspace_router: Option<Arc<mockforge_core::WorkspaceRouter>> = None;
+    if let Some(mt_config) = multi_tenant_config {
+        if mt_config.enabled {
+            use mockforge_core::{MultiTenantWorkspaceRegistry, WorkspaceRouter};
+            use std::sync::Arc;
+
+            info!(
+                "Multi-tenant mode enabled with {} routing strategy",
+                match mt_config.routing_strategy {
+                    mockforge_foundation::multi_tenant_types::RoutingStrategy::Path => "path-based",
+                    mockforge_foundation::multi_tenant_types::RoutingStrategy::Port => "port-based",
+                    mockforge_foundation::multi_tenant_types::RoutingStrategy::Both => "hybrid",
+                }
+            );
+
+            // Create the multi-tenant workspace registry
+            let mut registry = MultiTenantWorkspaceRegistry::new(mt_config.clone());
+
+            // Register the default workspace before wrapping in Arc
+            let default_workspace =
+                mockforge_core::Workspace::new(mt_config.default_workspace.clone());
+            if let Err(e) =
+                registry.register_workspace(mt_config.default_workspace.clone(), default_workspace)
+            {
+                warn!("Failed to register default workspace: {}", e);
+            } else {
+                info!("Registered default workspace: '{}'", mt_config.default_workspace);
             }
-        };
-        // Load webhook configs from ServerConfig
-        let mut webhook_configs = Vec::new();
-        let config_paths = [
-            "config.yaml",
-            "mockforge.yaml",
-            "tools/mockforge/config.yaml",
-            "../tools/mockforge/config.yaml",
-        ];
 
-        for path in &config_paths {
-            if let Ok(config) = mockforge_core::config::load_config(path).await {
-                if !config.incidents.webhooks.is_empty() {
-                    webhook_configs = config.incidents.webhooks.clone();
-                    info!("Loaded {} webhook configs from config: {}", webhook_configs.len(), path);
-                    break;
+            // Wrap registry in Arc for shared access
+            let registry = Arc::new(registry);
+
+            // Create workspace router; the finished app is wrapped with it below
+            workspace_router = Some(Arc::new(WorkspaceRouter::new(registry)));
+            info!("Workspace routing middleware initialized for HTTP server");
+        }
+    }
+
+    // Apply deceptive deploy configuration if enabled
+    let mut final_cors_config = cors_config;
+    let mut production_headers: Option<std::sync::Arc<HashMap<String, String>>> = None;
+    // Auth config from deceptive deploy OAuth (if configured)
+    let mut deceptive_deploy_auth_config: Option<mockforge_core::config::AuthConfig> = None;
+    let mut rate_limit_config = middleware::RateLimitConfig {
+        requests_per_minute: std::env::var("MOCKFORGE_RATE_LIMIT_RPM")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1000),
+        burst: std::env::var("MOCKFORGE_RATE_LIMIT_BURST")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(2000),
+        per_ip: true,
+        per_endpoint: std::env::var("MOCKFORGE_RATE_LIMIT_PER_ENDPOINT")
+            .is_ok_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")),
+        trust_forwarded_headers: std::env::var("MOCKFORGE_RATE_LIMIT_TRUST_FORWARDED")
+            .is_ok_and(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes")),
+    };
+
+    if let Some(deploy_config) = &deceptive_deploy_config {
+        if deploy_config.enabled {
+            info!("Deceptive deploy mode enabled - applying production-like configuration");
+
+            // Override CORS config if provided
+            if let Some(prod_cors) = &deploy_config.cors {
+                final_cors_config = Some(mockforge_core::config::HttpCorsConfig {
+                    enabled: true,
+                    allowed_origins: prod_cors.allowed_origins.clone(),
+                    allowed_methods: prod_cors.allowed_methods.clone(),
+                    allowed_headers: prod_cors.allowed_headers.clone(),
+                    allow_credentials: prod_cors.allow_credentials,
+                    origin_credentials: Default::default(),
+                });
+                info!("Applied production-like CORS configuration");
+            }
+
+            // Override rate limit config if provided
+            if let Some(prod_rate_limit) = &deploy_config.rate_limit {
+                rate_limit_config = middleware::RateLimitConfig {
+                    requests_per_minute: prod_rate_limit.requests_per_minute,
+                    burst: prod_rate_limit.burst,
+                    per_ip: prod_rate_limit.per_ip,
+                    per_endpoint: false,
+                    trust_forwarded_headers: prod_rate_limit.trust_forwarded_headers,
+                };
+                info!(
+                    "Applied production-like rate limiting: {} req/min, burst: {}",
+                    prod_rate_limit.requests_per_minute, prod_rate_limit.burst
+                );
+            }
+
+            // Set production headers
+            if !deploy_config.headers.is_empty() {
+                let headers_map: HashMap<String, String> = deploy_config.headers.clone();
+                production_headers = Some(std::sync::Arc::new(headers_map));
+                info!("Configured {} production headers", deploy_config.headers.len());
+            }
+
+            // Integrate OAuth config from deceptive deploy
+            if let Some(prod_oauth) = &deploy_config.oauth {
+                let oauth2_config: mockforge_core::config::OAuth2Config = prod_oauth.clone().into();
+                deceptive_deploy_auth_config = Some(mockforge_core::config::AuthConfig {
+                    oauth2: Some(oauth2_config),
+                    ..Default::default()
+                });
+                info!("Applied production-like OAuth configuration for deceptive deploy");
+            }
+        }
+    }
+
+    // Initialize rate limiter and state
+    let rate_limit_disabled = middleware::is_rate_limit_disabled();
+    let rate_limiter =
+        std::sync::Arc::new(middleware::GlobalRateLimiter::new(rate_limit_config.clone()));
+
+    let mut state = HttpServerState::new();
+    if rate_limit_disabled {
+        info!(
+            "HTTP rate limiting disabled (MOCKFORGE_RATE_LIMIT_ENABLED=false or --no-rate-limit)"
+        );
+    } else {
+        state = state.with_rate_limiter(rate_limiter.clone());
+    }
+
+    // Add production headers to state if configured
+    if let Some(headers) = production_headers.clone() {
+        state = state.with_production_headers(headers);
+    }
+
+    // Add rate limiting middleware (no-op when state.rate_limiter is None)
+    app = app.layer(from_fn_with_state(state.clone(), middleware::rate_limit_middleware));
+
+    // Add production headers middleware if configured
+    if state.production_headers.is_some() {
+        app =
+            app.layer(from_fn_with_state(state.clone(), middleware::production_headers_middleware));
+    }
+
+    // Optionally advertise `Connection: keep-alive` + `Keep-Alive: timeout=N,
+    // max=M` on every response (Issue #79 workaround for HTTP/1.0 upstream
+    // proxies that observe FIN from MockForge after each response). Opt in
+    // via `MOCKFORGE_HTTP_KEEPALIVE_HINT=1`.
+    if middleware::is_keepalive_hint_enabled() {
+        info!(
+            "MOCKFORGE_HTTP_KEEPALIVE_HINT enabled — emitting Connection: keep-alive + Keep-Alive headers on all responses (Issue #79 workaround)"
+        );
+        app = app.layer(axum::middleware::from_fn(middleware::keepalive_hint_middleware));
+    }
+
+    // Optionally break injected latency down per source (network profile,
+    // route chaos, handler) in a `Server-Timing` response header. Layered
+    // outside the latency middleware so they can record into it. Opt in via
+    // `http.server_timing` (or `MOCKFORGE_HTTP_SERVER_TIMING=1`).
+    if middleware::is_server_timing_enabled() {
+        info!("Server-Timing enabled — emitting Server-Timing headers");
+        app = app.layer(axum::middleware::from_fn(middleware::server_timing_middleware));
+    }
+
+    // Issue #79 (round 5): per-request log line with HTTP version + Connection
+    // header MockForge actually sees, so users debugging proxy ↔ MockForge
+    // negotiation can confirm whether their proxy is speaking HTTP/1.1 with
+    // keep-alive or HTTP/1.0 (which forces hyper to FIN after each response).
+    if middleware::is_conn_log_enabled() {
+        info!(
+            "MOCKFORGE_HTTP_LOG_CONN enabled — logging HTTP version + Connection headers per request (Issue #79 diagnostic)"
+        );
+        app = app.layer(axum::middleware::from_fn(middleware::conn_diag_middleware));
+    }
+
+    // Add authentication middleware if OAuth is configured via deceptive deploy
+    if let Some(auth_config) = deceptive_deploy_auth_config {
+        use crate::auth::{auth_middleware, create_oauth2_client, AuthState};
+        use std::collections::HashMap;
+        use std::sync::Arc;
+        use tokio::sync::RwLock;
+
+        // Create OAuth2 client if configured
+        let oauth2_client = if let Some(oauth2_config) = &auth_config.oauth2 {
+            match create_oauth2_client(oauth2_config) {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    warn!("Failed to create OAuth2 client from deceptive deploy config: {}", e);
+                    None
                 }
             }
-        }
-
-        if webhook_configs.is_empty() {
-            debug!("No webhook configs found in config files, using empty list");
-        }
-
-        let threat_state = ThreatModelingState {
-            analyzer: threat_analyzer,
-            webhook_configs,
-            #[cfg(feature = "database")]
-            database: database.clone(),
+        } else {
+            None
         };
 
-        // Initialize contract health state
-        let contract_health_state = ContractHealthState {
-            incident_manager: incident_manager.clone(),
-            semantic_manager: Arc::new(SemanticIncidentManager::new()),
-            #[cfg(feature = "database")]
-            database: database.clone(),
+        // Create auth state
+        let auth_state = AuthState {
+            config: auth_config,
+            spec: None, // OpenAPI spec not available in this context
+            oauth2_client,
+            introspection_cache: Arc::new(RwLock::new(HashMap::new())),
         };
 
-        // Register routers
-        app = app.merge(forecasting_router(forecasting_state));
-        debug!("Forecasting endpoints mounted at /api/v1/forecasts");
-
-        app = app.merge(semantic_drift_router(semantic_state));
-        debug!("Semantic drift endpoints mounted at /api/v1/semantic-drift");
-
-        app = app.merge(threat_modeling_router(threat_state));
-        debug!("Threat modeling endpoints mounted at /api/v1/threats");
-
-        app = app.merge(contract_health_router(contract_health_state));
-        debug!("Contract health endpoints mounted at /api/v1/contract-health");
+        // Apply auth middleware
+        app = app.layer(from_fn_with_state(auth_state, auth_middleware));
+        info!("Applied OAuth authentication middleware from deceptive deploy configuration");
     }
 
-    // Add protocol contracts endpoints with fitness registry initialization
+    // Add runtime daemon 404 detection middleware (if enabled)
+    #[cfg(feature = "runtime-daemon")]
     {
-        use crate::handlers::protocol_contracts::{
-            protocol_contracts_router, ProtocolContractState,
-        };
-        use mockforge_core::contract_drift::{
-            ConsumerImpactAnalyzer, FitnessFunctionRegistry, ProtocolContractRegistry,
-        };
+        use mockforge_runtime_daemon::{AutoGenerator, NotFoundDetector, RuntimeDaemonConfig};
         use std::sync::Arc;
-        use tokio::sync::RwLock;
 
-        // Initialize protocol contract registry
-        let contract_registry = Arc::new(RwLock::new(ProtocolContractRegistry::new()));
+        // Load runtime daemon config from environment
+        let daemon_config = RuntimeDaemonConfig::from_env();
 
-        // Initialize fitness function registry and load from config
-        let mut fitness_registry = FitnessFunctionRegistry::new();
+        if daemon_config.enabled {
+            info!("Runtime daemon enabled - auto-creating mocks from 404s");
 
-        // Try to load config and populate fitness rules
-        let config_paths = [
-            "config.yaml",
-            "mockforge.yaml",
-            "tools/mockforge/config.yaml",
-            "../tools/mockforge/config.yaml",
-        ];
+            // Determine management API URL (default to localhost:3000)
+            let management_api_url =
+                std::env::var("MOCKFORGE_MANAGEMENT_API_URL").unwrap_or_else(|_| {
+                    let port =
+                        std::env::var("MOCKFORGE_HTTP_PORT").unwrap_or_else(|_| "3000".to_string());
+                    format!("http://localhost:{}", port)
+                });
 
-        let mut config_loaded = false;
-        for path in &config_paths {
-            if let Ok(config) = mockforge_core::config::load_config(path).await {
-                if !config.contracts.fitness_rules.is_empty() {
-                    if let Err(e) =
-                        fitness_registry.load_from_config(&config.contracts.fitness_rules)
-                    {
-                        warn!("Failed to load fitness rules from config {}: {}", path, e);
-                    } else {
-                        info!(
-                            "Loaded {} fitness rules from config: {}",
-                            config.contracts.fitness_rules.len(),
-                            path
-                        );
-                        config_loaded = true;
-                        break;
-                    }
-                }
-            }
-        }
+            // Create auto-generator
+            let generator = Arc::new(AutoGenerator::new(daemon_config.clone(), management_api_url));
 
-        if !config_loaded {
-            debug!("No fitness rules found in config files, using empty registry");
+            // Create detector and set generator
+            let detector = NotFoundDetector::new(daemon_config.clone());
+            detector.set_generator(generator).await;
+
+            // Add middleware layer
+            let detector_clone = detector.clone();
+            app = app.layer(axum::middleware::from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let detector = detector_clone.clone();
+                    async move { detector.detect_and_auto_create(req, next).await }
+                },
+            ));
+
+            debug!("Runtime daemon 404 detection middleware added");
         }
+    }
 
-        let fitness_registry = Arc::new(RwLock::new(fitness_registry));
+    // Add /__mockforge/routes endpoint so the admin UI can discover registered routes
+    {
+        let routes_state = HttpServerState::with_routes(captured_routes);
+        let routes_router = Router::new()
+            .route("/__mockforge/routes", axum::routing::get(get_routes_handler))
+            .route("/__mockforge/routes/{method}/{*path}", axum::routing::get(get_route_handler))
+            .with_state(routes_state);
+        app = app.merge(routes_router);
+    }
 
-        // Reuse drift engine and incident manager from drift budget section
+    // Add API docs page (Scalar-powered interactive explorer)
+    app = app.route("/__mockforge/docs", axum::routing::get(get_docs_handler));
 
-        // Initialize consumer impact analyzer
-        let consumer_mapping_registry =
-            mockforge_core::contract_drift::ConsumerMappingRegistry::new();
-        let consumer_analyzer =
-            Arc::new(RwLock::new(ConsumerImpactAnalyzer::new(consumer_mapping_registry)));
+    // Note: OData URI rewrite is applied at the service level in serve_router_with_tls()
+    // because Router::layer() only applies to matched routes, not unmatched ones.
 
-        let protocol_state = ProtocolContractState {
-            registry: contract_registry,
-            drift_engine: Some(drift_engine.clone()),
-            incident_manager: Some(incident_manager.clone()),
-            fitness_registry: Some(fitness_registry),
-            consumer_analyzer: Some(consumer_analyzer),
+    // With `http.failed_replay`, keep recent 5xx requests so
+    // `POST /__mockforge/api/replay/failed` can re-run them through the
+    // finished router. The API is nested after
+    // the recording layer so replay calls themselves are never recorded.
+    if mockforge_core::config::effective_config().is_some_and(|c| c.http.failed_replay) {
+        use crate::failed_replay_runtime::{
+            failed_replay_api_router, record_failed_requests_middleware, FailedReplayState,
         };
+        let replay_state = FailedReplayState::new();
+        app =
+            app.layer(from_fn_with_state(replay_state.clone(), record_failed_requests_middleware));
+        app = app.nest("/__mockforge/api/replay", failed_replay_api_router(replay_state.clone()));
+        replay_state.attach(app.clone());
+    }
 
-        app = app.nest("/api/v1/contracts", protocol_contracts_router(protocol_state));
-        debug!("Protocol contracts endpoints mounted at /api/v1/contracts");
+    // Add request logging middleware to capture all requests for the admin dashboard
+    app = app.layer(axum::middleware::from_fn(request_logging::log_http_requests));
+    app = app.nest(
+        "/__mockforge/api/logs",
+        log_curl_api::log_curl_api_router().merge(log_poll_api::log_poll_api_router()),
+    );
+
+    // Add contract diff middleware for automatic request capture
+    // This captures requests for contract diff analysis
+    app = app.layer(axum::middleware::from_fn(contract_diff_middleware::capture_for_contract_diff));
+
+    // Add CORS middleware (use final_cors_config which may be overridden by deceptive deploy)
+    app = apply_cors_middleware(app, final_cors_config);
+
+    // Resolve workspace prefixes before anything routes the request, so
+    // prefixed paths reach their routes and the rate limiter can read the
+    // `WorkspaceContext` for per-workspace limits
+    if let Some(workspace_router) = workspace_router {
+        app = workspace_routing::with_workspace_routing(app, workspace_router);
     }
 
-    // Add behavioral cloning middleware (optional - applies learned behavior to requests)
-    #[cfg(feature = "behavioral-cloning")]
-    {
-        use crate::middleware::behavioral_cloning::BehavioralCloningMiddlewareState;
-        use std::path::PathBuf;
+    app
+}
 
-        // Determine database path (defaults to ./recordings.db)
-        let db_path = std::env::var("RECORDER_DATABASE_PATH")
-            .ok()
-            .map(PathBuf::from)
-            .or_else(|| std::env::current_dir().ok().map(|p| p.join("recordings.db")));
+// Note: start_with_traffic_shaping function removed due to compilation issues
+// Use build_router_with_traffic_shaping_and_multi_tenant directly instead
 
-        let bc_middleware_state = if let Some(path) = db_path {
-            BehavioralCloningMiddlewareState::with_database_path(path)
-        } else {
-            BehavioralCloningMiddlewareState::new()
-        };
+#[test]
+fn test_route_info_clone() {
+    let route = RouteInfo {
+        method: "POST".to_string(),
+        path: "/users".to_string(),
+        operation_id: Some("createUser".to_string()),
+        summary: None,
+        description: None,
+        parameters: vec![],
+    };
 
-        // Only enable if BEHAVIORAL_CLONING_ENABLED is set to true
-        let enabled = std::env::var("BEHAVIORAL_CLONING_ENABLED")
-            .ok()
-            .and_then(|v| v.parse::<bool>().ok())
-            .unwrap_or(false);
+    let cloned = route.clone();
+    assert_eq!(route.method, cloned.method);
+    assert_eq!(route.path, cloned.path);
+    assert_eq!(route.operation_id, cloned.operation_id);
+}
 
-        if enabled {
-            let bc_state_clone = bc_middleware_state.clone();
-            app = app.layer(axum::middleware::from_fn(
-                move |mut req: axum::extract::Request, next: axum::middleware::Next| {
-                    let state = bc_state_clone.clone();
-                    async move {
-                        // Insert state into extensions if not already present
-                        if req.extensions().get::<BehavioralCloningMiddlewareState>().is_none() {
-                            req.extensions_mut().insert(state);
-                        }
-                        // Call the middleware function
-                        middleware::behavioral_cloning::behavioral_cloning_middleware(req, next)
-                            .await
-                    }
-                },
-            ));
-            debug!("Behavioral cloning middleware enabled (applies learned behavior to requests)");
-        }
-    }
+#[test]
+fn test_http_server_state_new() {
+    let state = HttpServerState::new();
+    assert_eq!(state.routes.len(), 0);
+}
 
-    // Add consumer contracts endpoints
-    {
-        use crate::handlers::consumer_contracts::{
-            consumer_contracts_router, ConsumerContractsState,
-        };
-        use mockforge_contracts::consumer_contracts::{
-            ConsumerBreakingChangeDetector, ConsumerRegistry, UsageRecorder,
-        };
-        use std::sync::Arc;
+#[test]
+fn test_http_server_state_with_routes() {
+    let routes = vec![
+        RouteInfo {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            operation_id: Some("getUsers".to_string()),
+            summary: None,
+            description: None,
+            parameters: vec![],
+        },
+        RouteInfo {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            operation_id: Some("createUser".to_string()),
+            summary: None,
+            description: None,
+            parameters: vec![],
+        },
+    ];
 
-        // Initialize consumer registry
-        let registry = Arc::new(ConsumerRegistry::default());
+    let state = HttpServerState::with_routes(routes.clone());
+    assert_eq!(state.routes.len(), 2);
+    assert_eq!(state.routes[0].method, "GET");
+    assert_eq!(state.routes[1].method, "POST");
+}
 
-        // Initialize usage recorder
-        let usage_recorder = Arc::new(UsageRecorder::default());
+#[test]
+fn test_http_server_state_clone() {
+    let routes = vec![RouteInfo {
+        method: "GET".to_string(),
+        path: "/test".to_string(),
+        operation_id: None,
+        summary: None,
+        description: None,
+        parameters: vec![],
+    }];
 
-        // Initialize breaking change detector
-        let detector = Arc::new(ConsumerBreakingChangeDetector::new(usage_recorder.clone()));
+    let state = HttpServerState::with_routes(routes);
+    let cloned = state.clone();
 
-        let consumer_state = ConsumerContractsState {
-            registry,
-            usage_recorder,
-            detector,
-            violations: Arc::new(RwLock::new(HashMap::new())),
-        };
+    assert_eq!(state.routes.len(), cloned.routes.len());
+    assert_eq!(state.routes[0].method, cloned.routes[0].method);
+}
 
-        app = app.merge(consumer_contracts_router(consumer_state));
-        debug!("Consumer contracts endpoints mounted at /api/v1/consumers");
-    }
+#[tokio::test]
+async fn test_build_router_without_openapi() {
+    let _router = build_router(None, None, None).await;
+    // Should succeed without OpenAPI spec
+}
 
-    // Add behavioral cloning endpoints
-    #[cfg(feature = "behavioral-cloning")]
-    {
-        use crate::handlers::behavioral_cloning::{
-            behavioral_cloning_router, BehavioralCloningState,
-        };
-        use std::path::PathBuf;
+#[tokio::test]
+async fn test_build_router_with_nonexistent_spec() {
+    let _router = build_router(Some("/nonexistent/spec.yaml".to_string()), None, None).await;
+    // Should succeed but log a warning
+}
 
-        // Determine database path (defaults to ./recordings.db)
-        let db_path = std::env::var("RECORDER_DATABASE_PATH")
-            .ok()
-            .map(PathBuf::from)
-            .or_else(|| std::env::current_dir().ok().map(|p| p.join("recordings.db")));
+#[tokio::test]
+async fn test_build_router_with_auth_and_latency() {
+    let _router = build_router_with_auth_and_latency(None, None, None, None).await;
+    // Should succeed without parameters
+}
 
-        let bc_state = if let Some(path) = db_path {
-            BehavioralCloningState::with_database_path(path)
-        } else {
-            BehavioralCloningState::new()
-        };
+#[tokio::test]
+async fn test_build_router_with_latency() {
+    let _router = build_router_with_latency(None, None, None).await;
+    // Should succeed without parameters
+}
 
-        app = app.merge(behavioral_cloning_router(bc_state));
-        debug!("Behavioral cloning endpoints mounted at /api/v1/behavioral-cloning");
-    }
+#[tokio::test]
+async fn test_build_router_with_auth() {
+    let _router = build_router_with_auth(None, None, None).await;
+    // Should succeed without parameters
+}
 
-    // Add consistency engine and cross-protocol state management
-    {
-        use crate::consistency::{ConsistencyMiddlewareState, HttpAdapter};
-        use mockforge_intelligence::consistency::ConsistencyEngine;
-        use mockforge_intelligence::handlers::consistency::{consistency_router, ConsistencyState};
-        use std::sync::Arc;
+#[tokio::test]
+async fn test_build_router_with_chains() {
+    let _router = build_router_with_chains(None, None, None).await;
+    // Should succeed without parameters
+}
 
-        // Initialize consistency engine
-        let consistency_engine = Arc::new(ConsistencyEngine::new());
+#[test]
+fn test_route_info_with_all_fields() {
+    let route = RouteInfo {
+        method: "PUT".to_string(),
+        path: "/users/{id}".to_string(),
+        operation_id: Some("updateUser".to_string()),
+        summary: Some("Update user".to_string()),
+        description: Some("Updates an existing user".to_string()),
+        parameters: vec!["id".to_string(), "body".to_string()],
+    };
 
-        // Create and register HTTP adapter
-        let http_adapter = Arc::new(HttpAdapter::new(consistency_engine.clone()));
-        consistency_engine.register_adapter(http_adapter.clone()).await;
+    assert!(route.operation_id.is_some());
+    assert!(route.summary.is_some());
+    assert!(route.description.is_some());
+    assert_eq!(route.parameters.len(), 2);
+}
 
-        // Create consistency state for handlers
-        let consistency_state = ConsistencyState {
-            engine: consistency_engine.clone(),
-        };
+#[test]
+fn test_route_info_with_minimal_fields() {
+    let route = RouteInfo {
+        method: "DELETE".to_string(),
+        path: "/users/{id}".to_string(),
+        operation_id: None,
+        summary: None,
+        description: None,
+        parameters: vec![],
+    };
 
-        // Create X-Ray state first (needed for middleware)
-        use mockforge_intelligence::handlers::xray::XRayState;
-        let xray_state = Arc::new(XRayState::new(consistency_engine.clone()));
+    assert!(route.operation_id.is_none());
+    assert!(route.summary.is_none());
+    assert!(route.description.is_none());
+    assert_eq!(route.parameters.len(), 0);
+}
 
-        // Create consistency middleware state
-        let consistency_middleware_state = ConsistencyMiddlewareState {
-            engine: consistency_engine.clone(),
-            adapter: http_adapter,
-            xray_state: Some(xray_state.clone()),
-        };
+#[test]
+fn test_http_server_state_empty_routes() {
+    let state = HttpServerState::with_routes(vec![]);
+    assert_eq!(state.routes.len(), 0);
+}
 
-        // Reality-driven mock/proxy middleware (#222). Must be added
-        // BEFORE the consistency layer so it ends up inner — that way
-        // each request hits consistency first (which injects UnifiedState
-        // including reality_continuum_ratio), then this middleware reads
-        // that state and either forwards to upstream or falls through to
-        // the mock chain. Activated only when MOCKFORGE_PROXY_UPSTREAM is
-        // set; absent that, the layer isn't even installed.
-        if let Some(reality_cfg) = reality_proxy::RealityProxyConfig::from_env() {
-            tracing::info!(
-                upstream = %reality_cfg.upstream_base,
-                "Reality-driven proxy middleware enabled — requests will be split between mock and upstream based on reality_continuum_ratio"
-            );
-            app = app.layer(axum::middleware::from_fn(
-                move |req: axum::extract::Request, next: axum::middleware::Next| {
-                    let cfg = reality_cfg.clone();
-                    async move { reality_proxy::reality_proxy_middleware(cfg, req, next).await }
-                },
-            ));
-        }
+#[test]
+fn test_http_server_state_multiple_routes() {
+    let routes = vec![
+        RouteInfo {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            operation_id: Some("listUsers".to_string()),
+            summary: Some("List all users".to_string()),
+            description: None,
+            parameters: vec![],
+        },
+        RouteInfo {
+            method: "GET".to_string(),
+            path: "/users/{id}".to_string(),
+            operation_id: Some("getUser".to_string()),
+            summary: Some("Get a user".to_string()),
+            description: None,
+            parameters: vec!["id".to_string()],
+        },
+        RouteInfo {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            operation_id: Some("createUser".to_string()),
+            summary: Some("Create a user".to_string()),
+            description: None,
+            parameters: vec!["body".to_string()],
+        },
+    ];
 
-        // Add consistency middleware (before other middleware to inject state early)
-        let consistency_middleware_state_clone = consistency_middleware_state.clone();
-        app = app.layer(axum::middleware::from_fn(
-            move |mut req: axum::extract::Request, next: axum::middleware::Next| {
-                let state = consistency_middleware_state_clone.clone();
-                async move {
-                    // Insert state into extensions if not already present
-                    if req.extensions().get::<ConsistencyMiddlewareState>().is_none() {
-                        req.extensions_mut().insert(state);
-                    }
-                    // Call the middleware function
-                    consistency::middleware::consistency_middleware(req, next).await
-                }
-            },
-        ));
+    let state = HttpServerState::with_routes(routes);
+    assert_eq!(state.routes.len(), 3);
 
-        // Add consistency API endpoints
-        app = app.merge(consistency_router(consistency_state));
-        debug!("Consistency engine initialized and endpoints mounted at /api/v1/consistency");
+    // Verify different HTTP methods
+    let methods: Vec<&str> = state.routes.iter().map(|r| r.method.as_str()).collect();
+    assert!(methods.contains(&"GET"));
+    assert!(methods.contains(&"POST"));
+}
 
-        // Runtime named-scenario activation API. Locally-installed
-        // scenarios (from `~/.mockforge/scenario-metadata/*.json`) become
-        // listable + activatable via HTTP; activation writes through to
-        // the consistency engine's active_scenario slot for the chosen
-        // workspace. Failure to load the scenario index is non-fatal —
-        // the API just shows an empty list, which matches the natural
-        // state of a fresh deployment with no scenarios installed yet.
-        #[cfg(feature = "scenario-engine")]
-        {
-            use crate::scenarios_runtime::{scenarios_api_router, ScenarioRuntimeState};
-            let mut scenario_storage = match mockforge_scenarios::ScenarioStorage::new() {
-                Ok(s) => s,
-                Err(e) => {
-                    tracing::warn!(
-                        error = %e,
-                        "Failed to init scenario storage; runtime scenarios API will list empty"
-                    );
-                    // Construct an isolated tempdir-backed storage so the
-                    // API still mounts but lists nothing.
-                    let tmp = std::env::temp_dir().join("mockforge-empty-scenarios");
-                    mockforge_scenarios::ScenarioStorage::with_dir(&tmp)
-                        .expect("temp scenario storage")
-                }
-            };
-            if let Err(e) = scenario_storage.load().await {
-                tracing::warn!(
-                    error = %e,
-                    "Failed to load installed scenarios; API will list empty until scenarios are installed"
-                );
-            }
-            let scenarios_state =
-                ScenarioRuntimeState::new(scenario_storage, consistency_engine.clone());
-            app = app.nest("/__mockforge/api/scenarios", scenarios_api_router(scenarios_state));
-            debug!("Scenario runtime API mounted at /__mockforge/api/scenarios");
-        }
+#[test]
+fn test_http_server_state_with_rate_limiter() {
+    use std::sync::Arc;
 
-        // Add fidelity score endpoints
-        {
-            use mockforge_intelligence::handlers::fidelity::{fidelity_router, FidelityState};
-            let fidelity_state = FidelityState::new();
-            app = app.merge(fidelity_router(fidelity_state));
-            debug!("Fidelity score endpoints mounted at /api/v1/workspace/:workspace_id/fidelity");
-        }
+    let config = middleware::RateLimitConfig::default();
+    let rate_limiter = Arc::new(middleware::GlobalRateLimiter::new(config));
 
-        // Add scenario studio endpoints
-        {
-            use mockforge_intelligence::handlers::scenario_studio::{
-                scenario_studio_router, ScenarioStudioState,
-            };
-            let scenario_studio_state = ScenarioStudioState::new();
-            app = app.merge(scenario_studio_router(scenario_studio_state));
-            debug!("Scenario Studio endpoints mounted at /api/v1/scenario-studio");
-        }
+    let state = HttpServerState::new().with_rate_limiter(rate_limiter);
 
-        // Add performance mode endpoints
-        {
-            use crate::handlers::performance::{performance_router, PerformanceState};
-            let performance_state = PerformanceState::new();
-            app = app.nest("/api/performance", performance_router(performance_state));
-            debug!("Performance mode endpoints mounted at /api/performance");
-        }
+    assert!(state.rate_limiter.is_some());
+    assert_eq!(state.routes.len(), 0);
+}
 
-        // Add world state endpoints
-        {
-            use crate::handlers::world_state::{world_state_router, WorldStateState};
-            use mockforge_world_state::WorldStateEngine;
-            use std::sync::Arc;
-            use tokio::sync::RwLock;
+#[tokio::test]
+async fn test_build_router_includes_rate_limiter() {
+    let _router = build_router(None, None, None).await;
+    // Router should be created successfully with rate limiter initialized
+}
 
-            let world_state_engine = Arc::new(RwLock::new(WorldStateEngine::new()));
-            let world_state_state = WorldStateState {
-                engine: world_state_engine,
-            };
-            app = app.nest("/api/world-state", world_state_router().with_state(world_state_state));
-            debug!("World state endpoints mounted at /api/world-state");
-        }
+#[tokio::test]
+async fn test_cors_credentials_per_origin() {
+    use tower::ServiceExt;
+
+    let trusted = "https://trusted.example.com";
+    let other = "https://other.example.com";
+    let config = mockforge_core::config::HttpCorsConfig {
+        enabled: true,
+        allowed_origins: vec![trusted.to_string(), other.to_string()],
+        origin_credentials: HashMap::from([(trusted.to_string(), true)]),
+        ..Default::default()
+    };
+    let app = apply_cors_middleware(
+        Router::new().route("/", axum::routing::get(|| async { "ok" })),
+        Some(config),
+    );
 
-        // Add snapshot management endpoints
-        {
-            use crate::handlers::snapshots::{snapshot_router, SnapshotState};
-            use mockforge_core::snapshots::SnapshotManager;
-            use std::path::PathBuf;
+    let request = |origin: &str| {
+        Request::builder()
+            .uri("/")
+            .header("origin", origin)
+            .body(Body::empty())
+            .unwrap()
+    };
 
-            let snapshot_dir = std::env::var("MOCKFORGE_SNAPSHOT_DIR").ok().map(PathBuf::from);
-            let snapshot_manager = Arc::new(SnapshotManager::new(snapshot_dir));
+    let res = app.clone().oneshot(request(trusted)).await.unwrap();
+    assert_eq!(res.headers().get("access-control-allow-origin").unwrap(), trusted);
+    assert_eq!(res.headers().get("access-control-allow-credentials").unwrap(), "true");
 
-            let snapshot_state = SnapshotState {
-                manager: snapshot_manager,
-                consistency_engine: Some(consistency_engine.clone()),
-                workspace_persistence: None, // Can be initialized later if workspace persistence is available
-                vbr_engine: None, // Can be initialized when VBR engine is available in server state
-                recorder: None,   // Can be initialized when Recorder is available in server state
-            };
+    let res = app.oneshot(request(other)).await.unwrap();
+    assert_eq!(res.headers().get("access-control-allow-origin").unwrap(), other);
+    assert!(res.headers().get("access-control-allow-credentials").is_none());
+}
 
-            app = app.merge(snapshot_router(snapshot_state));
-            debug!("Snapshot management endpoints mounted at /api/v1/snapshots");
+#[tokio::test]
+async fn test_custom_route_localized_by_accept_language() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig};
+    use tower::ServiceExt;
 
-            // Add X-Ray API endpoints for browser extension
-            {
-                use mockforge_intelligence::handlers::xray::xray_router;
-                app = app.merge(xray_router((*xray_state).clone()));
-                debug!("X-Ray API endpoints mounted at /api/v1/xray");
-            }
-        }
+    let route = RouteConfig {
+        path: "/greeting".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({"message": "Hello"})),
+            localized: HashMap::from([
+                ("en".to_string(), serde_json::json!({"message": "Hello"})),
+                ("fr".to_string(), serde_json::json!({"message": "Bonjour"})),
+            ]),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/greeting",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
 
-        // Add A/B testing endpoints and middleware
-        {
-            use crate::handlers::ab_testing::{ab_testing_router, ABTestingState};
-            use crate::middleware::ab_testing::ab_testing_middleware;
+    let get = |accept_language: &str| {
+        Request::builder()
+            .uri("/greeting")
+            .header("accept-language", accept_language)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let message = |res: axum::response::Response| async move {
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice::<serde_json::Value>(&body).unwrap()["message"].clone()
+    };
 
-            let ab_testing_state = ABTestingState::new();
+    let res = app.clone().oneshot(get("fr-CA, en;q=0.8")).await.unwrap();
+    assert_eq!(res.headers().get("content-language").unwrap(), "fr");
+    assert_eq!(res.headers().get("vary").unwrap(), "accept-language");
+    assert_eq!(message(res).await, "Bonjour");
 
-            // Add A/B testing middleware (before other response middleware)
-            let ab_testing_state_clone = ab_testing_state.clone();
-            app = app.layer(axum::middleware::from_fn(
-                move |mut req: axum::extract::Request, next: axum::middleware::Next| {
-                    let state = ab_testing_state_clone.clone();
-                    async move {
-                        // Insert state into extensions if not already present
-                        if req.extensions().get::<ABTestingState>().is_none() {
-                            req.extensions_mut().insert(state);
-                        }
-                        // Call the middleware function
-                        ab_testing_middleware(req, next).await
-                    }
-                },
-            ));
+    let res = app.clone().oneshot(get("fr;q=0.3, en")).await.unwrap();
+    assert_eq!(res.headers().get("content-language").unwrap(), "en");
+    assert_eq!(message(res).await, "Hello");
 
-            // Add A/B testing API endpoints
-            app = app.merge(ab_testing_router(ab_testing_state));
-            debug!("A/B testing endpoints mounted at /api/v1/ab-tests");
-        }
-    }
+    // No acceptable language: default body, no Content-Language
+    let res = app.oneshot(get("de")).await.unwrap();
+    assert!(res.headers().get("content-language").is_none());
+    assert_eq!(message(res).await, "Hello");
+}
 
-    // Add PR generation endpoints (optional - only if configured)
-    {
-        use crate::handlers::pr_generation::{pr_generation_router, PRGenerationState};
-        use mockforge_intelligence::pr_generation::{PRGenerator, PRProvider};
-        use std::sync::Arc;
+#[tokio::test]
+async fn test_custom_route_reason_phrase() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-        // Load PR generation config from environment or use default
-        let pr_config = mockforge_intelligence::pr_generation::PRGenerationConfig::from_env();
+    let route = RouteConfig {
+        path: "/health".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "ok": true })),
+            reason_phrase: Some("Totally {{request.query.mood}}".to_string()),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/health",
+        custom_route_handler(
+            &route,
+            true,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let (addr, server) = serve_router_ephemeral(app).await.unwrap();
+    let server = tokio::spawn(server);
 
-        let generator = if pr_config.enabled && pr_config.token.is_some() {
-            let token = pr_config.token.as_ref().unwrap().clone();
-            let generator = match pr_config.provider {
-                PRProvider::GitHub => PRGenerator::new_github(
-                    pr_config.owner.clone(),
-                    pr_config.repo.clone(),
-                    token,
-                    pr_config.base_branch.clone(),
-                ),
-                PRProvider::GitLab => PRGenerator::new_gitlab(
-                    pr_config.owner.clone(),
-                    pr_config.repo.clone(),
-                    token,
-                    pr_config.base_branch.clone(),
-                ),
-            };
-            Some(Arc::new(generator))
-        } else {
-            None
-        };
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(
+            b"GET /health?mood=Fine HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        )
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 Totally Fine\r\n"), "got: {}", response);
 
-        let pr_state = PRGenerationState {
-            generator: generator.clone(),
-        };
+    server.abort();
+}
 
-        app = app.merge(pr_generation_router(pr_state));
-        if generator.is_some() {
-            debug!(
-                "PR generation endpoints mounted at /api/v1/pr (configured for {:?})",
-                pr_config.provider
-            );
-        } else {
-            debug!("PR generation endpoints mounted at /api/v1/pr (not configured - set GITHUB_TOKEN/GITLAB_TOKEN and PR_REPO_OWNER/PR_REPO_NAME)");
+#[tokio::test]
+async fn test_custom_route_head_mirrors_get() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig};
+    use tower::ServiceExt;
+
+    let route = |method: &str| RouteConfig {
+        path: "/users".to_string(),
+        method: method.to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            headers: HashMap::from([("x-total-count".to_string(), "2".to_string())]),
+            body: Some(serde_json::json!([{ "id": 1 }, { "id": 2 }])),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = |method: &str| {
+        Router::new().route(
+            "/users",
+            custom_route_handler(
+                &route(method),
+                false,
+                None,
+                route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+            ),
+        )
+    };
+    let request =
+        |method: &str| Request::builder().method(method).uri("/users").body(Body::empty()).unwrap();
+
+    let get = app("GET").oneshot(request("GET")).await.unwrap();
+    let head = app("GET").oneshot(request("HEAD")).await.unwrap();
+    assert_eq!(head.status(), axum::http::StatusCode::OK);
+    assert!(get.headers().contains_key(http::header::CONTENT_LENGTH));
+    assert_eq!(head.headers(), get.headers());
+    let body = axum::body::to_bytes(head.into_body(), usize::MAX).await.unwrap();
+    assert!(body.is_empty());
+
+    // Only GET routes answer HEAD
+    let res = app("POST").oneshot(request("HEAD")).await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn test_custom_route_compress_override() {
+    use mockforge_core::config::{CompressionConfig, RouteConfig, RouteResponseConfig};
+    use tower::ServiceExt;
+
+    let route = |path: &str, compress: Option<bool>| RouteConfig {
+        path: path.to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "data": "x".repeat(4096) })),
+            compress,
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let handler = |route: &RouteConfig| {
+        custom_route_handler(
+            route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        )
+    };
+    let app = Router::new()
+        .route("/report", handler(&route("/report", None)))
+        .route("/archive", handler(&route("/archive", Some(false))))
+        .layer(middleware::compression_layer(&CompressionConfig::default()));
+
+    let content_encoding = |path: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .uri(path)
+                .header(http::header::ACCEPT_ENCODING, "gzip")
+                .body(Body::empty())
+                .unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            assert_eq!(res.status(), axum::http::StatusCode::OK);
+            res.headers().get(http::header::CONTENT_ENCODING).cloned()
         }
-    }
+    };
 
-    // Add management WebSocket endpoint
-    app = app.nest("/__mockforge/ws", ws_management_router(ws_state));
+    // Global compression is on, but the opted-out route is sent as is
+    assert_eq!(content_encoding("/report").await.unwrap(), "gzip");
+    assert!(content_encoding("/archive").await.is_none());
+}
 
-    // Add workspace routing middleware if multi-tenant is enabled
-    if let Some(mt_config) = multi_tenant_config {
-        if mt_config.enabled {
-            use mockforge_core::{MultiTenantWorkspaceRegistry, WorkspaceRouter};
-            use std::sync::Arc;
+#[tokio::test]
+async fn test_custom_route_malformed_json_response() {
+    use mockforge_core::config::{
+        HttpMalformedJsonResponse, RouteConfig, RouteRequestConfig, RouteResponseConfig,
+        RouteValidationConfig,
+    };
+    use tower::ServiceExt;
 
-            info!(
-                "Multi-tenant mode enabled with {} routing strategy",
-                match mt_config.routing_strategy {
-                    mockforge_foundation::multi_tenant_types::RoutingStrategy::Path => "path-based",
-                    mockforge_foundation::multi_tenant_types::RoutingStrategy::Port => "port-based",
-                    mockforge_foundation::multi_tenant_types::RoutingStrategy::Both => "hybrid",
-                }
-            );
+    let route = RouteConfig {
+        path: "/orders".to_string(),
+        method: "POST".to_string(),
+        request: Some(RouteRequestConfig {
+            validation: Some(RouteValidationConfig {
+                schema: Some(serde_json::json!({ "type": "object" })),
+                content_types: HashMap::new(),
+            }),
+            ..Default::default()
+        }),
+        response: RouteResponseConfig {
+            status: 201,
+            body: Some(serde_json::json!({ "ok": true })),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let state = middleware::MalformedJsonState::new(HttpMalformedJsonResponse {
+        status: 422,
+        headers: HashMap::from([("x-error-code".to_string(), "E_JSON".to_string())]),
+        body: serde_json::json!({
+            "code": "MALFORMED_JSON",
+            "path": "{{request.path}}",
+            "detail": "{{error}}",
+        }),
+    });
+    let app = Router::new()
+        .route(
+            "/orders",
+            custom_route_handler(
+                &route,
+                false,
+                None,
+                route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+            ),
+        )
+        .layer(axum::middleware::from_fn_with_state(
+            state,
+            middleware::malformed_json_middleware,
+        ));
 
-            // Create the multi-tenant workspace registry
-            let mut registry = MultiTenantWorkspaceRegistry::new(mt_config.clone());
+    let send = |body: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/orders")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap();
+            app.oneshot(req).await.unwrap()
+        }
+    };
 
-            // Register the default workspace before wrapping in Arc
-            let default_workspace =
-                mockforge_core::Workspace::new(mt_config.default_workspace.clone());
-            if let Err(e) =
-                registry.register_workspace(mt_config.default_workspace.clone(), default_workspace)
-            {
-                warn!("Failed to register default workspace: {}", e);
-            } else {
-                info!("Registered default workspace: '{}'", mt_config.default_workspace);
-            }
+    let res = send(r#"{"item": "#).await;
+    assert_eq!(res.status(), axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(res.headers()["x-error-code"], "E_JSON");
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["code"], "MALFORMED_JSON");
+    assert_eq!(body["path"], "/orders");
+    assert!(body["detail"].as_str().unwrap().contains("EOF"));
+
+    // Well-formed bodies, and bodies failing the schema, are unaffected
+    assert_eq!(send(r#"{"item": 1}"#).await.status(), axum::http::StatusCode::CREATED);
+    let res = send("[]").await;
+    assert_eq!(res.status(), axum::http::StatusCode::BAD_REQUEST);
+    assert!(res.headers().get("x-error-code").is_none());
+}
 
-            // Wrap registry in Arc for shared access
-            let registry = Arc::new(registry);
+#[tokio::test]
+async fn test_custom_route_request_body_template_expansion() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig};
+    use tower::ServiceExt;
 
-            // Create workspace router
-            let _workspace_router = WorkspaceRouter::new(registry);
-            info!("Workspace routing middleware initialized for HTTP server");
+    let route = RouteConfig {
+        path: "/greet".to_string(),
+        method: "POST".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "greeting": "Hello {{request.body.user.name}}" })),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/greet",
+        custom_route_handler(
+            &route,
+            true,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let send = |content_type: &'static str, body: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/greet")
+                .header("content-type", content_type)
+                .body(Body::from(body))
+                .unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            assert_eq!(res.status(), 200);
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
         }
-    }
+    };
 
-    // Apply deceptive deploy configuration if enabled
-    let mut final_cors_config = cors_config;
-    let mut production_headers: Option<std::sync::Arc<HashMap<String, String>>> = None;
-    // Auth config from deceptive deploy OAuth (if configured)
-    let mut deceptive_deploy_auth_config: Option<mockforge_core::config::AuthConfig> = None;
-    let mut rate_limit_config = middleware::RateLimitConfig {
-        requests_per_minute: std::env::var("MOCKFORGE_RATE_LIMIT_RPM")
-            .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(1000),
-        burst: std::env::var("MOCKFORGE_RATE_LIMIT_BURST")
-            .ok()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(2000),
-        per_ip: true,
-        per_endpoint: false,
+    let body = send("application/json", r#"{"user":{"name":"Al"}}"#).await;
+    assert_eq!(body["greeting"], "Hello Al");
+
+    // Bodies that are not JSON leave the placeholder unexpanded
+    let body = send("application/json", "not json").await;
+    assert_eq!(body["greeting"], "Hello {{body.user.name}}");
+    let body = send("text/plain", r#"{"user":{"name":"Al"}}"#).await;
+    assert_eq!(body["greeting"], "Hello {{body.user.name}}");
+
+    // A chunked body with no Content-Length is still held to the limit
+    let chunk = axum::body::Bytes::from(vec![b' '; TEMPLATE_BODY_LIMIT + 1]);
+    let req = Request::builder()
+        .method("POST")
+        .uri("/greet")
+        .header("content-type", "application/json")
+        .body(Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(chunk)])))
+        .unwrap();
+    let res = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn test_custom_route_xml_body_template_expansion() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig};
+    use tower::ServiceExt;
+
+    let route = RouteConfig {
+        path: "/legacy/user".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            headers: HashMap::from([("Content-Type".to_string(), "application/xml".to_string())]),
+            body: Some(serde_json::json!(
+                "<?xml version=\"1.0\"?><user><name>{{request.query.name}}</name></user>"
+            )),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/legacy/user",
+        custom_route_handler(
+            &route,
+            true,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+
+    let req = Request::builder()
+        .uri("/legacy/user?name=Tom%20%26%20%3CJerry%3E")
+        .body(Body::empty())
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("content-type").unwrap(), "application/xml");
+
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let xml = std::str::from_utf8(&body).unwrap();
+    assert_eq!(xml, "<?xml version=\"1.0\"?><user><name>Tom &amp; &lt;Jerry&gt;</name></user>");
+    let doc = roxmltree::Document::parse(xml).expect("response should be well-formed XML");
+    let name = doc.descendants().find(|n| n.has_tag_name("name")).unwrap();
+    assert_eq!(name.text(), Some("Tom & <Jerry>"));
+}
+
+#[tokio::test]
+async fn test_custom_route_consistent_ids_per_key() {
+    use crate::consistency::{ConsistencyMiddlewareState, HttpAdapter};
+    use mockforge_core::config::{RouteConfig, RouteConsistentIdConfig, RouteResponseConfig};
+    use mockforge_core::consistency::ConsistencyEngine;
+    use tower::ServiceExt;
+
+    let route = RouteConfig {
+        path: "/users".to_string(),
+        method: "POST".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 201,
+            body: Some(serde_json::json!({ "email": "{{request.query.email}}" })),
+            consistent_id: Some(RouteConsistentIdConfig {
+                key: "{{request.query.email}}".to_string(),
+                field: "id".to_string(),
+                entity_type: Some("user".to_string()),
+            }),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let engine = Arc::new(ConsistencyEngine::new());
+    let state = ConsistencyMiddlewareState {
+        engine: engine.clone(),
+        adapter: Arc::new(HttpAdapter::new(engine.clone())),
+        xray_state: None,
+    };
+    let app = Router::new()
+        .route(
+            "/users",
+            custom_route_handler(
+                &route,
+                true,
+                None,
+                route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+            ),
+        )
+        .layer(axum::Extension(state));
+
+    let create = |email: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri(format!("/users?email={}", email))
+                .body(Body::empty())
+                .unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            assert_eq!(res.status(), axum::http::StatusCode::CREATED);
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body["email"], email);
+            body["id"].as_str().unwrap().to_string()
+        }
     };
 
-    if let Some(deploy_config) = &deceptive_deploy_config {
-        if deploy_config.enabled {
-            info!("Deceptive deploy mode enabled - applying production-like configuration");
+    let first = create("ada@example.com").await;
+    assert_eq!(create("ada@example.com").await, first);
+    assert_ne!(create("grace@example.com").await, first);
 
-            // Override CORS config if provided
-            if let Some(prod_cors) = &deploy_config.cors {
-                final_cors_config = Some(mockforge_core::config::HttpCorsConfig {
-                    enabled: true,
-                    allowed_origins: prod_cors.allowed_origins.clone(),
-                    allowed_methods: prod_cors.allowed_methods.clone(),
-                    allowed_headers: prod_cors.allowed_headers.clone(),
-                    allow_credentials: prod_cors.allow_credentials,
-                });
-                info!("Applied production-like CORS configuration");
-            }
+    // The id is tracked as an entity in the consistency engine
+    let entity = engine.get_entity("default", "user", "ada@example.com").await.unwrap();
+    assert_eq!(entity.data["id"], first.as_str());
+}
 
-            // Override rate limit config if provided
-            if let Some(prod_rate_limit) = &deploy_config.rate_limit {
-                rate_limit_config = middleware::RateLimitConfig {
-                    requests_per_minute: prod_rate_limit.requests_per_minute,
-                    burst: prod_rate_limit.burst,
-                    per_ip: prod_rate_limit.per_ip,
-                    per_endpoint: false,
-                };
-                info!(
-                    "Applied production-like rate limiting: {} req/min, burst: {}",
-                    prod_rate_limit.requests_per_minute, prod_rate_limit.burst
-                );
-            }
+#[tokio::test]
+async fn test_custom_route_validates_body_per_content_type() {
+    use mockforge_core::config::{
+        RouteConfig, RouteRequestConfig, RouteResponseConfig, RouteValidationConfig,
+    };
+    use tower::ServiceExt;
 
-            // Set production headers
-            if !deploy_config.headers.is_empty() {
-                let headers_map: HashMap<String, String> = deploy_config.headers.clone();
-                production_headers = Some(std::sync::Arc::new(headers_map));
-                info!("Configured {} production headers", deploy_config.headers.len());
-            }
+    let route = RouteConfig {
+        path: "/signup".to_string(),
+        method: "POST".to_string(),
+        request: Some(RouteRequestConfig {
+            validation: Some(RouteValidationConfig {
+                schema: None,
+                content_types: HashMap::from([
+                    (
+                        "application/json".to_string(),
+                        serde_json::json!({
+                            "type": "object",
+                            "required": ["email"],
+                            "properties": { "email": { "type": "string" } }
+                        }),
+                    ),
+                    (
+                        "application/x-www-form-urlencoded".to_string(),
+                        serde_json::json!({
+                            "type": "object",
+                            "required": ["email", "age"],
+                            "properties": {
+                                "email": { "type": "string" },
+                                "age": { "type": "integer" }
+                            }
+                        }),
+                    ),
+                ]),
+            }),
+            ..Default::default()
+        }),
+        response: RouteResponseConfig {
+            status: 201,
+            body: Some(serde_json::json!({ "ok": true })),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/signup",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
 
-            // Integrate OAuth config from deceptive deploy
-            if let Some(prod_oauth) = &deploy_config.oauth {
-                let oauth2_config: mockforge_core::config::OAuth2Config = prod_oauth.clone().into();
-                deceptive_deploy_auth_config = Some(mockforge_core::config::AuthConfig {
-                    oauth2: Some(oauth2_config),
-                    ..Default::default()
-                });
-                info!("Applied production-like OAuth configuration for deceptive deploy");
-            }
+    let send = |content_type: &'static str, body: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/signup")
+                .header("content-type", content_type)
+                .body(Body::from(body))
+                .unwrap();
+            app.oneshot(req).await.unwrap().status().as_u16()
         }
-    }
-
-    // Initialize rate limiter and state
-    let rate_limit_disabled = middleware::is_rate_limit_disabled();
-    let rate_limiter =
-        std::sync::Arc::new(middleware::GlobalRateLimiter::new(rate_limit_config.clone()));
+    };
 
-    let mut state = HttpServerState::new();
-    if rate_limit_disabled {
-        info!(
-            "HTTP rate limiting disabled (MOCKFORGE_RATE_LIMIT_ENABLED=false or --no-rate-limit)"
-        );
-    } else {
-        state = state.with_rate_limiter(rate_limiter.clone());
-    }
+    assert_eq!(send("application/json", r#"{"email":"a@example.com"}"#).await, 201);
+    assert_eq!(send("application/json", r#"{"age":30}"#).await, 400);
+    assert_eq!(
+        send("application/x-www-form-urlencoded", "email=a%40example.com&age=30").await,
+        201
+    );
+    assert_eq!(send("application/x-www-form-urlencoded", "email=a%40example.com").await, 400);
+    assert_eq!(send("text/plain", "email=a@example.com").await, 415);
+}
 
-    // Add production headers to state if configured
-    if let Some(headers) = production_headers.clone() {
-        state = state.with_production_headers(headers);
-    }
+#[tokio::test]
+async fn test_custom_route_rejects_body_over_the_body_limit() {
+    use mockforge_core::config::{
+        RouteConfig, RouteRequestConfig, RouteResponseConfig, RouteValidationConfig,
+    };
+    use tower::ServiceExt;
 
-    // Add rate limiting middleware (no-op when state.rate_limiter is None)
-    app = app.layer(from_fn_with_state(state.clone(), middleware::rate_limit_middleware));
+    let route = RouteConfig {
+        path: "/upload".to_string(),
+        method: "POST".to_string(),
+        request: Some(RouteRequestConfig {
+            validation: Some(RouteValidationConfig {
+                schema: Some(serde_json::json!({ "type": "object" })),
+                content_types: HashMap::new(),
+            }),
+            ..Default::default()
+        }),
+        response: RouteResponseConfig {
+            status: 201,
+            body: Some(serde_json::json!({ "ok": true })),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/upload",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let limit = http_body_limit_mb() * 1024 * 1024;
+
+    // A declared length over the limit is rejected before the body is read
+    let req = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header("content-type", "application/json")
+        .header("content-length", (limit + 1).to_string())
+        .body(Body::from("{}"))
+        .unwrap();
+    let res = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+
+    // A chunked body with no Content-Length is cut off at the limit
+    let chunk = axum::body::Bytes::from(vec![b' '; 1024 * 1024]);
+    let chunks = std::iter::repeat_n(chunk, limit / (1024 * 1024) + 1).map(Ok::<_, std::io::Error>);
+    let req = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header("content-type", "application/json")
+        .body(Body::from_stream(futures::stream::iter(chunks)))
+        .unwrap();
+    let res = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+
+    let req = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header("content-type", "application/json")
+        .body(Body::from("{}"))
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::CREATED);
+}
 
-    // Add production headers middleware if configured
-    if state.production_headers.is_some() {
-        app =
-            app.layer(from_fn_with_state(state.clone(), middleware::production_headers_middleware));
-    }
+#[test]
+fn test_time_window_variants_follow_the_clock() {
+    use chrono::TimeZone;
+    use mockforge_core::config::RouteTimeWindow;
+
+    let business_hours = RouteTimeWindow {
+        start: "09:00".to_string(),
+        end: "17:00".to_string(),
+        timezone: "America/New_York".to_string(),
+    };
+    let schedules = [
+        VariantSchedule::new(Some(&business_hours), "/support"),
+        VariantSchedule::new(None, "/support"),
+    ];
 
-    // Optionally advertise `Connection: keep-alive` + `Keep-Alive: timeout=N,
-    // max=M` on every response (Issue #79 workaround for HTTP/1.0 upstream
-    // proxies that observe FIN from MockForge after each response). Opt in
-    // via `MOCKFORGE_HTTP_KEEPALIVE_HINT=1`.
-    if middleware::is_keepalive_hint_enabled() {
-        info!(
-            "MOCKFORGE_HTTP_KEEPALIVE_HINT enabled — emitting Connection: keep-alive + Keep-Alive headers on all responses (Issue #79 workaround)"
-        );
-        app = app.layer(axum::middleware::from_fn(middleware::keepalive_hint_middleware));
-    }
+    // 14:00 UTC is 10:00 in New York (EDT): the business-hours variant
+    let inside = chrono::Utc.with_ymd_and_hms(2026, 10, 16, 14, 0, 0).unwrap();
+    assert_eq!(eligible_variants(&schedules, inside), vec![0]);
 
-    // Issue #79 (round 5): per-request log line with HTTP version + Connection
-    // header MockForge actually sees, so users debugging proxy ↔ MockForge
-    // negotiation can confirm whether their proxy is speaking HTTP/1.1 with
-    // keep-alive or HTTP/1.0 (which forces hyper to FIN after each response).
-    if middleware::is_conn_log_enabled() {
-        info!(
-            "MOCKFORGE_HTTP_LOG_CONN enabled — logging HTTP version + Connection headers per request (Issue #79 diagnostic)"
-        );
-        app = app.layer(axum::middleware::from_fn(middleware::conn_diag_middleware));
-    }
+    // 23:00 UTC is 19:00 in New York: only the fallback variant
+    let outside = chrono::Utc.with_ymd_and_hms(2026, 10, 16, 23, 0, 0).unwrap();
+    assert_eq!(eligible_variants(&schedules, outside), vec![1]);
 
-    // Add authentication middleware if OAuth is configured via deceptive deploy
-    if let Some(auth_config) = deceptive_deploy_auth_config {
-        use crate::auth::{auth_middleware, create_oauth2_client, AuthState};
-        use std::collections::HashMap;
-        use std::sync::Arc;
-        use tokio::sync::RwLock;
+    let overnight = RouteTimeWindow {
+        start: "22:00".to_string(),
+        end: "06:00".to_string(),
+        timezone: "UTC".to_string(),
+    };
+    let night = VariantSchedule::new(Some(&overnight), "/support");
+    assert!(night.is_active(outside));
+    assert!(!night.is_active(inside));
 
-        // Create OAuth2 client if configured
-        let oauth2_client = if let Some(oauth2_config) = &auth_config.oauth2 {
-            match create_oauth2_client(oauth2_config) {
-                Ok(client) => Some(client),
-                Err(e) => {
-                    warn!("Failed to create OAuth2 client from deceptive deploy config: {}", e);
-                    None
-                }
-            }
-        } else {
-            None
-        };
+    let invalid = RouteTimeWindow {
+        timezone: "Mars/Olympus_Mons".to_string(),
+        ..business_hours
+    };
+    assert!(!VariantSchedule::new(Some(&invalid), "/support").is_active(inside));
+}
 
-        // Create auth state
-        let auth_state = AuthState {
-            config: auth_config,
-            spec: None, // OpenAPI spec not available in this context
-            oauth2_client,
-            introspection_cache: Arc::new(RwLock::new(HashMap::new())),
-        };
+#[tokio::test]
+async fn test_custom_route_round_robin_variants() {
+    use mockforge_core::config::{
+        RouteConfig, RouteResponseConfig, RouteResponseSelection, RouteResponseVariant,
+    };
+    use tower::ServiceExt;
+
+    let variant = |status: Option<u16>, state: &str| RouteResponseVariant {
+        status,
+        headers: HashMap::from([("x-backend-state".to_string(), state.to_string())]),
+        body: Some(serde_json::json!({ "state": state })),
+        weight: 1.0,
+        time_window: None,
+        feature_flags: Vec::new(),
+        multipart: None,
+        raw_query_regex: None,
+    };
+    let route = RouteConfig {
+        path: "/backend".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: None,
+            variants: vec![
+                variant(None, "healthy"),
+                variant(Some(202), "busy"),
+                variant(Some(503), "down"),
+            ],
+            selection: RouteResponseSelection::RoundRobin,
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/backend",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
 
-        // Apply auth middleware
-        app = app.layer(from_fn_with_state(auth_state, auth_middleware));
-        info!("Applied OAuth authentication middleware from deceptive deploy configuration");
+    let mut seen = Vec::new();
+    for _ in 0..6 {
+        let req = Request::builder().uri("/backend").body(Body::empty()).unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        let status = res.status().as_u16();
+        let header = res.headers().get("x-backend-state").unwrap().to_str().unwrap().to_string();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["state"], header.as_str());
+        seen.push((status, header));
     }
+    let expected: Vec<(u16, String)> = [(200, "healthy"), (202, "busy"), (503, "down")]
+        .iter()
+        .cycle()
+        .take(6)
+        .map(|(status, state)| (*status, state.to_string()))
+        .collect();
+    assert_eq!(seen, expected);
+}
 
-    // Add runtime daemon 404 detection middleware (if enabled)
-    #[cfg(feature = "runtime-daemon")]
-    {
-        use mockforge_runtime_daemon::{AutoGenerator, NotFoundDetector, RuntimeDaemonConfig};
-        use std::sync::Arc;
+#[tokio::test]
+async fn test_custom_route_sequence_variants() {
+    use mockforge_core::config::{
+        RouteConfig, RouteResponseConfig, RouteResponseSelection, RouteResponseVariant,
+    };
+    use tower::ServiceExt;
+
+    let variant = |status: u16, state: &str| RouteResponseVariant {
+        status: Some(status),
+        headers: HashMap::new(),
+        body: Some(serde_json::json!({ "status": state })),
+        weight: 1.0,
+        time_window: None,
+        feature_flags: Vec::new(),
+        multipart: None,
+        raw_query_regex: None,
+    };
+    let route = |hash_on: Vec<String>| RouteConfig {
+        path: "/jobs/1".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: None,
+            variants: vec![
+                variant(202, "pending"),
+                variant(202, "pending"),
+                variant(200, "done"),
+            ],
+            selection: RouteResponseSelection::Sequence,
+            hash_on,
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = |route: RouteConfig| {
+        Router::new().route(
+            "/jobs/1",
+            custom_route_handler(
+                &route,
+                false,
+                None,
+                route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+            ),
+        )
+    };
+    let poll = |app: Router, session: Option<&'static str>| async move {
+        let mut req = Request::builder().uri("/jobs/1");
+        if let Some(session) = session {
+            req = req.header("x-session-id", session);
+        }
+        let res = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+        let status = res.status().as_u16();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        (status, body["status"].as_str().unwrap().to_string())
+    };
 
-        // Load runtime daemon config from environment
-        let daemon_config = RuntimeDaemonConfig::from_env();
+    // Polling walks the script and then holds on the final response
+    let shared = app(route(Vec::new()));
+    let mut seen = Vec::new();
+    for _ in 0..4 {
+        seen.push(poll(shared.clone(), None).await);
+    }
+    let expected: Vec<(u16, String)> = [
+        (202, "pending"),
+        (202, "pending"),
+        (200, "done"),
+        (200, "done"),
+    ]
+    .iter()
+    .map(|(status, state)| (*status, state.to_string()))
+    .collect();
+    assert_eq!(seen, expected);
+
+    // With hash_on, each session steps through its own copy of the script
+    let sessions = app(route(vec!["header.x-session-id".to_string()]));
+    for _ in 0..3 {
+        poll(sessions.clone(), Some("a")).await;
+    }
+    assert_eq!(poll(sessions.clone(), Some("a")).await, (200, "done".to_string()));
+    assert_eq!(poll(sessions.clone(), Some("b")).await, (202, "pending".to_string()));
+}
 
-        if daemon_config.enabled {
-            info!("Runtime daemon enabled - auto-creating mocks from 404s");
+#[tokio::test]
+async fn test_custom_route_feature_flag_variants() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig, RouteResponseVariant};
+    use tower::ServiceExt;
+
+    let variant = |checkout: &str, flags: &[&str]| RouteResponseVariant {
+        status: None,
+        headers: HashMap::new(),
+        body: Some(serde_json::json!({ "checkout": checkout })),
+        weight: 1.0,
+        time_window: None,
+        feature_flags: flags.iter().map(|f| f.to_string()).collect(),
+        multipart: None,
+        raw_query_regex: None,
+    };
+    let route = RouteConfig {
+        path: "/checkout".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: None,
+            variants: vec![
+                variant("classic", &[]),
+                variant("one-click", &["new-checkout", "saved-cards"]),
+            ],
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/checkout",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let checkout = |flags: Option<&str>| {
+        let mut req = Request::builder().uri("/checkout");
+        if let Some(flags) = flags {
+            req = req.header("x-feature-flags", flags);
+        }
+        let req = req.body(Body::empty()).unwrap();
+        let app = app.clone();
+        async move {
+            let res = app.oneshot(req).await.unwrap();
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            body["checkout"].as_str().unwrap().to_string()
+        }
+    };
 
-            // Determine management API URL (default to localhost:3000)
-            let management_api_url =
-                std::env::var("MOCKFORGE_MANAGEMENT_API_URL").unwrap_or_else(|_| {
-                    let port =
-                        std::env::var("MOCKFORGE_HTTP_PORT").unwrap_or_else(|_| "3000".to_string());
-                    format!("http://localhost:{}", port)
-                });
+    // Every required flag present selects the flagged variant, repeatedly
+    for _ in 0..3 {
+        assert_eq!(checkout(Some("dark-mode, saved-cards,new-checkout")).await, "one-click");
+    }
+    // Missing any one flag (or the header) falls back to the unflagged variant
+    for flags in [None, Some("new-checkout"), Some("saved-cards, dark-mode")] {
+        assert_eq!(checkout(flags).await, "classic");
+    }
+}
 
-            // Create auto-generator
-            let generator = Arc::new(AutoGenerator::new(daemon_config.clone(), management_api_url));
+#[tokio::test]
+async fn test_custom_route_raw_query_regex_variants() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig, RouteResponseVariant};
+    use tower::ServiceExt;
+
+    let variant = |source: &str, raw_query_regex: Option<&str>| RouteResponseVariant {
+        status: None,
+        headers: HashMap::new(),
+        body: Some(serde_json::json!({ "source": source, "query": "{{request.raw_query}}" })),
+        weight: 1.0,
+        time_window: None,
+        feature_flags: Vec::new(),
+        multipart: None,
+        raw_query_regex: raw_query_regex.map(str::to_string),
+    };
+    let route = RouteConfig {
+        path: "/products".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: None,
+            variants: vec![
+                variant("all", None),
+                variant("filtered", Some(r"(^|&)filter\[name\]=[^&]+")),
+            ],
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/products",
+        custom_route_handler(
+            &route,
+            true,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let get = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        }
+    };
 
-            // Create detector and set generator
-            let detector = NotFoundDetector::new(daemon_config.clone());
-            detector.set_generator(generator).await;
+    // The bracketed filter matches the untouched query string and is echoed as sent
+    let body = get("/products?filter[name]=lamp&page=2").await;
+    assert_eq!(body["source"], "filtered");
+    assert_eq!(body["query"], "filter[name]=lamp&page=2");
+
+    // Other filters, or no query at all, fall back to the ungated variant
+    let body = get("/products?filter[brand]=acme").await;
+    assert_eq!(body["source"], "all");
+    let body = get("/products").await;
+    assert_eq!(body["source"], "all");
+    assert_eq!(body["query"], "");
+}
 
-            // Add middleware layer
-            let detector_clone = detector.clone();
-            app = app.layer(axum::middleware::from_fn(
-                move |req: axum::extract::Request, next: axum::middleware::Next| {
-                    let detector = detector_clone.clone();
-                    async move { detector.detect_and_auto_create(req, next).await }
-                },
-            ));
+#[tokio::test]
+async fn test_custom_route_multipart_variants_and_templates() {
+    use mockforge_core::config::{
+        RouteConfig, RouteMultipartFileMatch, RouteMultipartMatch, RouteResponseConfig,
+        RouteResponseVariant,
+    };
+    use tower::ServiceExt;
+
+    let variant =
+        |body: serde_json::Value, multipart: Option<RouteMultipartMatch>| RouteResponseVariant {
+            status: None,
+            headers: HashMap::new(),
+            body: Some(body),
+            weight: 1.0,
+            time_window: None,
+            feature_flags: Vec::new(),
+            multipart,
+            raw_query_regex: None,
+        };
+    let pro_upload = RouteMultipartMatch {
+        fields: HashMap::from([("plan".to_string(), "pro".to_string())]),
+        files: HashMap::from([(
+            "avatar".to_string(),
+            RouteMultipartFileMatch {
+                content_type: Some("image/*".to_string()),
+            },
+        )]),
+    };
+    let route = RouteConfig {
+        path: "/profile".to_string(),
+        method: "POST".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: None,
+            variants: vec![
+                variant(serde_json::json!({ "tier": "free" }), None),
+                variant(
+                    serde_json::json!({ "tier": "pro", "user": "{{multipart.username}}" }),
+                    Some(pro_upload),
+                ),
+            ],
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/profile",
+        custom_route_handler(
+            &route,
+            true,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let upload = |plan: &str, avatar_type: &str| {
+        let body = format!(
+            "--XB\r\nContent-Disposition: form-data; name=\"username\"\r\n\r\nada\r\n\
+             --XB\r\nContent-Disposition: form-data; name=\"plan\"\r\n\r\n{plan}\r\n\
+             --XB\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"a.bin\"\r\n\
+             Content-Type: {avatar_type}\r\n\r\n0101\r\n--XB--\r\n"
+        );
+        let req = Request::builder()
+            .method("POST")
+            .uri("/profile")
+            .header("content-type", "multipart/form-data; boundary=XB")
+            .body(Body::from(body))
+            .unwrap();
+        let app = app.clone();
+        async move {
+            let res = app.oneshot(req).await.unwrap();
+            assert_eq!(res.status(), axum::http::StatusCode::OK);
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        }
+    };
 
-            debug!("Runtime daemon 404 detection middleware added");
+    // Matching field value and file type select the variant, which echoes a field
+    assert_eq!(
+        upload("pro", "image/png").await,
+        serde_json::json!({ "tier": "pro", "user": "ada" })
+    );
+    // A different field value or file type falls back to the ungated variant
+    assert_eq!(upload("free", "image/png").await["tier"], "free");
+    assert_eq!(upload("pro", "application/pdf").await["tier"], "free");
+}
+
+#[tokio::test]
+async fn test_custom_route_hash_selection_is_stable() {
+    use mockforge_core::config::{
+        RouteConfig, RouteResponseConfig, RouteResponseSelection, RouteResponseVariant,
+    };
+    use tower::ServiceExt;
+
+    let fixture = |name: &str| RouteResponseVariant {
+        status: None,
+        headers: HashMap::new(),
+        body: Some(serde_json::json!({ "fixture": name })),
+        weight: 1.0,
+        time_window: None,
+        feature_flags: Vec::new(),
+        multipart: None,
+        raw_query_regex: None,
+    };
+    let route = RouteConfig {
+        path: "/quotes".to_string(),
+        method: "POST".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: None,
+            variants: vec![fixture("a"), fixture("b"), fixture("c"), fixture("d")],
+            selection: RouteResponseSelection::Hash,
+            hash_on: vec![
+                "path".to_string(),
+                "header.x-tenant".to_string(),
+                "body.customer.id".to_string(),
+            ],
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/quotes",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let fixture_for = |customer: &str, request_id: &str| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/quotes")
+            .header("x-tenant", "acme")
+            .header("x-request-id", request_id)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::json!({ "customer": { "id": customer } }).to_string()))
+            .unwrap();
+        let app = app.clone();
+        async move {
+            let res = app.oneshot(req).await.unwrap();
+            let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            body["fixture"].as_str().unwrap().to_string()
         }
-    }
+    };
 
-    // Add /__mockforge/routes endpoint so the admin UI can discover registered routes
-    {
-        let routes_state = HttpServerState::with_routes(captured_routes);
-        let routes_router = Router::new()
-            .route("/__mockforge/routes", axum::routing::get(get_routes_handler))
-            .with_state(routes_state);
-        app = app.merge(routes_router);
+    // Identical requests pick the same fixture; attributes outside `hash_on`
+    // (here `x-request-id`) do not matter
+    let first = fixture_for("c-1", "r-1").await;
+    for i in 2..6 {
+        assert_eq!(fixture_for("c-1", &format!("r-{}", i)).await, first);
     }
 
-    // Add API docs page (Scalar-powered interactive explorer)
-    app = app.route("/__mockforge/docs", axum::routing::get(get_docs_handler));
+    // A different customer id re-keys the pick
+    let mut picks = std::collections::HashSet::new();
+    for i in 0..8 {
+        picks.insert(fixture_for(&format!("c-{}", i), "r-1").await);
+    }
+    assert!(picks.len() > 1, "all customers hashed to {:?}", picks);
+}
 
-    // Note: OData URI rewrite is applied at the service level in serve_router_with_tls()
-    // because Router::layer() only applies to matched routes, not unmatched ones.
+#[tokio::test]
+async fn test_custom_route_deprecation_headers() {
+    use mockforge_core::config::{RouteConfig, RouteDeprecationConfig, RouteResponseConfig};
+    use tower::ServiceExt;
 
-    // Add request logging middleware to capture all requests for the admin dashboard
-    app = app.layer(axum::middleware::from_fn(request_logging::log_http_requests));
+    let route = |path: &str, deprecation: Option<RouteDeprecationConfig>| RouteConfig {
+        path: path.to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "ok": true })),
+            deprecation,
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let deprecated = route(
+        "/v1/orders",
+        Some(RouteDeprecationConfig {
+            date: Some("2026-01-01".to_string()),
+            sunset: Some("{{now+30d}}".to_string()),
+            link: Some("https://docs.example.com/migrate-v2".to_string()),
+        }),
+    );
+    let current = route("/v2/orders", None);
+    let chaos = route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new());
+    let app = Router::new()
+        .route("/v1/orders", custom_route_handler(&deprecated, false, None, chaos.clone()))
+        .route("/v2/orders", custom_route_handler(&current, false, None, chaos));
+
+    let req = Request::builder().uri("/v1/orders").body(Body::empty()).unwrap();
+    let res = app.clone().oneshot(req).await.unwrap();
+    let headers = res.headers();
+    assert_eq!(headers["deprecation"], "@1767225600");
+    assert_eq!(headers["link"], "<https://docs.example.com/migrate-v2>; rel=\"deprecation\"");
+    let sunset = headers["sunset"].to_str().unwrap();
+    assert!(sunset.ends_with(" GMT"), "not an HTTP-date: {}", sunset);
+    let sunset = chrono::DateTime::parse_from_rfc2822(sunset).unwrap();
+    let days = (sunset.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_days();
+    assert!((29..=30).contains(&days), "sunset {} days away", days);
+
+    let req = Request::builder().uri("/v2/orders").body(Body::empty()).unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    for name in ["deprecation", "sunset", "link"] {
+        assert!(!res.headers().contains_key(name), "unexpected {} header", name);
+    }
+}
 
-    // Add contract diff middleware for automatic request capture
-    // This captures requests for contract diff analysis
-    app = app.layer(axum::middleware::from_fn(contract_diff_middleware::capture_for_contract_diff));
+#[tokio::test]
+async fn test_custom_route_quota_window() {
+    use mockforge_core::config::{RouteConfig, RouteQuotaConfig, RouteResponseConfig};
+    use tower::ServiceExt;
 
-    // Add CORS middleware (use final_cors_config which may be overridden by deceptive deploy)
-    app = apply_cors_middleware(app, final_cors_config);
+    let route = RouteConfig {
+        path: "/quota".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "ok": true })),
+            quota: Some(RouteQuotaConfig {
+                limit: 5,
+                window_ms: 400,
+                status: 429,
+                headers: HashMap::new(),
+                body: None,
+            }),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/quota",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let get = || {
+        let req = Request::builder().uri("/quota").body(Body::empty()).unwrap();
+        app.clone().oneshot(req)
+    };
 
-    app
+    for _ in 0..5 {
+        assert_eq!(get().await.unwrap().status(), axum::http::StatusCode::OK);
+    }
+    let res = get().await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(res.headers()["retry-after"], "1");
+
+    // The next window starts fresh
+    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+    assert_eq!(get().await.unwrap().status(), axum::http::StatusCode::OK);
 }
 
-// Note: start_with_traffic_shaping function removed due to compilation issues
-// Use build_router_with_traffic_shaping_and_multi_tenant directly instead
+#[tokio::test]
+async fn test_custom_route_flaky_alternation() {
+    use mockforge_core::config::{RouteConfig, RouteFlakyConfig, RouteResponseConfig};
+    use tower::ServiceExt;
 
-#[test]
-fn test_route_info_clone() {
-    let route = RouteInfo {
-        method: "POST".to_string(),
-        path: "/users".to_string(),
-        operation_id: Some("createUser".to_string()),
-        summary: None,
-        description: None,
-        parameters: vec![],
+    let route = |flaky: RouteFlakyConfig| RouteConfig {
+        path: "/flaky".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "ok": true })),
+            flaky: Some(flaky),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let statuses = |route: RouteConfig, calls: usize| async move {
+        let app = Router::new().route(
+            "/flaky",
+            custom_route_handler(
+                &route,
+                false,
+                None,
+                route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+            ),
+        );
+        let mut statuses = Vec::new();
+        for _ in 0..calls {
+            let req = Request::builder().uri("/flaky").body(Body::empty()).unwrap();
+            statuses.push(app.clone().oneshot(req).await.unwrap().status().as_u16());
+        }
+        statuses
     };
 
-    let cloned = route.clone();
-    assert_eq!(route.method, cloned.method);
-    assert_eq!(route.path, cloned.path);
-    assert_eq!(route.operation_id, cloned.operation_id);
+    let alternating = route(RouteFlakyConfig {
+        failure_pattern: vec![false, true],
+        every_nth: None,
+        status: 503,
+        headers: HashMap::new(),
+        body: None,
+    });
+    assert_eq!(statuses(alternating, 6).await, vec![200, 503, 200, 503, 200, 503]);
+
+    let every_third = route(RouteFlakyConfig {
+        failure_pattern: Vec::new(),
+        every_nth: Some(3),
+        status: 500,
+        headers: HashMap::new(),
+        body: None,
+    });
+    assert_eq!(statuses(every_third, 6).await, vec![200, 200, 500, 200, 200, 500]);
 }
 
 #[test]
-fn test_http_server_state_new() {
-    let state = HttpServerState::new();
-    assert_eq!(state.routes.len(), 0);
+fn test_error_ramp_rises_to_target_rate() {
+    use mockforge_core::config::RouteErrorRampConfig;
+
+    let ramp = ErrorRamp::new(RouteErrorRampConfig {
+        target_error_rate: 0.3,
+        ramp_secs: 100,
+        status: 503,
+        headers: HashMap::new(),
+        body: None,
+    });
+    let start = chrono::Utc::now();
+    // One request every 100ms: 1000 requests over the ramp, 1000 after it
+    let failures = |from: i64, count: i64| {
+        (from..from + count)
+            .filter(|i| ramp.fails_at(start + chrono::Duration::milliseconds(i * 100)))
+            .count()
+    };
+
+    // The first 10 seconds run at most 3% failures
+    let early = failures(0, 100);
+    assert!(early <= 3, "early failures: {early}");
+    let _ = failures(100, 900);
+    // Past the ramp, the rate holds at the target
+    let late = failures(1000, 1000);
+    assert!((295..=305).contains(&late), "late failures: {late}");
 }
 
-#[test]
-fn test_http_server_state_with_routes() {
-    let routes = vec![
-        RouteInfo {
-            method: "GET".to_string(),
-            path: "/users".to_string(),
-            operation_id: Some("getUsers".to_string()),
-            summary: None,
-            description: None,
-            parameters: vec![],
-        },
-        RouteInfo {
-            method: "POST".to_string(),
-            path: "/users".to_string(),
-            operation_id: Some("createUser".to_string()),
-            summary: None,
-            description: None,
-            parameters: vec![],
+#[tokio::test]
+async fn test_custom_route_rejects_protobuf_without_descriptor() {
+    use mockforge_core::config::{RouteConfig, RouteResponseConfig};
+    use tower::ServiceExt;
+
+    let route = RouteConfig {
+        path: "/orders".to_string(),
+        method: "POST".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 201,
+            body: Some(serde_json::json!({ "ok": true })),
+            ..Default::default()
         },
-    ];
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/orders",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let post = |content_type: &'static str| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/orders")
+            .header("content-type", content_type)
+            .body(Body::from(vec![0x0a, 0x03, b'A', b'-', b'1']))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
 
-    let state = HttpServerState::with_routes(routes.clone());
-    assert_eq!(state.routes.len(), 2);
-    assert_eq!(state.routes[0].method, "GET");
-    assert_eq!(state.routes[1].method, "POST");
+    let res = post("application/x-protobuf").await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    let res = post("application/octet-stream").await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::CREATED);
 }
 
-#[test]
-fn test_http_server_state_clone() {
-    let routes = vec![RouteInfo {
-        method: "GET".to_string(),
-        path: "/test".to_string(),
-        operation_id: None,
-        summary: None,
-        description: None,
-        parameters: vec![],
-    }];
+#[tokio::test]
+async fn test_custom_route_streams_ndjson_incrementally() {
+    use mockforge_core::config::{
+        RouteConfig, RouteResponseConfig, RouteStreamConfig, RouteStreamFormat,
+    };
+    use std::time::{Duration, Instant};
 
-    let state = HttpServerState::with_routes(routes);
-    let cloned = state.clone();
+    let route = RouteConfig {
+        path: "/events".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!([{"id": 1}, {"id": 2}, {"id": 3}])),
+            stream: Some(RouteStreamConfig {
+                format: RouteStreamFormat::Ndjson,
+                delay_ms: 150,
+                delays_ms: Vec::new(),
+            }),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/events",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let mut res = reqwest::get(format!("http://{}/events", addr)).await.unwrap();
+    assert_eq!(res.headers()["content-type"], "application/x-ndjson");
+
+    // Record when each complete line arrives
+    let mut buffer = Vec::new();
+    let mut lines = Vec::new();
+    while let Some(chunk) = res.chunk().await.unwrap() {
+        buffer.extend_from_slice(&chunk);
+        while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            let value: serde_json::Value = serde_json::from_slice(&line).unwrap();
+            assert!(value.is_object());
+            lines.push((value, Instant::now()));
+        }
+    }
+    assert!(buffer.is_empty());
 
-    assert_eq!(state.routes.len(), cloned.routes.len());
-    assert_eq!(state.routes[0].method, cloned.routes[0].method);
+    let ids: Vec<_> = lines.iter().map(|(v, _)| v["id"].clone()).collect();
+    assert_eq!(ids, vec![1, 2, 3]);
+    // Lines are spaced by the configured delay rather than arriving together
+    for pair in lines.windows(2) {
+        assert!(pair[1].1 - pair[0].1 >= Duration::from_millis(100));
+    }
 }
 
 #[tokio::test]
-async fn test_build_router_without_openapi() {
-    let _router = build_router(None, None, None).await;
-    // Should succeed without OpenAPI spec
-}
+async fn test_custom_route_body_transfer_latency() {
+    use mockforge_core::config::{RouteConfig, RouteLatencyConfig, RouteResponseConfig};
+    use std::time::{Duration, Instant};
 
-#[tokio::test]
-async fn test_build_router_with_nonexistent_spec() {
-    let _router = build_router(Some("/nonexistent/spec.yaml".to_string()), None, None).await;
-    // Should succeed but log a warning
+    let route = RouteConfig {
+        path: "/report".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "rows": "x".repeat(400) })),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: Some(RouteLatencyConfig {
+            enabled: true,
+            body_transfer_ms: Some(600),
+            cache: None,
+            ..Default::default()
+        }),
+    };
+    let app = Router::new().route(
+        "/report",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let started = Instant::now();
+    let res = reqwest::get(format!("http://{}/report", addr)).await.unwrap();
+    let time_to_headers = started.elapsed();
+    let body: serde_json::Value = res.json().await.unwrap();
+    let total = started.elapsed();
+
+    assert!(
+        time_to_headers < Duration::from_millis(300),
+        "headers took {:?}",
+        time_to_headers
+    );
+    assert!(total >= Duration::from_millis(550), "body arrived after only {:?}", total);
+    assert_eq!(body["rows"].as_str().unwrap().len(), 400);
 }
 
 #[tokio::test]
-async fn test_build_router_with_auth_and_latency() {
-    let _router = build_router_with_auth_and_latency(None, None, None, None).await;
-    // Should succeed without parameters
-}
+async fn test_custom_route_streams_delayed_chunks() {
+    use mockforge_core::config::{
+        RouteConfig, RouteResponseConfig, RouteStreamConfig, RouteStreamFormat,
+    };
+    use std::time::{Duration, Instant};
 
-#[tokio::test]
-async fn test_build_router_with_latency() {
-    let _router = build_router_with_latency(None, None, None).await;
-    // Should succeed without parameters
-}
+    let route = RouteConfig {
+        path: "/download".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            headers: HashMap::from([("Content-Type".to_string(), "text/plain".to_string())]),
+            body: Some(serde_json::json!(["hello ", "chunked ", "world"])),
+            stream: Some(RouteStreamConfig {
+                format: RouteStreamFormat::Chunks,
+                delay_ms: 0,
+                delays_ms: vec![0, 150, 300],
+            }),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/download",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let mut res = reqwest::get(format!("http://{}/download", addr)).await.unwrap();
+    assert_eq!(res.headers()["content-type"], "text/plain");
+
+    let mut body = Vec::new();
+    let mut arrivals = Vec::new();
+    while let Some(chunk) = res.chunk().await.unwrap() {
+        body.extend_from_slice(&chunk);
+        arrivals.push(Instant::now());
+    }
 
-#[tokio::test]
-async fn test_build_router_with_auth() {
-    let _router = build_router_with_auth(None, None, None).await;
-    // Should succeed without parameters
+    assert_eq!(body, b"hello chunked world");
+    assert_eq!(arrivals.len(), 3);
+    // Each chunk waits for its own configured delay
+    assert!(arrivals[1] - arrivals[0] >= Duration::from_millis(100));
+    assert!(arrivals[2] - arrivals[1] >= Duration::from_millis(250));
 }
 
 #[tokio::test]
-async fn test_build_router_with_chains() {
-    let _router = build_router_with_chains(None, None, None).await;
-    // Should succeed without parameters
-}
+async fn test_custom_route_delay_expression_scales_with_query() {
+    use mockforge_core::config::{RouteConfig, RouteDelayConfig, RouteResponseConfig};
+    use std::time::{Duration, Instant};
+    use tower::ServiceExt;
 
-#[test]
-fn test_route_info_with_all_fields() {
-    let route = RouteInfo {
-        method: "PUT".to_string(),
-        path: "/users/{id}".to_string(),
-        operation_id: Some("updateUser".to_string()),
-        summary: Some("Update user".to_string()),
-        description: Some("Updates an existing user".to_string()),
-        parameters: vec!["id".to_string(), "body".to_string()],
+    let route = RouteConfig {
+        path: "/items".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "ok": true })),
+            delay: Some(RouteDelayConfig {
+                expression: "query.count * 40".to_string(),
+                max_ms: 1_000,
+            }),
+            ..Default::default()
+        },
+        fault_injection: None,
+        latency: None,
+    };
+    let app = Router::new().route(
+        "/items",
+        custom_route_handler(
+            &route,
+            false,
+            None,
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let timed = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let started = Instant::now();
+            let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            assert_eq!(res.status(), axum::http::StatusCode::OK);
+            started.elapsed()
+        }
     };
 
-    assert!(route.operation_id.is_some());
-    assert!(route.summary.is_some());
-    assert!(route.description.is_some());
-    assert_eq!(route.parameters.len(), 2);
+    let small = timed("/items?count=1").await;
+    let large = timed("/items?count=10").await;
+    let capped = timed("/items?count=1000").await;
+
+    assert!(small >= Duration::from_millis(40), "count=1 took {:?}", small);
+    assert!(small < Duration::from_millis(300), "count=1 took {:?}", small);
+    assert!(large >= Duration::from_millis(400), "count=10 took {:?}", large);
+    assert!(capped >= Duration::from_millis(1_000), "count=1000 took {:?}", capped);
+    assert!(capped < Duration::from_millis(2_000), "count=1000 took {:?}", capped);
 }
 
-#[test]
-fn test_route_info_with_minimal_fields() {
-    let route = RouteInfo {
-        method: "DELETE".to_string(),
-        path: "/users/{id}".to_string(),
-        operation_id: None,
-        summary: None,
-        description: None,
-        parameters: vec![],
-    };
+#[tokio::test]
+async fn test_serve_router_ephemeral_reports_bound_address() {
+    let app = Router::new().route("/ping", axum::routing::get(|| async { "pong" }));
+    let (addr, server) = serve_router_ephemeral(app).await.unwrap();
+    assert!(addr.ip().is_loopback());
+    assert_ne!(addr.port(), 0);
+    let server = tokio::spawn(server);
+
+    let body = reqwest::get(format!("http://{}/ping", addr))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert_eq!(body, "pong");
 
-    assert!(route.operation_id.is_none());
-    assert!(route.summary.is_none());
-    assert!(route.description.is_none());
-    assert_eq!(route.parameters.len(), 0);
+    server.abort();
 }
 
-#[test]
-fn test_http_server_state_empty_routes() {
-    let state = HttpServerState::with_routes(vec![]);
-    assert_eq!(state.routes.len(), 0);
-}
+#[tokio::test]
+async fn test_custom_route_connection_error_sets_retry_after() {
+    use mockforge_core::config::{
+        RouteConfig, RouteFaultInjectionConfig, RouteFaultType, RouteResponseConfig,
+    };
+    use tower::ServiceExt;
 
-#[test]
-fn test_http_server_state_multiple_routes() {
-    let routes = vec![
-        RouteInfo {
-            method: "GET".to_string(),
-            path: "/users".to_string(),
-            operation_id: Some("listUsers".to_string()),
-            summary: Some("List all users".to_string()),
-            description: None,
-            parameters: vec![],
-        },
-        RouteInfo {
-            method: "GET".to_string(),
-            path: "/users/{id}".to_string(),
-            operation_id: Some("getUser".to_string()),
-            summary: Some("Get a user".to_string()),
-            description: None,
-            parameters: vec!["id".to_string()],
-        },
-        RouteInfo {
-            method: "POST".to_string(),
-            path: "/users".to_string(),
-            operation_id: Some("createUser".to_string()),
-            summary: Some("Create a user".to_string()),
-            description: None,
-            parameters: vec!["body".to_string()],
+    let route = RouteConfig {
+        path: "/flaky-upstream".to_string(),
+        method: "GET".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "ok": true })),
+            ..Default::default()
         },
-    ];
-
-    let state = HttpServerState::with_routes(routes);
-    assert_eq!(state.routes.len(), 3);
+        fault_injection: Some(RouteFaultInjectionConfig {
+            enabled: true,
+            probability: 1.0,
+            fault_types: vec![RouteFaultType::ConnectionError {
+                message: None,
+                retry_after_secs: Some(30),
+            }],
+            weights: None,
+            request_matcher: None,
+        }),
+        latency: None,
+    };
+    let injector = mockforge_route_chaos::RouteChaosInjector::new(vec![route.clone()]).unwrap();
+    let app = Router::new().route(
+        "/flaky-upstream",
+        custom_route_handler(
+            &route,
+            false,
+            Some(Arc::new(injector)),
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
 
-    // Verify different HTTP methods
-    let methods: Vec<&str> = state.routes.iter().map(|r| r.method.as_str()).collect();
-    assert!(methods.contains(&"GET"));
-    assert!(methods.contains(&"POST"));
+    let req = Request::builder().uri("/flaky-upstream").body(Body::empty()).unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(res.headers()[http::header::RETRY_AFTER], "30");
 }
 
-#[test]
-fn test_http_server_state_with_rate_limiter() {
-    use std::sync::Arc;
-
-    let config = middleware::RateLimitConfig::default();
-    let rate_limiter = Arc::new(middleware::GlobalRateLimiter::new(config));
+#[tokio::test]
+async fn test_custom_route_fault_only_for_selected_clients() {
+    use mockforge_core::config::{
+        HeaderMatchConfig, RequestMatcherConfig, RouteConfig, RouteFaultInjectionConfig,
+        RouteFaultType, RouteResponseConfig,
+    };
+    use tower::ServiceExt;
 
-    let state = HttpServerState::new().with_rate_limiter(rate_limiter);
+    let route = RouteConfig {
+        path: "/checkout".to_string(),
+        method: "POST".to_string(),
+        request: None,
+        response: RouteResponseConfig {
+            status: 200,
+            body: Some(serde_json::json!({ "ok": true })),
+            ..Default::default()
+        },
+        fault_injection: Some(RouteFaultInjectionConfig {
+            enabled: true,
+            probability: 1.0,
+            fault_types: vec![RouteFaultType::HttpError {
+                status_code: 502,
+                message: None,
+            }],
+            weights: None,
+            request_matcher: Some(RequestMatcherConfig {
+                headers: vec![HeaderMatchConfig {
+                    name: "X-Chaos-Client".to_string(),
+                    value: Some("me".to_string()),
+                }],
+                ..Default::default()
+            }),
+        }),
+        latency: None,
+    };
+    let injector = mockforge_route_chaos::RouteChaosInjector::new(vec![route.clone()]).unwrap();
+    let app = Router::new().route(
+        "/checkout",
+        custom_route_handler(
+            &route,
+            false,
+            Some(Arc::new(injector)),
+            route_chaos_runtime::RuntimeRouteChaosState::new(Vec::new()),
+        ),
+    );
+    let send = |client: Option<&str>| {
+        let mut req = Request::builder().method("POST").uri("/checkout");
+        if let Some(client) = client {
+            req = req.header("x-chaos-client", client);
+        }
+        app.clone().oneshot(req.body(Body::empty()).unwrap())
+    };
 
-    assert!(state.rate_limiter.is_some());
-    assert_eq!(state.routes.len(), 0);
+    assert_eq!(send(Some("me")).await.unwrap().status(), axum::http::StatusCode::BAD_GATEWAY);
+    assert_eq!(send(None).await.unwrap().status(), axum::http::StatusCode::OK);
+    assert_eq!(send(Some("someone-else")).await.unwrap().status(), axum::http::StatusCode::OK);
 }
 
 #[tokio::test]
-async fn test_build_router_includes_rate_limiter() {
-    let _router = build_router(None, None, None).await;
-    // Router should be created successfully with rate limiter initialized
+async fn test_get_route_detail_by_method_and_path() {
+    use tower::ServiceExt;
+
+    let route = |method: &str, path: &str, operation_id: &str| RouteInfo {
+        method: method.to_string(),
+        path: path.to_string(),
+        operation_id: Some(operation_id.to_string()),
+        summary: Some(format!("{} summary", operation_id)),
+        description: None,
+        parameters: vec!["id".to_string()],
+    };
+    let state = HttpServerState::with_routes(vec![
+        route("GET", "/users/{id}", "getUser"),
+        route("DELETE", "/users/{id}", "deleteUser"),
+    ]);
+    let app = Router::new()
+        .route("/__mockforge/routes/{method}/{*path}", axum::routing::get(get_route_handler))
+        .with_state(state);
+    let get = |uri: &str| app.clone().oneshot(Request::get(uri).body(Body::empty()).unwrap());
+
+    for uri in [
+        "/__mockforge/routes/DELETE/%2Fusers%2F%7Bid%7D",
+        "/__mockforge/routes/delete/users/%7Bid%7D",
+    ] {
+        let res = get(uri).await.unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::OK, "{}", uri);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let detail: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(detail["method"], "DELETE");
+        assert_eq!(detail["path"], "/users/{id}");
+        assert_eq!(detail["operation_id"], "deleteUser");
+        assert_eq!(detail["summary"], "deleteUser summary");
+        assert_eq!(detail["parameters"], serde_json::json!(["id"]));
+    }
+
+    let res = get("/__mockforge/routes/POST/%2Fusers%2F%7Bid%7D").await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::NOT_FOUND);
+    let res = get("/__mockforge/routes/GET/%2Forders").await.unwrap();
+    assert_eq!(res.status(), axum::http::StatusCode::NOT_FOUND);
 }
@@ -0,0 +1,196 @@
+//! Load generation against an in-process router.
+//!
+//! [`load_test`] replays a request plan at a fixed rate through a [`Router`]
+//! without opening sockets, so the numbers reflect the mock's own handling
+//! (routing, middleware, templating) rather than the network. Requests are
+//! started in plan order, one every `1 / target_rps` seconds, which keeps runs
+//! comparable across builds when measuring regressions.
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{Method, Request};
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use tokio::time::MissedTickBehavior;
+use tower::ServiceExt;
+
+/// One request in a load plan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedRequest {
+    /// HTTP method
+    pub method: String,
+    /// Path and optional query string
+    pub path: String,
+    /// Request body, sent as JSON when present
+    #[serde(default)]
+    pub body: Option<serde_json::Value>,
+}
+
+impl PlannedRequest {
+    /// Build the request, from a loopback client so handlers and middleware
+    /// reading `ConnectInfo` see the same thing as with a real connection
+    fn to_request(&self) -> Option<Request<Body>> {
+        let method = Method::from_bytes(self.method.to_ascii_uppercase().as_bytes()).ok()?;
+        let builder = Request::builder().method(method).uri(&self.path);
+        let mut request = match &self.body {
+            Some(body) => builder
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(body).ok()?)),
+            None => builder.body(Body::empty()),
+        }
+        .ok()?;
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+        Some(request)
+    }
+}
+
+/// Results of a [`load_test`] run
+#[derive(Debug, Clone)]
+pub struct LoadTestStats {
+    /// Requests that received a response
+    pub requests: usize,
+    /// Plan entries that could not be sent (invalid method or path) or whose
+    /// response body failed to read
+    pub errors: usize,
+    /// Responses by status code
+    pub status_counts: BTreeMap<u16, usize>,
+    /// Time from the first request starting to the last response finishing
+    pub elapsed: Duration,
+    /// Completed requests per second over `elapsed`
+    pub throughput_rps: f64,
+    /// Fastest response
+    pub latency_min: Duration,
+    /// Average response time
+    pub latency_mean: Duration,
+    /// Median response time
+    pub latency_p50: Duration,
+    /// 95th percentile response time
+    pub latency_p95: Duration,
+    /// 99th percentile response time
+    pub latency_p99: Duration,
+    /// Slowest response
+    pub latency_max: Duration,
+}
+
+/// Replay `plan` against `router` at `target_rps` requests per second
+///
+/// Each request is timed until its response body has been read. Requests
+/// overlap when responses take longer than the send interval, as they would
+/// from independent clients. A `target_rps` of zero is treated as one.
+pub async fn load_test(router: Router, plan: &[PlannedRequest], target_rps: u32) -> LoadTestStats {
+    let mut ticker = tokio::time::interval(Duration::from_secs(1) / target_rps.max(1));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Burst);
+
+    let started = Instant::now();
+    let mut in_flight = JoinSet::new();
+    let mut errors = 0;
+    for planned in plan {
+        ticker.tick().await;
+        let Some(request) = planned.to_request() else {
+            errors += 1;
+            continue;
+        };
+        let router = router.clone();
+        in_flight.spawn(async move {
+            let sent = Instant::now();
+            let response = match router.oneshot(request).await {
+                Ok(response) => response,
+                Err(infallible) => match infallible {},
+            };
+            let status = response.status().as_u16();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await;
+            body.ok().map(|_| (status, sent.elapsed()))
+        });
+    }
+
+    let mut status_counts = BTreeMap::new();
+    let mut latencies = Vec::with_capacity(plan.len());
+    while let Some(result) = in_flight.join_next().await {
+        match result.ok().flatten() {
+            Some((status, latency)) => {
+                *status_counts.entry(status).or_insert(0) += 1;
+                latencies.push(latency);
+            }
+            None => errors += 1,
+        }
+    }
+    let elapsed = started.elapsed();
+
+    latencies.sort_unstable();
+    let percentile = |p: usize| {
+        let rank = (latencies.len() * p).div_ceil(100).max(1);
+        latencies.get(rank - 1).copied().unwrap_or_default()
+    };
+    let total: Duration = latencies.iter().sum();
+    LoadTestStats {
+        requests: latencies.len(),
+        errors,
+        status_counts,
+        elapsed,
+        throughput_rps: latencies.len() as f64 / elapsed.as_secs_f64().max(f64::EPSILON),
+        latency_min: latencies.first().copied().unwrap_or_default(),
+        latency_mean: total.checked_div(latencies.len() as u32).unwrap_or_default(),
+        latency_p50: percentile(50),
+        latency_p95: percentile(95),
+        latency_p99: percentile(99),
+        latency_max: latencies.last().copied().unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+
+    fn planned(method: &str, path: &str, body: Option<serde_json::Value>) -> PlannedRequest {
+        PlannedRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            body,
+        }
+    }
+
+    #[tokio::test]
+    async fn stats_cover_every_planned_request() {
+        let app = Router::new()
+            .route("/users", get(|| async { "[]" }).post(|body: String| async move { body }));
+        let plan = vec![
+            planned("GET", "/users", None),
+            planned("post", "/users", Some(serde_json::json!({ "name": "Ada" }))),
+            planned("GET", "/missing", None),
+            planned("GET", "/users?page=2", None),
+        ];
+
+        let stats = load_test(app, &plan, 200).await;
+
+        assert_eq!(stats.requests, plan.len());
+        assert_eq!(stats.errors, 0);
+        assert_eq!(stats.status_counts.values().sum::<usize>(), plan.len());
+        assert_eq!(stats.status_counts[&200], 3);
+        assert_eq!(stats.status_counts[&404], 1);
+        assert!(stats.latency_min <= stats.latency_p50);
+        assert!(stats.latency_p50 <= stats.latency_p99);
+        assert!(stats.latency_p99 <= stats.latency_max);
+        assert!(stats.throughput_rps > 0.0);
+    }
+
+    #[tokio::test]
+    async fn invalid_plan_entries_count_as_errors() {
+        let app = Router::new().route("/ok", get(|| async { "ok" }));
+        let plan = vec![
+            planned("GET", "/ok", None),
+            planned("NOT A METHOD", "/ok", None),
+        ];
+
+        let stats = load_test(app, &plan, 1000).await;
+
+        assert_eq!(stats.requests, 1);
+        assert_eq!(stats.errors, 1);
+    }
+}
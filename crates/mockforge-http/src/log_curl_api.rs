@@ -0,0 +1,162 @@
+//! curl reproductions of logged requests.
+//!
+//! Bug reports are easier to act on with a command that re-sends the exact
+//! request. This API rebuilds one from a centralized request-log entry: the
+//! method, the concrete URI, the recorded headers and (for small text bodies)
+//! the body. Only headers the request logger keeps are included, so
+//! credentials such as `Authorization` or cookies never appear in the output.
+//!
+//! ## Endpoints
+//!
+//! - `GET /__mockforge/api/logs/{id}/curl` — the command as `text/plain`
+
+use axum::extract::Path;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use mockforge_core::request_logger::RequestLogEntry;
+
+/// Headers left out of the command: curl derives them from the URL and body
+const DERIVED_HEADERS: [&str; 2] = ["host", "content-length"];
+
+/// Quote `value` for a POSIX shell, leaving plain tokens bare
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | ':' | '@'));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// The path and query the entry was logged with. Falls back to rebuilding the
+/// query from `query_params` for entries recorded without `request_uri`.
+fn request_uri(entry: &RequestLogEntry) -> String {
+    if let Some(uri) = entry.metadata.get("request_uri") {
+        return uri.clone();
+    }
+    if entry.query_params.is_empty() {
+        return entry.path.clone();
+    }
+    let mut params: Vec<_> = entry.query_params.iter().collect();
+    params.sort();
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    format!("{}?{}", entry.path, query)
+}
+
+/// Build a curl command reproducing `entry` against `base_url`
+/// (e.g. `http://localhost:3000`)
+pub fn curl_command(entry: &RequestLogEntry, base_url: &str) -> String {
+    let url = format!("{}{}", base_url.trim_end_matches('/'), request_uri(entry));
+    let mut parts = vec![format!(
+        "curl -X {} {}",
+        shell_quote(&entry.method),
+        shell_quote(&url)
+    )];
+
+    let mut headers: Vec<_> = entry
+        .headers
+        .iter()
+        .filter(|(name, _)| !DERIVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()))
+        .collect();
+    headers.sort();
+    for (name, value) in headers {
+        parts.push(format!("-H {}", shell_quote(&format!("{}: {}", name, value))));
+    }
+
+    if let Some(body) = entry.metadata.get("request_body") {
+        parts.push(format!("--data-raw {}", shell_quote(body)));
+    }
+    parts.join(" \\\n  ")
+}
+
+async fn log_curl_handler(Path(id): Path<String>, headers: HeaderMap) -> Response {
+    let Some(logger) = mockforge_core::get_global_logger() else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Request logging is not enabled").into_response();
+    };
+    let Some(entry) = logger.get_log(&id).await else {
+        return (StatusCode::NOT_FOUND, format!("No logged request with id '{}'", id))
+            .into_response();
+    };
+
+    // Prefer the host the original request was sent to
+    let host = entry
+        .headers
+        .get("host")
+        .cloned()
+        .or_else(|| headers.get(header::HOST).and_then(|h| h.to_str().ok()).map(str::to_string))
+        .unwrap_or_else(|| "localhost".to_string());
+    let command = curl_command(&entry, &format!("http://{}", host));
+    ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], command).into_response()
+}
+
+/// Build the log curl API router. Mount under `/__mockforge/api/logs`.
+pub fn log_curl_api_router() -> Router {
+    Router::new().route("/{id}/curl", get(log_curl_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use mockforge_core::create_http_log_entry;
+    use std::collections::HashMap;
+    use tower::ServiceExt;
+
+    #[test]
+    fn quotes_values_that_need_it() {
+        assert_eq!(shell_quote("POST"), "POST");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[tokio::test]
+    async fn logged_request_is_rendered_as_curl() {
+        let logger = mockforge_core::init_global_logger(1000);
+        let mut entry = create_http_log_entry(
+            "POST",
+            "/orders/{id}",
+            201,
+            3,
+            None,
+            None,
+            HashMap::from([
+                ("content-type".to_string(), "application/json".to_string()),
+                ("host".to_string(), "api.test:3000".to_string()),
+            ]),
+            0,
+            None,
+        );
+        entry
+            .metadata
+            .insert("request_uri".to_string(), "/orders/42?dry_run=true".to_string());
+        entry
+            .metadata
+            .insert("request_body".to_string(), r#"{"note":"it's urgent"}"#.to_string());
+        let id = entry.id.clone();
+        logger.log_request(entry).await;
+
+        let app = log_curl_api_router();
+        let req = Request::builder().uri(format!("/{}/curl", id)).body(Body::empty()).unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let command = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(command.starts_with("curl -X POST 'http://api.test:3000/orders/42?dry_run=true'"));
+        assert!(command.contains("-H 'content-type: application/json'"));
+        assert!(!command.contains("host:"));
+        assert!(command.contains(r#"--data-raw '{"note":"it'\''s urgent"}'"#));
+
+        let req = Request::builder().uri("/missing/curl").body(Body::empty()).unwrap();
+        assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::NOT_FOUND);
+    }
+}
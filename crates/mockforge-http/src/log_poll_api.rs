@@ -0,0 +1,121 @@
+//! Long-polling access to the request log.
+//!
+//! A fallback for environments where proxies block SSE and WebSockets: the
+//! client passes the cursor from its previous poll, the request blocks until
+//! something has been logged after it (or the timeout passes), and the
+//! response carries the new entries with the cursor for the next poll.
+//!
+//! ## Endpoints
+//!
+//! - `GET /__mockforge/api/logs/poll?since=<cursor>&timeout_ms=<ms>` — entries
+//!   logged after `since` (default `0`, everything still buffered), oldest
+//!   first. Waits up to `timeout_ms` (default 30s, at most 60s) when there
+//!   are none yet, then answers with an empty list and the current cursor.
+
+use axum::extract::Query;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use mockforge_core::request_logger::RequestLogEntry;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Wait used when the client does not pass `timeout_ms`
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+/// Longest wait a client may ask for
+const MAX_TIMEOUT_MS: u64 = 60_000;
+
+/// Query parameters of a poll
+#[derive(Debug, Deserialize)]
+pub struct LogPollQuery {
+    /// Cursor returned by the previous poll
+    #[serde(default)]
+    pub since: u64,
+    /// How long to wait for new entries, in milliseconds
+    pub timeout_ms: Option<u64>,
+}
+
+/// Result of a poll
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogPollResponse {
+    /// Entries logged after the requested cursor, oldest first
+    pub entries: Vec<RequestLogEntry>,
+    /// Cursor to pass as `since` on the next poll
+    pub cursor: u64,
+}
+
+async fn log_poll_handler(Query(query): Query<LogPollQuery>) -> Response {
+    let Some(logger) = mockforge_core::get_global_logger() else {
+        return (StatusCode::SERVICE_UNAVAILABLE, "Request logging is not enabled").into_response();
+    };
+    let timeout =
+        Duration::from_millis(query.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS).min(MAX_TIMEOUT_MS));
+    let (entries, cursor) = logger.wait_for_logs_since(query.since, timeout).await;
+    Json(LogPollResponse { entries, cursor }).into_response()
+}
+
+/// Build the log polling router. Mount under `/__mockforge/api/logs`.
+pub fn log_poll_api_router() -> Router {
+    Router::new().route("/poll", get(log_poll_handler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::extract::ConnectInfo;
+    use axum::http::Request;
+    use std::net::SocketAddr;
+    use tower::ServiceExt;
+
+    async fn poll(app: &Router, since: u64) -> LogPollResponse {
+        let req = Request::builder()
+            .uri(format!("/__mockforge/api/logs/poll?since={}&timeout_ms=5000", since))
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn poll_returns_request_logged_while_waiting() {
+        let logger = mockforge_core::init_global_logger(1000);
+        let app = Router::new()
+            .route("/orders/poll-test", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn(crate::request_logging::log_http_requests))
+            .nest("/__mockforge/api/logs", log_poll_api_router());
+        let (_, start) = logger.logs_since(u64::MAX).await;
+
+        let poller = {
+            let app = app.clone();
+            tokio::spawn(async move {
+                // Other tests share the global logger; keep polling until the
+                // request below shows up
+                let mut cursor = start;
+                loop {
+                    let polled = poll(&app, cursor).await;
+                    assert!(polled.cursor > cursor, "cursor did not advance");
+                    cursor = polled.cursor;
+                    if let Some(entry) =
+                        polled.entries.into_iter().find(|e| e.path == "/orders/poll-test")
+                    {
+                        return (entry, cursor);
+                    }
+                }
+            })
+        };
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut req = Request::builder().uri("/orders/poll-test").body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+        let (entry, cursor) = poller.await.unwrap();
+        assert_eq!(entry.method, "GET");
+        assert!(cursor > start);
+    }
+}
@@ -0,0 +1,303 @@
+//! Export of request log entries to external sinks.
+//!
+//! The request logger keeps recent entries in memory only. For centralized
+//! observability, entries can also be pushed to an external [`LogSink`] in
+//! near-real-time. A [`LogExporter`] queues entries from the request path
+//! and delivers them in batches from a background task, retrying failed
+//! batches with exponential backoff. Request handling never waits on the
+//! sink: when the queue is full (e.g. the sink is down), new entries are
+//! dropped rather than buffered without bound.
+//!
+//! The built-in sink is [`WebhookLogSink`], which POSTs each batch as a JSON
+//! array. Other destinations (e.g. a Kafka topic) implement [`LogSink`].
+//!
+//! ## Environment
+//!
+//! - `MOCKFORGE_LOG_SINK_WEBHOOK_URL` — enables the webhook sink
+//! - `MOCKFORGE_LOG_SINK_BATCH_SIZE` — entries per batch (default 100)
+//! - `MOCKFORGE_LOG_SINK_FLUSH_MS` — longest wait before a partial batch is
+//!   sent (default 1000)
+//! - `MOCKFORGE_LOG_SINK_MAX_RETRIES` — retries per batch before it is
+//!   dropped (default 5)
+
+use async_trait::async_trait;
+use mockforge_core::request_logger::RequestLogEntry;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+/// Upper bound on the delay between retries of one batch
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// A destination for exported request log entries
+#[async_trait]
+pub trait LogSink: Send + Sync + 'static {
+    /// Sink name, for diagnostics
+    fn name(&self) -> &str;
+
+    /// Deliver a batch of entries. An error causes the batch to be retried.
+    async fn send(&self, batch: &[RequestLogEntry]) -> Result<(), String>;
+}
+
+/// Sink that POSTs each batch to a webhook as a JSON array of entries
+pub struct WebhookLogSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookLogSink {
+    /// Create a webhook sink posting to `url`
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            url: url.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl LogSink for WebhookLogSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, batch: &[RequestLogEntry]) -> Result<(), String> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(batch)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("webhook responded with {}", response.status()))
+        }
+    }
+}
+
+/// Batching and retry settings for a [`LogExporter`]
+#[derive(Debug, Clone)]
+pub struct LogExportConfig {
+    /// Most entries sent in one batch
+    pub batch_size: usize,
+    /// Longest time an entry waits for its batch to fill before it is sent
+    pub flush_interval: Duration,
+    /// Retries of a failed batch before it is dropped
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each further retry
+    pub initial_backoff: Duration,
+    /// Entries queued for export before new ones are dropped
+    pub queue_capacity: usize,
+}
+
+impl Default for LogExportConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            flush_interval: Duration::from_secs(1),
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            queue_capacity: 10_000,
+        }
+    }
+}
+
+impl LogExportConfig {
+    /// Read the settings from `MOCKFORGE_LOG_SINK_*`, falling back to the
+    /// defaults
+    pub fn from_env() -> Self {
+        fn env<T: std::str::FromStr>(name: &str) -> Option<T> {
+            std::env::var(name).ok().and_then(|v| v.parse().ok())
+        }
+
+        let defaults = Self::default();
+        Self {
+            batch_size: env("MOCKFORGE_LOG_SINK_BATCH_SIZE")
+                .filter(|&n: &usize| n > 0)
+                .unwrap_or(defaults.batch_size),
+            flush_interval: env("MOCKFORGE_LOG_SINK_FLUSH_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.flush_interval),
+            max_retries: env("MOCKFORGE_LOG_SINK_MAX_RETRIES").unwrap_or(defaults.max_retries),
+            ..defaults
+        }
+    }
+}
+
+/// Queues log entries and delivers them to a [`LogSink`] from a background
+/// task
+#[derive(Clone)]
+pub struct LogExporter {
+    tx: mpsc::Sender<RequestLogEntry>,
+}
+
+impl LogExporter {
+    /// Start exporting to `sink`. Must be called from within a Tokio runtime.
+    pub fn spawn(sink: Arc<dyn LogSink>, config: LogExportConfig) -> Self {
+        let (tx, rx) = mpsc::channel(config.queue_capacity.max(1));
+        tokio::spawn(run_exporter(rx, sink, config));
+        Self { tx }
+    }
+
+    /// Queue an entry for export without waiting. Dropped if the queue is
+    /// full.
+    pub fn export(&self, entry: RequestLogEntry) {
+        if let Err(mpsc::error::TrySendError::Full(entry)) = self.tx.try_send(entry) {
+            debug!(id = %entry.id, "Log export queue full; dropping entry");
+        }
+    }
+}
+
+async fn run_exporter(
+    mut rx: mpsc::Receiver<RequestLogEntry>,
+    sink: Arc<dyn LogSink>,
+    config: LogExportConfig,
+) {
+    let batch_size = config.batch_size.max(1);
+    let mut batch = Vec::with_capacity(batch_size);
+    // Each batch starts with the next entry and fills until it is full or
+    // the flush interval has passed
+    while let Some(entry) = rx.recv().await {
+        batch.push(entry);
+        let deadline = tokio::time::sleep(config.flush_interval);
+        tokio::pin!(deadline);
+        while batch.len() < batch_size {
+            tokio::select! {
+                entry = rx.recv() => match entry {
+                    Some(entry) => batch.push(entry),
+                    None => break,
+                },
+                _ = &mut deadline => break,
+            }
+        }
+        deliver(sink.as_ref(), &batch, &config).await;
+        batch.clear();
+    }
+}
+
+/// Send `batch`, retrying with exponential backoff
+async fn deliver(sink: &dyn LogSink, batch: &[RequestLogEntry], config: &LogExportConfig) {
+    let mut backoff = config.initial_backoff;
+    let mut attempt = 0;
+    loop {
+        match sink.send(batch).await {
+            Ok(()) => return,
+            Err(e) if attempt < config.max_retries => {
+                debug!(sink = sink.name(), attempt, error = %e, "Log export failed; retrying");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                attempt += 1;
+            }
+            Err(e) => {
+                warn!(
+                    sink = sink.name(),
+                    entries = batch.len(),
+                    error = %e,
+                    "Dropping request log batch after {} retries",
+                    config.max_retries
+                );
+                return;
+            }
+        }
+    }
+}
+
+static GLOBAL_EXPORTER: std::sync::OnceLock<LogExporter> = std::sync::OnceLock::new();
+
+/// Start the global exporter from `MOCKFORGE_LOG_SINK_*` if a sink is
+/// configured. Returns whether export is enabled. Must be called from within
+/// a Tokio runtime.
+pub fn init_log_sink_from_env() -> bool {
+    let Some(url) = std::env::var("MOCKFORGE_LOG_SINK_WEBHOOK_URL").ok().filter(|u| !u.is_empty())
+    else {
+        return GLOBAL_EXPORTER.get().is_some();
+    };
+    GLOBAL_EXPORTER.get_or_init(|| {
+        LogExporter::spawn(Arc::new(WebhookLogSink::new(url)), LogExportConfig::from_env())
+    });
+    true
+}
+
+/// Queue `entry` on the global exporter, if one was started
+pub fn export_log_entry(entry: &RequestLogEntry) {
+    if let Some(exporter) = GLOBAL_EXPORTER.get() {
+        exporter.export(entry.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use mockforge_core::create_http_log_entry;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct Receiver {
+        calls: Arc<AtomicUsize>,
+        received: Arc<Mutex<Vec<RequestLogEntry>>>,
+    }
+
+    /// Webhook that rejects its first call, then records every entry
+    async fn receive(
+        State(receiver): State<Receiver>,
+        Json(batch): Json<Vec<RequestLogEntry>>,
+    ) -> StatusCode {
+        if receiver.calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            return StatusCode::SERVICE_UNAVAILABLE;
+        }
+        receiver.received.lock().unwrap().extend(batch);
+        StatusCode::NO_CONTENT
+    }
+
+    #[tokio::test]
+    async fn entries_arrive_at_webhook_after_retry() {
+        let receiver = Receiver::default();
+        let app = Router::new().route("/logs", post(receive)).with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let exporter = LogExporter::spawn(
+            Arc::new(WebhookLogSink::new(format!("http://{}/logs", addr))),
+            LogExportConfig {
+                batch_size: 2,
+                flush_interval: Duration::from_millis(50),
+                max_retries: 3,
+                initial_backoff: Duration::from_millis(10),
+                queue_capacity: 100,
+            },
+        );
+        let entries: Vec<RequestLogEntry> = ["/a", "/b", "/c"]
+            .into_iter()
+            .map(|path| {
+                create_http_log_entry("GET", path, 200, 1, None, None, HashMap::new(), 0, None)
+            })
+            .collect();
+        for entry in &entries {
+            exporter.export(entry.clone());
+        }
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while receiver.received.lock().unwrap().len() < entries.len() {
+            assert!(tokio::time::Instant::now() < deadline, "log entries did not arrive");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let received = receiver.received.lock().unwrap();
+        let ids: Vec<&str> = received.iter().map(|e| e.id.as_str()).collect();
+        let expected: Vec<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, expected);
+        assert_eq!(received[1].path, "/b");
+        // One rejected call, then a full batch and a partial one
+        assert_eq!(receiver.calls.load(Ordering::SeqCst), 3);
+    }
+}
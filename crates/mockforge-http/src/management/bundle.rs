@@ -0,0 +1,142 @@
+//! Management endpoint exporting a self-contained test harness.
+//!
+//!   - `GET /__mockforge/api/export/bundle` → a zip holding what another
+//!     instance needs to reproduce this mock:
+//!       - `mockforge.yaml` — the effective config, secrets redacted as in
+//!         `config/export.yaml`, pointing at the bundled spec
+//!       - `openapi.json` — the loaded OpenAPI spec
+//!       - `fixtures/*.json` — custom fixtures from the fixtures directory
+//!       - `mocks.json` — mocks created through the management API
+//!       - `README.md` — how to run the bundle
+//!
+//! Entries with nothing to export are left out.
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+
+use super::config_export::redact_secrets;
+use super::ManagementState;
+
+/// Bundle entry holding the effective config
+const CONFIG_FILE: &str = "mockforge.yaml";
+
+/// Bundle entry holding the OpenAPI spec
+const SPEC_FILE: &str = "openapi.json";
+
+const README: &str = "# MockForge bundle
+
+Exported from a running MockForge instance with
+`GET /__mockforge/api/export/bundle`. To reproduce the setup, run from this
+directory:
+
+    MOCKFORGE_FIXTURES_DIR=./fixtures mockforge serve --config mockforge.yaml
+
+Mocks created through the management API are in `mocks.json`. Load them into
+the running instance with:
+
+    curl -X POST -H 'Content-Type: application/json' \\
+      --data @mocks.json http://localhost:3000/__mockforge/api/import
+
+Secrets in `mockforge.yaml` were redacted and must be filled back in.
+";
+
+pub(crate) async fn export_bundle(State(state): State<ManagementState>) -> Response {
+    match build_bundle(&state).await {
+        Ok(bundle) => (
+            [
+                (header::CONTENT_TYPE, "application/zip"),
+                (header::CONTENT_DISPOSITION, "attachment; filename=\"mockforge-bundle.zip\""),
+            ],
+            bundle,
+        )
+            .into_response(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(serde_json::json!({
+                "error": "Failed to build bundle",
+                "message": e
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// Collect the bundle entries and zip them
+async fn build_bundle(state: &ManagementState) -> Result<Vec<u8>, String> {
+    let mut files = vec![("README.md".to_string(), README.as_bytes().to_vec())];
+
+    let spec = match &state.spec {
+        Some(spec) => Some(match &spec.raw_document {
+            Some(doc) => doc.clone(),
+            None => serde_json::to_value(&spec.spec).map_err(|e| e.to_string())?,
+        }),
+        None => None,
+    };
+
+    if let Some(config) = &state.server_config {
+        let mut value = serde_json::to_value(&*config.read().await).map_err(|e| e.to_string())?;
+        redact_secrets(&mut value);
+        if spec.is_some() {
+            if let Some(http) = value.get_mut("http").and_then(|http| http.as_object_mut()) {
+                http.insert("openapi_spec".to_string(), SPEC_FILE.into());
+            }
+        }
+        let yaml = serde_yaml::to_string(&value).map_err(|e| e.to_string())?;
+        files.push((CONFIG_FILE.to_string(), yaml.into_bytes()));
+    }
+
+    if let Some(spec) = spec {
+        let json = serde_json::to_vec_pretty(&spec).map_err(|e| e.to_string())?;
+        files.push((SPEC_FILE.to_string(), json));
+    }
+
+    files.extend(read_fixtures(&state.fixtures_dir).await);
+
+    let mocks = state.mocks.read().await;
+    if !mocks.is_empty() {
+        let json = serde_json::to_vec_pretty(&*mocks).map_err(|e| e.to_string())?;
+        files.push(("mocks.json".to_string(), json));
+    }
+    drop(mocks);
+
+    write_zip(&files).map_err(|e| e.to_string())
+}
+
+/// The `.json` files directly under `dir` (as the fixture loader reads them),
+/// as `fixtures/<name>` entries sorted by name. A missing directory has none.
+async fn read_fixtures(dir: &Path) -> Vec<(String, Vec<u8>)> {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return Vec::new();
+    };
+    let mut fixtures = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if let Ok(contents) = tokio::fs::read(&path).await {
+            fixtures.push((format!("fixtures/{name}"), contents));
+        }
+    }
+    fixtures.sort();
+    fixtures
+}
+
+fn write_zip(files: &[(String, Vec<u8>)]) -> zip::result::ZipResult<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(contents)?;
+    }
+    Ok(zip.finish()?.into_inner())
+}
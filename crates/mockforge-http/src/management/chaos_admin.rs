@@ -1,5 +1,6 @@
 //! Chaos engineering + network profile admin endpoints
 //! (`GET|POST /__mockforge/chaos/config`,
+//! `GET /__mockforge/chaos/status`,
 //! `GET /__mockforge/network/profiles`,
 //! `POST /__mockforge/network/profile/apply`).
 //!
@@ -161,6 +162,41 @@ pub(crate) async fn update_chaos_config(
     }
 }
 
+/// Chaos dashboard feed: per-route fault probabilities, latency profiles and
+/// injected-fault counters from route chaos, plus the global chaos config
+/// (fault injection, latency, circuit breaker) when the chaos API is wired in.
+pub(crate) async fn get_chaos_status(State(state): State<ManagementState>) -> impl IntoResponse {
+    let (routes, faults_injected_total) = match &state.route_chaos {
+        Some(route_chaos) => {
+            let summary = route_chaos.summary();
+            (summary.routes, summary.faults_injected_total)
+        }
+        None => (Vec::new(), 0),
+    };
+
+    #[cfg(feature = "chaos")]
+    let global = match &state.chaos_api_state {
+        Some(chaos_state) => {
+            let config = chaos_state.config.read().await;
+            serde_json::json!({
+                "enabled": config.enabled,
+                "fault_injection": config.fault_injection,
+                "latency": config.latency,
+                "circuit_breaker": config.circuit_breaker,
+            })
+        }
+        None => serde_json::Value::Null,
+    };
+    #[cfg(not(feature = "chaos"))]
+    let global = serde_json::Value::Null;
+
+    Json(serde_json::json!({
+        "routes": routes,
+        "faults_injected_total": faults_injected_total,
+        "global": global,
+    }))
+}
+
 /// List available network profiles
 pub(crate) async fn list_network_profiles() -> impl IntoResponse {
     use mockforge_chaos::core_network_profiles::NetworkProfileCatalog;
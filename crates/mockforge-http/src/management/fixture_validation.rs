@@ -0,0 +1,129 @@
+//! Management endpoint checking a fixture against the loaded OpenAPI spec.
+//!
+//!   - `POST /__mockforge/api/fixtures/validate` → validates the fixture's
+//!     response (status, headers and body) against the operation its method
+//!     and path resolve to, and lists what does not conform
+//!
+//! The body is a fixture in either format read by `CustomFixtureLoader`
+//! (flat or nested). A fixture path may be concrete (`/users/42`) or the
+//! spec's own template (`/users/{id}`).
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use mockforge_openapi::custom_fixture::{CustomFixture, CustomFixtureLoader, NestedFixture};
+use mockforge_openapi::{OpenApiSpec, ResponseValidator};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{path_matches_pattern, ManagementState};
+
+#[derive(Debug, Serialize)]
+pub(crate) struct FixtureValidationResponse {
+    /// Whether the fixture conforms to the spec.
+    pub valid: bool,
+    /// Spec path template the fixture resolved to, if any.
+    pub operation_path: Option<String>,
+    /// Upper-cased HTTP method of the fixture.
+    pub method: String,
+    /// Response status the fixture serves.
+    pub status: u16,
+    /// Violations; empty when `valid`.
+    pub errors: Vec<String>,
+}
+
+pub(crate) async fn validate_fixture(
+    State(state): State<ManagementState>,
+    Json(payload): Json<Value>,
+) -> Response {
+    let Some(spec) = &state.spec else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "No OpenAPI spec loaded",
+                "message": "Start the server with a spec to validate fixtures against it"
+            })),
+        )
+            .into_response();
+    };
+
+    let fixture = match parse_fixture(payload) {
+        Ok(fixture) => fixture,
+        Err(message) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "error": "Invalid fixture",
+                    "message": message
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    Json(check_fixture(spec, &fixture)).into_response()
+}
+
+/// Accept the flat format first, then the nested one, as the loader does
+fn parse_fixture(payload: Value) -> Result<CustomFixture, String> {
+    match serde_json::from_value::<CustomFixture>(payload.clone()) {
+        Ok(mut fixture) => {
+            fixture.path = CustomFixtureLoader::normalize_path(&fixture.path);
+            Ok(fixture)
+        }
+        Err(flat_error) => {
+            let nested: NestedFixture = serde_json::from_value(payload)
+                .map_err(|_| format!("not a valid flat or nested fixture: {}", flat_error))?;
+            CustomFixtureLoader::convert_nested_to_flat(nested).map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn check_fixture(spec: &OpenApiSpec, fixture: &CustomFixture) -> FixtureValidationResponse {
+    let method = fixture.method.to_ascii_uppercase();
+    let path = fixture.path.split('?').next().unwrap_or_default();
+    let mut response = FixtureValidationResponse {
+        valid: false,
+        operation_path: None,
+        method: method.clone(),
+        status: fixture.status,
+        errors: Vec::new(),
+    };
+
+    // An exact template match wins over one that only matches by parameters
+    let templates: Vec<&String> = spec.paths().paths.keys().collect();
+    let template = templates
+        .iter()
+        .find(|template| template.as_str() == path)
+        .or_else(|| templates.iter().find(|template| path_matches_pattern(template, path)));
+    let Some(template) = template else {
+        response.errors.push(format!("No path in the spec matches {}", path));
+        return response;
+    };
+    response.operation_path = Some(template.to_string());
+
+    let Some(operation) = spec.operations_for_path(template).remove(&method) else {
+        response
+            .errors
+            .push(format!("{} {} is not defined in the spec", method, template));
+        return response;
+    };
+
+    let body = (!fixture.response.is_null()).then_some(&fixture.response);
+    match ResponseValidator::validate_response(
+        spec,
+        &operation,
+        fixture.status,
+        &fixture.headers,
+        body,
+    ) {
+        Ok(result) => {
+            response.valid = result.valid;
+            response.errors = result.errors;
+        }
+        Err(e) => response.errors.push(e.to_string()),
+    }
+    response
+}
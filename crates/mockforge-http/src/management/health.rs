@@ -177,6 +177,13 @@ pub(crate) async fn get_stats(State(state): State<ManagementState>) -> Json<Serv
     })
 }
 
+/// List live SSE/WebSocket connections
+pub(crate) async fn get_connections(
+    State(state): State<ManagementState>,
+) -> Json<crate::connection_tracker::ConnectionsSummary> {
+    Json(state.connections.summary())
+}
+
 /// Get server configuration
 pub(crate) async fn get_config(State(state): State<ManagementState>) -> Json<super::ServerConfig> {
     Json(super::ServerConfig {
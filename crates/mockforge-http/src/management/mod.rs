@@ -3,27 +3,37 @@
 /// Provides REST endpoints for controlling mocks, server configuration,
 /// and integration with developer tools (VS Code extension, CI/CD, etc.)
 mod ai_gen;
+mod bundle;
 mod chaos_admin;
+mod config_export;
 mod conformance;
+mod fixture_validation;
 mod health;
 mod import_export;
 mod migration;
 mod mocks;
 mod protocols;
 mod proxy;
+mod route_drift;
 mod rule_explanations;
 mod traffic_to_openapi;
+mod validation_report;
 
 // `ai_gen.rs` was split into four topic files under #656; the route
 // wiring below pulls handlers from each via these glob re-exports.
 pub use ai_gen::*;
+pub(crate) use bundle::export_bundle;
 pub use chaos_admin::*;
+pub(crate) use config_export::export_config_yaml;
 pub(crate) use conformance::{clear_conformance_violations, get_conformance_violations};
+pub(crate) use fixture_validation::validate_fixture;
 pub use health::*;
 pub use import_export::*;
 pub use proxy::{BodyTransformRequest, ProxyRuleRequest, ProxyRuleResponse};
+pub(crate) use route_drift::{clear_route_drift, get_route_drift};
 pub use rule_explanations::*;
 pub use traffic_to_openapi::*;
+pub(crate) use validation_report::{clear_validation_report, get_validation_report};
 
 use axum::{
     body::Body,
@@ -159,6 +169,9 @@ pub struct RequestMatchCriteria {
     /// Headers that must be present and match (case-insensitive header names)
     #[serde(skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub headers: std::collections::HashMap<String, String>,
+    /// Headers that must not be present (case-insensitive header names)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub header_absent: Vec<String>,
     /// Query parameters that must be present and match
     #[serde(skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub query_params: std::collections::HashMap<String, String>,
@@ -180,7 +193,7 @@ pub struct RequestMatchCriteria {
 ///
 /// This function implements comprehensive request matching including:
 /// - Method and path matching
-/// - Header matching (with regex support)
+/// - Header matching (with regex support) and header absence
 /// - Query parameter matching
 /// - Body pattern matching (exact, regex, JSONPath, XPath)
 /// - Custom matcher expressions
@@ -230,6 +243,13 @@ pub fn mock_matches_request(
             }
         }
 
+        // Check headers that must be missing
+        for key in &criteria.header_absent {
+            if headers.keys().any(|k| k.eq_ignore_ascii_case(key)) {
+                return false;
+            }
+        }
+
         // Check query parameters
         for (key, expected_value) in &criteria.query_params {
             if let Some(actual_value) = query_params.get(key) {
@@ -773,6 +793,12 @@ pub struct ManagementState {
     /// Conformance testing state
     #[cfg(feature = "conformance")]
     pub conformance_state: crate::handlers::conformance::ConformanceState,
+    /// Live SSE/WebSocket connections reported by `/connections`
+    pub connections: crate::connection_tracker::ConnectionTracker,
+    /// Route-chaos rules and fault counters reported by `/chaos/status`
+    pub route_chaos: Option<crate::route_chaos_runtime::RuntimeRouteChaosState>,
+    /// Directory of custom fixtures included in `/export/bundle`
+    pub fixtures_dir: std::path::PathBuf,
 }
 
 impl ManagementState {
@@ -827,6 +853,9 @@ impl ManagementState {
             }),
             #[cfg(feature = "conformance")]
             conformance_state: crate::handlers::conformance::ConformanceState::new(),
+            connections: crate::connection_tracker::global_connections().clone(),
+            route_chaos: None,
+            fixtures_dir: crate::fixtures_api::FixturesApiState::from_env().fixtures_dir,
         }
     }
 
@@ -863,6 +892,24 @@ impl ManagementState {
         self
     }
 
+    /// Report connections from this tracker instead of the process-wide one
+    pub fn with_connections(
+        mut self,
+        connections: crate::connection_tracker::ConnectionTracker,
+    ) -> Self {
+        self.connections = connections;
+        self
+    }
+
+    /// Add route-chaos state for the chaos status feed
+    pub fn with_route_chaos(
+        mut self,
+        route_chaos: crate::route_chaos_runtime::RuntimeRouteChaosState,
+    ) -> Self {
+        self.route_chaos = Some(route_chaos);
+        self
+    }
+
     /// Add proxy configuration to management state
     pub fn with_proxy_config(mut self, proxy_config: Arc<RwLock<ProxyConfig>>) -> Self {
         self.proxy_config = Some(proxy_config);
@@ -924,6 +971,12 @@ impl ManagementState {
         self.server_config = Some(server_config);
         self
     }
+
+    /// Set the custom fixtures directory (defaults to `MOCKFORGE_FIXTURES_DIR`)
+    pub fn with_fixtures_dir(mut self, fixtures_dir: impl Into<std::path::PathBuf>) -> Self {
+        self.fixtures_dir = fixtures_dir.into();
+        self
+    }
 }
 
 /// Build the management API router
@@ -932,24 +985,34 @@ pub fn management_router(state: ManagementState) -> Router {
         .route("/capabilities", get(get_capabilities))
         .route("/health", get(health_check))
         .route("/stats", get(get_stats))
+        .route("/connections", get(get_connections))
         .route("/config", get(get_config))
         .route("/config/validate", post(validate_config))
         .route("/config/bulk", post(bulk_update_config))
+        .route("/config/export.yaml", get(export_config_yaml))
         .route("/mocks", get(mocks::list_mocks))
         .route("/mocks", post(mocks::create_mock))
         .route("/mocks/{id}", get(mocks::get_mock))
         .route("/mocks/{id}", put(mocks::update_mock))
         .route("/mocks/{id}", delete(mocks::delete_mock))
         .route("/export", get(export_mocks))
+        .route("/export/bundle", get(export_bundle))
         .route("/import", post(import_mocks))
         .route("/spec", get(get_openapi_spec))
+        .route("/fixtures/validate", post(validate_fixture))
         // Issue #79 round 12 — server-side spec violation feed for the
         // new TUI "Conformance" screen. Backed by the bounded ring
         // buffer in `mockforge_foundation::conformance_violations` that
         // the OpenAPI router populates whenever
         // `validate_request_with_all` rejects an incoming request.
         .route("/conformance/violations", get(get_conformance_violations))
-        .route("/conformance/violations", delete(clear_conformance_violations));
+        .route("/conformance/violations", delete(clear_conformance_violations))
+        // Per-route aggregate of requests accepted with violations in Warn mode
+        .route("/validation-report", get(get_validation_report))
+        .route("/validation-report", delete(clear_validation_report))
+        // Per-route fields of served responses that drifted from the spec
+        .route("/drift/routes", get(get_route_drift))
+        .route("/drift/routes", delete(clear_route_drift));
 
     #[cfg(feature = "smtp")]
     let router = router
@@ -1043,6 +1106,7 @@ pub fn management_router(state: ManagementState) -> Router {
         .route("/mockai/rules/explanations", get(list_rule_explanations))
         .route("/mockai/rules/{id}/explanation", get(get_rule_explanation))
         .route("/chaos/config", get(get_chaos_config))
+        .route("/chaos/status", get(get_chaos_status))
         .route("/chaos/config", post(update_chaos_config))
         .route("/network/profiles", get(list_network_profiles))
         .route("/network/profile/apply", post(apply_network_profile));
@@ -1512,4 +1576,385 @@ mod tests {
 
         assert!(!mock_matches_request(&mock, "POST", "/xml", &headers, &query, Some(body)));
     }
+
+    #[tokio::test]
+    async fn dynamic_mock_selected_by_header_absence() {
+        let state = ManagementState::new(None, None, 3000);
+        {
+            let mut mocks = state.mocks.write().await;
+            for (id, status, header_absent, priority) in [
+                ("unauthorized", 401, vec!["Authorization".to_string()], Some(1)),
+                ("ok", 200, Vec::new(), None),
+            ] {
+                mocks.push(MockConfig {
+                    id: id.to_string(),
+                    name: String::new(),
+                    method: "GET".to_string(),
+                    path: "/me".to_string(),
+                    response: MockResponse {
+                        body: serde_json::json!({ "mock": id }),
+                        headers: None,
+                    },
+                    enabled: true,
+                    latency_ms: None,
+                    status_code: Some(status),
+                    request_match: Some(RequestMatchCriteria {
+                        header_absent,
+                        ..Default::default()
+                    }),
+                    priority,
+                    scenario: None,
+                    required_scenario_state: None,
+                    new_scenario_state: None,
+                });
+            }
+        }
+
+        let anonymous = Request::builder().uri("/me").body(Body::empty()).unwrap();
+        let res = serve_dynamic_mock(&state, anonymous).await.unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let authorized = Request::builder()
+            .uri("/me")
+            .header("authorization", "Bearer token")
+            .body(Body::empty())
+            .unwrap();
+        let res = serve_dynamic_mock(&state, authorized).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn chaos_status_reports_faulty_route() {
+        use crate::route_chaos_runtime::RuntimeRouteChaosState;
+        use mockforge_core::config::{
+            RouteConfig, RouteFaultInjectionConfig, RouteFaultType, RouteResponseConfig,
+        };
+        use tower::ServiceExt;
+
+        let route_chaos = RuntimeRouteChaosState::new(Vec::new());
+        route_chaos
+            .upsert(RouteConfig {
+                path: "/payments".to_string(),
+                method: "POST".to_string(),
+                request: None,
+                response: RouteResponseConfig {
+                    status: 200,
+                    body: None,
+                    ..Default::default()
+                },
+                fault_injection: Some(RouteFaultInjectionConfig {
+                    enabled: true,
+                    probability: 0.25,
+                    fault_types: vec![RouteFaultType::HttpError {
+                        status_code: 503,
+                        message: None,
+                    }],
+                    weights: None,
+                    request_matcher: None,
+                }),
+                latency: None,
+            })
+            .unwrap();
+        let state = ManagementState::new(None, None, 3000).with_route_chaos(route_chaos);
+
+        let response = management_router(state)
+            .oneshot(Request::builder().uri("/chaos/status").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let routes = status["routes"].as_array().unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0]["method"], "POST");
+        assert_eq!(routes[0]["path"], "/payments");
+        assert_eq!(routes[0]["source"], "runtime");
+        assert_eq!(routes[0]["fault_probability"], 0.25);
+        assert_eq!(routes[0]["faults_injected"], 0);
+        assert_eq!(status["faults_injected_total"], 0);
+    }
+
+    #[tokio::test]
+    async fn config_export_yaml_round_trips() {
+        use mockforge_core::config::ServerConfig;
+        use tower::ServiceExt;
+
+        let config: ServerConfig = serde_yaml::from_str(
+            r#"
+routes:
+  - path: /users
+    method: GET
+    response: { status: 200 }
+  - path: /users
+    method: POST
+    response: { status: 201 }
+"#,
+        )
+        .unwrap();
+        let state = ManagementState::new(None, None, 3000)
+            .with_server_config(Arc::new(RwLock::new(config)));
+
+        let response = management_router(state)
+            .oneshot(Request::builder().uri("/config/export.yaml").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/yaml");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let exported: ServerConfig = serde_yaml::from_slice(&body).unwrap();
+        assert_eq!(exported.routes.len(), 2);
+        assert_eq!(exported.routes[1].response.status, 201);
+    }
+
+    #[tokio::test]
+    async fn config_export_yaml_requires_config() {
+        use tower::ServiceExt;
+
+        let response = management_router(ManagementState::new(None, None, 3000))
+            .oneshot(Request::builder().uri("/config/export.yaml").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn export_bundle_contains_config_spec_and_fixtures() {
+        use mockforge_core::config::ServerConfig;
+        use std::io::Read;
+        use tower::ServiceExt;
+
+        let fixtures = tempfile::tempdir().unwrap();
+        std::fs::write(
+            fixtures.path().join("get-user.json"),
+            r#"{"method":"GET","path":"/users/{id}","response":{"status":200,"body":{"id":1}}}"#,
+        )
+        .unwrap();
+        std::fs::write(fixtures.path().join("notes.txt"), "not a fixture").unwrap();
+
+        let spec = OpenApiSpec::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Users", "version": "1.0.0" },
+            "paths": {}
+        }))
+        .unwrap();
+        let mut config = ServerConfig::default();
+        config.http.openapi_spec = Some("/home/dev/specs/users.yaml".to_string());
+        let state = ManagementState::new(Some(Arc::new(spec)), None, 3000)
+            .with_server_config(Arc::new(RwLock::new(config)))
+            .with_fixtures_dir(fixtures.path());
+
+        let response = management_router(state)
+            .oneshot(Request::builder().uri("/export/bundle").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/zip");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut bundle = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        let mut names: Vec<&str> = bundle.file_names().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "README.md",
+                "fixtures/get-user.json",
+                "mockforge.yaml",
+                "openapi.json"
+            ]
+        );
+
+        let mut yaml = String::new();
+        bundle.by_name("mockforge.yaml").unwrap().read_to_string(&mut yaml).unwrap();
+        let exported: ServerConfig = serde_yaml::from_str(&yaml).unwrap();
+        // The bundled config points at the bundled spec
+        assert_eq!(exported.http.openapi_spec.as_deref(), Some("openapi.json"));
+
+        let mut fixture = String::new();
+        bundle
+            .by_name("fixtures/get-user.json")
+            .unwrap()
+            .read_to_string(&mut fixture)
+            .unwrap();
+        assert!(fixture.contains("/users/{id}"));
+    }
+
+    #[tokio::test]
+    async fn fixtures_validate_checks_body_against_response_schema() {
+        use tower::ServiceExt;
+
+        let spec = OpenApiSpec::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Users", "version": "1.0.0" },
+            "paths": {
+                "/users/{id}": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "A user",
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "object",
+                                            "required": ["id", "name"],
+                                            "properties": {
+                                                "id": { "type": "integer" },
+                                                "name": { "type": "string" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let app = management_router(ManagementState::new(Some(Arc::new(spec)), None, 3000));
+        let validate = |fixture: serde_json::Value| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::builder()
+                            .method("POST")
+                            .uri("/fixtures/validate")
+                            .header("content-type", "application/json")
+                            .body(Body::from(fixture.to_string()))
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+            }
+        };
+
+        let conforming = validate(serde_json::json!({
+            "method": "GET",
+            "path": "/users/42",
+            "status": 200,
+            "response": { "id": 42, "name": "Ada" }
+        }))
+        .await;
+        assert_eq!(conforming["valid"], true);
+        assert_eq!(conforming["operation_path"], "/users/{id}");
+        assert_eq!(conforming["errors"], serde_json::json!([]));
+
+        // Nested format, wrong type for `id` and `name` missing
+        let non_conforming = validate(serde_json::json!({
+            "request": { "method": "GET", "path": "/users/{id}" },
+            "response": { "status": 200, "body": { "id": "42" } }
+        }))
+        .await;
+        assert_eq!(non_conforming["valid"], false);
+        let errors = non_conforming["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| e.as_str().unwrap().contains("\"name\"")));
+    }
+
+    #[tokio::test]
+    async fn drift_routes_flags_responses_that_drift_from_the_spec() {
+        use crate::middleware::drift_tracking::{
+            drift_tracking_middleware_with_extensions, DriftTrackingState,
+        };
+        use mockforge_contracts::consumer_contracts::{
+            ConsumerBreakingChangeDetector, UsageRecorder,
+        };
+        use mockforge_core::contract_drift::{DriftBudgetConfig, DriftBudgetEngine};
+        use mockforge_core::incidents::{IncidentManager, IncidentStore};
+        use tower::ServiceExt;
+
+        let spec = OpenApiSpec::from_json(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": { "title": "Orders", "version": "1.0.0" },
+            "paths": {
+                "/drift-orders/{id}": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "description": "An order",
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/Order" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Order": {
+                        "type": "object",
+                        "required": ["id", "total"],
+                        "properties": {
+                            "id": { "type": "integer" },
+                            "total": { "type": "number" }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let usage_recorder = Arc::new(UsageRecorder::default());
+        let state = DriftTrackingState {
+            diff_analyzer: None,
+            spec: Some(Arc::new(spec)),
+            drift_engine: Arc::new(DriftBudgetEngine::new(DriftBudgetConfig::default())),
+            incident_manager: Arc::new(IncidentManager::new(Arc::new(IncidentStore::default()))),
+            usage_recorder: usage_recorder.clone(),
+            consumer_detector: Arc::new(ConsumerBreakingChangeDetector::new(usage_recorder)),
+            enabled: true,
+        };
+        // The mock serves a string id, drops `total` and adds `currency`
+        let app = Router::new()
+            .route(
+                "/drift-orders/{id}",
+                get(|| async { axum::Json(serde_json::json!({ "id": "42", "currency": "EUR" })) }),
+            )
+            .layer(axum::middleware::from_fn(crate::middleware::buffer_response_middleware))
+            .layer(axum::middleware::from_fn(
+                move |mut req: Request<Body>, next: axum::middleware::Next| {
+                    req.extensions_mut().insert(state.clone());
+                    drift_tracking_middleware_with_extensions(req, next)
+                },
+            ));
+        for _ in 0..2 {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri("/drift-orders/42").body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = management_router(ManagementState::new(None, None, 3000))
+            .oneshot(Request::builder().uri("/drift/routes").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let report: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let route = report["routes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|route| route["path"] == "/drift-orders/{id}")
+            .expect("drifting route is flagged");
+        assert_eq!(route["method"], "GET");
+        assert_eq!(route["drifted"], 2);
+        assert_eq!(route["added"], serde_json::json!(["$.currency"]));
+        assert_eq!(route["removed"], serde_json::json!(["$.total"]));
+        assert_eq!(
+            route["type_changed"],
+            serde_json::json!([{ "field": "$.id", "expected": "integer", "found": "string" }])
+        );
+    }
 }
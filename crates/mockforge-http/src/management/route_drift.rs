@@ -0,0 +1,33 @@
+//! Management endpoint for the per-route response drift report.
+//!
+//! The drift tracking middleware compares each JSON response with the
+//! schema the loaded spec declares for it and aggregates the differences
+//! per route in `mockforge_foundation::route_drift`. This module exposes:
+//!
+//!   - `GET    /__mockforge/api/drift/routes` → drifted fields per route
+//!   - `DELETE /__mockforge/api/drift/routes` → reset the report
+
+use axum::Json;
+use mockforge_foundation::route_drift;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct RouteDriftResponse {
+    /// Responses compared against a schema across all routes.
+    pub checked: u64,
+    /// Routes whose responses drifted, most drifted first.
+    pub routes: Vec<route_drift::RouteDriftReport>,
+}
+
+pub(crate) async fn get_route_drift() -> Json<RouteDriftResponse> {
+    Json(RouteDriftResponse {
+        checked: route_drift::total_checked(),
+        routes: route_drift::snapshot(),
+    })
+}
+
+pub(crate) async fn clear_route_drift() -> Json<serde_json::Value> {
+    let before = route_drift::snapshot().len();
+    route_drift::clear();
+    Json(serde_json::json!({ "cleared": before }))
+}
@@ -0,0 +1,32 @@
+//! Management endpoint for the per-route validation warning report.
+//!
+//! In `Warn` validation mode non-conforming requests are served anyway;
+//! the OpenAPI router aggregates their violations per route in
+//! `mockforge_foundation::validation_report`. This module exposes:
+//!
+//!   - `GET    /__mockforge/api/validation-report` → counts + samples per route
+//!   - `DELETE /__mockforge/api/validation-report` → reset the report
+
+use axum::Json;
+use mockforge_foundation::validation_report;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ValidationReportResponse {
+    /// Warned requests across all routes.
+    pub total: u64,
+    /// Per-route aggregates, most violations first.
+    pub routes: Vec<validation_report::RouteValidationReport>,
+}
+
+pub(crate) async fn get_validation_report() -> Json<ValidationReportResponse> {
+    let routes = validation_report::snapshot();
+    let total = routes.iter().map(|r| r.count).sum();
+    Json(ValidationReportResponse { total, routes })
+}
+
+pub(crate) async fn clear_validation_report() -> Json<serde_json::Value> {
+    let before = validation_report::total();
+    validation_report::clear();
+    Json(serde_json::json!({ "cleared": before }))
+}
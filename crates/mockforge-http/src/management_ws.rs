@@ -2,9 +2,12 @@
 ///
 /// Provides real-time notifications when mocks are created, updated, or deleted.
 /// Used by developer tools like VS Code extension for live synchronization.
+/// Once `http.streaming_limit.max_websocket` sockets are open, new clients are
+/// refused the upgrade with 503 Service Unavailable.
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
-use axum::response::IntoResponse;
+use axum::http::Extensions;
+use axum::response::Response;
 use axum::routing::get;
 use axum::Router;
 use futures::stream::StreamExt;
@@ -13,6 +16,10 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use tracing::*;
 
+use crate::connection_tracker::{
+    self, global_connections, ConnectionGuard, ConnectionKind, ConnectionTracker,
+};
+
 /// Default broadcast channel capacity for WebSocket mock events
 const DEFAULT_WS_BROADCAST_CAPACITY: usize = 100;
 
@@ -224,6 +231,8 @@ impl MockEvent {
 pub struct WsManagementState {
     /// Broadcast channel for sending events to all connected clients
     pub tx: broadcast::Sender<MockEvent>,
+    /// Tracker that lists (and limits) connected clients
+    pub connections: ConnectionTracker,
 }
 
 impl WsManagementState {
@@ -234,7 +243,16 @@ impl WsManagementState {
     pub fn new() -> Self {
         let capacity = get_ws_broadcast_capacity();
         let (tx, _) = broadcast::channel(capacity);
-        Self { tx }
+        Self {
+            tx,
+            connections: global_connections().clone(),
+        }
+    }
+
+    /// Track (and limit) clients in `connections` instead of the global tracker
+    pub fn with_connections(mut self, connections: ConnectionTracker) -> Self {
+        self.connections = connections;
+        self
     }
 
     /// Broadcast an event to all connected clients
@@ -256,12 +274,23 @@ impl Default for WsManagementState {
 async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<WsManagementState>,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+    extensions: Extensions,
+) -> Response {
+    let remote_addr = connection_tracker::remote_addr(&extensions);
+    let Some(guard) =
+        state
+            .connections
+            .try_register(ConnectionKind::WebSocket, remote_addr, "management")
+    else {
+        debug!("Rejecting WebSocket client: connection limit reached");
+        return connection_tracker::limit_reached_response(ConnectionKind::WebSocket);
+    };
+    ws.on_upgrade(move |socket| handle_socket(socket, state, guard))
 }
 
-/// Handle a WebSocket connection
-async fn handle_socket(socket: WebSocket, state: WsManagementState) {
+/// Handle a WebSocket connection; it stays listed in the connection tracker
+/// until this returns and `_connection` is dropped
+async fn handle_socket(socket: WebSocket, state: WsManagementState, _connection: ConnectionGuard) {
     let (mut sender, mut receiver) = socket.split();
 
     // Subscribe to broadcast channel
@@ -379,4 +408,31 @@ mod tests {
         let _router = ws_management_router(state);
         // Router should be created successfully
     }
+
+    #[tokio::test]
+    async fn test_clients_over_the_limit_are_refused() {
+        use mockforge_core::config::HttpStreamingLimitConfig;
+        use tokio_tungstenite::tungstenite::Error as ClientError;
+
+        let tracker = ConnectionTracker::new();
+        tracker.set_limits(HttpStreamingLimitConfig {
+            max_sse: None,
+            max_websocket: Some(1),
+        });
+        let app = ws_management_router(WsManagementState::new().with_connections(tracker));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        // The first client fills the only slot and is greeted as usual
+        let (mut first, _) = tokio_tungstenite::connect_async(&url).await.unwrap();
+        let greeting = first.next().await.unwrap().unwrap();
+        assert!(greeting.into_text().unwrap().contains("connected"));
+
+        // The next one is refused the upgrade
+        match tokio_tungstenite::connect_async(&url).await {
+            Err(ClientError::Http(response)) => assert_eq!(response.status(), 503),
+            other => panic!("expected a 503, got {other:?}"),
+        }
+    }
 }
@@ -137,6 +137,9 @@ pub async fn collect_http_metrics(
         None,
     );
 
+    // Hit count and last-hit time per route template for the coverage report
+    mockforge_foundation::route_hits::record(&method, &path);
+
     // Bump TPS / RPS counters for the dashboard rate sampler.
     mockforge_foundation::rate_counters::record_response(status_code);
 
@@ -0,0 +1,138 @@
+//! Size-capped body capture for analysis middleware
+//!
+//! Contract diff, drift tracking and response buffering need a copy of a
+//! body, but buffering it whole lets one large payload exhaust memory.
+//! [`capture_prefix`] reads at most `limit` bytes into the copy; anything
+//! beyond is streamed through untouched, so the handler (or client) still
+//! sees the full body while the captured copy is truncated.
+
+use axum::body::{Body, Bytes};
+use futures::{stream, StreamExt};
+use serde_json::Value;
+
+/// Appended to the text of a truncated capture
+pub const TRUNCATION_MARKER: &str = "...[truncated]";
+
+/// A captured copy of (the start of) a body
+#[derive(Debug, Clone, Default)]
+pub struct CapturedBody {
+    /// The captured bytes, at most the capture limit
+    pub bytes: Bytes,
+    /// Whether the body was longer than the limit (or failed mid-stream)
+    pub truncated: bool,
+}
+
+impl CapturedBody {
+    /// The body as a JSON value for diffing: parsed JSON when complete, or
+    /// the captured text followed by [`TRUNCATION_MARKER`] when truncated.
+    /// `None` for empty or non-JSON complete bodies.
+    pub fn to_value(&self) -> Option<Value> {
+        if self.truncated {
+            Some(Value::String(format!(
+                "{}{}",
+                String::from_utf8_lossy(&self.bytes),
+                TRUNCATION_MARKER
+            )))
+        } else if self.bytes.is_empty() {
+            None
+        } else {
+            serde_json::from_slice(&self.bytes).ok()
+        }
+    }
+}
+
+/// Capture up to `limit` bytes of `body`, returning the copy and a body that
+/// still yields every byte of the original
+pub async fn capture_prefix(body: Body, limit: usize) -> (CapturedBody, Body) {
+    let mut data = body.into_data_stream();
+    let mut chunks: Vec<Bytes> = Vec::new();
+    let mut captured_len = 0;
+
+    loop {
+        match data.next().await {
+            None => {
+                let bytes = concat(&chunks);
+                let captured = CapturedBody {
+                    bytes: bytes.clone(),
+                    truncated: false,
+                };
+                return (captured, Body::from(bytes));
+            }
+            Some(Ok(chunk)) if captured_len + chunk.len() <= limit => {
+                captured_len += chunk.len();
+                chunks.push(chunk);
+            }
+            Some(Ok(chunk)) => {
+                // Over the limit: keep what fits and stream the rest through
+                let mut prefix = chunks.clone();
+                prefix.push(chunk.slice(..limit - captured_len));
+                let captured = CapturedBody {
+                    bytes: concat(&prefix),
+                    truncated: true,
+                };
+                chunks.push(chunk);
+                let head = stream::iter(chunks.into_iter().map(Ok::<_, axum::Error>));
+                return (captured, Body::from_stream(head.chain(data)));
+            }
+            Some(Err(e)) => {
+                tracing::debug!(error = %e, "Body failed mid-stream during capture");
+                let captured = CapturedBody {
+                    bytes: concat(&chunks),
+                    truncated: true,
+                };
+                let head = stream::iter(chunks.into_iter().map(Ok));
+                return (captured, Body::from_stream(head.chain(stream::once(async { Err(e) }))));
+            }
+        }
+    }
+}
+
+fn concat(chunks: &[Bytes]) -> Bytes {
+    match chunks {
+        [] => Bytes::new(),
+        [single] => single.clone(),
+        _ => Bytes::from(chunks.concat()),
+    }
+}
+
+/// Read a capture limit in MiB from `env_var`, defaulting to 10 MiB
+pub(crate) fn capture_limit_from_env(env_var: &str) -> usize {
+    const DEFAULT_MB: usize = 10;
+    std::env::var(env_var)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MB)
+        .saturating_mul(1024 * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    fn chunked(chunks: &[&'static [u8]]) -> Body {
+        Body::from_stream(stream::iter(
+            chunks
+                .iter()
+                .map(|c| Ok::<_, axum::Error>(Bytes::from_static(c)))
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    #[tokio::test]
+    async fn body_within_limit_is_captured_whole() {
+        let (captured, body) = capture_prefix(chunked(&[b"{\"a\":", b"1}"]), 16).await;
+        assert!(!captured.truncated);
+        assert_eq!(captured.to_value(), Some(serde_json::json!({"a": 1})));
+        assert_eq!(to_bytes(body, usize::MAX).await.unwrap(), "{\"a\":1}");
+    }
+
+    #[tokio::test]
+    async fn body_over_limit_is_truncated_but_forwarded_whole() {
+        let (captured, body) = capture_prefix(chunked(&[b"abcd", b"efgh", b"ijkl"]), 6).await;
+        assert!(captured.truncated);
+        assert_eq!(captured.bytes, "abcdef");
+        assert_eq!(captured.to_value(), Some(Value::String(format!("abcdef{TRUNCATION_MARKER}"))));
+        assert_eq!(to_bytes(body, usize::MAX).await.unwrap(), "abcdefghijkl");
+    }
+}
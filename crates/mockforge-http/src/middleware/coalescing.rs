@@ -0,0 +1,367 @@
+//! Request coalescing (single-flight) middleware.
+//!
+//! Simulates a caching reverse proxy that collapses concurrent cache misses:
+//! while a request is in flight, identical requests (`http.coalescing`) wait
+//! for it and are answered with a copy of its response, so the handler runs
+//! once per burst. Requests are identical when their method, path, query,
+//! body and configured `key_headers` match. Nothing is cached afterwards —
+//! the next request after the flight completes runs the handler again.
+//!
+//! Long-lived exchanges are never coalesced: upgrade requests (WebSocket),
+//! requests accepting `text/event-stream`, and responses whose body has no
+//! known length (SSE, NDJSON, chunked streams) pass straight through, and
+//! anyone who joined such a flight runs the request on its own. Request
+//! bodies over 16 MiB are passed through as well.
+
+use super::body_capture::capture_prefix;
+use axum::{
+    body::{to_bytes, Body, Bytes, HttpBody},
+    extract::State,
+    http::{
+        header::{ACCEPT, CONNECTION, CONTENT_LENGTH, UPGRADE},
+        HeaderMap, Request, StatusCode, Version,
+    },
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use mockforge_core::config::HttpCoalescingConfig;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+use tracing::debug;
+
+/// Requests with larger bodies are passed through without coalescing
+const MAX_COALESCE_BODY: usize = 16 * 1024 * 1024;
+
+/// A response buffered so every waiter can get its own copy
+struct SharedResponse {
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl SharedResponse {
+    fn to_response(&self) -> Response {
+        let mut response = Response::new(Body::from(self.body.clone()));
+        *response.status_mut() = self.status;
+        *response.version_mut() = self.version;
+        *response.headers_mut() = self.headers.clone();
+        response
+    }
+}
+
+/// Resolves to the leading request's buffered response, or `None` when it
+/// cannot be shared (a streaming response, or the leader failed or was
+/// cancelled) and each waiter has to run the request itself
+type Flight = Shared<BoxFuture<'static, Option<Arc<SharedResponse>>>>;
+
+type FlightRegistry = Arc<Mutex<HashMap<String, Flight>>>;
+
+/// Removes a flight from the registry once its leading request is done with
+/// it, however that ends, so requests arriving afterwards start a fresh one
+struct FlightGuard {
+    registry: FlightRegistry,
+    key: String,
+}
+
+impl Drop for FlightGuard {
+    fn drop(&mut self) {
+        self.registry.lock().unwrap_or_else(|p| p.into_inner()).remove(&self.key);
+    }
+}
+
+/// Upgrades (WebSocket) and event streams are long-lived; each has to reach
+/// the handler on its own
+fn is_streaming_request(headers: &HeaderMap) -> bool {
+    let mentions = |name, token: &str| {
+        headers
+            .get_all(name)
+            .iter()
+            .any(|v| v.to_str().is_ok_and(|v| v.to_ascii_lowercase().contains(token)))
+    };
+    headers.contains_key(UPGRADE)
+        || mentions(CONNECTION, "upgrade")
+        || mentions(ACCEPT, "text/event-stream")
+}
+
+/// Shared state for [`coalescing_middleware`]. Cheap to clone (Arc).
+#[derive(Clone)]
+pub struct CoalescingState {
+    config: Arc<HttpCoalescingConfig>,
+    in_flight: FlightRegistry,
+}
+
+impl CoalescingState {
+    /// Create coalescing state from config
+    pub fn new(config: HttpCoalescingConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn coalesces(&self, method: &str) -> bool {
+        self.config.methods.iter().any(|m| m.eq_ignore_ascii_case(method))
+    }
+
+    /// Fingerprint of the parts of a request that decide its response
+    fn fingerprint(&self, parts: &axum::http::request::Parts, body: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(parts.method.as_str());
+        hasher.update([0]);
+        hasher.update(parts.uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/"));
+        for name in &self.config.key_headers {
+            hasher.update([0]);
+            hasher.update(name.to_ascii_lowercase());
+            for value in parts.headers.get_all(name.as_str()) {
+                hasher.update([0]);
+                hasher.update(value.as_bytes());
+            }
+        }
+        hasher.update([0]);
+        hasher.update(body);
+        hex::encode(hasher.finalize())
+    }
+}
+
+/// Middleware: join an identical in-flight request if there is one,
+/// otherwise run the request and share its response with anyone who joins
+pub async fn coalescing_middleware(
+    State(state): State<CoalescingState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !state.config.enabled
+        || !state.coalesces(req.method().as_str())
+        || is_streaming_request(req.headers())
+    {
+        return next.run(req).await;
+    }
+
+    // A body declared over the limit is never buffered; anything else is
+    // captured up to the limit and reaches the handler whole either way
+    let (parts, body) = req.into_parts();
+    let declared_len = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_len.is_some_and(|len| len > MAX_COALESCE_BODY) {
+        debug!("Request body too large to coalesce");
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+    let (captured, body) = capture_prefix(body, MAX_COALESCE_BODY).await;
+    if captured.truncated {
+        debug!("Request body too large to coalesce");
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+    let key = state.fingerprint(&parts, &captured.bytes);
+    let req = Request::from_parts(parts, body);
+
+    let leader = {
+        let mut in_flight = state.in_flight.lock().unwrap_or_else(|p| p.into_inner());
+        match in_flight.get(&key) {
+            Some(flight) => Err(flight.clone()),
+            None => {
+                let (tx, rx) = oneshot::channel();
+                let flight = async move { rx.await.ok().flatten() }.boxed().shared();
+                in_flight.insert(key.clone(), flight);
+                Ok(tx)
+            }
+        }
+    };
+    let tx = match leader {
+        Ok(tx) => tx,
+        Err(flight) => {
+            debug!(key = %key, "Coalescing request into in-flight request");
+            return match flight.await {
+                Some(shared) => shared.to_response(),
+                None => next.run(req).await,
+            };
+        }
+    };
+
+    let guard = FlightGuard {
+        registry: state.in_flight.clone(),
+        key,
+    };
+    let response = next.run(req).await;
+    // A body of unknown length may never end; hand it to this client as is
+    if response.body().size_hint().exact().is_none() {
+        drop(guard);
+        let _ = tx.send(None);
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX).await;
+    drop(guard);
+    match body {
+        Ok(body) => {
+            let shared = Arc::new(SharedResponse {
+                status: parts.status,
+                version: parts.version,
+                headers: parts.headers,
+                body,
+            });
+            let _ = tx.send(Some(shared.clone()));
+            shared.to_response()
+        }
+        Err(e) => {
+            debug!(error = %e, "Failed to buffer coalesced response");
+            let _ = tx.send(None);
+            StatusCode::BAD_GATEWAY.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use tower::ServiceExt;
+
+    use futures::StreamExt;
+
+    fn app(calls: Arc<AtomicUsize>) -> Router {
+        let state = CoalescingState::new(HttpCoalescingConfig {
+            enabled: true,
+            methods: vec!["GET".to_string()],
+            key_headers: vec!["x-tenant".to_string()],
+        });
+        Router::new()
+            .route(
+                "/slow",
+                get(move || {
+                    let calls = calls.clone();
+                    async move {
+                        let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        format!("computed #{n}")
+                    }
+                }),
+            )
+            // An event stream that sends one event and then stays open
+            .route(
+                "/events",
+                get(|| async {
+                    let first = futures::stream::once(async {
+                        Ok::<_, std::io::Error>(Bytes::from("data: hello\n\n"))
+                    });
+                    Body::from_stream(first.chain(futures::stream::pending()))
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(state, coalescing_middleware))
+    }
+
+    async fn fetch(app: Router, tenant: &str) -> String {
+        let req = Request::builder()
+            .uri("/slow")
+            .header("x-tenant", tenant)
+            .body(Body::empty())
+            .unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn concurrent_identical_requests_run_handler_once() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone());
+
+        let bodies = futures::future::join_all((0..10).map(|_| fetch(app.clone(), "acme"))).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(bodies.iter().all(|b| b == "computed #1"), "{bodies:?}");
+
+        // The flight is over; the next request computes a fresh response
+        assert_eq!(fetch(app, "acme").await, "computed #2");
+    }
+
+    #[tokio::test]
+    async fn requests_differing_in_key_header_are_not_coalesced() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone());
+
+        futures::future::join(fetch(app.clone(), "acme"), fetch(app, "globex")).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn upgrade_and_event_stream_requests_are_not_coalesced() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone());
+        let send = |header: (&'static str, &'static str)| {
+            let req = Request::builder()
+                .uri("/slow")
+                .header(header.0, header.1)
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(req)
+        };
+
+        let upgrades = [("upgrade", "websocket"), ("connection", "Upgrade")];
+        futures::future::join_all(upgrades.into_iter().map(send)).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let streams = [
+            ("accept", "text/event-stream"),
+            ("accept", "text/event-stream"),
+        ];
+        futures::future::join_all(streams.into_iter().map(send)).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn streaming_responses_are_passed_through() {
+        let app = app(Arc::new(AtomicUsize::new(0)));
+        let open = |app: Router| async move {
+            let req = Request::builder().uri("/events").body(Body::empty()).unwrap();
+            let res = app.oneshot(req).await.unwrap();
+            let mut body = res.into_body().into_data_stream();
+            body.next().await.unwrap().unwrap()
+        };
+
+        // Both clients get the first event even though the stream never ends
+        let events = tokio::time::timeout(
+            Duration::from_secs(5),
+            futures::future::join(open(app.clone()), open(app)),
+        )
+        .await
+        .expect("streaming response should not be buffered");
+        assert_eq!(events.0, "data: hello\n\n");
+        assert_eq!(events.1, "data: hello\n\n");
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_are_passed_through() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(calls.clone());
+        let payload = Bytes::from(vec![b'x'; MAX_COALESCE_BODY + 1]);
+        let send = |req: Request<Body>| app.clone().oneshot(req);
+        let streamed = Request::builder()
+            .uri("/slow")
+            .body(Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(
+                payload.clone(),
+            )])))
+            .unwrap();
+        let declared = Request::builder()
+            .uri("/slow")
+            .header(CONTENT_LENGTH, payload.len())
+            .body(Body::from(payload.clone()))
+            .unwrap();
+
+        let (streamed, declared) = futures::future::join(send(streamed), send(declared)).await;
+        assert_eq!(streamed.unwrap().status(), StatusCode::OK);
+        assert_eq!(declared.unwrap().status(), StatusCode::OK);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
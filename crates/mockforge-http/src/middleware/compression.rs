@@ -0,0 +1,130 @@
+//! Response compression.
+//!
+//! Compresses responses per `performance.compression`: bodies of at least
+//! `min_size` bytes whose Content-Type is listed in `content_types`, with the
+//! configured `algorithm` and `level`, for clients that accept it. A custom
+//! route's `response.compress` overrides that decision through the
+//! [`RouteCompression`] response extension.
+
+use axum::body::HttpBody;
+use axum::http::{header, Response};
+use mockforge_core::config::CompressionConfig;
+use std::sync::Arc;
+use tower_http::compression::predicate::SizeAbove;
+use tower_http::compression::{CompressionLayer, CompressionLevel, Predicate};
+
+/// Response extension carrying a route's `compress` override
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteCompression(pub bool);
+
+/// Decides per response whether [`compression_layer`] compresses it
+#[derive(Debug, Clone)]
+pub struct CompressionPredicate {
+    enabled: bool,
+    min_size: SizeAbove,
+    content_types: Arc<Vec<String>>,
+}
+
+impl CompressionPredicate {
+    fn content_type_matches(&self, content_type: Option<&str>) -> bool {
+        if self.content_types.is_empty() {
+            return true;
+        }
+        let Some(content_type) = content_type else {
+            return false;
+        };
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        self.content_types.iter().any(|t| t.eq_ignore_ascii_case(essence))
+    }
+}
+
+impl Predicate for CompressionPredicate {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        if let Some(RouteCompression(compress)) = response.extensions().get() {
+            return *compress;
+        }
+        self.enabled
+            && self.min_size.should_compress(response)
+            && self.content_type_matches(
+                response.headers().get(header::CONTENT_TYPE).and_then(|v| v.to_str().ok()),
+            )
+    }
+}
+
+/// Build the compression layer for `config`. Routes with `compress: true`
+/// are compressed through it even when `config.enabled` is off.
+pub fn compression_layer(config: &CompressionConfig) -> CompressionLayer<CompressionPredicate> {
+    let algorithm = config.algorithm.to_ascii_lowercase();
+    let predicate = CompressionPredicate {
+        enabled: config.enabled,
+        min_size: SizeAbove::new(u16::try_from(config.min_size).unwrap_or(u16::MAX)),
+        content_types: Arc::new(config.content_types.clone()),
+    };
+    CompressionLayer::new()
+        .gzip(algorithm == "gzip")
+        .deflate(algorithm == "deflate")
+        .br(algorithm == "br" || algorithm == "brotli")
+        .zstd(algorithm == "zstd")
+        .quality(CompressionLevel::Precise(i32::try_from(config.level).unwrap_or(i32::MAX)))
+        .compress_when(predicate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, response::IntoResponse, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(enabled: bool) -> Router {
+        let body = "x".repeat(2048);
+        let opted_in = body.clone();
+        Router::new()
+            .route(
+                "/json",
+                get(move || async move { ([(header::CONTENT_TYPE, "application/json")], body) }),
+            )
+            .route(
+                "/forced",
+                get(move || async move {
+                    let mut response =
+                        ([(header::CONTENT_TYPE, "application/octet-stream")], opted_in)
+                            .into_response();
+                    response.extensions_mut().insert(RouteCompression(true));
+                    response
+                }),
+            )
+            .route("/small", get(|| async { ([(header::CONTENT_TYPE, "application/json")], "{}") }))
+            .layer(compression_layer(&CompressionConfig {
+                enabled,
+                ..CompressionConfig::default()
+            }))
+    }
+
+    async fn content_encoding(app: Router, path: &str) -> Option<String> {
+        let req = Request::builder()
+            .uri(path)
+            .header(header::ACCEPT_ENCODING, "gzip, br")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(req).await.unwrap();
+        response
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn compresses_listed_content_types_above_min_size() {
+        assert_eq!(content_encoding(app(true), "/json").await.as_deref(), Some("gzip"));
+        assert_eq!(content_encoding(app(true), "/small").await, None);
+        assert_eq!(content_encoding(app(false), "/json").await, None);
+    }
+
+    #[tokio::test]
+    async fn route_override_compresses_when_globally_disabled() {
+        assert_eq!(content_encoding(app(false), "/forced").await.as_deref(), Some("gzip"));
+    }
+}
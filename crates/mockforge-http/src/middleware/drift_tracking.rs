@@ -6,6 +6,7 @@
 // Uses DriftBudgetEngine + ContractDiffAnalyzer which stay in core.
 #![allow(deprecated)]
 
+use super::body_capture::{capture_limit_from_env, capture_prefix};
 use axum::{body::Body, extract::Request, http::Response, middleware::Next};
 use mockforge_contracts::consumer_contracts::{ConsumerBreakingChangeDetector, UsageRecorder};
 use mockforge_core::{
@@ -15,24 +16,19 @@ use mockforge_core::{
     openapi::OpenApiSpec,
 };
 use mockforge_foundation::contract_drift_types::DriftResult;
+use mockforge_foundation::route_drift;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, warn};
 
-/// Maximum request body size to buffer for drift tracking.
+/// Maximum request body size to capture for drift tracking.
 ///
-/// Issue #79 — see `contract_diff_middleware`. Same root cause: the
-/// old hard-coded 1 MiB cap, paired with the over-cap branch
-/// substituting `Body::empty()`, broke downstream handlers on large
-/// chunked uploads. Configurable via `MOCKFORGE_DRIFT_MAX_BODY_MB`.
+/// Issue #79 — see `contract_diff_middleware`. Bodies are forwarded whole
+/// and only the first part is captured for analysis. Configurable via
+/// `MOCKFORGE_DRIFT_MAX_BODY_MB`.
 fn max_drift_body_size() -> usize {
-    const DEFAULT_MB: usize = 10;
-    std::env::var("MOCKFORGE_DRIFT_MAX_BODY_MB")
-        .ok()
-        .and_then(|v| v.parse::<usize>().ok())
-        .unwrap_or(DEFAULT_MB)
-        .saturating_mul(1024 * 1024)
+    capture_limit_from_env("MOCKFORGE_DRIFT_MAX_BODY_MB")
 }
 
 /// State for drift tracking middleware
@@ -80,61 +76,26 @@ pub async fn drift_tracking_middleware_with_extensions(
     let path = req.uri().path().to_string();
     let max_body = max_drift_body_size();
 
-    // Issue #79 — pre-check Content-Length so we skip capture cleanly
-    // for over-cap requests instead of consuming the body and then
-    // substituting `Body::empty()` (which broke downstream handlers).
-    let content_length = req
-        .headers()
-        .get(http::header::CONTENT_LENGTH)
-        .and_then(|v| v.to_str().ok())
-        .and_then(|s| s.parse::<usize>().ok());
-    if let Some(len) = content_length {
-        if len > max_body {
-            debug!(
-                "drift_tracking: skipping capture for {} {} — content-length {} > cap {}",
-                method, path, len, max_body
-            );
-            return next.run(req).await;
-        }
-    }
-
     // Extract consumer identifier and headers from request
     let consumer_id = extract_consumer_id(&req);
 
     // Extract headers for capture
     let captured_headers = extract_headers_for_capture(&req);
 
-    // Buffer the request body so we can capture it and still forward it
+    // Keep a copy of at most `max_body` bytes of the body; the handler
+    // still receives all of it
     let (parts, body) = req.into_parts();
-    let body_bytes = match axum::body::to_bytes(body, max_body).await {
-        Ok(b) => b,
-        Err(_) => {
-            // Chunked over-cap body — body partially consumed and we
-            // cannot rebuild. 413 PayloadTooLarge instead of the old
-            // silent `Body::empty()` substitution. Issue #79.
-            return Response::builder()
-                .status(http::StatusCode::PAYLOAD_TOO_LARGE)
-                .header(
-                    http::header::CONTENT_TYPE,
-                    "application/json",
-                )
-                .body(Body::from(format!(
-                    r#"{{"error":"PAYLOAD_TOO_LARGE","message":"chunked request body exceeded drift_tracking capture cap (~{} MiB); raise MOCKFORGE_DRIFT_MAX_BODY_MB or send Content-Length"}}"#,
-                    max_body / (1024 * 1024)
-                )))
-                .unwrap_or_else(|_| Response::new(Body::from("payload too large")));
-        }
-    };
-
-    // Try to parse body as JSON for structured capture
-    let captured_body = if !body_bytes.is_empty() {
-        serde_json::from_slice::<Value>(&body_bytes).ok()
-    } else {
-        None
-    };
+    let (captured, body) = capture_prefix(body, max_body).await;
+    if captured.truncated {
+        debug!(
+            "drift_tracking: {} {} body exceeds capture cap {}; capturing truncated copy",
+            method, path, max_body
+        );
+    }
+    let captured_body = captured.to_value();
 
-    // Reconstruct the request with the buffered body
-    let req = Request::from_parts(parts, Body::from(body_bytes));
+    // Reconstruct the request with the pass-through body
+    let req = Request::from_parts(parts, body);
 
     // Process request and get response
     let response = next.run(req).await;
@@ -142,6 +103,11 @@ pub async fn drift_tracking_middleware_with_extensions(
     // Extract response body for consumer usage tracking
     let response_body = extract_response_body(&response);
 
+    // Compare the response against the schema the spec declares for it
+    if let (Some(spec), Some(body)) = (&state.spec, &response_body) {
+        record_response_drift(spec, &method, &path, response.status().as_u16(), body);
+    }
+
     // Record consumer usage if consumer is identified
     if let Some(ref consumer_id) = consumer_id {
         if let Some(body) = &response_body {
@@ -389,6 +355,63 @@ fn extract_response_body(response: &Response<Body>) -> Option<Value> {
     None
 }
 
+/// Record how a JSON response compares to the schema the spec declares for
+/// its operation and status. Responses without a JSON schema are skipped.
+fn record_response_drift(spec: &OpenApiSpec, method: &str, path: &str, status: u16, body: &Value) {
+    let Some(document) = &spec.raw_document else {
+        return;
+    };
+    let Some(paths) = document.get("paths").and_then(Value::as_object) else {
+        return;
+    };
+    // An exact template match wins over one that only matches by parameters
+    let Some((template, item)) = paths
+        .get_key_value(path)
+        .or_else(|| paths.iter().find(|(template, _)| template_matches(template, path)))
+    else {
+        return;
+    };
+    let Some(responses) = item
+        .get(method.to_ascii_lowercase().as_str())
+        .and_then(|operation| operation.get("responses"))
+    else {
+        return;
+    };
+    let Some(mut response) = responses
+        .get(status.to_string().as_str())
+        .or_else(|| responses.get(format!("{}XX", status / 100).as_str()))
+        .or_else(|| responses.get("default"))
+    else {
+        return;
+    };
+    if let Some(reference) = response.get("$ref").and_then(Value::as_str) {
+        match reference.strip_prefix('#').and_then(|pointer| document.pointer(pointer)) {
+            Some(resolved) => response = resolved,
+            None => return,
+        }
+    }
+    let schema = response
+        .get("content")
+        .and_then(Value::as_object)
+        .and_then(|content| content.iter().find(|(media_type, _)| media_type.contains("json")))
+        .and_then(|(_, media)| media.get("schema"));
+    if let Some(schema) = schema {
+        let findings = route_drift::diff_against_schema(schema, document, body);
+        route_drift::record(method, template, &findings);
+    }
+}
+
+/// Whether a spec path template (`/users/{id}`) matches a concrete path.
+fn template_matches(template: &str, path: &str) -> bool {
+    let template_parts: Vec<&str> = template.split('/').filter(|s| !s.is_empty()).collect();
+    let path_parts: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    template_parts.len() == path_parts.len()
+        && template_parts.iter().zip(&path_parts).all(|(template_part, path_part)| {
+            (template_part.starts_with('{') && template_part.ends_with('}'))
+                || template_part == path_part
+        })
+}
+
 /// Determine incident severity from drift result
 fn determine_severity(drift_result: &DriftResult) -> IncidentSeverity {
     if drift_result.breaking_changes > 0 {
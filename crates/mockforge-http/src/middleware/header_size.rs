@@ -0,0 +1,97 @@
+//! Request header size limit middleware.
+//!
+//! Rejects requests whose headers exceed `http.max_header_bytes` with
+//! `431 Request Header Fields Too Large`, so clients can be tested against
+//! servers and proxies that cap header size. Size is measured the way the
+//! headers appear on the wire: `name: value\r\n` for every header line.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderMap, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use tracing::debug;
+
+/// Bytes each header line adds on top of its name and value (`: ` and `\r\n`)
+const HEADER_LINE_OVERHEAD: usize = 4;
+
+/// Total on-the-wire size of a header map in bytes
+pub fn header_bytes(headers: &HeaderMap) -> usize {
+    headers
+        .iter()
+        .map(|(name, value)| name.as_str().len() + value.len() + HEADER_LINE_OVERHEAD)
+        .sum()
+}
+
+/// Middleware: reject requests whose headers exceed `max_header_bytes` with 431
+pub async fn max_header_size_middleware(
+    State(max_header_bytes): State<usize>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let size = header_bytes(req.headers());
+    if size <= max_header_bytes {
+        return next.run(req).await;
+    }
+
+    debug!(size, max_header_bytes, "Rejecting request with oversized headers");
+    let body = serde_json::json!({
+        "error": "request_header_fields_too_large",
+        "message": format!(
+            "Request headers are {} bytes, exceeding the {} byte limit",
+            size, max_header_bytes
+        ),
+    });
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(max_header_bytes: usize) -> Router {
+        Router::new().route("/", get(|| async { "ok" })).layer(
+            axum::middleware::from_fn_with_state(max_header_bytes, max_header_size_middleware),
+        )
+    }
+
+    #[test]
+    fn header_bytes_counts_wire_size() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-a", HeaderValue::from_static("12345"));
+        // "x-a: 12345\r\n"
+        assert_eq!(header_bytes(&headers), 12);
+    }
+
+    #[tokio::test]
+    async fn oversized_headers_get_431() {
+        let req = Request::builder()
+            .uri("/")
+            .header("x-large", "a".repeat(2048))
+            .body(Body::empty())
+            .unwrap();
+        let res = app(1024).oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn normal_headers_pass() {
+        let req = Request::builder()
+            .uri("/")
+            .header("accept", "application/json")
+            .header("x-request-id", "abc-123")
+            .body(Body::empty())
+            .unwrap();
+        let res = app(1024).oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
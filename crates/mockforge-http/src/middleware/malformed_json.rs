@@ -0,0 +1,87 @@
+//! Configurable response for malformed JSON request bodies.
+//!
+//! Routes that validate their body as JSON reject one that does not parse
+//! with a generic 400, tagged with [`MalformedJsonBody`]. This middleware
+//! swaps that response for `http.malformed_json_response`, so clients see the
+//! error envelope of the API being mocked.
+
+use crate::route_validation::MalformedJsonBody;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderName, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use mockforge_core::config::HttpMalformedJsonResponse;
+use mockforge_template_expansion::RequestContext;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Placeholder for the JSON parser's message in the body template
+const ERROR_TOKEN: &str = "{{error}}";
+
+/// Shared state for [`malformed_json_middleware`]. Cheap to clone (Arc).
+#[derive(Clone)]
+pub struct MalformedJsonState {
+    config: Arc<HttpMalformedJsonResponse>,
+}
+
+impl MalformedJsonState {
+    /// Create state from config
+    pub fn new(config: HttpMalformedJsonResponse) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+
+    fn response(&self, context: &RequestContext, error: &str) -> Response {
+        let body = mockforge_template_expansion::expand_templates_in_json(
+            self.config.body.clone(),
+            context,
+        );
+        let mut response = Json(substitute_error(body, error)).into_response();
+        *response.status_mut() =
+            StatusCode::from_u16(self.config.status).unwrap_or(StatusCode::BAD_REQUEST);
+        for (name, value) in &self.config.headers {
+            match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+                (Ok(name), Ok(value)) => {
+                    response.headers_mut().insert(name, value);
+                }
+                _ => warn!("Ignoring invalid malformed JSON response header: {} = {}", name, value),
+            }
+        }
+        response
+    }
+}
+
+/// Replace `{{error}}` in every string of an already expanded body, so the
+/// parser's message is never itself expanded
+fn substitute_error(value: Value, error: &str) -> Value {
+    match value {
+        Value::String(s) => Value::String(s.replace(ERROR_TOKEN, error)),
+        Value::Array(items) => {
+            Value::Array(items.into_iter().map(|v| substitute_error(v, error)).collect())
+        }
+        Value::Object(fields) => Value::Object(
+            fields.into_iter().map(|(k, v)| (k, substitute_error(v, error))).collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Middleware: replace responses for malformed JSON bodies with the
+/// configured one
+pub async fn malformed_json_middleware(
+    State(state): State<MalformedJsonState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let context = crate::custom_route_request_context(&req);
+    let response = next.run(req).await;
+    match response.extensions().get::<MalformedJsonBody>() {
+        Some(MalformedJsonBody(error)) => state.response(&context, error),
+        None => response,
+    }
+}
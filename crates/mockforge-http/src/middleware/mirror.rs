@@ -0,0 +1,338 @@
+//! Request mirroring (traffic tap) middleware.
+//!
+//! For shadow testing: a sampled copy of each request is sent to a secondary
+//! upstream (`http.mirror.target_url`) while the client still gets the mock
+//! response. Mirroring is fire-and-forget — the copy is sent from a spawned
+//! task, the mirror's response is discarded, and errors or timeouts are only
+//! logged, so a slow or broken mirror can never affect the client.
+//! Requests with bodies over 16 MiB are served as usual but not mirrored.
+
+use super::body_capture::capture_prefix;
+use axum::{
+    body::Body,
+    extract::State,
+    http::{
+        header::{CONNECTION, CONTENT_LENGTH, HOST},
+        HeaderMap, Request,
+    },
+    middleware::Next,
+    response::Response,
+};
+use mockforge_core::config::HttpMirrorConfig;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Requests with larger bodies are forwarded but not mirrored
+const MAX_MIRROR_BODY: usize = 16 * 1024 * 1024;
+
+/// Headers describing the client's connection to us rather than the request,
+/// plus `Content-Length`, which the mirror client sets for the buffered body
+const CONNECTION_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "transfer-encoding",
+    "te",
+    "trailer",
+    "upgrade",
+    "content-length",
+];
+
+/// Shared state for [`mirror_middleware`]. Cheap to clone (Arc).
+#[derive(Clone)]
+pub struct MirrorState {
+    config: Arc<HttpMirrorConfig>,
+    client: reqwest::Client,
+}
+
+impl MirrorState {
+    /// Create mirror state from config
+    pub fn new(config: HttpMirrorConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .unwrap_or_default();
+        Self {
+            config: Arc::new(config),
+            client,
+        }
+    }
+
+    /// Whether this request is picked by the sample rate
+    fn sampled(&self) -> bool {
+        let rate = self.config.sample_rate;
+        rate >= 1.0 || (rate > 0.0 && rand::random::<f64>() < rate)
+    }
+
+    /// Whether a header is forwarded under the include/exclude filters;
+    /// `Host` and hop-by-hop headers never are
+    fn forwards_header(&self, name: &str) -> bool {
+        if name.eq_ignore_ascii_case(HOST.as_str())
+            || CONNECTION_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name))
+            || name.get(..6).is_some_and(|prefix| prefix.eq_ignore_ascii_case("proxy-"))
+        {
+            return false;
+        }
+        let included = self.config.include_headers.is_empty()
+            || self.config.include_headers.iter().any(|h| h.eq_ignore_ascii_case(name));
+        let excluded = self.config.exclude_headers.iter().any(|h| h.eq_ignore_ascii_case(name));
+        included && !excluded
+    }
+}
+
+/// Middleware: send a copy of sampled requests to the mirror, then serve the
+/// request as usual
+pub async fn mirror_middleware(
+    State(state): State<MirrorState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !state.config.enabled || !state.sampled() {
+        return next.run(req).await;
+    }
+
+    // The body has to be buffered to send it twice. One declared over the
+    // limit is never buffered; anything else is captured up to the limit and
+    // reaches the handler whole either way.
+    let (parts, body) = req.into_parts();
+    let declared_len = parts
+        .headers
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+    if declared_len.is_some_and(|len| len > MAX_MIRROR_BODY) {
+        debug!("Request body too large to mirror");
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+    let (captured, body) = capture_prefix(body, MAX_MIRROR_BODY).await;
+    if captured.truncated {
+        debug!("Request body too large to mirror");
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+
+    let path_and_query = parts.uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
+    let url = format!("{}{}", state.config.target_url.trim_end_matches('/'), path_and_query);
+    // Headers listed in `Connection` are hop-by-hop too
+    let connection_listed: Vec<&str> = parts
+        .headers
+        .get_all(CONNECTION)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .collect();
+    let listed = |name: &str| connection_listed.iter().any(|h| h.eq_ignore_ascii_case(name));
+    let mut headers = HeaderMap::new();
+    for (name, value) in parts.headers.iter() {
+        if state.forwards_header(name.as_str()) && !listed(name.as_str()) {
+            headers.append(name.clone(), value.clone());
+        }
+    }
+    let mirrored = state
+        .client
+        .request(parts.method.clone(), &url)
+        .headers(headers)
+        .body(captured.bytes);
+    tokio::spawn(async move {
+        if let Err(e) = mirrored.send().await {
+            debug!(error = %e, url = %url, "Mirrored request failed");
+        }
+    });
+
+    next.run(Request::from_parts(parts, body)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Bytes};
+    use axum::http::StatusCode;
+    use axum::{routing::any, Router};
+    use tokio::sync::mpsc;
+    use tower::ServiceExt;
+
+    /// A mirror receiver that reports (method, path, headers, body) per request
+    async fn spawn_receiver(
+    ) -> (String, mpsc::UnboundedReceiver<(String, String, HeaderMap, String)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new().fallback(any(move |req: Request<Body>| {
+            let tx = tx.clone();
+            async move {
+                let (parts, body) = req.into_parts();
+                let body = to_bytes(body, usize::MAX).await.unwrap();
+                let _ = tx.send((
+                    parts.method.to_string(),
+                    parts.uri.to_string(),
+                    parts.headers,
+                    String::from_utf8_lossy(&body).into_owned(),
+                ));
+                (StatusCode::INTERNAL_SERVER_ERROR, "mirror response is ignored")
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (format!("http://{}", addr), rx)
+    }
+
+    fn config(target_url: String) -> HttpMirrorConfig {
+        HttpMirrorConfig {
+            enabled: true,
+            target_url,
+            sample_rate: 1.0,
+            include_headers: Vec::new(),
+            exclude_headers: vec!["authorization".to_string()],
+            timeout_ms: 1000,
+        }
+    }
+
+    fn app(state: MirrorState) -> Router {
+        Router::new()
+            .route("/orders", axum::routing::post(|body: String| async move { body }))
+            .layer(axum::middleware::from_fn_with_state(state, mirror_middleware))
+    }
+
+    #[tokio::test]
+    async fn mirror_receives_copy_of_request() {
+        let (target, mut received) = spawn_receiver().await;
+        let req = Request::builder()
+            .method("POST")
+            .uri("/orders?source=test")
+            .header("x-trace", "abc")
+            .header("authorization", "Bearer secret")
+            .body(Body::from("{\"id\":1}"))
+            .unwrap();
+
+        let res = app(MirrorState::new(config(target.clone()))).oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"{\"id\":1}");
+
+        let (method, uri, headers, body) =
+            tokio::time::timeout(Duration::from_secs(5), received.recv())
+                .await
+                .expect("mirror should receive the request")
+                .unwrap();
+        assert_eq!(method, "POST");
+        assert_eq!(uri, "/orders?source=test");
+        assert_eq!(headers.get("x-trace").unwrap(), "abc");
+        assert!(headers.get("authorization").is_none());
+        assert_eq!(body, "{\"id\":1}");
+
+        // A chunked request reaches the mirror with a body of the right
+        // length and none of the client's hop-by-hop headers
+        let chunks = ["{\"id\":", "2}"].map(|chunk| Ok::<_, std::io::Error>(Bytes::from(chunk)));
+        let req = Request::builder()
+            .method("POST")
+            .uri("/orders")
+            .header("transfer-encoding", "chunked")
+            .header("connection", "keep-alive, x-hop")
+            .header("keep-alive", "timeout=5")
+            .header("x-hop", "1")
+            .header("te", "trailers")
+            .header("proxy-authorization", "Basic c2VjcmV0")
+            .header("x-trace", "def")
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+        let res = app(MirrorState::new(config(target))).oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let (_, _, headers, body) = tokio::time::timeout(Duration::from_secs(5), received.recv())
+            .await
+            .expect("mirror should receive the chunked request")
+            .unwrap();
+        assert_eq!(body, "{\"id\":2}");
+        assert_eq!(headers.get("content-length").unwrap(), "8");
+        assert_eq!(headers.get("x-trace").unwrap(), "def");
+        for name in [
+            "transfer-encoding",
+            "keep-alive",
+            "x-hop",
+            "te",
+            "proxy-authorization",
+        ] {
+            assert!(headers.get(name).is_none(), "{} should not be mirrored", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn mirror_failure_does_not_affect_response() {
+        // Nothing listens on port 9 (discard); the mirror send fails
+        let state = MirrorState::new(config("http://127.0.0.1:9".to_string()));
+        let req = Request::builder()
+            .method("POST")
+            .uri("/orders")
+            .body(Body::from("payload"))
+            .unwrap();
+
+        let res = app(state).oneshot(req).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"payload");
+    }
+
+    #[tokio::test]
+    async fn oversized_body_is_forwarded_but_not_mirrored() {
+        let (target, mut received) = spawn_receiver().await;
+        let payload = "x".repeat(MAX_MIRROR_BODY + 1);
+        let streamed = Request::builder()
+            .method("POST")
+            .uri("/orders")
+            .body(Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(
+                Bytes::from(payload.clone()),
+            )])))
+            .unwrap();
+        let declared = Request::builder()
+            .method("POST")
+            .uri("/orders")
+            .header(CONTENT_LENGTH, payload.len())
+            .body(Body::from(payload.clone()))
+            .unwrap();
+
+        // Reports how many body bytes the handler saw
+        let app = Router::new()
+            .route(
+                "/orders",
+                axum::routing::post(|body: Body| async move {
+                    to_bytes(body, usize::MAX).await.unwrap().len().to_string()
+                }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                MirrorState::new(config(target)),
+                mirror_middleware,
+            ));
+
+        for req in [streamed, declared] {
+            let res = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(res.status(), StatusCode::OK);
+            let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+            assert_eq!(body, payload.len().to_string());
+        }
+        let mirrored = tokio::time::timeout(Duration::from_millis(300), received.recv()).await;
+        assert!(mirrored.is_err(), "oversized request should not be mirrored");
+    }
+
+    #[test]
+    fn header_filters() {
+        let mut cfg = config("http://mirror".to_string());
+        cfg.include_headers = vec!["X-Trace".to_string(), "Authorization".to_string()];
+        let state = MirrorState::new(cfg);
+        assert!(state.forwards_header("x-trace"));
+        assert!(!state.forwards_header("authorization"));
+        assert!(!state.forwards_header("cookie"));
+        assert!(!state.forwards_header("host"));
+
+        let state = MirrorState::new(config("http://mirror".to_string()));
+        for name in [
+            "Connection",
+            "Transfer-Encoding",
+            "Content-Length",
+            "Proxy-Authorization",
+        ] {
+            assert!(!state.forwards_header(name), "{}", name);
+        }
+        assert!(state.forwards_header("x-proxy-id"));
+    }
+}
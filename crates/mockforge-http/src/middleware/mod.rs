@@ -3,25 +3,47 @@
 pub mod ab_testing;
 #[cfg(feature = "behavioral-cloning")]
 pub mod behavioral_cloning;
+pub mod body_capture;
+pub mod coalescing;
+pub mod compression;
 pub mod conn_diagnostics;
 pub mod deceptive_canary;
 pub mod drift_tracking;
+pub mod header_size;
 pub mod keepalive_hint;
+pub mod malformed_json;
+pub mod mirror;
 pub mod production_headers;
 pub mod rate_limit;
 pub mod response_buffer;
+pub mod response_headers;
 pub mod security;
+pub mod server_timing;
+pub mod static_site;
+pub mod strict_paths;
 
 pub use ab_testing::ab_testing_middleware;
 #[cfg(feature = "behavioral-cloning")]
 pub use behavioral_cloning::{behavioral_cloning_middleware, BehavioralCloningMiddlewareState};
+pub use coalescing::{coalescing_middleware, CoalescingState};
+pub use compression::{compression_layer, RouteCompression};
 pub use conn_diagnostics::{conn_diag_middleware, is_conn_log_enabled};
 pub use deceptive_canary::{deceptive_canary_middleware, DeceptiveCanaryState};
 pub use drift_tracking::drift_tracking_middleware_with_extensions;
+pub use header_size::max_header_size_middleware;
 pub use keepalive_hint::{is_keepalive_hint_enabled, keepalive_hint_middleware};
+pub use malformed_json::{malformed_json_middleware, MalformedJsonState};
+pub use mirror::{mirror_middleware, MirrorState};
 pub use production_headers::production_headers_middleware;
 pub use rate_limit::{
     is_rate_limit_disabled, rate_limit_middleware, GlobalRateLimiter, RateLimitConfig,
+    TenantRateLimiter,
 };
-pub use response_buffer::{buffer_response_middleware, get_buffered_response, BufferedResponse};
+pub use response_buffer::{
+    buffer_response_middleware, buffer_response_with_limit, get_buffered_response, BufferedResponse,
+};
+pub use response_headers::{response_headers_middleware, ResponseHeadersState};
 pub use security::security_middleware;
+pub use server_timing::{is_server_timing_enabled, server_timing_middleware, ServerTimings};
+pub use static_site::{static_site_middleware, StaticSiteState};
+pub use strict_paths::strict_path_middleware;
@@ -5,18 +5,23 @@
 
 use axum::{
     body::Body,
-    extract::{ConnectInfo, State},
-    http::{HeaderName, HeaderValue, Request, StatusCode},
+    extract::{ConnectInfo, MatchedPath, State},
+    http::{HeaderMap, HeaderName, HeaderValue, Method, Request, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
+    Json,
 };
 use governor::{
-    clock::DefaultClock,
-    state::{InMemoryState, NotKeyed},
+    clock::{Clock, DefaultClock},
+    state::{keyed::DefaultKeyedStateStore, InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
-use std::net::SocketAddr;
+use mockforge_core::config::ProductionRateLimitConfig;
+use mockforge_core::multi_tenant::WorkspaceContext;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::num::NonZeroU32;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::warn;
@@ -29,7 +34,8 @@ use tracing::warn;
 /// CLI flag funnels through these env vars as well.
 ///
 /// When this returns true the router skips wiring a `GlobalRateLimiter` into
-/// state; the middleware then short-circuits and forwards every request.
+/// state; the middleware then forwards every request except those to
+/// workspaces with their own `rate_limit`.
 pub fn is_rate_limit_disabled() -> bool {
     fn truthy(v: &str) -> bool {
         matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on")
@@ -60,8 +66,14 @@ pub struct RateLimitConfig {
     pub burst: u32,
     /// Enable per-IP rate limiting
     pub per_ip: bool,
-    /// Enable per-endpoint rate limiting
+    /// Enable per-endpoint rate limiting, with one bucket per route
+    /// (method and path template, so `/users/1` and `/users/2` share one).
+    /// Combined with `per_ip`, each client gets a bucket per route
     pub per_endpoint: bool,
+    /// Key per-IP buckets on the leftmost `X-Forwarded-For` address instead
+    /// of the socket peer. Only enable behind a proxy that sets the header,
+    /// since clients can otherwise pick their own bucket
+    pub trust_forwarded_headers: bool,
 }
 
 impl Default for RateLimitConfig {
@@ -71,6 +83,7 @@ impl Default for RateLimitConfig {
             burst: 200,
             per_ip: true,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         }
     }
 }
@@ -86,43 +99,216 @@ pub struct RateLimitQuota {
     pub reset: u64,
 }
 
+/// Endpoint key shared by every request that matches no known route, so
+/// scanning arbitrary paths cannot create a bucket per path
+pub const UNMATCHED_ENDPOINT: &str = "<unmatched>";
+
+/// Keyed checks between sweeps of buckets that have refilled completely
+const PRUNE_INTERVAL: u64 = 4096;
+
+/// Rate limiter keyed on client IP, used for per-workspace limits
+type KeyedRateLimiter = RateLimiter<IpAddr, DefaultKeyedStateStore<IpAddr>, DefaultClock>;
+
+/// Bucket key for the global limits: the client address when `per_ip` is
+/// set and the route when `per_endpoint` is set
+type ClientKey = (Option<IpAddr>, Option<String>);
+
+/// Rate limiter keyed on [`ClientKey`]
+type ClientRateLimiter = RateLimiter<ClientKey, DefaultKeyedStateStore<ClientKey>, DefaultClock>;
+
+/// Per-workspace limiter together with the limits it was built from, so a
+/// workspace whose limits change gets a fresh bucket
+struct TenantLimiter {
+    requests_per_minute: u32,
+    burst: u32,
+    limiter: Arc<KeyedRateLimiter>,
+}
+
+impl TenantLimiter {
+    fn new(limits: &ProductionRateLimitConfig) -> Self {
+        Self {
+            requests_per_minute: limits.requests_per_minute,
+            burst: limits.burst,
+            limiter: Arc::new(RateLimiter::keyed(build_quota(
+                limits.requests_per_minute,
+                limits.burst,
+            ))),
+        }
+    }
+}
+
+/// Build a governor quota, falling back to the defaults for zero values
+fn build_quota(requests_per_minute: u32, burst: u32) -> Quota {
+    Quota::per_minute(
+        NonZeroU32::new(requests_per_minute)
+            .unwrap_or(NonZeroU32::new(100).expect("constant 100 is non-zero")),
+    )
+    .allow_burst(
+        NonZeroU32::new(burst).unwrap_or(NonZeroU32::new(200).expect("constant 200 is non-zero")),
+    )
+}
+
+/// Per-workspace rate limiters for multi-tenant mode
+///
+/// Kept apart from [`GlobalRateLimiter`] so a workspace's own `rate_limit`
+/// applies even when global rate limiting is disabled.
+#[derive(Default)]
+pub struct TenantRateLimiter {
+    /// Limiters created lazily by workspace ID
+    limiters: Mutex<HashMap<String, TenantLimiter>>,
+    /// Checks so far; every [`PRUNE_INTERVAL`] of them triggers a sweep
+    checks: AtomicU64,
+}
+
+impl TenantRateLimiter {
+    /// Create a limiter with no workspace buckets yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drop buckets that have refilled completely
+    pub fn prune(&self) {
+        let tenants = self.limiters.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for tenant in tenants.values() {
+            tenant.limiter.retain_recent();
+            tenant.limiter.shrink_to_fit();
+        }
+    }
+
+    /// Check a request against a workspace's own limits
+    ///
+    /// Buckets are keyed on `(workspace_id, ip)`, so two workspaces never share
+    /// quota. When the workspace limits are not per-IP, every client of the
+    /// workspace shares a single bucket.
+    pub fn check(
+        &self,
+        workspace_id: &str,
+        limits: &ProductionRateLimitConfig,
+        ip: IpAddr,
+    ) -> bool {
+        self.try_acquire(workspace_id, limits, ip).is_ok()
+    }
+
+    /// Like [`Self::check`], returning how long until the workspace bucket
+    /// allows the next request when it is exhausted
+    pub fn try_acquire(
+        &self,
+        workspace_id: &str,
+        limits: &ProductionRateLimitConfig,
+        ip: IpAddr,
+    ) -> Result<(), Duration> {
+        let limiter = {
+            let mut tenants = self.limiters.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let tenant = tenants
+                .entry(workspace_id.to_string())
+                .or_insert_with(|| TenantLimiter::new(limits));
+            if tenant.requests_per_minute != limits.requests_per_minute
+                || tenant.burst != limits.burst
+            {
+                *tenant = TenantLimiter::new(limits);
+            }
+            tenant.limiter.clone()
+        };
+
+        let checks = self.checks.fetch_add(1, Ordering::Relaxed) + 1;
+        if checks.is_multiple_of(PRUNE_INTERVAL) {
+            self.prune();
+        }
+        let key = if limits.per_ip {
+            ip
+        } else {
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        };
+        limiter
+            .check_key(&key)
+            .map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+}
+
 /// Global rate limiter state
 pub struct GlobalRateLimiter {
     limiter: Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>,
+    /// Per-client and/or per-route buckets with the global limits, used when
+    /// `per_ip` or `per_endpoint` is set
+    client_limiter: Arc<ClientRateLimiter>,
     config: RateLimitConfig,
     /// Track window start time for reset calculation
     window_start: Arc<Mutex<SystemTime>>,
     /// Track approximate remaining requests
     remaining_counter: Arc<Mutex<u32>>,
+    /// Keyed checks so far; every [`PRUNE_INTERVAL`] of them triggers a sweep
+    keyed_checks: AtomicU64,
 }
 
 impl GlobalRateLimiter {
     /// Create a new global rate limiter
     pub fn new(config: RateLimitConfig) -> Self {
-        let quota = Quota::per_minute(
-            NonZeroU32::new(config.requests_per_minute)
-                .unwrap_or(NonZeroU32::new(100).expect("constant 100 is non-zero")),
-        )
-        .allow_burst(
-            NonZeroU32::new(config.burst)
-                .unwrap_or(NonZeroU32::new(200).expect("constant 200 is non-zero")),
-        );
+        let quota = build_quota(config.requests_per_minute, config.burst);
 
         let limiter = Arc::new(RateLimiter::direct(quota));
+        let client_limiter = Arc::new(RateLimiter::keyed(quota));
         let window_start = Arc::new(Mutex::new(SystemTime::now()));
         let remaining_counter = Arc::new(Mutex::new(config.requests_per_minute));
 
         Self {
             limiter,
+            client_limiter,
             config,
             window_start,
             remaining_counter,
+            keyed_checks: AtomicU64::new(0),
+        }
+    }
+
+    /// Drop keyed buckets that have refilled completely, since they behave
+    /// exactly like fresh ones. Keeps per-client and per-route state bounded
+    /// by the clients and routes seen recently.
+    pub fn prune(&self) {
+        self.client_limiter.retain_recent();
+        self.client_limiter.shrink_to_fit();
+    }
+
+    fn prune_if_due(&self) {
+        let checks = self.keyed_checks.fetch_add(1, Ordering::Relaxed) + 1;
+        if checks.is_multiple_of(PRUNE_INTERVAL) {
+            self.prune();
         }
     }
 
     /// Check if request should be rate limited
     pub fn check_rate_limit(&self) -> bool {
-        self.limiter.check().is_ok()
+        self.try_acquire().is_ok()
+    }
+
+    /// Take a token from the global bucket, or return how long until the
+    /// bucket refills enough to allow the next request
+    pub fn try_acquire(&self) -> Result<(), Duration> {
+        self.limiter
+            .check()
+            .map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+
+    /// Take a token for a request from `ip` to `endpoint` (see
+    /// [`endpoint_key`]): from the bucket for that client and/or route when
+    /// `per_ip` or `per_endpoint` is set, otherwise from the global one
+    pub fn try_acquire_client(&self, ip: IpAddr, endpoint: &str) -> Result<(), Duration> {
+        if !self.config.per_ip && !self.config.per_endpoint {
+            return self.try_acquire();
+        }
+        self.prune_if_due();
+        let key = (
+            self.config.per_ip.then_some(ip),
+            self.config.per_endpoint.then(|| endpoint.to_string()),
+        );
+        self.client_limiter
+            .check_key(&key)
+            .map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+
+    /// Address rate limits are keyed on: the leftmost valid `X-Forwarded-For`
+    /// entry when `trust_forwarded_headers` is set, else the socket peer
+    pub fn client_ip(&self, headers: &HeaderMap, peer: IpAddr) -> IpAddr {
+        client_ip(headers, peer, self.config.trust_forwarded_headers)
     }
 
     /// Get rate limit quota information for headers
@@ -164,37 +350,156 @@ impl GlobalRateLimiter {
     }
 }
 
+/// The leftmost valid `X-Forwarded-For` entry when `trust_forwarded_headers`
+/// is set, else `peer`
+fn client_ip(headers: &HeaderMap, peer: IpAddr, trust_forwarded_headers: bool) -> IpAddr {
+    if !trust_forwarded_headers {
+        return peer;
+    }
+    headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(peer)
+}
+
+/// Route a request counts against for per-endpoint limits, as
+/// `"METHOD /path/template"`
+///
+/// The template comes from the registered routes, preferring the one with the
+/// most literal segments (`/users/me` over `/users/{id}`); requests to routes
+/// outside that list use the router's matched path. Requests that match no
+/// route at all share [`UNMATCHED_ENDPOINT`].
+pub fn endpoint_key(
+    routes: &[crate::RouteInfo],
+    method: &Method,
+    path: &str,
+    matched_path: Option<&str>,
+) -> String {
+    let template = routes
+        .iter()
+        .filter(|route| route.method.eq_ignore_ascii_case(method.as_str()))
+        .filter_map(|route| template_literals(&route.path, path).map(|n| (n, &route.path)))
+        .max_by_key(|(literals, _)| *literals)
+        .map(|(_, template)| template.as_str())
+        .or(matched_path);
+    match template {
+        Some(template) => format!("{} {}", method, template),
+        None => UNMATCHED_ENDPOINT.to_string(),
+    }
+}
+
+/// Number of literal segments in `template` when `path` matches it, where
+/// `{name}` segments match any one non-empty segment
+fn template_literals(template: &str, path: &str) -> Option<usize> {
+    let mut template_segments = template.trim_end_matches('/').split('/');
+    let mut path_segments = path.trim_end_matches('/').split('/');
+    let mut literals = 0;
+    loop {
+        match (template_segments.next(), path_segments.next()) {
+            (Some(t), Some(s)) if t.starts_with('{') && t.ends_with('}') && !s.is_empty() => {}
+            (Some(t), Some(s)) if t == s => literals += 1,
+            (None, None) => return Some(literals),
+            _ => return None,
+        }
+    }
+}
+
+/// Whole seconds a client must wait for `wait` to pass, rounded up so a
+/// retry after that long always finds a token (at least 1)
+fn retry_after_secs(wait: Duration) -> u64 {
+    let secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+    secs.max(1)
+}
+
 /// Rate limiting middleware
 ///
 /// This middleware:
 /// 1. Checks if the request should be rate limited
 /// 2. Adds rate limit headers to successful responses (for deceptive deploy)
 /// 3. Returns 429 with Retry-After header when rate limited
+///
+/// In multi-tenant mode, requests carrying a [`WorkspaceContext`] whose
+/// workspace defines its own `rate_limit` are checked against that workspace's
+/// limits instead of the global ones, even when global limiting is disabled.
 pub async fn rate_limit_middleware(
     State(state): State<crate::HttpServerState>,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     req: Request<Body>,
     next: Next,
 ) -> Response {
-    // Get rate limiter from app state
-    let quota_info = if let Some(limiter) = &state.rate_limiter {
-        // Check rate limit
-        if !limiter.check_rate_limit() {
-            warn!("Rate limit exceeded for IP: {}", addr.ip());
-            // Return 429 with Retry-After header per HTTP spec
-            let mut response = Response::builder()
-                .status(StatusCode::TOO_MANY_REQUESTS)
-                .body(Body::from("Too Many Requests"))
-                .unwrap_or_else(|_| Response::new(Body::from("Too Many Requests")));
-
-            // Add Retry-After header (60 seconds = 1 minute window)
-            let retry_after = HeaderValue::from_static("60");
+    let tenant_limits = req.extensions().get::<WorkspaceContext>().and_then(|ctx| {
+        ctx.workspace
+            .workspace
+            .config
+            .rate_limit
+            .clone()
+            .map(|limits| (ctx.workspace_id.clone(), limits))
+    });
+
+    // Global quota info for the rate limit headers, reporting the
+    // workspace's own limit when it has one
+    let global_quota = || {
+        state.rate_limiter.as_ref().map(|limiter| {
+            let mut quota = limiter.get_quota_info();
+            if let Some((_, limits)) = &tenant_limits {
+                quota.limit = limits.requests_per_minute;
+            }
+            quota
+        })
+    };
+
+    let checked = match &tenant_limits {
+        Some((workspace_id, limits)) => {
+            let ip = client_ip(req.headers(), addr.ip(), limits.trust_forwarded_headers);
+            Some((ip, state.tenant_rate_limiter.try_acquire(workspace_id, limits, ip)))
+        }
+        None => state.rate_limiter.as_ref().map(|limiter| {
+            let ip = limiter.client_ip(req.headers(), addr.ip());
+            let endpoint = endpoint_key(
+                &state.routes,
+                req.method(),
+                req.uri().path(),
+                req.extensions().get::<MatchedPath>().map(MatchedPath::as_str),
+            );
+            (ip, limiter.try_acquire_client(ip, &endpoint))
+        }),
+    };
+
+    let quota_info = match checked {
+        Some((ip, Err(wait))) => {
+            warn!("Rate limit exceeded for IP: {}", ip);
+            let retry_after = retry_after_secs(wait);
+            // Without a global limiter only the workspace bucket is known
+            let quota = global_quota().unwrap_or_else(|| RateLimitQuota {
+                limit: tenant_limits.as_ref().map_or(0, |(_, limits)| limits.requests_per_minute),
+                remaining: 0,
+                reset: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or(Duration::ZERO)
+                    .as_secs()
+                    + retry_after,
+            });
+
+            // Return 429 with a machine-readable body and the same retry hint
+            // as the Retry-After header
+            let mut response = (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(serde_json::json!({
+                    "error": "rate_limited",
+                    "retry_after_seconds": retry_after,
+                    "limit": quota.limit,
+                })),
+            )
+                .into_response();
+
+            // Retry-After: when the bucket next holds a token
             response
                 .headers_mut()
-                .insert(HeaderName::from_static("retry-after"), retry_after);
+                .insert(HeaderName::from_static("retry-after"), HeaderValue::from(retry_after));
 
             // Add rate limit headers to the 429 response
-            let quota = limiter.get_quota_info();
             if let Ok(limit_value) = HeaderValue::from_str(&quota.limit.to_string()) {
                 response
                     .headers_mut()
@@ -213,13 +518,12 @@ pub async fn rate_limit_middleware(
 
             return response;
         }
-
-        // Get quota information for headers
-        Some(limiter.get_quota_info())
-    } else {
-        // No rate limiter configured, allow request to proceed
-        tracing::debug!("No rate limiter configured, allowing request");
-        None
+        Some((_, Ok(()))) => global_quota(),
+        None => {
+            // No rate limiter configured, allow request to proceed
+            tracing::debug!("No rate limiter configured, allowing request");
+            None
+        }
     };
 
     // Process request and get response
@@ -321,6 +625,7 @@ mod tests {
             burst: 100,
             per_ip: false,
             per_endpoint: true,
+            trust_forwarded_headers: false,
         };
 
         assert_eq!(config.requests_per_minute, 50);
@@ -336,6 +641,7 @@ mod tests {
             burst: 150,
             per_ip: true,
             per_endpoint: true,
+            trust_forwarded_headers: false,
         };
 
         let cloned = config.clone();
@@ -420,6 +726,7 @@ mod tests {
             burst: 10,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -433,6 +740,7 @@ mod tests {
             burst: 5,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -450,6 +758,7 @@ mod tests {
             burst: 100,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -467,6 +776,7 @@ mod tests {
             burst: 50,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -485,6 +795,7 @@ mod tests {
             burst: 100,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -500,6 +811,7 @@ mod tests {
             burst: 50,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -532,6 +844,7 @@ mod tests {
             burst: 1000, // Very high burst
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -549,6 +862,7 @@ mod tests {
             burst: 1,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -557,6 +871,262 @@ mod tests {
         assert!(limiter.check_rate_limit());
     }
 
+    /// Router rate limited per client with `burst: 1`, and a sender that
+    /// calls it from the same peer with an optional `X-Forwarded-For`
+    fn forwarded_app(trust_forwarded_headers: bool) -> axum::Router {
+        use axum::routing::get;
+
+        let config = RateLimitConfig {
+            requests_per_minute: 1,
+            burst: 1,
+            per_ip: true,
+            per_endpoint: false,
+            trust_forwarded_headers,
+        };
+        let state = crate::HttpServerState::new()
+            .with_rate_limiter(Arc::new(GlobalRateLimiter::new(config)));
+        axum::Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(axum::middleware::from_fn_with_state(state, rate_limit_middleware))
+    }
+
+    async fn send_forwarded(app: &axum::Router, forwarded_for: Option<&str>) -> StatusCode {
+        use tower::ServiceExt;
+
+        let mut req = Request::builder().uri("/ping");
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.header("x-forwarded-for", forwarded_for);
+        }
+        let mut req = req.body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))));
+        app.clone().oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_trusted_forwarded_for_gets_independent_buckets() {
+        let app = forwarded_app(true);
+
+        assert_eq!(send_forwarded(&app, Some("203.0.113.7, 10.0.0.1")).await, StatusCode::OK);
+        assert_eq!(send_forwarded(&app, Some("198.51.100.4")).await, StatusCode::OK);
+        assert_eq!(send_forwarded(&app, Some("203.0.113.7")).await, StatusCode::TOO_MANY_REQUESTS);
+        // Missing or invalid headers fall back to the proxy's own address
+        assert_eq!(send_forwarded(&app, Some("not-an-ip")).await, StatusCode::OK);
+        assert_eq!(send_forwarded(&app, None).await, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_untrusted_forwarded_for_is_ignored() {
+        let app = forwarded_app(false);
+
+        assert_eq!(send_forwarded(&app, Some("203.0.113.7")).await, StatusCode::OK);
+        assert_eq!(send_forwarded(&app, Some("198.51.100.4")).await, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    /// Router rate limited per route with `burst: 1`, with the routes also
+    /// registered in state the way the OpenAPI router does
+    fn endpoint_app(per_ip: bool) -> axum::Router {
+        use axum::routing::get;
+
+        let config = RateLimitConfig {
+            requests_per_minute: 1,
+            burst: 1,
+            per_ip,
+            per_endpoint: true,
+            trust_forwarded_headers: true,
+        };
+        let routes = ["/users/{id}", "/orders/{id}"]
+            .into_iter()
+            .map(|path| crate::RouteInfo {
+                method: "GET".to_string(),
+                path: path.to_string(),
+                operation_id: None,
+                summary: None,
+                description: None,
+                parameters: vec!["id".to_string()],
+            })
+            .collect();
+        let state = crate::HttpServerState::with_routes(routes)
+            .with_rate_limiter(Arc::new(GlobalRateLimiter::new(config)));
+        axum::Router::new()
+            .route("/users/{id}", get(|| async { "user" }))
+            .route("/orders/{id}", get(|| async { "order" }))
+            .layer(axum::middleware::from_fn_with_state(state, rate_limit_middleware))
+    }
+
+    async fn send_to(app: &axum::Router, path: &str, forwarded_for: &str) -> StatusCode {
+        use tower::ServiceExt;
+
+        let mut req = Request::builder()
+            .uri(path)
+            .header("x-forwarded-for", forwarded_for)
+            .body(Body::empty())
+            .unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))));
+        app.clone().oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_per_endpoint_buckets_are_keyed_by_route_template() {
+        let app = endpoint_app(false);
+        let client = "203.0.113.7";
+
+        assert_eq!(send_to(&app, "/users/1", client).await, StatusCode::OK);
+        // Same template, different path parameter: same bucket
+        assert_eq!(send_to(&app, "/users/2", client).await, StatusCode::TOO_MANY_REQUESTS);
+        // Different template: its own bucket
+        assert_eq!(send_to(&app, "/orders/1", client).await, StatusCode::OK);
+        // Without per_ip, other clients share the route's bucket
+        assert_eq!(send_to(&app, "/orders/2", "198.51.100.4").await, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_per_endpoint_and_per_ip_compose() {
+        let app = endpoint_app(true);
+
+        assert_eq!(send_to(&app, "/users/1", "203.0.113.7").await, StatusCode::OK);
+        assert_eq!(send_to(&app, "/users/2", "198.51.100.4").await, StatusCode::OK);
+        assert_eq!(send_to(&app, "/users/3", "203.0.113.7").await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(send_to(&app, "/orders/1", "203.0.113.7").await, StatusCode::OK);
+    }
+
+    #[test]
+    fn test_endpoint_key_prefers_literal_segments() {
+        let route = |path: &str| crate::RouteInfo {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            operation_id: None,
+            summary: None,
+            description: None,
+            parameters: Vec::new(),
+        };
+        let routes = vec![route("/users/{id}"), route("/users/me")];
+
+        assert_eq!(endpoint_key(&routes, &Method::GET, "/users/me", None), "GET /users/me");
+        assert_eq!(endpoint_key(&routes, &Method::GET, "/users/42", None), "GET /users/{id}");
+        // Unregistered routes fall back to the matched path; unmatched
+        // requests all share one key
+        assert_eq!(
+            endpoint_key(&routes, &Method::POST, "/users/42", Some("/users/{user_id}")),
+            "POST /users/{user_id}"
+        );
+        assert_eq!(endpoint_key(&routes, &Method::GET, "/teams/7", None), UNMATCHED_ENDPOINT);
+        assert_eq!(endpoint_key(&routes, &Method::PUT, "/x/y/z", None), UNMATCHED_ENDPOINT);
+    }
+
+    #[test]
+    fn test_prune_drops_refilled_buckets() {
+        let limiter = GlobalRateLimiter::new(RateLimitConfig {
+            requests_per_minute: 600_000,
+            burst: 1,
+            per_ip: true,
+            per_endpoint: true,
+            trust_forwarded_headers: true,
+        });
+        for i in 0..50u8 {
+            let ip = IpAddr::V4(Ipv4Addr::new(203, 0, 113, i));
+            assert!(limiter.try_acquire_client(ip, "GET /users/{id}").is_ok());
+        }
+        assert_eq!(limiter.client_limiter.len(), 50);
+
+        // One token refills every 0.1ms, so every bucket is full again
+        std::thread::sleep(Duration::from_millis(20));
+        limiter.prune();
+        assert_eq!(limiter.client_limiter.len(), 0);
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up_to_whole_seconds() {
+        assert_eq!(retry_after_secs(Duration::from_millis(9_990)), 10);
+        assert_eq!(retry_after_secs(Duration::from_secs(10)), 10);
+        assert_eq!(retry_after_secs(Duration::from_millis(10_001)), 11);
+        assert_eq!(retry_after_secs(Duration::ZERO), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_matches_refill_interval() {
+        use axum::{routing::get, Router};
+        use tower::ServiceExt;
+
+        // 6 requests per minute refills one token every 10 seconds
+        let config = RateLimitConfig {
+            requests_per_minute: 6,
+            burst: 2,
+            per_ip: false,
+            per_endpoint: false,
+            trust_forwarded_headers: false,
+        };
+        let state = crate::HttpServerState::new()
+            .with_rate_limiter(Arc::new(GlobalRateLimiter::new(config)));
+        let app = Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(axum::middleware::from_fn_with_state(state, rate_limit_middleware));
+        let send = || {
+            let app = app.clone();
+            async move {
+                let mut req = Request::builder().uri("/ping").body(Body::empty()).unwrap();
+                req.extensions_mut()
+                    .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+                app.oneshot(req).await.unwrap()
+            }
+        };
+
+        for _ in 0..2 {
+            assert_eq!(send().await.status(), StatusCode::OK);
+        }
+        let response = send().await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()["retry-after"].to_str().unwrap().parse().unwrap();
+        assert_eq!(retry_after, 10);
+    }
+
+    #[tokio::test]
+    async fn test_rejection_carries_retry_after_and_json_body() {
+        use axum::{routing::get, Router};
+        use tower::ServiceExt;
+
+        let config = RateLimitConfig {
+            requests_per_minute: 30,
+            burst: 5,
+            per_ip: true,
+            per_endpoint: false,
+            trust_forwarded_headers: false,
+        };
+        let state = crate::HttpServerState::new()
+            .with_rate_limiter(Arc::new(GlobalRateLimiter::new(config)));
+        let app = Router::new()
+            .route("/ping", get(|| async { "pong" }))
+            .layer(axum::middleware::from_fn_with_state(state, rate_limit_middleware));
+
+        let mut rejected = None;
+        for _ in 0..20 {
+            let mut req = Request::builder().uri("/ping").body(Body::empty()).unwrap();
+            req.extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+            let response = app.clone().oneshot(req).await.unwrap();
+            if response.status() == StatusCode::TOO_MANY_REQUESTS {
+                rejected = Some(response);
+                break;
+            }
+        }
+        let response = rejected.expect("burst of 5 was never exhausted");
+
+        let retry_after: u64 = response.headers()["retry-after"].to_str().unwrap().parse().unwrap();
+        assert!(retry_after > 0);
+        assert_eq!(response.headers()["content-type"], "application/json");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "error": "rate_limited",
+                "retry_after_seconds": retry_after,
+                "limit": 30,
+            })
+        );
+    }
+
     // ==================== Edge Cases ====================
 
     #[test]
@@ -567,6 +1137,7 @@ mod tests {
             burst: 0,               // Will use default (200)
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         // Should not panic - NonZeroU32::new(0) returns None, unwrap_or handles it
@@ -604,6 +1175,7 @@ mod tests {
             burst: 5,
             per_ip: false,
             per_endpoint: false,
+            trust_forwarded_headers: false,
         };
 
         let limiter = GlobalRateLimiter::new(config);
@@ -615,4 +1187,126 @@ mod tests {
             assert!(quota.remaining <= 100, "Remaining should be reasonable");
         }
     }
+
+    // ==================== Per-Workspace Limits ====================
+
+    fn workspace_limits(requests_per_minute: u32, burst: u32) -> ProductionRateLimitConfig {
+        ProductionRateLimitConfig {
+            requests_per_minute,
+            burst,
+            per_ip: true,
+            trust_forwarded_headers: false,
+        }
+    }
+
+    #[test]
+    fn test_tenant_limits_are_independent_per_workspace() {
+        let limiter = TenantRateLimiter::new();
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let small = workspace_limits(2, 2);
+        let large = workspace_limits(5, 5);
+
+        for _ in 0..2 {
+            assert!(limiter.check("small", &small, ip));
+        }
+        assert!(!limiter.check("small", &small, ip));
+
+        // Exhausting "small" must not affect "large"
+        for _ in 0..5 {
+            assert!(limiter.check("large", &large, ip));
+        }
+        assert!(!limiter.check("large", &large, ip));
+    }
+
+    #[test]
+    fn test_tenant_limits_keyed_by_ip() {
+        let limiter = TenantRateLimiter::new();
+        let limits = workspace_limits(1, 1);
+        let first: IpAddr = "10.0.0.1".parse().unwrap();
+        let second: IpAddr = "10.0.0.2".parse().unwrap();
+
+        assert!(limiter.check("ws", &limits, first));
+        assert!(!limiter.check("ws", &limits, first));
+        assert!(limiter.check("ws", &limits, second));
+    }
+
+    fn workspace_context(id: &str, limits: Option<ProductionRateLimitConfig>) -> WorkspaceContext {
+        let mut workspace = mockforge_core::Workspace::new(id.to_string());
+        workspace.config.rate_limit = limits;
+        WorkspaceContext {
+            workspace_id: id.to_string(),
+            original_path: "/ping".to_string(),
+            stripped_path: "/ping".to_string(),
+            workspace: mockforge_core::multi_tenant::TenantWorkspace::new(workspace),
+        }
+    }
+
+    async fn send_in_workspace(app: &axum::Router, ctx: WorkspaceContext) -> StatusCode {
+        use tower::ServiceExt;
+
+        let mut req = Request::builder().uri("/ping").body(Body::empty()).unwrap();
+        req.extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+        req.extensions_mut().insert(ctx);
+        app.clone().oneshot(req).await.unwrap().status()
+    }
+
+    fn ping_app(state: crate::HttpServerState) -> axum::Router {
+        axum::Router::new()
+            .route("/ping", axum::routing::get(|| async { "pong" }))
+            .layer(axum::middleware::from_fn_with_state(state, rate_limit_middleware))
+    }
+
+    #[tokio::test]
+    async fn test_middleware_uses_workspace_limits() {
+        let global = RateLimitConfig {
+            requests_per_minute: 1000,
+            burst: 1000,
+            per_ip: true,
+            per_endpoint: false,
+            trust_forwarded_headers: false,
+        };
+        let app = ping_app(
+            crate::HttpServerState::new()
+                .with_rate_limiter(Arc::new(GlobalRateLimiter::new(global))),
+        );
+        let context = workspace_context;
+        let send = |ctx| send_in_workspace(&app, ctx);
+
+        let alpha = context("alpha", Some(workspace_limits(2, 2)));
+        let beta = context("beta", Some(workspace_limits(4, 4)));
+
+        for _ in 0..2 {
+            assert_eq!(send(alpha.clone()).await, StatusCode::OK);
+        }
+        assert_eq!(send(alpha.clone()).await, StatusCode::TOO_MANY_REQUESTS);
+
+        for _ in 0..4 {
+            assert_eq!(send(beta.clone()).await, StatusCode::OK);
+        }
+        assert_eq!(send(beta.clone()).await, StatusCode::TOO_MANY_REQUESTS);
+
+        // A workspace without its own limits falls back to the global limiter
+        let gamma = context("gamma", None);
+        for _ in 0..10 {
+            assert_eq!(send(gamma.clone()).await, StatusCode::OK);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_workspace_limits_apply_without_global_limiter() {
+        let app = ping_app(crate::HttpServerState::new());
+
+        let limited = workspace_context("limited", Some(workspace_limits(2, 2)));
+        for _ in 0..2 {
+            assert_eq!(send_in_workspace(&app, limited.clone()).await, StatusCode::OK);
+        }
+        assert_eq!(send_in_workspace(&app, limited).await, StatusCode::TOO_MANY_REQUESTS);
+
+        // Workspaces without their own limits are not limited at all
+        let open = workspace_context("open", None);
+        for _ in 0..10 {
+            assert_eq!(send_in_workspace(&app, open.clone()).await, StatusCode::OK);
+        }
+    }
 }
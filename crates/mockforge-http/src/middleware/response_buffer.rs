@@ -2,7 +2,10 @@
 //!
 //! This middleware buffers response bodies so they can be read multiple times,
 //! enabling downstream middleware to access the response body for analysis.
+//! At most `MOCKFORGE_RESPONSE_BUFFER_MAX_BODY_MB` (default 10) MiB is kept;
+//! longer bodies are buffered truncated and still sent to the client whole.
 
+use super::body_capture::{capture_limit_from_env, capture_prefix, TRUNCATION_MARKER};
 use axum::{body::Body, extract::Request, http::Response, middleware::Next};
 use serde_json::Value;
 
@@ -13,52 +16,68 @@ pub struct BufferedResponse {
     pub status: u16,
     /// Response headers
     pub headers: http::HeaderMap,
-    /// Response body as bytes
+    /// Response body as bytes (only the start of it when `truncated`)
     pub body: axum::body::Bytes,
+    /// Whether the body exceeded the buffer limit and was cut short
+    pub truncated: bool,
 }
 
 impl BufferedResponse {
-    /// Get response body as JSON value
+    /// Get response body as JSON value (`None` when truncated)
     pub fn json(&self) -> Option<Value> {
+        if self.truncated {
+            return None;
+        }
         serde_json::from_slice(&self.body).ok()
     }
 
-    /// Get response body as string
+    /// Get response body as string, ending in a truncation marker when
+    /// truncated
     pub fn text(&self) -> String {
-        String::from_utf8_lossy(&self.body).to_string()
+        let text = String::from_utf8_lossy(&self.body);
+        if self.truncated {
+            format!("{}{}", text, TRUNCATION_MARKER)
+        } else {
+            text.to_string()
+        }
     }
 }
 
 /// Middleware to buffer response bodies
 ///
-/// This middleware reads the entire response body into memory so it can be
-/// accessed multiple times by downstream middleware. The buffered response
-/// is stored in request extensions.
+/// This middleware reads the response body into memory (up to the configured
+/// limit) so it can be accessed multiple times by downstream middleware. The
+/// buffered response is stored in response extensions.
 pub async fn buffer_response_middleware(req: Request, next: Next) -> Response<Body> {
+    buffer_response_with_limit(req, next, max_response_buffer_size()).await
+}
+
+/// Maximum response body size to buffer, from
+/// `MOCKFORGE_RESPONSE_BUFFER_MAX_BODY_MB`
+fn max_response_buffer_size() -> usize {
+    capture_limit_from_env("MOCKFORGE_RESPONSE_BUFFER_MAX_BODY_MB")
+}
+
+/// [`buffer_response_middleware`] with an explicit limit in bytes
+pub async fn buffer_response_with_limit(req: Request, next: Next, limit: usize) -> Response<Body> {
     // Process request
     let response = next.run(req).await;
 
     // Extract response parts
     let (parts, body) = response.into_parts();
 
-    // Read body into bytes
-    let body_bytes = match axum::body::to_bytes(body, usize::MAX).await {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            tracing::warn!("Failed to buffer response body: {}", e);
-            // Return error response if body buffering fails
-            return Response::builder()
-                .status(http::StatusCode::INTERNAL_SERVER_ERROR)
-                .body(Body::from("Failed to buffer response"))
-                .expect("static response body should never fail to build");
-        }
-    };
+    // Keep a copy of at most `limit` bytes; the rest streams straight through
+    let (captured, body) = capture_prefix(body, limit).await;
+    if captured.truncated {
+        tracing::debug!("Response body exceeds buffer limit of {} bytes; truncated copy", limit);
+    }
 
     // Create buffered response
     let buffered = BufferedResponse {
         status: parts.status.as_u16(),
         headers: parts.headers.clone(),
-        body: body_bytes.clone(),
+        body: captured.bytes,
+        truncated: captured.truncated,
     };
 
     // Store in request extensions for downstream middleware
@@ -75,7 +94,7 @@ pub async fn buffer_response_middleware(req: Request, next: Next) -> Response<Bo
     }
 
     // Add buffered response to response extensions
-    let mut response = match response_builder.body(Body::from(body_bytes)) {
+    let mut response = match response_builder.body(body) {
         Ok(resp) => resp,
         Err(e) => {
             tracing::error!("Failed to build response: {}", e);
@@ -96,3 +115,30 @@ pub async fn buffer_response_middleware(req: Request, next: Next) -> Response<Bo
 pub fn get_buffered_response(response: &Response<Body>) -> Option<BufferedResponse> {
     response.extensions().get::<BufferedResponse>().cloned()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::to_bytes, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn large_body_is_buffered_truncated_and_sent_whole() {
+        let app = Router::new().route("/big", get(|| async { "x".repeat(3000) })).layer(
+            axum::middleware::from_fn(|req: Request, next: Next| {
+                buffer_response_with_limit(req, next, 1024)
+            }),
+        );
+
+        let req = axum::http::Request::builder().uri("/big").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+
+        let buffered = get_buffered_response(&res).unwrap();
+        assert!(buffered.truncated);
+        assert_eq!(buffered.body.len(), 1024);
+        assert!(buffered.text().ends_with(TRUNCATION_MARKER));
+        assert_eq!(buffered.json(), None);
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body.len(), 3000);
+    }
+}
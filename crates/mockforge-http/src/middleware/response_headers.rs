@@ -0,0 +1,105 @@
+//! Global response header middleware.
+//!
+//! Adds the headers configured under `http.response_headers` (e.g.
+//! `X-Mock: true` or security headers) to every response. A header the route
+//! already set is kept unless `override_existing` is on.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{HeaderName, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use mockforge_core::config::HttpResponseHeadersConfig;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Shared state for [`response_headers_middleware`]. Cheap to clone (Arc).
+#[derive(Clone)]
+pub struct ResponseHeadersState {
+    headers: Arc<Vec<(HeaderName, HeaderValue)>>,
+    override_existing: bool,
+}
+
+impl ResponseHeadersState {
+    /// Create state from config, skipping (and logging) invalid headers
+    pub fn new(config: HttpResponseHeadersConfig) -> Self {
+        let headers = config
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                match (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+                    (Ok(name), Ok(value)) => Some((name, value)),
+                    _ => {
+                        warn!("Ignoring invalid global response header: {} = {}", name, value);
+                        None
+                    }
+                }
+            })
+            .collect();
+        Self {
+            headers: Arc::new(headers),
+            override_existing: config.override_existing,
+        }
+    }
+}
+
+/// Middleware: add the configured headers to every response
+pub async fn response_headers_middleware(
+    State(state): State<ResponseHeadersState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let mut response = next.run(req).await;
+    let headers = response.headers_mut();
+    for (name, value) in state.headers.iter() {
+        if state.override_existing || !headers.contains_key(name) {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use std::collections::HashMap;
+    use tower::ServiceExt;
+
+    fn app(override_existing: bool) -> Router {
+        let state = ResponseHeadersState::new(HttpResponseHeadersConfig {
+            headers: HashMap::from([
+                ("x-mock".to_string(), "true".to_string()),
+                ("x-frame-options".to_string(), "DENY".to_string()),
+            ]),
+            override_existing,
+        });
+        Router::new()
+            .route("/anything/{id}", get(|| async { "ok" }))
+            .route("/framed", get(|| async { ([("x-frame-options", "SAMEORIGIN")], "ok") }))
+            .layer(axum::middleware::from_fn_with_state(state, response_headers_middleware))
+    }
+
+    async fn header(app: Router, uri: &str, name: &str) -> Option<String> {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        res.headers().get(name).map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[tokio::test]
+    async fn global_headers_appear_on_every_route() {
+        assert_eq!(header(app(false), "/anything/42", "x-mock").await.as_deref(), Some("true"));
+        // Unmatched routes get them too
+        assert_eq!(header(app(false), "/missing", "x-mock").await.as_deref(), Some("true"));
+    }
+
+    #[tokio::test]
+    async fn route_headers_win_unless_overriding() {
+        let frame =
+            |override_existing| header(app(override_existing), "/framed", "x-frame-options");
+        assert_eq!(frame(false).await.as_deref(), Some("SAMEORIGIN"));
+        assert_eq!(frame(true).await.as_deref(), Some("DENY"));
+    }
+}
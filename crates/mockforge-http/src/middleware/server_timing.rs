@@ -0,0 +1,193 @@
+//! `Server-Timing` response header middleware.
+//!
+//! When enabled with `http.server_timing` (or the
+//! `MOCKFORGE_HTTP_SERVER_TIMING` environment variable, which overrides it),
+//! every response carries a `Server-Timing` header breaking down where the
+//! mock spent its time:
+//!
+//! - `global` — latency injected by the active network profile
+//! - `route-chaos` — latency injected by per-route chaos rules (static config
+//!   or the runtime route-chaos API)
+//! - `handler` — everything else (routing, template expansion, the handler)
+//!
+//! Layers that inject latency record into the [`ServerTimings`] collector the
+//! middleware places in the request extensions. Without the middleware the
+//! collector is absent and recording is a no-op, so the hot path pays nothing
+//! when the feature is off.
+
+use axum::{
+    body::Body,
+    http::{Extensions, HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Metric name for latency injected by the active network profile
+pub const GLOBAL_METRIC: &str = "global";
+/// Metric name for latency injected by route-chaos rules
+pub const ROUTE_CHAOS_METRIC: &str = "route-chaos";
+/// Metric name for the remaining time spent producing the response
+pub const HANDLER_METRIC: &str = "handler";
+
+/// Is `Server-Timing` emission enabled? `MOCKFORGE_HTTP_SERVER_TIMING`
+/// decides when set (truthy values: `1`, `true`, `yes`, `on`); otherwise
+/// `http.server_timing` in the effective config does.
+pub fn is_server_timing_enabled() -> bool {
+    match std::env::var("MOCKFORGE_HTTP_SERVER_TIMING") {
+        Ok(v) => matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes" | "on"),
+        Err(_) => mockforge_core::config::effective_config().is_some_and(|c| c.http.server_timing),
+    }
+}
+
+/// Per-request collector of named timing metrics. Cheap to clone (Arc).
+#[derive(Debug, Clone, Default)]
+pub struct ServerTimings {
+    entries: Arc<Mutex<Vec<(&'static str, Duration)>>>,
+}
+
+impl ServerTimings {
+    /// Add `duration` to the metric `name`, creating it if needed
+    pub fn record(&self, name: &'static str, duration: Duration) {
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match entries.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, total)) => *total += duration,
+            None => entries.push((name, duration)),
+        }
+    }
+
+    /// Record into the collector carried by a request, if there is one
+    pub fn record_in(extensions: &Extensions, name: &'static str, duration: Duration) {
+        if let Some(timings) = extensions.get::<ServerTimings>() {
+            timings.record(name, duration);
+        }
+    }
+
+    /// Snapshot of the recorded metrics, in recording order
+    pub fn entries(&self) -> Vec<(&'static str, Duration)> {
+        self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    /// Render the recorded metrics plus a trailing `handler` metric covering
+    /// whatever part of `total` the other metrics don't account for
+    fn header_value(&self, total: Duration) -> String {
+        let entries = self.entries();
+        let injected: Duration = entries.iter().map(|(_, d)| *d).sum();
+        entries
+            .iter()
+            .copied()
+            .chain(std::iter::once((HANDLER_METRIC, total.saturating_sub(injected))))
+            .map(|(name, d)| format!("{};dur={:.3}", name, d.as_secs_f64() * 1000.0))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Middleware: install a [`ServerTimings`] collector for the request and
+/// stamp the resulting `Server-Timing` header on the response. Appends to,
+/// rather than replaces, any `Server-Timing` header set further in.
+pub async fn server_timing_middleware(mut req: Request<Body>, next: Next) -> Response<Body> {
+    let timings = ServerTimings::default();
+    req.extensions_mut().insert(timings.clone());
+
+    let start = Instant::now();
+    let mut response = next.run(req).await;
+
+    if let Ok(value) = HeaderValue::from_str(&timings.header_value(start.elapsed())) {
+        response.headers_mut().append("server-timing", value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    fn parse_metric(header: &str, name: &str) -> Option<f64> {
+        header.split(", ").find_map(|metric| {
+            let (metric_name, dur) = metric.split_once(";dur=")?;
+            (metric_name == name).then(|| dur.parse().ok())?
+        })
+    }
+
+    #[test]
+    fn record_accumulates_by_name() {
+        let timings = ServerTimings::default();
+        timings.record(ROUTE_CHAOS_METRIC, Duration::from_millis(5));
+        timings.record(GLOBAL_METRIC, Duration::from_millis(1));
+        timings.record(ROUTE_CHAOS_METRIC, Duration::from_millis(5));
+        assert_eq!(
+            timings.entries(),
+            vec![
+                (ROUTE_CHAOS_METRIC, Duration::from_millis(10)),
+                (GLOBAL_METRIC, Duration::from_millis(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn handler_metric_is_remainder() {
+        let timings = ServerTimings::default();
+        timings.record(GLOBAL_METRIC, Duration::from_millis(30));
+        let header = timings.header_value(Duration::from_millis(50));
+        assert_eq!(header, "global;dur=30.000, handler;dur=20.000");
+    }
+
+    #[tokio::test]
+    async fn middleware_reports_route_chaos_latency() {
+        use crate::route_chaos_runtime::{runtime_route_chaos_middleware, RuntimeRouteChaosState};
+        use mockforge_core::config::{RouteConfig, RouteLatencyConfig, RouteResponseConfig};
+
+        let route = RouteConfig {
+            path: "/slow".to_string(),
+            method: "GET".to_string(),
+            request: None,
+            response: RouteResponseConfig {
+                status: 200,
+                body: None,
+                ..Default::default()
+            },
+            fault_injection: None,
+            latency: Some(RouteLatencyConfig {
+                enabled: true,
+                fixed_delay_ms: Some(40),
+                ..Default::default()
+            }),
+        };
+        let chaos = RuntimeRouteChaosState::new(vec![route]);
+
+        let app: Router = Router::new()
+            .route("/slow", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(chaos, runtime_route_chaos_middleware))
+            .layer(axum::middleware::from_fn(server_timing_middleware));
+
+        let req = Request::builder().uri("/slow").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+
+        let header = res.headers().get("server-timing").unwrap().to_str().unwrap();
+        let route_chaos = parse_metric(header, ROUTE_CHAOS_METRIC).expect("route-chaos metric");
+        assert!(route_chaos >= 40.0, "route-chaos metric too small: {header}");
+        assert!(
+            parse_metric(header, HANDLER_METRIC).is_some(),
+            "missing handler metric: {header}"
+        );
+    }
+
+    #[tokio::test]
+    async fn recording_without_middleware_is_noop() {
+        let app: Router = Router::new().route(
+            "/",
+            get(|req: Request<Body>| async move {
+                ServerTimings::record_in(req.extensions(), GLOBAL_METRIC, Duration::from_millis(1));
+                "ok"
+            }),
+        );
+
+        let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        assert!(res.headers().get("server-timing").is_none());
+    }
+}
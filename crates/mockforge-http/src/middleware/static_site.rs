@@ -0,0 +1,144 @@
+//! Static site and SPA history-fallback middleware.
+//!
+//! Serves a directory (`http.static_site.dir`), typically a built single-page
+//! app, behind the mock routes: every request goes through the router first,
+//! and only GET/HEAD requests it answers with 404 are looked up on disk. With
+//! `spa_fallback`, a request for a client-side route such as
+//! `/settings/profile` that matches no file gets `index.html`, so the app can
+//! route it. Paths under `api_prefixes` and paths that name a file (have an
+//! extension) keep their 404.
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Method, Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use mockforge_core::config::HttpStaticSiteConfig;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tower::ServiceExt;
+use tower_http::services::{ServeDir, ServeFile};
+use tracing::debug;
+
+/// Shared state for [`static_site_middleware`]. Cheap to clone (Arc).
+#[derive(Clone)]
+pub struct StaticSiteState {
+    config: Arc<HttpStaticSiteConfig>,
+    index_path: Arc<PathBuf>,
+}
+
+impl StaticSiteState {
+    /// Create static site state from config
+    pub fn new(config: HttpStaticSiteConfig) -> Self {
+        let index_path = PathBuf::from(&config.dir).join(&config.index);
+        Self {
+            config: Arc::new(config),
+            index_path: Arc::new(index_path),
+        }
+    }
+
+    fn is_api_path(&self, path: &str) -> bool {
+        self.config.api_prefixes.iter().any(|prefix| {
+            let prefix = prefix.trim_end_matches('/');
+            !prefix.is_empty()
+                && (path == prefix
+                    || path.strip_prefix(prefix).is_some_and(|rest| rest.starts_with('/')))
+        })
+    }
+
+    /// Whether a request that matched no file should get the SPA entry document
+    fn falls_back(&self, path: &str) -> bool {
+        let last_segment = path.rsplit('/').next().unwrap_or_default();
+        self.config.spa_fallback && !self.is_api_path(path) && !last_segment.contains('.')
+    }
+}
+
+/// Middleware: serve files (or the SPA entry document) for GET/HEAD requests
+/// the router could not answer
+pub async fn static_site_middleware(
+    State(state): State<StaticSiteState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if !state.config.enabled || !matches!(*req.method(), Method::GET | Method::HEAD) {
+        return next.run(req).await;
+    }
+
+    let mut file_req = Request::new(Body::empty());
+    *file_req.method_mut() = req.method().clone();
+    *file_req.uri_mut() = req.uri().clone();
+    *file_req.headers_mut() = req.headers().clone();
+
+    let response = next.run(req).await;
+    if response.status() != StatusCode::NOT_FOUND {
+        return response;
+    }
+
+    let path = file_req.uri().path().to_string();
+    let Ok(file) = ServeDir::new(&state.config.dir).oneshot(file_req).await;
+    if file.status() != StatusCode::NOT_FOUND {
+        return file.map(Body::new);
+    }
+    if !state.falls_back(&path) {
+        return response;
+    }
+
+    debug!(path = %path, "Serving SPA entry document for client-side route");
+    let index_req = Request::new(Body::empty());
+    match ServeFile::new(state.index_path.as_ref()).oneshot(index_req).await {
+        Ok(index) if index.status().is_success() => index.map(Body::new),
+        _ => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::to_bytes, routing::get, Router};
+
+    fn app(dir: &std::path::Path) -> Router {
+        let state = StaticSiteState::new(HttpStaticSiteConfig {
+            enabled: true,
+            dir: dir.to_string_lossy().into_owned(),
+            spa_fallback: true,
+            index: "index.html".to_string(),
+            api_prefixes: vec!["/api".to_string()],
+        });
+        Router::new()
+            .route("/api/users", get(|| async { "users" }))
+            .route("/healthz", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(state, static_site_middleware))
+    }
+
+    async fn fetch(app: &Router, uri: &str) -> (StatusCode, String) {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        let status = res.status();
+        let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn deep_client_route_serves_index_while_api_routes_win() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("index.html"), "<div id=app></div>").unwrap();
+        std::fs::create_dir(dir.path().join("assets")).unwrap();
+        std::fs::write(dir.path().join("assets/app.js"), "boot()").unwrap();
+        let app = app(dir.path());
+
+        assert_eq!(
+            fetch(&app, "/settings/profile/42").await,
+            (StatusCode::OK, "<div id=app></div>".to_string())
+        );
+        assert_eq!(fetch(&app, "/").await, (StatusCode::OK, "<div id=app></div>".to_string()));
+        assert_eq!(fetch(&app, "/assets/app.js").await, (StatusCode::OK, "boot()".to_string()));
+        assert_eq!(fetch(&app, "/api/users").await, (StatusCode::OK, "users".to_string()));
+        assert_eq!(fetch(&app, "/healthz").await, (StatusCode::OK, "ok".to_string()));
+
+        // Unmatched API paths and missing assets keep their 404
+        assert_eq!(fetch(&app, "/api/orders").await.0, StatusCode::NOT_FOUND);
+        assert_eq!(fetch(&app, "/assets/missing.js").await.0, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,108 @@
+//! Strict path matching middleware.
+//!
+//! Axum's router only matches a path that equals a route template exactly,
+//! but requests it cannot route fall through to fallbacks that are more
+//! forgiving: dynamic mocks and custom fixtures ignore trailing and repeated
+//! slashes, so `/users/` is still answered by a `/users` mock. With
+//! `http.strict_paths` enabled, an unrouted request whose path only matches
+//! after such normalization is answered with 404 instead, so clients can be
+//! tested against APIs that insist on exact paths. Matching stays
+//! case-sensitive throughout, so `/Users` never reaches a `/users` route.
+//!
+//! Apply with `Router::layer` so routed requests (which carry a
+//! [`MatchedPath`]) are recognised.
+
+use axum::{
+    body::Body,
+    extract::MatchedPath,
+    http::{header::CONTENT_TYPE, HeaderValue, Request, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use tracing::debug;
+
+/// Whether `path` has a trailing slash (other than the root) or an empty
+/// segment, i.e. it would only match a route after normalization
+pub fn is_non_canonical_path(path: &str) -> bool {
+    (path.len() > 1 && path.ends_with('/')) || path.contains("//")
+}
+
+/// Middleware: 404 unrouted requests whose path is not in canonical form
+pub async fn strict_path_middleware(req: Request<Body>, next: Next) -> Response {
+    let path = req.uri().path();
+    if req.extensions().get::<MatchedPath>().is_some() || !is_non_canonical_path(path) {
+        return next.run(req).await;
+    }
+
+    debug!(path, "Rejecting non-canonical path in strict path mode");
+    let body = serde_json::json!({
+        "error": "not_found",
+        "message": format!("No route matches {} exactly (strict path mode)", path),
+    });
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = StatusCode::NOT_FOUND;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    /// A `/users` route plus a fallback that, like dynamic mocks and
+    /// fixtures, normalizes slashes before matching
+    fn app(strict: bool) -> Router {
+        let app = Router::new()
+            .route("/users", get(|| async { "users" }))
+            .route("/users/{id}", get(|| async { "user" }))
+            .fallback(|req: Request<Body>| async move {
+                let normalized: Vec<_> = req
+                    .uri()
+                    .path()
+                    .split('/')
+                    .filter(|s| !s.is_empty())
+                    .collect();
+                match normalized.as_slice() {
+                    ["users"] => (StatusCode::OK, "users (fallback)"),
+                    _ => (StatusCode::NOT_FOUND, "not found"),
+                }
+            });
+        if strict {
+            app.layer(axum::middleware::from_fn(strict_path_middleware))
+        } else {
+            app
+        }
+    }
+
+    async fn status(app: Router, uri: &str) -> StatusCode {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        app.oneshot(req).await.unwrap().status()
+    }
+
+    #[test]
+    fn canonical_paths() {
+        assert!(!is_non_canonical_path("/"));
+        assert!(!is_non_canonical_path("/users/7"));
+        assert!(is_non_canonical_path("/users/"));
+        assert!(is_non_canonical_path("/users//7"));
+    }
+
+    #[tokio::test]
+    async fn lenient_mode_serves_trailing_slash() {
+        assert_eq!(status(app(false), "/users/").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn strict_mode_requires_exact_paths() {
+        assert_eq!(status(app(true), "/users").await, StatusCode::OK);
+        assert_eq!(status(app(true), "/users/7").await, StatusCode::OK);
+        assert_eq!(status(app(true), "/users/").await, StatusCode::NOT_FOUND);
+        assert_eq!(status(app(true), "//users").await, StatusCode::NOT_FOUND);
+        assert_eq!(status(app(true), "/Users").await, StatusCode::NOT_FOUND);
+        assert_eq!(status(app(true), "/Users/").await, StatusCode::NOT_FOUND);
+    }
+}
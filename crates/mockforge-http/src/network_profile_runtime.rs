@@ -19,6 +19,7 @@
 //! - `POST   /__mockforge/api/network-profiles/{name}/activate`  — switch to profile
 //! - `POST   /__mockforge/api/network-profiles/deactivate`       — clear
 
+use crate::middleware::server_timing::{ServerTimings, GLOBAL_METRIC};
 use axum::extract::{Path as AxumPath, Request, State};
 use axum::http::StatusCode;
 use axum::middleware::Next;
@@ -92,6 +93,7 @@ pub async fn network_profile_middleware(
         let delay = profile.latency.calculate_latency(&[]);
         if !delay.is_zero() {
             tokio::time::sleep(delay).await;
+            ServerTimings::record_in(req.extensions(), GLOBAL_METRIC, delay);
         }
     }
     next.run(req).await
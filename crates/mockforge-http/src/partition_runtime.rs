@@ -0,0 +1,255 @@
+//! Runtime network partition API.
+//!
+//! Partition testing needs some routes to go dark — the connection is
+//! accepted but nothing ever comes back — while the rest of the mock stays
+//! healthy. This module holds one optional partition: a set of route
+//! selectors plus an optional timeout. A middleware holds every matching
+//! request open until the partition is cleared (the request then proceeds
+//! normally) or the timeout elapses (answered with `504 Gateway Timeout`).
+//!
+//! ## Endpoints
+//!
+//! - `GET    /__mockforge/api/partition` — active partition (or 204 if none)
+//! - `POST   /__mockforge/api/partition` — start or replace the partition
+//! - `DELETE /__mockforge/api/partition` — heal it, releasing held requests
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Routes a partition applies to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionSelector {
+    /// HTTP method to match; any method when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Path to match. `{param}` matches one segment and a trailing `/*`
+    /// matches any number of further segments.
+    pub path: String,
+}
+
+impl PartitionSelector {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        if self.method.as_deref().is_some_and(|m| !m.eq_ignore_ascii_case(method)) {
+            return false;
+        }
+        let mut pattern = self.path.split('/').filter(|s| !s.is_empty());
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        loop {
+            match (pattern.next(), segments.next()) {
+                (Some("*"), _) => return true,
+                (Some(expected), Some(actual)) => {
+                    let is_param = expected.starts_with('{') && expected.ends_with('}');
+                    if !is_param && expected != actual {
+                        return false;
+                    }
+                }
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+/// An active partition, as accepted and returned by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Partition {
+    /// Requests matching any of these hang
+    pub routes: Vec<PartitionSelector>,
+    /// Give up on a held request after this long and answer 504; held
+    /// until the partition is cleared when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl Partition {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        self.routes.iter().any(|selector| selector.matches(method, path))
+    }
+}
+
+/// Cheap-to-clone shared state holding the active partition. Held requests
+/// watch it, so clearing or replacing the partition wakes them at once.
+#[derive(Clone)]
+pub struct PartitionRuntimeState {
+    active: Arc<watch::Sender<Option<Partition>>>,
+}
+
+impl Default for PartitionRuntimeState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartitionRuntimeState {
+    /// Start with no partition; the middleware fast-paths off this.
+    pub fn new() -> Self {
+        Self {
+            active: Arc::new(watch::Sender::new(None)),
+        }
+    }
+
+    /// Active partition snapshot.
+    pub fn active(&self) -> Option<Partition> {
+        self.active.borrow().clone()
+    }
+
+    /// Start or replace the partition.
+    pub fn set(&self, partition: Partition) {
+        self.active.send_replace(Some(partition));
+    }
+
+    /// Heal the partition, letting held requests through.
+    pub fn clear(&self) {
+        self.active.send_replace(None);
+    }
+}
+
+/// Middleware that holds requests to partitioned routes until the
+/// partition no longer covers them or its timeout elapses.
+pub async fn partition_middleware(
+    State(state): State<PartitionRuntimeState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = req.method().as_str().to_string();
+    let path = req.uri().path().to_string();
+    let mut updates = state.active.subscribe();
+    // The watch guard is not Send, so settle the decision before awaiting
+    let held = updates
+        .borrow_and_update()
+        .as_ref()
+        .filter(|partition| partition.matches(&method, &path))
+        .map(|partition| partition.timeout_ms.map(Duration::from_millis));
+    let Some(timeout) = held else {
+        return next.run(req).await;
+    };
+
+    let healed = async {
+        loop {
+            if updates.changed().await.is_err() {
+                return;
+            }
+            let still_partitioned = updates
+                .borrow_and_update()
+                .as_ref()
+                .is_some_and(|partition| partition.matches(&method, &path));
+            if !still_partitioned {
+                return;
+            }
+        }
+    };
+    match timeout {
+        Some(timeout) => {
+            if tokio::time::timeout(timeout, healed).await.is_err() {
+                return StatusCode::GATEWAY_TIMEOUT.into_response();
+            }
+        }
+        None => healed.await,
+    }
+    next.run(req).await
+}
+
+async fn get_handler(State(state): State<PartitionRuntimeState>) -> Response {
+    match state.active() {
+        Some(partition) => Json(partition).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+async fn post_handler(
+    State(state): State<PartitionRuntimeState>,
+    Json(partition): Json<Partition>,
+) -> Json<Partition> {
+    state.set(partition.clone());
+    Json(partition)
+}
+
+async fn delete_handler(State(state): State<PartitionRuntimeState>) -> StatusCode {
+    state.clear();
+    StatusCode::NO_CONTENT
+}
+
+/// Build the partition API router. Mount under `/__mockforge/api/partition`.
+pub fn partition_api_router(state: PartitionRuntimeState) -> Router {
+    Router::new()
+        .route("/", get(get_handler).post(post_handler).delete(delete_handler))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::middleware::from_fn_with_state;
+    use tower::ServiceExt;
+
+    fn app(state: PartitionRuntimeState) -> Router {
+        Router::new()
+            .route("/orders/{id}", get(|| async { "order" }))
+            .route("/health", get(|| async { "ok" }))
+            .layer(from_fn_with_state(state.clone(), partition_middleware))
+            .nest("/__mockforge/api/partition", partition_api_router(state))
+    }
+
+    fn request(method: &str, uri: &str, body: &str) -> axum::http::Request<Body> {
+        axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn partitioned_route_times_out_while_others_respond() {
+        let app = app(PartitionRuntimeState::new());
+        let res = app
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/__mockforge/api/partition",
+                r#"{"routes":[{"method":"GET","path":"/orders/{id}"}],"timeout_ms":100}"#,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = app.clone().oneshot(request("GET", "/orders/7", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+        let res = app.clone().oneshot(request("GET", "/health", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res =
+            app.clone().oneshot(request("DELETE", "/__mockforge/api/partition", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        let res = app.oneshot(request("GET", "/orders/7", "")).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn clearing_the_partition_releases_held_requests() {
+        let state = PartitionRuntimeState::new();
+        state.set(Partition {
+            routes: vec![PartitionSelector {
+                method: None,
+                path: "/orders/*".to_string(),
+            }],
+            timeout_ms: None,
+        });
+        let held = tokio::spawn(app(state.clone()).oneshot(request("GET", "/orders/7", "")));
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!held.is_finished());
+        state.clear();
+        let res = held.await.unwrap().unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
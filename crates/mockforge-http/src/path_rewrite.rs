@@ -0,0 +1,140 @@
+//! Regex path rewrites applied before routing
+//!
+//! `http.rewrites` maps legacy or versioned paths onto the routes the mock
+//! actually serves, e.g. `/api/v(\d+)/(.*)` → `/$2` lets `/api/v1/users/123`
+//! reach `/users/{id}`. Rules are tried in order against the whole path and
+//! the first match rewrites it; the query string is kept. Invalid patterns
+//! are rejected when the config is loaded.
+//!
+//! `Router::layer` runs after routing, so the rewrite wraps the app in an
+//! outer router whose only service is the original one: the URI is changed
+//! before the inner router matches it.
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{Request, Uri};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Router;
+use mockforge_core::config::HttpRewriteRule;
+use regex::Regex;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+/// Compiled rewrite rules
+#[derive(Debug, Clone)]
+pub struct PathRewriter {
+    rules: Arc<Vec<(Regex, String)>>,
+}
+
+impl PathRewriter {
+    /// Compile `rules`
+    ///
+    /// # Errors
+    /// Returns the parse error of the first pattern that is not a valid
+    /// regular expression.
+    pub fn new(rules: &[HttpRewriteRule]) -> Result<Self, regex::Error> {
+        let rules = rules
+            .iter()
+            .map(|rule| Ok((rule.regex()?, rule.replacement.clone())))
+            .collect::<Result<_, regex::Error>>()?;
+        Ok(Self {
+            rules: Arc::new(rules),
+        })
+    }
+
+    /// The rewritten path, or `None` when no rule matches
+    pub fn rewrite(&self, path: &str) -> Option<String> {
+        self.rules
+            .iter()
+            .find(|(regex, _)| regex.is_match(path))
+            .map(|(regex, replacement)| regex.replace(path, replacement.as_str()).into_owned())
+    }
+}
+
+async fn path_rewrite_middleware(
+    State(rewriter): State<PathRewriter>,
+    mut req: Request<Body>,
+    next: Next,
+) -> Response {
+    if let Some(path) = rewriter.rewrite(req.uri().path()) {
+        let uri = match req.uri().query() {
+            Some(query) => format!("{}?{}", path, query),
+            None => path,
+        };
+        match uri.parse::<Uri>() {
+            Ok(uri) => {
+                debug!("Path rewrite: '{}' -> '{}'", req.uri(), uri);
+                *req.uri_mut() = uri;
+            }
+            Err(e) => warn!("Rewritten URI '{}' is invalid: {}", uri, e),
+        }
+    }
+    next.run(req).await
+}
+
+/// Wrap `app` so `rules` rewrite request paths before `app` routes them
+///
+/// # Errors
+/// Returns the parse error of the first invalid pattern.
+pub fn with_path_rewrites(app: Router, rules: &[HttpRewriteRule]) -> Result<Router, regex::Error> {
+    let rewriter = PathRewriter::new(rules)?;
+    Ok(Router::new()
+        .fallback_service(app)
+        .layer(axum::middleware::from_fn_with_state(rewriter, path_rewrite_middleware)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::{Path, RawQuery};
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    fn rule(pattern: &str, replacement: &str) -> HttpRewriteRule {
+        HttpRewriteRule {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn versioned_path_reaches_unversioned_route() {
+        let app = Router::new().route(
+            "/users/{id}",
+            get(|Path(id): Path<String>, RawQuery(query): RawQuery| async move {
+                format!("user {} {}", id, query.unwrap_or_default())
+            }),
+        );
+        let app = with_path_rewrites(app, &[rule(r"/api/v(\d+)/(.*)", "/$2")]).unwrap();
+
+        let req = Request::builder()
+            .uri("/api/v1/users/123?fields=name")
+            .body(Body::empty())
+            .unwrap();
+        let res = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), axum::http::StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, "user 123 fields=name");
+
+        // Paths the rule does not match are routed unchanged
+        let req = Request::builder().uri("/users/7").body(Body::empty()).unwrap();
+        assert_eq!(app.oneshot(req).await.unwrap().status(), axum::http::StatusCode::OK);
+    }
+
+    #[test]
+    fn first_matching_rule_wins_and_must_match_whole_path() {
+        let rewriter = PathRewriter::new(&[
+            rule(r"/legacy/(?<rest>.*)", "/v2/${rest}"),
+            rule(r"/legacy/.*", "/unreachable"),
+        ])
+        .unwrap();
+        assert_eq!(rewriter.rewrite("/legacy/orders/1").as_deref(), Some("/v2/orders/1"));
+        assert_eq!(rewriter.rewrite("/prefix/legacy/orders"), None);
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        assert!(PathRewriter::new(&[rule(r"/ok/.*", "/ok"), rule("(", "/invalid")]).is_err());
+    }
+}
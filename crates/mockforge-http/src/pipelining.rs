@@ -0,0 +1,394 @@
+//! Pipelined HTTP/1.1 serving with response-ordering anomalies
+//!
+//! hyper answers pipelined requests strictly one after another, so the
+//! anomalies configured under `http.pipelining` need their own accept loop.
+//! [`serve_pipelined`] reads requests off each connection itself: every
+//! complete request already buffered when the connection is read forms one
+//! batch, the batch is handled concurrently by the router, and the responses
+//! are written according to [`HttpPipeliningMode`]:
+//!
+//! - `delay_first` starts the first request of a batch `delay_ms` late, so the
+//!   requests behind it finish first, then writes the responses in request
+//!   order (head-of-line blocking as the client sees it).
+//! - `reverse` writes the batch's responses last-to-first, which HTTP/1.1
+//!   forbids; clients should notice the mismatch.
+//!
+//! Single requests are served normally. Response bodies are buffered and
+//! sent with `Content-Length`; requests using `Transfer-Encoding` get 501 and
+//! the connection is closed.
+
+use axum::body::Body;
+use axum::extract::ConnectInfo;
+use axum::http::{header, Request, StatusCode, Version};
+use axum::response::Response;
+use axum::Router;
+use mockforge_core::config::{HttpConnectionLimitConfig, HttpPipeliningConfig, HttpPipeliningMode};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tower::ServiceExt;
+use tracing::{debug, info};
+
+use crate::connection_limit::ConnectionLimiter;
+
+/// Most headers accepted per request.
+const MAX_HEADERS: usize = 64;
+/// Largest request head accepted before answering 431.
+const MAX_HEAD_BYTES: usize = 64 * 1024;
+/// Largest request body accepted before answering 413.
+const MAX_BODY_BYTES: usize = 50 * 1024 * 1024;
+
+/// Bind `port` and serve `app` with the configured pipelining anomalies.
+///
+/// Like [`crate::serve_router_with_tls_notify`], the bound port is reported on
+/// `bound_port_tx` (useful when `port` is `0`), each new connection waits
+/// `accept_delay` before it is served, and `connection_limit` caps how many
+/// connections are served at once.
+pub async fn serve_router_pipelined(
+    port: u16,
+    app: Router,
+    config: HttpPipeliningConfig,
+    bound_port_tx: Option<tokio::sync::oneshot::Sender<u16>>,
+    accept_delay: Duration,
+    connection_limit: Option<HttpConnectionLimitConfig>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let addr = mockforge_core::wildcard_socket_addr(port);
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Failed to bind HTTP server to port {}: {}", port, e))?;
+    let local_addr = listener.local_addr()?;
+    info!("HTTP listening on {} (pipelining anomalies: {:?})", local_addr, config.mode);
+    if let Some(tx) = bound_port_tx {
+        let _ = tx.send(local_addr.port());
+    }
+    let limiter = connection_limit.as_ref().map(ConnectionLimiter::new);
+    serve_pipelined(listener, app, config, accept_delay, limiter).await?;
+    Ok(())
+}
+
+/// Accept connections on `listener` and serve them with the pipelining
+/// anomalies in `config`, waiting `accept_delay` after each accept. With a
+/// `limiter`, connections it turns away get a 503 and are closed.
+pub async fn serve_pipelined(
+    listener: TcpListener,
+    app: Router,
+    config: HttpPipeliningConfig,
+    accept_delay: Duration,
+    limiter: Option<Arc<ConnectionLimiter>>,
+) -> std::io::Result<()> {
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        if !accept_delay.is_zero() {
+            tokio::time::sleep(accept_delay).await;
+        }
+        let admission = limiter.as_ref().map(|limiter| limiter.enter());
+        let app = app.clone();
+        let config = config.clone();
+        tokio::spawn(async move {
+            if let Some(admission) = &admission {
+                if !admission.admitted().await {
+                    let rejected = error_response(StatusCode::SERVICE_UNAVAILABLE);
+                    let _ = stream.write_all(&rejected).await;
+                    return;
+                }
+            }
+            if let Err(e) = serve_connection(stream, peer, app, &config).await {
+                debug!("Pipelined connection from {} ended: {}", peer, e);
+            }
+        });
+    }
+}
+
+/// A request read off the wire.
+struct ParsedRequest {
+    request: Request<Body>,
+    /// `HEAD` responses carry no body.
+    head: bool,
+    /// The client asked to close the connection after this request.
+    close: bool,
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    peer: SocketAddr,
+    app: Router,
+    config: &HttpPipeliningConfig,
+) -> std::io::Result<()> {
+    let mut buf = Vec::with_capacity(8192);
+    let mut chunk = [0u8; 8192];
+    loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+
+        // Every complete request buffered so far forms one pipelined batch
+        let mut batch = Vec::new();
+        let mut rejection = None;
+        let mut close = false;
+        while !close {
+            match parse_request(&buf, peer) {
+                Ok(Some((parsed, consumed))) => {
+                    buf.drain(..consumed);
+                    close = parsed.close;
+                    batch.push(parsed);
+                }
+                Ok(None) => break,
+                Err(status) => {
+                    rejection = Some(status);
+                    close = true;
+                }
+            }
+        }
+        if batch.is_empty() && rejection.is_none() {
+            continue;
+        }
+
+        for response in respond(&app, batch, config).await {
+            stream.write_all(&response).await?;
+        }
+        if let Some(status) = rejection {
+            stream.write_all(&error_response(status)).await?;
+        }
+        stream.flush().await?;
+        if close {
+            return Ok(());
+        }
+    }
+}
+
+/// Parse one request from the front of `buf`; `Ok(None)` means more bytes
+/// are needed.
+fn parse_request(
+    buf: &[u8],
+    peer: SocketAddr,
+) -> Result<Option<(ParsedRequest, usize)>, StatusCode> {
+    let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+    let mut parsed = httparse::Request::new(&mut headers);
+    let head_len = match parsed.parse(buf) {
+        Ok(httparse::Status::Complete(len)) => len,
+        Ok(httparse::Status::Partial) if buf.len() > MAX_HEAD_BYTES => {
+            return Err(StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE)
+        }
+        Ok(httparse::Status::Partial) => return Ok(None),
+        Err(_) => return Err(StatusCode::BAD_REQUEST),
+    };
+
+    let method = parsed.method.unwrap_or("GET");
+    let http10 = parsed.version == Some(0);
+    let mut builder =
+        Request::builder()
+            .method(method)
+            .uri(parsed.path.unwrap_or("/"))
+            .version(if http10 {
+                Version::HTTP_10
+            } else {
+                Version::HTTP_11
+            });
+    let mut content_length = 0;
+    let mut close = http10;
+    for h in parsed.headers.iter() {
+        if h.name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(StatusCode::NOT_IMPLEMENTED);
+        }
+        if h.name.eq_ignore_ascii_case("content-length") {
+            content_length = std::str::from_utf8(h.value)
+                .ok()
+                .and_then(|v| v.trim().parse::<usize>().ok())
+                .ok_or(StatusCode::BAD_REQUEST)?;
+            if content_length > MAX_BODY_BYTES {
+                return Err(StatusCode::PAYLOAD_TOO_LARGE);
+            }
+        }
+        if h.name.eq_ignore_ascii_case("connection") {
+            let value = String::from_utf8_lossy(h.value).to_ascii_lowercase();
+            if value.contains("close") {
+                close = true;
+            } else if value.contains("keep-alive") {
+                close = false;
+            }
+        }
+        builder = builder.header(h.name, h.value);
+    }
+
+    let total = head_len + content_length;
+    if buf.len() < total {
+        return Ok(None);
+    }
+    let request = builder
+        .extension(ConnectInfo(peer))
+        .body(Body::from(buf[head_len..total].to_vec()))
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    let parsed = ParsedRequest {
+        request,
+        head: method.eq_ignore_ascii_case("HEAD"),
+        close,
+    };
+    Ok(Some((parsed, total)))
+}
+
+/// Handle a batch concurrently and return the serialized responses in the
+/// order they should be written.
+async fn respond(
+    app: &Router,
+    batch: Vec<ParsedRequest>,
+    config: &HttpPipeliningConfig,
+) -> Vec<Vec<u8>> {
+    let pipelined = batch.len() > 1;
+    let delay = Duration::from_millis(config.delay_ms);
+    let handles: Vec<_> = batch
+        .into_iter()
+        .enumerate()
+        .map(|(i, parsed)| {
+            let app = app.clone();
+            let delayed = pipelined && i == 0 && config.mode == HttpPipeliningMode::DelayFirst;
+            tokio::spawn(async move {
+                if delayed {
+                    tokio::time::sleep(delay).await;
+                }
+                let response = match app.oneshot(parsed.request).await {
+                    Ok(response) => response,
+                    Err(never) => match never {},
+                };
+                serialize_response(response, parsed.head, parsed.close).await
+            })
+        })
+        .collect();
+
+    let mut responses = Vec::with_capacity(handles.len());
+    for handle in handles {
+        responses.push(
+            handle
+                .await
+                .unwrap_or_else(|_| error_response(StatusCode::INTERNAL_SERVER_ERROR)),
+        );
+    }
+    if pipelined && config.mode == HttpPipeliningMode::Reverse {
+        responses.reverse();
+    }
+    responses
+}
+
+async fn serialize_response(response: Response, head: bool, close: bool) -> Vec<u8> {
+    let (parts, body) = response.into_parts();
+    let body = axum::body::to_bytes(body, usize::MAX).await.unwrap_or_default();
+
+    let mut out = status_line(parts.status);
+    for (name, value) in &parts.headers {
+        if name == header::CONTENT_LENGTH
+            || name == header::TRANSFER_ENCODING
+            || name == header::CONNECTION
+        {
+            continue;
+        }
+        out.extend_from_slice(name.as_str().as_bytes());
+        out.extend_from_slice(b": ");
+        out.extend_from_slice(value.as_bytes());
+        out.extend_from_slice(b"\r\n");
+    }
+    out.extend_from_slice(format!("content-length: {}\r\n", body.len()).as_bytes());
+    if close {
+        out.extend_from_slice(b"connection: close\r\n");
+    }
+    out.extend_from_slice(b"\r\n");
+    if !head {
+        out.extend_from_slice(&body);
+    }
+    out
+}
+
+fn error_response(status: StatusCode) -> Vec<u8> {
+    let mut out = status_line(status);
+    out.extend_from_slice(b"content-length: 0\r\nconnection: close\r\n\r\n");
+    out
+}
+
+fn status_line(status: StatusCode) -> Vec<u8> {
+    format!("HTTP/1.1 {} {}\r\n", status.as_u16(), status.canonical_reason().unwrap_or(""))
+        .into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Path;
+    use axum::routing::get;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Echo `<name>:<n>`, where `n` counts handled requests.
+    fn app() -> Router {
+        let handled = Arc::new(AtomicUsize::new(0));
+        Router::new().route(
+            "/{name}",
+            get(move |Path(name): Path<String>| {
+                let handled = handled.clone();
+                async move { format!("{}:{}", name, handled.fetch_add(1, Ordering::SeqCst)) }
+            }),
+        )
+    }
+
+    /// Send `paths` as one pipelined write and return the response bodies in
+    /// the order they arrived.
+    async fn pipeline(mode: HttpPipeliningMode, paths: &[&str]) -> Vec<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let config = HttpPipeliningConfig {
+            enabled: true,
+            mode,
+            delay_ms: 200,
+        };
+        tokio::spawn(serve_pipelined(listener, app(), config, Duration::ZERO, None));
+
+        let mut requests = String::new();
+        for (i, path) in paths.iter().enumerate() {
+            let connection = if i + 1 == paths.len() {
+                "close"
+            } else {
+                "keep-alive"
+            };
+            requests.push_str(&format!(
+                "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: {}\r\n\r\n",
+                path, connection
+            ));
+        }
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(requests.as_bytes()).await.unwrap();
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).await.unwrap();
+
+        let raw = String::from_utf8(raw).unwrap();
+        let mut bodies = Vec::new();
+        let mut rest = raw.as_str();
+        while let Some(head_end) = rest.find("\r\n\r\n") {
+            let (head, tail) = rest.split_at(head_end + 4);
+            assert!(head.starts_with("HTTP/1.1 200"), "unexpected response: {}", head);
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .unwrap()
+                .parse()
+                .unwrap();
+            bodies.push(tail[..length].to_string());
+            rest = &tail[length..];
+        }
+        bodies
+    }
+
+    #[tokio::test]
+    async fn delay_first_lets_the_second_request_finish_first() {
+        let bodies = pipeline(HttpPipeliningMode::DelayFirst, &["/a", "/b"]).await;
+        // `/b` was handled first, but the responses keep request order
+        assert_eq!(bodies, vec!["a:1", "b:0"]);
+    }
+
+    #[tokio::test]
+    async fn reverse_writes_responses_out_of_order() {
+        let bodies = pipeline(HttpPipeliningMode::Reverse, &["/a", "/b"]).await;
+        let paths: Vec<&str> = bodies.iter().map(|b| b.split(':').next().unwrap()).collect();
+        assert_eq!(paths, vec!["b", "a"]);
+    }
+}
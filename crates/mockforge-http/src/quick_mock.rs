@@ -53,10 +53,16 @@
 //!   }
 //! }
 //! ```
+//!
+//! They also carry an RFC 5988 `Link` header with `first`, `prev`, `next`
+//! and `last` relations, so clients that page by following links work too:
+//! ```text
+//! Link: </users?page=1&limit=50>; rel="first", </users?page=2&limit=50>; rel="next", </users?page=2&limit=50>; rel="last"
+//! ```
 
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{OriginalUri, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
     response::Json,
     routing::get,
     Router,
@@ -177,9 +183,10 @@ pub async fn build_quick_router(state: QuickMockState) -> Router {
                 get({
                     let resource = resource.clone();
                     move |State(state): State<QuickMockState>,
+                          OriginalUri(uri): OriginalUri,
                           Query(params): Query<ListQueryParams>| {
                         let resource = resource.clone();
-                        async move { list_handler_impl(state, resource, params).await }
+                        async move { list_handler_impl(state, resource, uri, params).await }
                     }
                 })
                 .post({
@@ -232,8 +239,9 @@ pub async fn build_quick_router(state: QuickMockState) -> Router {
 async fn list_handler_impl(
     state: QuickMockState,
     resource: String,
+    uri: Uri,
     params: ListQueryParams,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<(HeaderMap, Json<Value>), StatusCode> {
     let data = state.data.read().await;
 
     if let Some(items) = data.get(&resource) {
@@ -305,7 +313,12 @@ async fn list_handler_impl(
 
         let total_pages = total.div_ceil(limit);
 
-        Ok(Json(json!({
+        let mut headers = HeaderMap::new();
+        if let Some(link) = pagination_link_header(&uri, page, limit, total_pages) {
+            headers.insert(header::LINK, link);
+        }
+
+        let body = json!({
             "data": paginated_items,
             "pagination": {
                 "page": page,
@@ -315,12 +328,54 @@ async fn list_handler_impl(
                 "hasNext": page < total_pages,
                 "hasPrev": page > 1
             }
-        })))
+        });
+        Ok((headers, Json(body)))
     } else {
         Err(StatusCode::NOT_FOUND)
     }
 }
 
+/// Build the RFC 5988 `Link` header for one page of a list. Each link keeps
+/// the request's filter and sort parameters as sent and only swaps `page`
+/// (and pins `limit`). Returns `None` for an empty list, which has no pages.
+fn pagination_link_header(
+    uri: &Uri,
+    page: usize,
+    limit: usize,
+    total_pages: usize,
+) -> Option<HeaderValue> {
+    if total_pages == 0 {
+        return None;
+    }
+    let kept: Vec<&str> = uri
+        .query()
+        .unwrap_or_default()
+        .split('&')
+        .filter(|pair| {
+            let name = pair.split('=').next().unwrap_or_default();
+            !pair.is_empty() && name != "page" && name != "limit"
+        })
+        .collect();
+    let link = |target: usize, rel: &str| {
+        let mut query = kept.clone();
+        let page_param = format!("page={}", target);
+        let limit_param = format!("limit={}", limit);
+        query.push(&page_param);
+        query.push(&limit_param);
+        format!("<{}?{}>; rel=\"{}\"", uri.path(), query.join("&"), rel)
+    };
+
+    let mut links = vec![link(1, "first")];
+    if page > 1 {
+        links.push(link((page - 1).min(total_pages), "prev"));
+    }
+    if page < total_pages {
+        links.push(link(page + 1, "next"));
+    }
+    links.push(link(total_pages, "last"));
+    HeaderValue::from_str(&links.join(", ")).ok()
+}
+
 /// Implementation for getting a single item by ID
 async fn get_handler_impl(
     state: QuickMockState,
@@ -640,6 +695,46 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn test_pagination_link_header() {
+        let json_data = json!({
+            "users": [
+                {"id": 1, "role": "admin"},
+                {"id": 2, "role": "admin"},
+                {"id": 3, "role": "admin"},
+                {"id": 4, "role": "admin"},
+                {"id": 5, "role": "admin"}
+            ]
+        });
+
+        let state = QuickMockState::from_json(json_data).await.unwrap();
+        let router = build_quick_router(state).await;
+        let link_for = |uri: &'static str| {
+            let router = router.clone();
+            async move {
+                let response = router
+                    .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                assert_eq!(response.status(), StatusCode::OK);
+                response.headers()[header::LINK].to_str().unwrap().to_string()
+            }
+        };
+
+        let first = link_for("/users?role=admin&limit=2").await;
+        assert_eq!(
+            first,
+            "</users?role=admin&page=1&limit=2>; rel=\"first\", \
+             </users?role=admin&page=2&limit=2>; rel=\"next\", \
+             </users?role=admin&page=3&limit=2>; rel=\"last\""
+        );
+
+        let last = link_for("/users?page=3&limit=2").await;
+        assert!(!last.contains("rel=\"next\""));
+        assert!(last.contains("</users?page=2&limit=2>; rel=\"prev\""));
+        assert!(last.contains("</users?page=3&limit=2>; rel=\"last\""));
+    }
+
     #[tokio::test]
     async fn test_filtering() {
         let json_data = json!({
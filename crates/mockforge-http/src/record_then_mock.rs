@@ -0,0 +1,577 @@
+//! Record-then-mock: capture a live API, then serve the capture as a mock.
+//!
+//! A single workflow for "point MockForge at my real API and turn what it
+//! sees into a mock". The router returned by [`RecordThenMock::router`]
+//! starts in the *recording* phase: every request is proxied to the
+//! upstream and the response captured. Once the configured request count or
+//! duration is reached, the captures are persisted as flat custom fixtures
+//! (the same format `CustomFixtureLoader` reads at startup) and the router
+//! swaps itself to the *mocking* phase, serving those fixtures without ever
+//! touching the upstream again. The swap happens in place, so the mock keeps
+//! serving on the same port.
+//!
+//! ## Usage
+//!
+//! ```rust,no_run
+//! use mockforge_http::record_then_mock::{record_then_mock, RecordThenMockConfig};
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let config = RecordThenMockConfig::new("https://api.example.com", "./fixtures")
+//!     .with_max_requests(50)
+//!     .with_duration(Duration::from_secs(300));
+//! let workflow = record_then_mock(config);
+//!
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
+//! axum::serve(listener, workflow.router()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use axum::body::{to_bytes, Body};
+use axum::extract::{Request, State};
+use axum::http::header::{CONTENT_TYPE, HOST};
+use axum::http::{HeaderName, HeaderValue, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use mockforge_openapi::custom_fixture::CustomFixture;
+use mockforge_openapi::{CustomFixtureLoader, RequestFingerprint};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+/// Cap on proxied request/response bodies, matching Axum's default limit
+const MAX_BODY: usize = 16 * 1024 * 1024;
+
+/// Configuration for a record-then-mock session
+#[derive(Debug, Clone)]
+pub struct RecordThenMockConfig {
+    /// Base URL of the real API to record, e.g. `https://api.example.com`
+    pub upstream: String,
+    /// Directory the captured fixtures are written to
+    pub fixtures_dir: PathBuf,
+    /// Stop recording after this many captured responses
+    pub max_requests: Option<usize>,
+    /// Stop recording after this much time has elapsed
+    pub duration: Option<Duration>,
+}
+
+impl RecordThenMockConfig {
+    /// Create a config with no stop condition; call [`RecordThenMock::finish`]
+    /// or set one of the limits to end recording
+    pub fn new(upstream: impl Into<String>, fixtures_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            upstream: upstream.into().trim_end_matches('/').to_string(),
+            fixtures_dir: fixtures_dir.into(),
+            max_requests: None,
+            duration: None,
+        }
+    }
+
+    /// Stop recording after `max_requests` captured responses
+    pub fn with_max_requests(mut self, max_requests: usize) -> Self {
+        self.max_requests = Some(max_requests);
+        self
+    }
+
+    /// Stop recording once `duration` has elapsed
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+}
+
+/// Current phase of a record-then-mock session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordThenMockPhase {
+    /// Proxying to the upstream and capturing responses
+    Recording,
+    /// Serving the captured fixtures
+    Mocking,
+}
+
+/// Errors raised while persisting captured fixtures
+#[derive(Debug, thiserror::Error)]
+pub enum RecordThenMockError {
+    /// Writing a fixture file failed
+    #[error("failed to write fixture {path}: {source}")]
+    Write {
+        /// Fixture file path
+        path: PathBuf,
+        /// Underlying I/O error
+        source: std::io::Error,
+    },
+    /// Loading the written fixtures back failed
+    #[error("failed to load recorded fixtures: {0}")]
+    Load(String),
+}
+
+/// Handle to a record-then-mock session. Cheap to clone (Arc).
+#[derive(Clone)]
+pub struct RecordThenMock {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    config: RecordThenMockConfig,
+    client: reqwest::Client,
+    /// Responses captured so far, in arrival order
+    recorded: Mutex<Vec<CustomFixture>>,
+    /// Set once recording stops; later captures are dropped
+    finishing: AtomicBool,
+    /// `Some` once the session has swapped to the mocking phase
+    loader: RwLock<Option<Arc<CustomFixtureLoader>>>,
+    mocking: Notify,
+}
+
+/// Clears the `finishing` flag when dropped; forgotten once the swap succeeds
+struct FinishingGuard<'a>(&'a AtomicBool);
+
+impl Drop for FinishingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Start a record-then-mock session. If the config sets a `duration`, a
+/// background task ends recording when it elapses.
+pub fn record_then_mock(config: RecordThenMockConfig) -> RecordThenMock {
+    let workflow = RecordThenMock::new(config);
+    if let Some(duration) = workflow.inner.config.duration {
+        let timer = workflow.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(duration).await;
+            if let Err(e) = timer.finish().await {
+                warn!(error = %e, "Record-then-mock failed to persist fixtures");
+            }
+        });
+    }
+    workflow
+}
+
+impl RecordThenMock {
+    /// Create a session in the recording phase. Prefer [`record_then_mock`],
+    /// which also arms the duration limit.
+    pub fn new(config: RecordThenMockConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                client: reqwest::Client::new(),
+                recorded: Mutex::new(Vec::new()),
+                finishing: AtomicBool::new(false),
+                loader: RwLock::new(None),
+                mocking: Notify::new(),
+            }),
+        }
+    }
+
+    /// Router serving the session. It proxies while recording and serves
+    /// fixtures once mocking, so it can stay mounted across the swap.
+    pub fn router(&self) -> Router {
+        Router::new().fallback(handle).with_state(self.clone())
+    }
+
+    /// Current phase of the session
+    pub fn phase(&self) -> RecordThenMockPhase {
+        if self.loader().is_some() {
+            RecordThenMockPhase::Mocking
+        } else {
+            RecordThenMockPhase::Recording
+        }
+    }
+
+    /// Number of responses captured so far
+    pub fn recorded_count(&self) -> usize {
+        self.inner
+            .recorded
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    /// Wait until the session has swapped to the mocking phase
+    pub async fn wait_until_mocking(&self) {
+        loop {
+            let notified = self.inner.mocking.notified();
+            if self.phase() == RecordThenMockPhase::Mocking {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Stop recording, persist the captures to the fixtures directory and
+    /// swap to the mocking phase. Returns the number of fixtures written.
+    /// Calling it again after the first call is a no-op returning 0. If
+    /// persisting fails the session keeps recording, so `finish` can be
+    /// retried without losing captures.
+    pub async fn finish(&self) -> Result<usize, RecordThenMockError> {
+        if self.inner.finishing.swap(true, Ordering::SeqCst) {
+            return Ok(0);
+        }
+        // Reopen recording if persisting fails or the call is cancelled
+        let guard = FinishingGuard(&self.inner.finishing);
+        let count = self.persist_and_swap().await?;
+        std::mem::forget(guard);
+        Ok(count)
+    }
+
+    async fn persist_and_swap(&self) -> Result<usize, RecordThenMockError> {
+        let recorded = self
+            .inner
+            .recorded
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        let fixtures_dir = &self.inner.config.fixtures_dir;
+        tokio::fs::create_dir_all(fixtures_dir).await.map_err(|source| {
+            RecordThenMockError::Write {
+                path: fixtures_dir.clone(),
+                source,
+            }
+        })?;
+        for (index, fixture) in recorded.iter().enumerate() {
+            let path = fixtures_dir.join(fixture_file_name(index, fixture));
+            let content = serde_json::to_vec_pretty(fixture).unwrap_or_default();
+            tokio::fs::write(&path, content)
+                .await
+                .map_err(|source| RecordThenMockError::Write { path, source })?;
+        }
+
+        let mut loader = CustomFixtureLoader::new(fixtures_dir.clone(), true);
+        loader
+            .load_fixtures()
+            .await
+            .map_err(|e| RecordThenMockError::Load(e.to_string()))?;
+        *self.inner.loader.write().unwrap_or_else(|poisoned| poisoned.into_inner()) =
+            Some(Arc::new(loader));
+        self.inner.mocking.notify_waiters();
+
+        info!(
+            "Record-then-mock captured {} response(s) into {}; now serving fixtures",
+            recorded.len(),
+            fixtures_dir.display()
+        );
+        Ok(recorded.len())
+    }
+
+    fn loader(&self) -> Option<Arc<CustomFixtureLoader>> {
+        self.inner
+            .loader
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Capture a response; returns whether the request-count limit is now reached
+    fn record(&self, fixture: CustomFixture) -> bool {
+        if self.inner.finishing.load(Ordering::SeqCst) {
+            return false;
+        }
+        let mut recorded =
+            self.inner.recorded.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        recorded.push(fixture);
+        self.inner.config.max_requests.is_some_and(|max| recorded.len() >= max)
+    }
+}
+
+async fn handle(State(workflow): State<RecordThenMock>, req: Request) -> Response {
+    if let Some(loader) = workflow.loader() {
+        return serve_fixture(&loader, req);
+    }
+
+    match proxy_and_capture(&workflow, req).await {
+        Ok((response, fixture)) => {
+            if workflow.record(fixture) {
+                if let Err(e) = workflow.finish().await {
+                    warn!(error = %e, "Record-then-mock failed to persist fixtures");
+                }
+            }
+            response
+        }
+        Err(message) => {
+            warn!(error = %message, "Record-then-mock upstream request failed");
+            json_response(
+                StatusCode::BAD_GATEWAY,
+                serde_json::json!({
+                    "error": "record_then_mock_upstream_failed",
+                    "message": message,
+                }),
+            )
+        }
+    }
+}
+
+/// Forward the request to the upstream and capture its response as a fixture
+async fn proxy_and_capture(
+    workflow: &RecordThenMock,
+    req: Request,
+) -> Result<(Response, CustomFixture), String> {
+    let (parts, body) = req.into_parts();
+    let body_bytes = to_bytes(body, MAX_BODY).await.map_err(|e| e.to_string())?;
+
+    let path_and_query = parts.uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
+    let url = format!("{}{}", workflow.inner.config.upstream, path_and_query);
+    let method = reqwest::Method::from_bytes(parts.method.as_str().as_bytes())
+        .unwrap_or(reqwest::Method::GET);
+    let mut upstream_req = workflow.inner.client.request(method, &url);
+    for (name, value) in parts.headers.iter() {
+        if name == HOST || is_hop_by_hop(name.as_str()) {
+            continue;
+        }
+        upstream_req = upstream_req.header(name.as_str(), value);
+    }
+    if !body_bytes.is_empty() {
+        upstream_req = upstream_req.body(body_bytes);
+    }
+
+    let upstream_resp = upstream_req.send().await.map_err(|e| e.to_string())?;
+    let status = upstream_resp.status().as_u16();
+    let mut headers = HashMap::new();
+    for (name, value) in upstream_resp.headers() {
+        if is_hop_by_hop(name.as_str()) {
+            continue;
+        }
+        if let Ok(value) = value.to_str() {
+            headers.insert(name.as_str().to_string(), value.to_string());
+        }
+    }
+    let resp_bytes = upstream_resp.bytes().await.map_err(|e| e.to_string())?;
+
+    let fixture = CustomFixture {
+        method: parts.method.as_str().to_string(),
+        path: CustomFixtureLoader::normalize_path(parts.uri.path()),
+        status,
+        response: serde_json::from_slice(&resp_bytes)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&resp_bytes).into_owned())),
+        headers: headers.clone(),
+        delay_ms: 0,
+    };
+
+    let mut response = Response::new(Body::from(resp_bytes));
+    *response.status_mut() = StatusCode::from_u16(status).unwrap_or(StatusCode::BAD_GATEWAY);
+    insert_headers(&mut response, &headers);
+    Ok((response, fixture))
+}
+
+/// Serve a request from the recorded fixtures, or 404 if nothing matches
+fn serve_fixture(loader: &CustomFixtureLoader, req: Request) -> Response {
+    let fingerprint = RequestFingerprint::new(req.method().clone(), req.uri(), req.headers(), None);
+    let Some(fixture) = loader.load_fixture(&fingerprint) else {
+        return json_response(
+            StatusCode::NOT_FOUND,
+            serde_json::json!({
+                "error": "no_recorded_fixture",
+                "message": format!("No recorded response for {} {}", req.method(), req.uri().path()),
+            }),
+        );
+    };
+
+    // Non-JSON bodies were captured as strings; replay them verbatim
+    let body = match &fixture.response {
+        Value::String(text) if !is_json_content(&fixture.headers) => text.clone().into_bytes(),
+        value => serde_json::to_vec(value).unwrap_or_default(),
+    };
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = StatusCode::from_u16(fixture.status).unwrap_or(StatusCode::OK);
+    insert_headers(&mut response, &fixture.headers);
+    response.headers_mut().insert(
+        HeaderName::from_static("x-mockforge-source"),
+        HeaderValue::from_static("recorded-fixture"),
+    );
+    response
+}
+
+fn insert_headers(response: &mut Response, headers: &HashMap<String, String>) {
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) =
+            (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value))
+        {
+            response.headers_mut().insert(name, value);
+        }
+    }
+}
+
+fn json_response(status: StatusCode, body: Value) -> Response {
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+fn is_json_content(headers: &HashMap<String, String>) -> bool {
+    headers
+        .iter()
+        .any(|(name, value)| name.eq_ignore_ascii_case("content-type") && value.contains("json"))
+}
+
+/// `0001-get-api-users.json` — indexed so repeated paths don't collide
+fn fixture_file_name(index: usize, fixture: &CustomFixture) -> String {
+    let slug: String = fixture
+        .path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug.trim_matches('-');
+    let slug = if slug.is_empty() { "root" } else { slug };
+    format!("{:04}-{}-{}.json", index + 1, fixture.method.to_ascii_lowercase(), slug)
+}
+
+fn is_hop_by_hop(name: &str) -> bool {
+    matches!(
+        name.to_ascii_lowercase().as_str(),
+        "connection"
+            | "keep-alive"
+            | "proxy-authenticate"
+            | "proxy-authorization"
+            | "te"
+            | "trailers"
+            | "transfer-encoding"
+            | "upgrade"
+            | "content-length"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use axum::Json;
+    use std::sync::atomic::AtomicUsize;
+    use tower::ServiceExt;
+
+    async fn spawn_upstream(hits: Arc<AtomicUsize>) -> (String, tokio::task::JoinHandle<()>) {
+        let app = Router::new()
+            .route(
+                "/users",
+                get({
+                    let hits = hits.clone();
+                    move || async move {
+                        hits.fetch_add(1, Ordering::SeqCst);
+                        Json(serde_json::json!([{ "id": 1, "name": "Ada" }]))
+                    }
+                }),
+            )
+            .route(
+                "/status",
+                get(move || async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    "upstream ok"
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (format!("http://{}", addr), handle)
+    }
+
+    async fn get_body(router: &Router, uri: &str) -> (StatusCode, Vec<u8>) {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = router.clone().oneshot(req).await.unwrap();
+        let status = res.status();
+        (status, to_bytes(res.into_body(), MAX_BODY).await.unwrap().to_vec())
+    }
+
+    #[test]
+    fn fixture_file_names_are_indexed_slugs() {
+        let fixture = CustomFixture {
+            method: "GET".to_string(),
+            path: "/api/users/{id}".to_string(),
+            status: 200,
+            response: Value::Null,
+            headers: HashMap::new(),
+            delay_ms: 0,
+        };
+        assert_eq!(fixture_file_name(0, &fixture), "0001-get-api-users--id.json");
+    }
+
+    #[tokio::test]
+    async fn records_then_serves_without_upstream() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let (upstream, server) = spawn_upstream(hits.clone()).await;
+        let dir = tempfile::tempdir().unwrap();
+
+        let workflow =
+            record_then_mock(RecordThenMockConfig::new(upstream, dir.path()).with_max_requests(2));
+        let router = workflow.router();
+
+        let (status, users) = get_body(&router, "/users").await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, text) = get_body(&router, "/status").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(text, b"upstream ok");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        workflow.wait_until_mocking().await;
+        assert_eq!(workflow.phase(), RecordThenMockPhase::Mocking);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+
+        // Take the upstream away entirely; the mock must still answer
+        server.abort();
+
+        let (status, replayed) = get_body(&router, "/users").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            serde_json::from_slice::<Value>(&replayed).unwrap(),
+            serde_json::from_slice::<Value>(&users).unwrap()
+        );
+        let (status, replayed) = get_body(&router, "/status").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(replayed, b"upstream ok");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let (status, _) = get_body(&router, "/unrecorded").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn failed_finish_keeps_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        // A file where the fixtures directory should go makes persisting fail
+        let fixtures_dir = dir.path().join("fixtures");
+        std::fs::write(&fixtures_dir, b"").unwrap();
+        let workflow =
+            RecordThenMock::new(RecordThenMockConfig::new("http://127.0.0.1:9", &fixtures_dir));
+        let fixture = |path: &str| CustomFixture {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            status: 200,
+            response: Value::Null,
+            headers: HashMap::new(),
+            delay_ms: 0,
+        };
+
+        workflow.record(fixture("/a"));
+        assert!(workflow.finish().await.is_err());
+        assert_eq!(workflow.phase(), RecordThenMockPhase::Recording);
+
+        // Captures keep accumulating, and a retry persists all of them
+        workflow.record(fixture("/b"));
+        assert_eq!(workflow.recorded_count(), 2);
+        std::fs::remove_file(&fixtures_dir).unwrap();
+        assert_eq!(workflow.finish().await.unwrap(), 2);
+        assert_eq!(workflow.phase(), RecordThenMockPhase::Mocking);
+    }
+
+    #[tokio::test]
+    async fn finish_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let workflow =
+            RecordThenMock::new(RecordThenMockConfig::new("http://127.0.0.1:9", dir.path()));
+        assert_eq!(workflow.phase(), RecordThenMockPhase::Recording);
+        assert_eq!(workflow.finish().await.unwrap(), 0);
+        assert_eq!(workflow.phase(), RecordThenMockPhase::Mocking);
+        assert_eq!(workflow.finish().await.unwrap(), 0);
+    }
+}
@@ -16,6 +16,10 @@ use std::net::SocketAddr;
 use std::time::Instant;
 use tracing::info;
 
+/// Request bodies up to this size are kept on the log entry (for curl
+/// reproduction); larger ones are streamed through and not recorded
+const MAX_LOGGED_BODY: usize = 64 * 1024;
+
 /// HTTP request logging middleware
 pub async fn log_http_requests(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
@@ -51,6 +55,19 @@ pub async fn log_http_requests(
     // Must be done before calling next.run() which consumes the request
     let reality_metadata = req.extensions().get::<RealityTraceMetadata>().cloned();
 
+    // Keep the concrete URI and a copy of a small text body so the entry can
+    // be replayed with curl
+    let request_uri = req.uri().path_and_query().map(|pq| pq.as_str()).unwrap_or(&uri).to_string();
+    let (parts, body) = req.into_parts();
+    let (captured, body) =
+        crate::middleware::body_capture::capture_prefix(body, MAX_LOGGED_BODY).await;
+    let req = Request::from_parts(parts, body);
+    let request_body = if captured.truncated || captured.bytes.is_empty() {
+        None
+    } else {
+        String::from_utf8(captured.bytes.to_vec()).ok()
+    };
+
     // Call the next middleware/handler
     let response = next.run(req).await;
 
@@ -93,6 +110,10 @@ pub async fn log_http_requests(
 
     // Attach reality metadata if available
     log_entry.reality_metadata = reality_metadata;
+    log_entry.metadata.insert("request_uri".to_string(), request_uri);
+    if let Some(request_body) = request_body {
+        log_entry.metadata.insert("request_body".to_string(), request_body);
+    }
 
     // Extract response generation trace from response extensions (set by handler)
     if let Some(trace) = response.extensions().get::<ResponseGenerationTrace>() {
@@ -102,7 +123,9 @@ pub async fn log_http_requests(
         }
     }
 
-    // Log to centralized logger
+    // Queue for external export (never waits on the sink), then log to the
+    // centralized logger
+    crate::log_sink::export_log_entry(&log_entry);
     log_request_global(log_entry).await;
 
     // Also log to console for debugging (include query params if present)
@@ -23,18 +23,33 @@
 //! - `PUT    /__mockforge/api/route-chaos`           — replace all rules
 //! - `POST   /__mockforge/api/route-chaos/route`     — add or upsert one rule
 //! - `DELETE /__mockforge/api/route-chaos/route`     — remove one rule by method+path
+//!
+//! A rule's `latency.body_transfer_ms` spreads the response body over that
+//! duration (see [`throttle_body`]), separately from the delay before the
+//! first byte.
+//!
+//! The state also keeps a snapshot of the static rules and per-rule counters
+//! of injected faults, which back the chaos status feed
+//! (`GET /__mockforge/api/chaos/status`).
 
+use crate::middleware::server_timing::{ServerTimings, ROUTE_CHAOS_METRIC};
 use axum::body::Body;
-use axum::extract::{Request, State};
-use axum::http::{header::CONTENT_TYPE, HeaderName, HeaderValue, StatusCode};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{
+    header::{CONTENT_TYPE, RETRY_AFTER},
+    HeaderName, HeaderValue, StatusCode,
+};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::{Json, Router};
-use mockforge_core::config::RouteConfig;
+use mockforge_core::config::{RouteConfig, RouteFaultType, RouteLatencyConfig};
 use mockforge_route_chaos::RouteChaosInjector;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
 use tracing::warn;
 
 /// Shared, mutable set of route-chaos rules. Cheap to clone (Arc).
@@ -49,6 +64,51 @@ struct Inner {
     /// Rebuilt every time `routes` mutates. `None` when the rule set is
     /// empty — saves the matcher walk on the hot path.
     injector: RwLock<Option<RouteChaosInjector>>,
+    /// Rules baked into the static per-route handlers, for reporting only.
+    static_routes: RwLock<Vec<RouteConfig>>,
+    /// Faults injected so far, keyed by (source, METHOD, path pattern).
+    fault_counts: RwLock<HashMap<(RuleSource, String, String), u64>>,
+}
+
+/// Where a route-chaos rule comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleSource {
+    /// `routes` in the YAML config, baked in at startup
+    Static,
+    /// Added through the runtime route-chaos API
+    Runtime,
+}
+
+/// One rule's entry in the chaos status feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteChaosStatus {
+    /// HTTP method
+    pub method: String,
+    /// Route path pattern
+    pub path: String,
+    /// Static config or runtime API
+    pub source: RuleSource,
+    /// Fault probability (0.0-1.0), when fault injection is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fault_probability: Option<f64>,
+    /// Fault types picked from when a fault fires
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fault_types: Vec<RouteFaultType>,
+    /// Latency profile, when latency injection is enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency: Option<RouteLatencyConfig>,
+    /// Faults injected by this rule so far
+    pub faults_injected: u64,
+}
+
+/// Route-chaos part of the chaos status feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouteChaosSummary {
+    /// Rules with fault or latency injection enabled; static rules first
+    pub routes: Vec<RouteChaosStatus>,
+    /// Faults injected across all rules
+    pub faults_injected_total: u64,
 }
 
 impl RuntimeRouteChaosState {
@@ -60,6 +120,8 @@ impl RuntimeRouteChaosState {
             inner: Arc::new(Inner {
                 routes: RwLock::new(initial),
                 injector: RwLock::new(injector),
+                static_routes: RwLock::new(Vec::new()),
+                fault_counts: RwLock::new(HashMap::new()),
             }),
         }
     }
@@ -120,6 +182,54 @@ impl RuntimeRouteChaosState {
         removed
     }
 
+    /// Record the static rules so the status feed can report them.
+    pub fn set_static_routes(&self, routes: Vec<RouteConfig>) {
+        *self.inner.static_routes.write().expect("route-chaos state poisoned") = routes;
+    }
+
+    /// Count a fault injected by the rule `method path` from `source`.
+    pub fn record_fault(&self, source: RuleSource, method: &str, path: &str) {
+        let mut counts = self.inner.fault_counts.write().expect("route-chaos state poisoned");
+        *counts
+            .entry((source, method.to_ascii_uppercase(), path.to_string()))
+            .or_insert(0) += 1;
+    }
+
+    /// Rules with fault or latency injection enabled, with their counters.
+    pub fn summary(&self) -> RouteChaosSummary {
+        let counts = self.inner.fault_counts.read().expect("route-chaos state poisoned");
+        let static_routes = self.inner.static_routes.read().expect("route-chaos state poisoned");
+        let runtime_routes = self.list();
+
+        let routes: Vec<RouteChaosStatus> = static_routes
+            .iter()
+            .map(|r| (RuleSource::Static, r))
+            .chain(runtime_routes.iter().map(|r| (RuleSource::Runtime, r)))
+            .filter_map(|(source, route)| {
+                let fault = route.fault_injection.as_ref().filter(|f| f.enabled);
+                let latency = route.latency.as_ref().filter(|l| l.enabled);
+                if fault.is_none() && latency.is_none() {
+                    return None;
+                }
+                let key = (source, route.method.to_ascii_uppercase(), route.path.clone());
+                Some(RouteChaosStatus {
+                    method: route.method.to_ascii_uppercase(),
+                    path: route.path.clone(),
+                    source,
+                    fault_probability: fault.map(|f| f.probability),
+                    fault_types: fault.map(|f| f.fault_types.clone()).unwrap_or_default(),
+                    latency: latency.cloned(),
+                    faults_injected: counts.get(&key).copied().unwrap_or(0),
+                })
+            })
+            .collect();
+
+        RouteChaosSummary {
+            routes,
+            faults_injected_total: counts.values().sum(),
+        }
+    }
+
     /// Snapshot of the current injector. Returns None when the rule set
     /// is empty — the middleware fast-paths off this.
     fn current_injector(&self) -> Option<RouteChaosInjector> {
@@ -154,8 +264,13 @@ pub async fn runtime_route_chaos_middleware(
     use mockforge_core::priority_handler::RouteChaosInjectorTrait;
     let method = req.method().clone();
     let uri = req.uri().clone();
+    let headers = req.headers().clone();
+    let client = req.extensions().get::<ConnectInfo<SocketAddr>>().map(|info| info.0.ip());
 
-    if let Some(fault) = injector.get_fault_response(&method, &uri) {
+    if let Some(fault) = injector.get_fault_response_for_client(&method, &uri, &headers, client) {
+        if let Some(route) = injector.matched_route_with_headers(&method, &uri, &headers) {
+            state.record_fault(RuleSource::Runtime, &route.method, &route.path);
+        }
         let status =
             StatusCode::from_u16(fault.status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
         let body = serde_json::to_vec(&serde_json::json!({
@@ -171,14 +286,66 @@ pub async fn runtime_route_chaos_middleware(
             HeaderName::from_static("x-mockforge-source"),
             HeaderValue::from_static("route-chaos-runtime"),
         );
+        if let Some(secs) = fault.retry_after_secs {
+            resp.headers_mut().insert(RETRY_AFTER, secs.into());
+        }
         return resp;
     }
 
-    if let Err(e) = injector.inject_latency(&method, &uri).await {
+    let body_transfer = injector
+        .matched_route_with_headers(&method, &uri, &headers)
+        .and_then(|route| route.latency.as_ref())
+        .and_then(body_transfer_duration);
+
+    let started = Instant::now();
+    if let Err(e) = injector.inject_latency_with_headers(&method, &uri, &headers).await {
         warn!(error = %e, "Runtime route-chaos latency injection errored; continuing");
     }
+    ServerTimings::record_in(req.extensions(), ROUTE_CHAOS_METRIC, started.elapsed());
+
+    let response = next.run(req).await;
+    match body_transfer {
+        Some(duration) => throttle_body(response, duration).await,
+        None => response,
+    }
+}
+
+/// Pieces a throttled body is split into
+const BODY_TRANSFER_PIECES: usize = 10;
 
-    next.run(req).await
+/// The body transfer time a latency config asks for, if any
+pub fn body_transfer_duration(latency: &RouteLatencyConfig) -> Option<Duration> {
+    latency
+        .body_transfer_ms
+        .filter(|ms| latency.enabled && *ms > 0)
+        .map(Duration::from_millis)
+}
+
+/// Re-send a response's body in pieces spread evenly over `duration`: the
+/// headers go out at once, the last byte about `duration` later
+pub async fn throttle_body(response: Response, duration: Duration) -> Response {
+    use futures::StreamExt;
+
+    let (parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            warn!(error = %e, "Failed to buffer response body for throttling");
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let piece_len = bytes.len().div_ceil(BODY_TRANSFER_PIECES).max(1);
+    let pieces: Vec<axum::body::Bytes> = if bytes.is_empty() {
+        vec![bytes]
+    } else {
+        bytes.chunks(piece_len).map(|piece| bytes.slice_ref(piece)).collect()
+    };
+    let pause = duration / pieces.len() as u32;
+    let stream = futures::stream::iter(pieces).then(move |piece| async move {
+        tokio::time::sleep(pause).await;
+        Ok::<_, std::convert::Infallible>(piece)
+    });
+    Response::from_parts(parts, Body::from_stream(stream))
 }
 
 #[derive(Debug, Serialize)]
@@ -256,8 +423,8 @@ mod tests {
             request: None,
             response: RouteResponseConfig {
                 status: 200,
-                headers: Default::default(),
                 body: None,
+                ..Default::default()
             },
             fault_injection: None,
             latency: None,
@@ -313,6 +480,33 @@ mod tests {
         assert_eq!(rules[0].path, "/b");
     }
 
+    #[test]
+    fn summary_reports_enabled_rules_with_counters() {
+        use mockforge_core::config::RouteFaultInjectionConfig;
+
+        let state = RuntimeRouteChaosState::new(Vec::new());
+        let mut faulty = dummy_route("get", "/orders");
+        faulty.fault_injection = Some(RouteFaultInjectionConfig {
+            enabled: true,
+            probability: 0.3,
+            fault_types: Vec::new(),
+            weights: None,
+            request_matcher: None,
+        });
+        state.set_static_routes(vec![faulty, dummy_route("GET", "/plain")]);
+        state.record_fault(RuleSource::Static, "GET", "/orders");
+        state.record_fault(RuleSource::Static, "get", "/orders");
+
+        let summary = state.summary();
+        assert_eq!(summary.routes.len(), 1, "rules without chaos are omitted");
+        let route = &summary.routes[0];
+        assert_eq!((route.method.as_str(), route.path.as_str()), ("GET", "/orders"));
+        assert_eq!(route.source, RuleSource::Static);
+        assert_eq!(route.fault_probability, Some(0.3));
+        assert_eq!(route.faults_injected, 2);
+        assert_eq!(summary.faults_injected_total, 2);
+    }
+
     #[test]
     fn replace_all_swaps_atomically() {
         let state = RuntimeRouteChaosState::new(vec![dummy_route("GET", "/a")]);
@@ -0,0 +1,318 @@
+//! Request-dependent delays for custom routes
+//!
+//! A route's `response.delay.expression` is a small arithmetic expression
+//! (`+ - * /`, parentheses, unary minus) over numbers and request values,
+//! e.g. `body.size_kb * 10`. It is parsed once when the route is built and
+//! evaluated per request; the result is clamped to `0..=max_ms`.
+//!
+//! Request values are `query.<name>`, `header.<name>`, `body.<field>`
+//! (dot-separated for nested JSON fields), `body.size_bytes` and
+//! `body.size_kb`. Missing or non-numeric values evaluate to 0. A `-` after
+//! a value is subtraction (`body.size_kb-1`), except in header names, which
+//! may contain it: write `header.x-items - 1` to subtract from a header.
+
+use axum::body::Body;
+use axum::http::{HeaderName, Request};
+use mockforge_core::config::RouteDelayConfig;
+use serde_json::Value;
+use std::time::Duration;
+
+/// A request value referenced by a delay expression
+#[derive(Debug, Clone, PartialEq)]
+enum Variable {
+    Query(String),
+    Header(HeaderName),
+    /// JSON pointer into the request body
+    BodyField(String),
+    BodySizeBytes,
+    BodySizeKb,
+}
+
+impl Variable {
+    fn parse(name: &str) -> Result<Self, String> {
+        let (scope, rest) = name
+            .split_once('.')
+            .filter(|(_, rest)| !rest.is_empty())
+            .ok_or_else(|| format!("Unknown value '{}'", name))?;
+        match scope {
+            "query" => Ok(Self::Query(rest.to_string())),
+            "header" => HeaderName::from_bytes(rest.as_bytes())
+                .map(Self::Header)
+                .map_err(|_| format!("Invalid header name '{}'", rest)),
+            "body" => Ok(match rest {
+                "size_bytes" => Self::BodySizeBytes,
+                "size_kb" => Self::BodySizeKb,
+                field => Self::BodyField(format!("/{}", field.replace('.', "/"))),
+            }),
+            _ => Err(format!("Unknown value '{}'", name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Number(f64),
+    Variable(Variable),
+    Neg(Box<Expr>),
+    Binary(char, Box<Expr>, Box<Expr>),
+}
+
+/// Request values an expression is evaluated against
+struct Context<'a> {
+    req: &'a Request<Body>,
+    body: &'a [u8],
+    json_body: Option<Value>,
+}
+
+impl Context<'_> {
+    fn value(&self, variable: &Variable) -> f64 {
+        let parse = |s: &str| s.trim().parse::<f64>().unwrap_or(0.0);
+        match variable {
+            Variable::Query(name) => self
+                .req
+                .uri()
+                .query()
+                .and_then(|q| {
+                    url::form_urlencoded::parse(q.as_bytes())
+                        .find(|(k, _)| k == name)
+                        .map(|(_, v)| parse(&v))
+                })
+                .unwrap_or(0.0),
+            Variable::Header(name) => {
+                self.req.headers().get(name).and_then(|v| v.to_str().ok()).map_or(0.0, parse)
+            }
+            Variable::BodyField(pointer) => {
+                match self.json_body.as_ref().and_then(|b| b.pointer(pointer)) {
+                    Some(Value::Number(n)) => n.as_f64().unwrap_or(0.0),
+                    Some(Value::String(s)) => parse(s),
+                    _ => 0.0,
+                }
+            }
+            Variable::BodySizeBytes => self.body.len() as f64,
+            Variable::BodySizeKb => self.body.len() as f64 / 1024.0,
+        }
+    }
+}
+
+impl Expr {
+    fn eval(&self, ctx: &Context<'_>) -> f64 {
+        match self {
+            Self::Number(n) => *n,
+            Self::Variable(variable) => ctx.value(variable),
+            Self::Neg(inner) => -inner.eval(ctx),
+            Self::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.eval(ctx), rhs.eval(ctx));
+                match op {
+                    '+' => lhs + rhs,
+                    '-' => lhs - rhs,
+                    '*' => lhs * rhs,
+                    _ => lhs / rhs,
+                }
+            }
+        }
+    }
+
+    /// Whether any referenced request value satisfies `pred`
+    fn references(&self, pred: &impl Fn(&Variable) -> bool) -> bool {
+        match self {
+            Self::Number(_) => false,
+            Self::Variable(variable) => pred(variable),
+            Self::Neg(inner) => inner.references(pred),
+            Self::Binary(_, lhs, rhs) => lhs.references(pred) || rhs.references(pred),
+        }
+    }
+}
+
+/// Recursive-descent parser over the expression text
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&mut self) -> Option<char> {
+        self.input = self.input.trim_start();
+        self.input.chars().next()
+    }
+
+    fn bump(&mut self) {
+        if let Some(c) = self.input.chars().next() {
+            self.input = &self.input[c.len_utf8()..];
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.bump();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+        Ok(lhs)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.bump();
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.factor()?));
+        }
+        Ok(lhs)
+    }
+
+    fn factor(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some('-') => {
+                self.bump();
+                Ok(Expr::Neg(Box::new(self.factor()?)))
+            }
+            Some('(') => {
+                self.bump();
+                let inner = self.expr()?;
+                if self.peek() != Some(')') {
+                    return Err("Expected ')'".to_string());
+                }
+                self.bump();
+                Ok(inner)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let token = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                token
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| format!("Invalid number '{}'", token))
+            }
+            Some(c) if c.is_ascii_alphabetic() => {
+                // `-` is subtraction except inside a header name
+                let header = self.input.starts_with("header.");
+                let token = self.take_while(|c| {
+                    c.is_ascii_alphanumeric() || matches!(c, '.' | '_') || (header && c == '-')
+                });
+                Variable::parse(token).map(Expr::Variable)
+            }
+            Some(c) => Err(format!("Unexpected '{}'", c)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+
+    fn take_while(&mut self, accept: impl Fn(char) -> bool) -> &'a str {
+        let len = self.input.find(|c| !accept(c)).unwrap_or(self.input.len());
+        let (token, rest) = self.input.split_at(len);
+        self.input = rest;
+        token
+    }
+}
+
+/// A parsed `response.delay` expression
+#[derive(Debug)]
+pub struct RouteDelay {
+    expr: Expr,
+    max_ms: u64,
+}
+
+impl RouteDelay {
+    /// Parse the configured expression
+    pub fn new(config: &RouteDelayConfig) -> Result<Self, String> {
+        let mut parser = Parser {
+            input: &config.expression,
+        };
+        let expr = parser.expr()?;
+        if let Some(c) = parser.peek() {
+            return Err(format!(
+                "Unexpected '{}' at offset {}",
+                c,
+                config.expression.len() - parser.input.len()
+            ));
+        }
+        Ok(Self {
+            expr,
+            max_ms: config.max_ms,
+        })
+    }
+
+    /// Whether the expression reads the request body
+    pub fn reads_body(&self) -> bool {
+        self.expr.references(&|v| {
+            matches!(v, Variable::BodyField(_) | Variable::BodySizeBytes | Variable::BodySizeKb)
+        })
+    }
+
+    /// Evaluate the delay for a request whose buffered body is `body`
+    pub fn duration(&self, req: &Request<Body>, body: &[u8]) -> Duration {
+        let json_body = self
+            .expr
+            .references(&|v| matches!(v, Variable::BodyField(_)))
+            .then(|| serde_json::from_slice(body).ok())
+            .flatten();
+        let ms = self.expr.eval(&Context {
+            req,
+            body,
+            json_body,
+        });
+        let ms = if ms.is_nan() {
+            0.0
+        } else {
+            ms.clamp(0.0, self.max_ms as f64)
+        };
+        Duration::from_millis(ms as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_delay(expression: &str, max_ms: u64) -> Result<RouteDelay, String> {
+        RouteDelay::new(&RouteDelayConfig {
+            expression: expression.to_string(),
+            max_ms,
+        })
+    }
+
+    fn request(uri: &str) -> Request<Body> {
+        Request::builder().uri(uri).header("x-items", "4").body(Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn evaluates_request_values_with_precedence() {
+        let delay = parse_delay("10 + query.n * (header.x-items - 1) + body.order.qty / 2", 10_000)
+            .unwrap();
+        let body = br#"{"order": {"qty": 8}}"#;
+        assert!(delay.reads_body());
+        assert_eq!(delay.duration(&request("/?n=5"), body), Duration::from_millis(29));
+        // Missing values count as 0
+        assert_eq!(delay.duration(&request("/"), b""), Duration::from_millis(10));
+    }
+
+    #[test]
+    fn minus_after_a_value_is_subtraction() {
+        let body = br#"{"qty": 8}"#;
+        let delay = parse_delay("body.qty-1", 1_000).unwrap();
+        assert_eq!(delay.duration(&request("/"), body), Duration::from_millis(7));
+        let delay = parse_delay("query.n-1", 1_000).unwrap();
+        assert_eq!(delay.duration(&request("/?n=5"), b""), Duration::from_millis(4));
+        let delay = parse_delay("body.size_kb-1+2", 1_000).unwrap();
+        assert_eq!(delay.duration(&request("/"), &[0; 2048]), Duration::from_millis(3));
+        // Header names keep their dashes
+        let delay = parse_delay("header.x-items-1", 1_000).unwrap();
+        assert_eq!(delay.duration(&request("/"), b""), Duration::ZERO);
+        let delay = parse_delay("header.x-items - 1", 1_000).unwrap();
+        assert_eq!(delay.duration(&request("/"), b""), Duration::from_millis(3));
+    }
+
+    #[test]
+    fn result_is_clamped() {
+        let delay = parse_delay("body.size_bytes * 100 - 50", 1_000).unwrap();
+        assert_eq!(delay.duration(&request("/"), b""), Duration::ZERO);
+        assert_eq!(delay.duration(&request("/"), &[0; 64]), Duration::from_millis(1_000));
+        // Division by zero saturates at the cap
+        let delay = parse_delay("1 / 0", 500).unwrap();
+        assert_eq!(delay.duration(&request("/"), b""), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        assert!(parse_delay("query.n *", 100).is_err());
+        assert!(parse_delay("(1 + 2", 100).is_err());
+        assert!(parse_delay("cookie.session", 100).is_err());
+        assert!(parse_delay("1 2", 100).is_err());
+    }
+}
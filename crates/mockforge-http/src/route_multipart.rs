@@ -0,0 +1,173 @@
+//! Multipart form matching for custom routes
+//!
+//! `multipart/form-data` request bodies are parsed into their text fields and
+//! file uploads so response variants can be gated on them (`multipart`
+//! conditions) and templates can echo them (`{{multipart.<field>}}`). File
+//! contents are not kept; only each upload's file name, Content-Type and size.
+
+use axum::body::{Body, Bytes};
+use axum::extract::{FromRequest, Multipart};
+use axum::http::{header::CONTENT_TYPE, Request};
+use mockforge_core::config::{RouteMultipartFileMatch, RouteMultipartMatch};
+use std::collections::HashMap;
+
+/// One uploaded file part
+#[derive(Debug, Clone, Default)]
+pub struct MultipartFile {
+    /// File name sent by the client, if any
+    pub file_name: Option<String>,
+    /// Content-Type of the part, if any
+    pub content_type: Option<String>,
+    /// Size of the file contents in bytes
+    pub size: usize,
+}
+
+/// The fields and files of a parsed `multipart/form-data` body
+#[derive(Debug, Clone, Default)]
+pub struct MultipartForm {
+    /// Text fields by name (the last value wins for repeated names)
+    pub fields: HashMap<String, String>,
+    /// File parts by field name
+    pub files: HashMap<String, MultipartFile>,
+}
+
+/// Whether `content_type` is `multipart/form-data`
+pub fn is_multipart(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|ct| {
+        ct.split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .eq_ignore_ascii_case("multipart/form-data")
+    })
+}
+
+impl MultipartForm {
+    /// Parse a buffered `multipart/form-data` body. Malformed bodies yield
+    /// `None`.
+    pub async fn parse(content_type: &str, body: Bytes) -> Option<Self> {
+        let req = Request::builder()
+            .header(CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .ok()?;
+        let mut multipart = Multipart::from_request(req, &()).await.ok()?;
+
+        let mut form = Self::default();
+        while let Some(field) = multipart.next_field().await.ok()? {
+            let Some(name) = field.name().map(str::to_string) else {
+                continue;
+            };
+            if field.file_name().is_some() {
+                let file_name = field.file_name().map(str::to_string);
+                let content_type = field.content_type().map(str::to_string);
+                let size = field.bytes().await.ok()?.len();
+                form.files.insert(
+                    name,
+                    MultipartFile {
+                        file_name,
+                        content_type,
+                        size,
+                    },
+                );
+            } else {
+                form.fields.insert(name, field.text().await.ok()?);
+            }
+        }
+        Some(form)
+    }
+
+    /// Whether the form satisfies every field and file condition
+    pub fn matches(&self, conditions: &RouteMultipartMatch) -> bool {
+        conditions
+            .fields
+            .iter()
+            .all(|(name, value)| self.fields.get(name) == Some(value))
+            && conditions.files.iter().all(|(name, condition)| {
+                self.files.get(name).is_some_and(|file| file_matches(file, condition))
+            })
+    }
+}
+
+fn file_matches(file: &MultipartFile, condition: &RouteMultipartFileMatch) -> bool {
+    let Some(expected) = &condition.content_type else {
+        return true;
+    };
+    let Some(actual) = &file.content_type else {
+        return false;
+    };
+    let actual = actual.split(';').next().unwrap_or_default().trim();
+    match expected.strip_suffix("/*") {
+        Some(top_level) => actual
+            .split_once('/')
+            .is_some_and(|(actual_top, _)| actual_top.eq_ignore_ascii_case(top_level)),
+        None => actual.eq_ignore_ascii_case(expected.trim()),
+    }
+}
+
+/// Narrow `eligible` by multipart conditions: the variants whose conditions
+/// the form satisfies, or — when none does — the variants without conditions
+pub fn multipart_matched_variants(
+    eligible: Vec<usize>,
+    conditions: &[Option<RouteMultipartMatch>],
+    form: Option<&MultipartForm>,
+) -> Vec<usize> {
+    let (gated, ungated): (Vec<usize>, Vec<usize>) =
+        eligible.into_iter().partition(|&i| conditions[i].is_some());
+    let matched: Vec<usize> = gated
+        .into_iter()
+        .filter(|&i| {
+            form.zip(conditions[i].as_ref()).is_some_and(|(form, cond)| form.matches(cond))
+        })
+        .collect();
+    if matched.is_empty() {
+        ungated
+    } else {
+        matched
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDARY: &str = "XBOUNDARY";
+
+    fn body() -> Bytes {
+        Bytes::from(format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"plan\"\r\n\r\npro\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"me.png\"\r\n\
+             Content-Type: image/png\r\n\r\nPNGDATA\r\n--{b}--\r\n",
+            b = BOUNDARY
+        ))
+    }
+
+    #[tokio::test]
+    async fn parses_fields_and_files() {
+        let form =
+            MultipartForm::parse(&format!("multipart/form-data; boundary={BOUNDARY}"), body())
+                .await
+                .unwrap();
+        assert_eq!(form.fields["plan"], "pro");
+        let avatar = &form.files["avatar"];
+        assert_eq!(avatar.file_name.as_deref(), Some("me.png"));
+        assert_eq!(avatar.content_type.as_deref(), Some("image/png"));
+        assert_eq!(avatar.size, 7);
+    }
+
+    #[test]
+    fn file_content_type_conditions() {
+        let file = MultipartFile {
+            file_name: None,
+            content_type: Some("image/PNG".to_string()),
+            size: 0,
+        };
+        let condition = |ct: Option<&str>| RouteMultipartFileMatch {
+            content_type: ct.map(str::to_string),
+        };
+        assert!(file_matches(&file, &condition(None)));
+        assert!(file_matches(&file, &condition(Some("image/png"))));
+        assert!(file_matches(&file, &condition(Some("image/*"))));
+        assert!(!file_matches(&file, &condition(Some("application/pdf"))));
+        assert!(!file_matches(&file, &condition(Some("text/*"))));
+    }
+}
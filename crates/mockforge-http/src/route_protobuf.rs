@@ -0,0 +1,174 @@
+//! Protobuf bodies for custom routes
+//!
+//! Some REST-style APIs exchange `application/x-protobuf` bodies instead of
+//! JSON. A route's `response.protobuf` names a compiled descriptor set and
+//! the request/response message types; [`RouteProtobufCodec`] decodes
+//! request bodies into JSON (so templates can read them as `request.body`)
+//! and encodes the route's JSON response body into protobuf. Both directions
+//! use the canonical proto3 JSON mapping.
+//!
+//! Protobuf requests to a route without a request message are rejected with
+//! 415.
+
+use crate::route_validation::BodyValidationError;
+use mockforge_core::config::RouteProtobufConfig;
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use serde_json::Value;
+
+/// Media type of protobuf response bodies
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+/// Whether a `Content-Type` header value denotes a protobuf body
+pub fn is_protobuf(content_type: Option<&str>) -> bool {
+    let media_type = content_type
+        .and_then(|ct| ct.split(';').next())
+        .map(|ct| ct.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+    matches!(
+        media_type.as_str(),
+        "application/x-protobuf" | "application/protobuf" | "application/vnd.google.protobuf"
+    )
+}
+
+/// Resolved message descriptors for one route's `response.protobuf`
+#[derive(Debug)]
+pub struct RouteProtobufCodec {
+    request: Option<MessageDescriptor>,
+    response: Option<MessageDescriptor>,
+}
+
+impl RouteProtobufCodec {
+    /// Load the descriptor set named by the config and resolve its messages
+    pub fn new(config: &RouteProtobufConfig) -> Result<Self, String> {
+        let bytes = std::fs::read(&config.descriptor_set).map_err(|e| {
+            format!("Failed to read descriptor set '{}': {}", config.descriptor_set, e)
+        })?;
+        Self::from_descriptor_set(
+            &bytes,
+            config.request_message.as_deref(),
+            config.response_message.as_deref(),
+        )
+    }
+
+    /// Resolve messages from an encoded `FileDescriptorSet`
+    pub fn from_descriptor_set(
+        bytes: &[u8],
+        request_message: Option<&str>,
+        response_message: Option<&str>,
+    ) -> Result<Self, String> {
+        let pool = DescriptorPool::decode(bytes)
+            .map_err(|e| format!("Invalid protobuf descriptor set: {}", e))?;
+        let resolve = |name: Option<&str>| {
+            name.map(|name| {
+                pool.get_message_by_name(name.trim_start_matches('.'))
+                    .ok_or_else(|| format!("Message '{}' not found in descriptor set", name))
+            })
+            .transpose()
+        };
+        Ok(Self {
+            request: resolve(request_message)?,
+            response: resolve(response_message)?,
+        })
+    }
+
+    /// Whether protobuf request bodies can be decoded
+    pub fn decodes_requests(&self) -> bool {
+        self.request.is_some()
+    }
+
+    /// Decode a protobuf request body into its JSON form
+    pub fn decode_request(&self, body: &[u8]) -> Result<Value, BodyValidationError> {
+        let descriptor = self.request.clone().ok_or_else(|| {
+            BodyValidationError::UnsupportedMediaType(
+                "Route has no protobuf request message configured".to_string(),
+            )
+        })?;
+        let message = DynamicMessage::decode(descriptor, body).map_err(|e| {
+            BodyValidationError::Invalid(format!("Request body is not a valid protobuf: {}", e))
+        })?;
+        serde_json::to_value(&message).map_err(|e| BodyValidationError::Invalid(e.to_string()))
+    }
+
+    /// Encode a JSON response body with the response message, if one is
+    /// configured
+    pub fn encode_response(&self, body: &Value) -> Option<Result<Vec<u8>, String>> {
+        let descriptor = self.response.clone()?;
+        Some(
+            DynamicMessage::deserialize(descriptor, body)
+                .map(|message| message.encode_to_vec())
+                .map_err(|e| format!("Response body does not match the protobuf message: {}", e)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+
+    /// `shop.v1.Order { string id = 1; int32 quantity = 2; }`
+    fn descriptor_set() -> Vec<u8> {
+        let field = |name: &str, number: i32, ty: Type| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(ty as i32),
+            json_name: Some(name.to_string()),
+            ..Default::default()
+        };
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("shop.proto".to_string()),
+                package: Some("shop.v1".to_string()),
+                syntax: Some("proto3".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Order".to_string()),
+                    field: vec![
+                        field("id", 1, Type::String),
+                        field("quantity", 2, Type::Int32),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+        .encode_to_vec()
+    }
+
+    #[test]
+    fn round_trips_a_message() {
+        let codec = RouteProtobufCodec::from_descriptor_set(
+            &descriptor_set(),
+            Some("shop.v1.Order"),
+            Some("shop.v1.Order"),
+        )
+        .unwrap();
+
+        let json = serde_json::json!({ "id": "A-1", "quantity": 3 });
+        let encoded = codec.encode_response(&json).unwrap().unwrap();
+        assert_eq!(codec.decode_request(&encoded).unwrap(), json);
+    }
+
+    #[test]
+    fn unknown_message_is_rejected() {
+        let err = RouteProtobufCodec::from_descriptor_set(
+            &descriptor_set(),
+            Some("shop.v1.Missing"),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.contains("shop.v1.Missing"));
+    }
+
+    #[test]
+    fn protobuf_media_types() {
+        assert!(is_protobuf(Some("application/x-protobuf")));
+        assert!(is_protobuf(Some("application/protobuf; proto=shop.v1.Order")));
+        assert!(!is_protobuf(Some("application/json")));
+        assert!(!is_protobuf(None));
+    }
+}
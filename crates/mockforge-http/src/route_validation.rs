@@ -0,0 +1,301 @@
+//! Request-body validation for custom routes
+//!
+//! A route's `request.validation.schema` validates the body as JSON. Routes
+//! that accept several encodings list a schema per media type under
+//! `request.validation.content_types` instead; [`RouteBodyValidator`] picks
+//! the one matching the request's `Content-Type` (exact match first, then
+//! `type/*`, then `*/*`) and decodes the body accordingly:
+//!
+//! - JSON media types (`application/json`, `*+json`) are parsed as JSON.
+//! - `application/x-www-form-urlencoded` bodies become an object of fields;
+//!   values are coerced to the `integer`/`number`/`boolean` type the schema
+//!   declares for that property, and repeated fields collect into arrays
+//!   when the property is an `array`.
+//! - Anything else is validated as a single string.
+//!
+//! A media type without a schema is rejected with 415. JSON that does not
+//! parse is rejected with 400 and tagged with [`MalformedJsonBody`], so
+//! `http.malformed_json_response` can replace the response.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Json, Response};
+use mockforge_core::config::RouteValidationConfig;
+use mockforge_core::Validator;
+use serde_json::{Map, Value};
+
+/// Why a request body was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum BodyValidationError {
+    /// No schema is configured for the request's media type (415)
+    UnsupportedMediaType(String),
+    /// The body could not be decoded or does not match the schema (400)
+    Invalid(String),
+    /// The body should be JSON but does not parse (400)
+    MalformedJson(String),
+}
+
+/// Response extension marking a 400 for a body that is not valid JSON,
+/// carrying the parser's message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MalformedJsonBody(pub String);
+
+impl IntoResponse for BodyValidationError {
+    fn into_response(self) -> Response {
+        match self {
+            Self::UnsupportedMediaType(message) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                Json(serde_json::json!({
+                    "error": "unsupported_media_type",
+                    "message": message,
+                })),
+            )
+                .into_response(),
+            Self::Invalid(detail) => invalid_response(&detail),
+            Self::MalformedJson(error) => {
+                let mut response =
+                    invalid_response(&format!("Request body is not valid JSON: {}", error));
+                response.extensions_mut().insert(MalformedJsonBody(error));
+                response
+            }
+        }
+    }
+}
+
+fn invalid_response(detail: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({
+            "error": "request validation failed",
+            "detail": detail,
+        })),
+    )
+        .into_response()
+}
+
+/// Compiled schemas for one route's `request.validation`
+#[derive(Debug)]
+pub struct RouteBodyValidator {
+    /// Schema applied to every request when no per-type schemas exist
+    default: Option<Validator>,
+    /// `(media type, raw schema, compiled schema)`, keys lowercased
+    by_type: Vec<(String, Value, Validator)>,
+}
+
+impl RouteBodyValidator {
+    /// Compile the schemas of a route's validation config
+    pub fn new(config: &RouteValidationConfig) -> mockforge_core::Result<Self> {
+        let default = config.schema.as_ref().map(Validator::from_json_schema).transpose()?;
+        let by_type = config
+            .content_types
+            .iter()
+            .map(|(media_type, schema)| {
+                Ok((
+                    media_type.trim().to_ascii_lowercase(),
+                    schema.clone(),
+                    Validator::from_json_schema(schema)?,
+                ))
+            })
+            .collect::<mockforge_core::Result<_>>()?;
+        Ok(Self { default, by_type })
+    }
+
+    /// Validate `body`, sent with the given `Content-Type` header value
+    pub fn validate(
+        &self,
+        content_type: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), BodyValidationError> {
+        let media_type = content_type
+            .and_then(|ct| ct.split(';').next())
+            .map(|ct| ct.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+
+        if self.by_type.is_empty() {
+            let Some(validator) = &self.default else {
+                return Ok(());
+            };
+            return check(validator, &parse_json(body)?);
+        }
+
+        let (_, schema, validator) = self.schema_for(&media_type).ok_or_else(|| {
+            let mut accepted: Vec<&str> = self.by_type.iter().map(|(t, _, _)| t.as_str()).collect();
+            accepted.sort_unstable();
+            BodyValidationError::UnsupportedMediaType(format!(
+                "Content-Type '{}' is not accepted; expected one of: {}",
+                content_type.unwrap_or(""),
+                accepted.join(", ")
+            ))
+        })?;
+
+        let value = if is_json(&media_type) {
+            parse_json(body)?
+        } else if media_type == "application/x-www-form-urlencoded" {
+            form_to_json(body, schema)
+        } else {
+            Value::String(String::from_utf8_lossy(body).into_owned())
+        };
+        check(validator, &value)
+    }
+
+    fn schema_for(&self, media_type: &str) -> Option<&(String, Value, Validator)> {
+        let wildcard = media_type.split('/').next().map(|major| format!("{}/*", major));
+        self.by_type
+            .iter()
+            .find(|(t, _, _)| t == media_type)
+            .or_else(|| self.by_type.iter().find(|(t, _, _)| Some(t) == wildcard.as_ref()))
+            .or_else(|| self.by_type.iter().find(|(t, _, _)| t == "*/*"))
+    }
+}
+
+/// Whether `content_type` is a JSON media type (`application/json`, `*+json`)
+pub fn is_json_content_type(content_type: Option<&str>) -> bool {
+    content_type.is_some_and(|ct| {
+        is_json(&ct.split(';').next().unwrap_or_default().trim().to_ascii_lowercase())
+    })
+}
+
+fn is_json(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+fn parse_json(body: &[u8]) -> Result<Value, BodyValidationError> {
+    if body.is_empty() {
+        return Ok(Value::Null);
+    }
+    serde_json::from_slice(body).map_err(|e| BodyValidationError::MalformedJson(e.to_string()))
+}
+
+fn check(validator: &Validator, value: &Value) -> Result<(), BodyValidationError> {
+    validator
+        .validate(value)
+        .map_err(|e| BodyValidationError::Invalid(e.to_string()))
+}
+
+/// Decode a form body into an object, coercing values by the property types
+/// the schema declares
+fn form_to_json(body: &[u8], schema: &Value) -> Value {
+    let property_type = |name: &str| {
+        schema
+            .get("properties")
+            .and_then(|p| p.get(name))
+            .and_then(|p| p.get("type"))
+            .and_then(Value::as_str)
+    };
+    let coerce = |ty: Option<&str>, raw: &str| {
+        let coerced = match ty {
+            Some("integer") => raw.parse::<i64>().ok().map(Value::from),
+            Some("number") => raw
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            Some("boolean") => raw.parse::<bool>().ok().map(Value::Bool),
+            _ => None,
+        };
+        // Leave unparseable values as strings so the schema reports the mismatch
+        coerced.unwrap_or_else(|| Value::String(raw.to_string()))
+    };
+
+    let mut fields = Map::new();
+    for (name, raw) in url::form_urlencoded::parse(body) {
+        if property_type(&name) == Some("array") {
+            let item_type = schema
+                .pointer(&format!(
+                    "/properties/{}/items/type",
+                    name.replace('~', "~0").replace('/', "~1")
+                ))
+                .and_then(Value::as_str);
+            let entry = fields.entry(name.into_owned()).or_insert_with(|| Value::Array(Vec::new()));
+            if let Value::Array(items) = entry {
+                items.push(coerce(item_type, &raw));
+            }
+        } else {
+            fields.insert(name.to_string(), coerce(property_type(&name), &raw));
+        }
+    }
+    Value::Object(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn validator() -> RouteBodyValidator {
+        RouteBodyValidator::new(&RouteValidationConfig {
+            schema: None,
+            content_types: HashMap::from([
+                (
+                    "application/json".to_string(),
+                    serde_json::json!({
+                        "type": "object",
+                        "required": ["name", "tags"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "tags": { "type": "array", "items": { "type": "string" } }
+                        }
+                    }),
+                ),
+                (
+                    "application/x-www-form-urlencoded".to_string(),
+                    serde_json::json!({
+                        "type": "object",
+                        "required": ["name", "age"],
+                        "properties": {
+                            "name": { "type": "string" },
+                            "age": { "type": "integer", "minimum": 18 },
+                            "subscribed": { "type": "boolean" }
+                        }
+                    }),
+                ),
+            ]),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn json_body_uses_json_schema() {
+        let validator = validator();
+        let ct = Some("application/json; charset=utf-8");
+        assert_eq!(validator.validate(ct, br#"{"name":"ada","tags":["x"]}"#), Ok(()));
+        assert!(matches!(
+            validator.validate(ct, br#"{"name":"ada"}"#),
+            Err(BodyValidationError::Invalid(_))
+        ));
+        assert!(matches!(
+            validator.validate(ct, b"name=ada"),
+            Err(BodyValidationError::MalformedJson(_))
+        ));
+    }
+
+    #[test]
+    fn form_body_uses_form_schema() {
+        let validator = validator();
+        let ct = Some("application/x-www-form-urlencoded");
+        // The JSON schema's required `tags` does not apply to forms
+        assert_eq!(validator.validate(ct, b"name=ada&age=36&subscribed=true"), Ok(()));
+        // Coerced to an integer, then rejected by `minimum`
+        assert!(matches!(
+            validator.validate(ct, b"name=ada&age=12"),
+            Err(BodyValidationError::Invalid(_))
+        ));
+        // Unparseable values stay strings and fail the type check
+        assert!(matches!(
+            validator.validate(ct, b"name=ada&age=forty"),
+            Err(BodyValidationError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn unlisted_media_type_is_unsupported() {
+        let validator = validator();
+        assert!(matches!(
+            validator.validate(Some("text/plain"), b"hello"),
+            Err(BodyValidationError::UnsupportedMediaType(_))
+        ));
+        assert!(matches!(
+            validator.validate(None, b"{}"),
+            Err(BodyValidationError::UnsupportedMediaType(_))
+        ));
+    }
+}
@@ -0,0 +1,324 @@
+//! Runtime scenario presets.
+//!
+//! `http.scenario_presets` names bundles of settings such as "all healthy",
+//! "database down" or "high latency". One call switches the whole mock into
+//! a preset, replacing the one active before; a middleware then applies the
+//! active preset's maintenance mode, route faults and latency to every
+//! request outside `/__mockforge`, so the management APIs stay reachable.
+//!
+//! ## Endpoints
+//!
+//! - `GET    /__mockforge/api/scenario`        — active preset (or 204 if none)
+//! - `POST   /__mockforge/api/scenario/{name}` — activate a preset and return
+//!   the applied state (404 for unknown names)
+//! - `DELETE /__mockforge/api/scenario`        — deactivate
+
+use axum::extract::{Path, Request, State};
+use axum::http::{header::RETRY_AFTER, HeaderName, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use mockforge_core::config::{
+    HttpScenarioFaultConfig, HttpScenarioLatencyConfig, HttpScenarioMaintenanceConfig,
+    HttpScenarioPresetConfig,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// The active scenario preset, as returned by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveScenarioPreset {
+    /// Preset name
+    pub name: String,
+    /// When the preset was activated
+    pub activated_at: DateTime<Utc>,
+    /// The applied settings
+    #[serde(flatten)]
+    pub preset: HttpScenarioPresetConfig,
+}
+
+/// Configured presets and the active one. Cheap to clone (Arc).
+#[derive(Clone)]
+pub struct ScenarioPresetState {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    presets: HashMap<String, HttpScenarioPresetConfig>,
+    active: RwLock<Option<Arc<ActiveScenarioPreset>>>,
+}
+
+impl ScenarioPresetState {
+    /// Start with the configured presets and none active
+    pub fn new(presets: HashMap<String, HttpScenarioPresetConfig>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                presets,
+                active: RwLock::new(None),
+            }),
+        }
+    }
+
+    /// Names of the configured presets, sorted
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.inner.presets.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// The active preset, if any
+    pub fn active(&self) -> Option<Arc<ActiveScenarioPreset>> {
+        self.inner.active.read().expect("scenario preset state poisoned").clone()
+    }
+
+    /// Activate the preset `name`, replacing the active one. Returns `None`
+    /// when no preset has that name.
+    pub fn activate(&self, name: &str) -> Option<Arc<ActiveScenarioPreset>> {
+        let preset = self.inner.presets.get(name)?.clone();
+        let active = Arc::new(ActiveScenarioPreset {
+            name: name.to_string(),
+            activated_at: mockforge_foundation::clock::now(),
+            preset,
+        });
+        *self.inner.active.write().expect("scenario preset state poisoned") = Some(active.clone());
+        Some(active)
+    }
+
+    /// Deactivate the active preset
+    pub fn deactivate(&self) {
+        *self.inner.active.write().expect("scenario preset state poisoned") = None;
+    }
+}
+
+/// Whether `path` matches `pattern`: `{name}` segments match any one
+/// segment, and a trailing `*` matches the rest of the path
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern_segments = pattern.trim_end_matches('/').split('/');
+    let mut path_segments = path.trim_end_matches('/').split('/');
+    loop {
+        match (pattern_segments.next(), path_segments.next()) {
+            (Some("*"), _) => return pattern_segments.next().is_none(),
+            (Some(p), Some(s)) if p.starts_with('{') && p.ends_with('}') && !s.is_empty() => {}
+            (Some(p), Some(s)) if p == s => {}
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+fn fault_applies(fault: &HttpScenarioFaultConfig, method: &Method, path: &str) -> bool {
+    fault.method.as_deref().is_none_or(|m| m.eq_ignore_ascii_case(method.as_str()))
+        && path_matches(&fault.path, path)
+}
+
+fn latency_delay(latency: &HttpScenarioLatencyConfig) -> Duration {
+    let jitter = latency.jitter_ms as i64;
+    let offset = if jitter > 0 {
+        rand::rng().random_range(-jitter..=jitter)
+    } else {
+        0
+    };
+    Duration::from_millis((latency.base_ms as i64 + offset).max(0) as u64)
+}
+
+fn scenario_response(name: &str, status: StatusCode, body: serde_json::Value) -> Response {
+    let mut response = (status, Json(body)).into_response();
+    response.headers_mut().insert(
+        HeaderName::from_static("x-mockforge-source"),
+        HeaderValue::from_static("scenario-preset"),
+    );
+    if let Ok(value) = HeaderValue::from_str(name) {
+        response
+            .headers_mut()
+            .insert(HeaderName::from_static("x-mockforge-scenario"), value);
+    }
+    response
+}
+
+fn maintenance_response(name: &str, maintenance: &HttpScenarioMaintenanceConfig) -> Response {
+    let message = maintenance
+        .message
+        .clone()
+        .unwrap_or_else(|| "Service is down for maintenance".to_string());
+    let mut response = scenario_response(
+        name,
+        StatusCode::SERVICE_UNAVAILABLE,
+        serde_json::json!({ "error": "maintenance", "message": message }),
+    );
+    if let Some(secs) = maintenance.retry_after_secs {
+        response.headers_mut().insert(RETRY_AFTER, secs.into());
+    }
+    response
+}
+
+fn fault_response(name: &str, fault: &HttpScenarioFaultConfig) -> Response {
+    let status = StatusCode::from_u16(fault.status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+    let body = fault.body.clone().unwrap_or_else(|| {
+        serde_json::json!({
+            "error": "scenario_fault",
+            "message": format!("Simulated failure from scenario '{}'", name),
+        })
+    });
+    scenario_response(name, status, body)
+}
+
+/// Middleware applying the active preset: maintenance mode first, then the
+/// first matching route fault, then latency
+pub async fn scenario_preset_middleware(
+    State(state): State<ScenarioPresetState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    if req.uri().path().starts_with("/__mockforge") {
+        return next.run(req).await;
+    }
+    let Some(active) = state.active() else {
+        return next.run(req).await;
+    };
+    let preset = &active.preset;
+
+    if let Some(maintenance) = &preset.maintenance {
+        return maintenance_response(&active.name, maintenance);
+    }
+
+    let fault = preset
+        .faults
+        .iter()
+        .find(|fault| fault_applies(fault, req.method(), req.uri().path()))
+        .filter(|fault| rand::rng().random::<f64>() < fault.probability);
+    if let Some(fault) = fault {
+        return fault_response(&active.name, fault);
+    }
+
+    if let Some(latency) = &preset.latency {
+        let delay = latency_delay(latency);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+    next.run(req).await
+}
+
+async fn get_handler(State(state): State<ScenarioPresetState>) -> Response {
+    match state.active() {
+        Some(active) => Json(active.as_ref().clone()).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+async fn activate_handler(
+    State(state): State<ScenarioPresetState>,
+    Path(name): Path<String>,
+) -> Response {
+    match state.activate(&name) {
+        Some(active) => Json(active.as_ref().clone()).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "error": "unknown_scenario",
+                "message": format!("No scenario preset named '{}'", name),
+                "available": state.names(),
+            })),
+        )
+            .into_response(),
+    }
+}
+
+async fn delete_handler(State(state): State<ScenarioPresetState>) -> StatusCode {
+    state.deactivate();
+    StatusCode::NO_CONTENT
+}
+
+/// Build the scenario preset API router. Mount under
+/// `/__mockforge/api/scenario`.
+pub fn scenario_preset_api_router(state: ScenarioPresetState) -> Router {
+    Router::new()
+        .route("/", get(get_handler).delete(delete_handler))
+        .route("/{name}", post(activate_handler))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::middleware::from_fn_with_state;
+    use tower::ServiceExt;
+
+    #[test]
+    fn path_patterns() {
+        assert!(path_matches("/api/orders", "/api/orders/"));
+        assert!(path_matches("/api/orders/{id}", "/api/orders/7"));
+        assert!(!path_matches("/api/orders/{id}", "/api/orders"));
+        assert!(path_matches("/api/orders/*", "/api/orders/7/items"));
+        assert!(!path_matches("/api/orders/*", "/api/users/7"));
+    }
+
+    #[tokio::test]
+    async fn database_down_preset_fails_data_routes() {
+        let presets = HashMap::from([
+            (
+                "database down".to_string(),
+                HttpScenarioPresetConfig {
+                    faults: vec![HttpScenarioFaultConfig {
+                        path: "/api/orders/*".to_string(),
+                        method: None,
+                        status: 503,
+                        probability: 1.0,
+                        body: None,
+                    }],
+                    ..Default::default()
+                },
+            ),
+            ("all healthy".to_string(), HttpScenarioPresetConfig::default()),
+        ]);
+        let state = ScenarioPresetState::new(presets);
+        let app = Router::new()
+            .route("/api/orders", get(|| async { "orders" }))
+            .route("/api/orders/{id}", get(|| async { "order" }))
+            .route("/api/health", get(|| async { "ok" }))
+            .layer(from_fn_with_state(state.clone(), scenario_preset_middleware))
+            .nest("/__mockforge/api/scenario", scenario_preset_api_router(state));
+        let call = |method: &str, uri: &str| {
+            let req = axum::http::Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            app.clone().oneshot(req)
+        };
+        let status = |uri: &'static str| {
+            let res = call("GET", uri);
+            async move { res.await.unwrap().status() }
+        };
+
+        assert_eq!(status("/api/orders/7").await, StatusCode::OK);
+
+        let res = call("POST", "/__mockforge/api/scenario/database%20down").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        let applied: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(applied["name"], "database down");
+        assert_eq!(applied["faults"][0]["status"], 503);
+
+        assert_eq!(status("/api/orders").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(status("/api/orders/7").await, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(status("/api/health").await, StatusCode::OK);
+        assert_eq!(status("/__mockforge/api/scenario").await, StatusCode::OK);
+
+        let res = call("POST", "/__mockforge/api/scenario/all%20healthy").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(status("/api/orders/7").await, StatusCode::OK);
+
+        let res = call("POST", "/__mockforge/api/scenario/meteor%20strike").await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let res = call("DELETE", "/__mockforge/api/scenario").await.unwrap();
+        assert_eq!(res.status(), StatusCode::NO_CONTENT);
+        assert_eq!(status("/__mockforge/api/scenario").await, StatusCode::NO_CONTENT);
+    }
+}
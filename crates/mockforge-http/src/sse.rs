@@ -1,16 +1,29 @@
 //! Server Sent Events (SSE) support for MockForge
+//!
+//! `GET /sse` streams templated events. Between events the stream sends
+//! `:keepalive` comment heartbeats every `heartbeat_interval_ms`, each
+//! interval shifted by a random amount up to `heartbeat_jitter_ms` either way,
+//! so clients' liveness and reconnection logic can be tested against
+//! irregular heartbeats. Both can be overridden per request with the
+//! `heartbeat` and `heartbeat_jitter` query parameters.
+//!
+//! Streams are listed in the connection tracker; once `http.streaming_limit`
+//! `max_sse` streams are open, further clients get 503 Service Unavailable.
 
 use axum::{
     extract::{Query, State},
-    response::{sse::Event, Sse},
+    http::Extensions,
+    response::{sse::Event, IntoResponse, Response, Sse},
     routing::get,
     Router,
 };
-use futures::stream::{self, Stream};
+use futures::stream::{self, Stream, StreamExt};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::convert::Infallible;
 use std::time::Duration;
 
+use crate::connection_tracker::{self, global_connections, ConnectionKind, ConnectionTracker};
 use mockforge_core::templating;
 
 /// SSE configuration parameters
@@ -26,6 +39,17 @@ pub struct SSEConfig {
     pub max_events: usize,
     /// Initial delay before first event in milliseconds
     pub initial_delay_ms: u64,
+    /// Interval between heartbeat comments in milliseconds
+    #[serde(default = "default_heartbeat_interval_ms")]
+    pub heartbeat_interval_ms: u64,
+    /// Maximum random deviation of each heartbeat interval in milliseconds
+    /// (0 = fixed cadence)
+    #[serde(default)]
+    pub heartbeat_jitter_ms: u64,
+}
+
+fn default_heartbeat_interval_ms() -> u64 {
+    1000
 }
 
 /// Query parameters for SSE endpoint
@@ -39,6 +63,10 @@ pub struct SSEQueryParams {
     pub interval: Option<u64>,
     /// Maximum events override
     pub max_events: Option<usize>,
+    /// Heartbeat interval override (milliseconds)
+    pub heartbeat: Option<u64>,
+    /// Heartbeat jitter override (milliseconds)
+    pub heartbeat_jitter: Option<u64>,
 }
 
 /// SSE event data structure
@@ -60,12 +88,22 @@ pub struct SSEEventData {
 #[derive(Clone)]
 pub struct SSEStreamManager {
     config: SSEConfig,
+    connections: ConnectionTracker,
 }
 
 impl SSEStreamManager {
     /// Create a new SSE stream manager
     pub fn new(config: SSEConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            connections: global_connections().clone(),
+        }
+    }
+
+    /// Track (and limit) streams in `connections` instead of the global tracker
+    pub fn with_connections(mut self, connections: ConnectionTracker) -> Self {
+        self.connections = connections;
+        self
     }
 
     /// Create default SSE configuration
@@ -77,9 +115,34 @@ impl SSEStreamManager {
             interval_ms: 1000,
             max_events: 0, // unlimited
             initial_delay_ms: 0,
+            heartbeat_interval_ms: default_heartbeat_interval_ms(),
+            heartbeat_jitter_ms: 0,
         }
     }
 
+    /// Create SSE stream with heartbeat comments interleaved between events.
+    /// The stream ends when the event stream does.
+    pub fn create_stream_with_heartbeats(
+        &self,
+        query_params: SSEQueryParams,
+    ) -> impl Stream<Item = Result<Event, Infallible>> {
+        let heartbeat_interval =
+            query_params.heartbeat.unwrap_or(self.config.heartbeat_interval_ms);
+        let heartbeat_jitter =
+            query_params.heartbeat_jitter.unwrap_or(self.config.heartbeat_jitter_ms);
+
+        // `None` marks the end of the event stream so the endless heartbeats stop with it
+        let events = self.create_stream(query_params).map(Some).chain(stream::once(async { None }));
+        let heartbeats = stream::unfold((), move |()| async move {
+            tokio::time::sleep(heartbeat_delay(heartbeat_interval, heartbeat_jitter)).await;
+            Some((Some(Ok(Event::default().comment("keepalive"))), ()))
+        });
+
+        stream::select(events, heartbeats)
+            .take_while(|item| std::future::ready(item.is_some()))
+            .filter_map(std::future::ready)
+    }
+
     /// Create SSE stream
     pub fn create_stream(
         &self,
@@ -176,10 +239,30 @@ impl SSEStreamManager {
             config.max_events = max_events;
         }
 
+        if let Some(heartbeat) = params.heartbeat {
+            config.heartbeat_interval_ms = heartbeat;
+        }
+
+        if let Some(heartbeat_jitter) = params.heartbeat_jitter {
+            config.heartbeat_jitter_ms = heartbeat_jitter;
+        }
+
         config
     }
 }
 
+/// Time until the next heartbeat: `interval_ms` shifted uniformly by up to
+/// `jitter_ms` either way, never less than 1ms
+fn heartbeat_delay(interval_ms: u64, jitter_ms: u64) -> Duration {
+    let offset = if jitter_ms == 0 {
+        0
+    } else {
+        let jitter = jitter_ms.min(i64::MAX as u64) as i64;
+        rand::rng().random_range(-jitter..=jitter)
+    };
+    Duration::from_millis((interval_ms as i64).saturating_add(offset).max(1) as u64)
+}
+
 /// Create SSE router with default configuration
 pub fn sse_router() -> Router {
     sse_router_with_config(SSEStreamManager::default_config())
@@ -187,22 +270,27 @@ pub fn sse_router() -> Router {
 
 /// Create SSE router with custom configuration
 pub fn sse_router_with_config(config: SSEConfig) -> Router {
-    let manager = SSEStreamManager::new(config);
+    sse_router_with_manager(SSEStreamManager::new(config))
+}
+
+/// Create SSE router serving streams from `manager`
+pub fn sse_router_with_manager(manager: SSEStreamManager) -> Router {
     Router::new().route("/sse", get(sse_handler)).with_state(manager)
 }
 
-/// SSE handler
+/// SSE handler; the stream stays registered until the client disconnects
 async fn sse_handler(
     State(manager): State<SSEStreamManager>,
     Query(params): Query<SSEQueryParams>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
-    let stream = manager.create_stream(params);
-
-    Sse::new(stream).keep_alive(
-        axum::response::sse::KeepAlive::new()
-            .interval(Duration::from_secs(1))
-            .text("keepalive"),
-    )
+    extensions: Extensions,
+) -> Response {
+    let remote_addr = connection_tracker::remote_addr(&extensions);
+    let Some(guard) = manager.connections.try_register(ConnectionKind::Sse, remote_addr, "sse")
+    else {
+        return connection_tracker::limit_reached_response(ConnectionKind::Sse);
+    };
+    let stream = manager.create_stream_with_heartbeats(params);
+    Sse::new(connection_tracker::track_stream(guard, stream)).into_response()
 }
 
 #[cfg(test)]
@@ -231,6 +319,8 @@ mod tests {
             interval_ms: 500,
             max_events: 10,
             initial_delay_ms: 100,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         assert_eq!(config.event_type, Some("custom".to_string()));
@@ -248,6 +338,8 @@ mod tests {
             interval_ms: 250,
             max_events: 5,
             initial_delay_ms: 50,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let cloned = config.clone();
@@ -267,6 +359,8 @@ mod tests {
             interval_ms: 100,
             max_events: 1,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let debug_str = format!("{:?}", config);
@@ -284,6 +378,8 @@ mod tests {
             interval_ms: 200,
             max_events: 3,
             initial_delay_ms: 10,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -301,6 +397,8 @@ mod tests {
             interval_ms: 100,
             max_events: 1,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         assert!(config.event_type.is_none());
@@ -315,6 +413,8 @@ mod tests {
             data: None,
             interval: None,
             max_events: None,
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         assert!(params.event.is_none());
@@ -330,6 +430,8 @@ mod tests {
             data: Some("{\"custom\": true}".to_string()),
             interval: Some(500),
             max_events: Some(10),
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         assert_eq!(params.event, Some("custom_event".to_string()));
@@ -345,6 +447,8 @@ mod tests {
             data: None,
             interval: Some(100),
             max_events: None,
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         let debug_str = format!("{:?}", params);
@@ -415,6 +519,8 @@ mod tests {
             interval_ms: 100,
             max_events: 5,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let manager = SSEStreamManager::new(config.clone());
@@ -432,6 +538,8 @@ mod tests {
             interval_ms: 1000,
             max_events: 10,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let manager = SSEStreamManager::new(config);
@@ -440,6 +548,8 @@ mod tests {
             data: None,
             interval: None,
             max_events: None,
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         let merged = manager.merge_config_with_params(params);
@@ -458,6 +568,8 @@ mod tests {
             interval_ms: 1000,
             max_events: 10,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let manager = SSEStreamManager::new(config);
@@ -466,6 +578,8 @@ mod tests {
             data: Some("overridden data".to_string()),
             interval: Some(500),
             max_events: Some(5),
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         let merged = manager.merge_config_with_params(params);
@@ -484,6 +598,8 @@ mod tests {
             interval_ms: 1000,
             max_events: 10,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let manager = SSEStreamManager::new(config);
@@ -492,6 +608,8 @@ mod tests {
             data: None,
             interval: Some(2000),
             max_events: None,
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         let merged = manager.merge_config_with_params(params);
@@ -552,6 +670,8 @@ mod tests {
             interval_ms: 10,
             max_events: 3,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let manager = SSEStreamManager::new(config);
@@ -560,6 +680,8 @@ mod tests {
             data: None,
             interval: None,
             max_events: None,
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         let mut stream = manager.create_stream(params);
@@ -595,6 +717,8 @@ mod tests {
             interval_ms: 1,
             max_events: 1,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let manager = SSEStreamManager::new(config);
@@ -603,6 +727,8 @@ mod tests {
             data: None,
             interval: None,
             max_events: None,
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         let mut stream = manager.create_stream(params);
@@ -626,6 +752,8 @@ mod tests {
             interval_ms: 1000,
             max_events: 100,
             initial_delay_ms: 0,
+            heartbeat_interval_ms: 1000,
+            heartbeat_jitter_ms: 0,
         };
 
         let manager = SSEStreamManager::new(config);
@@ -634,6 +762,8 @@ mod tests {
             data: None,
             interval: Some(1),
             max_events: Some(2),
+            heartbeat: None,
+            heartbeat_jitter: None,
         };
 
         let mut stream = manager.create_stream(params);
@@ -648,4 +778,67 @@ mod tests {
 
         assert_eq!(count, 2);
     }
+
+    #[tokio::test]
+    async fn test_heartbeats_arrive_within_interval_and_jitter() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let mut config = SSEStreamManager::default_config();
+        // Delay the first event well past the test so only heartbeats arrive
+        config.interval_ms = 60_000;
+        config.initial_delay_ms = 1;
+        config.heartbeat_interval_ms = 80;
+        config.heartbeat_jitter_ms = 30;
+
+        let req = Request::builder().uri("/sse").body(Body::empty()).unwrap();
+        let res = sse_router_with_config(config).oneshot(req).await.unwrap();
+        let mut body = res.into_body().into_data_stream();
+
+        let mut last = tokio::time::Instant::now();
+        for _ in 0..5 {
+            let chunk = body.next().await.unwrap().unwrap();
+            let gap = last.elapsed();
+            last = tokio::time::Instant::now();
+
+            assert_eq!(&chunk[..], b":keepalive\n\n");
+            assert!(gap >= Duration::from_millis(50), "heartbeat too early: {gap:?}");
+            // Upper bound allows some scheduling slack on top of the jitter
+            assert!(gap <= Duration::from_millis(150), "heartbeat too late: {gap:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sse_clients_over_the_limit_get_503() {
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use mockforge_core::config::HttpStreamingLimitConfig;
+        use tower::ServiceExt;
+
+        let tracker = ConnectionTracker::new();
+        tracker.set_limits(HttpStreamingLimitConfig {
+            max_sse: Some(2),
+            max_websocket: None,
+        });
+        let manager = SSEStreamManager::new(SSEStreamManager::default_config())
+            .with_connections(tracker.clone());
+        let app = sse_router_with_manager(manager);
+        let open =
+            || app.clone().oneshot(Request::builder().uri("/sse").body(Body::empty()).unwrap());
+
+        // Open streams up to the limit and keep them alive
+        let first = open().await.unwrap();
+        let second = open().await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::OK);
+        assert_eq!(tracker.summary().sse, 2);
+
+        let rejected = open().await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        // Closing a stream frees its slot
+        drop(first);
+        assert_eq!(open().await.unwrap().status(), StatusCode::OK);
+    }
 }
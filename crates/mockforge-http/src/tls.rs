@@ -24,17 +24,83 @@ pub fn init_crypto_provider() {
     });
 }
 
-/// Create a rustls ServerConfig builder with the appropriate TLS protocol versions.
+/// Create a rustls ServerConfig builder honoring the configured protocol floor
+/// and cipher-suite allow-list.
 ///
-/// When `tls13_only` is true, restricts to TLS 1.3 only. Otherwise uses safe defaults (TLS 1.2+).
+/// `min_version: "1.3"` restricts to TLS 1.3 only; otherwise TLS 1.2 and 1.3 are
+/// offered. A non-empty `cipher_suites` list replaces the ring provider's default
+/// suites, so clients that cannot negotiate one of them fail the handshake.
 fn tls_config_builder(
-    tls13_only: bool,
-) -> rustls::ConfigBuilder<rustls::server::ServerConfig, rustls::WantsVerifier> {
-    if tls13_only {
-        rustls::server::ServerConfig::builder_with_protocol_versions(&[&rustls::version::TLS13])
-    } else {
-        rustls::server::ServerConfig::builder()
+    config: &HttpTlsConfig,
+) -> std::result::Result<
+    rustls::ConfigBuilder<rustls::server::ServerConfig, rustls::WantsVerifier>,
+    String,
+> {
+    let versions: &[&'static rustls::SupportedProtocolVersion] =
+        if is_tls13_only(&config.min_version) {
+            &[&rustls::version::TLS13]
+        } else {
+            rustls::DEFAULT_VERSIONS
+        };
+
+    let mut provider = rustls::crypto::ring::default_provider();
+    if !config.cipher_suites.is_empty() {
+        provider.cipher_suites =
+            select_cipher_suites(&provider.cipher_suites, &config.cipher_suites)?;
+        info!("Restricting TLS cipher suites to {:?}", config.cipher_suites);
+    }
+
+    rustls::server::ServerConfig::builder_with_provider(Arc::new(provider))
+        .with_protocol_versions(versions)
+        .map_err(|e| {
+            format!(
+                "Invalid TLS configuration (min_version={}, cipher_suites={:?}): {}",
+                config.min_version, config.cipher_suites, e
+            )
+        })
+}
+
+/// Pick the provider suites named in `names`, in the configured order.
+///
+/// Names are matched case-insensitively against the rustls names
+/// (`TLS13_AES_256_GCM_SHA384`); the IANA spelling of TLS 1.3 suites
+/// (`TLS_AES_256_GCM_SHA384`) is accepted too.
+fn select_cipher_suites(
+    available: &[rustls::SupportedCipherSuite],
+    names: &[String],
+) -> std::result::Result<Vec<rustls::SupportedCipherSuite>, String> {
+    let suite_name = |suite: &rustls::SupportedCipherSuite| format!("{:?}", suite.suite());
+
+    let mut selected = Vec::with_capacity(names.len());
+    for name in names {
+        let wanted = name.trim().to_ascii_uppercase().replace('-', "_");
+        let found = available.iter().find(|suite| {
+            let rustls_name = suite_name(suite);
+            rustls_name == wanted
+                || rustls_name
+                    .strip_prefix("TLS13_")
+                    .is_some_and(|rest| wanted.strip_prefix("TLS_") == Some(rest))
+        });
+        match found {
+            Some(suite) => {
+                if !selected
+                    .iter()
+                    .any(|s: &rustls::SupportedCipherSuite| s.suite() == suite.suite())
+                {
+                    selected.push(*suite);
+                }
+            }
+            None => {
+                let supported: Vec<String> = available.iter().map(suite_name).collect();
+                return Err(format!(
+                    "Unsupported TLS cipher suite '{}'; supported suites: {}",
+                    name,
+                    supported.join(", ")
+                ));
+            }
+        }
     }
+    Ok(selected)
 }
 
 /// Determine if TLS 1.3 only mode is requested from configuration.
@@ -133,8 +199,8 @@ pub fn load_tls_acceptor(config: &HttpTlsConfig) -> Result<TlsAcceptor> {
         "off"
     };
 
-    // Determine TLS protocol versions based on min_version config
-    let tls13_only = is_tls13_only(&config.min_version);
+    // Protocol floor and cipher-suite allow-list are applied by the builder
+    let builder = || tls_config_builder(config).map_err(mockforge_core::Error::internal);
 
     let server_config = match mtls_mode {
         "required" => {
@@ -181,13 +247,14 @@ pub fn load_tls_acceptor(config: &HttpTlsConfig) -> Result<TlsAcceptor> {
                 let key = keys.remove(0);
 
                 // Build with mTLS support (required)
-                tls_config_builder(tls13_only)
+                builder()?
                     .with_client_cert_verifier(client_verifier)
                     .with_single_cert(server_certs, key)
                     .map_err(|e| {
-                        mockforge_core::Error::internal(format!(
-                            "TLS config error (mTLS required): {}",
-                            e
+                        mockforge_core::Error::internal(single_cert_error(
+                            config,
+                            "TLS config error (mTLS required)",
+                            e,
                         ))
                     })?
             } else {
@@ -244,51 +311,63 @@ pub fn load_tls_acceptor(config: &HttpTlsConfig) -> Result<TlsAcceptor> {
                 // WebPkiClientVerifier which accepts any client cert that validates,
                 // but connections without certs will also work (we can't enforce optional-only)
                 // For true optional mTLS, we'd need custom verifier logic
-                tls_config_builder(tls13_only)
+                builder()?
                     .with_client_cert_verifier(client_verifier)
                     .with_single_cert(server_certs, key)
                     .map_err(|e| {
-                        mockforge_core::Error::internal(format!(
-                            "TLS config error (mTLS optional): {}",
-                            e
+                        mockforge_core::Error::internal(single_cert_error(
+                            config,
+                            "TLS config error (mTLS optional)",
+                            e,
                         ))
                     })?
             } else {
                 // Optional mTLS without CA: just standard TLS
                 info!("mTLS optional mode specified but no CA file provided, using standard TLS");
                 let key = keys.remove(0);
-                tls_config_builder(tls13_only)
-                    .with_no_client_auth()
-                    .with_single_cert(server_certs, key)
-                    .map_err(|e| {
-                        mockforge_core::Error::internal(format!("TLS config error: {}", e))
-                    })?
+                builder()?.with_no_client_auth().with_single_cert(server_certs, key).map_err(
+                    |e| {
+                        mockforge_core::Error::internal(single_cert_error(
+                            config,
+                            "TLS config error",
+                            e,
+                        ))
+                    },
+                )?
             }
         }
         _ => {
             // Standard TLS: no client certificate required
             let key = keys.remove(0);
-            tls_config_builder(tls13_only)
+            builder()?
                 .with_no_client_auth()
                 .with_single_cert(server_certs, key)
-                .map_err(|e| mockforge_core::Error::internal(format!("TLS config error: {}", e)))?
+                .map_err(|e| {
+                    mockforge_core::Error::internal(single_cert_error(
+                        config,
+                        "TLS config error",
+                        e,
+                    ))
+                })?
         }
     };
 
-    // Log cipher suite configuration (cipher suites are controlled by rustls's
-    // safe defaults and not overridable without also selecting specific crypto providers)
-    if !config.cipher_suites.is_empty() {
-        info!(
-            "Custom cipher suites specified: {:?}. Note: rustls enforces safe cipher suites; \
-             for fine-grained control, configure the rustls CryptoProvider.",
-            config.cipher_suites
-        );
-    }
-
     info!("TLS acceptor configured successfully");
     Ok(TlsAcceptor::from(Arc::new(server_config)))
 }
 
+/// Describe a `with_single_cert` failure, naming both files when the private
+/// key does not belong to the certificate
+fn single_cert_error(config: &HttpTlsConfig, context: &str, error: rustls::Error) -> String {
+    match error {
+        rustls::Error::InconsistentKeys(_) => format!(
+            "{}: private key {} does not match certificate {}",
+            context, config.key_file, config.cert_file
+        ),
+        other => format!("{}: {}", context, other),
+    }
+}
+
 /// Load TLS server configuration for use with axum-server
 ///
 /// This function is similar to load_tls_acceptor but returns the ServerConfig
@@ -337,8 +416,8 @@ pub fn load_tls_server_config(
         return Err(format!("No private keys found in {}", config.key_file).into());
     }
 
-    // Determine TLS protocol versions based on min_version config
-    let tls13_only = is_tls13_only(&config.min_version);
+    // Protocol floor and cipher-suite allow-list are applied by the builder
+    let builder = || tls_config_builder(config);
 
     // Determine mTLS mode
     let mtls_mode = if !config.mtls_mode.is_empty() && config.mtls_mode != "off" {
@@ -375,10 +454,10 @@ pub fn load_tls_server_config(
 
                 let key = keys.remove(0);
 
-                tls_config_builder(tls13_only)
+                builder()?
                     .with_client_cert_verifier(client_verifier)
                     .with_single_cert(server_certs, key)
-                    .map_err(|e| format!("TLS config error (mTLS required): {}", e))?
+                    .map_err(|e| single_cert_error(config, "TLS config error (mTLS required)", e))?
             } else {
                 return Err("mTLS mode 'required' requires CA certificate file".to_string().into());
             }
@@ -408,24 +487,24 @@ pub fn load_tls_server_config(
 
                 let key = keys.remove(0);
 
-                tls_config_builder(tls13_only)
+                builder()?
                     .with_client_cert_verifier(client_verifier)
                     .with_single_cert(server_certs, key)
-                    .map_err(|e| format!("TLS config error (mTLS optional): {}", e))?
+                    .map_err(|e| single_cert_error(config, "TLS config error (mTLS optional)", e))?
             } else {
                 let key = keys.remove(0);
-                tls_config_builder(tls13_only)
+                builder()?
                     .with_no_client_auth()
                     .with_single_cert(server_certs, key)
-                    .map_err(|e| format!("TLS config error: {}", e))?
+                    .map_err(|e| single_cert_error(config, "TLS config error", e))?
             }
         }
         _ => {
             let key = keys.remove(0);
-            tls_config_builder(tls13_only)
+            builder()?
                 .with_no_client_auth()
                 .with_single_cert(server_certs, key)
-                .map_err(|e| format!("TLS config error: {}", e))?
+                .map_err(|e| single_cert_error(config, "TLS config error", e))?
         }
     };
 
@@ -458,6 +537,177 @@ mod tests {
         (cert, key)
     }
 
+    /// Write a real self-signed `localhost` certificate and key to temp files.
+    fn create_self_signed_cert() -> (rcgen::CertifiedKey, NamedTempFile, NamedTempFile) {
+        let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let mut cert = NamedTempFile::new().unwrap();
+        cert.write_all(certified.cert.pem().as_bytes()).unwrap();
+        let mut key = NamedTempFile::new().unwrap();
+        key.write_all(certified.key_pair.serialize_pem().as_bytes()).unwrap();
+        (certified, cert, key)
+    }
+
+    fn tls_config(
+        cert: &NamedTempFile,
+        key: &NamedTempFile,
+        min_version: &str,
+        cipher_suites: &[&str],
+    ) -> HttpTlsConfig {
+        HttpTlsConfig {
+            enabled: true,
+            cert_file: cert.path().to_string_lossy().to_string(),
+            key_file: key.path().to_string_lossy().to_string(),
+            ca_file: None,
+            min_version: min_version.to_string(),
+            cipher_suites: cipher_suites.iter().map(|s| s.to_string()).collect(),
+            require_client_cert: false,
+            mtls_mode: "off".to_string(),
+        }
+    }
+
+    /// Handshake against `acceptor` with a client limited to `versions`,
+    /// returning the negotiated protocol version and cipher suite.
+    async fn handshake(
+        acceptor: TlsAcceptor,
+        certified: &rcgen::CertifiedKey,
+        versions: &[&'static rustls::SupportedProtocolVersion],
+    ) -> std::io::Result<(rustls::ProtocolVersion, rustls::CipherSuite)> {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await?;
+            acceptor.accept(stream).await.map(|_| ())
+        });
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(certified.cert.der().clone()).unwrap();
+        let client_config = rustls::ClientConfig::builder_with_protocol_versions(versions)
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+        let server_name = rustls::pki_types::ServerName::try_from("localhost").unwrap();
+        let stream = tokio::net::TcpStream::connect(addr).await?;
+        let result = connector.connect(server_name, stream).await.map(|tls| {
+            let conn = tls.get_ref().1;
+            (
+                conn.protocol_version().unwrap(),
+                conn.negotiated_cipher_suite().unwrap().suite(),
+            )
+        });
+        let _ = server.await;
+        result
+    }
+
+    #[tokio::test]
+    async fn test_tls12_client_handshakes_with_default_floor() {
+        init_crypto_provider();
+        let (certified, cert, key) = create_self_signed_cert();
+        let acceptor = load_tls_acceptor(&tls_config(&cert, &key, "1.2", &[])).unwrap();
+
+        let (version, _) =
+            handshake(acceptor, &certified, &[&rustls::version::TLS12]).await.unwrap();
+        assert_eq!(version, rustls::ProtocolVersion::TLSv1_2);
+    }
+
+    #[tokio::test]
+    async fn test_tls13_only_rejects_tls12_clients() {
+        init_crypto_provider();
+        let (certified, cert, key) = create_self_signed_cert();
+        let config = tls_config(&cert, &key, "1.3", &[]);
+
+        let acceptor = load_tls_acceptor(&config).unwrap();
+        assert!(handshake(acceptor, &certified, &[&rustls::version::TLS12]).await.is_err());
+
+        let acceptor = load_tls_acceptor(&config).unwrap();
+        let (version, _) =
+            handshake(acceptor, &certified, &[&rustls::version::TLS13]).await.unwrap();
+        assert_eq!(version, rustls::ProtocolVersion::TLSv1_3);
+    }
+
+    #[tokio::test]
+    async fn test_cipher_suite_allow_list() {
+        init_crypto_provider();
+        let (certified, cert, key) = create_self_signed_cert();
+        // rcgen issues ECDSA certificates, so allow one ECDSA TLS 1.2 suite plus
+        // a TLS 1.3 suite spelled the IANA way.
+        let config = tls_config(
+            &cert,
+            &key,
+            "1.2",
+            &[
+                "TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256",
+                "TLS_AES_256_GCM_SHA384",
+            ],
+        );
+
+        let acceptor = load_tls_acceptor(&config).unwrap();
+        let (_, suite) = handshake(acceptor, &certified, &[&rustls::version::TLS12]).await.unwrap();
+        assert_eq!(suite, rustls::CipherSuite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256);
+
+        let acceptor = load_tls_acceptor(&config).unwrap();
+        let (_, suite) = handshake(acceptor, &certified, &[&rustls::version::TLS13]).await.unwrap();
+        assert_eq!(suite, rustls::CipherSuite::TLS13_AES_256_GCM_SHA384);
+
+        let unknown = tls_config(&cert, &key, "1.2", &["TLS_RSA_WITH_RC4_128_MD5"]);
+        let err = load_tls_acceptor(&unknown).err().unwrap().to_string();
+        assert!(err.contains("Unsupported TLS cipher suite"), "got: {}", err);
+
+        // A TLS 1.3 floor with only TLS 1.2 suites leaves nothing to negotiate
+        let unusable = tls_config(&cert, &key, "1.3", &["TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256"]);
+        assert!(load_tls_acceptor(&unusable).is_err());
+    }
+
+    #[test]
+    fn test_mismatched_key_is_reported_clearly() {
+        init_crypto_provider();
+        let (_, cert, _) = create_self_signed_cert();
+        let (_, _, other_key) = create_self_signed_cert();
+        let config = tls_config(&cert, &other_key, "1.2", &[]);
+
+        let err = load_tls_server_config(&config).err().unwrap().to_string();
+        assert!(err.contains("does not match certificate"), "unexpected error: {}", err);
+        let err = load_tls_acceptor(&config).err().unwrap().to_string();
+        assert!(err.contains("does not match certificate"), "unexpected error: {}", err);
+    }
+
+    #[tokio::test]
+    async fn test_serves_https_natively() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        init_crypto_provider();
+        let (certified, cert, key) = create_self_signed_cert();
+        let app = axum::Router::new().route("/ping", axum::routing::get(|| async { "pong" }));
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let server = tokio::spawn(crate::serve_router_with_tls_notify(
+            0,
+            app,
+            Some(tls_config(&cert, &key, "1.2", &[])),
+            Some(tx),
+        ));
+        let port = rx.await.unwrap();
+        assert_ne!(port, 0);
+
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(certified.cert.der().clone()).unwrap();
+        let client_config = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+        let server_name = rustls::pki_types::ServerName::try_from("localhost").unwrap();
+        let stream = tokio::net::TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let mut tls = connector.connect(server_name, stream).await.unwrap();
+
+        tls.write_all(b"GET /ping HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        tls.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "unexpected response: {}", response);
+        assert!(response.ends_with("pong"), "unexpected response: {}", response);
+
+        server.abort();
+    }
+
     #[test]
     fn test_tls_config_validation() {
         init_crypto_provider();
@@ -7,8 +7,9 @@ use axum::{http::StatusCode, response::IntoResponse, routing::post, Json, Router
 use mockforge_core::{
     request_logger::get_global_logger,
     verification::{
-        verify_at_least, verify_never, verify_requests, verify_sequence, VerificationCount,
-        VerificationRequest, VerificationResult,
+        verify_at_least, verify_batch, verify_never, verify_requests, verify_sequence,
+        BatchVerificationItem, BatchVerificationResult, VerificationCount, VerificationRequest,
+        VerificationResult,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -43,6 +44,16 @@ pub struct SequenceRequest {
     pub patterns: Vec<VerificationRequest>,
 }
 
+/// Request body for batch verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchVerifyRequest {
+    /// Expectations to check, answered in the same order
+    pub expectations: Vec<BatchVerificationItem>,
+    /// Stop at the first failing expectation
+    #[serde(default)]
+    pub short_circuit: bool,
+}
+
 /// Shared state for verification API (currently empty, but kept for future extensibility)
 #[derive(Clone)]
 pub struct VerificationState;
@@ -68,6 +79,7 @@ pub fn verification_router() -> Router {
         .route("/api/verification/sequence", post(handle_sequence))
         .route("/api/verification/never", post(handle_never))
         .route("/api/verification/at-least", post(handle_at_least))
+        .route("/api/verification/batch", post(handle_batch))
 }
 
 /// Verify requests against a pattern and count assertion
@@ -209,6 +221,33 @@ async fn handle_at_least(Json(request): Json<AtLeastRequest>) -> impl IntoRespon
     (status, Json(result)).into_response()
 }
 
+/// Verify several expectations against one snapshot of the request log
+async fn handle_batch(Json(request): Json<BatchVerifyRequest>) -> impl IntoResponse {
+    let logger = match get_global_logger() {
+        Some(logger) => logger,
+        None => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(BatchVerificationResult {
+                    matched: false,
+                    results: Vec::new(),
+                }),
+            )
+                .into_response();
+        }
+    };
+
+    let result = verify_batch(logger, &request.expectations, request.short_circuit).await;
+
+    let status = if result.matched {
+        StatusCode::OK
+    } else {
+        StatusCode::EXPECTATION_FAILED
+    };
+
+    (status, Json(result)).into_response()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,6 +345,22 @@ mod tests {
         assert!(response.status().is_client_error() || response.status().is_server_error());
     }
 
+    #[tokio::test]
+    async fn test_verification_router_has_batch_route() {
+        let router = verification_router();
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/verification/batch")
+            .header("Content-Type", "application/json")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = router.oneshot(request).await.unwrap();
+        // Should return an error since body is empty
+        assert!(response.status().is_client_error() || response.status().is_server_error());
+    }
+
     // ==================== VerificationState Tests ====================
 
     #[test]
@@ -0,0 +1,84 @@
+//! Multi-tenant workspace routing applied before routing
+//!
+//! In path-based multi-tenant mode `/workspace/{id}/users/7` is served by the
+//! `/users/{id}` route of workspace `{id}`. `Router::layer` runs after
+//! routing, so the workspace middleware wraps the app in an outer router
+//! whose only service is the original one: the prefix is stripped and the
+//! [`WorkspaceContext`](mockforge_core::multi_tenant::WorkspaceContext)
+//! attached before the inner router matches the request, and every layer
+//! inside (the rate limiter among them) sees both.
+
+use axum::extract::Request;
+use axum::middleware::Next;
+use axum::Router;
+use mockforge_core::multi_tenant::middleware::axum_middleware::workspace_middleware;
+use mockforge_core::multi_tenant::WorkspaceRouter;
+use std::sync::Arc;
+
+/// Wrap `app` so workspace prefixes are resolved before `app` routes requests
+pub fn with_workspace_routing(app: Router, workspace_router: Arc<WorkspaceRouter>) -> Router {
+    Router::new().fallback_service(app).layer(axum::middleware::from_fn(
+        move |req: Request, next: Next| workspace_middleware(workspace_router.clone(), req, next),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::{Path, RawQuery};
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::Extension;
+    use mockforge_core::multi_tenant::{
+        MultiTenantConfig, MultiTenantWorkspaceRegistry, WorkspaceContext,
+    };
+    use mockforge_core::Workspace;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        let mut registry = MultiTenantWorkspaceRegistry::new(MultiTenantConfig {
+            enabled: true,
+            ..Default::default()
+        });
+        for id in ["default", "team-a"] {
+            registry
+                .register_workspace(id.to_string(), Workspace::new(id.to_string()))
+                .unwrap();
+        }
+        let routes = Router::new().route(
+            "/users/{id}",
+            get(
+                |Path(id): Path<String>,
+                 RawQuery(query): RawQuery,
+                 Extension(context): Extension<WorkspaceContext>| async move {
+                    format!("{} user {} {}", context.workspace_id, id, query.unwrap_or_default())
+                },
+            ),
+        );
+        with_workspace_routing(routes, Arc::new(WorkspaceRouter::new(Arc::new(registry))))
+    }
+
+    async fn get_body(app: Router, uri: &str) -> (StatusCode, String) {
+        let req = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let res = app.oneshot(req).await.unwrap();
+        let status = res.status();
+        let body = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+        (status, String::from_utf8_lossy(&body).into_owned())
+    }
+
+    #[tokio::test]
+    async fn prefixed_path_reaches_the_workspace_route() {
+        let (status, body) = get_body(app(), "/workspace/team-a/users/7?fields=name").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "team-a user 7 fields=name");
+
+        // Unprefixed paths go to the default workspace unchanged
+        let (status, body) = get_body(app(), "/users/7").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "default user 7 ");
+
+        let (status, _) = get_body(app(), "/workspace/unknown/users/7").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -38,6 +38,7 @@ async fn test_rate_limit_headers_present() {
         burst: 200,
         per_ip: false,
         per_endpoint: false,
+        trust_forwarded_headers: false,
     };
     let rate_limiter =
         Arc::new(mockforge_http::middleware::GlobalRateLimiter::new(rate_limit_config));
@@ -248,6 +249,7 @@ async fn test_rate_limit_enforcement() {
         burst: 2,
         per_ip: false,
         per_endpoint: false,
+        trust_forwarded_headers: false,
     };
     let rate_limiter =
         Arc::new(mockforge_http::middleware::GlobalRateLimiter::new(rate_limit_config));
@@ -69,3 +69,38 @@ async fn media_example_token_expansion_toggle() {
 
     drop(server);
 }
+
+#[tokio::test]
+async fn media_example_echoes_path_params() {
+    let spec = serde_json::json!({
+        "openapi":"3.0.0",
+        "info": {"title":"E2E","version":"1"},
+        "paths": {"/users/{id}": {"get": {
+            "parameters": [{"name":"id","in":"path","required":true,"schema":{"type":"integer"}}],
+            "responses": {"200":{
+                "description":"ok",
+                "content": {"application/json": {"example": {"id":"{{path.id}}","self":"/users/{{path.id}}"}}}
+            }}
+        }}}
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("spec.json");
+    tokio::fs::write(&path, serde_json::to_vec(&spec).unwrap()).await.unwrap();
+
+    let app: Router = build_router(Some(path.to_string_lossy().to_string()), None, None).await;
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr: SocketAddr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .unwrap()
+    });
+
+    let resp = reqwest::get(format!("http://{}/users/42", addr)).await.unwrap();
+    assert!(resp.status().is_success());
+    let j: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(j["id"], 42);
+    assert_eq!(j["self"], "/users/42");
+
+    drop(server);
+}
@@ -91,3 +91,147 @@ async fn toggling_validation_mode_runtime() {
     // Cleanup server
     drop(server);
 }
+
+#[tokio::test]
+async fn warn_mode_violations_appear_in_validation_report() {
+    let spec = serde_json::json!({
+        "openapi":"3.0.0",
+        "info": {"title":"Report","version":"1"},
+        "paths": {"/report/items/{id}": {"get": {
+            "parameters": [
+                {"name":"id","in":"path","required":true,"schema":{"type":"string"}},
+                {"name":"limit","in":"query","required":true,"schema":{"type":"integer"}}
+            ],
+            "responses": {"200":{"description":"ok"}}
+        }}}
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("spec.json");
+    tokio::fs::write(&path, serde_json::to_vec(&spec).unwrap()).await.unwrap();
+
+    let opts = Some(ValidationOptions {
+        request_mode: ValidationMode::Warn,
+        aggregate_errors: true,
+        validate_responses: false,
+        overrides: std::collections::HashMap::new(),
+        admin_skip_prefixes: vec!["/__mockforge".into()],
+        response_template_expand: false,
+        validation_status: None,
+    });
+    let app: Router = build_router(Some(path.to_string_lossy().to_string()), opts, None).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr: SocketAddr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .unwrap()
+    });
+
+    // Missing required `limit`: served anyway in warn mode, but recorded
+    let client = reqwest::Client::new();
+    let res = client.get(format!("http://{}/report/items/42", addr)).send().await.unwrap();
+    assert!(res.status().is_success());
+
+    let report: serde_json::Value = client
+        .get(format!("http://{}/__mockforge/api/validation-report", addr))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let route = report["routes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["path"] == "/report/items/{id}")
+        .expect("warned route should be in the report");
+    assert_eq!(route["method"], "GET");
+    assert_eq!(route["count"], 1);
+    let samples = route["samples"].as_array().unwrap();
+    assert!(samples.iter().any(|s| s.as_str().unwrap().contains("limit")));
+
+    drop(server);
+}
+
+#[tokio::test]
+async fn validation_coverage_counts_valid_and_invalid_requests() {
+    let spec = serde_json::json!({
+        "openapi":"3.0.0",
+        "info": {"title":"Coverage","version":"1"},
+        "paths": {
+            "/coverage/orders": {"get": {
+                "parameters": [
+                    {"name":"limit","in":"query","required":true,"schema":{"type":"integer"}}
+                ],
+                "responses": {"200":{"description":"ok"}}
+            }},
+            "/coverage/untouched": {"get": {
+                "responses": {"200":{"description":"ok"}}
+            }}
+        }
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("spec.json");
+    tokio::fs::write(&path, serde_json::to_vec(&spec).unwrap()).await.unwrap();
+
+    let opts = Some(ValidationOptions {
+        request_mode: ValidationMode::Enforce,
+        aggregate_errors: true,
+        validate_responses: false,
+        overrides: std::collections::HashMap::new(),
+        admin_skip_prefixes: vec!["/__mockforge".into()],
+        response_template_expand: false,
+        validation_status: None,
+    });
+    let app: Router = build_router(Some(path.to_string_lossy().to_string()), opts, None).await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr: SocketAddr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .unwrap()
+    });
+
+    let client = reqwest::Client::new();
+    for query in ["?limit=10", "?limit=20", "?limit=many"] {
+        client
+            .get(format!("http://{}/coverage/orders{}", addr, query))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let report: serde_json::Value = client
+        .get(format!("http://{}/__mockforge/coverage/validation", addr))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let operation = |path: &str| {
+        report["operations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|op| op["path"] == path)
+            .cloned()
+            .unwrap_or_else(|| panic!("{} should be in the report", path))
+    };
+    let orders = operation("/coverage/orders");
+    assert_eq!(orders["method"], "GET");
+    assert_eq!(orders["valid_requests"], 2);
+    assert_eq!(orders["invalid_requests"], 1);
+    let untouched = operation("/coverage/untouched");
+    assert_eq!(untouched["valid_requests"], 0);
+    assert_eq!(untouched["invalid_requests"], 0);
+    assert_eq!(report["total_operations"], 2);
+    assert_eq!(report["valid_covered"], 1);
+    assert_eq!(report["invalid_covered"], 1);
+    assert_eq!(report["valid_coverage_percentage"], 50.0);
+
+    drop(server);
+}
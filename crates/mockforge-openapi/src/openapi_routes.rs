@@ -708,6 +708,11 @@ impl OpenApiRouteRegistry {
                     if let Err(ct_err) =
                         validator.check_request_content_type(&path_template, &method, actual_ct)
                     {
+                        mockforge_foundation::validation_outcomes::record(
+                            &method,
+                            &path_template,
+                            false,
+                        );
                         let status_code =
                             validator.options.validation_status.unwrap_or_else(|| {
                                 std::env::var("MOCKFORGE_VALIDATION_STATUS")
@@ -883,6 +888,8 @@ impl OpenApiRouteRegistry {
                 // Treating it as a forcing override (not just OR) lets tests and
                 // ad-hoc operator overrides disable token expansion explicitly.
                 let mut final_response = mock_response.clone();
+                // Matched path parameters are always available as `{{path.<name>}}`
+                expand_path_params(&mut final_response, &path_params, &operation);
                 let env_expand: Option<bool> = std::env::var("MOCKFORGE_RESPONSE_TEMPLATE_EXPAND")
                     .ok()
                     .map(|v| v == "1" || v.eq_ignore_ascii_case("true"));
@@ -1647,6 +1654,11 @@ impl OpenApiRouteRegistry {
                     }
                 }
             }
+            mockforge_foundation::validation_outcomes::record(
+                method,
+                &route.path,
+                errors.is_empty(),
+            );
             if errors.is_empty() {
                 return Ok(());
             }
@@ -1654,6 +1666,7 @@ impl OpenApiRouteRegistry {
                 ValidationMode::Disabled => Ok(()),
                 ValidationMode::Warn => {
                     tracing::warn!("Request validation warnings: {:?}", errors);
+                    mockforge_foundation::validation_report::record(method, &route.path, &errors);
                     Ok(())
                 }
                 ValidationMode::Enforce => Err(Error::validation(
@@ -2367,11 +2380,12 @@ impl OpenApiRouteRegistry {
                         .or_else(|| std::env::var("MOCKFORGE_HTTP_SCENARIO").ok());
 
                     // Fallback to standard response generation
-                    let (status, response) = route
+                    let (status, mut response) = route
                         .mock_response_with_status_and_scenario_and_override(
                             scenario.as_deref(),
                             status_override,
                         );
+                    expand_path_params(&mut response, &path_params, &route.operation);
                     let status_code = axum::http::StatusCode::from_u16(status)
                         .unwrap_or(axum::http::StatusCode::OK);
                     let mut resp = (status_code, Json(response)).into_response();
@@ -2425,6 +2439,99 @@ fn inject_spec_response_headers(
     }
 }
 
+/// Substitute `{{path.<name>}}` (or `{{request.path.<name>}}`) tokens in a
+/// generated response with the request's matched path parameters.
+///
+/// A string that is exactly one token takes the parameter's declared schema
+/// type, so `"{{path.id}}"` renders as `42` for an integer `id`; tokens inside
+/// longer strings are substituted as text. Tokens naming unknown parameters
+/// are left untouched for the template engine.
+fn expand_path_params(
+    body: &mut Value,
+    path_params: &HashMap<String, String>,
+    operation: &openapiv3::Operation,
+) {
+    if path_params.is_empty() {
+        return;
+    }
+    match body {
+        Value::String(s) if s.contains("{{") => {
+            if let Some(name) = whole_path_param_token(s) {
+                if let Some(raw) = path_params.get(name) {
+                    let typed = match path_param_schema(operation, name) {
+                        // Keep integers integral (`coerce_value_for_schema` yields `42.0`)
+                        Some(schema)
+                            if matches!(
+                                schema.schema_kind,
+                                openapiv3::SchemaKind::Type(openapiv3::Type::Integer(_))
+                            ) =>
+                        {
+                            raw.parse::<i64>()
+                                .map(Value::from)
+                                .unwrap_or_else(|_| Value::String(raw.clone()))
+                        }
+                        Some(schema) => {
+                            coerce_value_for_schema(&Value::String(raw.clone()), schema)
+                        }
+                        None => Value::String(raw.clone()),
+                    };
+                    *body = typed;
+                    return;
+                }
+            }
+            let mut out = String::with_capacity(s.len());
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("{{") {
+                out.push_str(&rest[..start]);
+                let token = &rest[start..];
+                let Some(end) = token.find("}}") else {
+                    out.push_str(token);
+                    rest = "";
+                    break;
+                };
+                match whole_path_param_token(&token[..end + 2]).and_then(|n| path_params.get(n)) {
+                    Some(value) => out.push_str(value),
+                    None => out.push_str(&token[..end + 2]),
+                }
+                rest = &token[end + 2..];
+            }
+            out.push_str(rest);
+            *s = out;
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|item| expand_path_params(item, path_params, operation)),
+        Value::Object(fields) => fields
+            .values_mut()
+            .for_each(|field| expand_path_params(field, path_params, operation)),
+        _ => {}
+    }
+}
+
+/// Parameter name when `s` is a single `{{path.<name>}}` token
+fn whole_path_param_token(s: &str) -> Option<&str> {
+    let key = s.strip_prefix("{{")?.strip_suffix("}}")?.trim();
+    let key = key.strip_prefix("request.").unwrap_or(key);
+    key.strip_prefix("path.")
+        .filter(|name| !name.contains("{{") && !name.contains("}}"))
+}
+
+/// Inline schema of the operation's path parameter `name`
+fn path_param_schema<'a>(
+    operation: &'a openapiv3::Operation,
+    name: &str,
+) -> Option<&'a openapiv3::Schema> {
+    operation.parameters.iter().find_map(|p| match p.as_item()? {
+        openapiv3::Parameter::Path { parameter_data, .. } if parameter_data.name == name => {
+            match &parameter_data.format {
+                openapiv3::ParameterSchemaOrContent::Schema(schema) => schema.as_item(),
+                openapiv3::ParameterSchemaOrContent::Content(_) => None,
+            }
+        }
+        _ => None,
+    })
+}
+
 // Note: templating helpers are now in core::templating (shared across modules)
 
 /// Extract multipart form data from request body bytes
@@ -5125,4 +5232,41 @@ mod tests {
             )
             .is_err());
     }
+
+    /// `{{path.<name>}}` tokens take the matched value, typed by the
+    /// parameter's schema when the token is the whole string.
+    #[test]
+    fn path_param_tokens_expand_in_generated_responses() {
+        let operation: openapiv3::Operation = serde_json::from_value(json!({
+            "parameters": [
+                {"name": "id", "in": "path", "required": true, "schema": {"type": "integer"}},
+                {"name": "slug", "in": "path", "required": true, "schema": {"type": "string"}}
+            ],
+            "responses": {"200": {"description": "ok"}}
+        }))
+        .unwrap();
+        let params = HashMap::from([
+            ("id".to_string(), "42".to_string()),
+            ("slug".to_string(), "ada".to_string()),
+        ]);
+
+        let mut body = json!({
+            "id": "{{path.id}}",
+            "slug": "{{ request.path.slug }}",
+            "href": "/users/{{path.id}}/{{path.slug}}",
+            "other": "{{path.missing}} and {{uuid}}",
+            "items": [{"owner": "{{path.id}}"}]
+        });
+        expand_path_params(&mut body, &params, &operation);
+        assert_eq!(
+            body,
+            json!({
+                "id": 42,
+                "slug": "ada",
+                "href": "/users/42/ada",
+                "other": "{{path.missing}} and {{uuid}}",
+                "items": [{"owner": 42}]
+            })
+        );
+    }
 }
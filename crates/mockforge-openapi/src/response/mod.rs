@@ -1994,6 +1994,58 @@ paths:
         }
     }
 
+    #[test]
+    fn test_generate_response_uses_property_defaults() {
+        let spec = OpenApiSpec::from_string(
+            r#"openapi: 3.0.0
+info:
+  title: Test API
+  version: 1.0.0
+paths:
+  /settings:
+    get:
+      responses:
+        '200':
+          description: OK
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  theme:
+                    type: string
+                    default: dark
+                  retries:
+                    type: integer
+                    default: 3
+                  region:
+                    type: string
+                    example: eu-west-1
+                    default: us-east-1
+"#,
+            Some("yaml"),
+        )
+        .unwrap();
+
+        let operation = spec
+            .spec
+            .paths
+            .paths
+            .get("/settings")
+            .and_then(|p| p.as_item())
+            .and_then(|p| p.get.as_ref())
+            .unwrap();
+
+        let response =
+            ResponseGenerator::generate_response(&spec, operation, 200, Some("application/json"))
+                .unwrap();
+
+        assert_eq!(response["theme"], json!("dark"));
+        assert_eq!(response["retries"], json!(3));
+        // An explicit example still wins over the default
+        assert_eq!(response["region"], json!("eu-west-1"));
+    }
+
     #[test]
     fn test_generate_response_with_missing_response_reference() {
         // Test response generation with missing response reference (lines 294-298)
@@ -25,9 +25,10 @@ impl ResponseGenerator {
     ///
     /// Priority order:
     /// 1. Schema-level example (schema.schema_data.example)
-    /// 2. Property-level examples when generating objects
-    /// 3. Generated values based on schema type
-    /// 4. Persona traits (if persona provided)
+    /// 2. Schema-level default (schema.schema_data.default)
+    /// 3. Property-level examples and defaults when generating objects
+    /// 4. Generated values based on schema type
+    /// 5. Persona traits (if persona provided)
     pub(crate) fn generate_example_from_schema(
         spec: &OpenApiSpec,
         schema: &Schema,
@@ -40,6 +41,13 @@ impl ResponseGenerator {
             return example.clone();
         }
 
+        // A declared default is just as deterministic, so prefer it over
+        // generated values as well
+        if let Some(default) = schema.schema_data.default.as_ref() {
+            tracing::debug!("Using schema-level default: {:?}", default);
+            return default.clone();
+        }
+
         // Note: schema-level example check happens at the top of the function (line 380-383)
         // At this point, if we have a schema-level example, we've already returned it
         // So we only generate defaults when no example exists
@@ -8,18 +8,26 @@
 //! `rng()` (which is Send-safe) from rand.
 
 use async_trait::async_trait;
-use axum::http::{Method, Uri};
+use axum::http::{header, HeaderMap, Method, Uri};
+use ipnet::IpNet;
 use mockforge_core::config::{
-    LatencyDistribution, RouteConfig, RouteFaultType, RouteLatencyConfig,
+    LatencyDistribution, RequestMatcherConfig, RouteConfig, RouteFaultInjectionConfig,
+    RouteFaultType, RouteLatencyConfig,
 };
 use mockforge_core::priority_handler::{
     RouteChaosInjectorTrait, RouteFaultResponse as CoreRouteFaultResponse,
 };
 use mockforge_core::{Error, Result};
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
 use rand::rng;
+use rand::seq::index::sample;
 use rand::Rng;
 use regex::Regex;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use tracing::debug;
 
@@ -39,6 +47,8 @@ struct CompiledRoute {
     path_pattern: Regex,
     /// HTTP method
     method: Method,
+    /// Compiled `request.raw_query_regex`, matched against the raw query string
+    raw_query: Option<Regex>,
 }
 
 impl RouteMatcher {
@@ -56,10 +66,22 @@ impl RouteMatcher {
                 Error::internal(format!("Invalid HTTP method '{}': {}", route.method, e))
             })?;
 
+            let raw_query = route
+                .request
+                .as_ref()
+                .and_then(|request| request.raw_query_regex.as_deref())
+                .map(|pattern| {
+                    Regex::new(pattern).map_err(|e| {
+                        Error::internal(format!("Invalid raw_query_regex '{pattern}': {e}"))
+                    })
+                })
+                .transpose()?;
+
             compiled_routes.push(CompiledRoute {
                 config: route,
                 path_pattern,
                 method,
+                raw_query,
             });
         }
 
@@ -69,8 +91,26 @@ impl RouteMatcher {
     }
 
     /// Match a request to a route configuration
+    ///
+    /// Routes requiring headers never match here; use
+    /// [`Self::match_route_with_headers`] to match on them.
     pub fn match_route(&self, method: &Method, uri: &Uri) -> Option<&RouteConfig> {
+        self.match_route_with_headers(method, uri, &HeaderMap::new())
+    }
+
+    /// Match a request to a route configuration, also checking the query
+    /// parameters and headers the route requires
+    pub fn match_route_with_headers(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Option<&RouteConfig> {
         let path = uri.path();
+        let query: Vec<(String, String)> = uri
+            .query()
+            .map(|q| url::form_urlencoded::parse(q.as_bytes()).into_owned().collect())
+            .unwrap_or_default();
 
         for compiled_route in &self.routes {
             // Check method match
@@ -79,7 +119,31 @@ impl RouteMatcher {
             }
 
             // Check path match
-            if compiled_route.path_pattern.is_match(path) {
+            if !compiled_route.path_pattern.is_match(path) {
+                continue;
+            }
+
+            // Check the raw query regex, then required query parameters and headers
+            if compiled_route
+                .raw_query
+                .as_ref()
+                .is_some_and(|re| !re.is_match(uri.query().unwrap_or_default()))
+            {
+                continue;
+            }
+            let Some(request) = &compiled_route.config.request else {
+                return Some(&compiled_route.config);
+            };
+            let query_matches = request
+                .query
+                .iter()
+                .all(|(name, value)| query.iter().any(|(n, v)| n == name && v == value));
+            let headers_match = request.headers.iter().all(|(name, value)| {
+                headers
+                    .get(name.to_ascii_lowercase().as_str())
+                    .is_some_and(|v| v.as_bytes() == value.as_bytes())
+            });
+            if query_matches && headers_match {
                 return Some(&compiled_route.config);
             }
         }
@@ -143,22 +207,70 @@ impl RouteMatcher {
 pub struct RouteChaosInjector {
     /// Route matcher
     matcher: RouteMatcher,
+    /// Warm cache keys for routes simulating a cache, with their expiry
+    warm_keys: Arc<Mutex<HashMap<String, Instant>>>,
+    /// Circuit breaker state per route, keyed by method and path pattern
+    breakers: Arc<Mutex<HashMap<String, BreakerState>>>,
+}
+
+/// State of one route's circuit breaker
+#[derive(Debug, Clone, Copy, Default)]
+struct BreakerState {
+    /// Matching requests counted since the breaker last closed
+    matches: u32,
+    /// When the breaker closes again, while it is open
+    open_until: Option<Instant>,
 }
 
 #[async_trait]
 impl RouteChaosInjectorTrait for RouteChaosInjector {
     /// Inject latency for this request
     async fn inject_latency(&self, method: &Method, uri: &Uri) -> Result<()> {
-        self.inject_latency_impl(method, uri).await
+        self.inject_latency_impl(method, uri, &HeaderMap::new()).await
     }
 
     /// Get fault injection response for a request
     fn get_fault_response(&self, method: &Method, uri: &Uri) -> Option<CoreRouteFaultResponse> {
-        self.get_fault_response_impl(method, uri).map(|r| CoreRouteFaultResponse {
-            status_code: r.status_code,
-            error_message: r.error_message,
-            fault_type: r.fault_type,
-        })
+        self.get_fault_response_with_headers(method, uri, &HeaderMap::new())
+    }
+
+    /// Inject latency for this request, matching routes on its headers
+    async fn inject_latency_with_headers(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Result<()> {
+        self.inject_latency_impl(method, uri, headers).await
+    }
+
+    /// Get fault injection response for a request, matching routes on its
+    /// headers
+    fn get_fault_response_with_headers(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Option<CoreRouteFaultResponse> {
+        self.get_fault_response_for_client(method, uri, headers, None)
+    }
+
+    /// Get fault injection response for a request from `client`, matching
+    /// routes on its headers and fault client selectors on both
+    fn get_fault_response_for_client(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+        client: Option<IpAddr>,
+    ) -> Option<CoreRouteFaultResponse> {
+        self.get_fault_response_impl(method, uri, headers, client)
+            .map(|r| CoreRouteFaultResponse {
+                status_code: r.status_code,
+                error_message: r.error_message,
+                fault_type: r.fault_type,
+                retry_after_secs: r.retry_after_secs,
+            })
     }
 }
 
@@ -166,10 +278,71 @@ impl RouteChaosInjector {
     /// Create a new route chaos injector
     ///
     /// # Errors
-    /// Returns an error if any route has an invalid HTTP method or path pattern.
+    /// Returns an error if any route has an invalid HTTP method or path
+    /// pattern, fault weights that do not pair up with its fault types, or
+    /// latency distribution parameters that cannot be sampled.
     pub fn new(routes: Vec<RouteConfig>) -> Result<Self> {
+        for route in &routes {
+            if let Some(fault_config) = &route.fault_injection {
+                Self::validate_fault_weights(route, fault_config)?;
+            }
+            if let Some(latency_config) = &route.latency {
+                latency_config.validate().map_err(|e| {
+                    Error::config(format!("Route {} {}: {}", route.method, route.path, e))
+                })?;
+            }
+        }
         let matcher = RouteMatcher::new(routes)?;
-        Ok(Self { matcher })
+        Ok(Self {
+            matcher,
+            warm_keys: Arc::default(),
+            breakers: Arc::default(),
+        })
+    }
+
+    /// Check that fault weights, when given, have one usable weight per fault
+    /// type
+    fn validate_fault_weights(
+        route: &RouteConfig,
+        fault_config: &RouteFaultInjectionConfig,
+    ) -> Result<()> {
+        let Some(weights) = &fault_config.weights else {
+            return Ok(());
+        };
+        if weights.len() != fault_config.fault_types.len() {
+            return Err(Error::config(format!(
+                "Route {} {}: {} fault weights for {} fault types",
+                route.method,
+                route.path,
+                weights.len(),
+                fault_config.fault_types.len()
+            )));
+        }
+        if !weights.is_empty() {
+            WeightedIndex::new(weights).map_err(|e| {
+                Error::config(format!(
+                    "Route {} {}: invalid fault weights: {}",
+                    route.method, route.path, e
+                ))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// The configured route a request matches, if any
+    pub fn matched_route(&self, method: &Method, uri: &Uri) -> Option<&RouteConfig> {
+        self.matcher.match_route(method, uri)
+    }
+
+    /// The configured route a request matches, if any, taking its headers
+    /// into account
+    pub fn matched_route_with_headers(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Option<&RouteConfig> {
+        self.matcher.match_route_with_headers(method, uri, headers)
     }
 
     /// Check if a fault should be injected for this request
@@ -178,12 +351,58 @@ impl RouteChaosInjector {
         method: &Method,
         uri: &Uri,
     ) -> Option<RouteFaultInjectionResult> {
-        let route = self.matcher.match_route(method, uri)?;
+        self.should_inject_fault_with_headers(method, uri, &HeaderMap::new())
+    }
+
+    /// Check if a fault should be injected for this request, matching routes
+    /// on its headers
+    pub fn should_inject_fault_with_headers(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Option<RouteFaultInjectionResult> {
+        self.should_inject_fault_for_client(method, uri, headers, None)
+    }
+
+    /// Check if a fault should be injected for this request from `client`
+    /// (its peer address, when known). Faults with a `request_matcher` only
+    /// apply to requests that match it.
+    pub fn should_inject_fault_for_client(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+        client: Option<IpAddr>,
+    ) -> Option<RouteFaultInjectionResult> {
+        let route = self.matcher.match_route_with_headers(method, uri, headers)?;
         let fault_config = route.fault_injection.as_ref()?;
 
         if !fault_config.enabled {
             return None;
         }
+        if let Some(matcher) = &fault_config.request_matcher {
+            if !Self::request_matches(matcher, headers, client) {
+                return None;
+            }
+        }
+
+        // Circuit breakers count every matching request, independent of the
+        // probability roll
+        let breaker = fault_config.fault_types.iter().find_map(|fault_type| match fault_type {
+            RouteFaultType::CircuitBreaker {
+                trip_after,
+                open_for_ms,
+            } => Some((fault_type, *trip_after, Duration::from_millis(*open_for_ms))),
+            _ => None,
+        });
+        if let Some((breaker, trip_after, open_for)) = breaker {
+            if self.breaker_is_open(route, trip_after, open_for) {
+                return Some(RouteFaultInjectionResult {
+                    fault_type: breaker.clone(),
+                });
+            }
+        }
 
         // Check probability - using rng() which is Send-safe
         let mut rng = rng();
@@ -191,22 +410,112 @@ impl RouteChaosInjector {
             return None;
         }
 
-        // Select a random fault type
-        if fault_config.fault_types.is_empty() {
+        // Select one of the other fault types, by weight when weights are
+        // configured
+        let candidates: Vec<usize> = (0..fault_config.fault_types.len())
+            .filter(|&i| {
+                !matches!(fault_config.fault_types[i], RouteFaultType::CircuitBreaker { .. })
+            })
+            .collect();
+        if candidates.is_empty() {
             return None;
         }
 
-        let fault_type =
-            &fault_config.fault_types[rng.random_range(0..fault_config.fault_types.len())];
+        let weighted = fault_config
+            .weights
+            .as_deref()
+            .map(|weights| WeightedIndex::new(candidates.iter().map(|&i| weights[i])));
+        let index = match weighted {
+            Some(Ok(weighted)) => candidates[weighted.sample(&mut rng)],
+            _ => candidates[rng.random_range(0..candidates.len())],
+        };
+        let fault_type = &fault_config.fault_types[index];
 
         Some(RouteFaultInjectionResult {
             fault_type: fault_type.clone(),
         })
     }
 
+    /// Whether a request satisfies a fault's request matcher: every populated
+    /// field must match, and within `source_ips` any entry may. The body size
+    /// is taken from `Content-Length`, so bodies without one count as empty.
+    fn request_matches(
+        matcher: &RequestMatcherConfig,
+        headers: &HeaderMap,
+        client: Option<IpAddr>,
+    ) -> bool {
+        if !matcher.source_ips.is_empty()
+            && !client.is_some_and(|ip| {
+                matcher.source_ips.iter().any(|range| {
+                    range
+                        .parse::<IpNet>()
+                        .map(|net| net.contains(&ip))
+                        .or_else(|_| range.parse::<IpAddr>().map(|addr| addr == ip))
+                        .unwrap_or(false)
+                })
+            })
+        {
+            return false;
+        }
+
+        let headers_match = matcher.headers.iter().all(|wanted| {
+            headers.get_all(wanted.name.as_str()).iter().any(|value| match &wanted.value {
+                Some(expected) => value.to_str().is_ok_and(|v| v == expected),
+                None => true,
+            })
+        });
+        if !headers_match {
+            return false;
+        }
+
+        let body_size = headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(0);
+        if matcher.min_body_size_bytes.is_some_and(|min| body_size < min)
+            || matcher.max_body_size_bytes.is_some_and(|max| body_size > max)
+        {
+            return false;
+        }
+
+        let chunked = headers
+            .get(header::TRANSFER_ENCODING)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.to_ascii_lowercase().contains("chunked"));
+        matcher.chunked_only.is_none_or(|want| want == chunked)
+    }
+
+    /// Count a request against the route's circuit breaker and report
+    /// whether it is open. The request after the `trip_after`th opens it for
+    /// `open_for`; once that passes, the breaker closes and counts again.
+    fn breaker_is_open(&self, route: &RouteConfig, trip_after: u32, open_for: Duration) -> bool {
+        let key = format!("{} {}", route.method.to_uppercase(), route.path);
+        let now = Instant::now();
+        let mut breakers = self.breakers.lock().unwrap_or_else(|p| p.into_inner());
+        let state = breakers.entry(key).or_default();
+        if let Some(open_until) = state.open_until {
+            if now < open_until {
+                return true;
+            }
+            *state = BreakerState::default();
+        }
+        state.matches = state.matches.saturating_add(1);
+        if state.matches > trip_after {
+            state.open_until = Some(now + open_for);
+            return true;
+        }
+        false
+    }
+
     /// Inject latency for this request (internal implementation)
-    async fn inject_latency_impl(&self, method: &Method, uri: &Uri) -> Result<()> {
-        let Some(route) = self.matcher.match_route(method, uri) else {
+    async fn inject_latency_impl(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> Result<()> {
+        let Some(route) = self.matcher.match_route_with_headers(method, uri, headers) else {
             return Ok(()); // No route match, no latency injection
         };
 
@@ -218,6 +527,16 @@ impl RouteChaosInjector {
             return Ok(());
         }
 
+        // Warm keys on a simulated cache skip the configured (miss) delay
+        if let Some(cache) = &latency_config.cache {
+            if self.is_cache_hit(method, uri, Duration::from_millis(cache.ttl_ms)) {
+                if cache.hit_delay_ms > 0 {
+                    sleep(Duration::from_millis(cache.hit_delay_ms)).await;
+                }
+                return Ok(());
+            }
+        }
+
         // Calculate delay before any await point to ensure Send safety
         // All RNG operations must complete before the await
         let delay_ms = {
@@ -240,7 +559,24 @@ impl RouteChaosInjector {
         Ok(())
     }
 
+    /// Whether the request's cache key is warm. A miss warms the key for
+    /// `ttl`; hits leave the expiry unchanged.
+    fn is_cache_hit(&self, method: &Method, uri: &Uri, ttl: Duration) -> bool {
+        let key =
+            format!("{} {}", method, uri.path_and_query().map_or(uri.path(), |pq| pq.as_str()));
+        let now = Instant::now();
+        let mut warm_keys = self.warm_keys.lock().unwrap_or_else(|p| p.into_inner());
+        if warm_keys.get(&key).is_some_and(|expires| *expires > now) {
+            return true;
+        }
+        warm_keys.retain(|_, expires| *expires > now);
+        warm_keys.insert(key, now + ttl);
+        false
+    }
+
     /// Calculate delay based on latency configuration
+    ///
+    /// Delays drawn from a distribution are clamped to `max_delay_ms`.
     #[allow(
         clippy::cast_possible_truncation,
         clippy::cast_sign_loss,
@@ -250,6 +586,10 @@ impl RouteChaosInjector {
         // Using rng() which is Send-safe
         let mut rng = rng();
 
+        let sampled = !matches!(
+            config.distribution,
+            LatencyDistribution::Fixed | LatencyDistribution::Uniform
+        );
         let base_delay = match &config.distribution {
             LatencyDistribution::Fixed => config.fixed_delay_ms.unwrap_or(0),
             LatencyDistribution::Normal {
@@ -278,25 +618,53 @@ impl RouteChaosInjector {
                     config.fixed_delay_ms.unwrap_or(0)
                 }
             }
+            LatencyDistribution::LogNormal { mu, sigma } => {
+                // Box-Muller for the underlying normal; 1 - u keeps ln away from 0
+                let u1: f64 = rng.random();
+                let u2: f64 = rng.random();
+                let z0 = (-2.0 * (1.0 - u1).ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                let value = (mu + sigma * z0).exp();
+                // Clamp to non-negative before casting (NaN becomes 0)
+                value.max(0.0) as u64
+            }
+            LatencyDistribution::Pareto { scale_ms, shape } => {
+                // Inverse transform sampling: scale / U^(1/shape), U in (0, 1]
+                let u: f64 = rng.random();
+                let value = scale_ms / (1.0 - u).powf(1.0 / shape);
+                // Clamp to non-negative before casting (NaN becomes 0)
+                value.max(0.0) as u64
+            }
         };
 
         // Apply jitter
-        if config.jitter_percent > 0.0 {
+        let delay = if config.jitter_percent > 0.0 {
             let jitter = (base_delay as f64 * config.jitter_percent / 100.0) as u64;
             let jitter_offset = rng.random_range(0..=jitter);
             if rng.random_bool(0.5) {
-                base_delay + jitter_offset
+                base_delay.saturating_add(jitter_offset)
             } else {
                 base_delay.saturating_sub(jitter_offset)
             }
         } else {
             base_delay
+        };
+
+        if sampled {
+            delay.min(config.max_delay_ms)
+        } else {
+            delay
         }
     }
 
     /// Get fault injection response for a request (internal implementation)
-    fn get_fault_response_impl(&self, method: &Method, uri: &Uri) -> Option<RouteFaultResponse> {
-        let fault_result = self.should_inject_fault(method, uri)?;
+    fn get_fault_response_impl(
+        &self,
+        method: &Method,
+        uri: &Uri,
+        headers: &HeaderMap,
+        client: Option<IpAddr>,
+    ) -> Option<RouteFaultResponse> {
+        let fault_result = self.should_inject_fault_for_client(method, uri, headers, client)?;
 
         match &fault_result.fault_type {
             RouteFaultType::HttpError {
@@ -308,11 +676,16 @@ impl RouteChaosInjector {
                     .clone()
                     .unwrap_or_else(|| format!("Injected HTTP error {status_code}")),
                 fault_type: "http_error".to_string(),
+                retry_after_secs: None,
             }),
-            RouteFaultType::ConnectionError { message } => Some(RouteFaultResponse {
+            RouteFaultType::ConnectionError {
+                message,
+                retry_after_secs,
+            } => Some(RouteFaultResponse {
                 status_code: 503,
                 error_message: message.clone().unwrap_or_else(|| "Connection error".to_string()),
                 fault_type: "connection_error".to_string(),
+                retry_after_secs: *retry_after_secs,
             }),
             RouteFaultType::Timeout {
                 duration_ms,
@@ -323,19 +696,69 @@ impl RouteChaosInjector {
                     .clone()
                     .unwrap_or_else(|| format!("Request timeout after {duration_ms}ms")),
                 fault_type: "timeout".to_string(),
+                retry_after_secs: None,
             }),
             RouteFaultType::PartialResponse { truncate_percent } => Some(RouteFaultResponse {
                 status_code: 200,
                 error_message: format!("Partial response (truncated at {truncate_percent}%)"),
                 fault_type: "partial_response".to_string(),
+                retry_after_secs: None,
             }),
             RouteFaultType::PayloadCorruption { corruption_type } => Some(RouteFaultResponse {
                 status_code: 200,
                 error_message: format!("Payload corruption ({corruption_type})"),
                 fault_type: "payload_corruption".to_string(),
+                retry_after_secs: None,
+            }),
+            RouteFaultType::CircuitBreaker { open_for_ms, .. } => Some(RouteFaultResponse {
+                status_code: 503,
+                error_message: "Circuit breaker open".to_string(),
+                fault_type: "circuit_breaker".to_string(),
+                retry_after_secs: Some(open_for_ms.div_ceil(1000)),
             }),
         }
     }
+
+    /// Corrupt a response body for a `PayloadCorruption` fault
+    ///
+    /// Supported corruption types are `random_bytes` (replace ~10% of bytes
+    /// with different values, keeping the length), `truncate` (cut at a
+    /// random offset between 50% and 90% of the length) and `bit_flip` (flip
+    /// one bit in ~10% of bytes). Unknown types leave the body unchanged.
+    pub fn corrupt_payload(&self, body: &[u8], corruption_type: &str) -> Vec<u8> {
+        let mut corrupted = body.to_vec();
+        if body.is_empty() {
+            return corrupted;
+        }
+
+        // Using rng() which is Send-safe
+        let mut rng = rng();
+        let affected = (body.len() / 10).max(1);
+        match corruption_type {
+            "random_bytes" => {
+                for index in sample(&mut rng, body.len(), affected) {
+                    // XOR with a non-zero value so the byte always changes
+                    corrupted[index] ^= rng.random_range(1..=u8::MAX);
+                }
+            }
+            "truncate" => {
+                let min = body.len() / 2;
+                let max = body.len() * 9 / 10;
+                corrupted.truncate(if max > min {
+                    rng.random_range(min..=max)
+                } else {
+                    min
+                });
+            }
+            "bit_flip" => {
+                for index in sample(&mut rng, body.len(), affected) {
+                    corrupted[index] ^= 1 << rng.random_range(0..8);
+                }
+            }
+            other => debug!("Unknown payload corruption type '{}'; body left intact", other),
+        }
+        corrupted
+    }
 }
 
 /// Result of fault injection check
@@ -354,12 +777,14 @@ pub struct RouteFaultResponse {
     pub error_message: String,
     /// Fault type identifier
     pub fault_type: String,
+    /// Seconds to advertise in a `Retry-After` header, if any
+    pub retry_after_secs: Option<u64>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mockforge_core::config::{RouteConfig, RouteResponseConfig};
+    use mockforge_core::config::{RouteConfig, RouteRequestConfig, RouteResponseConfig};
     use std::collections::HashMap;
 
     fn create_test_route(path: &str, method: &str) -> RouteConfig {
@@ -369,8 +794,8 @@ mod tests {
             request: None,
             response: RouteResponseConfig {
                 status: 200,
-                headers: HashMap::new(),
                 body: None,
+                ..Default::default()
             },
             fault_injection: None,
             latency: None,
@@ -472,6 +897,60 @@ mod tests {
         assert!(cloned.match_route(&Method::GET, &Uri::from_static("/test")).is_some());
     }
 
+    #[test]
+    fn test_route_matching_required_header() {
+        let mut route = create_test_route("/users/{id}", "GET");
+        route.request = Some(RouteRequestConfig {
+            headers: HashMap::from([("X-Tenant".to_string(), "acme".to_string())]),
+            ..Default::default()
+        });
+        let matcher = RouteMatcher::new(vec![route]).unwrap();
+        let uri = Uri::from_static("/users/123");
+
+        let mut headers = HeaderMap::new();
+        assert!(matcher.match_route_with_headers(&Method::GET, &uri, &headers).is_none());
+        assert!(matcher.match_route(&Method::GET, &uri).is_none());
+
+        headers.insert("x-tenant", "other".parse().unwrap());
+        assert!(matcher.match_route_with_headers(&Method::GET, &uri, &headers).is_none());
+
+        headers.insert("x-tenant", "acme".parse().unwrap());
+        assert!(matcher.match_route_with_headers(&Method::GET, &uri, &headers).is_some());
+    }
+
+    #[test]
+    fn test_route_matching_raw_query_regex() {
+        let mut route = create_test_route("/items", "GET");
+        route.request = Some(RouteRequestConfig {
+            raw_query_regex: Some(r"(^|&)filter\[name\]=[^&]+".to_string()),
+            ..Default::default()
+        });
+        let matcher = RouteMatcher::new(vec![route]).unwrap();
+
+        let matches =
+            |uri: &'static str| matcher.match_route(&Method::GET, &Uri::from_static(uri)).is_some();
+        assert!(matches("/items?filter[name]=x"));
+        assert!(matches("/items?page=2&filter[name]=widget"));
+        assert!(!matches("/items?filter[type]=x"));
+        assert!(!matches("/items"));
+    }
+
+    #[test]
+    fn test_route_matching_required_query() {
+        let mut route = create_test_route("/search", "GET");
+        route.request = Some(RouteRequestConfig {
+            query: HashMap::from([("q".to_string(), "rust lang".to_string())]),
+            ..Default::default()
+        });
+        let matcher = RouteMatcher::new(vec![route]).unwrap();
+
+        assert!(matcher.match_route(&Method::GET, &Uri::from_static("/search")).is_none());
+        assert!(matcher.match_route(&Method::GET, &Uri::from_static("/search?q=go")).is_none());
+        assert!(matcher
+            .match_route(&Method::GET, &Uri::from_static("/search?page=2&q=rust+lang"))
+            .is_some());
+    }
+
     // RouteChaosInjector tests
     #[test]
     fn test_route_chaos_injector_new() {
@@ -515,6 +994,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -525,6 +1007,60 @@ mod tests {
         assert!(elapsed >= Duration::from_millis(10));
     }
 
+    #[tokio::test]
+    async fn test_latency_cache_simulation_delays_only_misses() {
+        use mockforge_core::config::{RouteCacheLatencyConfig, RouteLatencyConfig};
+
+        let mut route = create_test_route("/items/{id}", "GET");
+        route.latency = Some(RouteLatencyConfig {
+            enabled: true,
+            fixed_delay_ms: Some(100),
+            cache: Some(RouteCacheLatencyConfig {
+                ttl_ms: 60_000,
+                hit_delay_ms: 0,
+            }),
+            ..Default::default()
+        });
+        let injector = RouteChaosInjector::new(vec![route]).unwrap();
+        let timed = |uri: &'static str| {
+            let injector = injector.clone();
+            async move {
+                let start = std::time::Instant::now();
+                injector.inject_latency(&Method::GET, &Uri::from_static(uri)).await.unwrap();
+                start.elapsed()
+            }
+        };
+
+        assert!(timed("/items/1").await >= Duration::from_millis(100));
+        assert!(timed("/items/1").await < Duration::from_millis(50));
+        // A different key is a separate cache entry
+        assert!(timed("/items/2").await >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_latency_cache_simulation_expires_after_ttl() {
+        use mockforge_core::config::{RouteCacheLatencyConfig, RouteLatencyConfig};
+
+        let mut route = create_test_route("/test", "GET");
+        route.latency = Some(RouteLatencyConfig {
+            enabled: true,
+            fixed_delay_ms: Some(50),
+            cache: Some(RouteCacheLatencyConfig {
+                ttl_ms: 20,
+                hit_delay_ms: 0,
+            }),
+            ..Default::default()
+        });
+        let injector = RouteChaosInjector::new(vec![route]).unwrap();
+        let uri = Uri::from_static("/test");
+
+        injector.inject_latency(&Method::GET, &uri).await.unwrap();
+        sleep(Duration::from_millis(30)).await;
+        let start = std::time::Instant::now();
+        injector.inject_latency(&Method::GET, &uri).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
     #[tokio::test]
     async fn test_latency_injection_disabled() {
         use mockforge_core::config::RouteLatencyConfig;
@@ -537,6 +1073,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -587,6 +1126,9 @@ mod tests {
             random_delay_range_ms: Some((5, 15)),
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Uniform,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -614,6 +1156,9 @@ mod tests {
                 mean_ms: 10.0,
                 std_dev_ms: 1.0,
             },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -633,6 +1178,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Exponential { lambda: 0.1 },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -652,6 +1200,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 50.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -672,6 +1223,8 @@ mod tests {
                 status_code: 500,
                 message: Some("Test error".to_string()),
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -694,6 +1247,8 @@ mod tests {
                 status_code: 503,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -715,7 +1270,10 @@ mod tests {
             probability: 1.0,
             fault_types: vec![RouteFaultType::ConnectionError {
                 message: Some("Network failure".to_string()),
+                retry_after_secs: Some(30),
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -725,6 +1283,7 @@ mod tests {
         assert_eq!(response.status_code, 503);
         assert_eq!(response.error_message, "Network failure");
         assert_eq!(response.fault_type, "connection_error");
+        assert_eq!(response.retry_after_secs, Some(30));
     }
 
     #[test]
@@ -735,7 +1294,12 @@ mod tests {
         route.fault_injection = Some(RouteFaultInjectionConfig {
             enabled: true,
             probability: 1.0,
-            fault_types: vec![RouteFaultType::ConnectionError { message: None }],
+            fault_types: vec![RouteFaultType::ConnectionError {
+                message: None,
+                retry_after_secs: None,
+            }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -758,6 +1322,8 @@ mod tests {
                 duration_ms: 5000,
                 message: Some("Gateway timeout".to_string()),
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -781,6 +1347,8 @@ mod tests {
                 duration_ms: 3000,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -802,6 +1370,8 @@ mod tests {
             fault_types: vec![RouteFaultType::PartialResponse {
                 truncate_percent: 50.0,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -824,6 +1394,8 @@ mod tests {
             fault_types: vec![RouteFaultType::PayloadCorruption {
                 corruption_type: "random_bytes".to_string(),
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -835,6 +1407,27 @@ mod tests {
         assert_eq!(response.fault_type, "payload_corruption");
     }
 
+    #[test]
+    fn test_corrupt_payload() {
+        let injector = RouteChaosInjector::new(vec![]).unwrap();
+        let body = br#"{"id":42,"name":"Widget","tags":["a","b","c"],"price":9.99}"#;
+
+        let corrupted = injector.corrupt_payload(body, "random_bytes");
+        assert_eq!(corrupted.len(), body.len());
+        assert_ne!(corrupted, body.to_vec());
+
+        let truncated = injector.corrupt_payload(body, "truncate");
+        assert!(truncated.len() < body.len());
+        assert_eq!(truncated[..], body[..truncated.len()]);
+
+        let flipped = injector.corrupt_payload(body, "bit_flip");
+        assert_eq!(flipped.len(), body.len());
+        assert_ne!(flipped, body.to_vec());
+
+        assert_eq!(injector.corrupt_payload(body, "unknown"), body.to_vec());
+        assert!(injector.corrupt_payload(b"", "truncate").is_empty());
+    }
+
     #[test]
     fn test_fault_injection_disabled() {
         use mockforge_core::config::{RouteFaultInjectionConfig, RouteFaultType};
@@ -847,6 +1440,8 @@ mod tests {
                 status_code: 500,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -864,6 +1459,8 @@ mod tests {
             enabled: true,
             probability: 1.0,
             fault_types: vec![],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -872,6 +1469,131 @@ mod tests {
         assert!(response.is_none());
     }
 
+    fn weighted_fault_route(weights: Option<Vec<f64>>) -> RouteConfig {
+        let mut route = create_test_route("/test", "GET");
+        route.fault_injection = Some(RouteFaultInjectionConfig {
+            enabled: true,
+            probability: 1.0,
+            fault_types: vec![
+                RouteFaultType::HttpError {
+                    status_code: 500,
+                    message: None,
+                },
+                RouteFaultType::Timeout {
+                    duration_ms: 10,
+                    message: None,
+                },
+            ],
+            weights,
+            request_matcher: None,
+        });
+        route
+    }
+
+    #[test]
+    fn test_fault_injection_weighted_selection() {
+        let injector =
+            RouteChaosInjector::new(vec![weighted_fault_route(Some(vec![0.9, 0.1]))]).unwrap();
+
+        let http_errors = (0..2000)
+            .filter(|_| {
+                let result =
+                    injector.should_inject_fault(&Method::GET, &Uri::from_static("/test")).unwrap();
+                matches!(result.fault_type, RouteFaultType::HttpError { .. })
+            })
+            .count();
+        // Expected 1800; far from the ~1000 of uniform selection
+        assert!((1650..=1950).contains(&http_errors), "http errors: {http_errors}");
+    }
+
+    #[test]
+    fn test_fault_injection_weights_must_match_fault_types() {
+        let err = RouteChaosInjector::new(vec![weighted_fault_route(Some(vec![1.0]))]).unwrap_err();
+        assert!(err.to_string().contains("1 fault weights for 2 fault types"));
+
+        assert!(RouteChaosInjector::new(vec![weighted_fault_route(Some(vec![0.0, 0.0]))]).is_err());
+        assert!(RouteChaosInjector::new(vec![weighted_fault_route(None)]).is_ok());
+    }
+
+    #[test]
+    fn test_fault_injection_circuit_breaker_opens_then_closes() {
+        fn assert_clone_send<T: Clone + Send + Sync>() {}
+        assert_clone_send::<RouteChaosInjector>();
+
+        let mut route = create_test_route("/orders/{id}", "GET");
+        route.fault_injection = Some(RouteFaultInjectionConfig {
+            enabled: true,
+            probability: 0.0,
+            fault_types: vec![RouteFaultType::CircuitBreaker {
+                trip_after: 3,
+                open_for_ms: 100,
+            }],
+            weights: None,
+            request_matcher: None,
+        });
+        let injector = RouteChaosInjector::new(vec![route]).unwrap();
+        // Clones share the breaker state
+        let clone = injector.clone();
+        let status = |injector: &RouteChaosInjector, path: &'static str| {
+            injector
+                .get_fault_response(&Method::GET, &Uri::from_static(path))
+                .map(|r| r.status_code)
+        };
+
+        assert_eq!(status(&injector, "/orders/1"), None);
+        assert_eq!(status(&clone, "/orders/2"), None);
+        assert_eq!(status(&injector, "/orders/3"), None);
+        // Request N+1 trips the breaker, and it stays open for the window
+        assert_eq!(status(&clone, "/orders/4"), Some(503));
+        assert_eq!(status(&injector, "/orders/5"), Some(503));
+        let response = injector
+            .get_fault_response(&Method::GET, &Uri::from_static("/orders/6"))
+            .unwrap();
+        assert_eq!(response.fault_type, "circuit_breaker");
+        assert_eq!(response.retry_after_secs, Some(1));
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(status(&injector, "/orders/7"), None);
+        assert_eq!(status(&injector, "/orders/8"), None);
+    }
+
+    #[test]
+    fn test_fault_injection_request_matcher_by_source_ip() {
+        use mockforge_core::config::{
+            RequestMatcherConfig, RouteFaultInjectionConfig, RouteFaultType,
+        };
+
+        let mut route = create_test_route("/test", "GET");
+        route.fault_injection = Some(RouteFaultInjectionConfig {
+            enabled: true,
+            probability: 1.0,
+            fault_types: vec![RouteFaultType::HttpError {
+                status_code: 500,
+                message: None,
+            }],
+            weights: None,
+            request_matcher: Some(RequestMatcherConfig {
+                source_ips: vec!["192.0.2.10".to_string(), "198.51.100.0/24".to_string()],
+                ..Default::default()
+            }),
+        });
+        let injector = RouteChaosInjector::new(vec![route]).unwrap();
+        let fault_for = |client: Option<&str>| {
+            injector.get_fault_response_for_client(
+                &Method::GET,
+                &Uri::from_static("/test"),
+                &HeaderMap::new(),
+                client.map(|ip| ip.parse().unwrap()),
+            )
+        };
+
+        assert_eq!(fault_for(Some("192.0.2.10")).unwrap().status_code, 500);
+        assert_eq!(fault_for(Some("198.51.100.77")).unwrap().status_code, 500);
+        assert!(fault_for(Some("192.0.2.11")).is_none());
+        // Callers that do not know the peer address never match an IP list
+        assert!(fault_for(None).is_none());
+    }
+
     #[test]
     fn test_fault_injection_no_config() {
         let route = create_test_route("/test", "GET");
@@ -893,6 +1615,8 @@ mod tests {
                 status_code: 500,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -908,6 +1632,7 @@ mod tests {
             status_code: 500,
             error_message: "Error".to_string(),
             fault_type: "http_error".to_string(),
+            retry_after_secs: None,
         };
         let debug = format!("{response:?}");
         assert!(debug.contains("RouteFaultResponse"));
@@ -920,6 +1645,7 @@ mod tests {
             status_code: 503,
             error_message: "Service unavailable".to_string(),
             fault_type: "connection_error".to_string(),
+            retry_after_secs: None,
         };
         let cloned = response.clone();
         assert_eq!(response.status_code, cloned.status_code);
@@ -968,6 +1694,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -993,6 +1722,8 @@ mod tests {
                 status_code: 502,
                 message: Some("Bad gateway".to_string()),
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1135,6 +1866,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1152,6 +1886,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 20.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1170,6 +1907,9 @@ mod tests {
             random_delay_range_ms: Some((50, 150)),
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Uniform,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1187,6 +1927,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Uniform,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1205,6 +1948,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Uniform,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1225,6 +1971,9 @@ mod tests {
                 mean_ms: 100.0,
                 std_dev_ms: 10.0,
             },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         // Normal distribution should produce values, typically around the mean
@@ -1243,6 +1992,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Exponential { lambda: 0.01 },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         // Exponential distribution should produce non-negative values (delay is u64, always >= 0)
@@ -1263,6 +2015,9 @@ mod tests {
                 mean_ms: 100.0,
                 std_dev_ms: 5.0,
             },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         // Should produce non-negative values with jitter applied (delay is u64, always >= 0)
@@ -1280,6 +2035,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1297,6 +2055,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 100.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1315,6 +2076,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 200.0, // 200% jitter could cause subtraction to go negative
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         let delay = RouteChaosInjector::calculate_delay(&config);
@@ -1322,6 +2086,111 @@ mod tests {
         assert!(delay < u64::MAX);
     }
 
+    fn distribution_config(distribution: LatencyDistribution) -> RouteLatencyConfig {
+        RouteLatencyConfig {
+            enabled: true,
+            probability: 1.0,
+            fixed_delay_ms: None,
+            random_delay_range_ms: None,
+            jitter_percent: 0.0,
+            distribution,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
+        }
+    }
+
+    fn mean_delay(config: &RouteLatencyConfig, samples: u32) -> f64 {
+        let total: f64 =
+            (0..samples).map(|_| RouteChaosInjector::calculate_delay(config) as f64).sum();
+        total / f64::from(samples)
+    }
+
+    #[test]
+    fn test_calculate_delay_log_normal_mean() {
+        // Mean of a log-normal is exp(mu + sigma^2 / 2) ≈ 61.9 ms here
+        let config = distribution_config(LatencyDistribution::LogNormal {
+            mu: 4.0,
+            sigma: 0.5,
+        });
+        let mean = mean_delay(&config, 20_000);
+        assert!((55.0..70.0).contains(&mean), "log-normal mean {mean}");
+    }
+
+    #[test]
+    fn test_calculate_delay_pareto_mean_and_minimum() {
+        // Mean of a Pareto is shape * scale / (shape - 1) = 15 ms here
+        let config = distribution_config(LatencyDistribution::Pareto {
+            scale_ms: 10.0,
+            shape: 3.0,
+        });
+        let mean = mean_delay(&config, 20_000);
+        assert!((13.0..17.0).contains(&mean), "pareto mean {mean}");
+        for _ in 0..1000 {
+            assert!(RouteChaosInjector::calculate_delay(&config) >= 10);
+        }
+    }
+
+    #[test]
+    fn test_calculate_delay_heavy_tailed_is_clamped() {
+        let distributions = [
+            LatencyDistribution::LogNormal {
+                mu: 1e6,
+                sigma: 1e6,
+            },
+            LatencyDistribution::LogNormal {
+                mu: -1e6,
+                sigma: 0.0,
+            },
+            LatencyDistribution::Pareto {
+                scale_ms: 1.0,
+                shape: 1e-9,
+            },
+        ];
+        for distribution in distributions {
+            let mut config = distribution_config(distribution);
+            config.max_delay_ms = 250;
+            for jitter_percent in [0.0, 50.0] {
+                config.jitter_percent = jitter_percent;
+                for _ in 0..100 {
+                    assert!(RouteChaosInjector::calculate_delay(&config) <= 250);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_distribution_parameters_are_rejected() {
+        let distributions = [
+            LatencyDistribution::LogNormal {
+                mu: f64::NAN,
+                sigma: 1.0,
+            },
+            LatencyDistribution::LogNormal {
+                mu: 1.0,
+                sigma: -1.0,
+            },
+            LatencyDistribution::Pareto {
+                scale_ms: 1.0,
+                shape: 0.0,
+            },
+            LatencyDistribution::Pareto {
+                scale_ms: -5.0,
+                shape: 2.0,
+            },
+            LatencyDistribution::Pareto {
+                scale_ms: f64::INFINITY,
+                shape: 2.0,
+            },
+        ];
+        for distribution in distributions {
+            let mut route = create_test_route("/slow", "GET");
+            route.latency = Some(distribution_config(distribution));
+            let err = RouteChaosInjector::new(vec![route]).err().expect("invalid parameters");
+            assert!(err.to_string().contains("GET /slow"), "{err}");
+        }
+    }
+
     // should_inject_fault tests
     #[test]
     fn test_should_inject_fault_zero_probability() {
@@ -1335,6 +2204,8 @@ mod tests {
                 status_code: 500,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1367,8 +2238,13 @@ mod tests {
                     duration_ms: 1000,
                     message: None,
                 },
-                RouteFaultType::ConnectionError { message: None },
+                RouteFaultType::ConnectionError {
+                    message: None,
+                    retry_after_secs: None,
+                },
             ],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1401,6 +2277,8 @@ mod tests {
                     message: None,
                 },
             ],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1431,6 +2309,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1456,6 +2337,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1566,6 +2450,8 @@ mod tests {
                 status_code: 429,
                 message: Some("Rate limit exceeded".to_string()),
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1592,6 +2478,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Fixed,
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         });
         route.fault_injection = Some(RouteFaultInjectionConfig {
             enabled: true,
@@ -1600,6 +2489,8 @@ mod tests {
                 duration_ms: 5000,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1630,6 +2521,8 @@ mod tests {
                 status_code: 404,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let mut route2 = create_test_route("/api/v1/orders", "GET");
@@ -1640,6 +2533,8 @@ mod tests {
                 status_code: 500,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route1, route2]).unwrap();
@@ -1700,6 +2595,8 @@ mod tests {
                 status_code: 500,
                 message: None,
             }],
+            weights: None,
+            request_matcher: None,
         });
 
         let injector = RouteChaosInjector::new(vec![route]).unwrap();
@@ -1728,6 +2625,9 @@ mod tests {
                 mean_ms: 10.0,
                 std_dev_ms: 100.0, // Large std dev can create negative values
             },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
 
         // Run multiple times to potentially hit negative values that should be clamped
@@ -1751,6 +2651,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Exponential { lambda: 0.001 },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
         // delay is u64, always >= 0
         let _ = RouteChaosInjector::calculate_delay(&config);
@@ -1763,6 +2666,9 @@ mod tests {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: LatencyDistribution::Exponential { lambda: 10.0 },
+            body_transfer_ms: None,
+            cache: None,
+            max_delay_ms: 60_000,
         };
         // delay is u64, always >= 0
         let _ = RouteChaosInjector::calculate_delay(&config);
@@ -79,6 +79,9 @@ pub struct RequestMatchCriteria {
     /// Headers that must be present and match (case-insensitive header names)
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub headers: HashMap<String, String>,
+    /// Headers that must not be present (case-insensitive header names)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub header_absent: Vec<String>,
     /// Query parameters that must be present and match
     #[serde(skip_serializing_if = "HashMap::is_empty")]
     pub query_params: HashMap<String, String>,
@@ -499,6 +502,23 @@ impl MockConfigBuilder {
         self
     }
 
+    /// Require a header to be absent from the request
+    ///
+    /// # Examples
+    /// ```rust
+    /// use mockforge_sdk::MockConfigBuilder;
+    ///
+    /// MockConfigBuilder::new("GET", "/api/me")
+    ///     .without_header("Authorization")
+    ///     .status(401);
+    /// ```
+    pub fn without_header(mut self, name: impl Into<String>) -> Self {
+        let match_criteria =
+            self.config.request_match.get_or_insert_with(RequestMatchCriteria::default);
+        match_criteria.header_absent.push(name.into());
+        self
+    }
+
     /// Require a specific query parameter to be present and match
     ///
     /// # Examples
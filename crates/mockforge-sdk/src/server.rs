@@ -372,6 +372,7 @@ impl MockServer {
                 status: stub.status,
                 headers: stub.headers,
                 body: Some(stub.body),
+                ..Default::default()
             },
             fault_injection: None,
             latency: None,
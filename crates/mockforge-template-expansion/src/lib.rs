@@ -12,6 +12,7 @@
 //! bringing `rng()` into scope. `RequestContext` is duplicated here.
 
 use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Request context for prompt template expansion
@@ -28,6 +29,8 @@ pub struct RequestContext {
     pub path_params: HashMap<String, Value>,
     /// Query parameters
     pub query_params: HashMap<String, Value>,
+    /// Query string as sent, undecoded and without the leading `?`
+    pub raw_query: String,
     /// Request headers
     pub headers: HashMap<String, Value>,
     /// Request body (if JSON)
@@ -63,6 +66,13 @@ impl RequestContext {
         self
     }
 
+    /// Set the raw query string
+    #[must_use]
+    pub fn with_raw_query(mut self, raw_query: impl Into<String>) -> Self {
+        self.raw_query = raw_query.into();
+        self
+    }
+
     /// Set headers
     #[must_use]
     pub fn with_headers(mut self, headers: HashMap<String, Value>) -> Self {
@@ -104,6 +114,12 @@ impl RequestContext {
 /// # Returns
 /// String with all template variables replaced with actual values from context
 ///
+/// # Blocks
+/// `{{#if key}}...{{/if}}` renders its content only when `key` resolves to a
+/// truthy value, and `{{#each key}}...{{/each}}` renders its content once per
+/// element of the array `key` resolves to, with the element available as
+/// `{{this}}` / `{{this.field}}` and its position as `{{@index}}`. Blocks nest.
+///
 /// # Example
 /// ```
 /// use mockforge_template_expansion::{expand_prompt_template, RequestContext};
@@ -131,8 +147,145 @@ pub fn expand_prompt_template(template: &str, context: &RequestContext) -> Strin
     // real `authorization` header. The previous implementation ran an ordered
     // chain of full-buffer `String::replace` passes, which leaked values across
     // namespaces depending on pass order.
-    let bytes = template.as_bytes();
+    expand_tokens(template, context, |value, out| out.push_str(value))
+}
+
+/// List the placeholders `context` cannot fill
+///
+/// Returns the distinct names (the placeholder text minus braces and
+/// filters, e.g. `body.missing`) that [`expand_prompt_template`] would leave
+/// in its output verbatim, in order of first appearance. Blocks are walked
+/// the way expansion renders them, so placeholders inside a skipped
+/// `{{#if}}` or an empty `{{#each}}` are not reported.
+///
+/// # Example
+/// ```
+/// use mockforge_template_expansion::{unresolved_variables, RequestContext};
+///
+/// let context = RequestContext::new("GET".to_string(), "/api".to_string());
+/// let missing = unresolved_variables("{{method}} {{query.q}} {{query.q}}", &context);
+/// assert_eq!(missing, vec!["query.q".to_string()]);
+/// ```
+#[must_use]
+pub fn unresolved_variables(template: &str, context: &RequestContext) -> Vec<String> {
+    let mut names = Vec::new();
+    collect_unresolved(template, context, None, &mut names);
+    names.into_iter().map(str::to_string).collect()
+}
+
+/// Walk `template` like [`render`] without producing output, pushing each
+/// distinct placeholder that would be kept literally onto `names`.
+fn collect_unresolved<'t>(
+    template: &'t str,
+    context: &RequestContext,
+    scope: Option<&Scope<'_>>,
+    names: &mut Vec<&'t str>,
+) {
+    let mut i = 0;
+    while let Some(start_rel) = template[i..].find("{{") {
+        let start = i + start_rel;
+        let Some(close_rel) = find_close(&template[start + 2..]) else {
+            i = start + 2;
+            continue;
+        };
+        let key = &template[start + 2..start + 2 + close_rel];
+        let after = start + 2 + close_rel + 2;
+        if let Some((kind, expr)) = BlockKind::parse_open(key) {
+            if let Some((body_end, block_end)) = find_block_end(template, after, kind) {
+                let body = &template[after..body_end];
+                let value = lookup_value(expr, context, scope);
+                match kind {
+                    BlockKind::If => {
+                        if value.as_deref().is_some_and(is_truthy) {
+                            collect_unresolved(body, context, scope, names);
+                        }
+                    }
+                    BlockKind::Each => {
+                        if let Some(Value::Array(items)) = value.as_deref() {
+                            for (index, item) in items.iter().enumerate() {
+                                let scope = Scope { item, index };
+                                collect_unresolved(body, context, Some(&scope), names);
+                            }
+                        }
+                    }
+                }
+                i = block_end;
+                continue;
+            }
+        }
+        if resolve_placeholder(key, context, scope).is_none() {
+            let name = split_filters(key).0.trim();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        i = after;
+    }
+}
+
+/// Single-pass tokenizer shared by the string and XML expanders. `emit`
+/// writes each resolved value into the output buffer (verbatim, or escaped
+/// for the target format); everything outside placeholders is copied as-is.
+fn expand_tokens(
+    template: &str,
+    context: &RequestContext,
+    emit: impl Fn(&str, &mut String),
+) -> String {
     let mut out = String::with_capacity(template.len());
+    render(template, context, None, &emit, &mut out);
+    out
+}
+
+/// The array element an `{{#each}}` block is currently rendering, bound to
+/// `{{this}}` (and `{{this.field}}`) with its position as `{{@index}}`.
+struct Scope<'a> {
+    item: &'a Value,
+    index: usize,
+}
+
+/// Kind of a `{{#kind expr}}...{{/kind}}` block
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    If,
+    Each,
+}
+
+impl BlockKind {
+    /// Parse the contents of an opening tag (`#if query.flag`) into the
+    /// block kind and the expression it tests or iterates.
+    fn parse_open(content: &str) -> Option<(Self, &str)> {
+        let content = content.trim();
+        let (kind, expr) = if let Some(rest) = content.strip_prefix("#if") {
+            (Self::If, rest)
+        } else {
+            (Self::Each, content.strip_prefix("#each")?)
+        };
+        if !expr.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let expr = expr.trim();
+        Some((kind, expr.strip_prefix("request.").unwrap_or(expr)))
+    }
+
+    fn parse_close(content: &str) -> Option<Self> {
+        match content.trim() {
+            "/if" => Some(Self::If),
+            "/each" => Some(Self::Each),
+            _ => None,
+        }
+    }
+}
+
+/// Render `template` into `out`. Blocks re-render their own template text
+/// (never a substituted value), so values stay inert inside blocks too.
+fn render(
+    template: &str,
+    context: &RequestContext,
+    scope: Option<&Scope<'_>>,
+    emit: &impl Fn(&str, &mut String),
+    out: &mut String,
+) {
+    let bytes = template.as_bytes();
     let mut i = 0;
     while i < bytes.len() {
         // Look for the start of a placeholder: literal "{{"
@@ -141,10 +294,34 @@ pub fn expand_prompt_template(template: &str, context: &RequestContext) -> Strin
             if let Some(close_rel) = find_close(&template[i + 2..]) {
                 let key = &template[i + 2..i + 2 + close_rel];
                 let after = i + 2 + close_rel + 2; // index just past the "}}"
-                match resolve_key(key.trim(), context) {
+                if let Some((kind, expr)) = BlockKind::parse_open(key) {
+                    if let Some((body_end, block_end)) = find_block_end(template, after, kind) {
+                        let body = &template[after..body_end];
+                        let value = lookup_value(expr, context, scope);
+                        match kind {
+                            BlockKind::If => {
+                                if value.as_deref().is_some_and(is_truthy) {
+                                    render(body, context, scope, emit, out);
+                                }
+                            }
+                            BlockKind::Each => {
+                                if let Some(Value::Array(items)) = value.as_deref() {
+                                    for (index, item) in items.iter().enumerate() {
+                                        let scope = Scope { item, index };
+                                        render(body, context, Some(&scope), emit, out);
+                                    }
+                                }
+                            }
+                        }
+                        i = block_end;
+                        continue;
+                    }
+                    // Unterminated block: falls through and is kept literally
+                }
+                match resolve_placeholder(key, context, scope) {
                     Some(value) => {
-                        // Write the resolved value verbatim; it is never rescanned.
-                        out.push_str(&value);
+                        // Write the resolved value out; it is never rescanned.
+                        emit(&value, out);
                     }
                     None => {
                         // Unknown placeholder: preserve it literally (legacy behavior).
@@ -165,7 +342,48 @@ pub fn expand_prompt_template(template: &str, context: &RequestContext) -> Strin
         out.push_str(&template[i..i + ch_len]);
         i += ch_len;
     }
-    out
+}
+
+/// Find the `{{/kind}}` closing a block whose body starts at `from`, skipping
+/// over nested blocks. Returns the byte offsets where the body ends and just
+/// past the closing tag, or `None` when the block is never closed.
+fn find_block_end(template: &str, from: usize, kind: BlockKind) -> Option<(usize, usize)> {
+    let mut open = vec![kind];
+    let mut i = from;
+    while let Some(start_rel) = template[i..].find("{{") {
+        let start = i + start_rel;
+        let Some(close_rel) = find_close(&template[start + 2..]) else {
+            i = start + 2;
+            continue;
+        };
+        let key = &template[start + 2..start + 2 + close_rel];
+        let after = start + 2 + close_rel + 2;
+        if let Some((nested, _)) = BlockKind::parse_open(key) {
+            open.push(nested);
+        } else if let Some(closed) = BlockKind::parse_close(key) {
+            if open.pop() != Some(closed) {
+                return None;
+            }
+            if open.is_empty() {
+                return Some((start, after));
+            }
+        }
+        i = after;
+    }
+    None
+}
+
+/// Whether a block condition holds: missing values, `null`, `false`, `0`,
+/// `""` and `[]` are false, as in Handlebars.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(_) => true,
+    }
 }
 
 /// Find the byte offset (relative to `s`) of the closing `}}` of the placeholder
@@ -174,7 +392,8 @@ pub fn expand_prompt_template(template: &str, context: &RequestContext) -> Strin
 /// Returns `None` if a new `{{` is encountered before any `}}` (so the leading
 /// `{{` is a false start and should be emitted literally) or if there is no
 /// closing `}}` at all. This keeps placeholder contents free of nested `{{`,
-/// matching how a well-formed `{{ key }}` token looks.
+/// matching how a well-formed `{{ key }}` token looks. A quoted default after
+/// `||` is skipped whole, so it may contain braces (and escaped quotes) of its own.
 fn find_close(s: &str) -> Option<usize> {
     let bytes = s.as_bytes();
     let mut j = 0;
@@ -186,11 +405,175 @@ fn find_close(s: &str) -> Option<usize> {
             // A fresh placeholder opened before this one closed -- abandon this span.
             return None;
         }
+        if bytes[j] == b'|' && bytes[j + 1] == b'|' {
+            j += 2;
+            while bytes.get(j) == Some(&b' ') {
+                j += 1;
+            }
+            if let Some(&quote @ (b'"' | b'\'')) = bytes.get(j) {
+                // An unterminated quote means there is no well-formed close
+                j += closing_quote(&s[j + 1..], quote)? + 2;
+            }
+            continue;
+        }
         j += 1;
     }
     None
 }
 
+/// Byte offset of the quote closing a quoted default, where `s` starts just
+/// after the opening one. A backslash escapes the character after it.
+fn closing_quote(s: &str, quote: u8) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut j = 0;
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' => j += 2,
+            byte if byte == quote => return Some(j),
+            _ => j += 1,
+        }
+    }
+    None
+}
+
+/// Drop the backslashes from a quoted default's escapes (`\"`, `\'`, `\\`).
+fn unescape_quoted(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => out.extend(chars.next()),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// One alternative of a `{{ key || other.key || "default" }}` placeholder
+enum Alternative<'a> {
+    /// A key resolved against the request context
+    Key(&'a str),
+    /// A quoted default, still holding its backslash escapes
+    Literal(&'a str),
+}
+
+/// Split placeholder contents on `||` into its alternatives. Quoted defaults
+/// may contain `||`, braces, spaces, punctuation and escaped quotes. Returns
+/// `None` when the contents are malformed (unterminated quote, text after a
+/// quote).
+fn split_alternatives(content: &str) -> Option<Vec<Alternative<'_>>> {
+    let mut alternatives = Vec::new();
+    let mut rest = content.trim_start();
+    loop {
+        let (alternative, remainder) = match rest.as_bytes().first() {
+            Some(&quote @ (b'"' | b'\'')) => {
+                let end = closing_quote(&rest[1..], quote)? + 1;
+                (Alternative::Literal(&rest[1..end]), rest[end + 1..].trim_start())
+            }
+            _ => {
+                let end = rest.find("||").unwrap_or(rest.len());
+                (Alternative::Key(rest[..end].trim()), &rest[end..])
+            }
+        };
+        alternatives.push(alternative);
+        match remainder.strip_prefix("||") {
+            Some(next) => rest = next.trim_start(),
+            None if remainder.is_empty() => return Some(alternatives),
+            None => return None,
+        }
+    }
+}
+
+/// Resolve the contents of one `{{ ... }}` placeholder: the first alternative
+/// that resolves to a non-empty value wins, so a default applies only when
+/// every key before it is missing or empty. Keys after the first may keep
+/// their `request.` prefix. Filters after a single `|` then transform the
+/// value left to right.
+fn resolve_placeholder(
+    content: &str,
+    context: &RequestContext,
+    scope: Option<&Scope<'_>>,
+) -> Option<String> {
+    let (content, filters) = split_filters(content);
+    let value = if content.contains("||") {
+        let mut empty = None;
+        split_alternatives(content)?
+            .into_iter()
+            .filter_map(|alternative| match alternative {
+                Alternative::Key(key) => {
+                    resolve_key(key.strip_prefix("request.").unwrap_or(key), context, scope)
+                }
+                Alternative::Literal(text) => Some(unescape_quoted(text)),
+            })
+            .find(|value| {
+                // Remember an empty value in case nothing better follows
+                if value.is_empty() {
+                    empty = Some(String::new());
+                }
+                !value.is_empty()
+            })
+            .or(empty)?
+    } else {
+        resolve_key(content.trim(), context, scope)?
+    };
+    Some(
+        filters
+            .into_iter()
+            .flat_map(|chain| chain.split('|'))
+            .fold(value, |value, filter| apply_filter(filter.trim(), value)),
+    )
+}
+
+/// Split `key | filter | filter` at the first single pipe, leaving `||`
+/// alternatives and quoted defaults alone. Returns the part before it and
+/// the `|`-separated filter chain after it, if any.
+fn split_filters(content: &str) -> (&str, Option<&str>) {
+    let bytes = content.as_bytes();
+    let mut quote = None;
+    let mut j = 0;
+    while j < bytes.len() {
+        match (quote, bytes[j]) {
+            (Some(_), b'\\') => j += 1,
+            (Some(open), byte) if byte == open => quote = None,
+            (Some(_), _) => {}
+            (None, byte @ (b'"' | b'\'')) => quote = Some(byte),
+            (None, b'|') if bytes.get(j + 1) == Some(&b'|') => j += 1,
+            (None, b'|') => return (&content[..j], Some(&content[j + 1..])),
+            (None, _) => {}
+        }
+        j += 1;
+    }
+    (content, None)
+}
+
+/// Apply one filter to a resolved value. Unknown filters leave it as is.
+fn apply_filter(filter: &str, value: String) -> String {
+    match filter {
+        "urlencode" => url_encode(&value),
+        "jsonescape" => {
+            // Escape as a JSON string, minus the surrounding quotes
+            let quoted = Value::String(value).to_string();
+            quoted[1..quoted.len() - 1].to_string()
+        }
+        "upper" => value.to_uppercase(),
+        "lower" => value.to_lowercase(),
+        _ => value,
+    }
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters.
+fn url_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(char::from(byte));
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
 /// Length in bytes of the UTF-8 character that starts with `first_byte`.
 fn utf8_char_len(first_byte: u8) -> usize {
     if first_byte < 0x80 {
@@ -211,27 +594,47 @@ fn utf8_char_len(first_byte: u8) -> usize {
 /// caller preserves the original placeholder text). This single resolution point
 /// is what guarantees the expansion is non-recursive: a value returned here is
 /// never fed back into the tokenizer.
-fn resolve_key(key: &str, context: &RequestContext) -> Option<String> {
+fn resolve_key(key: &str, context: &RequestContext, scope: Option<&Scope<'_>>) -> Option<String> {
     match key {
         "method" => Some(context.method.clone()),
         "path" => Some(context.path.clone()),
-        _ => {
-            let (prefix, field) = key.split_once('.')?;
-            match prefix {
-                "body" => context.body.as_ref().and_then(|b| lookup_json_field(b, field)),
-                "path" => context.path_params.get(field).map(json_value_to_string),
-                "query" => context.query_params.get(field).map(json_value_to_string),
-                "headers" => context.headers.get(field).map(json_value_to_string),
-                "multipart" => context.multipart_fields.get(field).map(json_value_to_string),
-                _ => None,
-            }
-        }
+        "raw_query" => Some(context.raw_query.clone()),
+        _ => lookup_value(key, context, scope).map(|value| json_value_to_string(&value)),
     }
 }
 
-/// Look up a top-level field on a JSON body object and render it as a string.
-fn lookup_json_field(value: &Value, field: &str) -> Option<String> {
-    value.as_object().and_then(|obj| obj.get(field)).map(json_value_to_string)
+/// Look up a `prefix.field` key (or `this` / `@index` inside an `{{#each}}`)
+/// as a JSON value, borrowing it from the context where possible.
+fn lookup_value<'a>(
+    key: &str,
+    context: &'a RequestContext,
+    scope: Option<&Scope<'a>>,
+) -> Option<Cow<'a, Value>> {
+    match key {
+        "this" => return scope.map(|scope| Cow::Borrowed(scope.item)),
+        "@index" => return scope.map(|scope| Cow::Owned(Value::from(scope.index))),
+        _ => {}
+    }
+    let (prefix, field) = key.split_once('.')?;
+    let value = match prefix {
+        "this" => scope.and_then(|scope| lookup_json_path(scope.item, field)),
+        "body" => context.body.as_ref().and_then(|b| lookup_json_path(b, field)),
+        "path" => context.path_params.get(field),
+        "query" => context.query_params.get(field),
+        "headers" => context.headers.get(field),
+        "multipart" => context.multipart_fields.get(field),
+        _ => None,
+    };
+    value.map(Cow::Borrowed)
+}
+
+/// Look up a dotted field path (`user.name`, `items.0.id`) on a JSON value.
+fn lookup_json_path<'a>(value: &'a Value, field: &str) -> Option<&'a Value> {
+    field.split('.').try_fold(value, |current, segment| match current {
+        Value::Object(obj) => obj.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    })
 }
 
 /// Render a JSON value as the string used for template substitution.
@@ -276,16 +679,8 @@ fn json_value_to_string(val: &Value) -> String {
 pub fn expand_templates_in_json(value: Value, context: &RequestContext) -> Value {
     match value {
         Value::String(s) => {
-            // Normalize {{request.query.name}} to {{query.name}} format for compatibility
-            let normalized = s
-                .replace("{{request.query.", "{{query.")
-                .replace("{{request.path.", "{{path.")
-                .replace("{{request.headers.", "{{headers.")
-                .replace("{{request.body.", "{{body.")
-                .replace("{{request.method}}", "{{method}}")
-                .replace("{{request.path}}", "{{path}}");
             // Use expand_prompt_template which is Send-safe and doesn't use rng()
-            Value::String(expand_prompt_template(&normalized, context))
+            Value::String(expand_prompt_template(&normalize_request_prefix(&s), context))
         }
         Value::Array(arr) => {
             Value::Array(arr.into_iter().map(|v| expand_templates_in_json(v, context)).collect())
@@ -299,6 +694,57 @@ pub fn expand_templates_in_json(value: Value, context: &RequestContext) -> Value
     }
 }
 
+/// Expand template variables in an XML document using request context
+///
+/// Accepts the same `{{request.*}}` tokens as [`expand_templates_in_json`].
+/// The document's own markup is left untouched; only substituted values are
+/// escaped (`&`, `<`, `>`, `"`, `'`), so a request value can never inject
+/// elements or break out of an attribute and the output stays well-formed.
+///
+/// # Example
+/// ```
+/// use mockforge_template_expansion::{expand_templates_in_xml, RequestContext};
+/// use serde_json::json;
+/// use std::collections::HashMap;
+///
+/// let mut query_params = HashMap::new();
+/// query_params.insert("name".to_string(), json!("Tom & <Jerry>"));
+/// let context = RequestContext::new("GET".to_string(), "/users".to_string())
+///     .with_query_params(query_params);
+///
+/// let expanded = expand_templates_in_xml("<name>{{request.query.name}}</name>", &context);
+/// assert_eq!(expanded, "<name>Tom &amp; &lt;Jerry&gt;</name>");
+/// ```
+#[must_use]
+pub fn expand_templates_in_xml(xml: &str, context: &RequestContext) -> String {
+    expand_tokens(&normalize_request_prefix(xml), context, push_xml_escaped)
+}
+
+/// Normalize `{{request.query.name}}` to `{{query.name}}` format for compatibility
+fn normalize_request_prefix(s: &str) -> String {
+    s.replace("{{request.query.", "{{query.")
+        .replace("{{request.path.", "{{path.")
+        .replace("{{request.headers.", "{{headers.")
+        .replace("{{request.body.", "{{body.")
+        .replace("{{request.method}}", "{{method}}")
+        .replace("{{request.path}}", "{{path}}")
+        .replace("{{request.raw_query}}", "{{raw_query}}")
+}
+
+/// Append `value` to `out` with XML special characters escaped
+fn push_xml_escaped(value: &str, out: &mut String) {
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -597,6 +1043,15 @@ mod tests {
         assert_eq!(expanded, "Search for term with limit 10");
     }
 
+    #[test]
+    fn test_expand_templates_raw_query() {
+        let context = RequestContext::new("GET".to_string(), "/items".to_string())
+            .with_raw_query("filter[name]=x&sort=-id");
+
+        let expanded = expand_templates_in_json(json!("q={{request.raw_query}}"), &context);
+        assert_eq!(expanded, json!("q=filter[name]=x&sort=-id"));
+    }
+
     #[test]
     fn test_expand_prompt_template_query_params_boolean() {
         let mut query_params = HashMap::new();
@@ -1027,6 +1482,181 @@ mod tests {
         assert_eq!(expanded["msg"], "value");
     }
 
+    #[test]
+    fn test_default_values_for_multiple_placeholders() {
+        let mut query_params = HashMap::new();
+        query_params.insert("name".to_string(), json!("Al"));
+        let context = RequestContext::new("GET".to_string(), "/greet".to_string())
+            .with_query_params(query_params);
+
+        let value = json!(
+            r#"Hi {{request.query.name || "guest"}}, role: {{request.query.role || "a visitor, welcome!"}}"#
+        );
+        let expanded = expand_templates_in_json(value, &context);
+        assert_eq!(expanded, "Hi Al, role: a visitor, welcome!");
+    }
+
+    #[test]
+    fn test_default_values_with_braces_and_chained_keys() {
+        let mut query_params = HashMap::new();
+        query_params.insert("fallback".to_string(), json!("from query"));
+        let context = RequestContext::new("GET".to_string(), "/t".to_string())
+            .with_query_params(query_params);
+
+        let template = r#"{{query.missing || '{"a": 1} || x'}} {{query.missing || request.query.fallback || "unused"}}"#;
+        assert_eq!(expand_prompt_template(template, &context), r#"{"a": 1} || x from query"#);
+
+        // Without a default, or with a malformed one, the placeholder is kept
+        let template = r#"{{query.missing || query.other}} {{query.missing || "oops"x}}"#;
+        assert_eq!(expand_prompt_template(template, &context), template);
+        let template = r#"{{query.missing || "unterminated}}"#;
+        assert_eq!(expand_prompt_template(template, &context), template);
+    }
+
+    #[test]
+    fn test_if_block_renders_content_when_truthy() {
+        let mut query_params = HashMap::new();
+        query_params.insert("flag".to_string(), json!("yes"));
+        query_params.insert("off".to_string(), json!(false));
+        let context = RequestContext::new("GET".to_string(), "/api".to_string())
+            .with_query_params(query_params);
+
+        let template = "a{{#if query.flag}}[{{query.flag}}]{{/if}}b{{#if query.off}}x{{/if}}{{#if query.missing}}y{{/if}}c";
+        assert_eq!(expand_prompt_template(template, &context), "a[yes]bc");
+    }
+
+    #[test]
+    fn test_each_block_over_empty_array_renders_nothing() {
+        let context = RequestContext::new("POST".to_string(), "/api".to_string())
+            .with_body(json!({"items": []}));
+
+        let template = "[{{#each body.items}}{{this.name}},{{/each}}]";
+        assert_eq!(expand_prompt_template(template, &context), "[]");
+    }
+
+    #[test]
+    fn test_each_block_binds_this_and_nests() {
+        let body = json!({"items": [
+            {"name": "a", "tags": ["x", "y"], "active": true},
+            {"name": "b", "tags": [], "active": false}
+        ]});
+        let context = RequestContext::new("POST".to_string(), "/api".to_string()).with_body(body);
+
+        let template = "{{#each request.body.items}}{{@index}}:{{this.name}}({{#each this.tags}}{{this}}{{/each}}){{#if this.active}}*{{/if}} {{/each}}";
+        assert_eq!(expand_prompt_template(template, &context), "0:a(xy)* 1:b() ");
+    }
+
+    #[test]
+    fn test_unterminated_block_is_kept_literally() {
+        let context = RequestContext::new("GET".to_string(), "/api".to_string());
+        let template = "{{#if method}}open {{/each}}";
+        assert_eq!(expand_prompt_template(template, &context), template);
+    }
+
+    #[test]
+    fn test_default_values() {
+        let mut query_params = HashMap::new();
+        query_params.insert("name".to_string(), json!("Ada"));
+        query_params.insert("blank".to_string(), json!(""));
+        let context = RequestContext::new("GET".to_string(), "/api".to_string())
+            .with_query_params(query_params);
+
+        // A present value wins over the default
+        assert_eq!(expand_prompt_template(r#"Hi {{query.name || "world"}}"#, &context), "Hi Ada");
+        // Missing and empty values both fall back to it
+        assert_eq!(
+            expand_prompt_template(r#"Hi {{query.missing || "world"}}"#, &context),
+            "Hi world"
+        );
+        assert_eq!(expand_prompt_template("Hi {{query.blank || 'world'}}", &context), "Hi world");
+        // An empty value is still used when nothing else resolves
+        assert_eq!(expand_prompt_template("[{{query.blank || query.missing}}]", &context), "[]");
+    }
+
+    #[test]
+    fn test_default_value_with_escaped_quotes() {
+        let context = RequestContext::new("GET".to_string(), "/api".to_string());
+
+        let template = r#"{{query.missing || "say \"hi\" \\o/"}} {{query.missing || 'it\'s'}}"#;
+        assert_eq!(expand_prompt_template(template, &context), r#"say "hi" \o/ it's"#);
+    }
+
+    #[test]
+    fn test_unresolved_variables() {
+        let mut query_params = HashMap::new();
+        query_params.insert("page".to_string(), json!("2"));
+        let body = json!({"user": {"name": "Ada"}, "tags": [{"id": 1}, {"id": 2}]});
+        let context = RequestContext::new("POST".to_string(), "/api".to_string())
+            .with_query_params(query_params)
+            .with_body(body);
+
+        let template = "{{method}} {{query.page}} {{query.absent}} {{body.user.name}} \
+            {{body.missing | upper}} {{query.absent}} {{query.gone || \"x\"}} \
+            {{#each body.tags}}{{this.id}}{{this.label}}{{/each}} \
+            {{#if body.nope}}{{headers.skipped}}{{/if}}";
+        assert_eq!(
+            unresolved_variables(template, &context),
+            vec!["query.absent", "body.missing", "this.label"]
+        );
+
+        assert!(unresolved_variables("{{path}} {{body.user.name}}", &context).is_empty());
+    }
+
+    #[test]
+    fn test_urlencode_filter() {
+        let mut query_params = HashMap::new();
+        query_params.insert("search".to_string(), json!("tom & jerry/100%"));
+        let context = RequestContext::new("GET".to_string(), "/api".to_string())
+            .with_query_params(query_params);
+
+        let template = "/search?q={{query.search | urlencode}}";
+        assert_eq!(
+            expand_prompt_template(template, &context),
+            "/search?q=tom%20%26%20jerry%2F100%25"
+        );
+    }
+
+    #[test]
+    fn test_jsonescape_filter() {
+        let body = json!({"note": "say \"hi\"\n\\o/"});
+        let context = RequestContext::new("POST".to_string(), "/api".to_string()).with_body(body);
+
+        let template = r#"{"note": "{{body.note | jsonescape}}"}"#;
+        let expanded = expand_prompt_template(template, &context);
+        assert_eq!(expanded, r#"{"note": "say \"hi\"\n\\o/"}"#);
+        let parsed: Value = serde_json::from_str(&expanded).unwrap();
+        assert_eq!(parsed["note"], "say \"hi\"\n\\o/");
+    }
+
+    #[test]
+    fn test_filters_chain_and_combine_with_defaults() {
+        let context = RequestContext::new("get".to_string(), "/Api/Items".to_string());
+
+        assert_eq!(
+            expand_prompt_template("{{path | upper}} {{method|upper|lower}}", &context),
+            "/API/ITEMS get"
+        );
+        // The default is filtered too; `|` inside a quoted default is literal
+        assert_eq!(
+            expand_prompt_template(r#"{{query.q || "a|b c" | urlencode}}"#, &context),
+            "a%7Cb%20c"
+        );
+        // Unknown filters fall back to the raw value
+        assert_eq!(expand_prompt_template("{{path | reverse}}", &context), "/Api/Items");
+        // Unresolved placeholders are kept whole
+        assert_eq!(expand_prompt_template("{{query.q | upper}}", &context), "{{query.q | upper}}");
+    }
+
+    #[test]
+    fn test_nested_body_field_paths() {
+        let body = json!({"user": {"name": "Al", "roles": ["admin", "ops"]}});
+        let context = RequestContext::new("POST".to_string(), "/api".to_string()).with_body(body);
+
+        let template = "{{body.user.name}} {{body.user.roles.1}} {{body.user.email}}";
+        let result = expand_prompt_template(template, &context);
+        assert_eq!(result, "Al ops {{body.user.email}}");
+    }
+
     #[test]
     fn test_key_with_special_chars_in_placeholder() {
         // Test that keys with special characters work correctly
@@ -1326,7 +1956,6 @@ mod tests {
 
     #[test]
     fn test_expand_body_with_deeply_nested_json() {
-        // expand_json_variables only handles top-level object keys
         let body = json!({
             "level1": {
                 "level2": {
@@ -1336,15 +1965,15 @@ mod tests {
         });
         let context = RequestContext::new("POST".to_string(), "/api".to_string()).with_body(body);
 
-        // Only {{body.level1}} works (top-level); {{body.level1.level2}} does NOT
+        // An intermediate object renders as JSON
         let template = "Nested: {{body.level1}}";
         let expanded = expand_prompt_template(template, &context);
         assert_eq!(expanded, r#"Nested: {"level2":{"value":"deep"}}"#);
 
-        // Dot-separated deep access is not supported
+        // Dot-separated paths reach leaves at any depth
         let template2 = "Deep: {{body.level1.level2.value}}";
         let expanded2 = expand_prompt_template(template2, &context);
-        assert_eq!(expanded2, "Deep: {{body.level1.level2.value}}");
+        assert_eq!(expanded2, "Deep: deep");
     }
 
     #[test]
@@ -1367,4 +1996,33 @@ mod tests {
         let expanded = expand_prompt_template(template, &context);
         assert_eq!(expanded, "Message: line1\nline2\nline3");
     }
+
+    // ==================== expand_templates_in_xml Tests ====================
+
+    #[test]
+    fn test_expand_xml_escapes_substituted_values() {
+        let mut query = HashMap::new();
+        query.insert("name".to_string(), json!("<b>\"A&B\"</b>"));
+        let context =
+            RequestContext::new("GET".to_string(), "/users".to_string()).with_query_params(query);
+
+        let xml = r#"<user id="{{request.query.name}}"><name>{{request.query.name}}</name></user>"#;
+        let expanded = expand_templates_in_xml(xml, &context);
+        assert_eq!(
+            expanded,
+            "<user id=\"&lt;b&gt;&quot;A&amp;B&quot;&lt;/b&gt;\">\
+             <name>&lt;b&gt;&quot;A&amp;B&quot;&lt;/b&gt;</name></user>"
+        );
+    }
+
+    #[test]
+    fn test_expand_xml_leaves_markup_and_unknown_tokens() {
+        let context = RequestContext::new("POST".to_string(), "/orders".to_string());
+        let xml = "<?xml version=\"1.0\"?><r m=\"{{request.method}}\">{{request.query.missing}} &amp; {{path}}</r>";
+        let expanded = expand_templates_in_xml(xml, &context);
+        assert_eq!(
+            expanded,
+            "<?xml version=\"1.0\"?><r m=\"POST\">{{query.missing}} &amp; /orders</r>"
+        );
+    }
 }
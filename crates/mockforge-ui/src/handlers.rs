@@ -20,11 +20,11 @@ use axum::{
     http::{self, StatusCode},
     response::{
         sse::{Event, Sse},
-        Html, IntoResponse, Json,
+        Html, IntoResponse, Json, Response,
     },
 };
 use chrono::Utc;
-use futures_util::stream::{self, Stream};
+use futures_util::stream;
 use mockforge_core::{Error, Result};
 use mockforge_plugin_loader::PluginRegistry;
 use serde::{Deserialize, Serialize};
@@ -1508,7 +1508,17 @@ const RECENT_LOGS_TTL_MINUTES: i64 = 5;
 /// SSE endpoint for real-time log streaming
 pub async fn logs_sse(
     State(_state): State<AdminState>,
-) -> Sse<impl Stream<Item = std::result::Result<Event, Infallible>>> {
+    extensions: axum::http::Extensions,
+) -> Response {
+    use mockforge_http::connection_tracker::{self, global_connections, ConnectionKind};
+
+    let Some(connection) = global_connections().try_register(
+        ConnectionKind::Sse,
+        connection_tracker::remote_addr(&extensions),
+        "logs",
+    ) else {
+        return connection_tracker::limit_reached_response(ConnectionKind::Sse);
+    };
     tracing::info!("SSE endpoint /logs/sse accessed - starting real-time log streaming for recent requests only");
 
     let stream = stream::unfold(std::collections::HashSet::new(), |mut seen_ids| async move {
@@ -1560,22 +1570,26 @@ pub async fn logs_sse(
                 tracing::info!("SSE: Sending {} new logs to client", new_logs.len());
 
                 let event_data = serde_json::to_string(&new_logs).unwrap_or_default();
-                let event = Ok(Event::default().event("new_logs").data(event_data));
+                let event =
+                    Ok::<_, Infallible>(Event::default().event("new_logs").data(event_data));
 
                 return Some((event, seen_ids));
             }
         }
 
         // Send keep-alive
-        let event = Ok(Event::default().event("keep_alive").data(""));
+        let event = Ok::<_, Infallible>(Event::default().event("keep_alive").data(""));
         Some((event, seen_ids))
     });
+    let stream = connection_tracker::track_stream(connection, stream);
 
-    Sse::new(stream).keep_alive(
-        axum::response::sse::KeepAlive::new()
-            .interval(Duration::from_secs(15))
-            .text("keep-alive-text"),
-    )
+    Sse::new(stream)
+        .keep_alive(
+            axum::response::sse::KeepAlive::new()
+                .interval(Duration::from_secs(15))
+                .text("keep-alive-text"),
+        )
+        .into_response()
 }
 
 /// Get metrics data
@@ -4701,6 +4715,7 @@ pub async fn set_reality_level(
                 connection_errors: false,
                 connection_error_probability: 0.0,
                 connection_error_kind: mockforge_chaos::config::ConnectionErrorKind::Http503,
+                retry_after_secs: None,
                 timeout_errors: config.chaos.inject_timeouts,
                 timeout_ms: config.chaos.timeout_ms,
                 timeout_probability: if config.chaos.inject_timeouts {
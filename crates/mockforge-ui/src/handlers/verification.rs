@@ -8,8 +8,9 @@ use axum::response::Json;
 use mockforge_core::{
     request_logger::get_global_logger,
     verification::{
-        verify_at_least, verify_never, verify_requests, verify_sequence, VerificationCount,
-        VerificationRequest, VerificationResult,
+        verify_at_least, verify_batch, verify_never, verify_requests, verify_sequence,
+        BatchVerificationItem, BatchVerificationResult, VerificationCount, VerificationRequest,
+        VerificationResult,
     },
 };
 use serde::{Deserialize, Serialize};
@@ -56,6 +57,16 @@ pub struct AtLeastRequest {
     pub min: usize,
 }
 
+/// Request body for batch verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchVerifyRequest {
+    /// Expectations to check, answered in the same order
+    pub expectations: Vec<BatchVerificationItem>,
+    /// Stop at the first failing expectation
+    #[serde(default)]
+    pub short_circuit: bool,
+}
+
 /// Verify requests against a pattern and count assertion
 pub async fn verify(
     State(_state): State<AdminState>,
@@ -178,6 +189,26 @@ pub async fn verify_at_least_handler(
     }
 }
 
+/// Verify several expectations in one call
+///
+/// Failed expectations do not fail the call: the response carries one result
+/// per expectation and an overall `matched` flag.
+pub async fn verify_batch_handler(
+    State(_state): State<AdminState>,
+    Json(request): Json<BatchVerifyRequest>,
+) -> Json<ApiResponse<BatchVerificationResult>> {
+    let logger = match get_global_logger() {
+        Some(logger) => logger,
+        None => {
+            return Json(ApiResponse::error("Request logger not initialized".to_string()));
+        }
+    };
+
+    let result = verify_batch(logger, &request.expectations, request.short_circuit).await;
+
+    Json(ApiResponse::success(result))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,6 +270,7 @@ pub fn create_admin_router(
         .route("/__mockforge/verification/sequence", post(verification::verify_sequence_handler))
         .route("/__mockforge/verification/never", post(verification::verify_never_handler))
         .route("/__mockforge/verification/at-least", post(verification::verify_at_least_handler))
+        .route("/__mockforge/verification/batch", post(verification::verify_batch_handler))
         // Reality Slider routes
         .route("/__mockforge/reality/level", get(get_reality_level))
         .route("/__mockforge/reality/level", axum::routing::put(set_reality_level))
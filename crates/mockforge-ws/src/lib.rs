@@ -261,47 +261,71 @@ pub async fn start_with_latency_and_host(
 }
 
 // WebSocket handlers
-async fn ws_handler_no_state(ws: WebSocketUpgrade) -> impl IntoResponse {
-    ws.on_upgrade(handle_socket)
+
+/// Admission permit attached by the embedding server's connection limit, if any
+type Permit = Option<Extension<ConnectionPermit>>;
+
+/// Serve a socket while holding its admission permit, so the connection
+/// counts against the server's limit until it closes
+async fn with_permit(permit: Permit, serve: impl std::future::Future<Output = ()>) {
+    let _permit = permit;
+    serve.await;
+}
+
+async fn ws_handler_no_state(ws: WebSocketUpgrade, permit: Permit) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| with_permit(permit, handle_socket(socket)))
 }
 
 async fn ws_handler_with_state(
     ws: WebSocketUpgrade,
     State(_latency): State<LatencyInjector>,
+    permit: Permit,
 ) -> impl IntoResponse {
-    ws.on_upgrade(handle_socket)
+    ws.on_upgrade(move |socket| with_permit(permit, handle_socket(socket)))
 }
 
 async fn ws_handler_with_proxy(
     ws: WebSocketUpgrade,
     State(proxy): State<WsProxyHandler>,
+    permit: Permit,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket_with_proxy(socket, proxy, "/ws".to_string()))
+    ws.on_upgrade(move |socket| {
+        with_permit(permit, handle_socket_with_proxy(socket, proxy, "/ws".to_string()))
+    })
 }
 
 async fn ws_handler_with_proxy_path(
     Path(path): Path<String>,
     ws: WebSocketUpgrade,
     State(proxy): State<WsProxyHandler>,
+    permit: Permit,
 ) -> impl IntoResponse {
     let full_path = format!("/ws/{}", path);
-    ws.on_upgrade(move |socket| handle_socket_with_proxy(socket, proxy, full_path))
+    ws.on_upgrade(move |socket| {
+        with_permit(permit, handle_socket_with_proxy(socket, proxy, full_path))
+    })
 }
 
 async fn ws_handler_with_registry(
     ws: WebSocketUpgrade,
     State(registry): State<std::sync::Arc<HandlerRegistry>>,
+    permit: Permit,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_socket_with_handlers(socket, registry, "/ws".to_string()))
+    ws.on_upgrade(move |socket| {
+        with_permit(permit, handle_socket_with_handlers(socket, registry, "/ws".to_string()))
+    })
 }
 
 async fn ws_handler_with_registry_path(
     Path(path): Path<String>,
     ws: WebSocketUpgrade,
     State(registry): State<std::sync::Arc<HandlerRegistry>>,
+    permit: Permit,
 ) -> impl IntoResponse {
     let full_path = format!("/ws/{}", path);
-    ws.on_upgrade(move |socket| handle_socket_with_handlers(socket, registry, full_path))
+    ws.on_upgrade(move |socket| {
+        with_permit(permit, handle_socket_with_handlers(socket, registry, full_path))
+    })
 }
 
 async fn handle_socket(mut socket: WebSocket) {
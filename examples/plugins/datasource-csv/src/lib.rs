@@ -9,12 +9,54 @@
 //! - Data querying with filtering and pagination
 //! - Multiple CSV datasets support
 //! - Caching for performance
-//! - Type inference for CSV columns
+//! - Lazy datasets that stream large files instead of loading every row
+//! - Type inference for CSV columns, including dates and timestamps
 
 use mockforge_plugin_core::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BinaryHeap, HashMap};
 use std::path::Path;
+#[cfg(test)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use thiserror::Error;
+
+/// Errors raised by the CSV data source plugin
+#[derive(Debug, Error)]
+pub enum CsvPluginError {
+    /// The configured CSV file does not exist
+    #[error("CSV file not found: {0}")]
+    FileNotFound(String),
+    /// The CSV file could not be read or parsed
+    #[error("failed to parse CSV file {path}: {message}")]
+    ParseError {
+        /// Path of the offending file
+        path: String,
+        /// Underlying parser error
+        message: String,
+    },
+    /// The plugin or dataset configuration is invalid
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+    /// No dataset with the requested name is loaded
+    #[error("dataset '{0}' not found")]
+    DatasetNotFound(String),
+}
+
+impl CsvPluginError {
+    fn from_csv(path: &str, err: csv::Error) -> Self {
+        match err.kind() {
+            csv::ErrorKind::Io(io) if io.kind() == std::io::ErrorKind::NotFound => {
+                Self::FileNotFound(path.to_string())
+            }
+            _ => Self::ParseError {
+                path: path.to_string(),
+                message: err.to_string(),
+            },
+        }
+    }
+}
 
 /// CSV file configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +67,30 @@ pub struct CsvFileConfig {
     pub path: String,
     /// Whether the CSV file has headers
     pub has_headers: bool,
+    /// Field delimiter, e.g. `;` or a tab (defaults to `,`)
+    #[serde(default)]
+    pub delimiter: Option<char>,
+    /// Quote character (defaults to `"`)
+    #[serde(default)]
+    pub quote: Option<char>,
+    /// Keep only the headers and column types in memory and stream rows from
+    /// the file on each query (for files too large to load whole). A query
+    /// holds at most one page of rows; a sorted one rereads the file once per
+    /// page it skips.
+    #[serde(default)]
+    pub lazy: bool,
+}
+
+/// Convert a configured CSV control character to the single byte the reader expects
+fn csv_byte(dataset: &str, setting: &str, c: Option<char>, default: u8) -> Result<u8, CsvPluginError> {
+    match c {
+        None => Ok(default),
+        Some(c) if c.is_ascii() => Ok(c as u8),
+        Some(c) => Err(CsvPluginError::InvalidConfig(format!(
+            "dataset '{}' {} '{}' must be a single ASCII character",
+            dataset, setting, c
+        ))),
+    }
 }
 
 /// Plugin configuration
@@ -36,6 +102,10 @@ pub struct CsvDataSourceConfig {
     pub cache_enabled: bool,
     /// Maximum rows to return per query
     pub max_rows_per_query: usize,
+    /// Let [`CsvDataSourcePlugin::reload_if_changed`] reload datasets whose
+    /// file changed on disk
+    #[serde(default)]
+    pub watch_enabled: bool,
 }
 
 impl Default for CsvDataSourceConfig {
@@ -44,6 +114,7 @@ impl Default for CsvDataSourceConfig {
             csv_files: vec![],
             cache_enabled: true,
             max_rows_per_query: 1000,
+            watch_enabled: false,
         }
     }
 }
@@ -55,10 +126,14 @@ pub struct CsvDataset {
     pub name: String,
     /// Column headers (if available)
     pub headers: Vec<String>,
-    /// Data rows
+    /// Data rows (empty for lazy datasets)
     pub rows: Vec<HashMap<String, String>>,
+    /// Number of data rows in the file
+    pub row_count: usize,
     /// Column types (inferred)
     pub column_types: HashMap<String, ColumnType>,
+    /// For lazy datasets, the file rows are streamed from on each query
+    pub lazy_source: Option<CsvFileConfig>,
 }
 
 /// Inferred column type
@@ -68,13 +143,154 @@ pub enum ColumnType {
     Integer,
     Float,
     Boolean,
+    /// Calendar date (`YYYY-MM-DD`)
+    Date,
+    /// Timestamp (RFC 3339 or `YYYY-MM-DD HH:MM:SS`)
+    DateTime,
+}
+
+/// Parse a `YYYY-MM-DD` date
+fn parse_date(value: &str) -> Option<chrono::NaiveDate> {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Parse an RFC 3339 timestamp (normalized to UTC) or `YYYY-MM-DD HH:MM:SS`
+fn parse_datetime(value: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.naive_utc())
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S"))
+        .ok()
+}
+
+/// Compare two values by their parsed form; values that do not parse sort
+/// after every value that does
+fn cmp_parsed<T>(
+    a: &str,
+    b: &str,
+    parse: impl Fn(&str) -> Option<T>,
+    cmp: impl Fn(&T, &T) -> std::cmp::Ordering,
+) -> std::cmp::Ordering {
+    match (parse(a.trim()), parse(b.trim())) {
+        (Some(a), Some(b)) => cmp(&a, &b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+/// Compare two values of a column by its inferred type: numerically for
+/// Integer/Float, chronologically for Date/DateTime, lexicographically
+/// otherwise
+fn cmp_typed(column_type: &ColumnType, a: &str, b: &str) -> std::cmp::Ordering {
+    match column_type {
+        ColumnType::Integer | ColumnType::Float => {
+            cmp_parsed(a, b, |v| v.parse::<f64>().ok(), f64::total_cmp)
+        }
+        ColumnType::Date => cmp_parsed(a, b, parse_date, Ord::cmp),
+        ColumnType::DateTime => cmp_parsed(a, b, parse_datetime, Ord::cmp),
+        ColumnType::String | ColumnType::Boolean => a.cmp(b),
+    }
+}
+
+/// Open a reader over the CSV file of `csv_config`
+fn open_reader(csv_config: &CsvFileConfig) -> Result<csv::Reader<std::fs::File>, CsvPluginError> {
+    let delimiter = csv_byte(&csv_config.name, "delimiter", csv_config.delimiter, b',')?;
+    let quote = csv_byte(&csv_config.name, "quote", csv_config.quote, b'"')?;
+    // Quoted fields may span lines; the reader keeps the embedded newlines in
+    // the field value.
+    csv::ReaderBuilder::new()
+        .has_headers(csv_config.has_headers)
+        .delimiter(delimiter)
+        .quote(quote)
+        .from_path(Path::new(&csv_config.path))
+        .map_err(|e| CsvPluginError::from_csv(&csv_config.path, e))
+}
+
+/// Map a record onto `headers`, skipping fields beyond the last header
+fn row_from_record(headers: &[String], record: &csv::StringRecord) -> HashMap<String, String> {
+    headers.iter().cloned().zip(record.iter().map(str::to_string)).collect()
+}
+
+/// Stream the rows of a lazy dataset's file, one record at a time
+fn stream_rows<'a>(
+    source: &'a CsvFileConfig,
+    headers: &'a [String],
+) -> Result<
+    impl Iterator<Item = Result<HashMap<String, String>, CsvPluginError>> + 'a,
+    CsvPluginError,
+> {
+    let reader = open_reader(source)?;
+    Ok(reader.into_records().map(move |result| {
+        result
+            .map(|record| row_from_record(headers, &record))
+            .map_err(|e| CsvPluginError::from_csv(&source.path, e))
+    }))
+}
+
+/// A row paired with its position in the file
+type PositionedRow = (usize, HashMap<String, String>);
+
+/// A row of a sorted lazy query, ordered by the query's order and then by
+/// file position so ties keep file order
+struct Ranked<'a, F> {
+    index: usize,
+    row: HashMap<String, String>,
+    order: &'a F,
+}
+
+impl<F> Ord for Ranked<'_, F>
+where
+    F: Fn(&HashMap<String, String>, &HashMap<String, String>) -> std::cmp::Ordering,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.order)(&self.row, &other.row).then(self.index.cmp(&other.index))
+    }
+}
+
+impl<F> PartialOrd for Ranked<'_, F>
+where
+    F: Fn(&HashMap<String, String>, &HashMap<String, String>) -> std::cmp::Ordering,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<F> PartialEq for Ranked<'_, F>
+where
+    F: Fn(&HashMap<String, String>, &HashMap<String, String>) -> std::cmp::Ordering,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl<F> Eq for Ranked<'_, F> where
+    F: Fn(&HashMap<String, String>, &HashMap<String, String>) -> std::cmp::Ordering
+{
 }
 
 /// CSV Data Source Plugin
 pub struct CsvDataSourcePlugin {
     config: CsvDataSourceConfig,
     datasets: HashMap<String, CsvDataset>,
-    cache: HashMap<String, Vec<HashMap<String, String>>>,
+    /// Results of [`CsvDataSourcePlugin::query`], keyed by dataset and query
+    cache: Mutex<HashMap<String, Vec<HashMap<String, String>>>>,
+    /// Modification time of each dataset's file when it was last loaded
+    loaded_mtimes: HashMap<String, SystemTime>,
+    /// Most rows any query over a lazy dataset has held in memory at once
+    #[cfg(test)]
+    lazy_peak_rows: AtomicUsize,
+}
+
+/// Prefix shared by every cache key of `dataset`
+fn cache_key_prefix(dataset: &str) -> String {
+    format!("{:?}|", dataset)
+}
+
+/// Modification time of the file at `path`, if it can be read
+fn file_mtime(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
 }
 
 impl CsvDataSourcePlugin {
@@ -83,7 +299,10 @@ impl CsvDataSourcePlugin {
         let mut plugin = Self {
             config,
             datasets: HashMap::new(),
-            cache: HashMap::new(),
+            cache: Mutex::new(HashMap::new()),
+            loaded_mtimes: HashMap::new(),
+            #[cfg(test)]
+            lazy_peak_rows: AtomicUsize::new(0),
         };
 
         // Load all configured CSV files
@@ -97,75 +316,293 @@ impl CsvDataSourcePlugin {
     }
 
     /// Load a CSV dataset
-    fn load_csv_dataset(&mut self, csv_config: &CsvFileConfig) -> Result<(), Box<dyn std::error::Error>> {
-        let path = Path::new(&csv_config.path);
-
-        // Read CSV file
-        let mut reader = csv::ReaderBuilder::new()
-            .has_headers(csv_config.has_headers)
-            .from_path(path)?;
+    fn load_csv_dataset(&mut self, csv_config: &CsvFileConfig) -> Result<(), CsvPluginError> {
+        if csv_config.name.is_empty() {
+            return Err(CsvPluginError::InvalidConfig("dataset name must not be empty".to_string()));
+        }
+        if csv_config.path.is_empty() {
+            return Err(CsvPluginError::InvalidConfig(format!(
+                "dataset '{}' has no CSV path",
+                csv_config.name
+            )));
+        }
+        let csv_err = |e| CsvPluginError::from_csv(&csv_config.path, e);
+        // Taken before reading so an edit made mid-load is picked up next time
+        let mtime = file_mtime(&csv_config.path);
+        let mut reader = open_reader(csv_config)?;
 
         let mut headers = Vec::new();
         let mut rows = Vec::new();
+        let mut row_count = 0;
         let mut column_types = HashMap::new();
 
         // Get headers
         if csv_config.has_headers {
-            headers = reader.headers()?.iter().map(|s| s.to_string()).collect();
-        } else {
-            // Generate column names for headerless CSV
-            if let Some(first_record) = reader.records().next() {
-                let record = first_record?;
-                headers = (0..record.len()).map(|i| format!("col{}", i + 1)).collect();
-
-                // Create a row from the first record
-                let mut row_data = HashMap::new();
-                for (i, field) in record.iter().enumerate() {
-                    let col_name = &headers[i];
-                    row_data.insert(col_name.clone(), field.to_string());
-                    self.infer_column_type(&mut column_types, col_name, field);
-                }
-                rows.push(row_data);
-            }
+            headers = reader.headers().map_err(csv_err)?.iter().map(|s| s.to_string()).collect();
         }
 
-        // Read all records
+        // Read all records, inferring column types as we go. Lazy datasets
+        // keep none of the rows.
         for result in reader.records() {
-            let record = result?;
-            let mut row_data = HashMap::new();
-
-            for (i, field) in record.iter().enumerate() {
-                let col_name = if i < headers.len() {
-                    &headers[i]
-                } else {
-                    continue; // Skip extra fields
-                };
+            let record = result.map_err(csv_err)?;
+            if !csv_config.has_headers && row_count == 0 {
+                // Generate column names for headerless CSV
+                headers = (0..record.len()).map(|i| format!("col{}", i + 1)).collect();
+            }
 
-                row_data.insert(col_name.clone(), field.to_string());
+            let row_data = row_from_record(&headers, &record);
+            for (col_name, field) in &row_data {
                 self.infer_column_type(&mut column_types, col_name, field);
             }
-
-            rows.push(row_data);
+            row_count += 1;
+            if !csv_config.lazy {
+                rows.push(row_data);
+            }
         }
 
         let dataset = CsvDataset {
             name: csv_config.name.clone(),
             headers,
             rows,
+            row_count,
             column_types,
+            lazy_source: csv_config.lazy.then(|| csv_config.clone()),
         };
 
         self.datasets.insert(csv_config.name.clone(), dataset);
+        match mtime {
+            Some(mtime) => self.loaded_mtimes.insert(csv_config.name.clone(), mtime),
+            None => self.loaded_mtimes.remove(&csv_config.name),
+        };
         Ok(())
     }
 
+    /// Reload the datasets whose file was modified since it was last loaded,
+    /// dropping their cached query results. Returns the names of the reloaded
+    /// datasets; nothing is reloaded unless `watch_enabled` is set.
+    ///
+    /// A file that has been deleted keeps its previously loaded dataset. A
+    /// file that no longer parses also keeps the old dataset, and the error is
+    /// returned; the reload is retried on the next call.
+    pub fn reload_if_changed(&mut self) -> Result<Vec<String>, CsvPluginError> {
+        if !self.config.watch_enabled {
+            return Ok(Vec::new());
+        }
+
+        let mut reloaded = Vec::new();
+        for csv_config in self.config.csv_files.clone() {
+            let Some(mtime) = file_mtime(&csv_config.path) else {
+                eprintln!(
+                    "CSV file for dataset {} is missing ({}); keeping the loaded data",
+                    csv_config.name, csv_config.path
+                );
+                continue;
+            };
+            if self.loaded_mtimes.get(&csv_config.name) == Some(&mtime) {
+                continue;
+            }
+
+            self.load_csv_dataset(&csv_config)?;
+            let prefix = cache_key_prefix(&csv_config.name);
+            self.cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .retain(|key, _| !key.starts_with(&prefix));
+            reloaded.push(csv_config.name);
+        }
+        Ok(reloaded)
+    }
+
+    /// Query a dataset: keep rows whose columns equal every `(column, value)`
+    /// in `filter`, sort by `sort_by` (numerically for Integer/Float columns,
+    /// chronologically for Date/DateTime, lexicographically otherwise) and return the page at `offset`. `limit`
+    /// is clamped to `max_rows_per_query`.
+    ///
+    /// Lazy datasets are streamed from their file: an unsorted query holds at
+    /// most `limit` rows, a sorted one at most `offset + limit`.
+    pub fn query(
+        &self,
+        dataset: &str,
+        filter: &[(String, String)],
+        sort_by: Option<&str>,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<HashMap<String, String>>, CsvPluginError> {
+        let data = self
+            .datasets
+            .get(dataset)
+            .ok_or_else(|| CsvPluginError::DatasetNotFound(dataset.to_string()))?;
+        let limit = limit.min(self.config.max_rows_per_query);
+        let cache_key =
+            format!("{}{:?}|{:?}|{}|{}", cache_key_prefix(dataset), filter, sort_by, limit, offset);
+        if self.config.cache_enabled {
+            let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(rows) = cache.get(&cache_key) {
+                return Ok(rows.clone());
+            }
+        }
+
+        let matches = |row: &HashMap<String, String>| {
+            filter.iter().all(|(column, value)| row.get(column) == Some(value))
+        };
+        let order = sort_by.map(|column| {
+            let column_type = data.column_types.get(column).cloned().unwrap_or(ColumnType::String);
+            move |a: &HashMap<String, String>, b: &HashMap<String, String>| {
+                let value =
+                    |row: &HashMap<String, String>| row.get(column).map_or("", String::as_str);
+                cmp_typed(&column_type, value(a), value(b))
+            }
+        });
+
+        let page: Vec<HashMap<String, String>> = match &data.lazy_source {
+            Some(source) => {
+                self.lazy_page(source, &data.headers, matches, order.as_ref(), offset, limit)?.0
+            }
+            None => {
+                let mut rows: Vec<&HashMap<String, String>> =
+                    data.rows.iter().filter(|row| matches(row)).collect();
+                if let Some(order) = &order {
+                    rows.sort_by(|a, b| order(a, b));
+                }
+                rows.into_iter().skip(offset).take(limit).cloned().collect()
+            }
+        };
+        if self.config.cache_enabled {
+            self.cache.lock().unwrap_or_else(|e| e.into_inner()).insert(cache_key, page.clone());
+        }
+        Ok(page)
+    }
+
+    /// Stream a lazy dataset, keeping the rows that pass `keep`, and return
+    /// the page at `offset` in `order` (file order without one) along with
+    /// the number of rows kept
+    ///
+    /// At most `limit` rows are held at once. Without an order the page is
+    /// collected in one pass. With one, each pass keeps the smallest rows
+    /// after the previous pass in a heap bounded by `limit`, so the rows
+    /// before `offset` are skipped a page at a time and the file is read
+    /// `offset / limit + 1` times instead of holding them.
+    fn lazy_page(
+        &self,
+        source: &CsvFileConfig,
+        headers: &[String],
+        keep: impl Fn(&HashMap<String, String>) -> bool,
+        order: Option<
+            impl Fn(&HashMap<String, String>, &HashMap<String, String>) -> std::cmp::Ordering,
+        >,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<HashMap<String, String>>, usize), CsvPluginError> {
+        let Some(order) = order else {
+            let mut held = Vec::new();
+            let mut kept = 0;
+            for row in stream_rows(source, headers)? {
+                let row = row?;
+                if !keep(&row) {
+                    continue;
+                }
+                kept += 1;
+                if kept > offset && held.len() < limit {
+                    held.push(row);
+                }
+            }
+            self.record_lazy_peak(held.len());
+            return Ok((held, kept));
+        };
+
+        let mut after = None;
+        let mut skipped = 0;
+        loop {
+            let take = (offset - skipped).min(limit);
+            let (mut rows, kept) = self.lazy_smallest(
+                source,
+                headers,
+                &keep,
+                &order,
+                after.as_ref(),
+                if take == 0 { limit } else { take },
+            )?;
+            if take == 0 || rows.len() < take {
+                if take > 0 {
+                    rows.clear();
+                }
+                return Ok((rows.into_iter().map(|(_, row)| row).collect(), kept));
+            }
+            skipped += take;
+            after = rows.pop();
+        }
+    }
+
+    /// Stream a lazy dataset and return, in order, the first `n` rows that
+    /// pass `keep` and sort after `after` (with their file positions), along
+    /// with the number of rows kept
+    fn lazy_smallest<F>(
+        &self,
+        source: &CsvFileConfig,
+        headers: &[String],
+        keep: &impl Fn(&HashMap<String, String>) -> bool,
+        order: &F,
+        after: Option<&PositionedRow>,
+        n: usize,
+    ) -> Result<(Vec<PositionedRow>, usize), CsvPluginError>
+    where
+        F: Fn(&HashMap<String, String>, &HashMap<String, String>) -> std::cmp::Ordering,
+    {
+        let mut heap = BinaryHeap::with_capacity(n.saturating_add(1));
+        let mut kept = 0;
+        for (index, row) in stream_rows(source, headers)?.enumerate() {
+            let row = row?;
+            if !keep(&row) {
+                continue;
+            }
+            kept += 1;
+            let ranked = Ranked { index, row, order };
+            let past_cursor = after.is_none_or(|(after_index, after_row)| {
+                order(&ranked.row, after_row).then(index.cmp(after_index))
+                    == std::cmp::Ordering::Greater
+            });
+            if past_cursor && n > 0 {
+                heap.push(ranked);
+                if heap.len() > n {
+                    heap.pop();
+                }
+            }
+            self.record_lazy_peak(heap.len());
+        }
+        let rows = heap.into_sorted_vec().into_iter().map(|r| (r.index, r.row)).collect();
+        Ok((rows, kept))
+    }
+
+    /// Note how many rows a lazy query held at once
+    #[cfg_attr(not(test), allow(unused_variables))]
+    fn record_lazy_peak(&self, held: usize) {
+        #[cfg(test)]
+        self.lazy_peak_rows.fetch_max(held, Ordering::Relaxed);
+    }
+
     /// Infer column type from field value
+    ///
+    /// The first non-empty value decides the type, except that a Date or
+    /// DateTime column falls back to String as soon as a non-empty value
+    /// does not parse as that type.
     fn infer_column_type(&self, types: &mut HashMap<String, ColumnType>, column: &str, value: &str) {
-        if types.contains_key(column) {
-            return; // Type already inferred
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            return;
         }
 
-        let trimmed = value.trim();
+        match types.get(column) {
+            Some(ColumnType::Date) if parse_date(trimmed).is_none() => {
+                types.insert(column.to_string(), ColumnType::String);
+                return;
+            }
+            Some(ColumnType::DateTime) if parse_datetime(trimmed).is_none() => {
+                types.insert(column.to_string(), ColumnType::String);
+                return;
+            }
+            Some(_) => return, // Type already inferred
+            None => {}
+        }
 
         // Try to parse as boolean
         if trimmed.eq_ignore_ascii_case("true") || trimmed.eq_ignore_ascii_case("false") {
@@ -185,6 +622,16 @@ impl CsvDataSourcePlugin {
             return;
         }
 
+        // Try to parse as timestamp, then as date
+        if parse_datetime(trimmed).is_some() {
+            types.insert(column.to_string(), ColumnType::DateTime);
+            return;
+        }
+        if parse_date(trimmed).is_some() {
+            types.insert(column.to_string(), ColumnType::Date);
+            return;
+        }
+
         // Default to string
         types.insert(column.to_string(), ColumnType::String);
     }
@@ -194,39 +641,61 @@ impl CsvDataSourcePlugin {
         &self,
         dataset_name: &str,
         query: &DataSourceQuery,
-    ) -> Result<DataSet, DataSourceError> {
+    ) -> Result<DataSet, CsvPluginError> {
         let dataset = self.datasets.get(dataset_name)
-            .ok_or_else(|| DataSourceError::NotFound(format!("Dataset '{}' not found", dataset_name)))?;
-
-        let mut filtered_rows = dataset.rows.clone();
+            .ok_or_else(|| CsvPluginError::DatasetNotFound(dataset_name.to_string()))?;
 
-        // Apply filters
-        for filter in &query.filters {
-            filtered_rows.retain(|row| self.matches_filter(row, filter));
-        }
-
-        // Apply sorting
-        if let Some(sort) = &query.sort {
-            filtered_rows.sort_by(|a, b| {
-                let a_val = a.get(&sort.field).unwrap_or(&"".to_string());
-                let b_val = b.get(&sort.field).unwrap_or(&"".to_string());
-
-                match sort.direction {
-                    SortDirection::Asc => a_val.cmp(b_val),
-                    SortDirection::Desc => b_val.cmp(a_val),
+        let start_idx = query.offset.unwrap_or(0);
+        let limit = query
+            .limit
+            .unwrap_or(self.config.max_rows_per_query)
+            .min(self.config.max_rows_per_query);
+
+        let (paginated_rows, total_count) = if let Some(source) = &dataset.lazy_source {
+            let keep = |row: &HashMap<String, String>| {
+                query.filters.iter().all(|filter| self.matches_filter(row, filter))
+            };
+            let order = query.sort.as_ref().map(|sort| {
+                move |a: &HashMap<String, String>, b: &HashMap<String, String>| {
+                    let a_val = a.get(&sort.field).map_or("", String::as_str);
+                    let b_val = b.get(&sort.field).map_or("", String::as_str);
+                    match sort.direction {
+                        SortDirection::Asc => a_val.cmp(b_val),
+                        SortDirection::Desc => b_val.cmp(a_val),
+                    }
                 }
             });
-        }
+            self.lazy_page(source, &dataset.headers, keep, order, start_idx, limit)?
+        } else {
+            let mut filtered_rows = dataset.rows.clone();
 
-        // Apply pagination
-        let start_idx = query.offset.unwrap_or(0);
-        let limit = query.limit.unwrap_or(self.config.max_rows_per_query).min(self.config.max_rows_per_query);
-        let end_idx = (start_idx + limit).min(filtered_rows.len());
+            // Apply filters
+            for filter in &query.filters {
+                filtered_rows.retain(|row| self.matches_filter(row, filter));
+            }
 
-        let paginated_rows = if start_idx < filtered_rows.len() {
-            filtered_rows[start_idx..end_idx].to_vec()
-        } else {
-            vec![]
+            // Apply sorting
+            if let Some(sort) = &query.sort {
+                filtered_rows.sort_by(|a, b| {
+                    let a_val = a.get(&sort.field).unwrap_or(&"".to_string());
+                    let b_val = b.get(&sort.field).unwrap_or(&"".to_string());
+
+                    match sort.direction {
+                        SortDirection::Asc => a_val.cmp(b_val),
+                        SortDirection::Desc => b_val.cmp(a_val),
+                    }
+                });
+            }
+
+            // Apply pagination
+            let end_idx = (start_idx + limit).min(filtered_rows.len());
+
+            let paginated_rows = if start_idx < filtered_rows.len() {
+                filtered_rows[start_idx..end_idx].to_vec()
+            } else {
+                vec![]
+            };
+            (paginated_rows, filtered_rows.len())
         };
 
         // Convert to DataSet format
@@ -240,6 +709,7 @@ impl CsvDataSourcePlugin {
                 ColumnType::Integer => DataType::Integer,
                 ColumnType::Float => DataType::Float,
                 ColumnType::Boolean => DataType::Boolean,
+                ColumnType::Date | ColumnType::DateTime => DataType::DateTime,
             };
 
             ColumnInfo {
@@ -262,7 +732,7 @@ impl CsvDataSourcePlugin {
         Ok(DataSet {
             columns,
             rows,
-            total_count: filtered_rows.len() as u64,
+            total_count: total_count as u64,
         })
     }
 
@@ -317,7 +787,7 @@ impl CsvDataSourcePlugin {
             DatasetMetadata {
                 name: dataset.name.clone(),
                 description: format!("CSV dataset loaded from {}", dataset_name),
-                row_count: dataset.rows.len() as u64,
+                row_count: dataset.row_count as u64,
                 column_count: dataset.headers.len() as u32,
                 created_at: chrono::Utc::now(),
                 updated_at: chrono::Utc::now(),
@@ -350,13 +820,16 @@ impl DataSourcePlugin for CsvDataSourcePlugin {
     fn get_dataset_info(&self, dataset_name: &str) -> PluginResult<DatasetMetadata> {
         match self.get_dataset_metadata(dataset_name) {
             Some(metadata) => PluginResult::success(metadata, 0),
-            None => PluginResult::failure(format!("Dataset '{}' not found", dataset_name), 0),
+            None => PluginResult::failure(
+                CsvPluginError::DatasetNotFound(dataset_name.to_string()).to_string(),
+                0,
+            ),
         }
     }
 
     fn health_check(&self) -> PluginHealth {
         let loaded_datasets = self.datasets.len();
-        let total_rows: usize = self.datasets.values().map(|d| d.rows.len()).sum();
+        let total_rows: usize = self.datasets.values().map(|d| d.row_count).sum();
 
         PluginHealth::healthy(
             format!("CSV data source healthy: {} datasets, {} total rows", loaded_datasets, total_rows),
@@ -440,12 +913,16 @@ mod tests {
             name: "test_users".to_string(),
             path: temp_file.path().to_string_lossy().to_string(),
             has_headers: true,
+            delimiter: None,
+            quote: None,
+            lazy: false,
         };
 
         let config = CsvDataSourceConfig {
             csv_files: vec![csv_config],
             cache_enabled: false,
             max_rows_per_query: 100,
+            watch_enabled: false,
         };
 
         let plugin = CsvDataSourcePlugin::new(config);
@@ -473,6 +950,294 @@ mod tests {
         assert_eq!(data_set.total_count, 3);
     }
 
+    #[test]
+    fn test_missing_file_is_file_not_found() {
+        let mut plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig::default());
+        let csv_config = CsvFileConfig {
+            name: "missing".to_string(),
+            path: "/nonexistent/mockforge/missing.csv".to_string(),
+            has_headers: true,
+            delimiter: None,
+            quote: None,
+            lazy: false,
+        };
+
+        let err = plugin.load_csv_dataset(&csv_config).unwrap_err();
+        assert!(matches!(err, CsvPluginError::FileNotFound(ref p) if p == &csv_config.path));
+    }
+
+    #[test]
+    fn test_malformed_csv_is_parse_error() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "name,age").unwrap();
+        writeln!(temp_file, "Alice,25,extra").unwrap();
+
+        let mut plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig::default());
+        let csv_config = CsvFileConfig {
+            name: "broken".to_string(),
+            path: temp_file.path().to_string_lossy().to_string(),
+            has_headers: true,
+            delimiter: None,
+            quote: None,
+            lazy: false,
+        };
+
+        let err = plugin.load_csv_dataset(&csv_config).unwrap_err();
+        assert!(matches!(err, CsvPluginError::ParseError { .. }));
+        assert!(!plugin.datasets.contains_key("broken"));
+    }
+
+    #[test]
+    fn test_semicolon_file_with_multiline_quoted_field() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "name;note;qty\nAlice;'first line\nsecond; line';3\nBob;plain;4\n").unwrap();
+
+        let mut plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig::default());
+        let csv_config = CsvFileConfig {
+            name: "orders".to_string(),
+            path: temp_file.path().to_string_lossy().to_string(),
+            has_headers: true,
+            delimiter: Some(';'),
+            quote: Some('\''),
+            lazy: false,
+        };
+        plugin.load_csv_dataset(&csv_config).unwrap();
+
+        let dataset = plugin.datasets.get("orders").unwrap();
+        assert_eq!(dataset.headers, vec!["name", "note", "qty"]);
+        assert_eq!(dataset.rows.len(), 2);
+        assert_eq!(dataset.rows[0]["note"], "first line\nsecond; line");
+        assert_eq!(dataset.column_types["note"], ColumnType::String);
+        assert_eq!(dataset.column_types["qty"], ColumnType::Integer);
+    }
+
+    #[test]
+    fn test_non_ascii_delimiter_is_invalid_config() {
+        let mut plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig::default());
+        let csv_config = CsvFileConfig {
+            name: "bad".to_string(),
+            path: "unused.csv".to_string(),
+            has_headers: true,
+            delimiter: Some('§'),
+            quote: None,
+            lazy: false,
+        };
+
+        let err = plugin.load_csv_dataset(&csv_config).unwrap_err();
+        assert!(matches!(err, CsvPluginError::InvalidConfig(_)));
+    }
+
+    fn plugin_with_scores(cache_enabled: bool) -> (CsvDataSourcePlugin, NamedTempFile) {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "name,score,team").unwrap();
+        writeln!(temp_file, "Alice,100,red").unwrap();
+        writeln!(temp_file, "Bob,9,blue").unwrap();
+        writeln!(temp_file, "Carol,25,red").unwrap();
+        writeln!(temp_file, "Dave,3,red").unwrap();
+
+        let config = CsvDataSourceConfig {
+            csv_files: vec![CsvFileConfig {
+                name: "scores".to_string(),
+                path: temp_file.path().to_string_lossy().to_string(),
+                has_headers: true,
+                delimiter: None,
+                quote: None,
+                lazy: false,
+            }],
+            cache_enabled,
+            max_rows_per_query: 3,
+            watch_enabled: false,
+        };
+        (CsvDataSourcePlugin::new(config), temp_file)
+    }
+
+    fn names(rows: &[HashMap<String, String>]) -> Vec<&str> {
+        rows.iter().map(|row| row["name"].as_str()).collect()
+    }
+
+    #[test]
+    fn test_query_sorts_numeric_and_string_columns() {
+        let (plugin, _file) = plugin_with_scores(false);
+
+        // Integer column: 3 < 9 < 25 < 100, not "100" < "25" < "3" < "9"
+        let rows = plugin.query("scores", &[], Some("score"), 10, 0).unwrap();
+        assert_eq!(names(&rows), vec!["Dave", "Bob", "Carol"]);
+
+        let rows = plugin.query("scores", &[], Some("name"), 10, 0).unwrap();
+        assert_eq!(names(&rows), vec!["Alice", "Bob", "Carol"]);
+
+        let filter = [("team".to_string(), "red".to_string())];
+        let rows = plugin.query("scores", &filter, Some("score"), 2, 1).unwrap();
+        assert_eq!(names(&rows), vec!["Carol", "Alice"]);
+    }
+
+    #[test]
+    fn test_query_offset_past_end_is_empty() {
+        let (plugin, _file) = plugin_with_scores(false);
+        assert!(plugin.query("scores", &[], None, 10, 4).unwrap().is_empty());
+        assert!(plugin.query("scores", &[], None, 10, 100).unwrap().is_empty());
+        assert!(matches!(
+            plugin.query("nope", &[], None, 10, 0),
+            Err(CsvPluginError::DatasetNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_query_results_are_cached_per_query() {
+        let (plugin, _file) = plugin_with_scores(true);
+        plugin.query("scores", &[], Some("score"), 2, 0).unwrap();
+        plugin.query("scores", &[], Some("score"), 2, 0).unwrap();
+        plugin.query("scores", &[], Some("score"), 2, 1).unwrap();
+        assert_eq!(plugin.cache.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_date_columns_are_inferred_and_sorted_chronologically() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "id,joined,seen,note").unwrap();
+        writeln!(temp_file, "1,2024-03-10,2024-03-10T08:00:00+02:00,2024-01-01").unwrap();
+        writeln!(temp_file, "2,2023-12-31,2024-03-10 07:30:00,tomorrow").unwrap();
+        writeln!(temp_file, "3,,2024-03-09T23:00:00Z,2024-02-02").unwrap();
+        writeln!(temp_file, "4,2024-01-05,2024-03-10T05:00:00Z,").unwrap();
+
+        let mut plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig::default());
+        let csv_config = CsvFileConfig {
+            name: "members".to_string(),
+            path: temp_file.path().to_string_lossy().to_string(),
+            has_headers: true,
+            delimiter: None,
+            quote: None,
+            lazy: false,
+        };
+        plugin.load_csv_dataset(&csv_config).unwrap();
+
+        let types = &plugin.datasets["members"].column_types;
+        // Empty cells do not stop a clean column from being a date
+        assert_eq!(types["joined"], ColumnType::Date);
+        assert_eq!(types["seen"], ColumnType::DateTime);
+        // One value that is not a date keeps the whole column a string
+        assert_eq!(types["note"], ColumnType::String);
+
+        let ids = |rows: Vec<HashMap<String, String>>| -> Vec<String> {
+            rows.iter().map(|row| row["id"].clone()).collect()
+        };
+        let rows = plugin.query("members", &[], Some("joined"), 10, 0).unwrap();
+        assert_eq!(ids(rows), vec!["2", "4", "1", "3"]);
+        // Offsets are normalized: 08:00+02:00 is 06:00Z, between 05:00Z and 07:30
+        let rows = plugin.query("members", &[], Some("seen"), 10, 0).unwrap();
+        assert_eq!(ids(rows), vec!["3", "4", "1", "2"]);
+    }
+
+    #[test]
+    fn test_reload_if_changed_picks_up_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("scores.csv");
+        std::fs::write(&path, "name,score\nAlice,1\n").unwrap();
+
+        let mut plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig {
+            csv_files: vec![CsvFileConfig {
+                name: "scores".to_string(),
+                path: path.to_string_lossy().to_string(),
+                has_headers: true,
+                delimiter: None,
+                quote: None,
+                lazy: false,
+            }],
+            cache_enabled: true,
+            max_rows_per_query: 100,
+            watch_enabled: true,
+        });
+        assert_eq!(plugin.query("scores", &[], None, 10, 0).unwrap().len(), 1);
+        assert!(plugin.reload_if_changed().unwrap().is_empty());
+
+        // Edit the file, moving its mtime forward so the change is seen
+        // regardless of the filesystem's timestamp resolution
+        std::fs::write(&path, "name,score\nAlice,1\nBob,2\n").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(10);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(later).unwrap();
+
+        assert_eq!(plugin.reload_if_changed().unwrap(), vec!["scores"]);
+        // The cached one-row result was dropped
+        assert_eq!(plugin.query("scores", &[], None, 10, 0).unwrap().len(), 2);
+        assert!(plugin.reload_if_changed().unwrap().is_empty());
+
+        // A deleted file keeps the loaded dataset
+        std::fs::remove_file(&path).unwrap();
+        assert!(plugin.reload_if_changed().unwrap().is_empty());
+        assert_eq!(plugin.datasets["scores"].rows.len(), 2);
+    }
+
+    #[test]
+    fn test_lazy_dataset_streams_queries_within_row_limit() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "id,score,team").unwrap();
+        for i in 0..50_000 {
+            // Scores run 49999 down to 0 so sorting has to look at every row
+            writeln!(temp_file, "{},{},{}", i, 49_999 - i, if i % 2 == 0 { "red" } else { "blue" })
+                .unwrap();
+        }
+
+        let plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig {
+            csv_files: vec![CsvFileConfig {
+                name: "big".to_string(),
+                path: temp_file.path().to_string_lossy().to_string(),
+                has_headers: true,
+                delimiter: None,
+                quote: None,
+                lazy: true,
+            }],
+            cache_enabled: false,
+            max_rows_per_query: 100,
+            watch_enabled: false,
+        });
+        let dataset = &plugin.datasets["big"];
+        assert!(dataset.rows.is_empty());
+        assert_eq!(dataset.row_count, 50_000);
+        assert_eq!(dataset.column_types["score"], ColumnType::Integer);
+
+        let ids = |rows: &[HashMap<String, String>]| -> Vec<String> {
+            rows.iter().map(|row| row["id"].clone()).collect()
+        };
+        let filter = [("team".to_string(), "blue".to_string())];
+        let rows = plugin.query("big", &filter, None, 1_000, 10).unwrap();
+        assert_eq!(rows.len(), 100);
+        assert_eq!(rows[0]["id"], "21");
+
+        let rows = plugin.query("big", &[], Some("score"), 3, 0).unwrap();
+        assert_eq!(ids(&rows), vec!["49999", "49998", "49997"]);
+
+        // Skipped rows are not held either
+        let rows = plugin.query("big", &filter, Some("score"), 100, 1_000).unwrap();
+        assert_eq!(rows.len(), 100);
+        assert_eq!((rows[0]["score"].as_str(), rows[99]["score"].as_str()), ("2000", "2198"));
+
+        let query = DataSourceQuery {
+            filters: vec![],
+            sort: None,
+            limit: None,
+            offset: Some(49_950),
+        };
+        let data_set = plugin.query_dataset("big", &query).unwrap();
+        assert_eq!(data_set.rows.len(), 50);
+        assert_eq!(data_set.total_count, 50_000);
+
+        assert!(plugin.lazy_peak_rows.load(Ordering::Relaxed) <= 100);
+    }
+
+    #[test]
+    fn test_unknown_dataset_is_dataset_not_found() {
+        let plugin = CsvDataSourcePlugin::new(CsvDataSourceConfig::default());
+        let query = DataSourceQuery {
+            filters: vec![],
+            sort: None,
+            limit: None,
+            offset: None,
+        };
+
+        let err = plugin.query_dataset("nope", &query).unwrap_err();
+        assert!(matches!(err, CsvPluginError::DatasetNotFound(ref name) if name == "nope"));
+    }
+
     #[test]
     fn test_data_filtering() {
         let config = CsvDataSourceConfig::default();
@@ -152,8 +152,8 @@ async fn test_per_route_fault_injection() {
         request: None,
         response: mockforge_core::config::RouteResponseConfig {
             status: 200,
-            headers: HashMap::new(),
             body: None,
+            ..Default::default()
         },
         fault_injection: Some(RouteFaultInjectionConfig {
             enabled: true,
@@ -162,6 +162,8 @@ async fn test_per_route_fault_injection() {
                 status_code: 500,
                 message: Some("Test error".to_string()),
             }],
+            weights: None,
+            request_matcher: None,
         }),
         latency: None,
     };
@@ -188,8 +190,8 @@ async fn test_per_route_latency() {
         request: None,
         response: mockforge_core::config::RouteResponseConfig {
             status: 200,
-            headers: HashMap::new(),
             body: None,
+            ..Default::default()
         },
         fault_injection: None,
         latency: Some(RouteLatencyConfig {
@@ -199,6 +201,7 @@ async fn test_per_route_latency() {
             random_delay_range_ms: None,
             jitter_percent: 0.0,
             distribution: mockforge_core::config::LatencyDistribution::Fixed,
+            body_transfer_ms: None,
         }),
     };
 
@@ -376,8 +379,8 @@ async fn test_route_matcher() {
             request: None,
             response: mockforge_core::config::RouteResponseConfig {
                 status: 200,
-                headers: HashMap::new(),
                 body: None,
+                ..Default::default()
             },
             fault_injection: None,
             latency: None,
@@ -388,8 +391,8 @@ async fn test_route_matcher() {
             request: None,
             response: mockforge_core::config::RouteResponseConfig {
                 status: 200,
-                headers: HashMap::new(),
                 body: None,
+                ..Default::default()
             },
             fault_injection: None,
             latency: None,